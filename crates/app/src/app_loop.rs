@@ -1,9 +1,39 @@
+use crate::determinism::DeterminismCheck;
+use crate::profile::PlayerProfile;
+use crate::run_bundle::BundleRequest;
 use crate::ui_scale::{
     DEFAULT_UI_SCALE, UiScaleAction, decrease_ui_scale, increase_ui_scale, reset_ui_scale,
 };
 use core::journal::InputPayload;
-use core::{AdvanceStopReason, ChoicePromptId, EngineFailureReason, Game, Interrupt, RunOutcome};
+use core::{
+    AdvanceResult, AdvanceStopReason, ChoicePromptId, EngineFailureReason, Game, Interrupt,
+    LogEvent, RunOutcome,
+};
 use macroquad::prelude::KeyCode;
+use std::mem;
+use std::time::Instant;
+
+mod blitz;
+mod macros;
+mod movement_keys;
+mod palette;
+mod policy_keys;
+mod presets;
+mod prompt_keys;
+mod sim_clock;
+mod text;
+
+pub use blitz::{BLITZ_WINDOW_SECONDS, BlitzTimer};
+pub use macros::{MACRO_KEYS, MacroCommand, PolicyMacro, PolicyMacroBook};
+pub use palette::{PaletteCommand, palette_line, parse_command};
+pub use presets::PresetCommand;
+use prompt_keys::prompt_choice;
+use sim_clock::SIM_FRAME_BUDGET;
+pub use sim_clock::SimClock;
+pub use text::{
+    completion_reason_code, death_summary, fight_preview_suffix, finished_recap_lines, prompt_text,
+    queued_prompts_suffix, seen_by_line, stats_panel_lines, status_text,
+};
 
 /// How a run ended — either a normal game outcome or an engine-level failure.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,25 +53,70 @@ pub enum AppMode {
     #[default]
     Paused,
     AutoPlay,
+    /// Arrow keys steer the player one step at a time while prompts, sight and policy keep
+    /// working as in auto-play; Space hands control back to auto-explore.
+    Manual,
     PendingPrompt {
         interrupt: Interrupt,
         prompt_id: ChoicePromptId,
         auto_play_suspended: bool,
+        /// Set when the prompt interrupted manual steering, which resumes once it is answered.
+        steering: bool,
     },
     Finished(AppCompletion),
 }
 
+impl AppMode {
+    /// Mode to return to once a prompt is answered.
+    fn after_prompt(auto_play_suspended: bool, steering: bool) -> Self {
+        match (auto_play_suspended, steering) {
+            (true, _) => Self::AutoPlay,
+            (false, true) => Self::Manual,
+            (false, false) => Self::Paused,
+        }
+    }
+}
+
 pub struct AppState {
     pub mode: AppMode,
     pub ui_scale: f32,
     /// Inputs accepted during the current frame's `tick()` call.
     /// Drained by the caller after each tick to persist to the journal file.
     pub accepted_inputs: Vec<AcceptedInput>,
+    /// Paces auto-play by elapsed time rather than by rendered frames.
+    pub sim_clock: SimClock,
+    /// Text typed into the command palette; `Some` while it is open.
+    pub palette: Option<String>,
+    /// Saved policy macros and the one being recorded; kept across restarts.
+    pub policy_macros: PolicyMacroBook,
+    pub legend_visible: bool,
+    /// Enemy kinds met across runs; unlocks encyclopedia entries.
+    pub profile: PlayerProfile,
+    pub encyclopedia_visible: bool,
+    /// Run bundle export or import asked for this frame; the frontend takes and performs it.
+    pub bundle_request: Option<BundleRequest>,
+    /// Re-simulation of the finished run; the frontend starts it once the journal is complete.
+    pub determinism: Option<DeterminismCheck>,
+    /// Prompt deadlines, when playing the blitz variant.
+    pub blitz: Option<BlitzTimer>,
 }
 
 impl Default for AppState {
     fn default() -> Self {
-        Self { mode: AppMode::default(), ui_scale: DEFAULT_UI_SCALE, accepted_inputs: Vec::new() }
+        Self {
+            mode: AppMode::default(),
+            ui_scale: DEFAULT_UI_SCALE,
+            accepted_inputs: Vec::new(),
+            sim_clock: SimClock::default(),
+            palette: None,
+            policy_macros: PolicyMacroBook::default(),
+            legend_visible: false,
+            profile: PlayerProfile::default(),
+            encyclopedia_visible: false,
+            bundle_request: None,
+            determinism: None,
+            blitz: None,
+        }
     }
 }
 
@@ -63,19 +138,80 @@ impl AppState {
         true
     }
 
-    /// Process input and logic for a single frame, returning whether we should continue.
-    /// In a real test, we would mock `is_key_pressed`, but for now we can just provide
-    /// a list of keys pressed this frame, or a trait/closure.
-    pub fn tick(&mut self, game: &mut Game, keys_pressed: &[KeyCode]) {
+    /// Fresh state for a restarted run, keeping the player's UI scale, saved macros and profile.
+    pub fn reset_for_new_run(&mut self) {
+        let policy_macros =
+            PolicyMacroBook { recording: None, ..mem::take(&mut self.policy_macros) };
+        *self = Self {
+            ui_scale: self.ui_scale,
+            policy_macros,
+            legend_visible: self.legend_visible,
+            profile: mem::take(&mut self.profile),
+            encyclopedia_visible: self.encyclopedia_visible,
+            blitz: self.blitz.as_ref().map(|timer| BlitzTimer::new(timer.window_seconds)),
+            ..Self::default()
+        };
+    }
+
+    /// Run one presentation frame: handle this frame's keys, then simulate the ticks that
+    /// `frame_seconds` of wall time buys while auto-playing.
+    /// Returns how the run ended when it ended during this frame.
+    pub fn tick(
+        &mut self,
+        game: &mut Game,
+        keys_pressed: &[KeyCode],
+        frame_seconds: f32,
+    ) -> Option<AppCompletion> {
+        let was_finished = matches!(self.mode, AppMode::Finished(_));
         self.accepted_inputs.clear();
-        let mut advance_result = None;
+        let manual_step = if self.handle_palette_keys(game, keys_pressed) {
+            None
+        } else {
+            self.handle_input(game, keys_pressed)
+        };
+        self.enforce_blitz_deadline(game, frame_seconds);
+        let advance_result = manual_step.or_else(|| self.step_simulation(game, frame_seconds));
 
-        // Input handling
+        if let Some(result) = advance_result {
+            let auto_play_suspended = matches!(self.mode, AppMode::AutoPlay);
+            self.apply_stop_reason(result.stop_reason, auto_play_suspended);
+        }
         match &self.mode {
-            AppMode::Paused | AppMode::AutoPlay => {
+            AppMode::Finished(completion) if !was_finished => Some(completion.clone()),
+            _ => None,
+        }
+    }
+
+    /// Advance auto-play by the fixed-timestep ticks now due, if any.
+    fn step_simulation(&mut self, game: &mut Game, frame_seconds: f32) -> Option<AdvanceResult> {
+        if !matches!(self.mode, AppMode::AutoPlay) {
+            self.sim_clock.reset();
+            return None;
+        }
+        let due_ticks = self.sim_clock.due_ticks(frame_seconds);
+        if due_ticks == 0 {
+            return None;
+        }
+        let result = game.advance_until(due_ticks, Instant::now() + SIM_FRAME_BUDGET);
+        if matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted) {
+            self.sim_clock.defer(due_ticks - result.simulated_ticks);
+        } else {
+            self.sim_clock.reset();
+        }
+        Some(result)
+    }
+
+    /// Apply this frame's key presses, returning the result of a manual single step.
+    fn handle_input(&mut self, game: &mut Game, keys_pressed: &[KeyCode]) -> Option<AdvanceResult> {
+        let mut advance_result = None;
+        match &self.mode {
+            AppMode::Paused | AppMode::AutoPlay | AppMode::Manual => {
+                if keys_pressed.contains(&KeyCode::Tab) {
+                    self.sim_clock.turbo = !self.sim_clock.turbo;
+                }
                 if keys_pressed.contains(&KeyCode::Space) {
                     self.mode = match self.mode {
-                        AppMode::Paused => AppMode::AutoPlay,
+                        AppMode::Paused | AppMode::Manual => AppMode::AutoPlay,
                         AppMode::AutoPlay => {
                             game.request_pause();
                             AppMode::Paused
@@ -88,78 +224,18 @@ impl AppState {
                     advance_result = Some(game.advance(1));
                 }
 
-                if matches!(self.mode, AppMode::Paused) {
+                if matches!(self.mode, AppMode::Paused | AppMode::Manual) {
                     self.handle_policy_keys(game, keys_pressed);
                 }
+                if advance_result.is_none() {
+                    advance_result = self.handle_movement_keys(game, keys_pressed);
+                }
             }
-            AppMode::PendingPrompt { prompt_id, auto_play_suspended, interrupt } => {
-                let id = *prompt_id;
-                let resume = *auto_play_suspended;
-                match interrupt {
-                    Interrupt::LootFound { .. } => {
-                        if keys_pressed.contains(&KeyCode::L) {
-                            self.apply_and_record_choice(game, id, core::Choice::KeepLoot);
-                            self.mode = if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                        } else if keys_pressed.contains(&KeyCode::D) {
-                            self.apply_and_record_choice(game, id, core::Choice::DiscardLoot);
-                            self.mode = if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                        }
-                    }
-                    Interrupt::EnemyEncounter { .. } => {
-                        if keys_pressed.contains(&KeyCode::F) {
-                            self.apply_and_record_choice(game, id, core::Choice::Fight);
-                            self.mode = if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                        } else if keys_pressed.contains(&KeyCode::A) {
-                            self.apply_and_record_choice(game, id, core::Choice::Avoid);
-                            self.mode = if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                        }
-                    }
-                    Interrupt::DoorBlocked { .. } => {
-                        if keys_pressed.contains(&KeyCode::O) {
-                            self.apply_and_record_choice(game, id, core::Choice::OpenDoor);
-                            self.mode = if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                        }
-                    }
-                    Interrupt::FloorTransition { requires_branch_god_choice, .. } => {
-                        if *requires_branch_god_choice {
-                            if keys_pressed.contains(&KeyCode::Key1) {
-                                self.apply_and_record_choice(
-                                    game,
-                                    id,
-                                    core::Choice::DescendBranchAVeil,
-                                );
-                                self.mode =
-                                    if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                            } else if keys_pressed.contains(&KeyCode::Key2) {
-                                self.apply_and_record_choice(
-                                    game,
-                                    id,
-                                    core::Choice::DescendBranchAForge,
-                                );
-                                self.mode =
-                                    if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                            } else if keys_pressed.contains(&KeyCode::Key3) {
-                                self.apply_and_record_choice(
-                                    game,
-                                    id,
-                                    core::Choice::DescendBranchBVeil,
-                                );
-                                self.mode =
-                                    if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                            } else if keys_pressed.contains(&KeyCode::Key4) {
-                                self.apply_and_record_choice(
-                                    game,
-                                    id,
-                                    core::Choice::DescendBranchBForge,
-                                );
-                                self.mode =
-                                    if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                            }
-                        } else if keys_pressed.contains(&KeyCode::C) {
-                            self.apply_and_record_choice(game, id, core::Choice::Descend);
-                            self.mode = if resume { AppMode::AutoPlay } else { AppMode::Paused };
-                        }
-                    }
+            AppMode::PendingPrompt { prompt_id, auto_play_suspended, steering, interrupt } => {
+                if let Some(choice) = prompt_choice(interrupt, keys_pressed) {
+                    let (id, resume, steering) = (*prompt_id, *auto_play_suspended, *steering);
+                    self.apply_and_record_choice(game, id, choice);
+                    self.mode = AppMode::after_prompt(resume, steering);
                 }
 
                 self.handle_policy_keys(game, keys_pressed);
@@ -168,31 +244,21 @@ impl AppState {
                 // No inputs valid after completion
             }
         }
-
-        // Logic stepping
-        if matches!(self.mode, AppMode::AutoPlay) {
-            advance_result = Some(game.advance(10)); // Batch step for synchronous iteration
-        }
-
-        if let Some(result) = advance_result {
-            let auto_play_suspended = matches!(self.mode, AppMode::AutoPlay);
-            self.apply_stop_reason(result.stop_reason, auto_play_suspended);
-        }
+        advance_result
     }
 
     pub fn apply_stop_reason(&mut self, stop_reason: AdvanceStopReason, auto_play_suspended: bool) {
         match stop_reason {
+            // Steering pauses after every step; it stays in manual mode until Space.
+            AdvanceStopReason::PausedAtBoundary { .. } if self.mode == AppMode::Manual => {}
             AdvanceStopReason::PausedAtBoundary { .. } => {
                 self.mode = AppMode::Paused;
             }
             AdvanceStopReason::Interrupted(interrupt) => {
-                let prompt_id = match interrupt {
-                    Interrupt::LootFound { prompt_id, .. } => prompt_id,
-                    Interrupt::EnemyEncounter { prompt_id, .. } => prompt_id,
-                    Interrupt::DoorBlocked { prompt_id, .. } => prompt_id,
-                    Interrupt::FloorTransition { prompt_id, .. } => prompt_id,
-                };
-                self.mode = AppMode::PendingPrompt { interrupt, prompt_id, auto_play_suspended };
+                let prompt_id = interrupt.prompt_id();
+                let steering = self.mode == AppMode::Manual;
+                self.mode =
+                    AppMode::PendingPrompt { interrupt, prompt_id, auto_play_suspended, steering };
             }
             AdvanceStopReason::Finished(outcome) => {
                 self.mode = AppMode::Finished(AppCompletion::Outcome(outcome));
@@ -214,135 +280,16 @@ impl AppState {
         choice: core::Choice,
     ) {
         let tick = game.current_tick();
-        game.apply_choice(prompt_id, choice.clone()).expect("Failed to apply pending choice");
+        if let Err(error) = game.apply_choice(prompt_id, choice.clone()) {
+            game.push_log(LogEvent::Notice(format!("Choice rejected: {error}")));
+            return;
+        }
         self.accepted_inputs.push(AcceptedInput {
             tick_boundary: tick,
             payload: InputPayload::Choice { prompt_id, choice },
         });
     }
-
-    /// Apply a policy update to the game and record it if accepted.
-    fn apply_and_record_policy(&mut self, game: &mut Game, update: core::PolicyUpdate) {
-        let tick = game.current_tick();
-        if game.apply_policy_update(update.clone()).is_ok() {
-            self.accepted_inputs.push(AcceptedInput {
-                tick_boundary: tick,
-                payload: InputPayload::PolicyUpdate { tick_boundary: tick, update },
-            });
-        }
-    }
-
-    /// Process policy-related key presses while paused.
-    fn handle_policy_keys(&mut self, game: &mut Game, keys_pressed: &[KeyCode]) {
-        if keys_pressed.contains(&KeyCode::M) {
-            let next = match game.state().policy.fight_or_avoid {
-                core::FightMode::Fight => core::FightMode::Avoid,
-                core::FightMode::Avoid => core::FightMode::Fight,
-            };
-            self.apply_and_record_policy(game, core::PolicyUpdate::FightMode(next));
-        }
-        if keys_pressed.contains(&KeyCode::T) {
-            let next = match game.state().policy.stance {
-                core::Stance::Aggressive => core::Stance::Balanced,
-                core::Stance::Balanced => core::Stance::Defensive,
-                core::Stance::Defensive => core::Stance::Aggressive,
-            };
-            self.apply_and_record_policy(game, core::PolicyUpdate::Stance(next));
-        }
-        if keys_pressed.contains(&KeyCode::P) {
-            let next = match game.state().policy.target_priority.first() {
-                Some(core::TargetTag::Nearest) => vec![core::TargetTag::LowestHp],
-                _ => vec![core::TargetTag::Nearest, core::TargetTag::LowestHp],
-            };
-            self.apply_and_record_policy(game, core::PolicyUpdate::TargetPriority(next));
-        }
-        if keys_pressed.contains(&KeyCode::R) {
-            let current = game.state().policy.retreat_hp_threshold;
-            let next = if current < 90 { current + 10 } else { 0 };
-            self.apply_and_record_policy(game, core::PolicyUpdate::RetreatHpThreshold(next));
-        }
-        if keys_pressed.contains(&KeyCode::H) {
-            let next = match game.state().policy.auto_heal_if_below_threshold {
-                None => Some(30),
-                Some(80) => None,
-                Some(v) => Some(v + 10),
-            };
-            self.apply_and_record_policy(game, core::PolicyUpdate::AutoHealIfBelowThreshold(next));
-        }
-        if keys_pressed.contains(&KeyCode::I) {
-            let next = match game.state().policy.position_intent {
-                core::PositionIntent::HoldGround => core::PositionIntent::AdvanceToMelee,
-                core::PositionIntent::AdvanceToMelee => {
-                    core::PositionIntent::FleeToNearestExploredTile
-                }
-                core::PositionIntent::FleeToNearestExploredTile => core::PositionIntent::HoldGround,
-            };
-            self.apply_and_record_policy(game, core::PolicyUpdate::PositionIntent(next));
-        }
-        if keys_pressed.contains(&KeyCode::E) {
-            self.apply_and_record_policy(
-                game,
-                core::PolicyUpdate::ExplorationMode(core::ExploreMode::Thorough),
-            );
-        }
-        if keys_pressed.contains(&KeyCode::G) {
-            self.apply_and_record_policy(
-                game,
-                core::PolicyUpdate::ResourceAggression(core::Aggro::Conserve),
-            );
-        }
-    }
 }
 
 #[cfg(test)]
-mod tests {
-    use super::{AppCompletion, AppMode, AppState};
-    use crate::ui_scale::UiScaleAction;
-    use core::{AdvanceStopReason, DeathCause, EngineFailureReason, RunOutcome};
-
-    #[test]
-    fn finished_outcome_maps_to_finished_mode() {
-        let mut app = AppState::new();
-        app.apply_stop_reason(
-            AdvanceStopReason::Finished(RunOutcome::Defeat(DeathCause::Damage)),
-            false,
-        );
-        assert_eq!(
-            app.mode,
-            AppMode::Finished(AppCompletion::Outcome(RunOutcome::Defeat(DeathCause::Damage,)))
-        );
-    }
-
-    #[test]
-    fn engine_failure_maps_to_finished_mode_without_panic() {
-        let mut app = AppState::new();
-        app.apply_stop_reason(
-            AdvanceStopReason::EngineFailure(EngineFailureReason::StalledNoProgress),
-            true,
-        );
-        assert_eq!(
-            app.mode,
-            AppMode::Finished(
-                AppCompletion::EngineFailure(EngineFailureReason::StalledNoProgress,)
-            )
-        );
-    }
-
-    #[test]
-    fn ui_scale_actions_support_fractional_steps_and_reset() {
-        let mut app = AppState::new();
-        app.ui_scale = 1.0;
-
-        assert!(app.apply_ui_scale_action(UiScaleAction::Increase));
-        assert!((app.ui_scale - 1.1).abs() < 0.0001);
-
-        assert!(app.apply_ui_scale_action(UiScaleAction::Decrease));
-        assert!((app.ui_scale - 1.0).abs() < 0.0001);
-
-        app.ui_scale = 0.5;
-        assert!(!app.apply_ui_scale_action(UiScaleAction::Decrease));
-        app.ui_scale = 1.6;
-        assert!(app.apply_ui_scale_action(UiScaleAction::Reset));
-        assert!((app.ui_scale - 1.0).abs() < 0.0001);
-    }
-}
+mod tests;
@@ -0,0 +1,108 @@
+//! Blitz variant: every prompt must be answered within a real-time window, or the policy
+//! default is applied for the player. The timed-out answer is journaled as an ordinary
+//! choice, so replays never consult the clock.
+
+use super::{AppMode, AppState};
+use core::{ChoicePromptId, Game, LogEvent};
+
+/// Seconds a blitz prompt waits for the player.
+pub const BLITZ_WINDOW_SECONDS: f32 = 5.0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlitzTimer {
+    pub window_seconds: f32,
+    /// The prompt being timed and the seconds it has left.
+    running: Option<(ChoicePromptId, f32)>,
+}
+
+impl Default for BlitzTimer {
+    fn default() -> Self {
+        Self::new(BLITZ_WINDOW_SECONDS)
+    }
+}
+
+impl BlitzTimer {
+    pub fn new(window_seconds: f32) -> Self {
+        Self { window_seconds, running: None }
+    }
+
+    /// Count `frame_seconds` against `prompt_id`, starting a full window the first time the
+    /// prompt is seen. True once its window has run out.
+    pub fn run_down(&mut self, prompt_id: ChoicePromptId, frame_seconds: f32) -> bool {
+        let left = match self.running {
+            Some((timed, left)) if timed == prompt_id => left,
+            _ => self.window_seconds,
+        };
+        let left = left - frame_seconds.max(0.0);
+        self.running = Some((prompt_id, left));
+        left <= 0.0
+    }
+
+    /// Whole seconds left on `prompt_id`, rounded up, once its timer has started.
+    pub fn seconds_left(&self, prompt_id: ChoicePromptId) -> Option<u32> {
+        let (timed, left) = self.running?;
+        (timed == prompt_id).then(|| left.max(0.0).ceil() as u32)
+    }
+}
+
+impl AppState {
+    /// Answer a blitz prompt with the policy default once its window has run out.
+    pub(super) fn enforce_blitz_deadline(&mut self, game: &mut Game, frame_seconds: f32) {
+        let AppMode::PendingPrompt { prompt_id, auto_play_suspended, steering, .. } = self.mode
+        else {
+            return;
+        };
+        let Some(timer) = &mut self.blitz else {
+            return;
+        };
+        if !timer.run_down(prompt_id, frame_seconds) {
+            return;
+        }
+        let Some(choice) = game.policy_default_choice() else {
+            return;
+        };
+        game.push_log(LogEvent::Notice(format!("Time's up: the policy chose {choice:?}")));
+        self.apply_and_record_choice(game, prompt_id, choice);
+        self.mode = AppMode::after_prompt(auto_play_suspended, steering);
+    }
+
+    /// Seconds left to answer the pending prompt, in blitz mode.
+    pub fn blitz_seconds_left(&self) -> Option<u32> {
+        let AppMode::PendingPrompt { prompt_id, .. } = self.mode else {
+            return None;
+        };
+        self.blitz.as_ref()?.seconds_left(prompt_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_loop::AcceptedInput;
+    use core::journal::InputPayload;
+    use core::{AdvanceStopReason, ContentPack, GameMode};
+
+    #[test]
+    fn an_unanswered_prompt_takes_the_policy_default_and_journals_it() {
+        let mut app = AppState { blitz: Some(BlitzTimer::default()), ..AppState::new() };
+        let mut game = Game::new(2756, &ContentPack::default(), GameMode::Ironman);
+        let AdvanceStopReason::Interrupted(interrupt) = game.advance(5_000).stop_reason else {
+            panic!("the run reaches a prompt");
+        };
+        let prompt = interrupt.prompt_id();
+        app.apply_stop_reason(AdvanceStopReason::Interrupted(interrupt), true);
+        let expected = game.policy_default_choice().expect("a prompt is pending");
+
+        app.tick(&mut game, &[], 3.0);
+        assert_eq!(app.blitz_seconds_left(), Some(2));
+        assert!(app.accepted_inputs.is_empty(), "the window is still open");
+        app.tick(&mut game, &[], 2.5);
+        assert!(matches!(
+            app.accepted_inputs.as_slice(),
+            [AcceptedInput { payload: InputPayload::Choice { choice, .. }, .. }] if *choice == expected
+        ));
+        assert!(
+            !matches!(app.mode, AppMode::PendingPrompt { prompt_id, .. } if prompt_id == prompt)
+        );
+    }
+}
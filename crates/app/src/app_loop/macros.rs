@@ -0,0 +1,241 @@
+//! Named policy macros: recorded sequences of policy updates replayed with one key (F5-F8).
+//! A macro run is journaled as its constituent updates, so replays never need the macro itself.
+
+use std::path::PathBuf;
+use std::{iter, mem};
+
+use core::{Game, LogEvent, PolicyUpdate};
+use directories::ProjectDirs;
+use macroquad::prelude::KeyCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::AppState;
+use super::presets::builtin_presets;
+use crate::APP_NAME;
+use crate::save_file::{SaveSchema, load_save, write_save_atomic};
+
+/// Keys that run the first four saved macros, in order.
+pub const MACRO_KEYS: [KeyCode; 4] = [KeyCode::F5, KeyCode::F6, KeyCode::F7, KeyCode::F8];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PolicyMacro {
+    pub name: String,
+    pub updates: Vec<PolicyUpdate>,
+}
+
+impl PolicyMacro {
+    /// Add `update`, replacing an earlier update to the same policy setting.
+    fn record(&mut self, update: PolicyUpdate) {
+        let setting = mem::discriminant(&update);
+        self.updates.retain(|recorded| mem::discriminant(recorded) != setting);
+        self.updates.push(update);
+    }
+}
+
+/// Saved macros and starting policy presets, persisted with the app settings, plus the
+/// macro being recorded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PolicyMacroBook {
+    pub macros: Vec<PolicyMacro>,
+    /// Named policies to start a run with; settings files without any get the built-ins.
+    #[serde(default = "builtin_presets")]
+    pub presets: Vec<PolicyMacro>,
+    #[serde(skip)]
+    pub recording: Option<PolicyMacro>,
+    /// Set when `macros` or `presets` changed and should be written back; the caller clears it.
+    #[serde(skip)]
+    pub unsaved: bool,
+}
+
+impl Default for PolicyMacroBook {
+    fn default() -> Self {
+        Self { macros: Vec::new(), presets: builtin_presets(), recording: None, unsaved: false }
+    }
+}
+
+impl PolicyMacroBook {
+    pub fn get_default_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
+            let mut path = proj_dirs.data_dir().to_path_buf();
+            path.push("policy_macros.json");
+            path
+        })
+    }
+
+    /// Saved macros from the default path; a missing or unreadable file means none.
+    pub fn load_default() -> Self {
+        Self::get_default_path().and_then(|path| load_save::<Self>(&path).ok()).unwrap_or_default()
+    }
+
+    /// Write the saved macros to the default path, returning a warning for the log on failure.
+    pub fn save_default(&self) -> Result<(), String> {
+        let path = Self::get_default_path().ok_or("Warning: no settings directory for macros")?;
+        write_save_atomic(&path, self)
+            .map_err(|error| format!("Warning: failed to save policy macros: {error}"))
+    }
+}
+
+impl SaveSchema for PolicyMacroBook {
+    const KIND: &'static str = "policy_macros";
+    const VERSION: u32 = 1;
+
+    fn migrate(from_version: u32, _payload: Value) -> Result<Value, String> {
+        Err(format!("no migration from version {from_version}"))
+    }
+}
+
+/// What a `macro ...` palette command asks for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MacroCommand {
+    Record(String),
+    Save,
+    Cancel,
+    Run(String),
+    Delete(String),
+    List,
+}
+
+impl AppState {
+    /// Keep an accepted policy update in the macro being recorded, if any.
+    pub(super) fn record_macro_update(&mut self, update: &PolicyUpdate) {
+        if let Some(recording) = &mut self.policy_macros.recording {
+            recording.record(update.clone());
+        }
+    }
+
+    /// Run the macro bound to `key`, if it is a macro key with a saved macro behind it.
+    pub(super) fn handle_macro_keys(&mut self, game: &mut Game, keys_pressed: &[KeyCode]) {
+        for (slot, key) in MACRO_KEYS.iter().enumerate() {
+            if keys_pressed.contains(key)
+                && let Some(name) = self.policy_macros.macros.get(slot).map(|m| m.name.clone())
+            {
+                self.run_macro_command(game, MacroCommand::Run(name));
+            }
+        }
+    }
+
+    pub(super) fn run_macro_command(&mut self, game: &mut Game, command: MacroCommand) {
+        let book = &mut self.policy_macros;
+        let notice = match command {
+            MacroCommand::Record(name) => {
+                book.recording = Some(PolicyMacro { name: name.clone(), updates: Vec::new() });
+                format!("Recording macro '{name}'; change policy, then `macro save`")
+            }
+            MacroCommand::Save => match book.recording.take() {
+                Some(recorded) => {
+                    let name = recorded.name.clone();
+                    let count = recorded.updates.len();
+                    match book.macros.iter().position(|saved| saved.name == name) {
+                        Some(index) => book.macros[index] = recorded,
+                        None => book.macros.push(recorded),
+                    }
+                    book.unsaved = true;
+                    format!("Saved macro '{name}' ({count} updates)")
+                }
+                None => "No macro is being recorded".to_string(),
+            },
+            MacroCommand::Cancel => {
+                book.recording = None;
+                "Macro recording cancelled".to_string()
+            }
+            MacroCommand::Delete(name) => {
+                let before = book.macros.len();
+                book.macros.retain(|saved| saved.name != name);
+                book.unsaved |= book.macros.len() != before;
+                format!("Deleted macro '{name}'")
+            }
+            MacroCommand::List => {
+                let names: Vec<String> = book
+                    .macros
+                    .iter()
+                    .zip(["F5", "F6", "F7", "F8"].into_iter().map(Some).chain(iter::repeat(None)))
+                    .map(|(saved, key)| match key {
+                        Some(key) => format!("{key}={}", saved.name),
+                        None => saved.name.clone(),
+                    })
+                    .collect();
+                format!("Macros: {}", names.join(", "))
+            }
+            MacroCommand::Run(name) => {
+                let Some(saved) = book.macros.iter().find(|saved| saved.name == name).cloned()
+                else {
+                    game.push_log(LogEvent::Notice(format!("No macro named '{name}'")));
+                    return;
+                };
+                for update in saved.updates {
+                    self.apply_and_record_policy(game, update);
+                }
+                format!("Ran macro '{name}'")
+            }
+        };
+        game.push_log(LogEvent::Notice(notice));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_loop::AppMode;
+    use core::journal::InputPayload;
+    use core::{ContentPack, FightMode, GameMode, Stance};
+
+    fn type_command(app: &mut AppState, game: &mut Game, line: &str) {
+        app.tick(game, &[KeyCode::Enter], 0.0);
+        app.type_into_palette(&line.chars().collect::<Vec<_>>());
+        app.tick(game, &[KeyCode::Enter], 0.0);
+    }
+
+    #[test]
+    fn recorded_macro_replays_as_its_constituent_updates() {
+        let mut game = Game::new(2715, &ContentPack::default(), GameMode::Ironman);
+        let mut app = AppState::new();
+        type_command(&mut app, &mut game, "macro record \"boss prep\"");
+        type_command(&mut app, &mut game, "policy stance defensive");
+        type_command(&mut app, &mut game, "policy retreat 50");
+        type_command(&mut app, &mut game, "policy retreat 60");
+        type_command(&mut app, &mut game, "policy mode fight");
+        type_command(&mut app, &mut game, "macro save");
+        assert!(app.policy_macros.unsaved);
+        assert_eq!(
+            app.policy_macros.macros,
+            vec![PolicyMacro {
+                name: "boss prep".to_string(),
+                updates: vec![
+                    PolicyUpdate::Stance(Stance::Defensive),
+                    PolicyUpdate::RetreatHpThreshold(60),
+                    PolicyUpdate::FightMode(FightMode::Fight),
+                ],
+            }]
+        );
+
+        let mut fresh = Game::new(2715, &ContentPack::default(), GameMode::Ironman);
+        app.tick(&mut fresh, &[KeyCode::F5], 0.0);
+        assert_eq!(app.mode, AppMode::Paused);
+        let journaled: Vec<&InputPayload> =
+            app.accepted_inputs.iter().map(|input| &input.payload).collect();
+        assert_eq!(journaled.len(), 3);
+        assert!(
+            journaled.iter().all(|payload| matches!(payload, InputPayload::PolicyUpdate { .. }))
+        );
+        assert_eq!(fresh.state().policy.stance, Stance::Defensive);
+        assert_eq!(fresh.state().policy.retreat_hp_threshold, 60);
+    }
+
+    #[test]
+    fn macro_book_round_trips_without_the_recording() {
+        let book = PolicyMacroBook {
+            macros: vec![PolicyMacro {
+                name: "greedy".to_string(),
+                updates: vec![PolicyUpdate::RetreatHpThreshold(20)],
+            }],
+            presets: Vec::new(),
+            recording: Some(PolicyMacro { name: "draft".to_string(), updates: Vec::new() }),
+            unsaved: true,
+        };
+        let json = serde_json::to_string(&book).unwrap();
+        let loaded: PolicyMacroBook = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.macros, book.macros);
+        assert_eq!((loaded.recording, loaded.unsaved), (None, false));
+    }
+}
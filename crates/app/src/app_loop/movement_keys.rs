@@ -0,0 +1,118 @@
+//! Manual control: W, A, S and D each take one journaled step while paused, and the arrow keys
+//! take over steering from any running mode until Space hands the run back to auto-explore.
+
+use super::{AcceptedInput, AppMode, AppState};
+use core::journal::InputPayload;
+use core::{AdvanceResult, Direction, Game};
+use macroquad::prelude::KeyCode;
+
+const MOVEMENT_KEYS: [(KeyCode, Direction); 4] = [
+    (KeyCode::W, Direction::North),
+    (KeyCode::A, Direction::West),
+    (KeyCode::S, Direction::South),
+    (KeyCode::D, Direction::East),
+];
+
+/// Right is also the single-tick step while paused, so it only steers once in manual mode.
+const STEERING_KEYS: [(KeyCode, Direction); 4] = [
+    (KeyCode::Up, Direction::North),
+    (KeyCode::Left, Direction::West),
+    (KeyCode::Down, Direction::South),
+    (KeyCode::Right, Direction::East),
+];
+
+/// Ticks one step may take to resolve; only a player still busy with an earlier action needs
+/// more than one.
+const MANUAL_STEP_BUDGET: u32 = 64;
+
+impl AppState {
+    /// Take the step for the first movement or steering key pressed this frame, returning the
+    /// advance that resolved it. A steering key switches to manual mode, pausing auto-play at
+    /// the next boundary first. Steps into walls or other actors are ignored.
+    pub(super) fn handle_movement_keys(
+        &mut self,
+        game: &mut Game,
+        keys_pressed: &[KeyCode],
+    ) -> Option<AdvanceResult> {
+        let pressed = |bindings: [(KeyCode, Direction); 4]| {
+            bindings.into_iter().find(|(key, _)| keys_pressed.contains(key)).map(|(_, dir)| dir)
+        };
+        let steering = match self.mode {
+            AppMode::Paused if keys_pressed.contains(&KeyCode::Right) => None,
+            AppMode::Paused | AppMode::AutoPlay | AppMode::Manual => pressed(STEERING_KEYS),
+            _ => None,
+        };
+        let direction = match (steering, &self.mode) {
+            (Some(direction), mode) => {
+                if *mode == AppMode::AutoPlay {
+                    game.request_pause();
+                    game.advance(1);
+                }
+                self.mode = AppMode::Manual;
+                direction
+            }
+            (None, AppMode::Paused | AppMode::Manual) => pressed(MOVEMENT_KEYS)?,
+            (None, _) => return None,
+        };
+        let tick = game.current_tick();
+        game.apply_manual_step(direction).ok()?;
+        self.accepted_inputs.push(AcceptedInput {
+            tick_boundary: tick,
+            payload: InputPayload::ManualStep { tick_boundary: tick, direction },
+        });
+        Some(game.advance(MANUAL_STEP_BUDGET))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_loop::AppMode;
+    use core::{ContentPack, GameMode};
+
+    #[test]
+    fn movement_keys_step_once_record_the_input_and_stay_paused() {
+        let mut app = AppState::new();
+        let mut game = Game::new(99, &ContentPack::default(), GameMode::Ironman);
+        let start = game.state().actors[game.state().player_id].pos;
+        let (key, direction) = MOVEMENT_KEYS
+            .into_iter()
+            .find(|(_, direction)| {
+                game.state().map.is_discovered_walkable(direction.step_from(start))
+            })
+            .expect("the start tile has an open neighbour");
+
+        app.tick(&mut game, &[key], 0.0);
+        assert_eq!(game.state().actors[game.state().player_id].pos, direction.step_from(start));
+        assert!(matches!(
+            app.accepted_inputs.as_slice(),
+            [AcceptedInput { payload: InputPayload::ManualStep { .. }, .. }]
+        ));
+        assert!(matches!(app.mode, AppMode::Paused | AppMode::PendingPrompt { .. }));
+    }
+
+    #[test]
+    fn arrow_keys_take_over_from_auto_play_until_space() {
+        let mut app = AppState::new();
+        app.mode = AppMode::AutoPlay;
+        let mut game = Game::new(99, &ContentPack::default(), GameMode::Ironman);
+        let start = game.state().actors[game.state().player_id].pos;
+        let (key, direction) = STEERING_KEYS
+            .into_iter()
+            .find(|(_, direction)| {
+                game.state().map.is_discovered_walkable(direction.step_from(start))
+            })
+            .expect("the start tile has an open neighbour");
+
+        app.tick(&mut game, &[key], 0.0);
+        assert_eq!(game.state().actors[game.state().player_id].pos, direction.step_from(start));
+        assert!(matches!(
+            app.mode,
+            AppMode::Manual | AppMode::PendingPrompt { steering: true, .. }
+        ));
+        if app.mode == AppMode::Manual {
+            app.tick(&mut game, &[KeyCode::Space], 0.0);
+            assert_eq!(app.mode, AppMode::AutoPlay);
+        }
+    }
+}
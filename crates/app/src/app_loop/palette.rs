@@ -0,0 +1,250 @@
+//! Command palette opened with Enter while paused, e.g. `policy stance defensive` or `swap`.
+//! Commands become the same journaled inputs as their keys, so replays cannot tell them apart.
+
+use super::{AcceptedInput, AppMode, AppState, MacroCommand, PresetCommand};
+use crate::run_bundle::BundleRequest;
+use core::journal::InputPayload;
+use core::{
+    Aggro, ExploreMode, FightMode, Game, LogEvent, MarkerKind, PolicyUpdate, PositionIntent,
+    Stance, TargetTag,
+};
+use macroquad::prelude::KeyCode;
+
+const HELP: &str = "Commands: policy <mode|stance|priority|retreat|heal|intent|greed|explore|autofight|autoresolve> <value>, \
+                    swap, brace, wait <ticks>, mark here <danger|loot|stairs|avoid|clear>, note here \"text\", \
+                    macro <record|run|delete> <name>, macro <save|cancel|list>, preset <name>|save <name>|list, \
+                    export \"<file>\", import \"<file>\"";
+
+/// A parsed palette command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PaletteCommand {
+    Policy(PolicyUpdate),
+    Swap,
+    Brace,
+    /// Hold position for this many ticks.
+    Wait(u32),
+    Mark(Option<MarkerKind>),
+    /// A danger marker on the player's tile, with `text` echoed to the event log.
+    Note(String),
+    Macro(MacroCommand),
+    Preset(PresetCommand),
+    /// Export or import a run bundle; quote the path to keep its case.
+    Bundle(BundleRequest),
+    Help,
+}
+
+/// Parse one palette line; the error is shown to the player as-is.
+pub fn parse_command(line: &str) -> Result<PaletteCommand, String> {
+    let words = split_words(line)?;
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    match words.as_slice() {
+        ["policy", field, value] => parse_policy(field, value).map(PaletteCommand::Policy),
+        ["swap"] => Ok(PaletteCommand::Swap),
+        ["brace"] => Ok(PaletteCommand::Brace),
+        ["wait", ticks] => ticks
+            .parse()
+            .map(PaletteCommand::Wait)
+            .map_err(|_| format!("Expected a tick count, got {ticks}")),
+        ["mark", "here", marker] => parse_marker(marker).map(PaletteCommand::Mark),
+        ["note", "here", text] => Ok(PaletteCommand::Note(text.to_string())),
+        ["macro", "record", name] => {
+            Ok(PaletteCommand::Macro(MacroCommand::Record(name.to_string())))
+        }
+        ["macro", "run", name] => Ok(PaletteCommand::Macro(MacroCommand::Run(name.to_string()))),
+        ["macro", "delete", name] => {
+            Ok(PaletteCommand::Macro(MacroCommand::Delete(name.to_string())))
+        }
+        ["macro", "save"] => Ok(PaletteCommand::Macro(MacroCommand::Save)),
+        ["macro", "cancel"] => Ok(PaletteCommand::Macro(MacroCommand::Cancel)),
+        ["macro", "list"] => Ok(PaletteCommand::Macro(MacroCommand::List)),
+        ["preset", "save", name] => {
+            Ok(PaletteCommand::Preset(PresetCommand::Save(name.to_string())))
+        }
+        ["preset", "list"] => Ok(PaletteCommand::Preset(PresetCommand::List)),
+        ["preset", name] => Ok(PaletteCommand::Preset(PresetCommand::Choose(name.to_string()))),
+        ["export", path] => Ok(PaletteCommand::Bundle(BundleRequest::Export(path.into()))),
+        ["import", path] => Ok(PaletteCommand::Bundle(BundleRequest::Import(path.into()))),
+        ["use", ..] => Err("Nothing to use: consumables take effect when picked up".to_string()),
+        ["help"] | [] => Ok(PaletteCommand::Help),
+        _ => Err(format!("Unknown command: {line}")),
+    }
+}
+
+/// Lowercased words; a double-quoted phrase is one word and keeps its case.
+fn split_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut rest = line.trim();
+    while !rest.is_empty() {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').ok_or("Unclosed quote")?;
+            words.push(quoted[..end].to_string());
+            rest = quoted[end + 1..].trim_start();
+        } else {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            words.push(rest[..end].to_lowercase());
+            rest = rest[end..].trim_start();
+        }
+    }
+    Ok(words)
+}
+
+fn parse_policy(field: &str, value: &str) -> Result<PolicyUpdate, String> {
+    let update = match (field, value) {
+        ("mode", "fight") => PolicyUpdate::FightMode(FightMode::Fight),
+        ("mode", "avoid") => PolicyUpdate::FightMode(FightMode::Avoid),
+        ("stance", "aggressive") => PolicyUpdate::Stance(Stance::Aggressive),
+        ("stance", "balanced") => PolicyUpdate::Stance(Stance::Balanced),
+        ("stance", "defensive") => PolicyUpdate::Stance(Stance::Defensive),
+        ("priority", "nearest") => {
+            PolicyUpdate::TargetPriority(vec![TargetTag::Nearest, TargetTag::LowestHp])
+        }
+        ("priority", "weakest") => PolicyUpdate::TargetPriority(vec![TargetTag::LowestHp]),
+        ("heal", "off") => PolicyUpdate::AutoHealIfBelowThreshold(None),
+        ("intent", "hold") => PolicyUpdate::PositionIntent(PositionIntent::HoldGround),
+        ("intent", "advance") => PolicyUpdate::PositionIntent(PositionIntent::AdvanceToMelee),
+        ("intent", "flee") => {
+            PolicyUpdate::PositionIntent(PositionIntent::FleeToNearestExploredTile)
+        }
+        ("greed", "conserve") => PolicyUpdate::ResourceAggression(Aggro::Conserve),
+        ("greed", "greedy") => PolicyUpdate::ResourceAggression(Aggro::Greedy),
+        ("explore", "thorough") => PolicyUpdate::ExplorationMode(ExploreMode::Thorough),
+        ("explore", "rush") => PolicyUpdate::ExplorationMode(ExploreMode::Rush),
+        ("autofight", "on") => PolicyUpdate::AutoFightTrivial(true),
+        ("autofight", "off") => PolicyUpdate::AutoFightTrivial(false),
+        ("autoresolve", "on") => PolicyUpdate::AutoResolveEncounters(true),
+        ("autoresolve", "off") => PolicyUpdate::AutoResolveEncounters(false),
+        ("retreat" | "heal", percent) => {
+            let percent = percent
+                .parse::<u8>()
+                .ok()
+                .filter(|percent| *percent <= 100)
+                .ok_or_else(|| format!("Expected a percentage, got {percent}"))?;
+            if field == "retreat" {
+                PolicyUpdate::RetreatHpThreshold(percent)
+            } else {
+                PolicyUpdate::AutoHealIfBelowThreshold(Some(percent))
+            }
+        }
+        _ => return Err(format!("Unknown policy setting: {field} {value}")),
+    };
+    Ok(update)
+}
+
+fn parse_marker(marker: &str) -> Result<Option<MarkerKind>, String> {
+    match marker {
+        "danger" => Ok(Some(MarkerKind::Danger)),
+        "loot" => Ok(Some(MarkerKind::LootLater)),
+        "stairs" => Ok(Some(MarkerKind::Stairs)),
+        "avoid" => Ok(Some(MarkerKind::Avoid)),
+        "clear" => Ok(None),
+        _ => Err(format!("Unknown marker: {marker}")),
+    }
+}
+
+/// Status line shown while the palette is open.
+pub fn palette_line(buffer: &str) -> String {
+    format!("> {buffer}_  (Enter=run, Esc=cancel, help)")
+}
+
+impl AppState {
+    /// Append typed characters to the open palette; ignored while it is closed.
+    pub fn type_into_palette(&mut self, chars: &[char]) {
+        if let Some(buffer) = &mut self.palette {
+            buffer.extend(chars.iter().filter(|ch| !ch.is_control()));
+        }
+    }
+
+    /// Palette editing keys; returns whether the palette consumed this frame's input.
+    pub(super) fn handle_palette_keys(
+        &mut self,
+        game: &mut Game,
+        keys_pressed: &[KeyCode],
+    ) -> bool {
+        let Some(buffer) = &mut self.palette else {
+            let opens =
+                matches!(self.mode, AppMode::Paused) && keys_pressed.contains(&KeyCode::Enter);
+            if opens {
+                self.palette = Some(String::new());
+            }
+            return opens;
+        };
+        if keys_pressed.contains(&KeyCode::Backspace) {
+            buffer.pop();
+        }
+        if keys_pressed.contains(&KeyCode::Escape) {
+            self.palette = None;
+        } else if keys_pressed.contains(&KeyCode::Enter) {
+            let line = self.palette.take().unwrap_or_default();
+            self.run_palette_command(game, &line);
+        }
+        true
+    }
+
+    fn run_palette_command(&mut self, game: &mut Game, line: &str) {
+        let tick = game.current_tick();
+        let command = match parse_command(line) {
+            Ok(command) => command,
+            Err(message) => {
+                game.push_log(LogEvent::Notice(message));
+                return;
+            }
+        };
+        let payload = match command {
+            PaletteCommand::Policy(update) => {
+                self.apply_and_record_policy(game, update);
+                return;
+            }
+            PaletteCommand::Macro(command) => {
+                self.run_macro_command(game, command);
+                return;
+            }
+            PaletteCommand::Preset(command) => {
+                self.run_preset_command(game, command);
+                return;
+            }
+            PaletteCommand::Bundle(request) => {
+                self.bundle_request = Some(request);
+                return;
+            }
+            PaletteCommand::Help => {
+                game.push_log(LogEvent::Notice(HELP.to_string()));
+                return;
+            }
+            PaletteCommand::Swap => game
+                .apply_swap_weapon()
+                .map(|()| InputPayload::SwapActiveWeapon { tick_boundary: tick }),
+            PaletteCommand::Brace => {
+                game.apply_brace().map(|()| InputPayload::Brace { tick_boundary: tick })
+            }
+            PaletteCommand::Wait(ticks) => {
+                game.apply_wait(ticks).map(|()| InputPayload::Wait { tick_boundary: tick, ticks })
+            }
+            PaletteCommand::Mark(marker) => {
+                let pos = game.state().actors[game.state().player_id].pos;
+                game.set_marker(pos, marker).map(|()| InputPayload::SetMarker {
+                    tick_boundary: tick,
+                    pos,
+                    marker,
+                })
+            }
+            PaletteCommand::Note(text) => {
+                let pos = game.state().actors[game.state().player_id].pos;
+                let marker = Some(MarkerKind::Danger);
+                let placed = game.set_marker(pos, marker);
+                if placed.is_ok() {
+                    game.push_log(LogEvent::Notice(format!("Note ({}, {}): {text}", pos.x, pos.y)));
+                }
+                placed.map(|()| InputPayload::SetMarker { tick_boundary: tick, pos, marker })
+            }
+        };
+        match payload {
+            Ok(payload) => {
+                self.accepted_inputs.push(AcceptedInput { tick_boundary: tick, payload })
+            }
+            Err(error) => game.push_log(LogEvent::Notice(format!("Command rejected: {error}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+//! Tests for palette parsing and the journaled inputs its commands produce.
+
+use super::*;
+use core::{ContentPack, GameMode};
+
+#[test]
+fn commands_parse_case_insensitively_and_keep_quoted_text() {
+    assert_eq!(
+        parse_command("Policy STANCE defensive"),
+        Ok(PaletteCommand::Policy(PolicyUpdate::Stance(Stance::Defensive)))
+    );
+    assert_eq!(
+        parse_command("policy retreat 40"),
+        Ok(PaletteCommand::Policy(PolicyUpdate::RetreatHpThreshold(40)))
+    );
+    assert_eq!(
+        parse_command("policy explore rush"),
+        Ok(PaletteCommand::Policy(PolicyUpdate::ExplorationMode(ExploreMode::Rush)))
+    );
+    assert_eq!(
+        parse_command("policy autofight on"),
+        Ok(PaletteCommand::Policy(PolicyUpdate::AutoFightTrivial(true)))
+    );
+    assert_eq!(
+        parse_command("policy autoresolve off"),
+        Ok(PaletteCommand::Policy(PolicyUpdate::AutoResolveEncounters(false)))
+    );
+    assert_eq!(
+        parse_command("note here \"Ambush ahead\""),
+        Ok(PaletteCommand::Note("Ambush ahead".to_string()))
+    );
+    assert_eq!(parse_command("mark here clear"), Ok(PaletteCommand::Mark(None)));
+    assert_eq!(parse_command("wait 30"), Ok(PaletteCommand::Wait(30)));
+    assert_eq!(
+        parse_command("preset Slayer"),
+        Ok(PaletteCommand::Preset(PresetCommand::Choose("slayer".to_string())))
+    );
+    assert_eq!(
+        parse_command("preset save \"Glass Cannon\""),
+        Ok(PaletteCommand::Preset(PresetCommand::Save("Glass Cannon".to_string())))
+    );
+    assert!(parse_command("wait forever").is_err());
+    assert!(parse_command("policy retreat 140").is_err());
+    assert!(parse_command("note here \"open").is_err());
+    assert!(parse_command("use potion").is_err());
+    assert!(parse_command("dance").is_err());
+}
+
+#[test]
+fn palette_commands_record_the_same_inputs_as_keys() {
+    let mut game = Game::new(2714, &ContentPack::default(), GameMode::Ironman);
+    let mut app = AppState::new();
+    app.tick(&mut game, &[KeyCode::Enter], 0.0);
+    app.type_into_palette(&"swap".chars().collect::<Vec<_>>());
+    app.tick(&mut game, &[KeyCode::S, KeyCode::A], 0.0);
+    assert!(app.accepted_inputs.is_empty(), "typed letters do not trigger their keys");
+    assert_eq!(app.mode, AppMode::Paused);
+
+    app.tick(&mut game, &[KeyCode::Enter], 0.0);
+    assert!(app.palette.is_none());
+    assert!(matches!(
+        app.accepted_inputs.as_slice(),
+        [AcceptedInput { payload: InputPayload::SwapActiveWeapon { .. }, .. }]
+    ));
+
+    app.tick(&mut game, &[KeyCode::Enter], 0.0);
+    app.type_into_palette(&"note here \"ambush\"".chars().collect::<Vec<_>>());
+    app.tick(&mut game, &[KeyCode::Enter], 0.0);
+    let pos = game.state().actors[game.state().player_id].pos;
+    assert_eq!(game.state().map.marker_at(pos), Some(MarkerKind::Danger));
+    assert!(game.log().iter().any(|event| matches!(
+        event,
+        LogEvent::Notice(text) if text.ends_with("ambush")
+    )));
+}
+
+#[test]
+fn bundle_commands_are_queued_for_the_frontend_without_journaling() {
+    let mut game = Game::new(2721, &ContentPack::default(), GameMode::Ironman);
+    let mut app = AppState::new();
+    app.tick(&mut game, &[KeyCode::Enter], 0.0);
+    app.type_into_palette(&"export \"Runs/Keep.bundle\"".chars().collect::<Vec<_>>());
+    app.tick(&mut game, &[KeyCode::Enter], 0.0);
+
+    assert_eq!(app.bundle_request, Some(BundleRequest::Export("Runs/Keep.bundle".into())));
+    assert!(app.accepted_inputs.is_empty());
+    assert_eq!(
+        parse_command("import \"Run.bundle\""),
+        Ok(PaletteCommand::Bundle(BundleRequest::Import("Run.bundle".into())))
+    );
+}
@@ -0,0 +1,127 @@
+//! Paused-mode key bindings that adjust policy or map markers and record the accepted inputs.
+
+use super::{AcceptedInput, AppState};
+use core::content::keys;
+use core::journal::InputPayload;
+use core::{AutoPickupRule, Game, MarkerKind, PickupAction, PickupFilter};
+use macroquad::prelude::KeyCode;
+
+/// Auto-pickup preset toggled by the U key: keep potions, drop rusty swords.
+fn auto_pickup_preset() -> Vec<AutoPickupRule> {
+    vec![
+        AutoPickupRule { filter: PickupFilter::Potions, action: PickupAction::Keep },
+        AutoPickupRule {
+            filter: PickupFilter::Item(keys::WEAPON_RUSTY_SWORD.to_string()),
+            action: PickupAction::Discard,
+        },
+    ]
+}
+
+impl AppState {
+    /// Apply a policy update to the game and record it if accepted.
+    pub(super) fn apply_and_record_policy(&mut self, game: &mut Game, update: core::PolicyUpdate) {
+        let tick = game.current_tick();
+        if game.apply_policy_update(update.clone()).is_ok() {
+            self.record_macro_update(&update);
+            self.accepted_inputs.push(AcceptedInput {
+                tick_boundary: tick,
+                payload: InputPayload::PolicyUpdate { tick_boundary: tick, update },
+            });
+        }
+    }
+
+    /// Cycle the marker on the player's tile: none, danger, loot later, stairs, avoid.
+    fn cycle_player_tile_marker(&mut self, game: &mut Game) {
+        let state = game.state();
+        let pos = state.actors[state.player_id].pos;
+        let next = match state.map.marker_at(pos) {
+            None => Some(MarkerKind::Danger),
+            Some(MarkerKind::Danger) => Some(MarkerKind::LootLater),
+            Some(MarkerKind::LootLater) => Some(MarkerKind::Stairs),
+            Some(MarkerKind::Stairs) => Some(MarkerKind::Avoid),
+            Some(MarkerKind::Avoid) => None,
+        };
+        let tick = game.current_tick();
+        if game.set_marker(pos, next).is_ok() {
+            self.accepted_inputs.push(AcceptedInput {
+                tick_boundary: tick,
+                payload: InputPayload::SetMarker { tick_boundary: tick, pos, marker: next },
+            });
+        }
+    }
+
+    /// Process policy-related key presses while paused.
+    pub(super) fn handle_policy_keys(&mut self, game: &mut Game, keys_pressed: &[KeyCode]) {
+        if keys_pressed.contains(&KeyCode::M) {
+            let next = match game.state().policy.fight_or_avoid {
+                core::FightMode::Fight => core::FightMode::Avoid,
+                core::FightMode::Avoid => core::FightMode::Fight,
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::FightMode(next));
+        }
+        if keys_pressed.contains(&KeyCode::T) {
+            let next = match game.state().policy.stance {
+                core::Stance::Aggressive => core::Stance::Balanced,
+                core::Stance::Balanced => core::Stance::Defensive,
+                core::Stance::Defensive => core::Stance::Aggressive,
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::Stance(next));
+        }
+        if keys_pressed.contains(&KeyCode::P) {
+            let next = match game.state().policy.target_priority.first() {
+                Some(core::TargetTag::Nearest) => vec![core::TargetTag::LowestHp],
+                _ => vec![core::TargetTag::Nearest, core::TargetTag::LowestHp],
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::TargetPriority(next));
+        }
+        if keys_pressed.contains(&KeyCode::R) {
+            let current = game.state().policy.retreat_hp_threshold;
+            let next = if current < 90 { current + 10 } else { 0 };
+            self.apply_and_record_policy(game, core::PolicyUpdate::RetreatHpThreshold(next));
+        }
+        if keys_pressed.contains(&KeyCode::H) {
+            let next = match game.state().policy.auto_heal_if_below_threshold {
+                None => Some(30),
+                Some(80) => None,
+                Some(v) => Some(v + 10),
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::AutoHealIfBelowThreshold(next));
+        }
+        if keys_pressed.contains(&KeyCode::I) {
+            let next = match game.state().policy.position_intent {
+                core::PositionIntent::HoldGround => core::PositionIntent::AdvanceToMelee,
+                core::PositionIntent::AdvanceToMelee => {
+                    core::PositionIntent::FleeToNearestExploredTile
+                }
+                core::PositionIntent::FleeToNearestExploredTile => core::PositionIntent::HoldGround,
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::PositionIntent(next));
+        }
+        if keys_pressed.contains(&KeyCode::E) {
+            let next = match game.state().policy.exploration_mode {
+                core::ExploreMode::Thorough => core::ExploreMode::Rush,
+                core::ExploreMode::Rush => core::ExploreMode::Thorough,
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::ExplorationMode(next));
+        }
+        if keys_pressed.contains(&KeyCode::G) {
+            let next = match game.state().policy.resource_aggression {
+                core::Aggro::Conserve => core::Aggro::Greedy,
+                core::Aggro::Greedy => core::Aggro::Conserve,
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::ResourceAggression(next));
+        }
+        if keys_pressed.contains(&KeyCode::U) {
+            let next = if game.state().policy.auto_pickup.is_empty() {
+                auto_pickup_preset()
+            } else {
+                Vec::new()
+            };
+            self.apply_and_record_policy(game, core::PolicyUpdate::AutoPickup(next));
+        }
+        if keys_pressed.contains(&KeyCode::N) {
+            self.cycle_player_tile_marker(game);
+        }
+        self.handle_macro_keys(game, keys_pressed);
+    }
+}
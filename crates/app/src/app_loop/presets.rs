@@ -0,0 +1,166 @@
+//! Starting policy presets: named policies saved with the macros and chosen at run start.
+//! A chosen preset is journaled as its policy updates, so replays start from the same policy.
+
+use core::{
+    Aggro, ExploreMode, FightMode, Game, LogEvent, Policy, PolicyUpdate, PositionIntent, Stance,
+    TargetTag,
+};
+
+use super::{AppState, PolicyMacro};
+
+/// What a `preset ...` palette command asks for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PresetCommand {
+    /// Start the run on the named preset; only allowed before the first tick.
+    Choose(String),
+    /// Save the current policy under a name.
+    Save(String),
+    List,
+}
+
+/// The presets every settings file starts with.
+pub(super) fn builtin_presets() -> Vec<PolicyMacro> {
+    let preset = |name: &str, updates| PolicyMacro { name: name.to_string(), updates };
+    vec![
+        preset(
+            "explorer",
+            vec![
+                PolicyUpdate::FightMode(FightMode::Fight),
+                PolicyUpdate::Stance(Stance::Balanced),
+                PolicyUpdate::ResourceAggression(Aggro::Greedy),
+                PolicyUpdate::ExplorationMode(ExploreMode::Thorough),
+            ],
+        ),
+        preset(
+            "slayer",
+            vec![
+                PolicyUpdate::FightMode(FightMode::Fight),
+                PolicyUpdate::Stance(Stance::Aggressive),
+                PolicyUpdate::TargetPriority(vec![TargetTag::LowestHp, TargetTag::Nearest]),
+                PolicyUpdate::PositionIntent(PositionIntent::AdvanceToMelee),
+                PolicyUpdate::RetreatHpThreshold(20),
+                PolicyUpdate::AutoFightTrivial(true),
+            ],
+        ),
+        preset(
+            "coward",
+            vec![
+                PolicyUpdate::FightMode(FightMode::Avoid),
+                PolicyUpdate::Stance(Stance::Defensive),
+                PolicyUpdate::PositionIntent(PositionIntent::FleeToNearestExploredTile),
+                PolicyUpdate::RetreatHpThreshold(60),
+                PolicyUpdate::AutoHealIfBelowThreshold(Some(50)),
+                PolicyUpdate::ExplorationMode(ExploreMode::Rush),
+            ],
+        ),
+    ]
+}
+
+/// Every setting of `policy` as the updates that recreate it.
+fn policy_updates(policy: &Policy) -> Vec<PolicyUpdate> {
+    vec![
+        PolicyUpdate::FightMode(policy.fight_or_avoid),
+        PolicyUpdate::Stance(policy.stance),
+        PolicyUpdate::TargetPriority(policy.target_priority.clone()),
+        PolicyUpdate::RetreatHpThreshold(policy.retreat_hp_threshold),
+        PolicyUpdate::AutoHealIfBelowThreshold(policy.auto_heal_if_below_threshold),
+        PolicyUpdate::PositionIntent(policy.position_intent),
+        PolicyUpdate::ResourceAggression(policy.resource_aggression),
+        PolicyUpdate::ExplorationMode(policy.exploration_mode),
+        PolicyUpdate::AutoPickup(policy.auto_pickup.clone()),
+        PolicyUpdate::AutoFightTrivial(policy.auto_fight_trivial),
+        PolicyUpdate::AutoResolveEncounters(policy.auto_resolve_encounters),
+    ]
+}
+
+impl AppState {
+    pub(super) fn run_preset_command(&mut self, game: &mut Game, command: PresetCommand) {
+        let book = &mut self.policy_macros;
+        let notice = match command {
+            PresetCommand::List => {
+                let names: Vec<&str> = book.presets.iter().map(|p| p.name.as_str()).collect();
+                format!("Presets: {}", names.join(", "))
+            }
+            PresetCommand::Save(name) => {
+                let saved = PolicyMacro {
+                    name: name.clone(),
+                    updates: policy_updates(&game.state().policy),
+                };
+                match book.presets.iter().position(|preset| preset.name == name) {
+                    Some(index) => book.presets[index] = saved,
+                    None => book.presets.push(saved),
+                }
+                book.unsaved = true;
+                format!("Saved the current policy as preset '{name}'")
+            }
+            PresetCommand::Choose(_) if game.current_tick() > 0 => {
+                "Presets can only be chosen at run start".to_string()
+            }
+            PresetCommand::Choose(name) => {
+                let Some(chosen) = book.presets.iter().find(|preset| preset.name == name).cloned()
+                else {
+                    game.push_log(LogEvent::Notice(format!("No preset named '{name}'")));
+                    return;
+                };
+                for update in chosen.updates {
+                    self.apply_and_record_policy(game, update);
+                }
+                format!("Starting with the '{name}' preset")
+            }
+        };
+        game.push_log(LogEvent::Notice(notice));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::journal::InputPayload;
+    use core::{ContentPack, GameMode};
+    use macroquad::prelude::KeyCode;
+
+    fn type_command(app: &mut AppState, game: &mut Game, line: &str) {
+        app.tick(game, &[KeyCode::Enter], 0.0);
+        app.type_into_palette(&line.chars().collect::<Vec<_>>());
+        app.tick(game, &[KeyCode::Enter], 0.0);
+    }
+
+    #[test]
+    fn chosen_preset_is_journaled_so_a_replay_starts_on_the_same_policy() {
+        let mut game = Game::new(2743, &ContentPack::default(), GameMode::Ironman);
+        let mut app = AppState::new();
+        type_command(&mut app, &mut game, "preset slayer");
+        assert_eq!(game.state().policy.stance, Stance::Aggressive);
+        assert!(game.state().policy.auto_fight_trivial);
+
+        let mut replayed = Game::new(2743, &ContentPack::default(), GameMode::Ironman);
+        for input in &app.accepted_inputs {
+            let InputPayload::PolicyUpdate { tick_boundary: 0, update } = &input.payload else {
+                panic!("presets journal policy updates at tick 0");
+            };
+            replayed.apply_policy_update(update.clone()).unwrap();
+        }
+        assert_eq!(replayed.state().policy, game.state().policy);
+
+        game.advance(1);
+        app.accepted_inputs.clear();
+        type_command(&mut app, &mut game, "preset coward");
+        assert!(app.accepted_inputs.is_empty(), "presets only apply at run start");
+        assert_eq!(game.state().policy.stance, Stance::Aggressive);
+    }
+
+    #[test]
+    fn saved_preset_captures_the_whole_current_policy() {
+        let mut game = Game::new(2743, &ContentPack::default(), GameMode::Ironman);
+        let mut app = AppState::new();
+        type_command(&mut app, &mut game, "policy retreat 45");
+        type_command(&mut app, &mut game, "preset save \"Careful\"");
+        assert!(app.policy_macros.unsaved);
+
+        let mut fresh = Game::new(1, &ContentPack::default(), GameMode::Ironman);
+        let mut other = AppState { policy_macros: app.policy_macros.clone(), ..AppState::new() };
+        type_command(&mut other, &mut fresh, "preset \"Careful\"");
+        assert_eq!(fresh.state().policy, game.state().policy);
+        assert_eq!(fresh.state().policy.retreat_hp_threshold, 45);
+    }
+}
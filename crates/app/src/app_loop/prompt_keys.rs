@@ -0,0 +1,156 @@
+//! Key bindings that answer a pending prompt, kept pure so each binding is unit-testable.
+
+use std::iter;
+
+use core::content::INVENTORY_CAPACITY;
+use core::{Choice, Interrupt, SNOOZE_TICKS};
+use macroquad::prelude::KeyCode;
+
+/// Pact choices offered on a branch floor transition, bound to keys 1 through 4.
+const BRANCH_PACT_KEYS: [(KeyCode, Choice); 4] = [
+    (KeyCode::Key1, Choice::DescendBranchAVeil),
+    (KeyCode::Key2, Choice::DescendBranchAForge),
+    (KeyCode::Key3, Choice::DescendBranchBVeil),
+    (KeyCode::Key4, Choice::DescendBranchBForge),
+];
+
+/// Z snoozes a loot or door prompt for the engine's standard snooze length.
+const SNOOZE: (KeyCode, Choice) = (KeyCode::Z, Choice::IgnoreFor { ticks: SNOOZE_TICKS });
+
+/// Keys 1 through 3 pick the matching perk of a level-up draft.
+const PERK_DRAFT_KEYS: [KeyCode; 3] = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3];
+
+/// Keys 1 through 5 name an inventory slot: used in an encounter, dropped at a loot prompt.
+const INVENTORY_SLOT_KEYS: [KeyCode; INVENTORY_CAPACITY] =
+    [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5];
+
+fn slot_keys(choice: fn(u8) -> Choice) -> impl Iterator<Item = (KeyCode, Choice)> {
+    INVENTORY_SLOT_KEYS.into_iter().zip(0..).map(move |(key, slot)| (key, choice(slot)))
+}
+
+/// The choice this frame's keys make for `interrupt`, if any key answers it.
+pub(super) fn prompt_choice(interrupt: &Interrupt, keys_pressed: &[KeyCode]) -> Option<Choice> {
+    let bindings: Vec<(KeyCode, Choice)> = match interrupt {
+        Interrupt::LootFound { .. } => {
+            [(KeyCode::L, Choice::KeepLoot), (KeyCode::D, Choice::DiscardLoot), SNOOZE]
+                .into_iter()
+                .chain(slot_keys(Choice::DropItem))
+                .collect()
+        }
+        Interrupt::EnemyEncounter { .. } => {
+            [(KeyCode::F, Choice::Fight), (KeyCode::A, Choice::Avoid)]
+                .into_iter()
+                .chain(slot_keys(Choice::UseItem))
+                .collect()
+        }
+        Interrupt::DoorBlocked { .. } => vec![(KeyCode::O, Choice::OpenDoor), SNOOZE],
+        Interrupt::FloorTransition { requires_branch_god_choice: true, .. } => {
+            BRANCH_PACT_KEYS.to_vec()
+        }
+        Interrupt::FloorTransition { boons, .. } => {
+            let boon_keys = [KeyCode::Key1, KeyCode::Key2].into_iter().zip(boons);
+            iter::once((KeyCode::C, Choice::Descend))
+                .chain(boon_keys.map(|(key, boon)| (key, Choice::DescendWithBoon(boon.kind))))
+                .collect()
+        }
+        Interrupt::StairsLocked { .. } => vec![(KeyCode::C, Choice::SeekObjective)],
+        Interrupt::PactAltar { can_cleanse, .. } => {
+            let cleanse = can_cleanse.then_some((KeyCode::V, Choice::CleanseCurses));
+            [(KeyCode::X, Choice::BreakPact), (KeyCode::C, Choice::KeepPact)]
+                .into_iter()
+                .chain(cleanse)
+                .collect()
+        }
+        Interrupt::PerkDraft { offer, .. } => {
+            let indices = 0..offer.len() as u8;
+            PERK_DRAFT_KEYS
+                .into_iter()
+                .zip(indices)
+                .map(|(key, i)| (key, Choice::DraftPerk(i)))
+                .collect()
+        }
+    };
+    bindings.into_iter().find_map(|(key, choice)| keys_pressed.contains(&key).then_some(choice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::content::keys;
+    use core::{BoonKind, ChoicePromptId, GodBoon, GodId, ItemId, ItemKind, Pos};
+
+    fn transition(requires_branch_god_choice: bool, boons: Vec<GodBoon>) -> Interrupt {
+        Interrupt::FloorTransition {
+            prompt_id: ChoicePromptId(1),
+            current_floor: 1,
+            next_floor: Some(2),
+            requires_branch_god_choice,
+            boons,
+            branch_previews: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn prompt_keys_map_to_their_choices_and_ignore_other_keys() {
+        let door = Interrupt::DoorBlocked { prompt_id: ChoicePromptId(2), pos: Pos { y: 1, x: 1 } };
+        assert_eq!(prompt_choice(&door, &[KeyCode::O]), Some(Choice::OpenDoor));
+        assert_eq!(
+            prompt_choice(&door, &[KeyCode::Z]),
+            Some(Choice::IgnoreFor { ticks: SNOOZE_TICKS })
+        );
+        assert_eq!(prompt_choice(&door, &[KeyCode::L, KeyCode::C]), None);
+    }
+
+    #[test]
+    fn loot_number_keys_drop_an_inventory_stack() {
+        let loot = Interrupt::LootFound {
+            prompt_id: ChoicePromptId(5),
+            item: ItemId::default(),
+            kind: ItemKind::Consumable(keys::CONSUMABLE_TORCH),
+            curse_detected: false,
+            inventory_full: true,
+        };
+        assert_eq!(prompt_choice(&loot, &[KeyCode::Key5]), Some(Choice::DropItem(4)));
+        assert_eq!(prompt_choice(&loot, &[KeyCode::Key6]), None);
+    }
+
+    #[test]
+    fn floor_transition_keys_depend_on_branch_and_offered_boons() {
+        let branch = transition(true, Vec::new());
+        assert_eq!(prompt_choice(&branch, &[KeyCode::Key3]), Some(Choice::DescendBranchBVeil));
+        assert_eq!(prompt_choice(&branch, &[KeyCode::C]), None);
+
+        let boon = GodBoon { kind: BoonKind::Vigor, amount: 2 };
+        let plain = transition(false, vec![boon]);
+        assert_eq!(prompt_choice(&plain, &[KeyCode::C]), Some(Choice::Descend));
+        assert_eq!(
+            prompt_choice(&plain, &[KeyCode::Key1]),
+            Some(Choice::DescendWithBoon(BoonKind::Vigor))
+        );
+        assert_eq!(prompt_choice(&plain, &[KeyCode::Key2]), None, "only one boon was offered");
+    }
+
+    #[test]
+    fn perk_draft_number_keys_pick_from_the_offer() {
+        let draft = Interrupt::PerkDraft {
+            prompt_id: ChoicePromptId(3),
+            level: 2,
+            offer: vec![keys::PERK_SWIFT, keys::PERK_SCOUT],
+        };
+        assert_eq!(prompt_choice(&draft, &[KeyCode::Key2]), Some(Choice::DraftPerk(1)));
+        assert_eq!(prompt_choice(&draft, &[KeyCode::Key3]), None, "only two perks were offered");
+    }
+
+    #[test]
+    fn altar_cleanse_key_needs_a_curse_to_lift() {
+        let altar = |can_cleanse| Interrupt::PactAltar {
+            prompt_id: ChoicePromptId(4),
+            pos: Pos { y: 2, x: 2 },
+            current_god: GodId::Forge,
+            alternate_god: GodId::Veil,
+            can_cleanse,
+        };
+        assert_eq!(prompt_choice(&altar(true), &[KeyCode::V]), Some(Choice::CleanseCurses));
+        assert_eq!(prompt_choice(&altar(false), &[KeyCode::V]), None);
+    }
+}
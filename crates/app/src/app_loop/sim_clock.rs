@@ -0,0 +1,78 @@
+//! Fixed-timestep clock that turns elapsed frame time into simulation ticks during auto-play.
+//! Auto-play speed depends only on wall time, so it matches across machines and frame rates.
+
+use std::time::Duration;
+
+/// Simulation ticks per second of auto-play at normal speed.
+pub const AUTO_PLAY_TICKS_PER_SECOND: f64 = 600.0;
+/// Speed multiplier applied while turbo is on.
+pub const TURBO_MULTIPLIER: f64 = 4.0;
+/// Upper bound on ticks simulated in one frame, so a long stall does not freeze the next frame.
+/// Ticks past the cap stay owed and run over the following frames.
+pub const MAX_TICKS_PER_FRAME: u32 = 200;
+/// Wall time simulation may take per frame before yielding the rest to the next frame.
+pub const SIM_FRAME_BUDGET: Duration = Duration::from_millis(8);
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimClock {
+    accumulator_ticks: f64,
+    pub turbo: bool,
+}
+
+impl SimClock {
+    /// Ticks owed for `frame_seconds` of auto-play; fractional ticks carry to later frames.
+    pub fn due_ticks(&mut self, frame_seconds: f32) -> u32 {
+        let speed = if self.turbo { TURBO_MULTIPLIER } else { 1.0 };
+        self.accumulator_ticks +=
+            f64::from(frame_seconds.max(0.0)) * AUTO_PLAY_TICKS_PER_SECOND * speed;
+        let due = self.accumulator_ticks.floor().min(f64::from(MAX_TICKS_PER_FRAME));
+        self.accumulator_ticks -= due;
+        due as u32
+    }
+
+    /// Whole ticks still owed after this frame's share; non-zero while catching up on a stall.
+    pub fn backlog(&self) -> u32 {
+        self.accumulator_ticks.floor() as u32
+    }
+
+    /// Carry ticks that were due but not simulated within the frame budget to the next frame.
+    pub fn defer(&mut self, ticks: u32) {
+        self.accumulator_ticks += f64::from(ticks);
+    }
+
+    /// Forget partial progress, e.g. when auto-play pauses or stops on a prompt.
+    pub fn reset(&mut self) {
+        self.accumulator_ticks = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_rate_is_independent_of_frame_rate() {
+        let mut fast_frames = SimClock::default();
+        let fast: u32 = (0..240).map(|_| fast_frames.due_ticks(1.0 / 240.0)).sum();
+        let mut slow_frames = SimClock::default();
+        let slow: u32 = (0..30).map(|_| slow_frames.due_ticks(1.0 / 30.0)).sum();
+
+        assert!(fast.abs_diff(600) <= 1, "240 fps ran {fast} ticks in one second");
+        assert!(slow.abs_diff(600) <= 1, "30 fps ran {slow} ticks in one second");
+    }
+
+    #[test]
+    fn turbo_speeds_up_and_long_stalls_catch_up_over_later_frames() {
+        let mut clock = SimClock { turbo: true, ..SimClock::default() };
+        assert_eq!(clock.due_ticks(1.0 / 32.0), 75);
+        assert_eq!(clock.due_ticks(0.5), MAX_TICKS_PER_FRAME);
+        assert_eq!(clock.backlog(), 1_200 - MAX_TICKS_PER_FRAME);
+
+        let caught_up: u32 = (0..10).map(|_| clock.due_ticks(0.0)).sum();
+        assert_eq!(caught_up, 1_200 - MAX_TICKS_PER_FRAME, "no stalled tick is dropped");
+        assert_eq!(clock.backlog(), 0);
+
+        clock.defer(7);
+        assert_eq!(clock.due_ticks(0.0), 7, "deferred ticks run on the next frame");
+    }
+}
@@ -0,0 +1,96 @@
+//! Tests for app modes, stop reasons and the paused-mode keys handled in `app_loop`.
+
+use super::{AppCompletion, AppMode, AppState};
+use crate::ui_scale::UiScaleAction;
+use core::journal::InputPayload;
+use core::{
+    AdvanceStopReason, ContentPack, DeathCause, EngineFailureReason, Game, GameMode, MarkerKind,
+    RunOutcome,
+};
+use macroquad::prelude::KeyCode;
+
+#[test]
+fn finished_outcome_maps_to_finished_mode() {
+    let mut app = AppState::new();
+    app.apply_stop_reason(
+        AdvanceStopReason::Finished(RunOutcome::Defeat(DeathCause::Damage)),
+        false,
+    );
+    assert_eq!(
+        app.mode,
+        AppMode::Finished(AppCompletion::Outcome(RunOutcome::Defeat(DeathCause::Damage,)))
+    );
+}
+
+#[test]
+fn engine_failure_maps_to_finished_mode_without_panic() {
+    let game = Game::new(5, &ContentPack::default(), GameMode::Ironman);
+    let reason = EngineFailureReason::StalledNoProgress(game.engine_diagnostics());
+    let mut app = AppState::new();
+    app.apply_stop_reason(AdvanceStopReason::EngineFailure(reason.clone()), true);
+    assert_eq!(app.mode, AppMode::Finished(AppCompletion::EngineFailure(reason)));
+}
+
+#[test]
+fn tick_reports_a_finished_run_only_on_the_frame_it_ends() {
+    let mut game = Game::new(5, &ContentPack::default(), GameMode::Ironman);
+    let mut app = AppState::new();
+    let mut endings = Vec::new();
+    for _ in 0..20_000 {
+        let keys: &[KeyCode] =
+            if matches!(app.mode, AppMode::AutoPlay) { &[] } else { &[KeyCode::Space] };
+        endings.extend(app.tick(&mut game, keys, 1.0));
+        if let AppMode::PendingPrompt { prompt_id, .. } = app.mode {
+            let choice = game.available_choices().remove(0);
+            game.apply_choice(prompt_id, choice).expect("first choice applies");
+            app.mode = AppMode::Paused;
+        }
+    }
+    assert!(matches!(app.mode, AppMode::Finished(_)));
+    assert_eq!(endings.len(), 1);
+}
+
+#[test]
+fn reset_for_new_run_keeps_only_the_ui_scale() {
+    let mut app = AppState { ui_scale: 1.7, mode: AppMode::AutoPlay, ..AppState::default() };
+    app.sim_clock.turbo = true;
+    app.reset_for_new_run();
+    assert_eq!(app.mode, AppMode::Paused);
+    assert!(!app.sim_clock.turbo);
+    assert!((app.ui_scale - 1.7).abs() < 0.0001);
+}
+
+#[test]
+fn ui_scale_actions_support_fractional_steps_and_reset() {
+    let mut app = AppState::new();
+    app.ui_scale = 1.0;
+
+    assert!(app.apply_ui_scale_action(UiScaleAction::Increase));
+    assert!((app.ui_scale - 1.1).abs() < 0.0001);
+
+    assert!(app.apply_ui_scale_action(UiScaleAction::Decrease));
+    assert!((app.ui_scale - 1.0).abs() < 0.0001);
+
+    app.ui_scale = 0.5;
+    assert!(!app.apply_ui_scale_action(UiScaleAction::Decrease));
+    app.ui_scale = 1.6;
+    assert!(app.apply_ui_scale_action(UiScaleAction::Reset));
+    assert!((app.ui_scale - 1.0).abs() < 0.0001);
+}
+
+#[test]
+fn marker_key_cycles_player_tile_marker_and_records_input() {
+    let mut app = AppState::new();
+    let mut game = Game::new(99, &ContentPack::default(), GameMode::Ironman);
+    let pos = game.state().actors[game.state().player_id].pos;
+
+    app.tick(&mut game, &[KeyCode::N], 0.0);
+    assert_eq!(game.state().map.marker_at(pos), Some(MarkerKind::Danger));
+    assert!(matches!(
+        app.accepted_inputs.as_slice(),
+        [super::AcceptedInput {
+            payload: InputPayload::SetMarker { marker: Some(MarkerKind::Danger), .. },
+            ..
+        }]
+    ));
+}
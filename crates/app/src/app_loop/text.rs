@@ -0,0 +1,235 @@
+//! Text formatting for prompts, status lines, the stats panel, and run recaps.
+//! Pure functions of the app and game state, so the renderer only draws what they return.
+
+mod stats_panel;
+
+pub use stats_panel::stats_panel_lines;
+
+use super::{AppCompletion, AppMode};
+use crate::display_name::{actor_name, damage_source_name, perk_name, victory_name};
+use crate::message::compose_log_event;
+use crate::{engine_failure_code, format_snapshot_hash, reason_code};
+use core::{
+    BranchPreview, BranchProfile, ContentPack, DeathRecord, DensityTier, FightPreview, Game,
+    HazardTier, Interrupt, LayoutStyle, PromptKind, RunOutcome,
+};
+
+/// One branch's preview as a prompt suffix, e.g. `; A: many foes, no hazards, warren`.
+fn branch_preview_text(preview: &BranchPreview) -> String {
+    let branch = match preview.branch {
+        BranchProfile::BranchA => "A",
+        BranchProfile::BranchB => "B",
+        BranchProfile::Uncommitted => "?",
+    };
+    let foes = match preview.floor.enemy_density {
+        DensityTier::Sparse => "few foes",
+        DensityTier::Moderate => "some foes",
+        DensityTier::Dense => "many foes",
+    };
+    let hazards = match preview.floor.hazards {
+        HazardTier::Clear => "no hazards",
+        HazardTier::Scattered => "scattered hazards",
+        HazardTier::Heavy => "heavy hazards",
+    };
+    let layout = match preview.floor.layout {
+        LayoutStyle::Halls => "open halls",
+        LayoutStyle::Chambers => "chambers",
+        LayoutStyle::Warren => "warren",
+    };
+    format!("; {branch}: {foes}, {hazards}, {layout}")
+}
+
+pub fn status_text(content: &ContentPack, mode: &AppMode) -> String {
+    match mode {
+        AppMode::PendingPrompt { interrupt, .. } => prompt_text(content, interrupt),
+        AppMode::Finished(completion) => {
+            format!("Finished: {}", completion_reason_code(completion))
+        }
+        AppMode::AutoPlay => "Auto-Explore ON (Space to pause, Tab for turbo)".to_string(),
+        AppMode::Manual => "Manual (arrows to steer, Space to Auto-Explore)".to_string(),
+        AppMode::Paused => {
+            "Paused (Space to Auto-Explore, Right to step, WASD to move)".to_string()
+        }
+    }
+}
+
+pub fn prompt_text(content: &ContentPack, interrupt: &Interrupt) -> String {
+    match interrupt {
+        Interrupt::LootFound { curse_detected, inventory_full, .. } => {
+            let curse =
+                if *curse_detected { " - CURSED, it cannot be removed once kept" } else { "" };
+            let (full, drop) = if *inventory_full {
+                (" - pack full, keeping uses it now", ", 1-5=drop a stack")
+            } else {
+                ("", "")
+            };
+            format!("INTERRUPT: Loot found{curse}{full} (L=keep, D=discard, Z=snooze{drop})")
+        }
+        Interrupt::EnemyEncounter { threat, boss_intro, .. } => {
+            let dist_text = match threat.nearest_enemy_distance {
+                Some(distance) => distance.to_string(),
+                None => "?".to_string(),
+            };
+            let sighted = match boss_intro {
+                Some(intro) => format!("BOSS - {intro}"),
+                None => format!("{:?} sighted", threat.primary_enemy_kind),
+            };
+            format!(
+                "INTERRUPT: {sighted} (F=fight, A=avoid, 1-5=use item) {} visible, nearest={}, Tags: {:?}",
+                threat.visible_enemy_count, dist_text, threat.danger_tags
+            )
+        }
+        Interrupt::DoorBlocked { .. } => "INTERRUPT: Door blocked (O=open, Z=snooze)".to_string(),
+        Interrupt::FloorTransition {
+            next_floor,
+            requires_branch_god_choice,
+            boons,
+            branch_previews,
+            ..
+        } => {
+            if *requires_branch_god_choice {
+                let previews: String = branch_previews.iter().map(branch_preview_text).collect();
+                format!(
+                    "INTERRUPT: Choose pact (1=A+Veil, 2=A+Forge, 3=B+Veil, 4=B+Forge){previews}"
+                )
+            } else {
+                match next_floor {
+                    Some(floor) => {
+                        let boon_keys: String = boons
+                            .iter()
+                            .enumerate()
+                            .map(|(i, boon)| {
+                                format!(", {}={:?} +{}", i + 1, boon.kind, boon.amount)
+                            })
+                            .collect();
+                        format!("INTERRUPT: Stairs reached (C=descend to floor {floor}{boon_keys})")
+                    }
+                    None => "INTERRUPT: Final stairs reached (C=finish run)".to_string(),
+                }
+            }
+        }
+        Interrupt::StairsLocked { objective, .. } => {
+            format!("INTERRUPT: Stairs locked until {objective:?} is done (C=seek objective)")
+        }
+        Interrupt::PactAltar { current_god, alternate_god, can_cleanse, .. } => format!(
+            "INTERRUPT: Pact altar (X=break {current_god:?} pact for {alternate_god:?}, C=keep{})",
+            if *can_cleanse { ", V=cleanse curses" } else { "" }
+        ),
+        Interrupt::PerkDraft { level, offer, .. } => {
+            let picks: Vec<String> = offer
+                .iter()
+                .enumerate()
+                .map(|(i, id)| format!("{}={}", i + 1, perk_name(content, id)))
+                .collect();
+            format!("INTERRUPT: Level {level}! Draft a perk ({})", picks.join(", "))
+        }
+    }
+}
+
+/// Status suffix previewing prompts queued behind the active one, e.g. " (next: LootFound)".
+pub fn queued_prompts_suffix(queued: &[PromptKind]) -> String {
+    if queued.is_empty() {
+        return String::new();
+    }
+    let names: Vec<String> = queued.iter().map(|kind| format!("{kind:?}")).collect();
+    format!(" (next: {})", names.join(", "))
+}
+
+/// The encounter prompt's fight preview, e.g. ` (you'd deal 7, take 3)`.
+pub fn fight_preview_suffix(preview: &FightPreview) -> String {
+    format!(" (you'd deal {}, take {})", preview.damage_dealt, preview.damage_taken)
+}
+
+/// The final blow in one line, e.g. `Slain by Goblin (melee, 4 damage) on floor 3 at T120`.
+pub fn death_summary(content: &ContentPack, record: &DeathRecord) -> String {
+    let cause = match record.killer {
+        Some(kind) => format!("Slain by {}", actor_name(&content.display, kind)),
+        None => format!("Died of {}", damage_source_name(record.source)),
+    };
+    let source = format!("{:?}", record.source).to_lowercase();
+    format!(
+        "{cause} ({source}, {} damage) on floor {} at T{}",
+        record.damage, record.floor_index, record.tick
+    )
+}
+
+/// The threat panel's "what can see me" line, e.g. `Seen by: Goblin, Feral Hound`.
+pub fn seen_by_line(content: &ContentPack, game: &Game) -> String {
+    let watchers: Vec<&str> = game
+        .enemies_seeing_player()
+        .into_iter()
+        .filter_map(|enemy| game.state().actors.get(enemy))
+        .map(|actor| actor_name(&content.display, actor.kind))
+        .collect();
+    if watchers.is_empty() {
+        return "Seen by: nobody".to_string();
+    }
+    format!("Seen by: {}", watchers.join(", "))
+}
+
+pub fn completion_reason_code(completion: &AppCompletion) -> &'static str {
+    match completion {
+        AppCompletion::Outcome(outcome) => reason_code(outcome),
+        AppCompletion::EngineFailure(reason) => engine_failure_code(reason),
+    }
+}
+
+pub fn finished_recap_lines(
+    content: &ContentPack,
+    game: &Game,
+    run_seed: u64,
+    completion: &AppCompletion,
+) -> Vec<String> {
+    let mut lines = vec![
+        "Run recap:".to_string(),
+        format!("Reason: {}", completion_reason_code(completion)),
+        format!("Seed: {run_seed}"),
+        format!("Snapshot: {}", format_snapshot_hash(game.snapshot_hash())),
+        format!(
+            "Floor/Branch/God: {}/{:?}/{:?}",
+            game.state().floor_index,
+            game.state().branch_profile,
+            game.state().active_god
+        ),
+        format!("Floor name: {}", game.floor_name()),
+        format!("Tick: {}", game.current_tick()),
+        format!("Layout: {}", game.state().floor_provenance),
+    ];
+    if let AppCompletion::Outcome(RunOutcome::Victory(kind)) = completion {
+        lines.push(format!("Victory: {}", victory_name(*kind)));
+    }
+    if let Some(record) = game.death_record() {
+        lines.push(death_summary(content, record));
+        lines.push("Final moments:".to_string());
+        for event in &record.recent_events {
+            lines.push(format!("  {}", compose_log_event(content, game, event).plain_text()));
+        }
+    }
+    let floor_visits = game.run_stats().floor_visits.values();
+    let (total, revisits) = floor_visits.fold((0, 0), |(total, revisits), visits| {
+        (total + visits.total(), revisits + visits.revisits())
+    });
+    lines.push(format!("Backtracking: {revisits} of {total} steps revisited a tile"));
+    for record in &game.state().completed_objectives {
+        lines.push(format!(
+            "Objective F{}: {:?} at T{}",
+            record.floor_index, record.objective, record.completed_tick
+        ));
+    }
+    lines.push(format!("Threat trace (latest 5 of {}):", game.state().threat_trace.len()));
+
+    for trace in game.state().threat_trace.iter().take(5) {
+        lines.push(format!(
+            "T{} vis={} min_dist={:?} retreat={}",
+            trace.tick,
+            trace.visible_enemy_count,
+            trace.min_enemy_distance,
+            trace.retreat_triggered
+        ));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests;
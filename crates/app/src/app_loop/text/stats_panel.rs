@@ -0,0 +1,300 @@
+//! Stats panel snapshot and line formatting for the player HUD, alongside the prompt, status
+//! and recap text in the parent module.
+
+use crate::display_name::actor_name;
+use crate::format_snapshot_hash;
+use core::content::DisplayData;
+use core::state::{InventorySlot, ItemInstance};
+use core::{
+    AutoPickupRule, BranchProfile, ContentPack, Game, HudSnapshot, PickupFilter, WeaponSlot,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerHudSnapshot {
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: u32,
+    pub active_weapon_slot: WeaponSlot,
+    pub equipped_weapon: Option<ItemInstance>,
+    pub reserve_weapon: Option<ItemInstance>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// The stats panel's view: the core HUD snapshot plus the build and loadout details only
+/// this panel shows.
+pub struct StatsPanelSnapshot {
+    pub hud: HudSnapshot,
+    pub run_seed: u64,
+    pub branch_profile: BranchProfile,
+    /// Floors left on the oathbreaker curse; zero when the player is not cursed.
+    pub curse_floors_remaining: u8,
+    pub snapshot_hash: u64,
+    pub player: PlayerHudSnapshot,
+    pub active_perks: Vec<&'static str>,
+    /// Consumables stowed for later, oldest first.
+    pub inventory: Vec<InventorySlot>,
+    /// Potion appearances with the identified consumable id, sorted by appearance.
+    pub potions: Vec<(&'static str, Option<&'static str>)>,
+    pub kills_this_floor: u32,
+    pub auto_pickup: Vec<AutoPickupRule>,
+}
+
+pub fn gather_stats_panel_snapshot(game: &Game, run_seed: u64) -> StatsPanelSnapshot {
+    let state = game.state();
+    let player = &state.actors[state.player_id];
+    StatsPanelSnapshot {
+        hud: game.hud_snapshot(),
+        run_seed,
+        branch_profile: state.branch_profile,
+        curse_floors_remaining: state.curse_floors_remaining,
+        snapshot_hash: game.snapshot_hash(),
+        player: PlayerHudSnapshot {
+            attack: player.attack,
+            defense: player.defense,
+            speed: player.speed,
+            active_weapon_slot: player.active_weapon_slot,
+            equipped_weapon: player.equipped_weapon,
+            reserve_weapon: player.reserve_weapon,
+        },
+        active_perks: state.active_perks.clone(),
+        inventory: state.inventory.clone(),
+        potions: potion_entries(game),
+        kills_this_floor: state.kills_this_floor,
+        auto_pickup: state.policy.auto_pickup.clone(),
+    }
+}
+
+fn weapon_label(weapon: Option<ItemInstance>) -> String {
+    let Some(ItemInstance { key, enchant, cursed }) = weapon else {
+        return "None".to_string();
+    };
+    let enchant = if enchant == 0 { String::new() } else { format!(" +{enchant}") };
+    let curse = if cursed { " (cursed)" } else { "" };
+    format!("{key}{enchant}{curse}")
+}
+
+fn potion_entries(game: &Game) -> Vec<(&'static str, Option<&'static str>)> {
+    let state = game.state();
+    let mut entries: Vec<_> = state
+        .potion_appearances
+        .iter()
+        .map(|(&id, &appearance)| {
+            (appearance, state.identified_consumables.contains(id).then_some(id))
+        })
+        .collect();
+    entries.sort_unstable();
+    entries
+}
+
+pub fn stats_panel_lines_from_snapshot(
+    display: &DisplayData,
+    snapshot: &StatsPanelSnapshot,
+) -> Vec<String> {
+    let hud = &snapshot.hud;
+    let mut lines = vec![
+        format!("Tick: {}", hud.tick),
+        format!("Seed: {}", snapshot.run_seed),
+        format!("Floor: {} / 5", hud.floor_index),
+        format!("Branch: {:?}", snapshot.branch_profile),
+        format!("God: {:?}", hud.active_god),
+        format!("Hash: {}", format_snapshot_hash(snapshot.snapshot_hash)),
+    ];
+    if snapshot.curse_floors_remaining > 0 {
+        lines.insert(5, format!("Cursed: {} floors left", snapshot.curse_floors_remaining));
+    }
+
+    let intent_text = if let Some(intent) = hud.intent {
+        format!(
+            "Intent: {:?} target=({}, {}) path_len={}",
+            intent.reason, intent.target.x, intent.target.y, intent.path_len
+        )
+    } else {
+        "Intent: none".to_string()
+    };
+    lines.push(intent_text);
+    lines.push("Level: not tracked yet (planned with XP milestone)".to_string());
+
+    let p = &snapshot.player;
+    lines.push(format!(
+        "HP: {}/{}  ATK: {}  DEF: {}  SPD: {}",
+        hud.hp, hud.max_hp, p.attack, p.defense, p.speed
+    ));
+    lines.push(format!(
+        "Active Slot: {:?}  Weapon: {}",
+        p.active_weapon_slot,
+        weapon_label(p.equipped_weapon)
+    ));
+    lines.push(format!("Reserve: {}", weapon_label(p.reserve_weapon)));
+
+    let perks = if snapshot.active_perks.is_empty() {
+        "None".to_string()
+    } else {
+        snapshot.active_perks.join(", ")
+    };
+    lines.push(format!("Perks: {}", perks));
+    if !snapshot.inventory.is_empty() {
+        let stacks: Vec<String> =
+            snapshot.inventory.iter().map(|slot| format!("{} x{}", slot.id, slot.count)).collect();
+        lines.push(format!("Pack: {}", stacks.join(", ")));
+    }
+    let potions: Vec<String> = snapshot
+        .potions
+        .iter()
+        .map(|(appearance, id)| format!("{appearance}={}", id.unwrap_or("?")))
+        .collect();
+    lines.push(format!("Potions: {}", potions.join(", ")));
+    lines.push(format!("Kills this floor: {}", snapshot.kills_this_floor));
+    if !hud.threats.is_empty() {
+        let threats: Vec<String> = hud
+            .threats
+            .iter()
+            .map(|threat| format!("{} @{}", actor_name(display, threat.kind), threat.distance))
+            .collect();
+        lines.push(format!("Threats: {}", threats.join(", ")));
+    }
+
+    let policy = &hud.policy;
+    let auto_heal_text = policy
+        .auto_heal_if_below_threshold
+        .map(|v| format!("{v}%"))
+        .unwrap_or_else(|| "off".to_string());
+    lines.push(format!(
+        "Policy: stance={:?} retreat_if_hp<= {}% auto_heal={}",
+        policy.stance, policy.retreat_hp_threshold, auto_heal_text
+    ));
+    let pickup_rules: Vec<String> = snapshot
+        .auto_pickup
+        .iter()
+        .map(|rule| match &rule.filter {
+            PickupFilter::Item(key) => format!("{:?} {key}", rule.action),
+            filter => format!("{:?} {filter:?}", rule.action),
+        })
+        .collect();
+    lines.push(if pickup_rules.is_empty() {
+        "Auto-pickup: off".to_string()
+    } else {
+        format!("Auto-pickup: {}", pickup_rules.join(", "))
+    });
+
+    lines
+}
+
+pub fn stats_panel_lines(content: &ContentPack, game: &Game, run_seed: u64) -> Vec<String> {
+    let snapshot = gather_stats_panel_snapshot(game, run_seed);
+    stats_panel_lines_from_snapshot(&content.display, &snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        PlayerHudSnapshot, StatsPanelSnapshot, stats_panel_lines, stats_panel_lines_from_snapshot,
+    };
+    use core::content::{ContentPack, DisplayData};
+    use core::mapgen::BranchProfile;
+    use core::state::{InventorySlot, ItemInstance};
+    use core::{EntityId, HudSnapshot, HudThreat, PolicySummary, WeaponSlot};
+
+    #[test]
+    fn stats_panel_lines_cover_player_and_policy_data() {
+        let content = ContentPack::build_default();
+        let game = core::Game::new(7, &content, core::GameMode::Ironman);
+        let lines = stats_panel_lines(&content, &game, 7);
+
+        assert!(lines.iter().any(|l| l.contains("HP: 20/20")), "expected player HP line");
+        assert!(
+            lines.iter().any(|l| l.contains("Active Slot")),
+            "expected active weapon slot line"
+        );
+        assert!(lines.iter().any(|l| l.contains("Policy:")), "expected policy summary line");
+        assert!(
+            lines.iter().any(|l| l.contains("Level: not tracked yet")),
+            "expected level placeholder line"
+        );
+    }
+
+    #[test]
+    fn stats_panel_lines_update_when_snapshot_changes() {
+        let snapshot = StatsPanelSnapshot {
+            hud: HudSnapshot {
+                tick: 12,
+                hp: 5,
+                max_hp: 25,
+                floor_index: 2,
+                active_god: Some(core::GodId::Veil),
+                policy: PolicySummary {
+                    fight_or_avoid: core::FightMode::Fight,
+                    stance: core::Stance::Aggressive,
+                    retreat_hp_threshold: 15,
+                    auto_heal_if_below_threshold: Some(30),
+                },
+                intent: None,
+                threats: vec![HudThreat {
+                    enemy: EntityId::default(),
+                    kind: core::ActorKind::Goblin,
+                    hp: 4,
+                    distance: 3,
+                    danger_tags: vec![core::DangerTag::Melee],
+                }],
+                recent_events: Vec::new(),
+            },
+            run_seed: 99,
+            branch_profile: BranchProfile::BranchA,
+            curse_floors_remaining: 0,
+            snapshot_hash: 12345,
+            player: PlayerHudSnapshot {
+                attack: 9,
+                defense: 3,
+                speed: 11,
+                active_weapon_slot: WeaponSlot::Reserve,
+                equipped_weapon: Some(ItemInstance {
+                    key: "weapon_phase_dagger",
+                    enchant: 2,
+                    cursed: true,
+                }),
+                reserve_weapon: Some(ItemInstance::new("weapon_rusty_sword")),
+            },
+            active_perks: vec!["perk_scout"],
+            inventory: vec![InventorySlot { id: "consumable_minor_hp_pot", count: 2 }],
+            potions: vec![
+                ("Amber Potion", Some("consumable_haste_potion")),
+                ("Murky Potion", None),
+            ],
+            kills_this_floor: 4,
+            auto_pickup: vec![core::AutoPickupRule {
+                filter: core::PickupFilter::Item("weapon_rusty_sword".to_string()),
+                action: core::PickupAction::Discard,
+            }],
+        };
+
+        let lines = stats_panel_lines_from_snapshot(&DisplayData::default(), &snapshot);
+        assert!(
+            lines.iter().any(|l| l.contains("HP: 5/25")),
+            "expected updated hp values to render"
+        );
+        assert!(
+            lines.iter().any(|l| l.contains("weapon_phase_dagger +2 (cursed)")),
+            "expected equipped weapon id to render"
+        );
+        assert!(lines.iter().any(|l| l.contains("perk_scout")), "expected perk list to render");
+        assert!(lines.contains(&"Pack: consumable_minor_hp_pot x2".to_string()));
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.contains("Amber Potion=consumable_haste_potion, Murky Potion=?")),
+            "expected identified and unknown potions to render"
+        );
+        assert!(
+            lines.iter().any(|l| l.contains("Kills this floor: 4")),
+            "expected kill count to render"
+        );
+        assert!(lines.contains(&"Threats: Goblin @3".to_string()), "expected threats to render");
+        assert!(
+            lines.iter().any(|l| l.contains("retreat_if_hp<= 15%")),
+            "expected policy thresholds to render"
+        );
+        assert!(
+            lines.iter().any(|l| l == "Auto-pickup: Discard weapon_rusty_sword"),
+            "expected auto-pickup rules to render"
+        );
+    }
+}
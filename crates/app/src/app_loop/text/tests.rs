@@ -0,0 +1,239 @@
+//! Tests for prompt, status and recap text.
+
+use super::{
+    completion_reason_code, fight_preview_suffix, finished_recap_lines, prompt_text,
+    queued_prompts_suffix, status_text,
+};
+use crate::app_loop::{AppCompletion, AppMode};
+use core::{
+    ActorKind, BoonKind, BranchPreview, BranchProfile, ChoicePromptId, ContentPack, DamageSource,
+    DeathCause, DensityTier, EngineFailureReason, EntityId, FightPreview, FloorObjective,
+    FloorPreview, Game, GameMode, GodBoon, HazardTier, Interrupt, ItemId, ItemKind, LayoutStyle,
+    LogEvent, Pos, PromptKind, RunOutcome, ThreatSummary, VictoryKind,
+};
+
+#[test]
+fn status_text_reports_finished_reason_code() {
+    let mode =
+        AppMode::Finished(AppCompletion::Outcome(RunOutcome::Victory(VictoryKind::SpeedClear)));
+    assert_eq!(status_text(&ContentPack::default(), &mode), "Finished: WIN_SPEED");
+}
+
+#[test]
+fn completion_reason_covers_engine_failures() {
+    let game = Game::new(3, &ContentPack::default(), GameMode::Ironman);
+    let reason = EngineFailureReason::StalledNoProgress(game.engine_diagnostics());
+    let completion = AppCompletion::EngineFailure(reason);
+    assert_eq!(completion_reason_code(&completion), "ENG_STALLED_NO_PROGRESS");
+}
+
+#[test]
+fn prompt_text_covers_branch_floor_transition() {
+    let interrupt = Interrupt::FloorTransition {
+        prompt_id: ChoicePromptId(17),
+        current_floor: 3,
+        next_floor: Some(4),
+        requires_branch_god_choice: true,
+        boons: Vec::new(),
+        branch_previews: Vec::new(),
+    };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &interrupt),
+        "INTERRUPT: Choose pact (1=A+Veil, 2=A+Forge, 3=B+Veil, 4=B+Forge)"
+    );
+}
+
+#[test]
+fn pact_prompt_appends_each_branch_preview() {
+    let preview = |branch, enemy_density, hazards, layout| BranchPreview {
+        branch,
+        floor: FloorPreview { enemy_density, hazards, layout },
+    };
+    let interrupt = Interrupt::FloorTransition {
+        prompt_id: ChoicePromptId(18),
+        current_floor: 1,
+        next_floor: Some(2),
+        requires_branch_god_choice: true,
+        boons: Vec::new(),
+        branch_previews: vec![
+            preview(
+                BranchProfile::BranchA,
+                DensityTier::Dense,
+                HazardTier::Clear,
+                LayoutStyle::Warren,
+            ),
+            preview(
+                BranchProfile::BranchB,
+                DensityTier::Sparse,
+                HazardTier::Heavy,
+                LayoutStyle::Halls,
+            ),
+        ],
+    };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &interrupt),
+        "INTERRUPT: Choose pact (1=A+Veil, 2=A+Forge, 3=B+Veil, 4=B+Forge); \
+         A: many foes, no hazards, warren; B: few foes, heavy hazards, open halls"
+    );
+}
+
+#[test]
+fn prompt_text_covers_final_floor_transition() {
+    let interrupt = Interrupt::FloorTransition {
+        prompt_id: ChoicePromptId(31),
+        current_floor: 5,
+        next_floor: None,
+        requires_branch_god_choice: false,
+        boons: Vec::new(),
+        branch_previews: Vec::new(),
+    };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &interrupt),
+        "INTERRUPT: Final stairs reached (C=finish run)"
+    );
+}
+
+#[test]
+fn prompt_text_lists_offered_boons_by_number() {
+    let interrupt = Interrupt::FloorTransition {
+        prompt_id: ChoicePromptId(40),
+        current_floor: 2,
+        next_floor: Some(3),
+        requires_branch_god_choice: false,
+        boons: vec![
+            GodBoon { kind: BoonKind::Bulwark, amount: 1 },
+            GodBoon { kind: BoonKind::Vigor, amount: 2 },
+        ],
+        branch_previews: Vec::new(),
+    };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &interrupt),
+        "INTERRUPT: Stairs reached (C=descend to floor 3, 1=Bulwark +1, 2=Vigor +2)"
+    );
+}
+
+#[test]
+fn prompt_text_covers_door_blocked_interrupt() {
+    let interrupt =
+        Interrupt::DoorBlocked { prompt_id: ChoicePromptId(9), pos: Pos { x: 3, y: 5 } };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &interrupt),
+        "INTERRUPT: Door blocked (O=open, Z=snooze)"
+    );
+}
+
+#[test]
+fn loot_prompt_warns_when_the_pack_is_full() {
+    let loot = |inventory_full| Interrupt::LootFound {
+        prompt_id: ChoicePromptId(10),
+        item: ItemId::default(),
+        kind: ItemKind::Consumable("consumable_smoke_bomb"),
+        curse_detected: false,
+        inventory_full,
+    };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &loot(false)),
+        "INTERRUPT: Loot found (L=keep, D=discard, Z=snooze)"
+    );
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &loot(true)),
+        "INTERRUPT: Loot found - pack full, keeping uses it now \
+         (L=keep, D=discard, Z=snooze, 1-5=drop a stack)"
+    );
+}
+
+#[test]
+fn boss_encounter_prompt_opens_with_the_roster_intro() {
+    let enemy = EntityId::default();
+    let interrupt = Interrupt::EnemyEncounter {
+        prompt_id: ChoicePromptId(4),
+        enemies: vec![enemy],
+        primary_enemy: enemy,
+        retreat_eligible: false,
+        threat: ThreatSummary {
+            danger_tags: Vec::new(),
+            visible_enemy_count: 1,
+            nearest_enemy_distance: Some(1),
+            primary_enemy_kind: ActorKind::HollowKing,
+        },
+        boss_intro: Some("The Hollow King rises"),
+    };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &interrupt),
+        "INTERRUPT: BOSS - The Hollow King rises (F=fight, A=avoid, 1-5=use item) 1 visible, nearest=1, Tags: []"
+    );
+}
+
+#[test]
+fn prompt_text_names_the_objective_behind_locked_stairs() {
+    let interrupt = Interrupt::StairsLocked {
+        prompt_id: ChoicePromptId(12),
+        objective: FloorObjective::SlayElite,
+    };
+
+    assert_eq!(
+        prompt_text(&ContentPack::default(), &interrupt),
+        "INTERRUPT: Stairs locked until SlayElite is done (C=seek objective)"
+    );
+}
+
+#[test]
+fn status_text_reports_paused_mode() {
+    assert_eq!(
+        status_text(&ContentPack::default(), &AppMode::Paused),
+        "Paused (Space to Auto-Explore, Right to step, WASD to move)"
+    );
+}
+
+#[test]
+fn completion_reason_reports_damage_death() {
+    let completion = AppCompletion::Outcome(core::RunOutcome::Defeat(DeathCause::Damage));
+    assert_eq!(completion_reason_code(&completion), "DMG_HP_ZERO");
+}
+
+#[test]
+fn queued_prompts_suffix_lists_prompts_in_order() {
+    assert_eq!(queued_prompts_suffix(&[]), "");
+    assert_eq!(
+        queued_prompts_suffix(&[PromptKind::LootFound, PromptKind::FloorTransition]),
+        " (next: LootFound, FloorTransition)"
+    );
+}
+
+#[test]
+fn fight_preview_suffix_names_both_sides_of_the_exchange() {
+    let preview = FightPreview { damage_dealt: 7, damage_taken: 3, strikes_to_kill: 2 };
+    assert_eq!(fight_preview_suffix(&preview), " (you'd deal 7, take 3)");
+}
+
+#[test]
+fn victory_recap_names_the_leaderboard_category() {
+    let game = Game::new(2747, &ContentPack::default(), GameMode::Ironman);
+    let completion = AppCompletion::Outcome(RunOutcome::Victory(VictoryKind::BossSkip));
+    let lines = finished_recap_lines(&ContentPack::default(), &game, 2747, &completion);
+    assert!(lines.contains(&"Reason: WIN_BOSS_SKIP".to_string()));
+    assert!(lines.contains(&"Victory: boss skipped, the final boss still stands".to_string()));
+    assert!(lines.contains(&format!("Floor name: {}", game.floor_name())));
+}
+
+#[test]
+fn defeat_recap_names_the_final_blow_and_the_moments_before_it() {
+    let mut game = Game::new(2745, &ContentPack::default(), GameMode::Ironman);
+    game.push_log(LogEvent::Notice("Careful now".to_string()));
+    game.damage_player(99, DamageSource::Melee, Some(ActorKind::Goblin));
+    let completion = AppCompletion::Outcome(core::RunOutcome::Defeat(DeathCause::Damage));
+    let lines = finished_recap_lines(&ContentPack::default(), &game, 2745, &completion);
+
+    let floor = game.state().floor_index;
+    let summary = format!("Slain by Goblin (melee, 99 damage) on floor {floor} at T0");
+    let at = lines.iter().position(|line| *line == summary).expect("death summary line");
+    assert_eq!(lines[at + 1], "Final moments:");
+    assert!(lines.contains(&"  Careful now".to_string()));
+}
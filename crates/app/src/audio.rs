@@ -0,0 +1,273 @@
+//! Sound-effect hooks: structured core events mapped to sound cues, plus the audio settings.
+//! Core stays audio-unaware; this module only watches the log and run state between frames.
+
+use std::f32::consts::TAU;
+use std::path::PathBuf;
+
+use core::{Game, LogEvent, RunOutcome};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::APP_NAME;
+use crate::app_loop::{AppCompletion, AppMode};
+use crate::save_file::SaveSchema;
+
+pub const DEFAULT_VOLUME: f32 = 0.7;
+pub const VOLUME_STEP: f32 = 0.1;
+/// Sample rate of the synthesized cue tones.
+const CUE_SAMPLE_RATE: u32 = 22_050;
+
+/// A game moment that has a sound effect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SoundCue {
+    Hit,
+    Door,
+    Pickup,
+    Descend,
+    Death,
+}
+
+impl SoundCue {
+    pub const ALL: [SoundCue; 5] =
+        [SoundCue::Hit, SoundCue::Door, SoundCue::Pickup, SoundCue::Descend, SoundCue::Death];
+
+    /// Tone frequency in Hz and length in milliseconds.
+    fn tone(self) -> (f32, u32) {
+        match self {
+            SoundCue::Hit => (180.0, 90),
+            SoundCue::Door => (330.0, 140),
+            SoundCue::Pickup => (880.0, 110),
+            SoundCue::Descend => (440.0, 320),
+            SoundCue::Death => (110.0, 600),
+        }
+    }
+
+    /// The cue as a mono 16-bit PCM WAV file: a sine tone fading out to silence.
+    pub fn wav_bytes(self) -> Vec<u8> {
+        let (frequency, millis) = self.tone();
+        let sample_count = CUE_SAMPLE_RATE * millis / 1000;
+        let data_len = sample_count * 2;
+        let mut bytes = Vec::with_capacity(44 + data_len as usize);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&CUE_SAMPLE_RATE.to_le_bytes());
+        bytes.extend_from_slice(&(CUE_SAMPLE_RATE * 2).to_le_bytes());
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        for index in 0..sample_count {
+            let t = index as f32 / CUE_SAMPLE_RATE as f32;
+            let fade = 1.0 - index as f32 / sample_count as f32;
+            let sample = (t * frequency * TAU).sin() * fade * 0.5;
+            bytes.extend_from_slice(&((sample * f32::from(i16::MAX)) as i16).to_le_bytes());
+        }
+        bytes
+    }
+}
+
+/// The cue a log event plays, if any.
+pub fn cue_for_event(event: &LogEvent) -> Option<SoundCue> {
+    match event {
+        LogEvent::EncounterResolved { fought: true, .. }
+        | LogEvent::Counterattack { .. }
+        | LogEvent::EnemyStruck { .. } => Some(SoundCue::Hit),
+        LogEvent::DoorOpened { .. } => Some(SoundCue::Door),
+        LogEvent::ItemPickedUp { .. } => Some(SoundCue::Pickup),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioAction {
+    ToggleMute,
+    VolumeUp,
+    VolumeDown,
+}
+
+/// Persisted sound settings: a mute toggle and a master volume in `0.0..=1.0`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct AudioSettings {
+    pub enabled: bool,
+    pub volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self { enabled: true, volume: DEFAULT_VOLUME }
+    }
+}
+
+impl AudioSettings {
+    pub fn get_default_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
+            let mut path = proj_dirs.data_dir().to_path_buf();
+            path.push("audio_settings.json");
+            path
+        })
+    }
+
+    pub fn apply(&mut self, action: AudioAction) {
+        match action {
+            AudioAction::ToggleMute => self.enabled = !self.enabled,
+            AudioAction::VolumeUp => self.volume = clamp_volume(self.volume + VOLUME_STEP),
+            AudioAction::VolumeDown => self.volume = clamp_volume(self.volume - VOLUME_STEP),
+        }
+    }
+
+    /// Volume to play cues at; zero while muted.
+    pub fn effective_volume(&self) -> f32 {
+        if self.enabled { clamp_volume(self.volume) } else { 0.0 }
+    }
+
+    pub fn notice(&self) -> String {
+        if self.enabled {
+            format!("Sound on (volume {:.0}%)", clamp_volume(self.volume) * 100.0)
+        } else {
+            "Sound muted".to_string()
+        }
+    }
+}
+
+impl SaveSchema for AudioSettings {
+    const KIND: &'static str = "audio_settings";
+    const VERSION: u32 = 1;
+
+    fn migrate(from_version: u32, _payload: Value) -> Result<Value, String> {
+        Err(format!("no migration from version {from_version}"))
+    }
+}
+
+pub fn clamp_volume(value: f32) -> f32 {
+    if !value.is_finite() {
+        return DEFAULT_VOLUME;
+    }
+    value.clamp(0.0, 1.0)
+}
+
+/// Turns what changed since the previous frame into sound cues.
+/// The first frame of a run, and any restart, only catches up without playing anything.
+#[derive(Default)]
+pub struct SoundHooks {
+    synced: Option<SyncPoint>,
+}
+
+#[derive(Clone, Copy)]
+struct SyncPoint {
+    logged: u64,
+    floor_index: u8,
+    finished: bool,
+}
+
+impl SoundHooks {
+    pub fn next_cues(&mut self, game: &Game, mode: &AppMode) -> Vec<SoundCue> {
+        let now = SyncPoint {
+            logged: game.log().total_logged(),
+            floor_index: game.state().floor_index,
+            finished: matches!(mode, AppMode::Finished(_)),
+        };
+        let Some(last) = self.synced.replace(now).filter(|last| last.logged <= now.logged) else {
+            return Vec::new();
+        };
+
+        let mut cues: Vec<SoundCue> =
+            game.log().since(last.logged).filter_map(cue_for_event).collect();
+        if now.floor_index > last.floor_index {
+            cues.push(SoundCue::Descend);
+        }
+        if !last.finished
+            && let AppMode::Finished(AppCompletion::Outcome(RunOutcome::Defeat(_))) = mode
+        {
+            cues.push(SoundCue::Death);
+        }
+        cues.sort();
+        cues.dedup();
+        cues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{
+        AdvanceStopReason, ContentPack, DeathCause, EntityId, GameMode, ItemKind, Pos,
+        STARTING_FLOOR_INDEX,
+    };
+
+    #[test]
+    fn new_log_events_map_to_cues_once_and_restarts_stay_silent() {
+        let mut game = Game::new(2711, &ContentPack::default(), GameMode::Ironman);
+        let mut hooks = SoundHooks::default();
+        game.push_log(LogEvent::DoorOpened { pos: Pos { y: 1, x: 1 } });
+        assert!(hooks.next_cues(&game, &AppMode::Paused).is_empty(), "first frame catches up");
+
+        game.push_log(LogEvent::ItemPickedUp { kind: ItemKind::Consumable("torch") });
+        game.push_log(LogEvent::EncounterResolved { enemy: EntityId::default(), fought: true });
+        game.push_log(LogEvent::EncounterResolved { enemy: EntityId::default(), fought: false });
+        game.push_log(LogEvent::DoorOpened { pos: Pos { y: 1, x: 2 } });
+        assert_eq!(
+            hooks.next_cues(&game, &AppMode::Paused),
+            vec![SoundCue::Hit, SoundCue::Door, SoundCue::Pickup]
+        );
+        assert!(hooks.next_cues(&game, &AppMode::Paused).is_empty());
+
+        let dead =
+            AppMode::Finished(AppCompletion::Outcome(RunOutcome::Defeat(DeathCause::Poison)));
+        assert_eq!(hooks.next_cues(&game, &dead), vec![SoundCue::Death]);
+        assert!(hooks.next_cues(&game, &dead).is_empty());
+
+        let restarted = Game::new(2711, &ContentPack::default(), GameMode::Ironman);
+        assert!(hooks.next_cues(&restarted, &AppMode::Paused).is_empty());
+    }
+
+    #[test]
+    fn descending_plays_the_descend_cue() {
+        let mut game = Game::new(2711, &ContentPack::default(), GameMode::Ironman);
+        let mut hooks = SoundHooks::default();
+        hooks.next_cues(&game, &AppMode::AutoPlay);
+        let mut heard = Vec::new();
+        while game.state().floor_index == STARTING_FLOOR_INDEX {
+            if let AdvanceStopReason::Interrupted(interrupt) = game.advance(20).stop_reason {
+                let choice = game.available_choices().remove(0);
+                game.apply_choice(interrupt.prompt_id(), choice).expect("first choice applies");
+            }
+            heard.extend(hooks.next_cues(&game, &AppMode::AutoPlay));
+        }
+        assert!(heard.contains(&SoundCue::Descend));
+    }
+
+    #[test]
+    fn settings_toggle_mute_and_clamp_volume() {
+        let mut settings = AudioSettings::default();
+        settings.apply(AudioAction::ToggleMute);
+        assert_eq!(settings.effective_volume(), 0.0);
+        assert_eq!(settings.notice(), "Sound muted");
+
+        settings.apply(AudioAction::ToggleMute);
+        for _ in 0..5 {
+            settings.apply(AudioAction::VolumeUp);
+        }
+        assert_eq!(settings.effective_volume(), 1.0);
+        assert_eq!(settings.notice(), "Sound on (volume 100%)");
+        for _ in 0..12 {
+            settings.apply(AudioAction::VolumeDown);
+        }
+        assert_eq!(settings.effective_volume(), 0.0);
+    }
+
+    #[test]
+    fn cue_tones_are_well_formed_wav_files() {
+        for cue in SoundCue::ALL {
+            let bytes = cue.wav_bytes();
+            assert_eq!(&bytes[..4], b"RIFF");
+            assert_eq!(&bytes[8..16], b"WAVEfmt ");
+            let data_len = u32::from_le_bytes(bytes[40..44].try_into().unwrap()) as usize;
+            assert_eq!(bytes.len(), 44 + data_len);
+        }
+    }
+}
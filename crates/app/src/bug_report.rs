@@ -0,0 +1,125 @@
+//! Automatic bug-report bundles written when the engine fails mid-run.
+//! A bundle is a directory holding a JSON state dump and a copy of the run journal.
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{APP_NAME, engine_failure_code, format_snapshot_hash, get_current_unix_ms};
+use core::{EngineFailureReason, Game, damaged_tail_path};
+
+/// State dump stored as `state.json` inside a bug-report bundle.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BugReportState {
+    pub format_version: u32,
+    pub reason_code: String,
+    pub run_seed: u64,
+    pub difficulty: String,
+    pub tick: u64,
+    pub floor_index: u8,
+    pub player_x: i32,
+    pub player_y: i32,
+    pub last_intent: String,
+    pub reachable_frontier_count: usize,
+    pub snapshot_hash_hex: String,
+    pub created_at_unix_ms: u64,
+}
+
+impl BugReportState {
+    pub fn from_failure(game: &Game, reason: &EngineFailureReason) -> Self {
+        let diagnostics = reason.diagnostics();
+        Self {
+            format_version: 1,
+            reason_code: engine_failure_code(reason).to_string(),
+            run_seed: game.seed(),
+            difficulty: format!("{:?}", game.difficulty()),
+            tick: diagnostics.tick,
+            floor_index: diagnostics.floor_index,
+            player_x: diagnostics.player_pos.x,
+            player_y: diagnostics.player_pos.y,
+            last_intent: format!("{:?}", diagnostics.last_intent),
+            reachable_frontier_count: diagnostics.reachable_frontier_count,
+            snapshot_hash_hex: format_snapshot_hash(diagnostics.snapshot_hash),
+            created_at_unix_ms: get_current_unix_ms(),
+        }
+    }
+}
+
+/// OS-idiomatic directory that holds one subdirectory per bug-report bundle.
+pub fn get_default_bug_report_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
+        let mut path = proj_dirs.data_dir().to_path_buf();
+        path.push("bug_reports");
+        path
+    })
+}
+
+/// Write `state.json` plus copies of the journal and any damaged tail cut from it (when
+/// present) into a new bundle directory.
+pub fn write_bug_report_bundle(
+    root: &Path,
+    state: &BugReportState,
+    journal_path: Option<&Path>,
+) -> io::Result<PathBuf> {
+    let bundle_dir = root
+        .join(format!("{}-seed{}-tick{}", state.created_at_unix_ms, state.run_seed, state.tick));
+    fs::create_dir_all(&bundle_dir)?;
+
+    let json = serde_json::to_string_pretty(state).map_err(io::Error::other)?;
+    fs::write(bundle_dir.join("state.json"), json)?;
+    if let Some(journal) = journal_path.filter(|path| path.exists()) {
+        fs::copy(journal, bundle_dir.join("journal.jsonl"))?;
+        let damaged = damaged_tail_path(journal);
+        if damaged.exists() {
+            fs::copy(damaged, bundle_dir.join("journal.jsonl.damaged"))?;
+        }
+    }
+
+    Ok(bundle_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{ContentPack, GameMode};
+    use tempfile::tempdir;
+
+    #[test]
+    fn bundle_contains_state_dump_and_journal_copy() {
+        let dir = tempdir().unwrap();
+        let journal = dir.path().join("journal.jsonl");
+        fs::write(&journal, "{\"header\":true}\n").unwrap();
+        fs::write(damaged_tail_path(&journal), "{\"seq\":1,").unwrap();
+
+        let game = Game::new(4242, &ContentPack::default(), GameMode::Ironman);
+        let reason = EngineFailureReason::StalledNoProgress(game.engine_diagnostics());
+        let state = BugReportState::from_failure(&game, &reason);
+        assert_eq!(state.reason_code, "ENG_STALLED_NO_PROGRESS");
+
+        let bundle = write_bug_report_bundle(&dir.path().join("reports"), &state, Some(&journal))
+            .expect("bundle should be written");
+        let stored: BugReportState =
+            serde_json::from_str(&fs::read_to_string(bundle.join("state.json")).unwrap()).unwrap();
+        assert_eq!(stored, state);
+        assert_eq!(
+            fs::read_to_string(bundle.join("journal.jsonl")).unwrap(),
+            "{\"header\":true}\n"
+        );
+        let damaged = fs::read_to_string(bundle.join("journal.jsonl.damaged")).unwrap();
+        assert_eq!(damaged, "{\"seq\":1,");
+    }
+
+    #[test]
+    fn bundle_without_journal_still_writes_state() {
+        let dir = tempdir().unwrap();
+        let game = Game::new(7, &ContentPack::default(), GameMode::Ironman);
+        let reason = EngineFailureReason::StalledNoProgress(game.engine_diagnostics());
+        let state = BugReportState::from_failure(&game, &reason);
+
+        let bundle = write_bug_report_bundle(dir.path(), &state, None).unwrap();
+        assert!(bundle.join("state.json").exists());
+        assert!(!bundle.join("journal.jsonl").exists());
+    }
+}
@@ -0,0 +1,40 @@
+//! Build identity embedded in the binary and stamped into journals and morgue files, so every
+//! artifact traces back to the code and content that produced it. The values come from the
+//! generated submodule that `cargo xtask build-info` writes.
+
+mod generated;
+
+pub use generated::BUILD_INFO;
+
+/// Which build produced an artifact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// `<app version>+<git hash>`, the id written into journal headers.
+    pub build_id: &'static str,
+    /// Short commit hash, `dev` for unstamped builds, with `-dirty` for uncommitted changes.
+    pub git_hash: &'static str,
+    pub app_version: &'static str,
+    pub core_version: &'static str,
+    /// `ContentPack::content_hash` of the default content at generation time.
+    pub content_hash: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ContentPack;
+
+    #[test]
+    fn generated_build_info_matches_this_tree() {
+        assert_eq!(BUILD_INFO.app_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            BUILD_INFO.content_hash,
+            ContentPack::default().content_hash(),
+            "content changed; run `cargo xtask build-info --reset`"
+        );
+        assert_eq!(
+            BUILD_INFO.build_id,
+            format!("{}+{}", BUILD_INFO.app_version, BUILD_INFO.git_hash)
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Generated by `cargo xtask build-info`; do not edit by hand.
+//! `cargo xtask build-info --reset` restores the committed `dev` stamp.
+
+use super::BuildInfo;
+
+pub const BUILD_INFO: BuildInfo = BuildInfo {
+    build_id: "0.1.0+dev",
+    git_hash: "dev",
+    app_version: "0.1.0",
+    core_version: "0.1.0",
+    content_hash: 0x7e4d92fa1ec3ad4d,
+};
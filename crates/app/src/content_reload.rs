@@ -0,0 +1,208 @@
+//! Dev-only content hot-reload: watch a directory of TOML files and apply display overrides
+//! (glyph, color and name per actor kind, item category or tile kind) to the running frontend.
+//! Only display data is applied live; simulation content still needs a restart.
+
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use core::content::{DisplayData, GlyphColor, GlyphStyle};
+use directories::ProjectDirs;
+use serde::Deserialize;
+
+use crate::APP_NAME;
+
+/// Frames between directory scans; scanning every frame would stat the files 60 times a second.
+const POLL_EVERY_FRAMES: u32 = 30;
+
+/// One overridden entry; `kind` is the Rust variant name, e.g. `"FeralHound"` or `"Wall"`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StyleOverride {
+    pub kind: String,
+    pub glyph: Option<String>,
+    pub color: Option<String>,
+    pub name: Option<String>,
+}
+
+/// Contents of one display override file.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct DisplayOverrides {
+    pub actors: Vec<StyleOverride>,
+    pub items: Vec<StyleOverride>,
+    pub tiles: Vec<StyleOverride>,
+}
+
+impl DisplayOverrides {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|error| error.to_string())
+    }
+
+    /// Apply every override to `display`, returning how many entries changed.
+    pub fn apply(&self, display: &mut DisplayData) -> Result<usize, String> {
+        let mut applied = 0;
+        applied += apply_styles(&mut display.actors, &self.actors)?;
+        applied += apply_styles(&mut display.items, &self.items)?;
+        applied += apply_styles(&mut display.tiles, &self.tiles)?;
+        Ok(applied)
+    }
+}
+
+fn apply_styles<K: Debug>(
+    styles: &mut [GlyphStyle<K>],
+    overrides: &[StyleOverride],
+) -> Result<usize, String> {
+    for entry in overrides {
+        let style = styles
+            .iter_mut()
+            .find(|style| format!("{:?}", style.kind) == entry.kind)
+            .ok_or_else(|| format!("unknown kind {}", entry.kind))?;
+        if let Some(color) = &entry.color {
+            style.color = parse_color(color)?;
+        }
+        // Display strings are `'static`; a reload leaks its few strings, fine for a dev loop.
+        if let Some(glyph) = &entry.glyph {
+            style.glyph = Box::leak(glyph.clone().into_boxed_str());
+        }
+        if let Some(name) = &entry.name {
+            style.name = Box::leak(name.clone().into_boxed_str());
+        }
+    }
+    Ok(overrides.len())
+}
+
+fn parse_color(name: &str) -> Result<GlyphColor, String> {
+    let color = match name.to_lowercase().as_str() {
+        "white" => GlyphColor::White,
+        "gray" => GlyphColor::Gray,
+        "lightgray" => GlyphColor::LightGray,
+        "green" => GlyphColor::Green,
+        "red" => GlyphColor::Red,
+        "orange" => GlyphColor::Orange,
+        "yellow" => GlyphColor::Yellow,
+        "gold" => GlyphColor::Gold,
+        "blue" => GlyphColor::Blue,
+        "skyblue" => GlyphColor::SkyBlue,
+        "purple" => GlyphColor::Purple,
+        "magenta" => GlyphColor::Magenta,
+        _ => return Err(format!("unknown color {name}")),
+    };
+    Ok(color)
+}
+
+/// Polls a content directory and reapplies its overrides whenever a `.toml` file changes.
+pub struct ContentWatcher {
+    dir: PathBuf,
+    /// Display data before any override, so removing an override reverts it.
+    base: DisplayData,
+    last_scan: Vec<(PathBuf, Option<SystemTime>, u64)>,
+    frames_until_poll: u32,
+}
+
+impl ContentWatcher {
+    pub fn new(dir: PathBuf, base: DisplayData) -> Self {
+        Self { dir, base, last_scan: Vec::new(), frames_until_poll: 0 }
+    }
+
+    pub fn get_default_dir() -> Option<PathBuf> {
+        ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
+            let mut path = proj_dirs.data_dir().to_path_buf();
+            path.push("content");
+            path
+        })
+    }
+
+    /// Call once per frame; returns a notice for the log when the directory changed.
+    pub fn reload_into(&mut self, display: &mut DisplayData) -> Option<String> {
+        if self.frames_until_poll > 0 {
+            self.frames_until_poll -= 1;
+            return None;
+        }
+        self.frames_until_poll = POLL_EVERY_FRAMES;
+        let scan = scan_toml_files(&self.dir);
+        if scan == self.last_scan {
+            return None;
+        }
+        self.last_scan = scan;
+
+        let mut reloaded = self.base.clone();
+        let mut applied = 0;
+        for (path, _, _) in &self.last_scan {
+            let result = fs::read_to_string(path)
+                .map_err(|error| error.to_string())
+                .and_then(|text| DisplayOverrides::parse(&text))
+                .and_then(|overrides| overrides.apply(&mut reloaded));
+            match result {
+                Ok(count) => applied += count,
+                Err(message) => {
+                    return Some(format!("Content reload failed in {}: {message}", path.display()));
+                }
+            }
+        }
+        *display = reloaded;
+        Some(format!("Content reloaded: {applied} display overrides"))
+    }
+}
+
+/// `.toml` files in `dir` with their modification time and size, sorted by path.
+fn scan_toml_files(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut scan: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+        .map(|path| {
+            let metadata = fs::metadata(&path).ok();
+            let modified = metadata.as_ref().and_then(|metadata| metadata.modified().ok());
+            let len = metadata.map_or(0, |metadata| metadata.len());
+            (path, modified, len)
+        })
+        .collect();
+    scan.sort();
+    scan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ActorKind;
+    use tempfile::tempdir;
+
+    #[test]
+    fn overrides_apply_and_unknown_kinds_are_reported() {
+        let mut display = DisplayData::default();
+        let overrides = DisplayOverrides::parse(
+            "[[actors]]\nkind = \"Goblin\"\nglyph = \"k\"\ncolor = \"gold\"\n\n\
+             [[tiles]]\nkind = \"Wall\"\nname = \"Rock\"\n",
+        )
+        .unwrap();
+        assert_eq!(overrides.apply(&mut display), Ok(2));
+        let goblin = display.actor(ActorKind::Goblin).unwrap();
+        assert_eq!((goblin.glyph, goblin.color, goblin.name), ("k", GlyphColor::Gold, "Goblin"));
+
+        let unknown = DisplayOverrides::parse("[[items]]\nkind = \"Scroll\"\n").unwrap();
+        assert!(unknown.apply(&mut display).is_err());
+    }
+
+    #[test]
+    fn watcher_reloads_on_change_and_reverts_removed_overrides() {
+        let dir = tempdir().unwrap();
+        let mut watcher = ContentWatcher::new(dir.path().to_path_buf(), DisplayData::default());
+        let mut display = DisplayData::default();
+        assert_eq!(watcher.reload_into(&mut display), None, "an empty directory is no change");
+
+        let file = dir.path().join("display.toml");
+        fs::write(&file, "[[actors]]\nkind = \"Player\"\nglyph = \"P\"\n").unwrap();
+        watcher.frames_until_poll = 0;
+        assert!(watcher.reload_into(&mut display).is_some());
+        assert_eq!(display.actor(ActorKind::Player).unwrap().glyph, "P");
+
+        fs::remove_file(&file).unwrap();
+        watcher.frames_until_poll = 0;
+        assert!(watcher.reload_into(&mut display).is_some());
+        assert_eq!(display, DisplayData::default());
+    }
+}
@@ -0,0 +1,90 @@
+//! Debug overlay listing engine internals, toggled with F3 and drawn over the map panel.
+//! The engine reports cumulative counters; this module turns them into per-frame figures.
+
+use core::{EngineInternals, PlannerPass};
+
+#[derive(Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+    last_fov_recomputes: Option<u64>,
+    frame_fov_recomputes: u64,
+}
+
+impl DebugOverlay {
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Record this frame's counters; call once per frame after the simulation step.
+    pub fn observe(&mut self, internals: &EngineInternals) {
+        self.frame_fov_recomputes = self
+            .last_fov_recomputes
+            .map_or(0, |last| internals.fov_recomputes.saturating_sub(last));
+        self.last_fov_recomputes = Some(internals.fov_recomputes);
+    }
+
+    pub fn lines(&self, internals: &EngineInternals) -> Vec<String> {
+        let pass = match internals.planner_pass {
+            Some(PlannerPass::Safe) => "safe",
+            Some(PlannerPass::HazardFallback) => "hazard fallback",
+            None => "-",
+        };
+        let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        vec![
+            "DEBUG (F3)".to_string(),
+            format!("no_progress_ticks: {}", internals.no_progress_ticks),
+            format!(
+                "suppressed enemy: {}",
+                or_dash(internals.suppressed_enemy.map(|enemy| format!("{enemy:?}")))
+            ),
+            format!(
+                "pending prompt: {}",
+                or_dash(internals.pending_prompt_id.map(|prompt| prompt.0.to_string()))
+            ),
+            format!("planner pass: {pass}"),
+            format!("last A* expansions: {}", internals.last_astar_expansions),
+            format!("FOV recomputes this frame: {}", self.frame_fov_recomputes),
+            format!("target scans: {}", internals.target_scans),
+            format!("path cache hits: {}", internals.path_cache_hits),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ChoicePromptId;
+
+    fn internals(fov_recomputes: u64) -> EngineInternals {
+        EngineInternals {
+            no_progress_ticks: 3,
+            suppressed_enemy: None,
+            pending_prompt_id: Some(ChoicePromptId(7)),
+            planner_pass: Some(PlannerPass::HazardFallback),
+            last_astar_expansions: 42,
+            fov_recomputes,
+            target_scans: 5,
+            path_cache_hits: 9,
+        }
+    }
+
+    #[test]
+    fn overlay_reports_fov_recomputes_per_frame() {
+        let mut overlay = DebugOverlay::default();
+        overlay.observe(&internals(100));
+        assert!(overlay.lines(&internals(100)).contains(&"FOV recomputes this frame: 0".into()));
+
+        overlay.observe(&internals(104));
+        let lines = overlay.lines(&internals(104));
+        assert!(lines.contains(&"FOV recomputes this frame: 4".to_string()));
+        assert!(lines.contains(&"pending prompt: 7".to_string()));
+        assert!(lines.contains(&"suppressed enemy: -".to_string()));
+        assert!(lines.contains(&"planner pass: hazard fallback".to_string()));
+        assert!(lines.contains(&"last A* expansions: 42".to_string()));
+        assert!(lines.contains(&"target scans: 5".to_string()));
+        assert!(lines.contains(&"path cache hits: 9".to_string()));
+
+        overlay.observe(&internals(1));
+        assert!(overlay.lines(&internals(1)).contains(&"FOV recomputes this frame: 0".into()));
+    }
+}
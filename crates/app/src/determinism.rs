@@ -0,0 +1,129 @@
+//! Background determinism check for finished runs: a worker thread re-simulates the run's
+//! journal and compares its reproducibility report with the live game's. A match earns
+//! the recap's "verified deterministic" badge.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use core::{
+    ContentPack, Game, ReproducibilityReport, load_journal_from_file, replay_reproducibility_report,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeterminismStatus {
+    Checking,
+    Verified,
+    /// The re-simulation failed or disagreed; the text says where.
+    Mismatch(String),
+}
+
+pub struct DeterminismCheck {
+    status: DeterminismStatus,
+    receiver: Receiver<DeterminismStatus>,
+}
+
+impl DeterminismCheck {
+    /// Start re-simulating the journal at `journal_path` against `game`'s report.
+    /// The worker replays with the default content pack, the one the app plays with.
+    pub fn spawn(journal_path: PathBuf, content: &ContentPack, game: &Game) -> Self {
+        let report = game.reproducibility_report(content);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let status = check_journal(&journal_path, &ContentPack::default(), &report);
+            // The app may have moved on to a new run and dropped the receiver.
+            let _ = sender.send(status);
+        });
+        Self { status: DeterminismStatus::Checking, receiver }
+    }
+
+    /// Pick up the worker's verdict once it arrives.
+    pub fn poll(&mut self) {
+        if let Ok(status) = self.receiver.try_recv() {
+            self.status = status;
+        }
+    }
+
+    pub fn status(&self) -> &DeterminismStatus {
+        &self.status
+    }
+
+    /// The recap line for the current status.
+    pub fn badge(&self) -> String {
+        match &self.status {
+            DeterminismStatus::Checking => "Determinism: re-simulating...".to_string(),
+            DeterminismStatus::Verified => "[VERIFIED DETERMINISTIC]".to_string(),
+            DeterminismStatus::Mismatch(reason) => format!("Determinism check failed: {reason}"),
+        }
+    }
+}
+
+/// Replay the journal at `path` and compare the result with `expected`.
+pub fn check_journal(
+    path: &Path,
+    content: &ContentPack,
+    expected: &ReproducibilityReport,
+) -> DeterminismStatus {
+    let loaded = match load_journal_from_file(path) {
+        Ok(loaded) => loaded,
+        Err(error) => return DeterminismStatus::Mismatch(format!("journal unreadable: {error}")),
+    };
+    match replay_reproducibility_report(content, &loaded.journal) {
+        Ok(replayed) => match expected.first_difference(&replayed) {
+            None => DeterminismStatus::Verified,
+            Some(field) => DeterminismStatus::Mismatch(format!("{field} differs")),
+        },
+        Err(error) => DeterminismStatus::Mismatch(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{AdvanceStopReason, Difficulty, GameMode, InputPayload, JournalWriter};
+    use tempfile::tempdir;
+
+    fn finished_run_journal(path: &Path) -> Game {
+        let content = ContentPack::default();
+        let mut game = Game::new(2733, &content, GameMode::Ironman);
+        let mut writer =
+            JournalWriter::create_for_run(path, 2733, Difficulty::Normal, false, "test", 0)
+                .unwrap();
+        loop {
+            match game.advance(200).stop_reason {
+                AdvanceStopReason::Interrupted(interrupt) => {
+                    let (prompt_id, choice) =
+                        (interrupt.prompt_id(), game.available_choices()[0].clone());
+                    let payload = InputPayload::Choice { prompt_id, choice: choice.clone() };
+                    writer.append(game.current_tick(), &payload).unwrap();
+                    game.apply_choice(prompt_id, choice).unwrap();
+                }
+                AdvanceStopReason::Finished(_) => return game,
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn background_check_verifies_a_faithful_journal_and_flags_drift() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let content = ContentPack::default();
+        let game = finished_run_journal(&path);
+
+        let mut check = DeterminismCheck::spawn(path.clone(), &content, &game);
+        while *check.status() == DeterminismStatus::Checking {
+            thread::yield_now();
+            check.poll();
+        }
+        assert_eq!(check.status(), &DeterminismStatus::Verified);
+        assert_eq!(check.badge(), "[VERIFIED DETERMINISTIC]");
+
+        let mut drifted = game.reproducibility_report(&content);
+        drifted.input_count += 1;
+        assert_eq!(
+            check_journal(&path, &content, &drifted),
+            DeterminismStatus::Mismatch("input count differs".to_string())
+        );
+    }
+}
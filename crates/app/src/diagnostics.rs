@@ -0,0 +1,73 @@
+//! Diagnostics persistence for the binary: the last-run state file, bug reports and morgues.
+//! Each writer reports its outcome as a log notice instead of interrupting the run.
+
+use app::{
+    app_loop::AppCompletion,
+    bug_report::{BugReportState, write_bug_report_bundle},
+    morgue::{MorgueFile, write_morgue_file},
+    run_state_file::RunStateFile,
+};
+use core::{ContentPack, EngineFailureReason, Game, LogEvent};
+use std::path::PathBuf;
+
+pub fn load_recovery_hint(diagnostics_path: &Option<PathBuf>) -> (Option<u64>, Option<LogEvent>) {
+    let Some(path) = diagnostics_path else {
+        return (None, None);
+    };
+    match RunStateFile::load(path) {
+        Ok(state) => (
+            Some(state.run_seed),
+            Some(LogEvent::RecoveryHint {
+                seed: state.run_seed,
+                hash_hex: state.snapshot_hash_hex,
+            }),
+        ),
+        Err(error) => {
+            if !error.is_missing() {
+                eprintln!("Warning: ignoring last run state: {error}");
+            }
+            (None, None)
+        }
+    }
+}
+
+/// Write a bug-report bundle for an engine failure and describe where it went.
+pub fn write_engine_failure_report(
+    bug_report_dir: &Option<PathBuf>,
+    journal_path: &Option<PathBuf>,
+    game: &Game,
+    reason: &EngineFailureReason,
+) -> LogEvent {
+    let Some(dir) = bug_report_dir else {
+        return LogEvent::Notice("Engine failure: no data directory for a bug report".to_string());
+    };
+    let state = BugReportState::from_failure(game, reason);
+    match write_bug_report_bundle(dir, &state, journal_path.as_deref()) {
+        Ok(bundle) => LogEvent::Notice(format!("Bug report written to {}", bundle.display())),
+        Err(error) => LogEvent::Notice(format!("Warning: failed to write bug report: {error}")),
+    }
+}
+
+/// Write the finished run's morgue file and describe where it went.
+pub fn write_run_morgue(
+    morgue_dir: &Option<PathBuf>,
+    content: &ContentPack,
+    game: &Game,
+    completion: &AppCompletion,
+) -> LogEvent {
+    let Some(dir) = morgue_dir else {
+        return LogEvent::Notice("Run finished: no data directory for a morgue file".to_string());
+    };
+    match write_morgue_file(dir, &MorgueFile::from_run(content, game, completion)) {
+        Ok(path) => LogEvent::Notice(format!("Morgue written to {}", path.display())),
+        Err(error) => LogEvent::Notice(format!("Warning: failed to write morgue: {error}")),
+    }
+}
+
+pub fn persist_run_state(diagnostics_path: &Option<PathBuf>, game: &Game) {
+    let Some(path) = diagnostics_path else {
+        return;
+    };
+
+    let _ = RunStateFile::from_game(game).write_atomic(path);
+}
@@ -0,0 +1,78 @@
+//! Command-line selection of the run difficulty preset.
+
+use core::Difficulty;
+
+pub fn resolve_difficulty_from_args(args: &[String]) -> Result<Difficulty, String> {
+    let mut selected = None;
+    let mut index = 1usize;
+
+    while index < args.len() {
+        let argument = args[index].as_str();
+        let value = if argument == "--difficulty" {
+            let Some(value) = args.get(index + 1) else {
+                return Err("missing value for --difficulty".to_string());
+            };
+            index += 1;
+            Some(value.as_str())
+        } else {
+            argument.strip_prefix("--difficulty=")
+        };
+
+        if let Some(value) = value {
+            if selected.is_some() {
+                return Err("difficulty provided more than once".to_string());
+            }
+            selected = Some(parse_difficulty_value(value)?);
+        }
+        index += 1;
+    }
+
+    Ok(selected.unwrap_or_default())
+}
+
+fn parse_difficulty_value(raw_value: &str) -> Result<Difficulty, String> {
+    match raw_value.to_ascii_lowercase().as_str() {
+        "relaxed" => Ok(Difficulty::Relaxed),
+        "normal" => Ok(Difficulty::Normal),
+        "brutal" => Ok(Difficulty::Brutal),
+        _ => Err(format!("difficulty '{raw_value}' must be relaxed, normal, or brutal")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn as_args(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|part| part.to_string()).collect()
+    }
+
+    #[test]
+    fn defaults_to_normal_when_flag_is_absent() {
+        let args = as_args(&["game", "--seed", "7"]);
+        assert_eq!(resolve_difficulty_from_args(&args), Ok(Difficulty::Normal));
+    }
+
+    #[test]
+    fn parses_separate_and_inline_values() {
+        let args = as_args(&["game", "--difficulty", "brutal"]);
+        assert_eq!(resolve_difficulty_from_args(&args), Ok(Difficulty::Brutal));
+        let args = as_args(&["game", "--difficulty=Relaxed"]);
+        assert_eq!(resolve_difficulty_from_args(&args), Ok(Difficulty::Relaxed));
+    }
+
+    #[test]
+    fn rejects_unknown_missing_and_duplicate_values() {
+        let unknown = resolve_difficulty_from_args(&as_args(&["game", "--difficulty=hard"]));
+        assert!(unknown.is_err_and(|err| err.contains("relaxed, normal, or brutal")));
+        let missing = resolve_difficulty_from_args(&as_args(&["game", "--difficulty"]));
+        assert!(missing.is_err_and(|err| err.contains("missing")));
+        let duplicate = resolve_difficulty_from_args(&as_args(&[
+            "game",
+            "--difficulty=normal",
+            "--difficulty",
+            "brutal",
+        ]));
+        assert!(duplicate.is_err_and(|err| err.contains("more than once")));
+    }
+}
@@ -0,0 +1,114 @@
+//! Player-facing names for actors, items and vaults, shared by every frontend.
+//! Consumable names go through the game so unidentified potions keep their disguise.
+
+use core::content::DisplayData;
+use core::{
+    ActorKind, Ambience, ContentPack, DamageSource, Game, ItemKind, VaultTemplate, VictoryKind,
+};
+
+/// What the pack's display data calls `kind`; a kind it has no entry for is "someone".
+pub fn actor_name(display: &DisplayData, kind: ActorKind) -> &'static str {
+    display.actor(kind).map_or("someone", |style| style.name)
+}
+
+pub fn vault_name(template: VaultTemplate) -> &'static str {
+    match template {
+        VaultTemplate::ShrineRoom => "Shrine Room",
+        VaultTemplate::GoblinCamp => "Goblin Camp",
+        VaultTemplate::PillarRoom => "Pillar Room",
+    }
+}
+
+pub fn victory_name(kind: VictoryKind) -> &'static str {
+    match kind {
+        VictoryKind::FullClear => "full clear, every floor explored and the boss slain",
+        VictoryKind::SpeedClear => "speed clear, the boss slain with ground left unexplored",
+        VictoryKind::BossSkip => "boss skipped, the final boss still stands",
+    }
+}
+
+/// The status-bar label for a floor's ambience; a still floor shows none.
+pub fn ambience_name(ambience: Ambience) -> Option<&'static str> {
+    match ambience {
+        Ambience::Still => None,
+        Ambience::Fog => Some("Fog"),
+        Ambience::Echoes => Some("Echoes"),
+        Ambience::Tremors => Some("Tremors"),
+    }
+}
+
+pub fn damage_source_name(source: DamageSource) -> &'static str {
+    match source {
+        DamageSource::Melee => "wounds",
+        DamageSource::Ranged => "arrows",
+        DamageSource::Poison => "poison",
+        DamageSource::Hazard => "a hazard",
+        DamageSource::Starvation => "starvation",
+        DamageSource::Curse => "a curse",
+    }
+}
+
+pub fn item_name(content: &ContentPack, game: &Game, kind: ItemKind) -> &'static str {
+    match kind {
+        ItemKind::Weapon(key) => content_name(content.weapons.iter().map(|w| (w.id, w.name)), key),
+        ItemKind::Consumable(key) => {
+            let label = game.consumable_label(key);
+            if label == key {
+                content_name(content.consumables.iter().map(|c| (c.id, c.name)), key)
+            } else {
+                label
+            }
+        }
+        ItemKind::Perk(key) => perk_name(content, key),
+    }
+}
+
+pub fn perk_name(content: &ContentPack, key: &'static str) -> &'static str {
+    content_name(content.perks.iter().map(|p| (p.id, p.name)), key)
+}
+
+fn content_name(
+    mut entries: impl Iterator<Item = (&'static str, &'static str)>,
+    key: &'static str,
+) -> &'static str {
+    entries.find(|(id, _)| *id == key).map_or(key, |(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::GameMode;
+    use core::content::keys;
+
+    #[test]
+    fn items_use_content_names_and_hide_unidentified_potions() {
+        let content = ContentPack::default();
+        let game = Game::new(3, &content, GameMode::Ironman);
+        assert_eq!(
+            item_name(&content, &game, ItemKind::Weapon(keys::WEAPON_IRON_MACE)),
+            "Iron Mace"
+        );
+        assert_eq!(item_name(&content, &game, ItemKind::Perk(keys::PERK_SCOUT)), "Scout");
+        assert_eq!(
+            item_name(&content, &game, ItemKind::Consumable(keys::CONSUMABLE_SMOKE_BOMB)),
+            "Smoke Bomb"
+        );
+
+        let potion = keys::CONSUMABLE_HASTE_POTION;
+        let disguised = item_name(&content, &game, ItemKind::Consumable(potion));
+        assert_eq!(disguised, game.state().potion_appearances[potion]);
+    }
+
+    #[test]
+    fn actor_names_come_from_the_display_data() {
+        let mut display = DisplayData::default();
+        assert_eq!(actor_name(&display, ActorKind::FeralHound), "Feral Hound");
+
+        let hound = display.actors.iter_mut().find(|style| style.kind == ActorKind::FeralHound);
+        hound.expect("hounds have a display entry").name = "Dire Wolf";
+        assert_eq!(actor_name(&display, ActorKind::FeralHound), "Dire Wolf");
+
+        display.actors.clear();
+        assert_eq!(actor_name(&display, ActorKind::Goblin), "someone");
+    }
+}
@@ -0,0 +1,73 @@
+//! Enemy encyclopedia text, opened with F2: one entry per bestiary kind, with stats, danger
+//! tags and spawn floors shown only for kinds the player profile has met.
+
+use core::ContentPack;
+use core::content::{BestiaryEntry, bestiary};
+
+use crate::profile::PlayerProfile;
+
+/// Lines for the encyclopedia screen: a header, then one line per enemy kind.
+pub fn encyclopedia_lines(profile: &PlayerProfile, content: &ContentPack) -> Vec<String> {
+    let entries = bestiary(content);
+    let known = entries.iter().filter(|entry| profile.encountered.contains(&entry.kind)).count();
+    let mut lines =
+        vec![format!("Encyclopedia: {known}/{} enemies met (F2 to close)", entries.len())];
+    lines.extend(entries.iter().map(|entry| {
+        if profile.encountered.contains(&entry.kind) {
+            let name = content.display.actor(entry.kind).map_or("?", |style| style.name);
+            known_entry_line(name, entry)
+        } else {
+            "??? (not yet encountered)".to_string()
+        }
+    }));
+    lines
+}
+
+fn known_entry_line(name: &str, entry: &BestiaryEntry) -> String {
+    let stats = entry.stats;
+    let tags: Vec<String> = entry.danger_tags.iter().map(|tag| format!("{tag:?}")).collect();
+    let floors: Vec<String> = entry
+        .floors
+        .iter()
+        .map(|chance| format!("F{} {}%", chance.floor, chance.percent))
+        .collect();
+    let mut floors = floors.join(", ");
+    if floors.is_empty() {
+        floors = "none".to_string();
+    }
+    let boss_of: Vec<String> = entry.boss_of.iter().map(|branch| format!("{branch:?}")).collect();
+    let boss_of =
+        if boss_of.is_empty() { String::new() } else { format!("; boss of {}", boss_of.join("/")) };
+    format!(
+        "{name}: HP {} ATK {} DEF {} SPD {} [{}] floors {}{boss_of}",
+        stats.hp,
+        stats.attack,
+        stats.defense,
+        stats.speed,
+        tags.join(", "),
+        floors
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ActorKind;
+
+    #[test]
+    fn only_met_kinds_show_their_details() {
+        let mut profile = PlayerProfile::default();
+        profile.encountered.insert(ActorKind::Goblin);
+        let content = ContentPack::default();
+        let lines = encyclopedia_lines(&profile, &content);
+
+        assert_eq!(lines[0], "Encyclopedia: 1/10 enemies met (F2 to close)");
+        assert!(lines[1].contains("HP 10 ATK 2 DEF 0 SPD 12"), "{}", lines[1]);
+        assert!(lines[1].ends_with("floors F1 60%"), "{}", lines[1]);
+        assert!(lines[2..].iter().all(|line| line.starts_with("???")));
+
+        profile.encountered.insert(ActorKind::HollowKing);
+        let lines = encyclopedia_lines(&profile, &content);
+        assert!(lines[10].ends_with("floors none; boss of BranchB"), "{}", lines[10]);
+    }
+}
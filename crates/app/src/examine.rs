@@ -0,0 +1,80 @@
+//! Examining a map tile: the visible enemy standing there and, once this profile has killed
+//! one of its kind, its danger tags from the bestiary.
+
+use core::content::{BestiaryEntry, DangerTag};
+use core::{ActorKind, ContentPack, GameState, Pos};
+
+use crate::display_name::actor_name;
+use crate::profile::PlayerProfile;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Examined {
+    pub kind: ActorKind,
+    pub hp: i32,
+    pub max_hp: i32,
+    /// `None` until this profile has killed an enemy of this kind.
+    pub danger_tags: Option<Vec<DangerTag>>,
+}
+
+/// What examining `pos` shows, if a visible enemy stands there.
+pub fn examine(
+    content: &ContentPack,
+    state: &GameState,
+    profile: &PlayerProfile,
+    pos: Pos,
+) -> Option<Examined> {
+    if !state.map.is_visible(pos) {
+        return None;
+    }
+    let (_, actor) =
+        state.actors.iter().find(|(id, actor)| *id != state.player_id && actor.pos == pos)?;
+    let danger_tags = profile
+        .slain
+        .contains(&actor.kind)
+        .then(|| BestiaryEntry::of(content, actor.kind).danger_tags);
+    Some(Examined { kind: actor.kind, hp: actor.hp, max_hp: actor.max_hp, danger_tags })
+}
+
+/// One line for the examine box.
+pub fn examine_line(content: &ContentPack, examined: &Examined) -> String {
+    let name = actor_name(&content.display, examined.kind);
+    let dangers = match &examined.danger_tags {
+        Some(tags) if tags.is_empty() => "no known dangers".to_string(),
+        Some(tags) => tags.iter().map(|tag| format!("{tag:?}")).collect::<Vec<_>>().join(", "),
+        None => "kill one to learn its dangers".to_string(),
+    };
+    format!("{name} HP {}/{} ({dangers})", examined.hp, examined.max_hp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{Game, GameMode};
+
+    #[test]
+    fn danger_tags_show_only_for_kinds_this_profile_has_killed() {
+        let content = ContentPack::default();
+        let game = Game::new(2760, &content, GameMode::Ironman);
+        let mut state = game.state().clone();
+        state.map.visible.fill(true);
+        let player = state.player_id;
+        let hound = state.actors.keys().find(|id| *id != player).expect("floor one has enemies");
+        state.actors[hound].kind = ActorKind::FeralHound;
+        let pos = state.actors[hound].pos;
+        let mut profile = PlayerProfile::default();
+
+        let unknown = examine(&content, &state, &profile, pos).expect("a hound stands there");
+        assert_eq!(unknown.danger_tags, None);
+        assert!(examine_line(&content, &unknown).ends_with("(kill one to learn its dangers)"));
+
+        profile.slain.insert(ActorKind::FeralHound);
+        let known = examine(&content, &state, &profile, pos).expect("a hound stands there");
+        assert_eq!(known.danger_tags, Some(vec![DangerTag::Fast]));
+        assert!(examine_line(&content, &known).starts_with("Feral Hound HP"));
+        assert!(examine_line(&content, &known).ends_with("(Fast)"));
+
+        state.map.set_visible(pos, false);
+        assert_eq!(examine(&content, &state, &profile, pos), None, "unseen enemies stay hidden");
+        assert_eq!(examine(&content, &state, &profile, state.actors[player].pos), None);
+    }
+}
@@ -1,11 +1,16 @@
 //! Keyboard input collection for one rendered frame.
 
+use std::iter;
+
+use app::audio::AudioAction;
 use app::ui_scale::UiScaleAction;
-use macroquad::prelude::{KeyCode, is_key_down, is_key_pressed};
+use macroquad::prelude::{KeyCode, get_char_pressed, is_key_down, is_key_pressed};
 
-const ACTION_KEYS: [KeyCode; 20] = [
+const ACTION_KEYS: [KeyCode; 34] = [
     KeyCode::L,
     KeyCode::D,
+    KeyCode::W,
+    KeyCode::S,
     KeyCode::F,
     KeyCode::A,
     KeyCode::B,
@@ -15,6 +20,7 @@ const ACTION_KEYS: [KeyCode; 20] = [
     KeyCode::Key4,
     KeyCode::O,
     KeyCode::C,
+    KeyCode::X,
     KeyCode::M,
     KeyCode::T,
     KeyCode::P,
@@ -24,6 +30,17 @@ const ACTION_KEYS: [KeyCode; 20] = [
     KeyCode::E,
     KeyCode::G,
     KeyCode::K,
+    KeyCode::N,
+    KeyCode::U,
+    KeyCode::Z,
+    KeyCode::Tab,
+    KeyCode::Enter,
+    KeyCode::Escape,
+    KeyCode::Backspace,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
 ];
 
 #[derive(Default)]
@@ -31,16 +48,24 @@ pub struct FrameInput {
     pub keys_pressed: Vec<KeyCode>,
     pub restart_with_recovered_seed: bool,
     pub ui_scale_action: Option<UiScaleAction>,
+    pub audio_action: Option<AudioAction>,
+    pub toggle_debug_overlay: bool,
+    pub toggle_glyph_legend: bool,
+    pub toggle_encyclopedia: bool,
+    /// Characters typed this frame, for the command palette.
+    pub typed_chars: Vec<char>,
 }
 
 pub fn capture_frame_input() -> FrameInput {
-    let mut keys_pressed = Vec::with_capacity(ACTION_KEYS.len() + 2);
+    let mut keys_pressed = Vec::with_capacity(ACTION_KEYS.len() + 5);
 
     if is_key_pressed(KeyCode::Space) {
         keys_pressed.push(KeyCode::Space);
     }
-    if is_key_pressed(KeyCode::Right) {
-        keys_pressed.push(KeyCode::Right);
+    for arrow in [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right] {
+        if is_key_pressed(arrow) {
+            keys_pressed.push(arrow);
+        }
     }
     for key in ACTION_KEYS {
         if is_key_pressed(key) {
@@ -63,5 +88,24 @@ pub fn capture_frame_input() -> FrameInput {
         None
     };
 
-    FrameInput { keys_pressed, restart_with_recovered_seed, ui_scale_action }
+    let audio_action = if ctrl_down && is_key_pressed(KeyCode::V) {
+        Some(AudioAction::ToggleMute)
+    } else if ctrl_down && is_key_pressed(KeyCode::Period) {
+        Some(AudioAction::VolumeUp)
+    } else if ctrl_down && is_key_pressed(KeyCode::Comma) {
+        Some(AudioAction::VolumeDown)
+    } else {
+        None
+    };
+
+    FrameInput {
+        keys_pressed,
+        restart_with_recovered_seed,
+        ui_scale_action,
+        audio_action,
+        toggle_debug_overlay: is_key_pressed(KeyCode::F3),
+        toggle_glyph_legend: is_key_pressed(KeyCode::F4),
+        toggle_encyclopedia: is_key_pressed(KeyCode::F2),
+        typed_chars: iter::from_fn(get_char_pressed).collect(),
+    }
 }
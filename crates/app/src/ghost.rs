@@ -0,0 +1,110 @@
+//! Ghost of a previous run, replayed from its journal in step with the live game.
+//! The ghost only exists for journals recorded with the same seed as the current run.
+
+use std::path::{Path, PathBuf};
+
+use core::{ContentPack, Game, Pos, ReplayStepper, load_journal_from_file};
+
+pub fn resolve_ghost_path_from_args(args: &[String]) -> Result<Option<PathBuf>, String> {
+    let mut selected = None;
+    let mut index = 1usize;
+
+    while index < args.len() {
+        let argument = args[index].as_str();
+        let value = if argument == "--ghost" {
+            let Some(value) = args.get(index + 1) else {
+                return Err("missing value for --ghost".to_string());
+            };
+            index += 1;
+            Some(value.as_str())
+        } else {
+            argument.strip_prefix("--ghost=")
+        };
+
+        if let Some(value) = value {
+            if selected.is_some() {
+                return Err("ghost journal provided more than once".to_string());
+            }
+            selected = Some(PathBuf::from(value));
+        }
+        index += 1;
+    }
+
+    Ok(selected)
+}
+
+pub struct GhostRun {
+    stepper: ReplayStepper,
+}
+
+impl GhostRun {
+    /// Load a journal to race against; it must have been recorded for `run_seed`.
+    pub fn load(path: &Path, content: &ContentPack, run_seed: u64) -> Result<Self, String> {
+        let loaded = load_journal_from_file(path).map_err(|e| format!("{e}"))?;
+        if loaded.journal.seed != run_seed {
+            return Err(format!(
+                "ghost journal seed {} does not match run seed {run_seed}",
+                loaded.journal.seed
+            ));
+        }
+        Ok(Self { stepper: ReplayStepper::new(content, &loaded.journal) })
+    }
+
+    /// Replay the ghost forward to the live game's tick.
+    pub fn sync_to(&mut self, tick: u64) -> Result<(), String> {
+        self.stepper.step_to_tick(tick).map_err(|e| format!("{e}"))
+    }
+
+    pub fn game(&self) -> &Game {
+        self.stepper.game()
+    }
+
+    /// The ghost's position, if it is on `floor_index` and has not finished its run.
+    pub fn player_pos_on_floor(&self, floor_index: u8) -> Option<Pos> {
+        let state = self.stepper.game().state();
+        let racing = state.floor_index == floor_index && !self.stepper.is_exhausted();
+        racing.then(|| state.actors[state.player_id].pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{GameMode, JournalWriter};
+    use tempfile::tempdir;
+
+    fn as_args(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|part| part.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_ghost_path_flags() {
+        assert_eq!(resolve_ghost_path_from_args(&as_args(&["game"])), Ok(None));
+        let args = as_args(&["game", "--ghost", "runs/a.jsonl"]);
+        assert_eq!(resolve_ghost_path_from_args(&args), Ok(Some(PathBuf::from("runs/a.jsonl"))));
+        let args = as_args(&["game", "--ghost=b.jsonl", "--ghost", "c.jsonl"]);
+        assert!(resolve_ghost_path_from_args(&args).is_err_and(|e| e.contains("more than once")));
+        let args = as_args(&["game", "--ghost"]);
+        assert!(resolve_ghost_path_from_args(&args).is_err_and(|e| e.contains("missing")));
+    }
+
+    #[test]
+    fn ghost_requires_matching_seed_and_follows_live_ticks() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("ghost.jsonl");
+        JournalWriter::create(&path, 555, "test", 0).unwrap();
+        let content = ContentPack::default();
+
+        assert!(GhostRun::load(&path, &content, 556).is_err_and(|e| e.contains("does not match")));
+
+        let mut ghost = GhostRun::load(&path, &content, 555).expect("same seed should load");
+        let mut live = Game::new(555, &content, GameMode::Ironman);
+        live.advance(3);
+        ghost.sync_to(live.current_tick()).unwrap();
+        assert_eq!(ghost.game().current_tick(), live.current_tick());
+        assert_eq!(
+            ghost.player_pos_on_floor(live.state().floor_index),
+            Some(live.state().actors[live.state().player_id].pos)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Glyph legend listing what is on screen right now, toggled with F4 beside the map.
+//! Entries come from the content pack's display data, the same table the map is drawn from,
+//! and enemy danger tags from the pack's enemy stats.
+
+use std::collections::BTreeSet;
+
+use core::content::{DangerTag, GlyphColor, ItemCategory, danger_tags};
+use core::{ContentPack, GameState};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LegendEntry {
+    pub glyph: &'static str,
+    pub color: GlyphColor,
+    pub label: String,
+}
+
+/// Entries for visible actors, then visible item categories, then discovered tile kinds,
+/// each in display-table order.
+pub fn legend_entries(state: &GameState, content: &ContentPack) -> Vec<LegendEntry> {
+    let display = &content.display;
+    let map = &state.map;
+    let actors: BTreeSet<_> = state
+        .actors
+        .values()
+        .filter(|actor| map.is_visible(actor.pos))
+        .map(|actor| actor.kind)
+        .collect();
+    let items: BTreeSet<_> = state
+        .items
+        .values()
+        .filter(|item| map.is_visible(item.pos))
+        .map(|item| ItemCategory::of(item.kind))
+        .collect();
+    let tiles: BTreeSet<_> =
+        map.positions().filter(|pos| map.is_discovered(*pos)).map(|pos| map.tile_at(pos)).collect();
+
+    let actor_entries =
+        display.actors.iter().filter(|style| actors.contains(&style.kind)).map(|style| {
+            let tags = content.enemies.get(&style.kind).map(danger_tags).unwrap_or_default();
+            let tags: Vec<&str> = tags.into_iter().map(danger_label).collect();
+            let label = if tags.is_empty() {
+                style.name.to_string()
+            } else {
+                format!("{} ({})", style.name, tags.join(", "))
+            };
+            LegendEntry { glyph: style.glyph, color: style.color, label }
+        });
+    let item_entries = display.items.iter().filter(|style| items.contains(&style.kind));
+    let tile_entries = display.tiles.iter().filter(|style| tiles.contains(&style.kind));
+    actor_entries
+        .chain(item_entries.map(|style| LegendEntry {
+            glyph: style.glyph,
+            color: style.color,
+            label: style.name.to_string(),
+        }))
+        .chain(tile_entries.map(|style| LegendEntry {
+            glyph: style.glyph,
+            color: style.color,
+            label: style.name.to_string(),
+        }))
+        .collect()
+}
+
+fn danger_label(tag: DangerTag) -> &'static str {
+    match tag {
+        DangerTag::Fast => "fast",
+        DangerTag::HardHitting => "hard-hitting",
+        DangerTag::Armored => "armored",
+        DangerTag::Boss => "boss",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{ActorKind, ContentPack, Game, GameMode};
+
+    #[test]
+    fn legend_lists_visible_enemies_with_the_packs_danger_tags_and_discovered_tiles() {
+        let content =
+            ContentPack::from_toml_str("[[enemies]]\nkind = \"FeralHound\"\ndefense = 3\n")
+                .expect("valid balance file");
+        let game = Game::new(2720, &content, GameMode::Ironman);
+        let mut state = game.state().clone();
+        for pos in state.map.positions().collect::<Vec<_>>() {
+            state.map.set_visible(pos, false);
+        }
+        let player = state.player_id;
+        state.map.set_visible(state.actors[player].pos, true);
+        let hound = state.actors.keys().find(|id| *id != player).expect("floor one has enemies");
+        state.actors[hound].kind = ActorKind::FeralHound;
+        state.actors[hound].pos = state.actors[player].pos;
+        state.items.clear();
+
+        let entries = legend_entries(&state, &content);
+        let labels: Vec<&str> = entries.iter().map(|entry| entry.label.as_str()).collect();
+        assert_eq!(labels[..2], ["You", "Feral Hound (fast, armored)"]);
+        assert_eq!(entries[1].glyph, "h");
+        assert!(labels.contains(&"Floor") && labels.contains(&"Wall"));
+        assert!(!labels.iter().any(|label| label.starts_with("Goblin")), "hidden enemies stay off");
+    }
+}
@@ -0,0 +1,71 @@
+//! Movement heatmaps built from the core run stats, for the finished screen and morgue files.
+//! Counts are bucketed into a few heat levels relative to the busiest tile on the floor.
+
+use core::{FloorVisits, Game};
+use serde::{Deserialize, Serialize};
+
+/// Glyph per heat level; level 0 is a tile the player never entered.
+const HEAT_GLYPHS: [char; 5] = [' ', '.', ':', '*', '#'];
+/// Highest heat level a visited tile can reach.
+pub const MAX_HEAT_LEVEL: u8 = (HEAT_GLYPHS.len() - 1) as u8;
+
+/// One floor's movement heatmap as stored in a morgue file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FloorHeatmap {
+    pub floor_index: u8,
+    /// Tile entries on this floor, the starting tile included.
+    pub total_visits: u32,
+    /// Entries into tiles already visited.
+    pub revisits: u32,
+    /// One string per map row, using ` .:*#` from unvisited to busiest.
+    pub rows: Vec<String>,
+}
+
+/// Heat level for `count` entries on a floor whose busiest tile saw `max_count`.
+pub fn heat_level(count: u32, max_count: u32) -> u8 {
+    if count == 0 || max_count == 0 {
+        return 0;
+    }
+    let scaled = (u64::from(count) * u64::from(MAX_HEAT_LEVEL)).div_ceil(u64::from(max_count));
+    scaled.clamp(1, u64::from(MAX_HEAT_LEVEL)) as u8
+}
+
+pub fn heatmap_rows(visits: &FloorVisits) -> Vec<String> {
+    let max_count = visits.max_count();
+    visits
+        .counts
+        .chunks(visits.width.max(1))
+        .map(|row| {
+            row.iter()
+                .map(|count| HEAT_GLYPHS[usize::from(heat_level(*count, max_count))])
+                .collect()
+        })
+        .collect()
+}
+
+/// Heatmaps for every floor the player walked, in floor order.
+pub fn run_heatmaps(game: &Game) -> Vec<FloorHeatmap> {
+    game.run_stats()
+        .floor_visits
+        .iter()
+        .map(|(floor_index, visits)| FloorHeatmap {
+            floor_index: *floor_index,
+            total_visits: visits.total(),
+            revisits: visits.revisits(),
+            rows: heatmap_rows(visits),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_bucket_counts_relative_to_the_busiest_tile() {
+        let visits = FloorVisits { width: 3, height: 2, counts: vec![0, 1, 2, 3, 4, 8] };
+        assert_eq!(heatmap_rows(&visits), vec![" ..".to_string(), "::#".to_string()]);
+        assert_eq!(heat_level(8, 8), MAX_HEAT_LEVEL);
+        assert_eq!(heat_level(1, 1000), 1, "a visited tile never reads as unvisited");
+    }
+}
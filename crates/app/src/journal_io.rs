@@ -0,0 +1,128 @@
+//! Journal file helpers for the binary: locating, creating, resuming, and replaying it.
+//! A journal left by a previous run is preserved on startup until the player replays or plays on.
+
+use app::APP_NAME;
+use app::build_info::BUILD_INFO;
+use app::run_bundle::{BundleRequest, RunBundle};
+use core::{
+    ContentPack, Game, JournalWriter, LogEvent, load_journal_from_file, recover_journal_from_file,
+    replay::replay_journal_inputs, truncate_to_valid_prefix,
+};
+use std::path::PathBuf;
+
+/// OS-idiomatic path for the journal file, alongside the diagnostics file.
+pub fn get_journal_path() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
+        let mut path = proj_dirs.data_dir().to_path_buf();
+        path.push("journal.jsonl");
+        path
+    })
+}
+
+/// Create a fresh journal file for a new run.
+pub fn create_journal_writer(path: &Option<PathBuf>, game: &Game) -> Option<JournalWriter> {
+    let path = path.as_ref()?;
+    let (seed, difficulty, director) = (game.seed(), game.difficulty(), game.director_enabled());
+    let (build_id, content_hash) = (BUILD_INFO.build_id, BUILD_INFO.content_hash);
+    match JournalWriter::create_for_run(path, seed, difficulty, director, build_id, content_hash) {
+        Ok(writer) => Some(writer),
+        Err(e) => {
+            eprintln!("Warning: could not create journal file: {e}");
+            None
+        }
+    }
+}
+
+/// On startup, preserve an existing non-empty journal so Shift+K replay can
+/// consume it without being truncated by a new run initialization.
+pub fn prepare_startup_journal_writer(
+    path: &Option<PathBuf>,
+    game: &Game,
+) -> (Option<JournalWriter>, bool) {
+    let Some(path_ref) = path.as_ref() else {
+        return (None, false);
+    };
+
+    if let Ok(recovered) = recover_journal_from_file(path_ref)
+        && recovered.salvaged_records > 0
+    {
+        return (None, true);
+    }
+
+    (create_journal_writer(path, game), false)
+}
+
+/// Resume appending to an existing journal after replay.
+pub fn resume_journal_writer(path: &Option<PathBuf>) -> Option<JournalWriter> {
+    let path = path.as_ref()?;
+    let loaded = load_journal_from_file(path).ok()?;
+    match JournalWriter::resume(path, loaded.last_sha256_hex, loaded.next_seq) {
+        Ok(writer) => Some(writer),
+        Err(e) => {
+            eprintln!("Warning: could not resume journal file: {e}");
+            None
+        }
+    }
+}
+
+/// Load and replay a journal file, salvaging the valid prefix of a damaged one.
+/// Returns the reconstructed Game and, if records were dropped, a note saying how
+/// many were kept. Only once the prefix replays is the damaged tail cut off, so the journal
+/// can be resumed; the cut bytes are kept in a `.damaged` file beside it for bug reports.
+pub fn try_replay_from_journal(
+    journal_path: &Option<PathBuf>,
+    content: &ContentPack,
+) -> Result<(Game, Option<String>), String> {
+    let path = journal_path.as_ref().ok_or_else(|| "journal path is unavailable".to_string())?;
+    let recovered = recover_journal_from_file(path).map_err(|e| format!("{e}"))?;
+    if recovered.loaded.journal.inputs.is_empty() {
+        return Err("journal has no recorded inputs".to_string());
+    }
+    let game =
+        replay_journal_inputs(content, &recovered.loaded.journal).map_err(|e| format!("{e}"))?;
+    let salvage_note = match &recovered.discarded_tail {
+        None => None,
+        Some(reason) => {
+            truncate_to_valid_prefix(path, &recovered).map_err(|e| format!("{e}"))?;
+            Some(format!(
+                "Salvaged {} journal records; dropped the rest: {reason}",
+                recovered.salvaged_records
+            ))
+        }
+    };
+    Ok((game, salvage_note))
+}
+
+/// Carry out a palette bundle request. An import validates the bundle and installs its journal,
+/// returning the run seed so the caller replays it exactly as Shift+K would.
+pub fn handle_bundle_request(
+    request: Option<BundleRequest>,
+    journal_path: &Option<PathBuf>,
+    game: &mut Game,
+    content: &ContentPack,
+) -> Option<u64> {
+    let request = request?;
+    let Some(journal) = journal_path.as_ref() else {
+        game.push_log(LogEvent::Notice("Bundles need a journal file".to_string()));
+        return None;
+    };
+    let result = match &request {
+        BundleRequest::Export(path) => RunBundle::from_run(game, journal, content)
+            .and_then(|bundle| bundle.write(path))
+            .map(|()| None)
+            .map_err(|e| e.to_string()),
+        BundleRequest::Import(path) => {
+            RunBundle::load(path).map_err(|e| e.to_string()).and_then(|bundle| {
+                bundle.check_compatible(content)?;
+                bundle.install_journal(journal).map(Some)
+            })
+        }
+    };
+    let notice = match (&request, &result) {
+        (BundleRequest::Export(path), Ok(_)) => format!("Run exported to {}", path.display()),
+        (BundleRequest::Import(path), Ok(_)) => format!("Run imported from {}", path.display()),
+        (_, Err(message)) => format!("Bundle failed: {message}"),
+    };
+    game.push_log(LogEvent::Notice(notice));
+    result.ok().flatten()
+}
@@ -0,0 +1,166 @@
+//! Command-line launch options, resolved together so flags can be checked against each other.
+//! A run code stands in for `--seed`, `--difficulty` and `--director`, so it cannot be
+//! combined with them. Practice arenas are off the record, so they take neither a run code
+//! nor a ghost.
+
+use std::path::PathBuf;
+
+use core::scenario::Scenario;
+use core::{ContentPack, Difficulty, Game, GameMode};
+
+use crate::difficulty::resolve_difficulty_from_args;
+use crate::ghost::resolve_ghost_path_from_args;
+use crate::practice::resolve_practice_arena_from_args;
+use crate::run_code::{MUTATOR_DIRECTOR, RunCode, resolve_run_code_from_args};
+use crate::seed::{SeedChoice, resolve_seed_from_args};
+use crate::spectator::resolve_spectate_addr_from_args;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LaunchOptions {
+    pub seed: SeedChoice,
+    pub difficulty: Difficulty,
+    /// Play with the adaptive difficulty director nudging spawns and loot.
+    pub director: bool,
+    pub ghost_path: Option<PathBuf>,
+    pub spectate_addr: Option<String>,
+    /// Journal wall-clock pacing alongside each input.
+    pub record_pacing: bool,
+    /// Fight in this practice arena instead of a generated dungeon.
+    pub practice: Option<Scenario>,
+    /// Blitz variant: prompts left unanswered for a few seconds take the policy default.
+    pub blitz: bool,
+}
+
+impl LaunchOptions {
+    pub fn from_args(
+        args: &[String],
+        generated_seed: u64,
+        content: &ContentPack,
+    ) -> Result<Self, String> {
+        let mut seed = resolve_seed_from_args(args, generated_seed)?;
+        let mut difficulty = resolve_difficulty_from_args(args)?;
+        let mut director = args.iter().any(|arg| arg == "--director");
+        let run_code = resolve_run_code_from_args(args)?;
+        if let Some(code) = &run_code {
+            let difficulty_given = args.iter().any(|arg| arg.starts_with("--difficulty"));
+            if matches!(seed, SeedChoice::Cli(_)) || difficulty_given || director {
+                return Err("--run-code already sets the seed, difficulty and director".to_string());
+            }
+            code.check_content(content)?;
+            seed = SeedChoice::Cli(code.seed);
+            difficulty = code.difficulty;
+            director = code.mutators & MUTATOR_DIRECTOR != 0;
+        }
+
+        let ghost_path = resolve_ghost_path_from_args(args)?;
+        let practice = resolve_practice_arena_from_args(args, content)?;
+        if practice.is_some() && (ghost_path.is_some() || run_code.is_some()) {
+            return Err("practice arenas cannot use --run-code or --ghost".to_string());
+        }
+
+        Ok(Self {
+            seed,
+            difficulty,
+            director,
+            ghost_path,
+            spectate_addr: resolve_spectate_addr_from_args(args)?,
+            record_pacing: args.iter().any(|arg| arg == "--record-pacing"),
+            practice,
+            blitz: args.iter().any(|arg| arg == "--blitz"),
+        })
+    }
+
+    /// The code that reproduces this launch on another machine.
+    pub fn run_code(&self, content: &ContentPack) -> RunCode {
+        let mutators = if self.director { MUTATOR_DIRECTOR } else { 0 };
+        RunCode { mutators, ..RunCode::new(self.seed.value(), self.difficulty, content) }
+    }
+
+    /// A new game for `seed` with this launch's difficulty, director setting and arena.
+    pub fn new_game(&self, seed: u64, content: &ContentPack) -> Game {
+        let mut game = match &self.practice {
+            Some(arena) => Game::from_scenario(seed, content, self.difficulty, arena)
+                .expect("practice arena was checked at launch"),
+            None => Game::new_with_difficulty(seed, content, GameMode::Ironman, self.difficulty),
+        };
+        game.set_director_enabled(self.director);
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn as_args(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|part| part.to_string()).collect()
+    }
+
+    #[test]
+    fn run_code_sets_seed_and_difficulty() {
+        let content = ContentPack::default();
+        let code = RunCode::new(4242, Difficulty::Relaxed, &content).encode();
+
+        let options =
+            LaunchOptions::from_args(&as_args(&["game", "--run-code", &code]), 1, &content)
+                .expect("valid run code");
+        assert_eq!(options.seed, SeedChoice::Cli(4242));
+        assert_eq!(options.difficulty, Difficulty::Relaxed);
+        assert_eq!(options.run_code(&content).encode(), code);
+
+        let generated = LaunchOptions::from_args(&as_args(&["game"]), 9, &content).unwrap();
+        assert_eq!(generated.seed, SeedChoice::Generated(9));
+        assert!(!generated.record_pacing);
+        assert!(!generated.director);
+        assert!(generated.practice.is_none());
+        assert!(!generated.blitz);
+    }
+
+    #[test]
+    fn practice_arena_replaces_the_dungeon_and_refuses_shared_runs() {
+        let content = ContentPack::default();
+        let args = as_args(&["game", "--arena", "gargoyle", "--kit", "perk_scout"]);
+        let options = LaunchOptions::from_args(&args, 3, &content).unwrap();
+        let game = options.new_game(3, &content);
+        assert_eq!(game.state().actors.len(), 2);
+        assert!(game.state().active_perks.contains(&"perk_scout"));
+
+        let code = RunCode::new(4242, Difficulty::Relaxed, &content).encode();
+        for extra in [["--run-code", code.as_str()], ["--ghost", "run.jsonl"]] {
+            let args = as_args(&["game", "--arena", "goblin", extra[0], extra[1]]);
+            assert!(
+                LaunchOptions::from_args(&args, 1, &content).is_err_and(|e| e.contains("practice"))
+            );
+        }
+    }
+
+    #[test]
+    fn director_flag_travels_in_the_run_code() {
+        let content = ContentPack::default();
+        let args = as_args(&["game", "--seed=77", "--director"]);
+        let options = LaunchOptions::from_args(&args, 1, &content).unwrap();
+        assert!(options.director);
+        assert!(options.new_game(77, &content).director_enabled());
+
+        let code = options.run_code(&content).encode();
+        let shared =
+            LaunchOptions::from_args(&as_args(&["game", "--run-code", &code]), 1, &content)
+                .unwrap();
+        assert!(shared.director);
+        let args = as_args(&["game", "--run-code", &code, "--director"]);
+        assert!(LaunchOptions::from_args(&args, 1, &content).is_err());
+    }
+
+    #[test]
+    fn run_code_conflicts_with_explicit_seed_or_difficulty() {
+        let content = ContentPack::default();
+        let code = RunCode::new(4242, Difficulty::Relaxed, &content).encode();
+        for extra in ["--seed=5", "--difficulty=brutal"] {
+            let args = as_args(&["game", "--run-code", &code, extra]);
+            assert!(
+                LaunchOptions::from_args(&args, 1, &content)
+                    .is_err_and(|e| e.contains("already sets"))
+            );
+        }
+    }
+}
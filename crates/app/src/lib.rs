@@ -1,8 +1,32 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub mod app_loop;
+pub mod audio;
+pub mod bug_report;
+pub mod build_info;
+#[cfg(feature = "content-hot-reload")]
+pub mod content_reload;
+pub mod debug_overlay;
+pub mod determinism;
+pub mod difficulty;
+pub mod display_name;
+pub mod encyclopedia;
+pub mod examine;
+pub mod ghost;
+pub mod glyph_legend;
+pub mod heatmap;
+pub mod launch;
+pub mod message;
+pub mod morgue;
+pub mod pacing;
+pub mod practice;
+pub mod profile;
+pub mod run_bundle;
+pub mod run_code;
 pub mod run_state_file;
+pub mod save_file;
 pub mod seed;
+pub mod spectator;
 pub mod ui_scale;
 
 /// Human-readable application name used for window title and data directories.
@@ -23,24 +47,43 @@ pub fn format_snapshot_hash(hash: u64) -> String {
     format!("0x{hash:016x}")
 }
 
-/// Map a `RunOutcome` to its reason code string.
+/// Map a `RunOutcome` to its reason code string. Codes are stable: recaps, morgues and
+/// tooling match on them, so an existing code never changes meaning.
+///
+/// | Outcome | Code |
+/// |---|---|
+/// | Victory, full clear | `WIN_CLEAR` |
+/// | Victory, speed clear | `WIN_SPEED` |
+/// | Victory, boss skipped | `WIN_BOSS_SKIP` |
+/// | Defeat by damage | `DMG_HP_ZERO` |
+/// | Defeat by poison | `PSN_HP_ZERO` |
+/// | Defeat by a hazard | `HZD_HP_ZERO` |
+/// | Defeat by starvation | `STV_HP_ZERO` |
+/// | Defeat by a curse | `CRS_HP_ZERO` |
 pub fn reason_code(outcome: &core::RunOutcome) -> &'static str {
     match outcome {
-        core::RunOutcome::Victory => "WIN_CLEAR",
+        core::RunOutcome::Victory(core::VictoryKind::FullClear) => "WIN_CLEAR",
+        core::RunOutcome::Victory(core::VictoryKind::SpeedClear) => "WIN_SPEED",
+        core::RunOutcome::Victory(core::VictoryKind::BossSkip) => "WIN_BOSS_SKIP",
         core::RunOutcome::Defeat(core::DeathCause::Damage) => "DMG_HP_ZERO",
         core::RunOutcome::Defeat(core::DeathCause::Poison) => "PSN_HP_ZERO",
+        core::RunOutcome::Defeat(core::DeathCause::Hazard) => "HZD_HP_ZERO",
+        core::RunOutcome::Defeat(core::DeathCause::Starvation) => "STV_HP_ZERO",
+        core::RunOutcome::Defeat(core::DeathCause::Curse) => "CRS_HP_ZERO",
     }
 }
 
 /// Map an `EngineFailureReason` to its reason code string.
 pub fn engine_failure_code(reason: &core::EngineFailureReason) -> &'static str {
     match reason {
-        core::EngineFailureReason::StalledNoProgress => "ENG_STALLED_NO_PROGRESS",
+        core::EngineFailureReason::StalledNoProgress(_) => "ENG_STALLED_NO_PROGRESS",
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
 
     #[test]
@@ -60,16 +103,47 @@ mod tests {
 
     #[test]
     fn reason_codes_are_correct() {
-        assert_eq!(reason_code(&core::RunOutcome::Victory), "WIN_CLEAR");
+        let victory = core::RunOutcome::Victory;
+        assert_eq!(reason_code(&victory(core::VictoryKind::FullClear)), "WIN_CLEAR");
+        assert_eq!(reason_code(&victory(core::VictoryKind::SpeedClear)), "WIN_SPEED");
+        assert_eq!(reason_code(&victory(core::VictoryKind::BossSkip)), "WIN_BOSS_SKIP");
         assert_eq!(reason_code(&core::RunOutcome::Defeat(core::DeathCause::Damage)), "DMG_HP_ZERO");
         assert_eq!(reason_code(&core::RunOutcome::Defeat(core::DeathCause::Poison)), "PSN_HP_ZERO");
+        assert_eq!(reason_code(&core::RunOutcome::Defeat(core::DeathCause::Hazard)), "HZD_HP_ZERO");
+        assert_eq!(
+            reason_code(&core::RunOutcome::Defeat(core::DeathCause::Starvation)),
+            "STV_HP_ZERO"
+        );
+        assert_eq!(reason_code(&core::RunOutcome::Defeat(core::DeathCause::Curse)), "CRS_HP_ZERO");
+    }
+
+    #[test]
+    fn every_outcome_has_a_unique_documented_code() {
+        let outcomes = core::VictoryKind::ALL
+            .map(core::RunOutcome::Victory)
+            .into_iter()
+            .chain(core::DeathCause::ALL.map(core::RunOutcome::Defeat));
+        let documented: Vec<&str> =
+            include_str!("lib.rs").lines().filter(|line| line.starts_with("/// | ")).collect();
+        let mut codes = BTreeSet::new();
+        for outcome in outcomes {
+            let code = reason_code(&outcome);
+            assert!(codes.insert(code), "{code} is used for more than one outcome");
+            let (prefix, rest) = code.split_once('_').expect("codes look like ABC_DETAIL");
+            assert_eq!(prefix.len(), 3, "{code}");
+            assert!(code.chars().all(|c| c.is_ascii_uppercase() || c == '_'), "{code}");
+            assert!(!rest.is_empty(), "{code}");
+            let row = format!("| `{code}` |");
+            assert!(documented.iter().any(|line| line.ends_with(&row)), "{code} is undocumented");
+        }
+        let rows_with_codes = documented.iter().filter(|line| line.contains('`')).count();
+        assert_eq!(rows_with_codes, codes.len(), "reason_code's table lists a stale code");
     }
 
     #[test]
     fn engine_failure_codes_are_correct() {
-        assert_eq!(
-            engine_failure_code(&core::EngineFailureReason::StalledNoProgress),
-            "ENG_STALLED_NO_PROGRESS"
-        );
+        let game = core::Game::new(1, &core::ContentPack::default(), core::GameMode::Ironman);
+        let reason = core::EngineFailureReason::StalledNoProgress(game.engine_diagnostics());
+        assert_eq!(engine_failure_code(&reason), "ENG_STALLED_NO_PROGRESS");
     }
 }
@@ -1,29 +1,47 @@
 //! Macroquad binary entrypoint that wires input, simulation, persistence, and rendering.
 
+mod diagnostics;
 mod frame_input;
 mod game_layout;
+mod journal_io;
+mod sound;
 mod ui_render;
 mod ui_scale_file;
-mod ui_text;
 mod window_config;
 
+#[cfg(feature = "content-hot-reload")]
+use app::content_reload::ContentWatcher;
 use app::{
     APP_NAME,
-    app_loop::AppState,
-    format_snapshot_hash, get_current_unix_ms,
+    app_loop::{AppCompletion, AppState, BlitzTimer, PolicyMacroBook},
+    bug_report::get_default_bug_report_dir,
+    debug_overlay::DebugOverlay,
+    determinism::DeterminismCheck,
+    get_current_unix_ms,
+    ghost::GhostRun,
+    launch::LaunchOptions,
+    morgue::get_default_morgue_dir,
+    pacing::PacingClock,
+    profile::PlayerProfile,
     run_state_file::RunStateFile,
-    seed::{generate_runtime_seed, resolve_seed_from_args},
+    seed::generate_runtime_seed,
+    spectator::SpectatorServer,
     ui_scale::clamp_ui_scale,
 };
-use core::{
-    ContentPack, Game, GameMode, JournalWriter, LogEvent, load_journal_from_file,
-    replay::replay_journal_inputs,
+use core::{ContentPack, Game, LogEvent};
+use diagnostics::{
+    load_recovery_hint, persist_run_state, write_engine_failure_report, write_run_morgue,
 };
 use frame_input::capture_frame_input;
 use game_layout::{compute_frame_layout, setup_layout};
+use journal_io::{
+    create_journal_writer, get_journal_path, handle_bundle_request, prepare_startup_journal_writer,
+    resume_journal_writer, try_replay_from_journal,
+};
 use macroquad::prelude::*;
 use macroquad::window::Conf;
-use std::{env, path::PathBuf, process::exit};
+use sound::SoundPlayer;
+use std::{env, mem, path::PathBuf, process::exit};
 use taffy::TaffyTree;
 use ui_render::draw_frame;
 use ui_scale_file::UiScaleFile;
@@ -36,28 +54,31 @@ fn window_conf() -> Conf {
 #[macroquad::main(window_conf)]
 async fn main() {
     let args: Vec<String> = env::args().collect();
-    let generated_seed = generate_runtime_seed();
-    let selected_seed = match resolve_seed_from_args(&args, generated_seed) {
-        Ok(seed_choice) => seed_choice,
-        Err(message) => {
-            let program_name = args.first().map_or("game", String::as_str);
-            eprintln!("Error: {message}");
-            eprintln!("Usage: {program_name} [--seed <u64>]");
-            exit(2);
-        }
-    };
+    let content = ContentPack::default();
+    let options = LaunchOptions::from_args(&args, generate_runtime_seed(), &content)
+        .unwrap_or_else(|message| exit_with_usage(&args, &message));
+    let ghost_path = options.ghost_path.clone();
+    let mut spectator = options
+        .spectate_addr
+        .as_deref()
+        .map(SpectatorServer::bind)
+        .transpose()
+        .unwrap_or_else(|message| exit_with_usage(&args, &message));
 
     let diagnostics_path = RunStateFile::get_default_path();
-    let journal_path = get_journal_path();
+    // Practice arenas stay off the record: no journal to replay and no morgue.
+    let practice = options.practice.is_some();
+    let journal_path = get_journal_path().filter(|_| !practice);
     let ui_scale_path = UiScaleFile::get_default_path();
+    let bug_report_dir = get_default_bug_report_dir();
+    let morgue_dir = get_default_morgue_dir().filter(|_| !practice);
     let (recovered_seed, recovery_hint) = load_recovery_hint(&diagnostics_path);
     let persisted_ui_scale = load_persisted_ui_scale(&ui_scale_path);
 
-    let content = ContentPack::default();
-    let mut current_run_seed = selected_seed.value();
-    let mut game = Game::new(current_run_seed, &content, GameMode::Ironman);
+    let mut current_run_seed = options.seed.value();
+    let mut game = options.new_game(current_run_seed, &content);
     let (mut journal_writer, preserved_existing_journal) =
-        prepare_startup_journal_writer(&journal_path, current_run_seed);
+        prepare_startup_journal_writer(&journal_path, &game);
 
     if let Some(path) = &diagnostics_path {
         game.push_log(LogEvent::Notice(format!("Logs: {}", path.display())));
@@ -75,14 +96,43 @@ async fn main() {
         game.push_log(hint);
         game.push_log(LogEvent::Notice("Press Shift+K to replay from last journal".to_string()));
     }
+    game.push_log(LogEvent::Notice(if practice {
+        "Practice arena: this run is not journaled or recorded".to_string()
+    } else {
+        format!("Run code: {}", options.run_code(&content).encode())
+    }));
+    let mut ghost = load_ghost(&ghost_path, &content, current_run_seed, &mut game);
     game.push_log(LogEvent::Notice(display_scale_notice(persisted_ui_scale)));
     game.push_log(LogEvent::Notice(
         "UI scale hotkeys: Ctrl+= larger, Ctrl+- smaller, Ctrl+0 reset".to_string(),
     ));
+    game.push_log(LogEvent::Notice(
+        "Sound hotkeys: Ctrl+V mute, Ctrl+. louder, Ctrl+, quieter".to_string(),
+    ));
+    game.push_log(LogEvent::Notice(
+        "Enter while paused opens the command palette; F5-F8 run policy macros; F4 legend; F2 encyclopedia"
+            .to_string(),
+    ));
+    game.push_log(LogEvent::Notice(
+        "Before the first tick, `preset <name>` starts the run on a saved policy".to_string(),
+    ));
 
-    let mut app_state =
-        AppState { ui_scale: runtime_ui_scale(persisted_ui_scale), ..AppState::default() };
+    let mut pacing = options.record_pacing.then(|| PacingClock::new(get_current_unix_ms()));
+    let mut app_state = AppState {
+        ui_scale: runtime_ui_scale(persisted_ui_scale),
+        policy_macros: PolicyMacroBook::load_default(),
+        profile: PlayerProfile::load_default(),
+        blitz: options.blitz.then(BlitzTimer::default),
+        ..AppState::default()
+    };
 
+    let mut sound = SoundPlayer::load().await;
+    let mut debug_overlay = DebugOverlay::default();
+    #[cfg(feature = "content-hot-reload")]
+    let mut content_watcher = ContentWatcher::get_default_dir()
+        .map(|dir| ContentWatcher::new(dir, content.display.clone()));
+    #[cfg(feature = "content-hot-reload")]
+    let mut content = content;
     let mut taffy: TaffyTree<()> = TaffyTree::new();
     let layout_nodes = setup_layout(&mut taffy);
 
@@ -90,28 +140,35 @@ async fn main() {
         clear_background(BLACK);
 
         let frame_input = capture_frame_input();
-        if frame_input.restart_with_recovered_seed
-            && let Some(seed) = recovered_seed
+        let request = app_state.bundle_request.take();
+        let imported_seed = handle_bundle_request(request, &journal_path, &mut game, &content);
+        if (frame_input.restart_with_recovered_seed || imported_seed.is_some())
+            && let Some(seed) = imported_seed.or(recovered_seed)
         {
             match try_replay_from_journal(&journal_path, &content) {
-                Ok(replayed_game) => {
+                Ok((replayed_game, salvage_note)) => {
                     current_run_seed = seed;
                     game = replayed_game;
-                    app_state = AppState { ui_scale: app_state.ui_scale, ..AppState::default() };
+                    if let Some(note) = salvage_note {
+                        game.push_log(LogEvent::Notice(note));
+                    }
+                    app_state.reset_for_new_run();
                     game.push_log(LogEvent::Notice(format!(
                         "REPLAYED journal for seed {seed} — tick {}",
                         game.current_tick()
                     )));
                     // Resume appending to the same journal file
                     journal_writer = resume_journal_writer(&journal_path);
+                    ghost = load_ghost(&ghost_path, &content, current_run_seed, &mut game);
                 }
                 Err(reason) => {
                     current_run_seed = seed;
-                    game = Game::new(current_run_seed, &content, GameMode::Ironman);
-                    app_state = AppState { ui_scale: app_state.ui_scale, ..AppState::default() };
-                    journal_writer = create_journal_writer(&journal_path, current_run_seed);
+                    game = options.new_game(current_run_seed, &content);
+                    app_state.reset_for_new_run();
+                    journal_writer = create_journal_writer(&journal_path, &game);
                     game.push_log(LogEvent::Notice(format!("REPLAY INCOMPLETE: {reason}")));
                     game.push_log(LogEvent::Notice(format!("RESTARTED WITH SEED: {seed}")));
+                    ghost = load_ghost(&ghost_path, &content, current_run_seed, &mut game);
                 }
             }
         }
@@ -123,17 +180,70 @@ async fn main() {
             game.push_log(LogEvent::Notice(format!("UI scale set to {:.2}", app_state.ui_scale)));
         }
 
-        app_state.tick(&mut game, &frame_input.keys_pressed);
+        if frame_input.toggle_debug_overlay {
+            debug_overlay.toggle();
+        }
+        app_state.legend_visible ^= frame_input.toggle_glyph_legend;
+        app_state.encyclopedia_visible ^= frame_input.toggle_encyclopedia;
+        #[cfg(feature = "content-hot-reload")]
+        if let Some(notice) =
+            content_watcher.as_mut().and_then(|watcher| watcher.reload_into(&mut content.display))
+        {
+            game.push_log(LogEvent::Notice(notice));
+        }
+        if let Some(action) = frame_input.audio_action {
+            sound.apply_action(action, &mut game);
+        }
+
+        let now_ms = get_current_unix_ms();
+        if let Some(clock) = &mut pacing {
+            clock.observe_frame(now_ms, &app_state.mode);
+        }
+        app_state.type_into_palette(&frame_input.typed_chars);
+        let just_finished = app_state.tick(&mut game, &frame_input.keys_pressed, get_frame_time());
+        if mem::take(&mut app_state.policy_macros.unsaved)
+            && let Err(warning) = app_state.policy_macros.save_default()
+        {
+            game.push_log(LogEvent::Notice(warning));
+        }
+        for kind in app_state.profile.unlock_visible(game.state()) {
+            game.push_log(LogEvent::Notice(format!("Encyclopedia: {kind:?} entry unlocked (F2)")));
+        }
+        for kind in app_state.profile.record_kills(game.log()) {
+            game.push_log(LogEvent::Notice(format!(
+                "First {kind:?} slain: examine shows its dangers"
+            )));
+        }
+        if mem::take(&mut app_state.profile.unsaved)
+            && let Err(warning) = app_state.profile.save_default()
+        {
+            game.push_log(LogEvent::Notice(warning));
+        }
+        if let Some(active) = &mut ghost
+            && let Err(reason) = active.sync_to(game.current_tick())
+        {
+            game.push_log(LogEvent::Notice(format!("Ghost stopped: {reason}")));
+            ghost = None;
+        }
+        sound.play_new_cues(&game, &app_state.mode);
+        debug_overlay.observe(&game.engine_internals());
+        if let Some(server) = &mut spectator {
+            server.broadcast(&content, &game);
+        }
 
         // Flush accepted inputs to the journal file
         if journal_writer.is_none() && !app_state.accepted_inputs.is_empty() {
             // We deferred writer creation to avoid truncating a previous run
             // before the user has a chance to replay it.
-            journal_writer = create_journal_writer(&journal_path, current_run_seed);
+            journal_writer = create_journal_writer(&journal_path, &game);
         }
         if let Some(writer) = &mut journal_writer {
             for input in app_state.accepted_inputs.drain(..) {
-                if writer.append(input.tick_boundary, &input.payload).is_err() {
+                let input_pacing = pacing.as_mut().map(|clock| clock.stamp(now_ms));
+                if writer
+                    .append_with_pacing(input.tick_boundary, &input.payload, input_pacing)
+                    .is_err()
+                {
                     game.push_log(LogEvent::Notice(
                         "Warning: failed to write journal entry".to_string(),
                     ));
@@ -141,143 +251,96 @@ async fn main() {
             }
         }
 
+        if let Some(AppCompletion::EngineFailure(reason)) = &just_finished {
+            let notice = write_engine_failure_report(&bug_report_dir, &journal_path, &game, reason);
+            game.push_log(notice);
+        }
+        if let Some(completion) = &just_finished
+            && !practice
+        {
+            let notice = write_run_morgue(&morgue_dir, &content, &game, completion);
+            game.push_log(notice);
+        }
+        if let Some(AppCompletion::Outcome(_)) = &just_finished
+            && let (Some(path), Some(_)) = (&journal_path, &journal_writer)
+        {
+            app_state.determinism = Some(DeterminismCheck::spawn(path.clone(), &content, &game));
+        }
+        if let Some(check) = &mut app_state.determinism {
+            check.poll();
+        }
+
         persist_run_state(&diagnostics_path, &game);
 
         let frame_layout =
             compute_frame_layout(&mut taffy, &layout_nodes, screen_width(), screen_height());
-        draw_frame(&game, &app_state, current_run_seed, &frame_layout, app_state.ui_scale);
+        let ghost_pos =
+            ghost.as_ref().and_then(|active| active.player_pos_on_floor(game.state().floor_index));
+        draw_frame(
+            &content,
+            &game,
+            &app_state,
+            current_run_seed,
+            &frame_layout,
+            ghost_pos,
+            &debug_overlay,
+        );
 
         next_frame().await
     }
 }
 
-fn load_persisted_ui_scale(path: &Option<PathBuf>) -> Option<f32> {
-    let path = path.as_ref()?;
-    UiScaleFile::load(path).ok().map(|state| clamp_ui_scale(state.ui_scale))
-}
-
-fn persist_ui_scale(path: &Option<PathBuf>, ui_scale: f32) {
-    let Some(path) = path.as_ref() else {
-        return;
-    };
-    let state = UiScaleFile { format_version: 1, ui_scale: clamp_ui_scale(ui_scale) };
-    if let Err(error) = state.write_atomic(path) {
-        eprintln!("Warning: failed to persist UI scale: {error}");
-    }
-}
-
-// ---------------------------------------------------------------------------
-// Journal helpers
-// ---------------------------------------------------------------------------
-
-/// OS-idiomatic path for the journal file, alongside the diagnostics file.
-fn get_journal_path() -> Option<PathBuf> {
-    directories::ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
-        let mut path = proj_dirs.data_dir().to_path_buf();
-        path.push("journal.jsonl");
-        path
-    })
+fn exit_with_usage(args: &[String], message: &str) -> ! {
+    let program_name = args.first().map_or("game", String::as_str);
+    eprintln!("Error: {message}");
+    eprintln!(
+        "Usage: {program_name} [--seed <u64>] [--difficulty <relaxed|normal|brutal>] \
+         [--director] [--run-code <code>] \
+         [--ghost <journal.jsonl>] [--spectate <addr:port>] [--record-pacing] [--blitz] \
+         [--arena <enemy,...> [--kit <item,...>]]"
+    );
+    exit(2);
 }
 
-/// Create a fresh journal file for a new run.
-fn create_journal_writer(path: &Option<PathBuf>, seed: u64) -> Option<JournalWriter> {
+/// Load the `--ghost` journal for the current seed, noting the outcome in the event log.
+fn load_ghost(
+    path: &Option<PathBuf>,
+    content: &ContentPack,
+    run_seed: u64,
+    game: &mut Game,
+) -> Option<GhostRun> {
     let path = path.as_ref()?;
-    match JournalWriter::create(path, seed, "dev", 0) {
-        Ok(writer) => Some(writer),
-        Err(e) => {
-            eprintln!("Warning: could not create journal file: {e}");
+    match GhostRun::load(path, content, run_seed) {
+        Ok(ghost) => {
+            game.push_log(LogEvent::Notice(format!("Racing ghost from {}", path.display())));
+            Some(ghost)
+        }
+        Err(reason) => {
+            game.push_log(LogEvent::Notice(format!("Ghost unavailable: {reason}")));
             None
         }
     }
 }
 
-/// On startup, preserve an existing non-empty journal so Shift+K replay can
-/// consume it without being truncated by a new run initialization.
-fn prepare_startup_journal_writer(
-    path: &Option<PathBuf>,
-    seed: u64,
-) -> (Option<JournalWriter>, bool) {
-    let Some(path_ref) = path.as_ref() else {
-        return (None, false);
-    };
-
-    if let Ok(loaded) = load_journal_from_file(path_ref)
-        && !loaded.journal.inputs.is_empty()
-    {
-        return (None, true);
-    }
-
-    (create_journal_writer(path, seed), false)
-}
-
-/// Resume appending to an existing journal after replay.
-fn resume_journal_writer(path: &Option<PathBuf>) -> Option<JournalWriter> {
+fn load_persisted_ui_scale(path: &Option<PathBuf>) -> Option<f32> {
     let path = path.as_ref()?;
-    let loaded = load_journal_from_file(path).ok()?;
-    match JournalWriter::resume(path, loaded.last_sha256_hex, loaded.next_seq) {
-        Ok(writer) => Some(writer),
-        Err(e) => {
-            eprintln!("Warning: could not resume journal file: {e}");
+    match UiScaleFile::load(path) {
+        Ok(state) => Some(clamp_ui_scale(state.ui_scale)),
+        Err(error) => {
+            if !error.is_missing() {
+                eprintln!("Warning: ignoring UI scale file: {error}");
+            }
             None
         }
     }
 }
 
-/// Try to load and replay a journal file. Returns the reconstructed Game
-/// on success, or an explanatory error if replay is incomplete.
-fn try_replay_from_journal(
-    journal_path: &Option<PathBuf>,
-    content: &ContentPack,
-) -> Result<Game, String> {
-    let path = journal_path.as_ref().ok_or_else(|| "journal path is unavailable".to_string())?;
-    let loaded = match load_journal_from_file(path) {
-        Ok(loaded) => loaded,
-        Err(e) => {
-            return Err(format!("{e}"));
-        }
-    };
-    if loaded.journal.inputs.is_empty() {
-        return Err("journal has no recorded inputs".to_string());
-    }
-    match replay_journal_inputs(content, &loaded.journal) {
-        Ok(game) => Ok(game),
-        Err(e) => Err(format!("{e}")),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// Diagnostics persistence
-// ---------------------------------------------------------------------------
-
-fn load_recovery_hint(diagnostics_path: &Option<PathBuf>) -> (Option<u64>, Option<LogEvent>) {
-    if let Some(state) = diagnostics_path.as_ref().and_then(|path| RunStateFile::load(path).ok()) {
-        return (
-            Some(state.run_seed),
-            Some(LogEvent::RecoveryHint {
-                seed: state.run_seed,
-                hash_hex: state.snapshot_hash_hex,
-            }),
-        );
-    }
-
-    (None, None)
-}
-
-fn persist_run_state(diagnostics_path: &Option<PathBuf>, game: &Game) {
-    let Some(path) = diagnostics_path else {
+fn persist_ui_scale(path: &Option<PathBuf>, ui_scale: f32) {
+    let Some(path) = path.as_ref() else {
         return;
     };
-
-    let state = RunStateFile {
-        format_version: 1,
-        run_seed: game.seed(),
-        snapshot_hash_hex: format_snapshot_hash(game.snapshot_hash()),
-        tick: game.current_tick(),
-        floor_index: game.state().floor_index,
-        branch_profile: format!("{:?}", game.state().branch_profile),
-        active_god: format!("{:?}", game.state().active_god),
-        updated_at_unix_ms: get_current_unix_ms(),
-    };
-
-    let _ = state.write_atomic(path);
+    let state = UiScaleFile { ui_scale: clamp_ui_scale(ui_scale) };
+    if let Err(error) = state.write_atomic(path) {
+        eprintln!("Warning: failed to persist UI scale: {error}");
+    }
 }
@@ -0,0 +1,245 @@
+//! Composition of structured `LogEvent`s into styled text spans.
+//! This module owns log wording and categories; frontends only map categories to colors.
+
+use core::{AutoReason, ContentPack, EntityId, Game, LogEvent, PromptKind};
+
+use crate::display_name::{actor_name, damage_source_name, item_name, perk_name, vault_name};
+
+/// Broad kind of a log message, used by frontends to pick a color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageCategory {
+    Exploration,
+    Combat,
+    Stealth,
+    Item,
+    System,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MessageSpan {
+    pub text: String,
+    /// Names of actors and items are emphasized so they stand out in the log.
+    pub emphasized: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComposedMessage {
+    pub category: MessageCategory,
+    pub spans: Vec<MessageSpan>,
+}
+
+impl ComposedMessage {
+    fn new(category: MessageCategory) -> Self {
+        Self { category, spans: Vec::new() }
+    }
+
+    fn text(mut self, text: impl Into<String>) -> Self {
+        self.spans.push(MessageSpan { text: text.into(), emphasized: false });
+        self
+    }
+
+    fn name(mut self, text: impl Into<String>) -> Self {
+        self.spans.push(MessageSpan { text: text.into(), emphasized: true });
+        self
+    }
+
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+
+pub fn auto_reason_text(reason: AutoReason) -> &'static str {
+    match reason {
+        AutoReason::Frontier => "Exploring the unknown...",
+        AutoReason::Loot => "Moving to collect loot...",
+        AutoReason::ThreatAvoidance => "Pathing around threats...",
+        AutoReason::Stuck => "Auto-explore is stuck.",
+        AutoReason::Door => "Moving to open a door...",
+        AutoReason::Objective => "Hunting the floor objective...",
+    }
+}
+
+pub fn compose_log_event(content: &ContentPack, game: &Game, event: &LogEvent) -> ComposedMessage {
+    use MessageCategory::*;
+    match event {
+        LogEvent::AutoReasonChanged { reason, .. } => {
+            ComposedMessage::new(Exploration).text(auto_reason_text(*reason))
+        }
+        LogEvent::RoomEntered { room } => {
+            ComposedMessage::new(Exploration).text(format!("Entered room {}", room.0 + 1))
+        }
+        LogEvent::VaultEntered { template, .. } => ComposedMessage::new(Exploration)
+            .text("This room is a vault: ")
+            .name(vault_name(*template)),
+        LogEvent::DoorOpened { .. } => ComposedMessage::new(Exploration).text("Opened the door"),
+        LogEvent::PromptSnoozed { prompt, ticks } => {
+            ComposedMessage::new(Exploration).text(match prompt {
+                PromptKind::DoorBlocked => format!("Left the door shut for {ticks} ticks"),
+                _ => format!("Left the loot where it lies for {ticks} ticks"),
+            })
+        }
+        LogEvent::ObjectiveCompleted { objective } => ComposedMessage::new(System)
+            .text("Objective complete: ")
+            .name(format!("{objective:?}"))
+            .text("; the stairs unlock"),
+        LogEvent::FloorEntered { floor_index, name } => ComposedMessage::new(Exploration)
+            .text(format!("Floor {floor_index}: "))
+            .name(name.clone()),
+        LogEvent::EnemyEncountered { enemy } => ComposedMessage::new(Combat)
+            .text("Encountered ")
+            .name(enemy_name(content, game, *enemy)),
+        LogEvent::PlayerSlain { killer: Some(kind), .. } => {
+            ComposedMessage::new(Combat).text("Slain by ").name(actor_name(&content.display, *kind))
+        }
+        LogEvent::PlayerSlain { killer: None, source } => {
+            ComposedMessage::new(Combat).text(format!("Died of {}", damage_source_name(*source)))
+        }
+        LogEvent::EncounterAutoFought { kind, damage } => ComposedMessage::new(Combat)
+            .text("Auto-fought ")
+            .name(actor_name(&content.display, *kind))
+            .text(format!(" for {damage} damage")),
+        LogEvent::EncounterResolved { enemy, fought } => {
+            let verb = if *fought { "Fought " } else { "Avoided " };
+            ComposedMessage::new(Combat).text(verb).name(enemy_name(content, game, *enemy))
+        }
+        LogEvent::EnemySlain { kind } => {
+            ComposedMessage::new(Combat).text("Slew ").name(actor_name(&content.display, *kind))
+        }
+        LogEvent::PlayerSpotted { enemy } => ComposedMessage::new(Stealth)
+            .text("Spotted by ")
+            .name(enemy_name(content, game, *enemy)),
+        LogEvent::ItemPickedUp { kind } => {
+            ComposedMessage::new(Item).text("Picked up ").name(item_name(content, game, *kind))
+        }
+        LogEvent::ItemDiscarded { kind } => {
+            ComposedMessage::new(Item).text("Discarded ").name(item_name(content, game, *kind))
+        }
+        LogEvent::ConsumableIdentified { id } => ComposedMessage::new(Item)
+            .text("Identified ")
+            .name(game.state().potion_appearances.get(id).copied().unwrap_or(id))
+            .text(" as ")
+            .name(item_name(content, game, core::ItemKind::Consumable(id))),
+        LogEvent::ConsumableUsed { id } => ComposedMessage::new(Item)
+            .text("Used ")
+            .name(item_name(content, game, core::ItemKind::Consumable(id))),
+        LogEvent::PotionDrunk { id } => ComposedMessage::new(Item).text("Drank ").name(item_name(
+            content,
+            game,
+            core::ItemKind::Consumable(id),
+        )),
+        LogEvent::PactBroken { from, to } => ComposedMessage::new(System)
+            .text("Broke the pact with ")
+            .name(format!("{from:?}"))
+            .text("; ")
+            .name(format!("{to:?}"))
+            .text(" answers"),
+        LogEvent::BoonGranted { boon } => ComposedMessage::new(System)
+            .text("Boon granted: ")
+            .name(format!("{:?} +{}", boon.kind, boon.amount)),
+        LogEvent::LeveledUp { level } => {
+            ComposedMessage::new(System).text(format!("Reached level {level}; a perk draft awaits"))
+        }
+        LogEvent::PerkDrafted { id } => {
+            ComposedMessage::new(System).text("Drafted perk: ").name(perk_name(content, id))
+        }
+        LogEvent::CurseBound { kind } => ComposedMessage::new(Item)
+            .name(item_name(content, game, *kind))
+            .text(" is cursed and binds to you"),
+        LogEvent::CursesCleansed => {
+            ComposedMessage::new(System).text("The altar lifts every curse you carry")
+        }
+        LogEvent::Counterattack { enemy, damage } => ComposedMessage::new(Combat)
+            .text("Braced and countered ")
+            .name(enemy_name(content, game, *enemy))
+            .text(format!(" for {damage}")),
+        LogEvent::EnemyStruck { enemy, damage } => ComposedMessage::new(Combat)
+            .name(enemy_name(content, game, *enemy))
+            .text(format!(" strikes you for {damage}")),
+        LogEvent::RecoveryHint { seed, hash_hex } => ComposedMessage::new(System)
+            .text(format!("Recovered last run: seed={seed} hash={hash_hex}")),
+        LogEvent::Notice(message) => ComposedMessage::new(System).text(message.clone()),
+    }
+}
+
+/// The enemy's display name, or its epithet when it is the floor's objective elite.
+fn enemy_name(content: &ContentPack, game: &Game, enemy: EntityId) -> String {
+    game.elite_name(enemy).unwrap_or_else(|| {
+        let actor = game.state().actors.get(enemy);
+        actor.map_or("enemy", |actor| actor_name(&content.display, actor.kind)).to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::content::keys;
+    use core::{ActorKind, DamageSource, GameMode, ItemKind, RoomId, VaultTemplate};
+
+    fn game() -> Game {
+        Game::new(11, &ContentPack::default(), GameMode::Ironman)
+    }
+
+    #[test]
+    fn enemy_events_emphasize_display_names() {
+        let game = game();
+        let (goblin, _) = game
+            .state()
+            .actors
+            .iter()
+            .find(|(_, actor)| actor.kind == ActorKind::Goblin)
+            .expect("starting floor should have a goblin");
+
+        let message = compose_log_event(
+            &ContentPack::default(),
+            &game,
+            &LogEvent::PlayerSpotted { enemy: goblin },
+        );
+        assert_eq!(message.category, MessageCategory::Stealth);
+        assert_eq!(message.plain_text(), "Spotted by Goblin");
+        assert!(message.spans[1].emphasized);
+    }
+
+    #[test]
+    fn item_and_system_events_use_their_categories() {
+        let game = game();
+        let pickup = compose_log_event(
+            &ContentPack::default(),
+            &game,
+            &LogEvent::ItemPickedUp { kind: ItemKind::Weapon(keys::WEAPON_RUSTY_SWORD) },
+        );
+        assert_eq!(pickup.category, MessageCategory::Item);
+        assert_eq!(pickup.plain_text(), "Picked up Rusty Sword");
+
+        let hint = LogEvent::RecoveryHint { seed: 42, hash_hex: "0xabc".to_string() };
+        let message = compose_log_event(&ContentPack::default(), &game, &hint);
+        assert_eq!(message.category, MessageCategory::System);
+        assert_eq!(message.plain_text(), "Recovered last run: seed=42 hash=0xabc");
+
+        let vault = LogEvent::VaultEntered { template: VaultTemplate::GoblinCamp, room: RoomId(2) };
+        let message = compose_log_event(&ContentPack::default(), &game, &vault);
+        assert_eq!(message.category, MessageCategory::Exploration);
+        assert_eq!(message.plain_text(), "This room is a vault: Goblin Camp");
+
+        let snoozed = LogEvent::PromptSnoozed { prompt: PromptKind::DoorBlocked, ticks: 40 };
+        assert_eq!(
+            compose_log_event(&ContentPack::default(), &game, &snoozed).plain_text(),
+            "Left the door shut for 40 ticks"
+        );
+
+        let entered = LogEvent::FloorEntered { floor_index: 3, name: "The Ashen Nave".to_string() };
+        let message = compose_log_event(&ContentPack::default(), &game, &entered);
+        assert_eq!(message.plain_text(), "Floor 3: The Ashen Nave");
+        assert!(message.spans[1].emphasized);
+
+        let slain = LogEvent::PlayerSlain { killer: None, source: DamageSource::Poison };
+        assert_eq!(
+            compose_log_event(&ContentPack::default(), &game, &slain).plain_text(),
+            "Died of poison"
+        );
+    }
+
+    #[test]
+    fn auto_reason_text_formats_frontier_reason() {
+        assert_eq!(auto_reason_text(AutoReason::Frontier), "Exploring the unknown...");
+    }
+}
@@ -0,0 +1,170 @@
+//! Morgue files written when a run ends, for post-run analysis of danger spikes.
+//! A morgue is one JSON file holding the run summary, the full retained threat trace and a
+//! movement heatmap per floor.
+
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::app_loop::{AppCompletion, death_summary};
+use crate::build_info::BUILD_INFO;
+use crate::heatmap::{FloorHeatmap, run_heatmaps};
+use crate::message::compose_log_event;
+use crate::{
+    APP_NAME, engine_failure_code, format_snapshot_hash, get_current_unix_ms, reason_code,
+};
+use core::{ActorKind, ContentPack, DamageSource, DeathRecord, FloorProvenance, Game, ThreatTrace};
+
+/// Run summary plus threat trace, stored as one JSON file per finished run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MorgueFile {
+    pub format_version: u32,
+    /// Build that wrote the morgue; empty in morgues written before build stamping.
+    #[serde(default)]
+    pub build_id: String,
+    /// Content hash of that build, as `0x` plus 16 hex digits; empty when unstamped.
+    #[serde(default)]
+    pub content_hash_hex: String,
+    pub reason_code: String,
+    pub run_seed: u64,
+    pub tick: u64,
+    pub floor_index: u8,
+    /// Generator behind the final floor's layout; absent from morgues written before it.
+    #[serde(default)]
+    pub floor_provenance: Option<FloorProvenance>,
+    pub snapshot_hash_hex: String,
+    /// How the player died; absent for other endings and from morgues written before it.
+    #[serde(default)]
+    pub death: Option<MorgueDeath>,
+    /// Every retained threat-trace entry, oldest first.
+    pub threat_trace: Vec<ThreatTrace>,
+    /// Player movement per floor walked; absent from morgues written before heatmaps.
+    #[serde(default)]
+    pub heatmaps: Vec<FloorHeatmap>,
+    pub created_at_unix_ms: u64,
+}
+
+/// The final blow of a run that ended in death.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MorgueDeath {
+    pub summary: String,
+    pub killer: Option<ActorKind>,
+    pub source: DamageSource,
+    pub damage: i32,
+    pub floor_index: u8,
+    pub tick: u64,
+    /// The log events just before the final blow, as the event log showed them.
+    pub final_moments: Vec<String>,
+}
+
+impl MorgueDeath {
+    fn from_record(content: &ContentPack, game: &Game, record: &DeathRecord) -> Self {
+        Self {
+            summary: death_summary(content, record),
+            killer: record.killer,
+            source: record.source,
+            damage: record.damage,
+            floor_index: record.floor_index,
+            tick: record.tick,
+            final_moments: record
+                .recent_events
+                .iter()
+                .map(|event| compose_log_event(content, game, event).plain_text())
+                .collect(),
+        }
+    }
+}
+
+impl MorgueFile {
+    pub fn from_run(content: &ContentPack, game: &Game, completion: &AppCompletion) -> Self {
+        let reason = match completion {
+            AppCompletion::Outcome(outcome) => reason_code(outcome),
+            AppCompletion::EngineFailure(reason) => engine_failure_code(reason),
+        };
+        Self {
+            format_version: 1,
+            build_id: BUILD_INFO.build_id.to_string(),
+            content_hash_hex: format_snapshot_hash(BUILD_INFO.content_hash),
+            reason_code: reason.to_string(),
+            run_seed: game.seed(),
+            tick: game.current_tick(),
+            floor_index: game.state().floor_index,
+            floor_provenance: Some(game.state().floor_provenance),
+            snapshot_hash_hex: format_snapshot_hash(game.snapshot_hash()),
+            death: game
+                .death_record()
+                .map(|record| MorgueDeath::from_record(content, game, record)),
+            threat_trace: game.state().threat_trace.iter().rev().cloned().collect(),
+            heatmaps: run_heatmaps(game),
+            created_at_unix_ms: get_current_unix_ms(),
+        }
+    }
+}
+
+/// OS-idiomatic directory that holds one JSON file per finished run.
+pub fn get_default_morgue_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
+        let mut path = proj_dirs.data_dir().to_path_buf();
+        path.push("morgue");
+        path
+    })
+}
+
+/// Write `morgue` into `root` under a name unique to the run and return its path.
+pub fn write_morgue_file(root: &Path, morgue: &MorgueFile) -> io::Result<PathBuf> {
+    fs::create_dir_all(root)?;
+    let path = root.join(format!(
+        "{}-seed{}-tick{}.json",
+        morgue.created_at_unix_ms, morgue.run_seed, morgue.tick
+    ));
+    let json = serde_json::to_string_pretty(morgue).map_err(io::Error::other)?;
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{AdvanceStopReason, DeathCause, GameMode, RunOutcome, VictoryKind};
+    use tempfile::tempdir;
+
+    #[test]
+    fn morgue_exports_the_whole_trace_oldest_first_and_round_trips() {
+        let dir = tempdir().unwrap();
+        let mut game = Game::new(2706, &ContentPack::default(), GameMode::Ironman);
+        while matches!(game.advance(5).stop_reason, AdvanceStopReason::BudgetExhausted) {}
+        assert!(game.state().threat_trace.len() > 1);
+        let victory = RunOutcome::Victory(VictoryKind::FullClear);
+        let morgue =
+            MorgueFile::from_run(&ContentPack::default(), &game, &AppCompletion::Outcome(victory));
+
+        assert_eq!(morgue.reason_code, "WIN_CLEAR");
+        assert_eq!(morgue.build_id, BUILD_INFO.build_id);
+        assert_eq!(morgue.floor_provenance, Some(game.state().floor_provenance));
+        assert_eq!(morgue.death, None);
+        assert_eq!(morgue.threat_trace.len(), game.state().threat_trace.len());
+        assert!(morgue.threat_trace.windows(2).all(|pair| pair[0].tick < pair[1].tick));
+        assert!(!morgue.heatmaps.is_empty());
+        assert!(morgue.heatmaps.iter().all(|heatmap| heatmap.total_visits > heatmap.revisits));
+
+        let path = write_morgue_file(&dir.path().join("morgue"), &morgue).unwrap();
+        let stored: MorgueFile = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(stored, morgue);
+    }
+
+    #[test]
+    fn defeat_morgue_keeps_the_final_blow() {
+        let mut game = Game::new(2745, &ContentPack::default(), GameMode::Ironman);
+        game.damage_player(99, DamageSource::Hazard, None);
+        let completion = AppCompletion::Outcome(RunOutcome::Defeat(DeathCause::Hazard));
+        let death = MorgueFile::from_run(&ContentPack::default(), &game, &completion)
+            .death
+            .expect("a death record");
+
+        assert_eq!((death.killer, death.source, death.damage), (None, DamageSource::Hazard, 99));
+        assert!(death.summary.starts_with("Died of a hazard (hazard, 99 damage)"));
+        assert_eq!(death.final_moments.len(), game.death_record().unwrap().recent_events.len());
+    }
+}
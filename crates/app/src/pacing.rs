@@ -0,0 +1,54 @@
+//! Wall-clock pacing of accepted inputs, journaled when the run is launched with `--record-pacing`.
+//! Pacing is analysis metadata only; a journal replays identically with or without it.
+
+use core::InputPacing;
+
+use crate::app_loop::AppMode;
+
+/// Tracks real time between accepted inputs and how much of it the game sat waiting on the player.
+pub struct PacingClock {
+    last_input_ms: u64,
+    last_frame_ms: u64,
+    paused_ms: u64,
+}
+
+impl PacingClock {
+    pub fn new(now_ms: u64) -> Self {
+        Self { last_input_ms: now_ms, last_frame_ms: now_ms, paused_ms: 0 }
+    }
+
+    /// Account for the time since the previous frame, spent in `mode`.
+    pub fn observe_frame(&mut self, now_ms: u64, mode: &AppMode) {
+        let elapsed = now_ms.saturating_sub(self.last_frame_ms);
+        if matches!(mode, AppMode::Paused | AppMode::Manual | AppMode::PendingPrompt { .. }) {
+            self.paused_ms += elapsed;
+        }
+        self.last_frame_ms = now_ms;
+    }
+
+    /// Pacing for an input accepted at `now_ms`, which starts the next interval.
+    pub fn stamp(&mut self, now_ms: u64) -> InputPacing {
+        let wall_ms_since_prev = now_ms.saturating_sub(self.last_input_ms);
+        let pacing =
+            InputPacing { wall_ms_since_prev, paused_ms: self.paused_ms.min(wall_ms_since_prev) };
+        self.last_input_ms = now_ms;
+        self.paused_ms = 0;
+        pacing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamps_split_wall_time_into_paused_and_running() {
+        let mut clock = PacingClock::new(1_000);
+        clock.observe_frame(1_400, &AppMode::Paused);
+        clock.observe_frame(1_500, &AppMode::AutoPlay);
+        assert_eq!(clock.stamp(1_500), InputPacing { wall_ms_since_prev: 500, paused_ms: 400 });
+
+        clock.observe_frame(1_600, &AppMode::AutoPlay);
+        assert_eq!(clock.stamp(1_650), InputPacing { wall_ms_since_prev: 150, paused_ms: 0 });
+    }
+}
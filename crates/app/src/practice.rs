@@ -0,0 +1,104 @@
+//! Practice arenas chosen on the command line: `--arena` lists the enemies to fight and
+//! `--kit` the items to start with. Practice runs skip mapgen and stay off the record,
+//! so they write no journal, morgue or run code.
+
+use core::content::ENEMY_KINDS;
+use core::scenario::Scenario;
+use core::{ActorKind, ContentPack};
+
+/// The arena `--arena` and `--kit` describe, checked against `content`; `None` without `--arena`.
+pub fn resolve_practice_arena_from_args(
+    args: &[String],
+    content: &ContentPack,
+) -> Result<Option<Scenario>, String> {
+    let enemies = flag_value(args, "--arena")?;
+    let kit = flag_value(args, "--kit")?;
+    let Some(enemies) = enemies else {
+        return match kit {
+            Some(_) => Err("--kit needs an --arena to equip".to_string()),
+            None => Ok(None),
+        };
+    };
+
+    let enemies = list_items(enemies).map(parse_enemy).collect::<Result<Vec<_>, _>>()?;
+    let equipment = kit.map(|kit| list_items(kit).map(str::to_string).collect());
+    let arena = Scenario::arena(&enemies, equipment.unwrap_or_default())
+        .map_err(|error| error.to_string())?;
+    arena.starting_kit(content).map_err(|error| error.to_string())?;
+    Ok(Some(arena))
+}
+
+fn list_items(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
+fn parse_enemy(name: &str) -> Result<ActorKind, String> {
+    ENEMY_KINDS
+        .into_iter()
+        .find(|kind| format!("{kind:?}").eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("unknown arena enemy '{name}'"))
+}
+
+/// The value of `flag`, given as `flag value` or `flag=value` at most once.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Result<Option<&'a str>, String> {
+    let mut selected = None;
+    let mut index = 1usize;
+
+    while index < args.len() {
+        let argument = args[index].as_str();
+        let value = if argument == flag {
+            let Some(value) = args.get(index + 1) else {
+                return Err(format!("missing value for {flag}"));
+            };
+            index += 1;
+            Some(value.as_str())
+        } else {
+            argument.strip_prefix(flag).and_then(|rest| rest.strip_prefix('='))
+        };
+
+        if let Some(value) = value {
+            if selected.is_some() {
+                return Err(format!("{flag} provided more than once"));
+            }
+            selected = Some(value);
+        }
+        index += 1;
+    }
+
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::content::keys;
+
+    fn as_args(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|part| part.to_string()).collect()
+    }
+
+    #[test]
+    fn arena_and_kit_flags_build_a_practice_scenario() {
+        let content = ContentPack::default();
+        let args =
+            as_args(&["game", "--arena", "goblin, FeralHound,goblin", "--kit=weapon_iron_mace"]);
+        let arena = resolve_practice_arena_from_args(&args, &content).unwrap().unwrap();
+        let kinds: Vec<ActorKind> = arena.enemies.iter().map(|enemy| enemy.kind).collect();
+        assert_eq!(kinds, [ActorKind::Goblin, ActorKind::FeralHound, ActorKind::Goblin]);
+        assert_eq!(arena.equipment, [keys::WEAPON_IRON_MACE]);
+
+        assert_eq!(resolve_practice_arena_from_args(&as_args(&["game"]), &content), Ok(None));
+    }
+
+    #[test]
+    fn bad_practice_flags_are_rejected() {
+        let content = ContentPack::default();
+        let rejects =
+            |parts: &[&str]| resolve_practice_arena_from_args(&as_args(parts), &content).is_err();
+        assert!(rejects(&["game", "--kit", "weapon_iron_mace"]));
+        assert!(rejects(&["game", "--arena", "dragon"]));
+        assert!(rejects(&["game", "--arena", "player"]));
+        assert!(rejects(&["game", "--arena", "goblin", "--kit", "weapon_missing"]));
+        assert!(rejects(&["game", "--arena", "goblin", "--arena=goblin"]));
+    }
+}
@@ -0,0 +1,131 @@
+//! Player profile kept across runs: the enemy kinds met so far, which unlock their
+//! encyclopedia entries, and the kinds killed, whose dangers examining an enemy then shows.
+//! Stored beside the app settings and never journaled.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use core::{ActorKind, EventLog, GameState, LogEvent};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::APP_NAME;
+use crate::save_file::{SaveSchema, load_save, write_save_atomic};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerProfile {
+    /// Enemy kinds the player has seen in any run.
+    pub encountered: BTreeSet<ActorKind>,
+    /// Enemy kinds the player has killed in any run.
+    #[serde(default)]
+    pub slain: BTreeSet<ActorKind>,
+    /// Run log events already checked for kills; a log shorter than this is a new run.
+    #[serde(skip)]
+    logged: u64,
+    /// Set when `encountered` or `slain` grew and should be written back; the caller clears it.
+    #[serde(skip)]
+    pub unsaved: bool,
+}
+
+impl PlayerProfile {
+    pub fn get_default_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
+            let mut path = proj_dirs.data_dir().to_path_buf();
+            path.push("profile.json");
+            path
+        })
+    }
+
+    /// Profile from the default path; a missing or unreadable file means a fresh profile.
+    pub fn load_default() -> Self {
+        Self::get_default_path().and_then(|path| load_save::<Self>(&path).ok()).unwrap_or_default()
+    }
+
+    /// Write the profile to the default path, returning a warning for the log on failure.
+    pub fn save_default(&self) -> Result<(), String> {
+        let path = Self::get_default_path().ok_or("Warning: no settings directory for profile")?;
+        write_save_atomic(&path, self)
+            .map_err(|error| format!("Warning: failed to save player profile: {error}"))
+    }
+
+    /// Record every enemy kind visible right now, returning the ones met for the first time.
+    pub fn unlock_visible(&mut self, state: &GameState) -> Vec<ActorKind> {
+        let visible: BTreeSet<ActorKind> = state
+            .actors
+            .iter()
+            .filter(|(id, actor)| *id != state.player_id && state.map.is_visible(actor.pos))
+            .map(|(_, actor)| actor.kind)
+            .collect();
+        let unlocked: Vec<ActorKind> =
+            visible.into_iter().filter(|kind| self.encountered.insert(*kind)).collect();
+        self.unsaved |= !unlocked.is_empty();
+        unlocked
+    }
+
+    /// Record the kinds of enemies slain since the last call, returning the first kills.
+    pub fn record_kills(&mut self, log: &EventLog) -> Vec<ActorKind> {
+        let cursor = if self.logged <= log.total_logged() { self.logged } else { 0 };
+        self.logged = log.total_logged();
+        let first_kills: Vec<ActorKind> = log
+            .since(cursor)
+            .filter_map(|event| match event {
+                LogEvent::EnemySlain { kind } => Some(*kind),
+                _ => None,
+            })
+            .filter(|kind| self.slain.insert(*kind))
+            .collect();
+        self.unsaved |= !first_kills.is_empty();
+        first_kills
+    }
+}
+
+impl SaveSchema for PlayerProfile {
+    const KIND: &'static str = "player_profile";
+    const VERSION: u32 = 1;
+
+    fn migrate(from_version: u32, _payload: Value) -> Result<Value, String> {
+        Err(format!("no migration from version {from_version}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{ContentPack, Game, GameMode};
+    use tempfile::tempdir;
+
+    #[test]
+    fn visible_enemies_unlock_once_and_the_profile_round_trips() {
+        let game = Game::new(2729, &ContentPack::default(), GameMode::Ironman);
+        let mut state = game.state().clone();
+        state.map.visible.fill(true);
+        let mut profile = PlayerProfile::default();
+
+        let unlocked = profile.unlock_visible(&state);
+        assert!(!unlocked.is_empty() && profile.unsaved);
+        assert!(!unlocked.contains(&ActorKind::Player));
+        profile.unsaved = false;
+        assert!(profile.unlock_visible(&state).is_empty());
+        assert!(!profile.unsaved);
+
+        let path = tempdir().unwrap().path().join("profile.json");
+        write_save_atomic(&path, &profile).unwrap();
+        assert_eq!(load_save::<PlayerProfile>(&path).unwrap(), profile);
+    }
+
+    #[test]
+    fn kills_are_recorded_once_each() {
+        let mut game = Game::new(2760, &ContentPack::default(), GameMode::Ironman);
+        let mut profile = PlayerProfile::default();
+        game.push_log(LogEvent::EnemySlain { kind: ActorKind::Goblin });
+        game.push_log(LogEvent::EnemySlain { kind: ActorKind::Goblin });
+
+        assert_eq!(profile.record_kills(game.log()), [ActorKind::Goblin]);
+        assert!(profile.unsaved && profile.slain.contains(&ActorKind::Goblin));
+        game.push_log(LogEvent::EnemySlain { kind: ActorKind::Goblin });
+        game.push_log(LogEvent::EnemySlain { kind: ActorKind::FeralHound });
+        assert_eq!(profile.record_kills(game.log()), [ActorKind::FeralHound]);
+        assert!(profile.record_kills(game.log()).is_empty(), "events are only read once");
+    }
+}
@@ -0,0 +1,146 @@
+//! Run bundles: an in-progress run packed into one file so it can move between computers.
+//! A bundle holds the run summary, the journal verbatim and the hash of the content it was
+//! played with; importing installs the journal, and the normal journal replay rebuilds the run.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::run_state_file::RunStateFile;
+use crate::save_file::{SaveLoadError, SaveSchema, load_save, write_save_atomic};
+use core::{ContentPack, Game, load_journal_from_file};
+
+/// A bundle action asked for from the command palette, carried out by the frontend.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BundleRequest {
+    Export(PathBuf),
+    Import(PathBuf),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RunBundle {
+    pub run_state: RunStateFile,
+    /// `ContentPack::content_hash` of the build that exported the run.
+    pub content_hash: u64,
+    /// The journal file's text, header line included.
+    pub journal: String,
+}
+
+impl RunBundle {
+    /// Bundle `game` with the journal at `journal_path`, which must be the journal of this run.
+    pub fn from_run(game: &Game, journal_path: &Path, content: &ContentPack) -> io::Result<Self> {
+        let loaded =
+            load_journal_from_file(journal_path).map_err(|e| io::Error::other(e.to_string()))?;
+        if loaded.journal.seed != game.seed() {
+            return Err(io::Error::other("the journal on disk belongs to a different run"));
+        }
+        Ok(Self {
+            run_state: RunStateFile::from_game(game),
+            content_hash: content.content_hash(),
+            journal: fs::read_to_string(journal_path)?,
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        write_save_atomic(path, self)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, SaveLoadError> {
+        load_save(path)
+    }
+
+    /// Refuse a bundle exported with different content: its journal would replay differently.
+    pub fn check_compatible(&self, content: &ContentPack) -> Result<(), String> {
+        if self.content_hash == content.content_hash() {
+            Ok(())
+        } else {
+            Err(format!(
+                "bundle was exported with different game content ({:016x}, this build has {:016x})",
+                self.content_hash,
+                content.content_hash()
+            ))
+        }
+    }
+
+    /// Check the bundled journal, then write it over `journal_path` and return the run seed.
+    /// Nothing is replaced unless the journal loads and belongs to the bundled run.
+    pub fn install_journal(&self, journal_path: &Path) -> Result<u64, String> {
+        let staged = journal_path.with_extension("import.tmp");
+        fs::write(&staged, &self.journal).map_err(|e| format!("failed to stage journal: {e}"))?;
+        let checked = match load_journal_from_file(&staged) {
+            Ok(loaded) if loaded.journal.seed == self.run_state.run_seed => Ok(()),
+            Ok(_) => Err("bundled journal belongs to a different run".to_string()),
+            Err(error) => Err(format!("bundled journal is damaged: {error}")),
+        };
+        if let Err(message) = checked {
+            let _ = fs::remove_file(&staged);
+            return Err(message);
+        }
+        fs::rename(&staged, journal_path).map_err(|e| format!("failed to install journal: {e}"))?;
+        Ok(self.run_state.run_seed)
+    }
+}
+
+impl SaveSchema for RunBundle {
+    const KIND: &'static str = "run_bundle";
+    const VERSION: u32 = 1;
+
+    fn migrate(from_version: u32, _payload: Value) -> Result<Value, String> {
+        Err(format!("no migration from version {from_version}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{GameMode, JournalWriter, PolicyUpdate, journal::InputPayload};
+    use tempfile::tempdir;
+
+    #[test]
+    fn exported_bundle_installs_its_journal_on_another_machine() {
+        let content = ContentPack::default();
+        let dir = tempdir().unwrap();
+        let journal_path = dir.path().join("journal.jsonl");
+        let mut game = Game::new(2721, &content, GameMode::Ironman);
+        let mut writer = JournalWriter::create(&journal_path, game.seed(), "dev", 0).unwrap();
+        game.advance(20);
+        let update = PolicyUpdate::RetreatHpThreshold(55);
+        game.apply_policy_update(update.clone()).unwrap();
+        let tick_boundary = game.current_tick();
+        writer
+            .append(tick_boundary, &InputPayload::PolicyUpdate { tick_boundary, update })
+            .unwrap();
+
+        let bundle_path = dir.path().join("run.bundle");
+        RunBundle::from_run(&game, &journal_path, &content).unwrap().write(&bundle_path).unwrap();
+
+        let other_journal = dir.path().join("other").join("journal.jsonl");
+        fs::create_dir_all(other_journal.parent().unwrap()).unwrap();
+        fs::write(&other_journal, "an unrelated run").unwrap();
+        let bundle = RunBundle::load(&bundle_path).unwrap();
+        bundle.check_compatible(&content).unwrap();
+        assert_eq!(bundle.install_journal(&other_journal), Ok(2721));
+        assert_eq!(fs::read_to_string(&other_journal).unwrap(), bundle.journal);
+    }
+
+    #[test]
+    fn foreign_content_and_mismatched_journals_are_refused() {
+        let content = ContentPack::default();
+        let dir = tempdir().unwrap();
+        let journal_path = dir.path().join("journal.jsonl");
+        JournalWriter::create(&journal_path, 7, "dev", 0).unwrap();
+        let mut bundle = RunBundle {
+            run_state: RunStateFile::from_game(&Game::new(8, &content, GameMode::Ironman)),
+            content_hash: content.content_hash() ^ 1,
+            journal: fs::read_to_string(&journal_path).unwrap(),
+        };
+        assert!(bundle.check_compatible(&content).is_err());
+
+        bundle.content_hash = content.content_hash();
+        let target = dir.path().join("installed.jsonl");
+        assert!(bundle.install_journal(&target).is_err(), "seed 7 journal in a seed 8 bundle");
+        assert!(!target.exists() && !target.with_extension("import.tmp").exists());
+    }
+}
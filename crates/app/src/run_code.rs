@@ -0,0 +1,206 @@
+//! Shareable run codes: seed, difficulty, mutators, and content hash in one checked string.
+//! Codes are unpadded RFC 4648 base32, so they survive chat clients and can be typed by hand.
+
+use core::{ContentPack, Difficulty};
+
+const RUN_CODE_VERSION: u8 = 1;
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+/// Version, seed, difficulty, mutators, and content hash.
+const PAYLOAD_LEN: usize = 1 + 8 + 1 + 2 + 8;
+const CHECKSUM_LEN: usize = 4;
+/// Mutator bit for runs played with the adaptive difficulty director.
+pub const MUTATOR_DIRECTOR: u16 = 1 << 0;
+/// Mutator bits this build understands; codes with any other bit set are rejected.
+pub const KNOWN_MUTATORS: u16 = MUTATOR_DIRECTOR;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RunCode {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub mutators: u16,
+    pub content_hash: u64,
+}
+
+impl RunCode {
+    pub fn new(seed: u64, difficulty: Difficulty, content: &ContentPack) -> Self {
+        Self { seed, difficulty, mutators: 0, content_hash: content.content_hash() }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(PAYLOAD_LEN + CHECKSUM_LEN);
+        bytes.push(RUN_CODE_VERSION);
+        bytes.extend_from_slice(&self.seed.to_be_bytes());
+        bytes.push(difficulty_byte(self.difficulty));
+        bytes.extend_from_slice(&self.mutators.to_be_bytes());
+        bytes.extend_from_slice(&self.content_hash.to_be_bytes());
+        let checksum = fnv1a_32(&bytes);
+        bytes.extend_from_slice(&checksum.to_be_bytes());
+        encode_base32(&bytes)
+    }
+
+    /// Parse a code, ignoring case, spaces, and dashes.
+    pub fn decode(code: &str) -> Result<Self, String> {
+        let bytes = decode_base32(code)?;
+        if bytes.len() != PAYLOAD_LEN + CHECKSUM_LEN {
+            return Err("run code has the wrong length".to_string());
+        }
+        let (payload, checksum) = bytes.split_at(PAYLOAD_LEN);
+        if fnv1a_32(payload).to_be_bytes() != checksum {
+            return Err("run code checksum does not match; check for typos".to_string());
+        }
+        if payload[0] != RUN_CODE_VERSION {
+            return Err(format!("unsupported run code version {}", payload[0]));
+        }
+
+        let seed = u64::from_be_bytes(payload[1..9].try_into().unwrap_or_default());
+        let difficulty = match payload[9] {
+            0 => Difficulty::Relaxed,
+            1 => Difficulty::Normal,
+            2 => Difficulty::Brutal,
+            other => return Err(format!("unknown difficulty {other} in run code")),
+        };
+        let mutators = u16::from_be_bytes([payload[10], payload[11]]);
+        if mutators & !KNOWN_MUTATORS != 0 {
+            return Err(format!(
+                "run code uses mutators this build does not know: {mutators:#06x}"
+            ));
+        }
+        let content_hash = u64::from_be_bytes(payload[12..20].try_into().unwrap_or_default());
+        Ok(Self { seed, difficulty, mutators, content_hash })
+    }
+
+    /// Refuse codes recorded against different content, which would not play the same run.
+    pub fn check_content(&self, content: &ContentPack) -> Result<(), String> {
+        if self.content_hash == content.content_hash() {
+            Ok(())
+        } else {
+            Err("run code was made with different game content".to_string())
+        }
+    }
+}
+
+pub fn resolve_run_code_from_args(args: &[String]) -> Result<Option<RunCode>, String> {
+    let mut selected = None;
+    let mut index = 1usize;
+
+    while index < args.len() {
+        let argument = args[index].as_str();
+        let value = if argument == "--run-code" {
+            let Some(value) = args.get(index + 1) else {
+                return Err("missing value for --run-code".to_string());
+            };
+            index += 1;
+            Some(value.as_str())
+        } else {
+            argument.strip_prefix("--run-code=")
+        };
+
+        if let Some(value) = value {
+            if selected.is_some() {
+                return Err("run code provided more than once".to_string());
+            }
+            selected = Some(RunCode::decode(value)?);
+        }
+        index += 1;
+    }
+
+    Ok(selected)
+}
+
+fn difficulty_byte(difficulty: Difficulty) -> u8 {
+    match difficulty {
+        Difficulty::Relaxed => 0,
+        Difficulty::Normal => 1,
+        Difficulty::Brutal => 2,
+    }
+}
+
+fn fnv1a_32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811C_9DC5, |hash, byte| (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193))
+}
+
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    encoded
+}
+
+fn decode_base32(code: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for ch in code.chars().filter(|ch| *ch != '-' && !ch.is_whitespace()) {
+        let upper = ch.to_ascii_uppercase() as u8;
+        let Some(value) = ALPHABET.iter().position(|letter| *letter == upper) else {
+            return Err(format!("'{ch}' is not a valid run code character"));
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RunCode {
+        RunCode::new(0xDEAD_BEEF_1234, Difficulty::Brutal, &ContentPack::default())
+    }
+
+    #[test]
+    fn run_codes_round_trip_loosely_typed() {
+        let code = sample().encode();
+        assert_eq!(code.len(), 39);
+        assert_eq!(RunCode::decode(&code), Ok(sample()));
+
+        let typed = format!("{}-{} ", code[..20].to_lowercase(), &code[20..]);
+        assert_eq!(RunCode::decode(&typed), Ok(sample()));
+    }
+
+    #[test]
+    fn corrupted_or_foreign_codes_are_rejected() {
+        let code = sample().encode();
+        let swapped = if code.starts_with('A') { "B" } else { "A" };
+        let typo = format!("{swapped}{}", &code[1..]);
+        assert!(RunCode::decode(&typo).is_err_and(|e| e.contains("checksum")));
+        assert!(RunCode::decode(&code[..30]).is_err_and(|e| e.contains("length")));
+        assert!(RunCode::decode("01").is_err_and(|e| e.contains("not a valid")));
+
+        let mutated = RunCode { mutators: 0x8000, ..sample() }.encode();
+        assert!(RunCode::decode(&mutated).is_err_and(|e| e.contains("mutators")));
+        let director = RunCode { mutators: MUTATOR_DIRECTOR, ..sample() };
+        assert_eq!(RunCode::decode(&director.encode()), Ok(director));
+
+        let foreign = RunCode { content_hash: 7, ..sample() };
+        assert!(foreign.check_content(&ContentPack::default()).is_err());
+        assert!(sample().check_content(&ContentPack::default()).is_ok());
+    }
+
+    #[test]
+    fn parses_run_code_flags() {
+        let args = |parts: &[&str]| parts.iter().map(|part| part.to_string()).collect::<Vec<_>>();
+        let code = sample().encode();
+        assert_eq!(resolve_run_code_from_args(&args(&["game"])), Ok(None));
+        let flag = format!("--run-code={code}");
+        assert_eq!(resolve_run_code_from_args(&args(&["game", &flag])), Ok(Some(sample())));
+        let twice = args(&["game", "--run-code", &code, &flag]);
+        assert!(resolve_run_code_from_args(&twice).is_err_and(|e| e.contains("more than once")));
+    }
+}
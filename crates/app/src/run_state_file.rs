@@ -1,14 +1,18 @@
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use serde_json::Value;
 use std::io;
 use std::path::{Path, PathBuf};
 
-use crate::APP_NAME;
+use core::Game;
+
+use crate::save_file::{
+    SaveLoadError, SaveSchema, load_save, strip_legacy_format_version, write_save_atomic,
+};
+use crate::{APP_NAME, format_snapshot_hash, get_current_unix_ms};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct RunStateFile {
-    pub format_version: u32,
     pub run_seed: u64,
     pub snapshot_hash_hex: String,
     pub tick: u64,
@@ -19,6 +23,19 @@ pub struct RunStateFile {
 }
 
 impl RunStateFile {
+    /// Summary of `game` as it stands now.
+    pub fn from_game(game: &Game) -> Self {
+        Self {
+            run_seed: game.seed(),
+            snapshot_hash_hex: format_snapshot_hash(game.snapshot_hash()),
+            tick: game.current_tick(),
+            floor_index: game.state().floor_index,
+            branch_profile: format!("{:?}", game.state().branch_profile),
+            active_god: format!("{:?}", game.state().active_god),
+            updated_at_unix_ms: get_current_unix_ms(),
+        }
+    }
+
     pub fn get_default_path() -> Option<PathBuf> {
         ProjectDirs::from("", "", APP_NAME).map(|proj_dirs| {
             let mut path = proj_dirs.data_dir().to_path_buf();
@@ -28,36 +45,35 @@ impl RunStateFile {
     }
 
     pub fn write_atomic(&self, path: &Path) -> io::Result<()> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        let tmp_path = path.with_extension("json.tmp");
-        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
-
-        fs::write(&tmp_path, json)?;
-        fs::rename(&tmp_path, path)?;
+        write_save_atomic(path, self)
+    }
 
-        Ok(())
+    pub fn load(path: &Path) -> Result<Self, SaveLoadError> {
+        load_save(path)
     }
+}
+
+impl SaveSchema for RunStateFile {
+    const KIND: &'static str = "run_state";
+    const VERSION: u32 = 1;
 
-    pub fn load(path: &Path) -> io::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let state: Self = serde_json::from_str(&content)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        Ok(state)
+    fn migrate(from_version: u32, payload: Value) -> Result<Value, String> {
+        match from_version {
+            0 => strip_legacy_format_version(payload),
+            other => Err(format!("no migration from version {other}")),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
     use tempfile::tempdir;
 
     #[test]
     fn test_json_roundtrip() {
         let state = RunStateFile {
-            format_version: 1,
             run_seed: 12345,
             snapshot_hash_hex: "0x00000000deadbeef".to_string(),
             tick: 100,
@@ -78,7 +94,6 @@ mod tests {
         let path = dir.path().join("state.json");
 
         let state = RunStateFile {
-            format_version: 1,
             run_seed: 99,
             snapshot_hash_hex: "0x123".to_string(),
             tick: 0,
@@ -98,4 +113,16 @@ mod tests {
         let tmp_path = path.with_extension("json.tmp");
         assert!(!tmp_path.exists());
     }
+
+    #[test]
+    fn loads_pre_envelope_run_state() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let legacy = r#"{"format_version":1,"run_seed":7,"snapshot_hash_hex":"0x1","tick":3,
+            "floor_index":1,"branch_profile":"None","active_god":"None","updated_at_unix_ms":0}"#;
+        fs::write(&path, legacy).unwrap();
+
+        let loaded = RunStateFile::load(&path).expect("legacy run state should migrate");
+        assert_eq!((loaded.run_seed, loaded.tick), (7, 3));
+    }
 }
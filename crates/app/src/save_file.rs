@@ -0,0 +1,232 @@
+//! Versioned envelope for the app's persisted JSON files (run state, UI profile).
+//! Every file carries a magic string, its kind, a schema version, and a payload hash.
+//! Older versions are upgraded one step at a time by each schema's `migrate`; newer,
+//! foreign, or damaged files are rejected with a `SaveLoadError` rather than misread.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Marks a file as one of ours before anything else is trusted.
+pub const SAVE_MAGIC: &str = "roguelike-save";
+
+/// A persisted schema with a stable kind name and a current version.
+pub trait SaveSchema: Serialize + DeserializeOwned {
+    const KIND: &'static str;
+    const VERSION: u32;
+
+    /// Upgrade a payload written at `from_version` to `from_version + 1`.
+    ///
+    /// Version 0 is the bare JSON written before envelopes existed.
+    fn migrate(from_version: u32, payload: Value) -> Result<Value, String>;
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveEnvelope {
+    magic: String,
+    kind: String,
+    version: u32,
+    payload_hash: String,
+    payload: Value,
+}
+
+#[derive(Debug)]
+pub enum SaveLoadError {
+    Io(io::Error),
+    /// The file is not valid JSON, or the payload does not match the schema.
+    Malformed(String),
+    /// The file has an envelope, but for a different kind of save.
+    WrongKind {
+        expected: &'static str,
+        found: String,
+    },
+    /// The payload was modified or truncated after it was written.
+    HashMismatch,
+    /// Written by a newer build; loading it could drop fields this build does not know.
+    FromFutureVersion {
+        found: u32,
+        supported: u32,
+    },
+    /// An old version could not be upgraded.
+    MigrationFailed {
+        from_version: u32,
+        message: String,
+    },
+}
+
+impl fmt::Display for SaveLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "save file I/O error: {e}"),
+            Self::Malformed(message) => write!(f, "save file is malformed: {message}"),
+            Self::WrongKind { expected, found } => {
+                write!(f, "expected a {expected} save file, found {found}")
+            }
+            Self::HashMismatch => write!(f, "save file payload hash does not match"),
+            Self::FromFutureVersion { found, supported } => {
+                write!(f, "save file version {found} is newer than supported version {supported}")
+            }
+            Self::MigrationFailed { from_version, message } => {
+                write!(f, "could not migrate save file from version {from_version}: {message}")
+            }
+        }
+    }
+}
+
+impl SaveLoadError {
+    /// True when there was simply no file yet, which callers usually treat as a fresh start.
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Self::Io(e) if e.kind() == io::ErrorKind::NotFound)
+    }
+}
+
+impl From<io::Error> for SaveLoadError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Write `value` in the current envelope via a temporary file and rename.
+pub fn write_save_atomic<T: SaveSchema>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let payload = serde_json::to_value(value).map_err(io::Error::other)?;
+    let envelope = SaveEnvelope {
+        magic: SAVE_MAGIC.to_string(),
+        kind: T::KIND.to_string(),
+        version: T::VERSION,
+        payload_hash: payload_hash_hex(&payload),
+        payload,
+    };
+    let json = serde_json::to_string_pretty(&envelope).map_err(io::Error::other)?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, json)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Load a save, migrating older versions up to `T::VERSION`.
+pub fn load_save<T: SaveSchema>(path: &Path) -> Result<T, SaveLoadError> {
+    let content = fs::read_to_string(path)?;
+    let raw: Value =
+        serde_json::from_str(&content).map_err(|e| SaveLoadError::Malformed(e.to_string()))?;
+
+    let (mut version, mut payload) = if raw.get("magic").and_then(Value::as_str) == Some(SAVE_MAGIC)
+    {
+        let envelope: SaveEnvelope =
+            serde_json::from_value(raw).map_err(|e| SaveLoadError::Malformed(e.to_string()))?;
+        if envelope.kind != T::KIND {
+            return Err(SaveLoadError::WrongKind { expected: T::KIND, found: envelope.kind });
+        }
+        if envelope.payload_hash != payload_hash_hex(&envelope.payload) {
+            return Err(SaveLoadError::HashMismatch);
+        }
+        (envelope.version, envelope.payload)
+    } else {
+        (0, raw)
+    };
+
+    if version > T::VERSION {
+        return Err(SaveLoadError::FromFutureVersion { found: version, supported: T::VERSION });
+    }
+    while version < T::VERSION {
+        payload = T::migrate(version, payload)
+            .map_err(|message| SaveLoadError::MigrationFailed { from_version: version, message })?;
+        version += 1;
+    }
+    serde_json::from_value(payload).map_err(|e| SaveLoadError::Malformed(e.to_string()))
+}
+
+/// Migration for pre-envelope files that kept their own `format_version` field.
+pub fn strip_legacy_format_version(payload: Value) -> Result<Value, String> {
+    let Value::Object(mut fields) = payload else {
+        return Err("legacy payload is not a JSON object".to_string());
+    };
+    match fields.remove("format_version").and_then(|version| version.as_u64()) {
+        Some(1) => Ok(Value::Object(fields)),
+        Some(other) => Err(format!("unknown legacy format_version {other}")),
+        None => Err("legacy payload has no format_version".to_string()),
+    }
+}
+
+/// FNV-1a over the payload's canonical JSON; object keys serialize in sorted order.
+fn payload_hash_hex(payload: &Value) -> String {
+    let hash = payload.to_string().bytes().fold(0xCBF2_9CE4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01B3)
+    });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Sample {
+        name: String,
+        level: u32,
+    }
+
+    impl SaveSchema for Sample {
+        const KIND: &'static str = "sample";
+        const VERSION: u32 = 2;
+
+        fn migrate(from_version: u32, payload: Value) -> Result<Value, String> {
+            match from_version {
+                0 => strip_legacy_format_version(payload),
+                1 => {
+                    let mut payload = payload;
+                    payload["level"] = Value::from(1);
+                    Ok(payload)
+                }
+                other => Err(format!("no migration from {other}")),
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_and_migrates_legacy_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        let sample = Sample { name: "ada".to_string(), level: 3 };
+        write_save_atomic(&path, &sample).unwrap();
+        assert_eq!(load_save::<Sample>(&path).unwrap(), sample);
+
+        fs::write(&path, r#"{"format_version":1,"name":"old"}"#).unwrap();
+        let migrated = load_save::<Sample>(&path).unwrap();
+        assert_eq!(migrated, Sample { name: "old".to_string(), level: 1 });
+    }
+
+    #[test]
+    fn rejects_tampered_foreign_and_future_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("sample.json");
+        write_save_atomic(&path, &Sample { name: "ada".to_string(), level: 3 }).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+
+        fs::write(&path, written.replace("\"ada\"", "\"eve\"")).unwrap();
+        assert!(matches!(load_save::<Sample>(&path), Err(SaveLoadError::HashMismatch)));
+
+        fs::write(&path, written.replace("\"sample\"", "\"other\"")).unwrap();
+        assert!(matches!(load_save::<Sample>(&path), Err(SaveLoadError::WrongKind { .. })));
+
+        fs::write(&path, written.replace("\"version\": 2", "\"version\": 9")).unwrap();
+        assert!(matches!(
+            load_save::<Sample>(&path),
+            Err(SaveLoadError::FromFutureVersion { found: 9, supported: 2 })
+        ));
+
+        fs::write(&path, r#"{"format_version":7,"name":"old"}"#).unwrap();
+        assert!(matches!(
+            load_save::<Sample>(&path),
+            Err(SaveLoadError::MigrationFailed { from_version: 0, .. })
+        ));
+    }
+}
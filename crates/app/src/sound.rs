@@ -0,0 +1,78 @@
+//! Sound playback for the app's sound-effect hooks, backed by macroquad audio.
+//! Built without the `audio` feature, cues and settings are still tracked but nothing plays.
+
+use std::path::PathBuf;
+
+use app::app_loop::AppMode;
+use app::audio::{AudioAction, AudioSettings, SoundCue, SoundHooks};
+use app::save_file::{load_save, write_save_atomic};
+use core::{Game, LogEvent};
+#[cfg(feature = "audio")]
+use macroquad::audio::{PlaySoundParams, Sound, load_sound_from_bytes, play_sound};
+
+pub struct SoundPlayer {
+    settings: AudioSettings,
+    settings_path: Option<PathBuf>,
+    hooks: SoundHooks,
+    #[cfg(feature = "audio")]
+    sounds: Vec<(SoundCue, Sound)>,
+}
+
+impl SoundPlayer {
+    pub async fn load() -> Self {
+        let settings_path = AudioSettings::get_default_path();
+        let settings = settings_path
+            .as_deref()
+            .and_then(|path| load_save::<AudioSettings>(path).ok())
+            .unwrap_or_default();
+        #[cfg(feature = "audio")]
+        let mut sounds = Vec::with_capacity(SoundCue::ALL.len());
+        #[cfg(feature = "audio")]
+        for cue in SoundCue::ALL {
+            if let Ok(sound) = load_sound_from_bytes(&cue.wav_bytes()).await {
+                sounds.push((cue, sound));
+            }
+        }
+        Self {
+            settings,
+            settings_path,
+            hooks: SoundHooks::default(),
+            #[cfg(feature = "audio")]
+            sounds,
+        }
+    }
+
+    /// Apply a settings key, persist the result, and note it in the event log.
+    pub fn apply_action(&mut self, action: AudioAction, game: &mut Game) {
+        self.settings.apply(action);
+        if let Some(path) = &self.settings_path
+            && write_save_atomic(path, &self.settings).is_err()
+        {
+            game.push_log(LogEvent::Notice("Warning: failed to save sound settings".to_string()));
+        }
+        game.push_log(LogEvent::Notice(self.settings.notice()));
+    }
+
+    /// Play the cues for everything that happened since the previous frame.
+    pub fn play_new_cues(&mut self, game: &Game, mode: &AppMode) {
+        let cues = self.hooks.next_cues(game, mode);
+        if self.settings.effective_volume() <= 0.0 {
+            return;
+        }
+        for cue in cues {
+            self.play(cue);
+        }
+    }
+
+    #[cfg(feature = "audio")]
+    fn play(&self, cue: SoundCue) {
+        if let Some((_, sound)) = self.sounds.iter().find(|(loaded, _)| *loaded == cue) {
+            let params =
+                PlaySoundParams { looped: false, volume: self.settings.effective_volume() };
+            play_sound(sound, params);
+        }
+    }
+
+    #[cfg(not(feature = "audio"))]
+    fn play(&self, _cue: SoundCue) {}
+}
@@ -0,0 +1,258 @@
+//! Live spectating: compact per-tick state deltas streamed to viewers over TCP.
+//! Each delta is one JSON line; log entries are composed with the same wording as the HUD.
+
+use core::{ActorKind, ContentPack, Game, Pos};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::net::{TcpListener, TcpStream};
+
+use crate::message::compose_log_event;
+
+/// Log lines sent to a viewer that joins mid-run.
+const JOIN_LOG_TAIL: usize = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ActorView {
+    pub kind: ActorKind,
+    pub pos: Pos,
+    pub hp: i32,
+}
+
+/// Changes since the previous delta; unchanged fields are omitted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpectatorDelta {
+    pub tick: u64,
+    pub snapshot_hash: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub floor_index: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actors: Option<Vec<ActorView>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log: Vec<String>,
+}
+
+/// Remembers what has already been sent so each delta only carries what changed.
+#[derive(Default)]
+pub struct SpectatorFeed {
+    last_tick: Option<u64>,
+    floor_index: Option<u8>,
+    actors: Vec<ActorView>,
+    logged: u64,
+}
+
+impl SpectatorFeed {
+    /// The delta since the last call, or `None` when nothing observable changed.
+    pub fn next_delta(&mut self, content: &ContentPack, game: &Game) -> Option<SpectatorDelta> {
+        let state = game.state();
+        // A shorter log means the run was restarted; start over from a full frame.
+        if game.log().total_logged() < self.logged {
+            *self = Self::default();
+        }
+
+        let actors = actor_views(game);
+        let tick_changed = self.last_tick != Some(game.current_tick());
+        let floor_changed = self.floor_index != Some(state.floor_index);
+        let actors_changed = self.last_tick.is_none() || actors != self.actors;
+        let log: Vec<String> = game
+            .log()
+            .since(self.logged)
+            .map(|event| compose_log_event(content, game, event).plain_text())
+            .collect();
+        if !tick_changed && !floor_changed && !actors_changed && log.is_empty() {
+            return None;
+        }
+
+        self.last_tick = Some(game.current_tick());
+        self.floor_index = Some(state.floor_index);
+        self.logged = game.log().total_logged();
+        Some(SpectatorDelta {
+            tick: game.current_tick(),
+            snapshot_hash: game.snapshot_hash(),
+            floor_index: floor_changed.then_some(state.floor_index),
+            actors: actors_changed.then(|| {
+                self.actors = actors;
+                self.actors.clone()
+            }),
+            log,
+        })
+    }
+}
+
+fn actor_views(game: &Game) -> Vec<ActorView> {
+    let mut views: Vec<ActorView> = game
+        .state()
+        .actors
+        .values()
+        .map(|actor| ActorView { kind: actor.kind, pos: actor.pos, hp: actor.hp })
+        .collect();
+    views.sort_by_key(|view| (view.pos.y, view.pos.x, view.kind));
+    views
+}
+
+/// Accepts viewers on a TCP socket and fans deltas out to all of them.
+pub struct SpectatorServer {
+    listener: TcpListener,
+    viewers: Vec<TcpStream>,
+    feed: SpectatorFeed,
+}
+
+impl SpectatorServer {
+    pub fn bind(addr: &str) -> Result<Self, String> {
+        let listener =
+            TcpListener::bind(addr).map_err(|e| format!("cannot listen on {addr}: {e}"))?;
+        listener.set_nonblocking(true).map_err(|e| format!("cannot listen on {addr}: {e}"))?;
+        Ok(Self { listener, viewers: Vec::new(), feed: SpectatorFeed::default() })
+    }
+
+    pub fn local_addr(&self) -> Option<String> {
+        self.listener.local_addr().ok().map(|addr| addr.to_string())
+    }
+
+    pub fn viewer_count(&self) -> usize {
+        self.viewers.len()
+    }
+
+    /// Send connected viewers the latest delta, then admit pending viewers with a full frame.
+    pub fn broadcast(&mut self, content: &ContentPack, game: &Game) {
+        if let Some(delta) = self.feed.next_delta(content, game) {
+            let line = delta_line(&delta);
+            self.viewers.retain_mut(|viewer| send_line(viewer, &line));
+        }
+        while let Ok((stream, _)) = self.listener.accept() {
+            self.admit(stream, content, game);
+        }
+    }
+
+    fn admit(&mut self, mut stream: TcpStream, content: &ContentPack, game: &Game) {
+        let configured = stream.set_nonblocking(true).is_ok() && stream.set_nodelay(true).is_ok();
+        let Some(mut frame) = SpectatorFeed::default().next_delta(content, game) else {
+            return;
+        };
+        let skipped = frame.log.len().saturating_sub(JOIN_LOG_TAIL);
+        frame.log.drain(..skipped);
+        if configured && send_line(&mut stream, &delta_line(&frame)) {
+            self.viewers.push(stream);
+        }
+    }
+}
+
+/// Write a whole line without blocking. A viewer whose socket buffer cannot take it has fallen
+/// behind and is dropped, since a partial line would corrupt its stream.
+fn send_line(viewer: &mut TcpStream, line: &str) -> bool {
+    matches!(viewer.write(line.as_bytes()), Ok(written) if written == line.len())
+}
+
+fn delta_line(delta: &SpectatorDelta) -> String {
+    let mut line = serde_json::to_string(delta).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+pub fn resolve_spectate_addr_from_args(args: &[String]) -> Result<Option<String>, String> {
+    let mut selected = None;
+    let mut index = 1usize;
+
+    while index < args.len() {
+        let argument = args[index].as_str();
+        let value = if argument == "--spectate" {
+            let Some(value) = args.get(index + 1) else {
+                return Err("missing value for --spectate".to_string());
+            };
+            index += 1;
+            Some(value.as_str())
+        } else {
+            argument.strip_prefix("--spectate=")
+        };
+
+        if let Some(value) = value {
+            if selected.is_some() {
+                return Err("spectate address provided more than once".to_string());
+            }
+            selected = Some(value.to_string());
+        }
+        index += 1;
+    }
+
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::{GameMode, LogEvent};
+    use std::io::{BufRead, BufReader};
+    use std::thread;
+    use std::time::Duration;
+
+    /// Broadcast until the pending viewer is admitted, giving up rather than spinning forever.
+    fn admit_viewer(server: &mut SpectatorServer, content: &ContentPack, game: &Game) {
+        for _ in 0..1_000 {
+            server.broadcast(content, game);
+            if server.viewer_count() > 0 {
+                return;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        panic!("the viewer was never admitted");
+    }
+
+    #[test]
+    fn feed_sends_full_frame_then_only_changes() {
+        let content = ContentPack::default();
+        let mut game = Game::new(77, &content, GameMode::Ironman);
+        let mut feed = SpectatorFeed::default();
+
+        let first = feed.next_delta(&content, &game).expect("first delta is a full frame");
+        assert_eq!(first.floor_index, Some(game.state().floor_index));
+        assert!(first.actors.is_some());
+        assert_eq!(feed.next_delta(&content, &game), None, "nothing changed since the last delta");
+
+        game.push_log(LogEvent::Notice("hello".to_string()));
+        let logged = feed.next_delta(&content, &game).expect("new log lines produce a delta");
+        assert_eq!(logged.log, vec!["hello".to_string()]);
+        assert_eq!(logged.floor_index, None);
+        assert_eq!(logged.actors, None);
+    }
+
+    #[test]
+    fn viewers_receive_json_lines_over_tcp() {
+        let content = ContentPack::default();
+        let mut game = Game::new(78, &content, GameMode::Ironman);
+        let mut server = SpectatorServer::bind("127.0.0.1:0").expect("bind loopback");
+        let viewer = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        let mut reader = BufReader::new(viewer);
+
+        admit_viewer(&mut server, &content, &game);
+        game.advance(1);
+        server.broadcast(&content, &game);
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let joined: SpectatorDelta = serde_json::from_str(&line).unwrap();
+        assert!(joined.actors.is_some());
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        let next: SpectatorDelta = serde_json::from_str(&line).unwrap();
+        assert_eq!(next.tick, game.current_tick());
+        assert_eq!(next.snapshot_hash, game.snapshot_hash());
+    }
+
+    #[test]
+    fn a_viewer_that_stops_reading_is_dropped() {
+        let content = ContentPack::default();
+        let mut game = Game::new(79, &content, GameMode::Ironman);
+        let mut server = SpectatorServer::bind("127.0.0.1:0").expect("bind loopback");
+        let _stalled = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+        admit_viewer(&mut server, &content, &game);
+
+        let filler = "x".repeat(64 * 1024);
+        for _ in 0..1_000 {
+            game.push_log(LogEvent::Notice(filler.clone()));
+            server.broadcast(&content, &game);
+            if server.viewer_count() == 0 {
+                return;
+            }
+        }
+        panic!("a viewer that never reads was kept");
+    }
+}
@@ -1,13 +1,28 @@
 //! Rendering for the main game frame and UI panels.
 
 use crate::game_layout::{FrameLayout, PanelRect};
-use crate::ui_text::{event_log_line, finished_recap_lines, stats_panel_lines, status_text};
-use app::app_loop::{AppMode, AppState};
-use core::{ActorKind, Game, GameState, Map, Pos, TileKind};
+use app::app_loop::{
+    AppMode, AppState, fight_preview_suffix, finished_recap_lines, palette_line,
+    queued_prompts_suffix, seen_by_line, stats_panel_lines, status_text,
+};
+use app::debug_overlay::DebugOverlay;
+use app::determinism::DeterminismCheck;
+use app::display_name::ambience_name;
+use core::content::DisplayData;
+use core::{ContentPack, Game, HazardKind, Interrupt, Map, MarkerKind, Pos};
 use macroquad::prelude::*;
 
+use encyclopedia::draw_encyclopedia;
+use event_log::draw_event_log;
+use examine::draw_examine;
+use heatmap::draw_movement_heatmap;
+use legend::{draw_glyph_legend, style_cell};
+use map_overlays::{build_actor_overlay, build_item_overlay};
+use policy_panel::draw_policy_panel;
+
 const BORDER_COLOR: Color = Color { r: 0.2, g: 0.2, b: 0.2, a: 1.0 };
 const BORDER_THICKNESS: f32 = 1.0;
+const GHOST_COLOR: Color = Color { r: 0.4, g: 0.8, b: 0.4, a: 0.45 };
 const PANEL_PAD_X: f32 = 15.0;
 const PANEL_PAD_Y: f32 = 25.0;
 const LINE_HEIGHT: f32 = 18.0;
@@ -16,19 +31,46 @@ const STATS_LINE_STEP: f32 = 15.0;
 type OverlayCell = (&'static str, Color);
 
 pub fn draw_frame(
+    content: &ContentPack,
     game: &Game,
     app_state: &AppState,
     run_seed: u64,
     layout: &FrameLayout,
-    ui_scale: f32,
+    ghost_pos: Option<Pos>,
+    debug_overlay: &DebugOverlay,
 ) {
+    let ui_scale = app_state.ui_scale;
     draw_panel_borders(layout, ui_scale);
-    draw_ascii_map(game, layout.map, ui_scale);
-    draw_event_log(game, layout.event_log, ui_scale);
-    draw_status_panel(app_state, layout.status, ui_scale);
-    draw_stats_panel(game, app_state, run_seed, layout.stats, ui_scale);
+    draw_ascii_map(game, &content.display, layout.map, ghost_pos, ui_scale);
+    draw_movement_heatmap(game, app_state, layout.map, ui_scale);
+    if debug_overlay.visible {
+        draw_debug_overlay(&debug_overlay.lines(&game.engine_internals()), layout.map, ui_scale);
+    }
+    if app_state.legend_visible {
+        draw_glyph_legend(game, content, layout.map, ui_scale);
+    }
+    if app_state.encyclopedia_visible {
+        draw_encyclopedia(app_state, content, layout.map, ui_scale);
+    }
+    draw_examine(content, game, app_state, layout.map, ui_scale);
+    draw_event_log(content, game, layout.event_log, ui_scale);
+    draw_status_panel(content, game, app_state, layout.status, ui_scale);
+    draw_stats_panel(content, game, app_state, run_seed, layout.stats, ui_scale);
     draw_policy_panel(game, app_state, layout.policy, ui_scale);
-    draw_threat_panel(game, layout.threat, ui_scale);
+    draw_threat_panel(content, game, layout.threat, ui_scale);
+}
+
+/// Engine internals drawn over the top-right corner of `panel` on a dimmed backdrop.
+fn draw_debug_overlay(lines: &[String], panel: PanelRect, ui_scale: f32) {
+    let width = scaled(280.0, ui_scale).min(panel.width);
+    let height = scaled(PANEL_PAD_Y, ui_scale) + lines.len() as f32 * scaled(LINE_HEIGHT, ui_scale);
+    let x = panel.x + panel.width - width;
+    draw_rectangle(x, panel.y, width, height, Color { r: 0.0, g: 0.0, b: 0.0, a: 0.75 });
+    for (index, line) in lines.iter().enumerate() {
+        let y =
+            panel.y + scaled(PANEL_PAD_Y, ui_scale) + index as f32 * scaled(LINE_HEIGHT, ui_scale);
+        draw_text(line, x + scaled(8.0, ui_scale), y, scaled(STATS_FONT_SIZE, ui_scale), GREEN);
+    }
 }
 
 fn draw_panel_borders(layout: &FrameLayout, ui_scale: f32) {
@@ -46,8 +88,48 @@ fn draw_panel_borders(layout: &FrameLayout, ui_scale: f32) {
     }
 }
 
-fn draw_status_panel(app_state: &AppState, panel: PanelRect, ui_scale: f32) {
-    let status = status_text(&app_state.mode);
+fn draw_status_panel(
+    content: &ContentPack,
+    game: &Game,
+    app_state: &AppState,
+    panel: PanelRect,
+    ui_scale: f32,
+) {
+    let mut status = match &app_state.palette {
+        Some(buffer) => palette_line(buffer),
+        None => status_text(content, &app_state.mode),
+    };
+    if let (None, Some(seconds)) = (&app_state.palette, app_state.blitz_seconds_left()) {
+        status.push_str(&format!(" [{seconds}s]"));
+    }
+    if app_state.palette.is_none()
+        && let AppMode::PendingPrompt {
+            interrupt: Interrupt::EnemyEncounter { primary_enemy, .. },
+            ..
+        } = &app_state.mode
+    {
+        if let Some(name) = game.elite_name(*primary_enemy) {
+            status.push_str(&format!(" - {name}"));
+        }
+        if let Some(preview) = game.preview_fight(*primary_enemy) {
+            status.push_str(&fight_preview_suffix(&preview));
+        }
+    }
+    if app_state.sim_clock.turbo {
+        status.push_str(" [TURBO]");
+    }
+    if app_state.sim_clock.backlog() > 0 {
+        status.push_str(&format!(" [catching up {} ticks]", app_state.sim_clock.backlog()));
+    }
+    if let Some(batch) = game.prompt_batch() {
+        status.push_str(&queued_prompts_suffix(&batch.queued));
+    }
+    if app_state.palette.is_none() {
+        status.push_str(&format!(" | {}", game.floor_name()));
+        if let Some(ambience) = ambience_name(game.state().ambience) {
+            status.push_str(&format!(" ({ambience})"));
+        }
+    }
     draw_text(
         &status,
         panel.x + scaled(PANEL_PAD_X, ui_scale),
@@ -58,6 +140,7 @@ fn draw_status_panel(app_state: &AppState, panel: PanelRect, ui_scale: f32) {
 }
 
 fn draw_stats_panel(
+    content: &ContentPack,
     game: &Game,
     app_state: &AppState,
     run_seed: u64,
@@ -68,14 +151,17 @@ fn draw_stats_panel(
     let mut text_y = panel.y + scaled(PANEL_PAD_Y, ui_scale);
 
     if let AppMode::Finished(completion) = &app_state.mode {
-        for line in finished_recap_lines(game, run_seed, completion) {
+        let badge = app_state.determinism.as_ref().map(DeterminismCheck::badge);
+        for line in
+            finished_recap_lines(content, game, run_seed, completion).into_iter().chain(badge)
+        {
             draw_text(&line, text_x, text_y, scaled(20.0, ui_scale), WHITE);
             text_y += scaled(20.0, ui_scale);
         }
         return;
     }
 
-    let raw_lines = stats_panel_lines(game, run_seed);
+    let raw_lines = stats_panel_lines(content, game, run_seed);
     let visible_lines = fit_lines_to_panel(
         &raw_lines,
         panel.height,
@@ -116,87 +202,12 @@ fn fit_lines_to_panel(
     fitted_lines
 }
 
-fn draw_policy_panel(game: &Game, app_state: &AppState, panel: PanelRect, ui_scale: f32) {
+fn draw_threat_panel(content: &ContentPack, game: &Game, panel: PanelRect, ui_scale: f32) {
     let text_x = panel.x + scaled(PANEL_PAD_X, ui_scale);
     let mut text_y = panel.y + scaled(PANEL_PAD_Y, ui_scale);
-    let policy = &game.state().policy;
 
-    if matches!(app_state.mode, AppMode::Finished(_)) {
-        draw_text("Policy: run ended", text_x, text_y, scaled(20.0, ui_scale), YELLOW);
-        return;
-    }
-
-    draw_text("Policy:", text_x, text_y, scaled(20.0, ui_scale), YELLOW);
-    text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("[M]ode: {:?}", policy.fight_or_avoid),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
-    text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("s[T]ance: {:?}", policy.stance),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
-    text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("[P]riority: {:?}", policy.target_priority),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
-    text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("[R]etreat HP: {}%", policy.retreat_hp_threshold),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
-    text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("[H]eal: {:?}", policy.auto_heal_if_below_threshold),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
-    text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("[I]ntent: {:?}", policy.position_intent),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
-    text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("[E]xplore: {:?}", policy.exploration_mode),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
+    draw_text(&seen_by_line(content, game), text_x, text_y, scaled(20.0, ui_scale), RED);
     text_y += scaled(20.0, ui_scale);
-    draw_text(
-        &format!("[G]reed: {:?}", policy.resource_aggression),
-        text_x,
-        text_y,
-        scaled(18.0, ui_scale),
-        LIGHTGRAY,
-    );
-}
-
-fn draw_threat_panel(game: &Game, panel: PanelRect, ui_scale: f32) {
-    let text_x = panel.x + scaled(PANEL_PAD_X, ui_scale);
-    let mut text_y = panel.y + scaled(PANEL_PAD_Y, ui_scale);
-
     draw_text("Threat Trace:", text_x, text_y, scaled(20.0, ui_scale), RED);
     text_y += scaled(20.0, ui_scale);
 
@@ -216,18 +227,28 @@ fn draw_threat_panel(game: &Game, panel: PanelRect, ui_scale: f32) {
     }
 }
 
-fn draw_ascii_map(game: &Game, panel: PanelRect, ui_scale: f32) {
+fn draw_ascii_map(
+    game: &Game,
+    display: &DisplayData,
+    panel: PanelRect,
+    ghost_pos: Option<Pos>,
+    ui_scale: f32,
+) {
     let state = game.state();
     let map = &state.map;
-    let item_overlay = build_item_overlay(state);
-    let actor_overlay = build_actor_overlay(state);
+    let item_overlay = build_item_overlay(state, display);
+    let mut actor_overlay = build_actor_overlay(game, display);
+    if let Some(index) = ghost_pos.filter(|pos| map.is_visible(*pos)).and_then(|pos| map.index(pos))
+    {
+        actor_overlay[index].get_or_insert(("@", GHOST_COLOR));
+    }
 
     for y in 0..map.internal_height {
         for x in 0..map.internal_width {
             let position = Pos { x: x as i32, y: y as i32 };
             let discovered = map.is_discovered(position);
             let (glyph, final_color) =
-                resolve_cell_render(map, position, &item_overlay, &actor_overlay);
+                resolve_cell_render(map, display, position, &item_overlay, &actor_overlay);
             let text_x = if discovered {
                 panel.x + scaled(PANEL_PAD_X, ui_scale) + x as f32 * scaled(11.0, ui_scale)
             } else {
@@ -245,48 +266,9 @@ fn draw_ascii_map(game: &Game, panel: PanelRect, ui_scale: f32) {
     }
 }
 
-fn build_item_overlay(state: &GameState) -> Vec<Option<OverlayCell>> {
-    let map = &state.map;
-    let mut overlay = vec![None; map.internal_width * map.internal_height];
-    let mut visible_items: Vec<_> =
-        state.items.values().filter(|item| map.is_visible(item.pos)).collect();
-    visible_items.sort_by_key(|item| (item.pos.y, item.pos.x, item.kind));
-
-    for item in visible_items {
-        if let Some(index) = map_cell_index(map, item.pos) {
-            overlay[index] = Some(("!", YELLOW));
-        }
-    }
-
-    overlay
-}
-
-fn build_actor_overlay(state: &GameState) -> Vec<Option<OverlayCell>> {
-    let map = &state.map;
-    let mut overlay = vec![None; map.internal_width * map.internal_height];
-    let mut visible_actors: Vec<_> =
-        state.actors.values().filter(|actor| map.is_visible(actor.pos)).collect();
-    visible_actors.sort_by_key(|actor| (actor.pos.y, actor.pos.x, actor.kind));
-
-    for actor in visible_actors {
-        if let Some(index) = map_cell_index(map, actor.pos) {
-            overlay[index] = Some(actor_glyph_and_color(actor.kind));
-        }
-    }
-
-    overlay
-}
-
-fn map_cell_index(map: &Map, position: Pos) -> Option<usize> {
-    if map.in_bounds(position) {
-        Some((position.y as usize) * map.internal_width + (position.x as usize))
-    } else {
-        None
-    }
-}
-
 fn resolve_cell_render(
     map: &Map,
+    display: &DisplayData,
     position: Pos,
     item_overlay: &[Option<OverlayCell>],
     actor_overlay: &[Option<OverlayCell>],
@@ -295,12 +277,29 @@ fn resolve_cell_render(
         return (" ", LIGHTGRAY);
     }
 
-    let mut glyph = tile_glyph(map.tile_at(position));
-    let mut final_color = if map.is_visible(position) { WHITE } else { GRAY };
+    let (mut glyph, mut final_color) = style_cell(display.tile(map.tile_at(position)));
+    if !map.is_visible(position) {
+        final_color = GRAY;
+    }
+    if let Some(marker) = map.marker_at(position) {
+        (glyph, final_color) = match marker {
+            MarkerKind::Danger => ("!", RED),
+            MarkerKind::LootLater => ("$", GOLD),
+            MarkerKind::Stairs => (">", SKYBLUE),
+            MarkerKind::Avoid => ("x", MAGENTA),
+        };
+    }
 
     if map.is_visible(position)
-        && let Some(index) = map_cell_index(map, position)
+        && let Some(index) = map.index(position)
     {
+        if let Some(hazard) = map.spreading_hazard_at(position) {
+            (glyph, final_color) = match hazard.kind {
+                HazardKind::Fire => ("^", ORANGE),
+                HazardKind::Gas => ("~", GREEN),
+                HazardKind::Rubble => ("%", BROWN),
+            };
+        }
         if let Some((item_glyph, item_color)) = item_overlay.get(index).and_then(|entry| *entry) {
             glyph = item_glyph;
             final_color = item_color;
@@ -310,60 +309,27 @@ fn resolve_cell_render(
             glyph = actor_glyph;
             final_color = actor_color;
         }
+    } else if let Some(remembered) =
+        map.index(position).and_then(|index| actor_overlay.get(index).copied().flatten())
+    {
+        // Out of sight, the actor overlay only holds fading last-known threats.
+        (glyph, final_color) = remembered;
     }
 
     (glyph, final_color)
 }
 
-fn draw_event_log(game: &Game, panel: PanelRect, ui_scale: f32) {
-    draw_text(
-        "Event log",
-        panel.x + scaled(PANEL_PAD_X, ui_scale),
-        panel.y + scaled(20.0, ui_scale),
-        scaled(24.0, ui_scale),
-        YELLOW,
-    );
-    let events = game.log();
-    let start = events.len().saturating_sub(10);
-
-    for (index, event) in events[start..].iter().enumerate() {
-        let line = event_log_line(event);
-        draw_text(
-            &line,
-            panel.x + scaled(PANEL_PAD_X, ui_scale),
-            panel.y + scaled(20.0, ui_scale) + (index as f32 + 1.0) * scaled(LINE_HEIGHT, ui_scale),
-            scaled(18.0, ui_scale),
-            LIGHTGRAY,
-        );
-    }
-}
-
 fn scaled(value: f32, ui_scale: f32) -> f32 {
     value * ui_scale
 }
 
-fn tile_glyph(tile: TileKind) -> &'static str {
-    match tile {
-        TileKind::Wall => "#",
-        TileKind::Floor => ".",
-        TileKind::ClosedDoor => "+",
-        TileKind::DownStairs => ">",
-    }
-}
-
-fn actor_glyph_and_color(kind: ActorKind) -> (&'static str, Color) {
-    match kind {
-        ActorKind::Player => ("@", GREEN),
-        ActorKind::Goblin => ("g", RED),
-        ActorKind::FeralHound => ("h", ORANGE),
-        ActorKind::BloodAcolyte => ("a", RED),
-        ActorKind::CorruptedGuard => ("C", BLUE),
-        ActorKind::LivingArmor => ("A", LIGHTGRAY),
-        ActorKind::Gargoyle => ("G", GRAY),
-        ActorKind::ShadowStalker => ("S", PURPLE),
-        ActorKind::AbyssalWarden => ("W", MAGENTA),
-    }
-}
+mod encyclopedia;
+mod event_log;
+mod examine;
+mod heatmap;
+mod legend;
+mod map_overlays;
+mod policy_panel;
 
 #[cfg(test)]
 mod tests;
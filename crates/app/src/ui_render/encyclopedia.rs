@@ -0,0 +1,34 @@
+//! Enemy encyclopedia screen drawn over the map panel while toggled on (F2).
+
+use super::{LINE_HEIGHT, PANEL_PAD_X, PANEL_PAD_Y, STATS_FONT_SIZE, scaled};
+use crate::game_layout::PanelRect;
+use app::app_loop::AppState;
+use app::encyclopedia::encyclopedia_lines;
+use core::ContentPack;
+use macroquad::prelude::*;
+
+pub(super) fn draw_encyclopedia(
+    app_state: &AppState,
+    content: &ContentPack,
+    panel: PanelRect,
+    ui_scale: f32,
+) {
+    draw_rectangle(
+        panel.x,
+        panel.y,
+        panel.width,
+        panel.height,
+        Color { r: 0.0, g: 0.0, b: 0.0, a: 0.9 },
+    );
+    let lines = encyclopedia_lines(&app_state.profile, content);
+    for (index, line) in lines.iter().enumerate() {
+        let y =
+            panel.y + scaled(PANEL_PAD_Y, ui_scale) + index as f32 * scaled(LINE_HEIGHT, ui_scale);
+        if y > panel.y + panel.height {
+            break;
+        }
+        let color = if index == 0 { YELLOW } else { WHITE };
+        let x = panel.x + scaled(PANEL_PAD_X, ui_scale);
+        draw_text(line, x, y, scaled(STATS_FONT_SIZE, ui_scale), color);
+    }
+}
@@ -0,0 +1,49 @@
+//! Event log panel drawing with per-category colors and emphasized names.
+
+use super::{LINE_HEIGHT, PANEL_PAD_X, scaled};
+use crate::game_layout::PanelRect;
+use app::message::{MessageCategory, compose_log_event};
+use core::{ContentPack, Game};
+use macroquad::prelude::*;
+
+const LOG_FONT_SIZE: f32 = 18.0;
+const VISIBLE_LOG_LINES: usize = 10;
+
+pub(super) fn draw_event_log(content: &ContentPack, game: &Game, panel: PanelRect, ui_scale: f32) {
+    draw_text(
+        "Event log",
+        panel.x + scaled(PANEL_PAD_X, ui_scale),
+        panel.y + scaled(20.0, ui_scale),
+        scaled(24.0, ui_scale),
+        YELLOW,
+    );
+    let font_size = scaled(LOG_FONT_SIZE, ui_scale);
+
+    for (index, event) in game.log().recent(VISIBLE_LOG_LINES).enumerate() {
+        let message = compose_log_event(content, game, event);
+        let color = category_color(message.category);
+        let mut x = panel.x + scaled(PANEL_PAD_X, ui_scale);
+        let y =
+            panel.y + scaled(20.0, ui_scale) + (index as f32 + 1.0) * scaled(LINE_HEIGHT, ui_scale);
+        for span in &message.spans {
+            if span.emphasized {
+                // Macroquad's default font has no bold face, so overstrike by one pixel.
+                draw_text(&span.text, x + 1.0, y, font_size, WHITE);
+                draw_text(&span.text, x, y, font_size, WHITE);
+            } else {
+                draw_text(&span.text, x, y, font_size, color);
+            }
+            x += measure_text(&span.text, None, font_size as u16, 1.0).width;
+        }
+    }
+}
+
+fn category_color(category: MessageCategory) -> Color {
+    match category {
+        MessageCategory::Exploration => LIGHTGRAY,
+        MessageCategory::Combat => Color { r: 1.0, g: 0.45, b: 0.4, a: 1.0 },
+        MessageCategory::Stealth => ORANGE,
+        MessageCategory::Item => Color { r: 0.5, g: 0.85, b: 1.0, a: 1.0 },
+        MessageCategory::System => GRAY,
+    }
+}
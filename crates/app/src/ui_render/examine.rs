@@ -0,0 +1,38 @@
+//! Examine box drawn along the top of the map panel while the mouse is over a visible enemy.
+
+use super::{LINE_HEIGHT, PANEL_PAD_X, STATS_FONT_SIZE, scaled};
+use crate::game_layout::PanelRect;
+use app::app_loop::AppState;
+use app::examine::{examine, examine_line};
+use core::{ContentPack, Game, Pos};
+use macroquad::prelude::*;
+
+/// Horizontal step of a discovered map cell, matching the ASCII map.
+const CELL_WIDTH: f32 = 11.0;
+
+pub(super) fn draw_examine(
+    content: &ContentPack,
+    game: &Game,
+    app_state: &AppState,
+    panel: PanelRect,
+    ui_scale: f32,
+) {
+    let (mouse_x, mouse_y) = mouse_position();
+    let column = (mouse_x - panel.x - scaled(PANEL_PAD_X, ui_scale)) / scaled(CELL_WIDTH, ui_scale);
+    let row = (mouse_y - panel.y - scaled(6.0, ui_scale)) / scaled(LINE_HEIGHT, ui_scale);
+    if column < 0.0 || row < 0.0 {
+        return;
+    }
+    let pos = Pos { x: column as i32, y: row as i32 };
+    let Some(examined) = examine(content, game.state(), &app_state.profile, pos) else {
+        return;
+    };
+    let line = examine_line(content, &examined);
+    let font_size = scaled(STATS_FONT_SIZE, ui_scale);
+    let width = measure_text(&line, None, font_size as u16, 1.0).width
+        + 2.0 * scaled(PANEL_PAD_X, ui_scale);
+    let height = scaled(LINE_HEIGHT, ui_scale) + scaled(8.0, ui_scale);
+    draw_rectangle(panel.x, panel.y, width, height, Color { r: 0.0, g: 0.0, b: 0.0, a: 0.8 });
+    let baseline = panel.y + scaled(LINE_HEIGHT, ui_scale);
+    draw_text(&line, panel.x + scaled(PANEL_PAD_X, ui_scale), baseline, font_size, WHITE);
+}
@@ -0,0 +1,43 @@
+//! Movement heatmap drawn over the map once the run has finished.
+//! Hotter tiles are redder and more opaque; unvisited tiles are left untouched.
+
+use super::{LINE_HEIGHT, PANEL_PAD_X, scaled};
+use crate::game_layout::PanelRect;
+use app::app_loop::{AppMode, AppState};
+use app::heatmap::{MAX_HEAT_LEVEL, heat_level};
+use core::{Game, Pos};
+use macroquad::prelude::*;
+
+/// Horizontal step of a discovered map cell, matching the ASCII map.
+const CELL_WIDTH: f32 = 11.0;
+
+pub(super) fn draw_movement_heatmap(
+    game: &Game,
+    app_state: &AppState,
+    panel: PanelRect,
+    ui_scale: f32,
+) {
+    if !matches!(app_state.mode, AppMode::Finished(_)) {
+        return;
+    }
+    let Some(visits) = game.run_stats().floor_visits.get(&game.state().floor_index) else {
+        return;
+    };
+    let max_count = visits.max_count();
+    for y in 0..visits.height {
+        for x in 0..visits.width {
+            let level = heat_level(visits.count_at(Pos { x: x as i32, y: y as i32 }), max_count);
+            if level == 0 {
+                continue;
+            }
+            let heat = f32::from(level) / f32::from(MAX_HEAT_LEVEL);
+            draw_rectangle(
+                panel.x + scaled(PANEL_PAD_X, ui_scale) + x as f32 * scaled(CELL_WIDTH, ui_scale),
+                panel.y + scaled(6.0, ui_scale) + y as f32 * scaled(LINE_HEIGHT, ui_scale),
+                scaled(CELL_WIDTH, ui_scale),
+                scaled(LINE_HEIGHT, ui_scale),
+                Color { r: 1.0, g: 0.8 * (1.0 - heat), b: 0.0, a: 0.15 + 0.35 * heat },
+            );
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! Glyph legend drawn over the bottom-right of the map panel while toggled on (F4).
+
+use super::{LINE_HEIGHT, OverlayCell, PANEL_PAD_Y, STATS_FONT_SIZE, scaled};
+use crate::game_layout::PanelRect;
+use app::glyph_legend::legend_entries;
+use core::content::{GlyphColor, GlyphStyle};
+use core::{ContentPack, Game};
+use macroquad::prelude::*;
+
+const LEGEND_WIDTH: f32 = 240.0;
+
+/// Glyph and color for a display-table entry; kinds missing from the table draw as `?`.
+pub(super) fn style_cell<K>(style: Option<&GlyphStyle<K>>) -> OverlayCell {
+    style.map_or(("?", WHITE), |style| (style.glyph, glyph_color(style.color)))
+}
+
+pub(super) fn draw_glyph_legend(
+    game: &Game,
+    content: &ContentPack,
+    panel: PanelRect,
+    ui_scale: f32,
+) {
+    let entries = legend_entries(game.state(), content);
+    let width = scaled(LEGEND_WIDTH, ui_scale).min(panel.width);
+    let height = (scaled(PANEL_PAD_Y, ui_scale)
+        + entries.len() as f32 * scaled(LINE_HEIGHT, ui_scale))
+    .min(panel.height);
+    let x = panel.x + panel.width - width;
+    let y = panel.y + panel.height - height;
+    draw_rectangle(x, y, width, height, Color { r: 0.0, g: 0.0, b: 0.0, a: 0.8 });
+    for (index, entry) in entries.iter().enumerate() {
+        let line_y =
+            y + scaled(PANEL_PAD_Y, ui_scale) + index as f32 * scaled(LINE_HEIGHT, ui_scale);
+        if line_y > panel.y + panel.height {
+            break;
+        }
+        let font_size = scaled(STATS_FONT_SIZE, ui_scale);
+        draw_text(
+            entry.glyph,
+            x + scaled(8.0, ui_scale),
+            line_y,
+            font_size,
+            glyph_color(entry.color),
+        );
+        draw_text(&entry.label, x + scaled(28.0, ui_scale), line_y, font_size, WHITE);
+    }
+}
+
+fn glyph_color(color: GlyphColor) -> Color {
+    match color {
+        GlyphColor::White => WHITE,
+        GlyphColor::Gray => GRAY,
+        GlyphColor::LightGray => LIGHTGRAY,
+        GlyphColor::Green => GREEN,
+        GlyphColor::Red => RED,
+        GlyphColor::Orange => ORANGE,
+        GlyphColor::Yellow => YELLOW,
+        GlyphColor::Gold => GOLD,
+        GlyphColor::Blue => BLUE,
+        GlyphColor::SkyBlue => SKYBLUE,
+        GlyphColor::Purple => PURPLE,
+        GlyphColor::Magenta => MAGENTA,
+    }
+}
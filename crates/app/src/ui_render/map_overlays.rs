@@ -0,0 +1,69 @@
+//! Per-cell item and actor overlays for the ASCII map, layered over the tile glyphs.
+//! Remembered enemies out of sight draw as their glyph, fading as the memory ages.
+
+use super::{OverlayCell, style_cell};
+use core::content::{DisplayData, ItemCategory};
+use core::{Game, GameState};
+use macroquad::prelude::*;
+
+/// Least opacity a last-known threat marker keeps until it is forgotten.
+const MIN_MEMORY_ALPHA: f32 = 0.15;
+
+pub(super) fn build_item_overlay(
+    state: &GameState,
+    display: &DisplayData,
+) -> Vec<Option<OverlayCell>> {
+    let map = &state.map;
+    let mut overlay = vec![None; map.internal_width * map.internal_height];
+    if let Some(index) =
+        state.altar_tile.filter(|pos| map.is_visible(*pos)).and_then(|pos| map.index(pos))
+    {
+        overlay[index] = Some(("_", GOLD));
+    }
+    for brazier in map.light.braziers.iter().filter(|pos| map.is_visible(**pos)) {
+        if let Some(index) = map.index(*brazier) {
+            overlay[index] = Some(("*", ORANGE));
+        }
+    }
+    let mut visible_items: Vec<_> =
+        state.items.values().filter(|item| map.is_visible(item.pos)).collect();
+    visible_items.sort_by_key(|item| (item.pos.y, item.pos.x, item.kind));
+
+    for item in visible_items {
+        if let Some(index) = map.index(item.pos) {
+            overlay[index] = Some(style_cell(display.item(ItemCategory::of(item.kind))));
+        }
+    }
+
+    overlay
+}
+
+/// Visible actors, plus faded markers where remembered enemies were last seen out of sight.
+pub(super) fn build_actor_overlay(game: &Game, display: &DisplayData) -> Vec<Option<OverlayCell>> {
+    let state = game.state();
+    let map = &state.map;
+    let mut overlay = vec![None; map.internal_width * map.internal_height];
+    let mut visible_actors: Vec<_> =
+        state.actors.values().filter(|actor| map.is_visible(actor.pos)).collect();
+    visible_actors.sort_by_key(|actor| (actor.pos.y, actor.pos.x, actor.kind));
+
+    for actor in visible_actors {
+        if let Some(index) = map.index(actor.pos) {
+            overlay[index] = Some(style_cell(display.actor(actor.kind)));
+        }
+    }
+    for threat in game.last_known_threats() {
+        if let Some(index) = map.index(threat.pos) {
+            let (glyph, color) = style_cell(display.actor(threat.kind));
+            overlay[index].get_or_insert((glyph, faded(color, threat.fade_percent)));
+        }
+    }
+
+    overlay
+}
+
+/// `color` dimmed to the share of memory left, `fade_percent` from 100 down to 0.
+fn faded(color: Color, fade_percent: u8) -> Color {
+    let alpha = MIN_MEMORY_ALPHA + (1.0 - MIN_MEMORY_ALPHA) * f32::from(fade_percent) / 100.0;
+    Color { a: color.a * alpha, ..color }
+}
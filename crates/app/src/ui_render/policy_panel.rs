@@ -0,0 +1,43 @@
+//! Policy panel drawing with the hotkey hint for each adjustable setting.
+
+use super::{PANEL_PAD_X, PANEL_PAD_Y, scaled};
+use crate::game_layout::PanelRect;
+use app::app_loop::{AppMode, AppState};
+use core::Game;
+use macroquad::prelude::*;
+
+pub(super) fn draw_policy_panel(
+    game: &Game,
+    app_state: &AppState,
+    panel: PanelRect,
+    ui_scale: f32,
+) {
+    let text_x = panel.x + scaled(PANEL_PAD_X, ui_scale);
+    let mut text_y = panel.y + scaled(PANEL_PAD_Y, ui_scale);
+    let policy = &game.state().policy;
+
+    if matches!(app_state.mode, AppMode::Finished(_)) {
+        draw_text("Policy: run ended", text_x, text_y, scaled(20.0, ui_scale), YELLOW);
+        return;
+    }
+
+    draw_text("Policy:", text_x, text_y, scaled(20.0, ui_scale), YELLOW);
+    let lines = [
+        format!("[M]ode: {:?}", policy.fight_or_avoid),
+        format!("s[T]ance: {:?}", policy.stance),
+        format!("[P]riority: {:?}", policy.target_priority),
+        format!("[R]etreat HP: {}%", policy.retreat_hp_threshold),
+        format!("[H]eal: {:?}", policy.auto_heal_if_below_threshold),
+        format!("[I]ntent: {:?}", policy.position_intent),
+        format!("[E]xplore: {:?}", policy.exploration_mode),
+        format!("[G]reed: {:?}", policy.resource_aggression),
+        format!("[U] auto-pickup: {} rules", policy.auto_pickup.len()),
+        format!("Auto-fight trivial: {}", if policy.auto_fight_trivial { "on" } else { "off" }),
+        format!("Auto-resolve: {}", if policy.auto_resolve_encounters { "on" } else { "off" }),
+        "[N] cycle marker here".to_string(),
+    ];
+    for line in &lines {
+        text_y += scaled(20.0, ui_scale);
+        draw_text(line, text_x, text_y, scaled(18.0, ui_scale), LIGHTGRAY);
+    }
+}
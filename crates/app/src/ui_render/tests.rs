@@ -1,6 +1,7 @@
-use super::{fit_lines_to_panel, map_cell_index, resolve_cell_render};
+use super::{fit_lines_to_panel, resolve_cell_render};
+use core::content::DisplayData;
 use core::{Map, Pos};
-use macroquad::prelude::{GRAY, LIGHTGRAY, RED, YELLOW};
+use macroquad::prelude::{Color, GRAY, LIGHTGRAY, RED, YELLOW};
 
 #[test]
 fn actor_overrides_item_and_tile() {
@@ -8,13 +9,14 @@ fn actor_overrides_item_and_tile() {
     let position = Pos { y: 1, x: 1 };
     map.reveal(position);
     map.set_visible(position, true);
-    let index = map_cell_index(&map, position).expect("position should be in bounds");
+    let index = map.index(position).expect("position should be in bounds");
     let mut item_overlay = vec![None; map.internal_width * map.internal_height];
     let mut actor_overlay = vec![None; map.internal_width * map.internal_height];
     item_overlay[index] = Some(("!", YELLOW));
     actor_overlay[index] = Some(("@", RED));
 
-    let rendered = resolve_cell_render(&map, position, &item_overlay, &actor_overlay);
+    let rendered =
+        resolve_cell_render(&map, &DisplayData::default(), position, &item_overlay, &actor_overlay);
     assert_eq!(rendered, ("@", RED));
 }
 
@@ -24,12 +26,13 @@ fn item_overrides_tile_when_visible() {
     let position = Pos { y: 1, x: 1 };
     map.reveal(position);
     map.set_visible(position, true);
-    let index = map_cell_index(&map, position).expect("position should be in bounds");
+    let index = map.index(position).expect("position should be in bounds");
     let mut item_overlay = vec![None; map.internal_width * map.internal_height];
     let actor_overlay = vec![None; map.internal_width * map.internal_height];
     item_overlay[index] = Some(("!", YELLOW));
 
-    let rendered = resolve_cell_render(&map, position, &item_overlay, &actor_overlay);
+    let rendered =
+        resolve_cell_render(&map, &DisplayData::default(), position, &item_overlay, &actor_overlay);
     assert_eq!(rendered, ("!", YELLOW));
 }
 
@@ -37,13 +40,14 @@ fn item_overrides_tile_when_visible() {
 fn undiscovered_cell_remains_hidden_even_if_overlay_exists() {
     let map = Map::new(3, 3);
     let position = Pos { y: 1, x: 1 };
-    let index = map_cell_index(&map, position).expect("position should be in bounds");
+    let index = map.index(position).expect("position should be in bounds");
     let mut item_overlay = vec![None; map.internal_width * map.internal_height];
     let mut actor_overlay = vec![None; map.internal_width * map.internal_height];
     item_overlay[index] = Some(("!", YELLOW));
     actor_overlay[index] = Some(("g", RED));
 
-    let rendered = resolve_cell_render(&map, position, &item_overlay, &actor_overlay);
+    let rendered =
+        resolve_cell_render(&map, &DisplayData::default(), position, &item_overlay, &actor_overlay);
     assert_eq!(rendered, (" ", LIGHTGRAY));
 }
 
@@ -55,7 +59,8 @@ fn hidden_discovered_cell_uses_dim_tile_without_entity_overlay() {
     let item_overlay = vec![None; map.internal_width * map.internal_height];
     let actor_overlay = vec![None; map.internal_width * map.internal_height];
 
-    let rendered = resolve_cell_render(&map, position, &item_overlay, &actor_overlay);
+    let rendered =
+        resolve_cell_render(&map, &DisplayData::default(), position, &item_overlay, &actor_overlay);
     assert_eq!(rendered, (".", GRAY));
 }
 
@@ -83,3 +88,19 @@ fn fit_lines_to_panel_returns_empty_when_no_vertical_space() {
     let fitted = fit_lines_to_panel(&lines, 20.0, 15.0, 25.0);
     assert!(fitted.is_empty());
 }
+
+#[test]
+fn out_of_sight_actor_overlay_draws_a_remembered_threat() {
+    let mut map = Map::new(3, 3);
+    let position = Pos { y: 1, x: 1 };
+    map.reveal(position);
+    let index = map.index(position).expect("position should be in bounds");
+    let item_overlay = vec![None; map.internal_width * map.internal_height];
+    let mut actor_overlay = vec![None; map.internal_width * map.internal_height];
+    let faded = Color { a: 0.5, ..RED };
+    actor_overlay[index] = Some(("g", faded));
+
+    let rendered =
+        resolve_cell_render(&map, &DisplayData::default(), position, &item_overlay, &actor_overlay);
+    assert_eq!(rendered, ("g", faded));
+}
@@ -2,15 +2,18 @@
 
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use serde_json::Value;
 use std::io;
 use std::path::{Path, PathBuf};
 
+use app::save_file::{
+    SaveLoadError, SaveSchema, load_save, strip_legacy_format_version, write_save_atomic,
+};
+
 use crate::APP_NAME;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct UiScaleFile {
-    pub format_version: u32,
     pub ui_scale: f32,
 }
 
@@ -24,22 +27,23 @@ impl UiScaleFile {
     }
 
     pub fn write_atomic(&self, path: &Path) -> io::Result<()> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
+        write_save_atomic(path, self)
+    }
 
-        let tmp_path = path.with_extension("json.tmp");
-        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
-        fs::write(&tmp_path, json)?;
-        fs::rename(&tmp_path, path)?;
-        Ok(())
+    pub fn load(path: &Path) -> Result<Self, SaveLoadError> {
+        load_save(path)
     }
+}
+
+impl SaveSchema for UiScaleFile {
+    const KIND: &'static str = "ui_scale";
+    const VERSION: u32 = 1;
 
-    pub fn load(path: &Path) -> io::Result<Self> {
-        let content = fs::read_to_string(path)?;
-        let state: Self = serde_json::from_str(&content)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
-        Ok(state)
+    fn migrate(from_version: u32, payload: Value) -> Result<Value, String> {
+        match from_version {
+            0 => strip_legacy_format_version(payload),
+            other => Err(format!("no migration from version {other}")),
+        }
     }
 }
 
@@ -50,7 +54,7 @@ mod tests {
 
     #[test]
     fn ui_scale_file_roundtrip() {
-        let state = UiScaleFile { format_version: 1, ui_scale: 1.35 };
+        let state = UiScaleFile { ui_scale: 1.35 };
         let json = serde_json::to_string(&state).expect("serialize");
         let decoded: UiScaleFile = serde_json::from_str(&json).expect("deserialize");
         assert_eq!(state, decoded);
@@ -60,7 +64,7 @@ mod tests {
     fn ui_scale_file_atomic_write_and_load() {
         let dir = tempdir().expect("tempdir");
         let path = dir.path().join("ui_scale.json");
-        let state = UiScaleFile { format_version: 1, ui_scale: 1.6 };
+        let state = UiScaleFile { ui_scale: 1.6 };
 
         state.write_atomic(&path).expect("write");
         let loaded = UiScaleFile::load(&path).expect("load");
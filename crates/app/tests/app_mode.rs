@@ -5,6 +5,9 @@ use core::{
 };
 use macroquad::prelude::KeyCode;
 
+/// One frame at 60 fps, which buys ten ticks of auto-play.
+const FRAME_SECONDS: f32 = 1.0 / 60.0;
+
 #[test]
 fn test_manual_stepping_preserves_suspended_state() {
     let content = ContentPack::default();
@@ -15,7 +18,7 @@ fn test_manual_stepping_preserves_suspended_state() {
     assert_eq!(app.mode, AppMode::Paused);
 
     // Press right to advance. Depending on the mock/game behavior, we might hit loot immediately.
-    app.tick(&mut game, &[KeyCode::Right]);
+    app.tick(&mut game, &[KeyCode::Right], FRAME_SECONDS);
 
     // If we hit a prompt, auto_play_suspended MUST be false since we were paused
     if let AppMode::PendingPrompt { auto_play_suspended, .. } = app.mode {
@@ -30,7 +33,7 @@ fn test_autoplay_sets_suspended_state() {
     let mut app = AppState::new();
 
     // Start auto-play
-    app.tick(&mut game, &[KeyCode::Space]);
+    app.tick(&mut game, &[KeyCode::Space], FRAME_SECONDS);
     match app.mode {
         AppMode::AutoPlay => {}
         AppMode::PendingPrompt { auto_play_suspended, .. } => assert!(auto_play_suspended),
@@ -39,7 +42,7 @@ fn test_autoplay_sets_suspended_state() {
 
     // Let it run. It should hit loot eventually.
     for _ in 0..100 {
-        app.tick(&mut game, &[]);
+        app.tick(&mut game, &[], FRAME_SECONDS);
         if let AppMode::PendingPrompt { auto_play_suspended, .. } = app.mode {
             assert!(auto_play_suspended);
             return;
@@ -54,13 +57,16 @@ fn test_auto_explore_interrupt_choice_and_resume_loop() {
     let mut game = Game::new(12345, &content, GameMode::Ironman);
     let mut app = AppState::new();
 
-    app.tick(&mut game, &[KeyCode::Space]);
+    app.tick(&mut game, &[KeyCode::Space], FRAME_SECONDS);
 
     if let AppMode::PendingPrompt { interrupt, .. } = &app.mode {
         let key = match interrupt {
             Interrupt::LootFound { .. } => KeyCode::L,
             Interrupt::EnemyEncounter { .. } => KeyCode::F,
             Interrupt::DoorBlocked { .. } => KeyCode::O,
+            Interrupt::StairsLocked { .. } => KeyCode::C,
+            Interrupt::PactAltar { .. } => KeyCode::C,
+            Interrupt::PerkDraft { .. } => KeyCode::Key1,
             Interrupt::FloorTransition { requires_branch_god_choice, .. } => {
                 if *requires_branch_god_choice {
                     KeyCode::Key1
@@ -69,7 +75,7 @@ fn test_auto_explore_interrupt_choice_and_resume_loop() {
                 }
             }
         };
-        app.tick(&mut game, &[key]);
+        app.tick(&mut game, &[key], FRAME_SECONDS);
     }
 
     assert!(
@@ -87,7 +93,7 @@ fn test_app_branch_choice_navigation() {
     // Run until floor transition
     let mut reached_transition = false;
     for _ in 0..1000 {
-        app.tick(&mut game, &[KeyCode::Right]); // manual step for precision
+        app.tick(&mut game, &[KeyCode::Right], FRAME_SECONDS); // manual step for precision
         if let AppMode::PendingPrompt { interrupt, .. } = &app.mode {
             if matches!(interrupt, Interrupt::FloorTransition { .. }) {
                 reached_transition = true;
@@ -98,15 +104,17 @@ fn test_app_branch_choice_navigation() {
                 Interrupt::LootFound { .. } => KeyCode::L,
                 Interrupt::EnemyEncounter { .. } => KeyCode::F,
                 Interrupt::DoorBlocked { .. } => KeyCode::O,
+                Interrupt::PactAltar { .. } => KeyCode::C,
+                Interrupt::PerkDraft { .. } => KeyCode::Key1,
                 _ => break,
             };
-            app.tick(&mut game, &[key]);
+            app.tick(&mut game, &[key], FRAME_SECONDS);
         }
     }
     assert!(reached_transition, "Did not reach floor transition");
 
     // Select Branch B + Forge
-    app.tick(&mut game, &[KeyCode::Key4]);
+    app.tick(&mut game, &[KeyCode::Key4], FRAME_SECONDS);
 
     // Verify branch is committed in game state
     assert_eq!(game.state().branch_profile, core::BranchProfile::BranchB);
@@ -125,7 +133,7 @@ fn test_regression_no_ascend_bindings() {
 
     // Try various keys that might be "ascend" in other games (U for Up, etc.)
     for key in [KeyCode::U, KeyCode::PageUp, KeyCode::W] {
-        app.tick(&mut game, &[key]);
+        app.tick(&mut game, &[key], FRAME_SECONDS);
         assert_eq!(game.state().floor_index, 1, "Floor index should not change on key {:?}", key);
     }
 }
@@ -135,7 +143,7 @@ fn test_finished_mode_triggers_for_normal_run_completion() {
     let content = ContentPack::default();
     let mut game = Game::new(12345, &content, GameMode::Ironman);
     let mut app = AppState::new();
-    app.tick(&mut game, &[KeyCode::Space]);
+    app.tick(&mut game, &[KeyCode::Space], FRAME_SECONDS);
 
     // Run the game to completion by auto-resolving all interrupts
     for _ in 0..5000 {
@@ -152,6 +160,9 @@ fn test_finished_mode_triggers_for_normal_run_completion() {
                     Interrupt::LootFound { .. } => KeyCode::L,
                     Interrupt::EnemyEncounter { .. } => KeyCode::F,
                     Interrupt::DoorBlocked { .. } => KeyCode::O,
+                    Interrupt::StairsLocked { .. } => KeyCode::C,
+                    Interrupt::PactAltar { .. } => KeyCode::C,
+                    Interrupt::PerkDraft { .. } => KeyCode::Key1,
                     Interrupt::FloorTransition { requires_branch_god_choice, .. } => {
                         if *requires_branch_god_choice {
                             KeyCode::Key1
@@ -160,10 +171,10 @@ fn test_finished_mode_triggers_for_normal_run_completion() {
                         }
                     }
                 };
-                app.tick(&mut game, &[key]);
+                app.tick(&mut game, &[key], FRAME_SECONDS);
             }
             _ => {
-                app.tick(&mut game, &[]);
+                app.tick(&mut game, &[], FRAME_SECONDS);
             }
         }
     }
@@ -180,13 +191,13 @@ fn test_finished_mode_triggers_for_engine_failure_no_panic() {
     let content = ContentPack::default();
     let mut game = Game::new(99999, &content, GameMode::Ironman);
     let mut app = AppState::new();
-    app.tick(&mut game, &[KeyCode::Space]);
+    app.tick(&mut game, &[KeyCode::Space], FRAME_SECONDS);
 
     // Run the game and track engine failure transitions
     for _ in 0..5000 {
         match &app.mode {
             AppMode::Finished(AppCompletion::EngineFailure(reason)) => {
-                assert_eq!(*reason, core::EngineFailureReason::StalledNoProgress);
+                assert!(matches!(reason, core::EngineFailureReason::StalledNoProgress(_)));
                 return; // Success: engine failure handled without panic
             }
             AppMode::Finished(AppCompletion::Outcome(_)) => {
@@ -197,6 +208,9 @@ fn test_finished_mode_triggers_for_engine_failure_no_panic() {
                     Interrupt::LootFound { .. } => KeyCode::L,
                     Interrupt::EnemyEncounter { .. } => KeyCode::F,
                     Interrupt::DoorBlocked { .. } => KeyCode::O,
+                    Interrupt::StairsLocked { .. } => KeyCode::C,
+                    Interrupt::PactAltar { .. } => KeyCode::C,
+                    Interrupt::PerkDraft { .. } => KeyCode::Key1,
                     Interrupt::FloorTransition { requires_branch_god_choice, .. } => {
                         if *requires_branch_god_choice {
                             KeyCode::Key1
@@ -205,10 +219,10 @@ fn test_finished_mode_triggers_for_engine_failure_no_panic() {
                         }
                     }
                 };
-                app.tick(&mut game, &[key]);
+                app.tick(&mut game, &[key], FRAME_SECONDS);
             }
             _ => {
-                app.tick(&mut game, &[]);
+                app.tick(&mut game, &[], FRAME_SECONDS);
             }
         }
     }
@@ -231,13 +245,9 @@ fn test_finished_mode_triggers_for_normal_outcome() {
 
 #[test]
 fn test_finished_mode_triggers_for_engine_failure_without_panic() {
+    let game = Game::new(7, &ContentPack::default(), GameMode::Ironman);
+    let reason = EngineFailureReason::StalledNoProgress(game.engine_diagnostics());
     let mut app = AppState::new();
-    app.apply_stop_reason(
-        AdvanceStopReason::EngineFailure(EngineFailureReason::StalledNoProgress),
-        true,
-    );
-    assert_eq!(
-        app.mode,
-        AppMode::Finished(AppCompletion::EngineFailure(EngineFailureReason::StalledNoProgress))
-    );
+    app.apply_stop_reason(AdvanceStopReason::EngineFailure(reason.clone()), true);
+    assert_eq!(app.mode, AppMode::Finished(AppCompletion::EngineFailure(reason)));
 }
@@ -0,0 +1,95 @@
+//! Baseline timings for the hot simulation paths: floor generation, A* on a worst-case
+//! maze, FOV at the widest radius, and long bot runs. Run with `cargo bench -p core`.
+
+use std::hint::black_box;
+
+use core::game::bench_support::{MAX_FOV_RADIUS, astar_path, compute_fov};
+use core::{
+    AdvanceStopReason, BranchProfile, ContentPack, Game, GameMode, MAX_FLOORS, Map, Pos,
+    STARTING_FLOOR_INDEX, TileKind, generate_floor,
+};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const MAZE_SIZE: usize = 61;
+const BOT_RUN_TICKS: u64 = 10_000;
+
+fn mapgen_per_floor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mapgen");
+    for floor_index in STARTING_FLOOR_INDEX..=MAX_FLOORS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(floor_index),
+            &floor_index,
+            |b, &floor| {
+                b.iter(|| generate_floor(black_box(0xBE7C), floor, BranchProfile::BranchA));
+            },
+        );
+    }
+    group.finish();
+}
+
+/// A fully discovered serpentine maze: every wall row leaves a single gap at alternating
+/// ends, so the only path from corner to corner visits almost every open tile.
+fn serpentine_maze() -> (Map, Pos, Pos) {
+    let mut map = Map::new(MAZE_SIZE, MAZE_SIZE);
+    let last = MAZE_SIZE as i32 - 2;
+    for y in (2..last).step_by(2) {
+        let gap = if (y / 2) % 2 == 0 { last } else { 1 };
+        for x in 1..=last {
+            if x != gap {
+                map.set_tile(Pos { y, x }, TileKind::Wall);
+            }
+        }
+    }
+    map.discovered.fill(true);
+    (map, Pos { y: 1, x: 1 }, Pos { y: last, x: last })
+}
+
+fn astar_worst_case(c: &mut Criterion) {
+    let (map, start, goal) = serpentine_maze();
+    assert!(astar_path(&map, start, goal).is_some(), "the maze must be solvable");
+    c.bench_function("astar/serpentine_maze", |b| {
+        b.iter(|| astar_path(black_box(&map), start, goal));
+    });
+}
+
+fn fov_max_radius(c: &mut Criterion) {
+    let mut map = Map::new(MAZE_SIZE, MAZE_SIZE);
+    let origin = Pos { y: MAZE_SIZE as i32 / 2, x: MAZE_SIZE as i32 / 2 };
+    c.bench_function("fov/open_room_max_radius", |b| {
+        b.iter(|| compute_fov(black_box(&mut map), origin, MAX_FOV_RADIUS));
+    });
+}
+
+/// Auto-play answering every prompt with its first available choice.
+fn bot_run(seed: u64, content: &ContentPack) -> u64 {
+    let mut game = Game::new(seed, content, GameMode::Ironman);
+    while game.current_tick() < BOT_RUN_TICKS {
+        match game.advance(100).stop_reason {
+            AdvanceStopReason::Interrupted(interrupt) => {
+                let choice = game.available_choices().into_iter().next();
+                match choice {
+                    Some(choice) => game.apply_choice(interrupt.prompt_id(), choice).ok(),
+                    None => break,
+                };
+            }
+            AdvanceStopReason::Finished(_) | AdvanceStopReason::EngineFailure(_) => break,
+            AdvanceStopReason::PausedAtBoundary { .. } | AdvanceStopReason::BudgetExhausted => {}
+        }
+    }
+    game.snapshot_hash()
+}
+
+fn bot_runs(c: &mut Criterion) {
+    let content = ContentPack::default();
+    let mut group = c.benchmark_group("bot_run_10k_ticks");
+    group.sample_size(10);
+    for seed in [7_u64, 2695] {
+        group.bench_with_input(BenchmarkId::from_parameter(seed), &seed, |b, &seed| {
+            b.iter(|| bot_run(seed, &content));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, mapgen_per_floor, astar_worst_case, fov_max_radius, bot_runs);
+criterion_main!(benches);
@@ -1,4 +1,35 @@
-use crate::types::ActorKind;
+use std::collections::BTreeMap;
+
+use crate::types::{ActorKind, Difficulty, FloorObjective};
+
+mod aggro;
+mod bestiary;
+mod bosses;
+mod catalog;
+mod display;
+mod fingerprint;
+mod lighting;
+mod loader;
+mod names;
+mod perk_tuning;
+mod rush;
+pub(crate) mod static_keys;
+mod wandering;
+
+pub use aggro::{AggroRange, default_aggro_range};
+pub use bestiary::{
+    BestiaryEntry, ENEMY_KINDS, ENEMY_SPAWN_TABLE, EnemyStats, SpawnChance, attack_reach, bestiary,
+    floor_spawn_table, get_enemy_stats,
+};
+pub use bosses::{BossPlacement, BossRoster, BossRule};
+pub use catalog::{Consumable, God, Perk, Weapon};
+pub use display::{DangerTag, DisplayData, GlyphColor, GlyphStyle, ItemCategory, danger_tags};
+pub use lighting::LightingRules;
+pub use loader::ContentLoadError;
+pub use names::NameTables;
+pub use perk_tuning::PerkTuning;
+pub use rush::RushDetourRules;
+pub use wandering::WanderingSpawnRate;
 
 pub mod keys {
     pub const WEAPON_RUSTY_SWORD: &str = "weapon_rusty_sword";
@@ -17,6 +48,9 @@ pub mod keys {
     pub const CONSUMABLE_SHRAPNEL_BOMB: &str = "consumable_shrapnel_bomb";
     pub const CONSUMABLE_HASTE_POTION: &str = "consumable_haste_potion";
     pub const CONSUMABLE_IRON_SKIN_POTION: &str = "consumable_iron_skin_potion";
+    pub const CONSUMABLE_WHETSTONE: &str = "consumable_whetstone";
+    pub const CONSUMABLE_ENCHANT_SCROLL: &str = "consumable_enchant_scroll";
+    pub const CONSUMABLE_TORCH: &str = "consumable_torch";
 
     pub const PERK_TOUGHNESS: &str = "perk_toughness";
     pub const PERK_SWIFT: &str = "perk_swift";
@@ -28,185 +62,225 @@ pub mod keys {
     pub const PERK_SCOUT: &str = "perk_scout";
     pub const PERK_RECKLESS_STRIKE: &str = "perk_reckless_strike";
     pub const PERK_SHADOW_STEP: &str = "perk_shadow_step";
+    pub const PERK_FLOOR_SIGIL: &str = "perk_floor_sigil";
 
     pub const GOD_VEIL: &str = "god_veil";
     pub const GOD_FORGE: &str = "god_forge";
 }
 
-pub struct EnemyStats {
-    pub hp: i32,
-    pub attack: i32,
-    pub defense: i32,
-    pub speed: u32,
+/// Map a serialized item key back to its static content key in the built-in catalogs.
+pub fn resolve_item_key(key: &str) -> Option<&'static str> {
+    let weapons = catalog::default_weapons().into_iter().map(|weapon| weapon.id);
+    let consumables = catalog::default_consumables().into_iter().map(|consumable| consumable.id);
+    weapons.chain(consumables).find(|id| *id == key)
+}
+
+/// Potions that spawn unidentified; their appearances are shuffled per run seed.
+pub const UNIDENTIFIED_POTIONS: [&str; 4] = [
+    keys::CONSUMABLE_MINOR_HP_POT,
+    keys::CONSUMABLE_MAJOR_HP_POT,
+    keys::CONSUMABLE_HASTE_POTION,
+    keys::CONSUMABLE_IRON_SKIN_POTION,
+];
+pub const POTION_APPEARANCES: [&str; 4] =
+    ["Murky Potion", "Fizzing Potion", "Amber Potion", "Cloudy Potion"];
+
+/// Highest enchantment a single weapon can reach from whetstones and scrolls.
+pub const MAX_WEAPON_ENCHANT: u8 = 3;
+
+/// Carry weight the player can hold before actions slow down.
+pub const BASE_CARRY_CAPACITY: u32 = 10;
+/// Extra carry capacity granted by the Toughness perk.
+pub const TOUGHNESS_CARRY_BONUS: u32 = 5;
+/// Consumable stacks the player's inventory holds.
+pub const INVENTORY_CAPACITY: usize = 5;
+
+/// Player actions that spend simulation time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionKind {
+    Move,
+    PickUp,
+    Discard,
+    OpenDoor,
+    SwapWeapon,
+    Fight,
+    Avoid,
+    Brace,
+    /// Using a consumable from the inventory, such as drinking a potion.
+    UseItem,
+}
+
+/// Base tick cost of each player action for an actor at `BASE_ACTION_SPEED`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ActionCosts {
+    pub move_step: u32,
+    pub pick_up: u32,
+    pub discard: u32,
+    pub open_door: u32,
+    pub swap_weapon: u32,
+    pub fight: u32,
+    pub avoid: u32,
+    pub brace: u32,
+    pub use_item: u32,
+}
+
+/// Speed at which action costs are paid exactly as listed in `ActionCosts`.
+pub const BASE_ACTION_SPEED: u32 = 10;
+
+impl ActionCosts {
+    pub fn base_cost(&self, action: ActionKind) -> u32 {
+        match action {
+            ActionKind::Move => self.move_step,
+            ActionKind::PickUp => self.pick_up,
+            ActionKind::Discard => self.discard,
+            ActionKind::OpenDoor => self.open_door,
+            ActionKind::SwapWeapon => self.swap_weapon,
+            ActionKind::Fight => self.fight,
+            ActionKind::Avoid => self.avoid,
+            ActionKind::Brace => self.brace,
+            ActionKind::UseItem => self.use_item,
+        }
+    }
+}
+
+impl Default for ActionCosts {
+    fn default() -> Self {
+        Self {
+            move_step: 1,
+            pick_up: 2,
+            discard: 1,
+            open_door: 2,
+            swap_weapon: 10,
+            fight: 3,
+            avoid: 1,
+            brace: 1,
+            use_item: 2,
+        }
+    }
+}
+
+/// Percent multipliers a difficulty preset applies on top of base content values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DifficultyScaling {
+    pub enemy_hp_percent: u32,
+    pub enemy_attack_percent: u32,
+    pub enemy_count_percent: u32,
+    pub healing_percent: u32,
 }
 
-pub fn get_enemy_stats(kind: ActorKind) -> EnemyStats {
-    match kind {
-        ActorKind::Goblin => EnemyStats { hp: 10, attack: 2, defense: 0, speed: 12 },
-        ActorKind::FeralHound => EnemyStats { hp: 6, attack: 3, defense: 0, speed: 15 },
-        ActorKind::BloodAcolyte => EnemyStats { hp: 12, attack: 5, defense: 0, speed: 10 },
-        ActorKind::CorruptedGuard => EnemyStats { hp: 18, attack: 4, defense: 2, speed: 9 },
-        ActorKind::LivingArmor => EnemyStats { hp: 25, attack: 3, defense: 4, speed: 5 },
-        ActorKind::Gargoyle => EnemyStats { hp: 20, attack: 4, defense: 3, speed: 8 },
-        ActorKind::ShadowStalker => EnemyStats { hp: 14, attack: 4, defense: 1, speed: 12 },
-        ActorKind::AbyssalWarden => EnemyStats { hp: 80, attack: 8, defense: 3, speed: 9 },
-        ActorKind::Player => EnemyStats { hp: 20, attack: 5, defense: 0, speed: 10 },
+impl DifficultyScaling {
+    pub fn scale_enemy_stats(&self, stats: EnemyStats) -> EnemyStats {
+        EnemyStats {
+            hp: scale_percent(stats.hp, self.enemy_hp_percent).max(1),
+            attack: scale_percent(stats.attack, self.enemy_attack_percent),
+            ..stats
+        }
+    }
+
+    pub fn scale_healing(&self, amount: i32) -> i32 {
+        scale_percent(amount, self.healing_percent)
     }
 }
 
-pub struct Weapon {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub attack_bonus: i32,
+/// Round-half-up percent scaling shared by every difficulty multiplier.
+fn scale_percent(value: i32, percent: u32) -> i32 {
+    (value * percent as i32 + 50) / 100
 }
 
-pub struct Consumable {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub heal_amount: i32,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DifficultyMultipliers {
+    pub relaxed: DifficultyScaling,
+    pub normal: DifficultyScaling,
+    pub brutal: DifficultyScaling,
 }
 
-pub struct Perk {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub description: &'static str,
+impl DifficultyMultipliers {
+    pub fn for_difficulty(&self, difficulty: Difficulty) -> DifficultyScaling {
+        match difficulty {
+            Difficulty::Relaxed => self.relaxed,
+            Difficulty::Normal => self.normal,
+            Difficulty::Brutal => self.brutal,
+        }
+    }
 }
 
-pub struct God {
-    pub id: &'static str,
-    pub name: &'static str,
-    pub description: &'static str,
+impl Default for DifficultyMultipliers {
+    fn default() -> Self {
+        Self {
+            relaxed: DifficultyScaling {
+                enemy_hp_percent: 75,
+                enemy_attack_percent: 75,
+                enemy_count_percent: 75,
+                healing_percent: 150,
+            },
+            normal: DifficultyScaling {
+                enemy_hp_percent: 100,
+                enemy_attack_percent: 100,
+                enemy_count_percent: 100,
+                healing_percent: 100,
+            },
+            brutal: DifficultyScaling {
+                enemy_hp_percent: 130,
+                enemy_attack_percent: 125,
+                enemy_count_percent: 150,
+                healing_percent: 75,
+            },
+        }
+    }
 }
 
+#[derive(Debug)]
 pub struct ContentPack {
     pub weapons: Vec<Weapon>,
     pub consumables: Vec<Consumable>,
     pub perks: Vec<Perk>,
     pub gods: Vec<God>,
+    /// Base stats for every enemy kind, before difficulty scaling.
+    pub enemies: BTreeMap<ActorKind, EnemyStats>,
+    /// How near each enemy kind notices the player and how far it chases from home.
+    pub aggro: BTreeMap<ActorKind, AggroRange>,
+    pub perk_tuning: PerkTuning,
+    pub action_costs: ActionCosts,
+    pub difficulty: DifficultyMultipliers,
+    pub wandering_spawns: WanderingSpawnRate,
+    pub rush_detour: RushDetourRules,
+    /// Newest threat-trace entries kept for the HUD and the post-run export.
+    pub threat_trace_depth: usize,
+    /// Newest run log events kept; older ones are dropped, so long runs stay small.
+    pub event_log_depth: usize,
+    /// Ticks an out-of-sight enemy's last known position lingers on the map overlay.
+    pub threat_memory_ticks: u64,
+    /// Floors whose down stairs stay locked until their objective is done; none by default.
+    pub floor_objectives: BTreeMap<u8, FloorObjective>,
+    /// Boss each branch ends on, with its spawn rule.
+    pub bosses: BossRoster,
+    pub lighting: LightingRules,
+    pub names: NameTables,
+    pub display: DisplayData,
 }
 
 impl ContentPack {
     pub fn build_default() -> Self {
         Self {
-            weapons: vec![
-                Weapon { id: keys::WEAPON_RUSTY_SWORD, name: "Rusty Sword", attack_bonus: 2 },
-                Weapon { id: keys::WEAPON_IRON_MACE, name: "Iron Mace", attack_bonus: 4 },
-                Weapon {
-                    id: keys::WEAPON_STEEL_LONGSWORD,
-                    name: "Steel Longsword",
-                    attack_bonus: 6,
-                },
-                Weapon { id: keys::WEAPON_PHASE_DAGGER, name: "Phase Dagger", attack_bonus: 3 }, // Weird: ignores armor
-                Weapon { id: keys::WEAPON_BLOOD_AXE, name: "Blood Axe", attack_bonus: 6 }, // Weird: lifesteal
-            ],
-            consumables: vec![
-                Consumable {
-                    id: keys::CONSUMABLE_MINOR_HP_POT,
-                    name: "Minor Health Potion",
-                    heal_amount: 10,
-                },
-                Consumable {
-                    id: keys::CONSUMABLE_MAJOR_HP_POT,
-                    name: "Major Health Potion",
-                    heal_amount: 25,
-                },
-                Consumable {
-                    id: keys::CONSUMABLE_TELEPORT_RUNE,
-                    name: "Teleport Rune",
-                    heal_amount: 0,
-                }, // Weird: Swap with enemy
-                Consumable {
-                    id: keys::CONSUMABLE_FORTIFICATION_SCROLL,
-                    name: "Fortification Scroll",
-                    heal_amount: 0,
-                }, // Weird: Wall spawn
-                Consumable {
-                    id: keys::CONSUMABLE_STASIS_HOURGLASS,
-                    name: "Stasis Hourglass",
-                    heal_amount: 0,
-                }, // Weird: Freeze
-                Consumable {
-                    id: keys::CONSUMABLE_MAGNETIC_LURE,
-                    name: "Magnetic Lure",
-                    heal_amount: 0,
-                }, // Weird: Pull
-                Consumable { id: keys::CONSUMABLE_SMOKE_BOMB, name: "Smoke Bomb", heal_amount: 0 }, // Weird: Blind/Drop aggro
-                Consumable {
-                    id: keys::CONSUMABLE_SHRAPNEL_BOMB,
-                    name: "Shrapnel Bomb",
-                    heal_amount: 0,
-                }, // Standard: AOE damage
-                Consumable {
-                    id: keys::CONSUMABLE_HASTE_POTION,
-                    name: "Potion of Haste",
-                    heal_amount: 0,
-                }, // Standard: speed buff
-                Consumable {
-                    id: keys::CONSUMABLE_IRON_SKIN_POTION,
-                    name: "Iron Skin Potion",
-                    heal_amount: 0,
-                }, // Standard: def buff
-            ],
-            perks: vec![
-                Perk {
-                    id: keys::PERK_TOUGHNESS,
-                    name: "Toughness",
-                    description: "Increases max HP by 5.",
-                },
-                Perk {
-                    id: keys::PERK_SWIFT,
-                    name: "Swift",
-                    description: "Action tick cost reduced by 1.",
-                },
-                Perk {
-                    id: keys::PERK_BERSERKER_RHYTHM,
-                    name: "Berserker Rhythm",
-                    description: "Attacks deal +3 damage if unarmed.",
-                },
-                Perk {
-                    id: keys::PERK_PACIFISTS_BOUNTY,
-                    name: "Pacifist's Bounty",
-                    description: "Gain max HP and full heal when you descend with 0 kills.",
-                },
-                Perk {
-                    id: keys::PERK_SNIPERS_EYE,
-                    name: "Sniper's Eye",
-                    description: "First strike on a floor deals +3 damage (Not Imp).",
-                },
-                Perk { id: keys::PERK_IRON_WILL, name: "Iron Will", description: "+2 Defense." },
-                Perk {
-                    id: keys::PERK_BLOODLUST,
-                    name: "Bloodlust",
-                    description: "Heal 2 HP on kill.",
-                },
-                Perk {
-                    id: keys::PERK_SCOUT,
-                    name: "Scout",
-                    description: "Increases FOV radius by 2.",
-                },
-                Perk {
-                    id: keys::PERK_RECKLESS_STRIKE,
-                    name: "Reckless Strike",
-                    description: "+4 Attack, -2 Defense.",
-                },
-                Perk {
-                    id: keys::PERK_SHADOW_STEP,
-                    name: "Shadow Step",
-                    description: "Choosing 'Avoid' teleports you.",
-                },
-            ],
-            gods: vec![
-                God {
-                    id: keys::GOD_VEIL,
-                    name: "Veil",
-                    description: "Avoid blinks you to the farthest nearby safe tile.",
-                },
-                God {
-                    id: keys::GOD_FORGE,
-                    name: "Forge",
-                    description: "Gain +2 max HP, heal +2, and +2 passive defense.",
-                },
-            ],
+            weapons: catalog::default_weapons(),
+            consumables: catalog::default_consumables(),
+            perks: catalog::default_perks(),
+            gods: catalog::default_gods(),
+            enemies: ENEMY_KINDS.into_iter().map(|kind| (kind, get_enemy_stats(kind))).collect(),
+            aggro: ENEMY_KINDS.into_iter().map(|kind| (kind, default_aggro_range(kind))).collect(),
+            perk_tuning: PerkTuning::default(),
+            action_costs: ActionCosts::default(),
+            difficulty: DifficultyMultipliers::default(),
+            wandering_spawns: WanderingSpawnRate::default(),
+            rush_detour: RushDetourRules::default(),
+            threat_trace_depth: 32,
+            event_log_depth: 512,
+            threat_memory_ticks: 60,
+            floor_objectives: BTreeMap::new(),
+            bosses: BossRoster::default(),
+            lighting: LightingRules::default(),
+            names: NameTables::default(),
+            display: DisplayData::default(),
         }
     }
 }
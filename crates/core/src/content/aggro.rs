@@ -0,0 +1,27 @@
+//! Aggro and leash radii per enemy kind: how close the player must come before the kind
+//! notices them, and how far it follows from its home tile before giving up the chase.
+
+use crate::types::ActorKind;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggroRange {
+    /// Farthest Manhattan distance at which this kind can notice the player.
+    pub aggro_radius: u32,
+    /// Farthest Manhattan distance from home this kind pursues before walking back.
+    pub leash_radius: u32,
+}
+
+/// Built-in radii: hounds and stalkers hunt far, sentries and statues keep to their posts.
+pub fn default_aggro_range(kind: ActorKind) -> AggroRange {
+    let (aggro_radius, leash_radius) = match kind {
+        ActorKind::FeralHound => (6, 12),
+        ActorKind::ShadowStalker => (6, 10),
+        ActorKind::CorruptedGuard => (4, 4),
+        ActorKind::LivingArmor => (3, 5),
+        ActorKind::Gargoyle => (3, 3),
+        ActorKind::AbyssalWarden | ActorKind::HollowKing => (5, 6),
+        ActorKind::BoneArcher => (7, 9),
+        ActorKind::Goblin | ActorKind::BloodAcolyte | ActorKind::Player => (5, 8),
+    };
+    AggroRange { aggro_radius, leash_radius }
+}
@@ -0,0 +1,176 @@
+//! Bestiary: the enemy spawn table by floor and per-kind entries built from it and the content
+//! pack, for encyclopedia and spawn-preview screens. Map generation rolls from the same table.
+
+use super::ContentPack;
+use super::display::{DangerTag, danger_tags};
+use crate::mapgen::{BranchProfile, MAX_FLOORS};
+use crate::types::ActorKind;
+
+/// Every kind that can spawn as an enemy, in bestiary order.
+pub const ENEMY_KINDS: [ActorKind; 10] = [
+    ActorKind::Goblin,
+    ActorKind::FeralHound,
+    ActorKind::BloodAcolyte,
+    ActorKind::CorruptedGuard,
+    ActorKind::LivingArmor,
+    ActorKind::Gargoyle,
+    ActorKind::ShadowStalker,
+    ActorKind::BoneArcher,
+    ActorKind::AbyssalWarden,
+    ActorKind::HollowKing,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnemyStats {
+    pub hp: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub speed: u32,
+}
+
+pub fn get_enemy_stats(kind: ActorKind) -> EnemyStats {
+    match kind {
+        ActorKind::Goblin => EnemyStats { hp: 10, attack: 2, defense: 0, speed: 12 },
+        ActorKind::FeralHound => EnemyStats { hp: 6, attack: 3, defense: 0, speed: 15 },
+        ActorKind::BloodAcolyte => EnemyStats { hp: 12, attack: 5, defense: 0, speed: 10 },
+        ActorKind::CorruptedGuard => EnemyStats { hp: 18, attack: 4, defense: 2, speed: 9 },
+        ActorKind::LivingArmor => EnemyStats { hp: 25, attack: 3, defense: 4, speed: 5 },
+        ActorKind::Gargoyle => EnemyStats { hp: 20, attack: 4, defense: 3, speed: 8 },
+        ActorKind::ShadowStalker => EnemyStats { hp: 14, attack: 4, defense: 1, speed: 12 },
+        ActorKind::AbyssalWarden => EnemyStats { hp: 80, attack: 8, defense: 3, speed: 9 },
+        ActorKind::HollowKing => EnemyStats { hp: 70, attack: 9, defense: 2, speed: 11 },
+        ActorKind::BoneArcher => EnemyStats { hp: 6, attack: 3, defense: 0, speed: 10 },
+        ActorKind::Player => EnemyStats { hp: 20, attack: 5, defense: 0, speed: 10 },
+    }
+}
+
+/// Farthest a kind's attack reaches: 1 for melee kinds, more for those that shoot, which
+/// also need a clear line to their target.
+pub fn attack_reach(kind: ActorKind) -> u32 {
+    match kind {
+        ActorKind::BoneArcher => 5,
+        _ => 1,
+    }
+}
+
+/// Enemy mix for floors 1 through `MAX_FLOORS`: each kind with its chance out of 100.
+/// Floors outside that range use the last row.
+pub const ENEMY_SPAWN_TABLE: [&[(ActorKind, u32)]; MAX_FLOORS as usize] = [
+    &[(ActorKind::Goblin, 60), (ActorKind::FeralHound, 30), (ActorKind::BloodAcolyte, 10)],
+    &[
+        (ActorKind::FeralHound, 20),
+        (ActorKind::BloodAcolyte, 30),
+        (ActorKind::CorruptedGuard, 30),
+        (ActorKind::Gargoyle, 20),
+    ],
+    &[
+        (ActorKind::BloodAcolyte, 20),
+        (ActorKind::CorruptedGuard, 30),
+        (ActorKind::Gargoyle, 30),
+        (ActorKind::LivingArmor, 20),
+    ],
+    &[
+        (ActorKind::CorruptedGuard, 20),
+        (ActorKind::Gargoyle, 20),
+        (ActorKind::LivingArmor, 30),
+        (ActorKind::ShadowStalker, 20),
+        (ActorKind::BoneArcher, 10),
+    ],
+    &[
+        (ActorKind::Gargoyle, 20),
+        (ActorKind::LivingArmor, 20),
+        (ActorKind::ShadowStalker, 30),
+        (ActorKind::AbyssalWarden, 30),
+    ],
+];
+
+/// The spawn-table row used for `floor_index`.
+pub fn floor_spawn_table(floor_index: u8) -> &'static [(ActorKind, u32)] {
+    let row = usize::from(floor_index).wrapping_sub(1).min(ENEMY_SPAWN_TABLE.len() - 1);
+    ENEMY_SPAWN_TABLE[row]
+}
+
+/// How likely a kind is on one floor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpawnChance {
+    pub floor: u8,
+    pub percent: u32,
+}
+
+/// Everything the encyclopedia knows about one enemy kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BestiaryEntry {
+    pub kind: ActorKind,
+    /// The pack's base stats before difficulty scaling.
+    pub stats: EnemyStats,
+    pub danger_tags: Vec<DangerTag>,
+    /// Floors whose spawn table includes the kind, shallowest first.
+    pub floors: Vec<SpawnChance>,
+    /// Branches the pack's boss roster ends on this kind.
+    pub boss_of: Vec<BranchProfile>,
+}
+
+impl BestiaryEntry {
+    pub fn of(content: &ContentPack, kind: ActorKind) -> Self {
+        let floors = (1..=MAX_FLOORS)
+            .filter_map(|floor| {
+                let row = floor_spawn_table(floor);
+                let percent = row.iter().find(|(entry, _)| *entry == kind)?.1;
+                Some(SpawnChance { floor, percent })
+            })
+            .collect();
+        let boss_of = content
+            .bosses
+            .by_branch
+            .iter()
+            .filter_map(|(branch, rule)| (rule.kind == kind).then_some(*branch))
+            .collect();
+        let stats = content.enemies.get(&kind).copied().unwrap_or_else(|| get_enemy_stats(kind));
+        Self { kind, stats, danger_tags: danger_tags(&stats), floors, boss_of }
+    }
+}
+
+/// One entry per enemy kind, in `ENEMY_KINDS` order.
+pub fn bestiary(content: &ContentPack) -> Vec<BestiaryEntry> {
+    ENEMY_KINDS.into_iter().map(|kind| BestiaryEntry::of(content, kind)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_rows_total_100_and_every_enemy_appears_somewhere() {
+        for row in ENEMY_SPAWN_TABLE {
+            assert_eq!(row.iter().map(|(_, percent)| percent).sum::<u32>(), 100);
+        }
+        let content = ContentPack::default();
+        let entries = bestiary(&content);
+        assert!(
+            entries.iter().all(|entry| !entry.floors.is_empty() || !entry.boss_of.is_empty()),
+            "{entries:?}"
+        );
+
+        let goblin = BestiaryEntry::of(&content, ActorKind::Goblin);
+        assert_eq!(goblin.floors, vec![SpawnChance { floor: 1, percent: 60 }]);
+        let warden = BestiaryEntry::of(&content, ActorKind::AbyssalWarden);
+        assert!(warden.danger_tags.contains(&DangerTag::Boss));
+        let king = BestiaryEntry::of(&content, ActorKind::HollowKing);
+        assert_eq!(king.boss_of, vec![BranchProfile::BranchB]);
+        assert_eq!(floor_spawn_table(0), floor_spawn_table(MAX_FLOORS + 3));
+    }
+
+    #[test]
+    fn entries_follow_the_pack_they_describe() {
+        let mut content =
+            ContentPack::from_toml_str("[[enemies]]\nkind = \"Goblin\"\nspeed = 14\nattack = 6\n")
+                .expect("valid balance file");
+        content.bosses.by_branch.remove(&BranchProfile::BranchB);
+        content.enemies.get_mut(&ActorKind::Goblin).expect("goblins are enemies").hp = 31;
+
+        assert!(BestiaryEntry::of(&content, ActorKind::HollowKing).boss_of.is_empty());
+        let goblin = BestiaryEntry::of(&content, ActorKind::Goblin);
+        assert_eq!((goblin.stats.hp, goblin.stats.speed), (31, 14));
+        assert_eq!(goblin.danger_tags, vec![DangerTag::Fast, DangerTag::HardHitting]);
+    }
+}
@@ -0,0 +1,83 @@
+//! Boss roster: which boss guards each branch, the floor it spawns on and where it stands.
+//! Map generation places the boss from this table and the encounter prompt opens with its intro.
+
+use std::collections::BTreeMap;
+
+use crate::mapgen::{BranchProfile, MAX_FLOORS};
+use crate::types::ActorKind;
+
+/// Where on its floor a boss is placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BossPlacement {
+    /// On the down stairs, so the floor cannot be left without meeting it.
+    GuardStairs,
+    /// On the walkable tile nearest the middle of the floor.
+    HoldCenter,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BossRule {
+    pub kind: ActorKind,
+    /// Floor the boss spawns on.
+    pub floor: u8,
+    pub placement: BossPlacement,
+    /// Line the encounter prompt opens with when the boss is met.
+    pub intro: &'static str,
+}
+
+/// The boss rule for each branch; a branch without an entry has no boss.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BossRoster {
+    pub by_branch: BTreeMap<BranchProfile, BossRule>,
+}
+
+impl BossRoster {
+    /// The boss spawning on `floor_index` down `branch`, if any.
+    pub fn boss_for(&self, branch: BranchProfile, floor_index: u8) -> Option<BossRule> {
+        self.by_branch.get(&branch).copied().filter(|rule| rule.floor == floor_index)
+    }
+
+    /// The rule that placed a boss of `kind`.
+    pub fn rule_of(&self, kind: ActorKind) -> Option<&BossRule> {
+        self.by_branch.values().find(|rule| rule.kind == kind)
+    }
+}
+
+impl Default for BossRoster {
+    fn default() -> Self {
+        let warden = BossRule {
+            kind: ActorKind::AbyssalWarden,
+            floor: MAX_FLOORS,
+            placement: BossPlacement::GuardStairs,
+            intro: "The Abyssal Warden bars the final stairs",
+        };
+        let king = BossRule {
+            kind: ActorKind::HollowKing,
+            floor: MAX_FLOORS,
+            placement: BossPlacement::HoldCenter,
+            intro: "The Hollow King rises from his empty throne",
+        };
+        Self {
+            by_branch: BTreeMap::from([
+                (BranchProfile::Uncommitted, warden),
+                (BranchProfile::BranchA, warden),
+                (BranchProfile::BranchB, king),
+            ]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_branch_has_its_own_final_boss() {
+        let roster = BossRoster::default();
+        let a = roster.boss_for(BranchProfile::BranchA, MAX_FLOORS).expect("branch A boss");
+        let b = roster.boss_for(BranchProfile::BranchB, MAX_FLOORS).expect("branch B boss");
+        assert_ne!(a.kind, b.kind);
+        assert_eq!(roster.boss_for(BranchProfile::BranchB, MAX_FLOORS - 1), None);
+        assert_eq!(roster.rule_of(ActorKind::HollowKing), Some(&b));
+    }
+}
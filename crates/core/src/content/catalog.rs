@@ -0,0 +1,150 @@
+//! The item and pact catalog: every weapon, consumable, perk and god with its base numbers.
+//! Effects live in the simulation; these tables only carry what a balance file may retune.
+
+use super::keys;
+
+#[derive(Clone, Debug)]
+pub struct Weapon {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub attack_bonus: i32,
+    /// Carry weight while held; see `BASE_CARRY_CAPACITY`.
+    pub weight: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Consumable {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub heal_amount: i32,
+}
+
+#[derive(Debug)]
+pub struct Perk {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Debug)]
+pub struct God {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub(super) fn default_weapons() -> Vec<Weapon> {
+    vec![
+        Weapon { id: keys::WEAPON_RUSTY_SWORD, name: "Rusty Sword", attack_bonus: 2, weight: 3 },
+        Weapon { id: keys::WEAPON_IRON_MACE, name: "Iron Mace", attack_bonus: 4, weight: 6 },
+        Weapon {
+            id: keys::WEAPON_STEEL_LONGSWORD,
+            name: "Steel Longsword",
+            attack_bonus: 6,
+            weight: 5,
+        },
+        Weapon { id: keys::WEAPON_PHASE_DAGGER, name: "Phase Dagger", attack_bonus: 3, weight: 1 }, // Weird: ignores armor
+        Weapon { id: keys::WEAPON_BLOOD_AXE, name: "Blood Axe", attack_bonus: 6, weight: 7 }, // Weird: lifesteal
+    ]
+}
+
+pub(super) fn default_consumables() -> Vec<Consumable> {
+    vec![
+        Consumable {
+            id: keys::CONSUMABLE_MINOR_HP_POT,
+            name: "Minor Health Potion",
+            heal_amount: 10,
+        },
+        Consumable {
+            id: keys::CONSUMABLE_MAJOR_HP_POT,
+            name: "Major Health Potion",
+            heal_amount: 25,
+        },
+        Consumable { id: keys::CONSUMABLE_TELEPORT_RUNE, name: "Teleport Rune", heal_amount: 0 }, // Weird: Swap with enemy
+        Consumable {
+            id: keys::CONSUMABLE_FORTIFICATION_SCROLL,
+            name: "Fortification Scroll",
+            heal_amount: 0,
+        }, // Weird: Wall spawn
+        Consumable {
+            id: keys::CONSUMABLE_STASIS_HOURGLASS,
+            name: "Stasis Hourglass",
+            heal_amount: 0,
+        }, // Weird: Freeze
+        Consumable { id: keys::CONSUMABLE_MAGNETIC_LURE, name: "Magnetic Lure", heal_amount: 0 }, // Weird: Pull
+        Consumable { id: keys::CONSUMABLE_SMOKE_BOMB, name: "Smoke Bomb", heal_amount: 0 }, // Weird: Blind/Drop aggro
+        Consumable { id: keys::CONSUMABLE_SHRAPNEL_BOMB, name: "Shrapnel Bomb", heal_amount: 0 }, // Standard: AOE damage
+        Consumable { id: keys::CONSUMABLE_HASTE_POTION, name: "Potion of Haste", heal_amount: 0 }, // Standard: speed buff
+        Consumable {
+            id: keys::CONSUMABLE_IRON_SKIN_POTION,
+            name: "Iron Skin Potion",
+            heal_amount: 0,
+        }, // Standard: def buff
+        Consumable { id: keys::CONSUMABLE_WHETSTONE, name: "Whetstone", heal_amount: 0 }, // Upgrade: +1 active weapon enchant
+        Consumable {
+            id: keys::CONSUMABLE_ENCHANT_SCROLL,
+            name: "Scroll of Enchant Weapon",
+            heal_amount: 0,
+        }, // Upgrade: +2 active weapon enchant
+        Consumable { id: keys::CONSUMABLE_TORCH, name: "Torch", heal_amount: 0 }, // Light: burns on Dark floors
+    ]
+}
+
+pub(super) fn default_perks() -> Vec<Perk> {
+    vec![
+        Perk {
+            id: keys::PERK_TOUGHNESS,
+            name: "Toughness",
+            description: "Increases max HP by 5 and carry capacity by 5.",
+        },
+        Perk { id: keys::PERK_SWIFT, name: "Swift", description: "Action tick cost reduced by 1." },
+        Perk {
+            id: keys::PERK_BERSERKER_RHYTHM,
+            name: "Berserker Rhythm",
+            description: "Attacks deal +3 damage if unarmed.",
+        },
+        Perk {
+            id: keys::PERK_PACIFISTS_BOUNTY,
+            name: "Pacifist's Bounty",
+            description: "Gain max HP and full heal when you descend with 0 kills.",
+        },
+        Perk {
+            id: keys::PERK_SNIPERS_EYE,
+            name: "Sniper's Eye",
+            description: "First strike on a floor deals +3 damage (Not Imp).",
+        },
+        Perk { id: keys::PERK_IRON_WILL, name: "Iron Will", description: "+2 Defense." },
+        Perk { id: keys::PERK_BLOODLUST, name: "Bloodlust", description: "Heal 2 HP on kill." },
+        Perk { id: keys::PERK_SCOUT, name: "Scout", description: "Increases FOV radius by 2." },
+        Perk {
+            id: keys::PERK_RECKLESS_STRIKE,
+            name: "Reckless Strike",
+            description: "+4 Attack, -2 Defense.",
+        },
+        Perk {
+            id: keys::PERK_SHADOW_STEP,
+            name: "Shadow Step",
+            description: "Choosing 'Avoid' teleports you.",
+        },
+        Perk {
+            id: keys::PERK_FLOOR_SIGIL,
+            name: "Floor Sigil",
+            description: "Unseals the down stairs of the floor it was found on.",
+        },
+    ]
+}
+
+pub(super) fn default_gods() -> Vec<God> {
+    vec![
+        God {
+            id: keys::GOD_VEIL,
+            name: "Veil",
+            description: "Avoid blinks you to the farthest nearby safe tile.",
+        },
+        God {
+            id: keys::GOD_FORGE,
+            name: "Forge",
+            description: "Gain +2 max HP, heal +2, and +2 passive defense.",
+        },
+    ]
+}
@@ -0,0 +1,157 @@
+//! Display data for the map: the glyph, color and name of every actor kind, item category and
+//! tile kind. Frontends draw the map and its legend from this table rather than their own.
+
+use super::EnemyStats;
+use crate::types::{ActorKind, ItemKind, TileKind};
+
+/// Enemy speed above which the legend tags a kind as fast (the player moves at 10).
+const FAST_SPEED: u32 = 10;
+/// Enemy attack at or above which the legend tags a kind as hard-hitting.
+const HARD_HITTING_ATTACK: i32 = 5;
+/// Enemy defense at or above which the legend tags a kind as armored.
+const ARMORED_DEFENSE: i32 = 3;
+/// Enemy HP at or above which the legend tags a kind as a boss.
+const BOSS_HP: i32 = 50;
+
+/// Named colors, so the core stays free of any rendering library.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlyphColor {
+    White,
+    Gray,
+    LightGray,
+    Green,
+    Red,
+    Orange,
+    Yellow,
+    Gold,
+    Blue,
+    SkyBlue,
+    Purple,
+    Magenta,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DangerTag {
+    Fast,
+    HardHitting,
+    Armored,
+    Boss,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ItemCategory {
+    Weapon,
+    Consumable,
+    Perk,
+}
+
+impl ItemCategory {
+    pub fn of(kind: ItemKind) -> Self {
+        match kind {
+            ItemKind::Weapon(_) => Self::Weapon,
+            ItemKind::Consumable(_) => Self::Consumable,
+            ItemKind::Perk(_) => Self::Perk,
+        }
+    }
+}
+
+/// How one kind of thing looks on the map and what the legend calls it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlyphStyle<K> {
+    pub kind: K,
+    pub glyph: &'static str,
+    pub color: GlyphColor,
+    pub name: &'static str,
+}
+
+fn style<K>(kind: K, glyph: &'static str, color: GlyphColor, name: &'static str) -> GlyphStyle<K> {
+    GlyphStyle { kind, glyph, color, name }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisplayData {
+    pub actors: Vec<GlyphStyle<ActorKind>>,
+    pub items: Vec<GlyphStyle<ItemCategory>>,
+    pub tiles: Vec<GlyphStyle<TileKind>>,
+}
+
+impl DisplayData {
+    pub fn actor(&self, kind: ActorKind) -> Option<&GlyphStyle<ActorKind>> {
+        self.actors.iter().find(|style| style.kind == kind)
+    }
+
+    pub fn item(&self, category: ItemCategory) -> Option<&GlyphStyle<ItemCategory>> {
+        self.items.iter().find(|style| style.kind == category)
+    }
+
+    pub fn tile(&self, kind: TileKind) -> Option<&GlyphStyle<TileKind>> {
+        self.tiles.iter().find(|style| style.kind == kind)
+    }
+}
+
+/// Danger tags for an enemy, read from its base stats in the content pack.
+pub fn danger_tags(stats: &EnemyStats) -> Vec<DangerTag> {
+    [
+        (stats.speed > FAST_SPEED, DangerTag::Fast),
+        (stats.attack >= HARD_HITTING_ATTACK, DangerTag::HardHitting),
+        (stats.defense >= ARMORED_DEFENSE, DangerTag::Armored),
+        (stats.hp >= BOSS_HP, DangerTag::Boss),
+    ]
+    .into_iter()
+    .filter_map(|(applies, tag)| applies.then_some(tag))
+    .collect()
+}
+
+impl Default for DisplayData {
+    fn default() -> Self {
+        Self {
+            actors: vec![
+                style(ActorKind::Player, "@", GlyphColor::Green, "You"),
+                style(ActorKind::Goblin, "g", GlyphColor::Red, "Goblin"),
+                style(ActorKind::FeralHound, "h", GlyphColor::Orange, "Feral Hound"),
+                style(ActorKind::BloodAcolyte, "a", GlyphColor::Red, "Blood Acolyte"),
+                style(ActorKind::CorruptedGuard, "C", GlyphColor::Blue, "Corrupted Guard"),
+                style(ActorKind::LivingArmor, "A", GlyphColor::LightGray, "Living Armor"),
+                style(ActorKind::Gargoyle, "G", GlyphColor::Gray, "Gargoyle"),
+                style(ActorKind::ShadowStalker, "S", GlyphColor::Purple, "Shadow Stalker"),
+                style(ActorKind::AbyssalWarden, "W", GlyphColor::Magenta, "Abyssal Warden"),
+                style(ActorKind::HollowKing, "K", GlyphColor::Gold, "Hollow King"),
+                style(ActorKind::BoneArcher, "r", GlyphColor::LightGray, "Bone Archer"),
+            ],
+            items: vec![
+                style(ItemCategory::Weapon, ")", GlyphColor::Yellow, "Weapon"),
+                style(ItemCategory::Consumable, "!", GlyphColor::Yellow, "Consumable"),
+                style(ItemCategory::Perk, "?", GlyphColor::Yellow, "Perk"),
+            ],
+            tiles: vec![
+                style(TileKind::Wall, "#", GlyphColor::White, "Wall"),
+                style(TileKind::Floor, ".", GlyphColor::White, "Floor"),
+                style(TileKind::ClosedDoor, "+", GlyphColor::White, "Closed door"),
+                style(TileKind::DownStairs, ">", GlyphColor::White, "Down stairs"),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    #[test]
+    fn every_kind_has_a_distinct_glyph_and_tags_follow_stats() {
+        let display = DisplayData::default();
+        let mut glyphs: Vec<&str> = display.actors.iter().map(|style| style.glyph).collect();
+        glyphs.extend(display.items.iter().map(|style| style.glyph));
+        glyphs.extend(display.tiles.iter().map(|style| style.glyph));
+        let count = glyphs.len();
+        glyphs.sort_unstable();
+        glyphs.dedup();
+        assert_eq!(glyphs.len(), count, "two kinds share a glyph");
+        assert_eq!(display.actors.len(), 11);
+
+        let enemies = ContentPack::default().enemies;
+        assert_eq!(danger_tags(&enemies[&ActorKind::FeralHound]), vec![DangerTag::Fast]);
+        assert!(danger_tags(&enemies[&ActorKind::AbyssalWarden]).contains(&DangerTag::Boss));
+    }
+}
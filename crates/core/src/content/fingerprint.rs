@@ -0,0 +1,220 @@
+//! The content fingerprint that saves, journals and run codes are checked against.
+//! Only definitions that change how a run plays are written, field by field. Display data,
+//! item names and flavour text are left out, so a glyph or colour tweak keeps old runs valid.
+//! The tables are destructured in full, so a new field fails to compile until it is sorted
+//! into hashed or cosmetic here.
+
+use std::hash::Hasher;
+
+use xxhash_rust::xxh3::Xxh3;
+
+use super::{
+    ActionCosts, AggroRange, BossRoster, BossRule, Consumable, ContentPack, DifficultyScaling,
+    EnemyStats, God, LightingRules, NameTables, Perk, PerkTuning, RushDetourRules,
+    WanderingSpawnRate, Weapon,
+};
+
+/// A key or name, ended so that adjacent strings cannot run together.
+fn write_text(hasher: &mut Xxh3, text: &str) {
+    hasher.write(text.as_bytes());
+    hasher.write_u8(0xFF);
+}
+
+impl ContentPack {
+    /// Fingerprint of every gameplay definition in the pack, used to tell whether two builds
+    /// play alike.
+    pub fn content_hash(&self) -> u64 {
+        let Self {
+            weapons,
+            consumables,
+            perks,
+            gods,
+            enemies,
+            aggro,
+            perk_tuning,
+            action_costs,
+            difficulty,
+            wandering_spawns,
+            rush_detour,
+            threat_trace_depth,
+            event_log_depth,
+            threat_memory_ticks,
+            floor_objectives,
+            bosses,
+            lighting,
+            names,
+            display: _,
+        } = self;
+        let mut hasher = Xxh3::new();
+        hasher.write_usize(weapons.len());
+        for Weapon { id, name: _, attack_bonus, weight } in weapons {
+            write_text(&mut hasher, id);
+            hasher.write_i32(*attack_bonus);
+            hasher.write_u32(*weight);
+        }
+        hasher.write_usize(consumables.len());
+        for Consumable { id, name: _, heal_amount } in consumables {
+            write_text(&mut hasher, id);
+            hasher.write_i32(*heal_amount);
+        }
+        hasher.write_usize(perks.len());
+        for Perk { id, name: _, description: _ } in perks {
+            write_text(&mut hasher, id);
+        }
+        hasher.write_usize(gods.len());
+        for God { id, name: _, description: _ } in gods {
+            write_text(&mut hasher, id);
+        }
+        for (kind, EnemyStats { hp, attack, defense, speed }) in enemies {
+            hasher.write_u8(*kind as u8);
+            for stat in [hp, attack, defense] {
+                hasher.write_i32(*stat);
+            }
+            hasher.write_u32(*speed);
+        }
+        for (kind, AggroRange { aggro_radius, leash_radius }) in aggro {
+            hasher.write_u8(*kind as u8);
+            hasher.write_u32(*aggro_radius);
+            hasher.write_u32(*leash_radius);
+        }
+        hash_perk_tuning(&mut hasher, perk_tuning);
+        hash_action_costs(&mut hasher, action_costs);
+        for scaling in [difficulty.relaxed, difficulty.normal, difficulty.brutal] {
+            hash_difficulty_scaling(&mut hasher, scaling);
+        }
+        hash_wandering_spawns(&mut hasher, wandering_spawns);
+        let RushDetourRules { min_missing_hp_percent, max_extra_ticks } = rush_detour;
+        hasher.write_u32(*min_missing_hp_percent);
+        hasher.write_u32(*max_extra_ticks);
+        hasher.write_usize(*threat_trace_depth);
+        hasher.write_usize(*event_log_depth);
+        hasher.write_u64(*threat_memory_ticks);
+        for (floor, objective) in floor_objectives {
+            hasher.write_u8(*floor);
+            hasher.write_u8(*objective as u8);
+        }
+        let BossRoster { by_branch } = bosses;
+        // The boss intro is flavour text; kind, floor and placement change the run.
+        for (branch, BossRule { kind, floor, placement, intro: _ }) in by_branch {
+            hasher.write_u8(*branch as u8);
+            hasher.write_u8(*kind as u8);
+            hasher.write_u8(*floor);
+            hasher.write_u8(*placement as u8);
+        }
+        hash_lighting(&mut hasher, lighting);
+        hash_names(&mut hasher, names);
+        hasher.finish()
+    }
+}
+
+fn hash_perk_tuning(hasher: &mut Xxh3, tuning: &PerkTuning) {
+    let PerkTuning {
+        toughness_carry_bonus,
+        swift_tick_discount,
+        berserker_unarmed_attack,
+        pacifist_max_hp,
+        iron_will_defense,
+        bloodlust_heal,
+        scout_fov_bonus,
+        reckless_attack,
+        reckless_defense_penalty,
+    } = *tuning;
+    hasher.write_u32(toughness_carry_bonus);
+    hasher.write_u32(swift_tick_discount);
+    for value in [
+        berserker_unarmed_attack,
+        pacifist_max_hp,
+        iron_will_defense,
+        bloodlust_heal,
+        scout_fov_bonus,
+        reckless_attack,
+        reckless_defense_penalty,
+    ] {
+        hasher.write_i32(value);
+    }
+}
+
+fn hash_action_costs(hasher: &mut Xxh3, costs: &ActionCosts) {
+    let ActionCosts {
+        move_step,
+        pick_up,
+        discard,
+        open_door,
+        swap_weapon,
+        fight,
+        avoid,
+        brace,
+        use_item,
+    } = *costs;
+    for cost in [move_step, pick_up, discard, open_door, swap_weapon, fight, avoid, brace, use_item]
+    {
+        hasher.write_u32(cost);
+    }
+}
+
+fn hash_difficulty_scaling(hasher: &mut Xxh3, scaling: DifficultyScaling) {
+    let DifficultyScaling {
+        enemy_hp_percent,
+        enemy_attack_percent,
+        enemy_count_percent,
+        healing_percent,
+    } = scaling;
+    for percent in [enemy_hp_percent, enemy_attack_percent, enemy_count_percent, healing_percent] {
+        hasher.write_u32(percent);
+    }
+}
+
+fn hash_wandering_spawns(hasher: &mut Xxh3, spawns: &WanderingSpawnRate) {
+    let WanderingSpawnRate {
+        grace_ticks,
+        interval_ticks,
+        chance_percent,
+        max_per_floor,
+        min_distance,
+    } = *spawns;
+    hasher.write_u64(grace_ticks);
+    hasher.write_u64(interval_ticks);
+    hasher.write_u32(chance_percent);
+    hasher.write_u8(max_per_floor);
+    hasher.write_u32(min_distance);
+}
+
+fn hash_lighting(hasher: &mut Xxh3, lighting: &LightingRules) {
+    let LightingRules { dark_floors, dark_fov_radius, torch_ticks, brazier_radius } = lighting;
+    hasher.write_usize(dark_floors.len());
+    for floor in dark_floors {
+        hasher.write_u8(*floor);
+    }
+    hasher.write_i32(*dark_fov_radius);
+    hasher.write_u64(*torch_ticks);
+    hasher.write_i32(*brazier_radius);
+}
+
+/// Names are drawn with the run's RNG, so the tables' contents and lengths shape the run.
+fn hash_names(hasher: &mut Xxh3, names: &NameTables) {
+    let NameTables { floor_adjectives, floor_places, elite_syllables, elite_epithets } = names;
+    for table in [floor_adjectives, floor_places, elite_syllables, elite_epithets] {
+        hasher.write_usize(table.len());
+        for name in table {
+            write_text(hasher, name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosmetic_edits_keep_the_hash_and_balance_edits_move_it() {
+        let default = ContentPack::default().content_hash();
+        let mut pack = ContentPack::default();
+        pack.display.actors[0].glyph = "?";
+        pack.weapons[0].name = "Old Sword";
+        pack.perks[0].description = "Reworded.";
+        assert_eq!(pack.content_hash(), default);
+
+        pack.weapons[0].weight += 1;
+        assert_ne!(pack.content_hash(), default);
+    }
+}
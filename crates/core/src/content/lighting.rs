@@ -0,0 +1,27 @@
+//! Lighting rules for Dark floors: shortened sight, burning torches, and fixed braziers.
+
+use std::collections::BTreeSet;
+
+/// Which floors are Dark and how far light reaches on them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LightingRules {
+    /// Floors generated without ambient light; none by default.
+    pub dark_floors: BTreeSet<u8>,
+    /// Sight radius over unlit tiles while the player has no burning torch.
+    pub dark_fov_radius: i32,
+    /// Ticks of light one torch gives; a new torch adds to whatever is still burning.
+    pub torch_ticks: u64,
+    /// Radius of the area each brazier keeps lit.
+    pub brazier_radius: i32,
+}
+
+impl Default for LightingRules {
+    fn default() -> Self {
+        Self {
+            dark_floors: BTreeSet::new(),
+            dark_fov_radius: 3,
+            torch_ticks: 400,
+            brazier_radius: 3,
+        }
+    }
+}
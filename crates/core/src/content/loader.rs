@@ -0,0 +1,208 @@
+//! Balance files: TOML overrides for enemy stats, weapons, consumables and perk amounts,
+//! applied on top of the built-in pack so a mod only lists what it changes.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::{ContentPack, PerkTuning, default_aggro_range};
+use crate::types::ActorKind;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentLoadError {
+    Io(String),
+    Parse(String),
+    UnknownWeapon(String),
+    UnknownConsumable(String),
+    /// Stats were given for a kind that never spawns as an enemy, e.g. `Player`.
+    NotAnEnemy(ActorKind),
+    /// A number outside the range the simulation accepts.
+    InvalidValue {
+        field: String,
+        value: i64,
+    },
+}
+
+impl fmt::Display for ContentLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(message) => write!(f, "cannot read balance file: {message}"),
+            Self::Parse(message) => write!(f, "balance file is not valid TOML: {message}"),
+            Self::UnknownWeapon(id) => write!(f, "unknown weapon id {id}"),
+            Self::UnknownConsumable(id) => write!(f, "unknown consumable id {id}"),
+            Self::NotAnEnemy(kind) => write!(f, "{kind:?} is not an enemy kind"),
+            Self::InvalidValue { field, value } => write!(f, "{field} cannot be {value}"),
+        }
+    }
+}
+
+impl Error for ContentLoadError {}
+
+/// Contents of one balance file; every section and field is optional.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+struct BalanceOverrides {
+    enemies: Vec<EnemyOverride>,
+    weapons: Vec<WeaponOverride>,
+    consumables: Vec<ConsumableOverride>,
+    perks: Option<PerkTuning>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct EnemyOverride {
+    kind: ActorKind,
+    hp: Option<i32>,
+    attack: Option<i32>,
+    defense: Option<i32>,
+    speed: Option<u32>,
+    aggro_radius: Option<u32>,
+    leash_radius: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct WeaponOverride {
+    id: String,
+    attack_bonus: Option<i32>,
+    weight: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ConsumableOverride {
+    id: String,
+    heal_amount: Option<i32>,
+}
+
+impl ContentPack {
+    /// The default pack with the balance file at `path` applied.
+    pub fn load_from_path(path: &Path) -> Result<Self, ContentLoadError> {
+        let text =
+            fs::read_to_string(path).map_err(|error| ContentLoadError::Io(error.to_string()))?;
+        Self::from_toml_str(&text)
+    }
+
+    /// The default pack with the balance overrides in `text` applied.
+    pub fn from_toml_str(text: &str) -> Result<Self, ContentLoadError> {
+        let overrides: BalanceOverrides =
+            toml::from_str(text).map_err(|error| ContentLoadError::Parse(error.to_string()))?;
+        let mut pack = Self::build_default();
+        for entry in overrides.enemies {
+            let stats = pack
+                .enemies
+                .get_mut(&entry.kind)
+                .ok_or(ContentLoadError::NotAnEnemy(entry.kind))?;
+            stats.hp = at_least("hp", entry.hp.unwrap_or(stats.hp), 1)?;
+            stats.attack = at_least("attack", entry.attack.unwrap_or(stats.attack), 0)?;
+            stats.defense = at_least("defense", entry.defense.unwrap_or(stats.defense), 0)?;
+            stats.speed = at_least("speed", entry.speed.unwrap_or(stats.speed), 1)?;
+            let range = pack.aggro.entry(entry.kind).or_insert(default_aggro_range(entry.kind));
+            range.aggro_radius = entry.aggro_radius.unwrap_or(range.aggro_radius);
+            range.leash_radius = entry.leash_radius.unwrap_or(range.leash_radius);
+        }
+        for entry in overrides.weapons {
+            let weapon = pack
+                .weapons
+                .iter_mut()
+                .find(|weapon| weapon.id == entry.id)
+                .ok_or(ContentLoadError::UnknownWeapon(entry.id))?;
+            weapon.attack_bonus =
+                at_least("attack_bonus", entry.attack_bonus.unwrap_or(weapon.attack_bonus), 0)?;
+            weapon.weight = entry.weight.unwrap_or(weapon.weight);
+        }
+        for entry in overrides.consumables {
+            let consumable = pack
+                .consumables
+                .iter_mut()
+                .find(|consumable| consumable.id == entry.id)
+                .ok_or(ContentLoadError::UnknownConsumable(entry.id))?;
+            consumable.heal_amount =
+                at_least("heal_amount", entry.heal_amount.unwrap_or(consumable.heal_amount), 0)?;
+        }
+        if let Some(perks) = overrides.perks {
+            at_least("perks.pacifist_max_hp", perks.pacifist_max_hp, 0)?;
+            at_least("perks.bloodlust_heal", perks.bloodlust_heal, 0)?;
+            pack.perk_tuning = perks;
+        }
+        Ok(pack)
+    }
+}
+
+fn at_least<T: Copy + PartialOrd + Into<i64>>(
+    field: &str,
+    value: T,
+    min: T,
+) -> Result<T, ContentLoadError> {
+    if value < min {
+        return Err(ContentLoadError::InvalidValue {
+            field: field.to_string(),
+            value: value.into(),
+        });
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::keys;
+
+    #[test]
+    fn overrides_replace_only_the_listed_numbers() {
+        let pack = ContentPack::from_toml_str(
+            r#"
+            [[enemies]]
+            kind = "Goblin"
+            hp = 14
+            leash_radius = 3
+
+            [[weapons]]
+            id = "weapon_iron_mace"
+            attack_bonus = 5
+
+            [[consumables]]
+            id = "consumable_minor_hp_pot"
+            heal_amount = 12
+
+            [perks]
+            scout_fov_bonus = 3
+            "#,
+        )
+        .expect("valid balance file");
+        let default = ContentPack::default();
+        let goblin = pack.enemies[&ActorKind::Goblin];
+        assert_eq!(goblin.hp, 14);
+        assert_eq!(goblin.attack, default.enemies[&ActorKind::Goblin].attack);
+        assert_eq!(pack.aggro[&ActorKind::Goblin].leash_radius, 3);
+        assert_eq!(pack.aggro[&ActorKind::Goblin].aggro_radius, 5);
+        let mace = pack.weapons.iter().find(|weapon| weapon.id == keys::WEAPON_IRON_MACE).unwrap();
+        assert_eq!((mace.attack_bonus, mace.weight), (5, 6));
+        assert_eq!(pack.consumables[0].heal_amount, 12);
+        assert_eq!(pack.perk_tuning, PerkTuning { scout_fov_bonus: 3, ..PerkTuning::default() });
+        assert_ne!(pack.content_hash(), default.content_hash());
+    }
+
+    #[test]
+    fn bad_balance_files_name_the_problem() {
+        let error = |text: &str| ContentPack::from_toml_str(text).unwrap_err();
+        assert_eq!(
+            error("[[weapons]]\nid = \"weapon_laser\""),
+            ContentLoadError::UnknownWeapon("weapon_laser".to_string())
+        );
+        assert_eq!(
+            error("[[enemies]]\nkind = \"Player\""),
+            ContentLoadError::NotAnEnemy(ActorKind::Player)
+        );
+        assert_eq!(
+            error("[[enemies]]\nkind = \"Goblin\"\nspeed = 0").to_string(),
+            "speed cannot be 0"
+        );
+        assert!(matches!(error("[perks]\nswiftness = 2"), ContentLoadError::Parse(_)));
+        let missing = ContentPack::load_from_path(Path::new("/nonexistent/balance.toml"));
+        assert!(matches!(missing, Err(ContentLoadError::Io(_))));
+    }
+}
@@ -0,0 +1,106 @@
+//! Cosmetic name tables: floor names like "The Sunken Gallery" and elite epithets like
+//! "Morgrath the Unbowed". Every pick is keyed off the floor seed, so replays show the same names.
+
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+/// Salts keeping each table's pick independent of the others for one floor seed.
+const FLOOR_ADJECTIVE_SALT: u64 = 1;
+const FLOOR_PLACE_SALT: u64 = 2;
+const ELITE_FIRST_SYLLABLE_SALT: u64 = 3;
+const ELITE_SECOND_SYLLABLE_SALT: u64 = 4;
+const ELITE_EPITHET_SALT: u64 = 5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NameTables {
+    pub floor_adjectives: Vec<&'static str>,
+    pub floor_places: Vec<&'static str>,
+    /// Syllables an elite's given name is built from, two to a name.
+    pub elite_syllables: Vec<&'static str>,
+    pub elite_epithets: Vec<&'static str>,
+}
+
+impl NameTables {
+    /// The floor's name, e.g. `The Sunken Gallery`.
+    pub fn floor_name(&self, floor_seed: u64) -> String {
+        let adjective = pick(&self.floor_adjectives, floor_seed, FLOOR_ADJECTIVE_SALT);
+        let place = pick(&self.floor_places, floor_seed, FLOOR_PLACE_SALT);
+        format!("The {adjective} {place}")
+    }
+
+    /// The name of the elite a floor's objective marks, e.g. `Morgrath the Unbowed`.
+    pub fn elite_name(&self, floor_seed: u64) -> String {
+        let first = pick(&self.elite_syllables, floor_seed, ELITE_FIRST_SYLLABLE_SALT);
+        let second = pick(&self.elite_syllables, floor_seed, ELITE_SECOND_SYLLABLE_SALT);
+        let epithet = pick(&self.elite_epithets, floor_seed, ELITE_EPITHET_SALT);
+        format!("{first}{} the {epithet}", second.to_lowercase())
+    }
+}
+
+fn pick(table: &[&'static str], floor_seed: u64, salt: u64) -> &'static str {
+    if table.is_empty() {
+        return "";
+    }
+    let roll = xxh3_64_with_seed(&salt.to_le_bytes(), floor_seed);
+    table[(roll % table.len() as u64) as usize]
+}
+
+impl Default for NameTables {
+    fn default() -> Self {
+        Self {
+            floor_adjectives: vec![
+                "Sunken",
+                "Hollow",
+                "Weeping",
+                "Ashen",
+                "Forgotten",
+                "Gilded",
+                "Drowned",
+                "Silent",
+                "Broken",
+                "Crimson",
+            ],
+            floor_places: vec![
+                "Gallery",
+                "Cloister",
+                "Ossuary",
+                "Vault",
+                "Undercroft",
+                "Cistern",
+                "Nave",
+                "Catacomb",
+                "Reliquary",
+                "Warren",
+            ],
+            elite_syllables: vec![
+                "Mor", "Grath", "Vel", "Ska", "Dru", "Keth", "Ul", "Zar", "Bra", "Nox",
+            ],
+            elite_epithets: vec![
+                "Unbowed",
+                "Gnawer",
+                "Pale",
+                "Oathless",
+                "Red-Handed",
+                "Cinderborn",
+                "Hungering",
+                "Twice-Buried",
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_follow_the_floor_seed_alone() {
+        let tables = NameTables::default();
+        assert_eq!(tables.floor_name(41), tables.floor_name(41));
+        assert_eq!(tables.elite_name(41), tables.elite_name(41));
+        assert!(tables.floor_name(41).starts_with("The "));
+        assert!(tables.elite_name(41).contains(" the "));
+
+        let names: Vec<String> = (0..20).map(|seed| tables.floor_name(seed)).collect();
+        assert!(names.iter().any(|name| *name != names[0]), "{names:?}");
+    }
+}
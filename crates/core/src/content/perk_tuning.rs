@@ -0,0 +1,39 @@
+//! Perk magnitudes: how much each perk changes the numbers it touches.
+//! Which stat a perk affects stays in the simulation; only the amounts are data.
+
+use serde::Deserialize;
+
+use super::TOUGHNESS_CARRY_BONUS;
+
+/// The amount behind each perk; a balance file may set any subset of them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PerkTuning {
+    pub toughness_carry_bonus: u32,
+    /// Ticks Swift takes off every action; an action never drops below one tick.
+    pub swift_tick_discount: u32,
+    pub berserker_unarmed_attack: i32,
+    /// Max HP Pacifist's Bounty grants on descending without a kill.
+    pub pacifist_max_hp: i32,
+    pub iron_will_defense: i32,
+    pub bloodlust_heal: i32,
+    pub scout_fov_bonus: i32,
+    pub reckless_attack: i32,
+    pub reckless_defense_penalty: i32,
+}
+
+impl Default for PerkTuning {
+    fn default() -> Self {
+        Self {
+            toughness_carry_bonus: TOUGHNESS_CARRY_BONUS,
+            swift_tick_discount: 1,
+            berserker_unarmed_attack: 3,
+            pacifist_max_hp: 5,
+            iron_will_defense: 2,
+            bloodlust_heal: 2,
+            scout_fov_bonus: 2,
+            reckless_attack: 4,
+            reckless_defense_penalty: 2,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Detour rule for Rush exploration, which heads for known down stairs by the fastest route.
+
+/// When a Rush route leaves the way to the stairs to pick up a known healing potion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RushDetourRules {
+    /// Detour only while the player is missing at least this percent of max HP.
+    pub min_missing_hp_percent: u32,
+    /// Most extra ticks a detour may add to the trip to the stairs.
+    pub max_extra_ticks: u32,
+}
+
+impl Default for RushDetourRules {
+    fn default() -> Self {
+        Self { min_missing_hp_percent: 30, max_extra_ticks: 12 }
+    }
+}
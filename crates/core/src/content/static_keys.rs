@@ -0,0 +1,93 @@
+//! Save-file form of static content keys: ids written as plain strings and resolved back to
+//! the built-in `&'static str` on load, so unknown ids fail the load instead of leaking.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer};
+
+use super::catalog::default_perks;
+use super::{POTION_APPEARANCES, resolve_item_key};
+use crate::types::ItemKind;
+
+/// The static key equal to `key`: an item, perk or potion appearance.
+fn resolve_static_key(key: &str) -> Option<&'static str> {
+    resolve_item_key(key)
+        .or_else(|| default_perks().into_iter().map(|perk| perk.id).find(|id| *id == key))
+        .or_else(|| POTION_APPEARANCES.into_iter().find(|appearance| *appearance == key))
+}
+
+fn resolve<E: DeError>(key: &str) -> Result<&'static str, E> {
+    resolve_static_key(key).ok_or_else(|| E::custom(format!("unknown content key: {key}")))
+}
+
+pub(crate) fn key<'de, D: Deserializer<'de>>(deserializer: D) -> Result<&'static str, D::Error> {
+    resolve(&String::deserialize(deserializer)?)
+}
+
+pub(crate) fn key_list<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<&'static str>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?.iter().map(|key| resolve(key)).collect()
+}
+
+pub(crate) fn key_set<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeSet<&'static str>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?.iter().map(|key| resolve(key)).collect()
+}
+
+pub(crate) fn key_map<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<&'static str, &'static str>, D::Error> {
+    BTreeMap::<String, String>::deserialize(deserializer)?
+        .iter()
+        .map(|(key, value)| Ok((resolve(key)?, resolve(value)?)))
+        .collect()
+}
+
+/// Owned wire form of `ItemKind`.
+#[derive(Deserialize)]
+enum StoredItemKind {
+    Weapon(String),
+    Consumable(String),
+    Perk(String),
+}
+
+impl<'de> Deserialize<'de> for ItemKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match StoredItemKind::deserialize(deserializer)? {
+            StoredItemKind::Weapon(key) => Self::Weapon(resolve(&key)?),
+            StoredItemKind::Consumable(key) => Self::Consumable(resolve(&key)?),
+            StoredItemKind::Perk(key) => Self::Perk(resolve(&key)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+
+    #[test]
+    fn keys_resolve_to_their_static_ids_and_unknown_ones_fail() {
+        let kind = ItemKind::Perk(keys::PERK_SCOUT);
+        let json = serde_json::to_string(&kind).unwrap();
+        assert_eq!(serde_json::from_str::<ItemKind>(&json).unwrap(), kind);
+        assert!(serde_json::from_str::<ItemKind>(&json.replace("scout", "spy")).is_err());
+
+        let mut json = serde_json::Deserializer::from_str("[\"Amber Potion\"]");
+        assert_eq!(key_list(&mut json).unwrap(), vec!["Amber Potion"]);
+    }
+
+    #[test]
+    fn every_catalog_weapon_and_consumable_resolves() {
+        let content = ContentPack::default();
+        let weapons = content.weapons.iter().map(|weapon| weapon.id);
+        for id in weapons.chain(content.consumables.iter().map(|consumable| consumable.id)) {
+            assert_eq!(resolve_item_key(id), Some(id));
+        }
+        assert_eq!(resolve_item_key("weapon_wooden_spoon"), None);
+        assert_eq!(resolve_item_key(keys::PERK_SCOUT), None, "perks are not items");
+    }
+}
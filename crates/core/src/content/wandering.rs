@@ -0,0 +1,28 @@
+//! Pacing for wandering monsters that arrive on a floor the player lingers on.
+
+/// How soon and how often wandering enemies appear after the player reaches a floor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WanderingSpawnRate {
+    /// Ticks on a floor before the first wanderer may appear.
+    pub grace_ticks: u64,
+    /// Ticks between later spawn rolls.
+    pub interval_ticks: u64,
+    /// Chance out of 100 that a spawn roll produces an enemy.
+    pub chance_percent: u32,
+    /// Most wanderers a single floor visit can receive; zero turns wandering spawns off.
+    pub max_per_floor: u8,
+    /// Minimum Manhattan distance from the player to a spawn tile.
+    pub min_distance: u32,
+}
+
+impl Default for WanderingSpawnRate {
+    fn default() -> Self {
+        Self {
+            grace_ticks: 600,
+            interval_ticks: 150,
+            chance_percent: 50,
+            max_per_floor: 3,
+            min_distance: 8,
+        }
+    }
+}
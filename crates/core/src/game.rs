@@ -2,62 +2,149 @@
 //! This module composes focused submodules into the public `Game` API.
 //! It does not own low-level navigation, visibility, item, or prompt implementation details.
 
+use std::collections::BTreeMap;
+
 use rand_chacha::ChaCha8Rng;
 
-use crate::content::keys;
+use crate::content::{
+    ActionCosts, AggroRange, BossRoster, Consumable, DifficultyScaling, EnemyStats, LightingRules,
+    NameTables, PerkTuning, RushDetourRules, WanderingSpawnRate, Weapon, keys,
+};
+use crate::ordering;
 use crate::state::GameState;
 use crate::types::*;
 
+mod action_time;
+mod ambience;
 mod auto_explore;
+mod auto_fight;
+mod balance;
+#[doc(hidden)]
+pub mod bench_support;
+mod boons;
 mod bootstrap;
+mod brace;
+mod checkpoint;
 mod choices;
+mod death;
+mod diagnostics;
+mod director;
+mod enemy_memory;
+mod enemy_turns;
 mod engine;
+mod fight_preview;
 mod floor_transition;
 mod hash;
+mod hazard_spread;
+mod hud;
+mod input;
+mod invariants;
 mod items;
+mod leveling;
+mod lighting;
+mod manual_step;
+mod markers;
+mod memory;
+mod names;
+mod objectives;
+mod path_preview;
 mod pathfinding;
+mod policy_default;
 mod prompts;
+mod save;
+mod scenario;
+mod snooze;
+mod stats;
+mod stealth;
+mod target_scan;
 mod threat;
+mod uniques;
+mod victory;
 mod visibility;
+mod waiting;
+mod wandering;
 
 #[cfg(test)]
 mod test_support;
 
+use ambience::roll_ambience;
 use auto_explore::{
-    choose_frontier_intent, is_frontier_candidate, is_intent_target_still_valid, path_for_intent,
+    PathCache, RushPlan, choose_frontier_intent, choose_frontier_intent_skipping,
+    choose_objective_intent, choose_rush_intent, is_frontier_candidate,
+    is_intent_target_still_valid, planner_pass,
 };
+pub use checkpoint::GameCheckpoint;
+use diagnostics::DebugCounters;
+use enemy_memory::EnemySighting;
 use pathfinding::{
-    astar_path, astar_path_allow_hazards, enemy_path_to_player, manhattan, neighbors,
+    astar_path, astar_search, enemy_path_to_player, manhattan, neighbors,
     reachable_discovered_walkable_tiles,
 };
 use prompts::PendingPrompt;
+pub use save::{GameSaveError, SAVE_FORMAT_VERSION};
+use snooze::SnoozeTarget;
 use visibility::compute_fov;
 
 pub(super) const FOV_RADIUS: i32 = 10;
+/// Most FOV radius Keen Sight boons can add over a run.
+pub(super) const MAX_FOV_BONUS: u8 = 3;
 pub(super) const MAX_NO_PROGRESS_TICKS: u32 = 64;
 
+#[derive(Clone)]
 pub struct Game {
     seed: u64,
     tick: u64,
-    #[expect(dead_code)]
     rng: ChaCha8Rng,
     state: GameState,
-    log: Vec<LogEvent>,
+    log: EventLog,
     next_input_seq: u64,
     pending_prompt: Option<PendingPrompt>,
     suppressed_enemy: Option<EntityId>,
     pause_requested: bool,
     at_pause_boundary: bool,
     finished_outcome: Option<RunOutcome>,
+    /// How the player died, set by the death pipeline alongside a defeat outcome.
+    death: Option<DeathRecord>,
     no_progress_ticks: u32,
+    action_costs: ActionCosts,
+    wandering_spawns: WanderingSpawnRate,
+    rush_detour: RushDetourRules,
+    threat_trace_depth: usize,
+    threat_memory_ticks: u64,
+    /// Last sighting of each enemy on this floor; presentation only, never hashed.
+    enemy_memory: BTreeMap<EntityId, EnemySighting>,
+    floor_objectives: BTreeMap<u8, FloorObjective>,
+    boss_roster: BossRoster,
+    /// Set once the player answers a locked-stairs prompt; cleared when they step off the stairs.
+    stairs_lock_acknowledged: bool,
+    /// Snoozed loot and door prompts, each with the tick its snooze runs out.
+    snoozed_targets: BTreeMap<SnoozeTarget, u64>,
+    lighting: LightingRules,
+    names: NameTables,
+    enemy_stats: BTreeMap<ActorKind, EnemyStats>,
+    aggro: BTreeMap<ActorKind, AggroRange>,
+    weapons: Vec<Weapon>,
+    consumables: Vec<Consumable>,
+    perk_tuning: PerkTuning,
+    difficulty: Difficulty,
+    difficulty_scaling: DifficultyScaling,
+    debug_counters: DebugCounters,
+    /// Breakdown of the advance call in progress; reset at the start of each call.
+    advance_tally: AdvanceBreakdown,
+    /// Last target scan with the fingerprint of the state it was computed from; never hashed.
+    target_scan: Option<(u64, TargetScan)>,
+    /// Routes the planner already searched on the current map revision; never hashed.
+    path_cache: PathCache,
+    stats: RunStats,
 }
 
 impl Game {
     pub fn get_fov_radius(&self) -> i32 {
+        let radius = FOV_RADIUS + i32::from(self.state.fov_bonus);
         if self.state.active_perks.contains(&keys::PERK_SCOUT) {
-            FOV_RADIUS + 2
+            self.ambient_sight(radius + self.perk_tuning.scout_fov_bonus)
         } else {
-            FOV_RADIUS
+            self.ambient_sight(radius)
         }
     }
 
@@ -65,6 +152,10 @@ impl Game {
         self.seed
     }
 
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
     pub fn current_tick(&self) -> u64 {
         self.tick
     }
@@ -81,7 +172,7 @@ impl Game {
         &self.state
     }
 
-    pub fn log(&self) -> &[LogEvent] {
+    pub fn log(&self) -> &EventLog {
         &self.log
     }
 }
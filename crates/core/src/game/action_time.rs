@@ -0,0 +1,81 @@
+//! Time cost of player actions and the busy-until bookkeeping that enforces it.
+//! This module exists so every action pays from the same content-defined cost table.
+//! It does not own which actions are legal; callers decide that before spending time.
+
+use super::*;
+use crate::content::{ActionKind, BASE_ACTION_SPEED};
+
+impl Game {
+    /// Ticks the player needs to finish `action`, after speed, load, and perk adjustments.
+    /// Faster players pay proportionally less, and every action costs at least one tick.
+    pub fn player_action_ticks(&self, action: ActionKind) -> u64 {
+        let base = self.apply_encumbrance(self.action_costs.base_cost(action));
+        let speed = self.state.actors[self.state.player_id].speed.max(1);
+        let mut ticks = (base * BASE_ACTION_SPEED).div_ceil(speed);
+        if self.state.active_perks.contains(&keys::PERK_SWIFT) {
+            ticks = ticks.saturating_sub(self.perk_tuning.swift_tick_discount);
+        }
+        u64::from(ticks.max(1))
+    }
+
+    /// Mark the player busy until `action` completes.
+    /// Costs stack when several actions are taken at the same boundary.
+    pub(super) fn spend_player_action(&mut self, action: ActionKind) {
+        let ticks = self.player_action_ticks(action);
+        let now = self.tick;
+        let player = &mut self.state.actors[self.state.player_id];
+        player.next_action_tick = player.next_action_tick.max(now) + ticks;
+    }
+
+    /// Whether the player is still finishing an earlier action this tick.
+    pub(super) fn player_is_busy(&self) -> bool {
+        self.tick < self.state.actors[self.state.player_id].next_action_tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    fn empty_game() -> Game {
+        let mut game = Game::new(8080, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game
+    }
+
+    #[test]
+    fn action_ticks_follow_content_table_at_base_speed() {
+        let game = empty_game();
+        assert_eq!(game.player_action_ticks(ActionKind::Move), 1);
+        assert_eq!(game.player_action_ticks(ActionKind::PickUp), 2);
+        assert_eq!(game.player_action_ticks(ActionKind::SwapWeapon), 10);
+    }
+
+    #[test]
+    fn speed_and_swift_perk_reduce_action_ticks() {
+        let mut game = empty_game();
+        game.state.actors[game.state.player_id].speed = 20;
+        assert_eq!(game.player_action_ticks(ActionKind::SwapWeapon), 5);
+        assert_eq!(game.player_action_ticks(ActionKind::Move), 1, "actions cost at least a tick");
+
+        game.state.active_perks.push(keys::PERK_SWIFT);
+        assert_eq!(game.player_action_ticks(ActionKind::SwapWeapon), 4);
+    }
+
+    #[test]
+    fn busy_player_waits_without_moving_or_stalling() {
+        let mut game = empty_game();
+        let start = game.state.actors[game.state.player_id].pos;
+        game.spend_player_action(ActionKind::Fight);
+
+        let result = game.advance(3);
+        assert_eq!(result.simulated_ticks, 3);
+        assert_eq!(game.state.actors[game.state.player_id].pos, start);
+        assert_eq!(game.no_progress_ticks, 0, "waiting out an action is not a stall");
+
+        game.advance(1);
+        assert_ne!(game.state.actors[game.state.player_id].pos, start);
+    }
+}
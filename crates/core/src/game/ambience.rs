@@ -0,0 +1,118 @@
+//! Floor ambience: fog, echoes and tremors rolled per floor from the run seed.
+//! Rolls never draw from the game RNG, so a floor's ambience depends only on the seed and
+//! its index, and revisiting a floor meets the same weather.
+
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::*;
+use crate::mapgen::STARTING_FLOOR_INDEX;
+
+/// Sight lost to fog, by the player and by every enemy watching for them.
+const FOG_SIGHT_PENALTY: u32 = 2;
+/// Extra distance at which enemies notice the player on an echoing floor.
+const ECHO_NOTICE_BONUS: u32 = 3;
+/// Ticks between tremors; each drops short-lived rubble on one open tile.
+const TREMOR_PERIOD_TICKS: u64 = 30;
+
+fn ambience_roll(run_seed: u64, floor_index: u8, salt: u64) -> u64 {
+    let mut bytes = [0_u8; 17];
+    bytes[..8].copy_from_slice(&run_seed.to_le_bytes());
+    bytes[8..16].copy_from_slice(&salt.to_le_bytes());
+    bytes[16] = floor_index;
+    xxh3_64(&bytes)
+}
+
+/// The ambience on `floor_index` for this seed; the starting floor is always still.
+pub(super) fn roll_ambience(run_seed: u64, floor_index: u8) -> Ambience {
+    if floor_index <= STARTING_FLOOR_INDEX {
+        return Ambience::Still;
+    }
+    match ambience_roll(run_seed, floor_index, 0) % 8 {
+        0 | 1 => Ambience::Fog,
+        2 => Ambience::Echoes,
+        3 => Ambience::Tremors,
+        _ => Ambience::Still,
+    }
+}
+
+impl Game {
+    /// The player's sight radius after the floor's ambience.
+    pub(super) fn ambient_sight(&self, radius: i32) -> i32 {
+        match self.state.ambience {
+            Ambience::Fog => (radius - FOG_SIGHT_PENALTY as i32).max(1),
+            _ => radius,
+        }
+    }
+
+    /// An enemy's notice radius after the floor's ambience.
+    pub(super) fn ambient_notice_radius(&self, radius: u32) -> u32 {
+        match self.state.ambience {
+            Ambience::Fog => radius.saturating_sub(FOG_SIGHT_PENALTY).max(1),
+            Ambience::Echoes => radius + ECHO_NOTICE_BONUS,
+            Ambience::Still | Ambience::Tremors => radius,
+        }
+    }
+
+    /// On a trembling floor, drop rubble every `TREMOR_PERIOD_TICKS` on an open floor tile
+    /// picked from the seed and tick, never under an actor.
+    pub(super) fn step_tremors(&mut self) {
+        if self.state.ambience != Ambience::Tremors
+            || !self.tick.is_multiple_of(TREMOR_PERIOD_TICKS)
+        {
+            return;
+        }
+        let occupied: Vec<Pos> = self.state.actors.values().map(|actor| actor.pos).collect();
+        let open: Vec<Pos> = self
+            .state
+            .map
+            .positions()
+            .filter(|pos| self.state.map.tile_at(*pos) == TileKind::Floor)
+            .filter(|pos| !occupied.contains(pos))
+            .collect();
+        if open.is_empty() {
+            return;
+        }
+        let roll = ambience_roll(self.seed, self.state.floor_index, self.tick);
+        let pos = open[(roll % open.len() as u64) as usize];
+        self.release_hazard(pos, HazardKind::Rubble);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    #[test]
+    fn ambience_is_fixed_by_seed_and_floor() {
+        let rolls = |seed| (1..=6).map(|floor| roll_ambience(seed, floor)).collect::<Vec<_>>();
+        assert_eq!(rolls(2758), rolls(2758));
+        assert_eq!(roll_ambience(2758, STARTING_FLOOR_INDEX), Ambience::Still);
+        let all: Vec<Ambience> = (0..64).flat_map(rolls).collect();
+        for ambience in [Ambience::Fog, Ambience::Echoes, Ambience::Tremors] {
+            assert!(all.contains(&ambience), "{ambience:?} never rolled");
+        }
+    }
+
+    #[test]
+    fn fog_shortens_sight_and_tremors_drop_rubble_the_same_way_on_replay() {
+        let mut game = Game::new(2758, &ContentPack::default(), GameMode::Ironman);
+        let clear = game.get_fov_radius();
+        game.state.ambience = Ambience::Fog;
+        assert_eq!(game.get_fov_radius(), clear - 2);
+
+        let rubble_after = |ticks: u64| {
+            let mut game = Game::new(2758, &ContentPack::default(), GameMode::Ironman);
+            game.state.ambience = Ambience::Tremors;
+            game.tick = ticks;
+            game.step_tremors();
+            let map = &game.state.map;
+            map.positions()
+                .filter(|pos| map.spreading_hazard_at(*pos).is_some())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rubble_after(TREMOR_PERIOD_TICKS).len(), 1);
+        assert_eq!(rubble_after(TREMOR_PERIOD_TICKS), rubble_after(TREMOR_PERIOD_TICKS));
+        assert!(rubble_after(TREMOR_PERIOD_TICKS + 1).is_empty());
+    }
+}
@@ -1,13 +1,18 @@
 //! Auto-explore target selection and deterministic intent planning.
-//! This module composes focused submodules for target selection, frontier rules, and route choice.
+//! This module composes focused submodules for target selection, frontier rules, route choice,
+//! and Rush routing to known stairs.
 
 mod frontier;
 mod pathing;
 mod planner;
+mod rush;
 mod search;
 
 pub(super) use frontier::{
     is_frontier_candidate, is_intent_target_still_valid, is_safe_frontier_candidate,
 };
-pub(super) use pathing::path_for_intent;
-pub(super) use planner::choose_frontier_intent;
+pub(super) use pathing::{PathCache, planner_pass};
+pub(super) use planner::{
+    choose_frontier_intent, choose_frontier_intent_skipping, choose_objective_intent,
+};
+pub(super) use rush::{RushPlan, choose_rush_intent};
@@ -1,15 +1,110 @@
 //! Route selection for executing a chosen auto-explore intent.
+//! Routes are cached per map revision, so revalidating an intent every tick reuses the search.
 
-use super::super::{AutoExploreIntent, AutoReason, Pos, astar_path, astar_path_allow_hazards};
+use std::collections::BTreeMap;
+use std::mem::size_of;
+
+use super::super::{AutoExploreIntent, AutoReason, PlannerPass, Pos};
+use crate::game::pathfinding::{PathSearch, ResumableSearch, SearchOptions};
 use crate::state::Map;
 
-pub(in crate::game) fn path_for_intent(
-    map: &Map,
-    start: Pos,
-    intent: AutoExploreIntent,
-) -> Option<Vec<Pos>> {
+/// Most routes the cache keeps before starting over; the planner rarely needs more than two.
+const PATH_CACHE_CAPACITY: usize = 16;
+
+/// Which map a cached route was searched on: the floor index and that map's revision.
+pub(in crate::game) type MapRevision = (u8, u64);
+
+/// Start, goal and whether hazards are avoided: what a planner route is searched for.
+type RouteKey = (Pos, Pos, bool);
+
+/// Planner routes keyed by start, goal and whether hazards were avoided, all found on one
+/// map revision; any change to the map empties it. During a budgeted advance a search may
+/// stop partway, and its frontier waits here until the planner asks for that route again.
+#[derive(Clone, Debug, Default)]
+pub(in crate::game) struct PathCache {
+    revision: Option<MapRevision>,
+    routes: BTreeMap<RouteKey, PathSearch>,
+    pending: Option<(RouteKey, ResumableSearch)>,
+    /// Expansions left for searches in the current budgeted advance; `None` searches whole.
+    expansion_budget: Option<u32>,
+    /// Whether a lookup since planning began ran out of budget before its route was found.
+    yielded: bool,
+}
+
+impl PathCache {
+    /// The route for `intent`, and whether it came from the cache instead of a fresh search;
+    /// `None` if the expansion budget ran out first, leaving the search to resume later.
+    pub(in crate::game) fn path_for_intent(
+        &mut self,
+        revision: MapRevision,
+        map: &Map,
+        start: Pos,
+        intent: AutoExploreIntent,
+    ) -> Option<(PathSearch, bool)> {
+        if self.revision != Some(revision) {
+            self.revision = Some(revision);
+            self.routes.clear();
+            self.pending = None;
+        }
+        let avoid_hazards = planner_pass(intent) == PlannerPass::Safe;
+        let key = (start, intent.target, avoid_hazards);
+        if let Some(search) = self.routes.get(&key) {
+            return Some((search.clone(), true));
+        }
+        let mut search = match self.pending.take() {
+            Some((pending_key, search)) if pending_key == key => search,
+            _ => ResumableSearch::new(
+                map,
+                start,
+                intent.target,
+                SearchOptions::player(avoid_hazards),
+            ),
+        };
+        let mut unlimited = u32::MAX;
+        let Some(found) =
+            search.resume(map, self.expansion_budget.as_mut().unwrap_or(&mut unlimited))
+        else {
+            self.pending = Some((key, search));
+            self.yielded = true;
+            return None;
+        };
+        if self.routes.len() >= PATH_CACHE_CAPACITY {
+            self.routes.clear();
+        }
+        self.routes.insert(key, found.clone());
+        Some((found, false))
+    }
+
+    /// Cap the expansions searches may spend until the budget is lifted with `None`.
+    pub(in crate::game) fn set_expansion_budget(&mut self, budget: Option<u32>) {
+        self.expansion_budget = budget;
+        self.yielded = false;
+    }
+
+    /// Start a planning pass with no search left waiting on budget.
+    pub(in crate::game) fn begin_planning(&mut self) {
+        self.yielded = false;
+    }
+
+    /// Whether this planning pass stopped on an unfinished search.
+    pub(in crate::game) fn yielded(&self) -> bool {
+        self.yielded
+    }
+
+    /// Bytes the cached routes and any unfinished search hold.
+    pub(in crate::game) fn heap_bytes(&self) -> usize {
+        let entry = size_of::<(RouteKey, PathSearch)>();
+        let path_bytes = |search: &PathSearch| search.path.as_ref().map_or(0, Vec::capacity);
+        let routes: usize =
+            self.routes.values().map(|search| entry + path_bytes(search) * size_of::<Pos>()).sum();
+        routes + self.pending.as_ref().map_or(0, |(_, search)| search.heap_bytes())
+    }
+}
+
+/// The pass an intent was planned in: threat-avoidance targets came from the hazard fallback.
+pub(in crate::game) fn planner_pass(intent: AutoExploreIntent) -> PlannerPass {
     match intent.reason {
-        AutoReason::ThreatAvoidance => astar_path_allow_hazards(map, start, intent.target),
-        _ => astar_path(map, start, intent.target),
+        AutoReason::ThreatAvoidance => PlannerPass::HazardFallback,
+        _ => PlannerPass::Safe,
     }
 }
@@ -1,28 +1,88 @@
 //! High-level target selection policy for auto-explore.
 
-use super::super::{AutoExploreIntent, AutoReason, Pos, TileKind};
+use super::super::{Aggro, AutoExploreIntent, AutoReason, Pos, TileKind};
 use super::frontier::is_frontier_candidate;
 use super::search::find_nearest_auto_target;
 use crate::state::Map;
 
-pub(in crate::game) fn choose_frontier_intent(map: &Map, start: Pos) -> Option<AutoExploreIntent> {
-    if let Some(intent) = find_nearest_frontier(map, start, true) {
+/// Pick the next auto-explore target: visible loot when the policy is Greedy, then
+/// the nearest frontier, then the down stairs once the floor is explored.
+pub(in crate::game) fn choose_frontier_intent(
+    map: &Map,
+    start: Pos,
+    visible_loot: &[Pos],
+    aggression: Aggro,
+) -> Option<AutoExploreIntent> {
+    choose_frontier_intent_skipping(map, start, visible_loot, aggression, &[])
+}
+
+/// As `choose_frontier_intent`, but never targeting a frontier in `skipped`.
+pub(in crate::game) fn choose_frontier_intent_skipping(
+    map: &Map,
+    start: Pos,
+    visible_loot: &[Pos],
+    aggression: Aggro,
+    skipped: &[Pos],
+) -> Option<AutoExploreIntent> {
+    if let Some(intent) = find_nearest_loot(map, start, visible_loot, aggression) {
         return Some(intent);
     }
 
-    if let Some(intent) = find_nearest_frontier(map, start, false) {
+    if let Some(intent) = find_nearest_frontier(map, start, true, skipped) {
+        return Some(intent);
+    }
+
+    if let Some(intent) = find_nearest_frontier(map, start, false, skipped) {
         return Some(AutoExploreIntent { reason: AutoReason::ThreatAvoidance, ..intent });
     }
 
     choose_downstairs_intent(map, start)
 }
 
-fn find_nearest_frontier(map: &Map, start: Pos, avoid_hazards: bool) -> Option<AutoExploreIntent> {
+/// Route to an open floor objective at `target`, used instead of locked down stairs.
+pub(in crate::game) fn choose_objective_intent(
+    map: &Map,
+    start: Pos,
+    target: Pos,
+) -> Option<AutoExploreIntent> {
+    find_nearest_auto_target(
+        map,
+        start,
+        true,
+        |current| current == target,
+        |_| AutoReason::Objective,
+    )
+}
+
+fn find_nearest_loot(
+    map: &Map,
+    start: Pos,
+    visible_loot: &[Pos],
+    aggression: Aggro,
+) -> Option<AutoExploreIntent> {
+    if aggression == Aggro::Conserve || visible_loot.is_empty() {
+        return None;
+    }
+    find_nearest_auto_target(
+        map,
+        start,
+        true,
+        |current| visible_loot.contains(&current),
+        |_| AutoReason::Loot,
+    )
+}
+
+fn find_nearest_frontier(
+    map: &Map,
+    start: Pos,
+    avoid_hazards: bool,
+    skipped: &[Pos],
+) -> Option<AutoExploreIntent> {
     find_nearest_auto_target(
         map,
         start,
         avoid_hazards,
-        |current| is_frontier_candidate(map, current),
+        |current| is_frontier_candidate(map, current) && !skipped.contains(&current),
         |target| {
             if map.tile_at(target) == TileKind::ClosedDoor {
                 AutoReason::Door
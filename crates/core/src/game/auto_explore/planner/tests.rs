@@ -9,3 +9,4 @@ use crate::*;
 mod downstairs_policy;
 mod frontier_policy;
 mod integration_regressions;
+mod loot_policy;
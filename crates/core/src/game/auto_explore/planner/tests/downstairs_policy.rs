@@ -4,42 +4,29 @@ use super::*;
 
 #[test]
 fn planner_targets_known_downstairs_when_no_frontier_remains() {
-    let mut map = Map::new(12, 8);
-    for y in 1..(map.internal_height - 1) {
-        for x in 1..(map.internal_width - 1) {
-            map.set_tile(Pos { y: y as i32, x: x as i32 }, TileKind::Wall);
-        }
-    }
-    for x in 2..=9 {
-        map.set_tile(Pos { y: 4, x }, TileKind::Floor);
-    }
     let stairs = Pos { y: 4, x: 9 };
-    map.set_tile(stairs, TileKind::DownStairs);
-    map.discovered.fill(true);
-    map.visible.fill(true);
+    let map = MapBuilder::solid(12, 8)
+        .corridor(Pos { y: 4, x: 2 }, stairs)
+        .stairs(stairs)
+        .visible_all()
+        .build();
 
     let start = Pos { y: 4, x: 3 };
-    let intent = choose_frontier_intent(&map, start).expect("stairs should be selected");
+    let intent = choose_frontier_intent(&map, start, &[], Aggro::Conserve)
+        .expect("stairs should be selected");
     assert_eq!(intent.target, stairs);
 }
 
 #[test]
 fn downstairs_prefers_nearest_then_y_x_tie_break() {
-    let mut map = Map::new(12, 8);
-    for y in 1..(map.internal_height - 1) {
-        for x in 1..(map.internal_width - 1) {
-            map.set_tile(Pos { y: y as i32, x: x as i32 }, TileKind::Wall);
-        }
-    }
-    for x in 2..=9 {
-        map.set_tile(Pos { y: 4, x }, TileKind::Floor);
-    }
     let near_stairs = Pos { y: 4, x: 4 };
     let far_stairs = Pos { y: 4, x: 8 };
-    map.set_tile(near_stairs, TileKind::DownStairs);
-    map.set_tile(far_stairs, TileKind::DownStairs);
-    map.discovered.fill(true);
-    map.visible.fill(true);
+    let map = MapBuilder::solid(12, 8)
+        .corridor(Pos { y: 4, x: 2 }, Pos { y: 4, x: 9 })
+        .stairs(near_stairs)
+        .stairs(far_stairs)
+        .visible_all()
+        .build();
 
     let start = Pos { y: 4, x: 2 };
     let nearest_intent = choose_downstairs_intent(&map, start).expect("stairs should be found");
@@ -47,18 +34,10 @@ fn downstairs_prefers_nearest_then_y_x_tie_break() {
     assert_eq!(nearest_intent.path_len, 2);
     assert_eq!(nearest_intent.reason, AutoReason::Frontier);
 
-    let mut tie_map = Map::new(11, 11);
-    for y in 1..10 {
-        for x in 1..10 {
-            tie_map.set_tile(Pos { y, x }, TileKind::Floor);
-        }
-    }
     let top_stairs = Pos { y: 3, x: 5 };
     let bottom_stairs = Pos { y: 5, x: 3 };
-    tie_map.set_tile(top_stairs, TileKind::DownStairs);
-    tie_map.set_tile(bottom_stairs, TileKind::DownStairs);
-    tie_map.discovered.fill(true);
-    tie_map.visible.fill(true);
+    let tie_map =
+        MapBuilder::open(11, 11).stairs(top_stairs).stairs(bottom_stairs).visible_all().build();
 
     let tie_start = Pos { y: 4, x: 4 };
     let tie_intent =
@@ -16,10 +16,11 @@ fn planner_avoids_hazard_route_when_safe_frontier_exists() {
 
     map.discovered.fill(true);
     map.visible.fill(true);
-    map.discovered[(4 * map.internal_width) + 6] = false;
-    map.discovered[(2 * map.internal_width) + 5] = false;
+    undiscover(&mut map, Pos { y: 4, x: 6 });
+    undiscover(&mut map, Pos { y: 2, x: 5 });
 
-    let intent = choose_frontier_intent(&map, start).expect("expected frontier intent");
+    let intent = choose_frontier_intent(&map, start, &[], Aggro::Conserve)
+        .expect("expected frontier intent");
     assert_eq!(intent.target, Pos { y: 2, x: 4 });
 }
 
@@ -27,9 +28,10 @@ fn planner_avoids_hazard_route_when_safe_frontier_exists() {
 fn planner_reports_threat_avoidance_when_only_hazard_frontier_exists() {
     let (mut map, start) = hazard_lane_fixture();
     map.set_hazard(Pos { y: 4, x: 5 }, true);
-    map.discovered[(4 * map.internal_width) + 6] = false;
+    undiscover(&mut map, Pos { y: 4, x: 6 });
 
-    let intent = choose_frontier_intent(&map, start).expect("hazard fallback intent");
+    let intent =
+        choose_frontier_intent(&map, start, &[], Aggro::Conserve).expect("hazard fallback intent");
     assert_eq!(intent.reason, AutoReason::ThreatAvoidance);
 }
 
@@ -48,11 +50,12 @@ fn safe_frontier_reachable_only_through_hazards_uses_threat_avoidance() {
     map.visible.fill(true);
     map.set_hazard(Pos { y: 4, x: 4 }, true);
     map.set_hazard(Pos { y: 4, x: 6 }, true);
-    map.discovered[(4 * map.internal_width) + 1] = false;
-    map.discovered[(4 * map.internal_width) + 9] = false;
+    undiscover(&mut map, Pos { y: 4, x: 1 });
+    undiscover(&mut map, Pos { y: 4, x: 9 });
 
     let start = Pos { y: 4, x: 5 };
-    let intent = choose_frontier_intent(&map, start).expect("fallback on safe frontier");
+    let intent = choose_frontier_intent(&map, start, &[], Aggro::Conserve)
+        .expect("fallback on safe frontier");
     assert_eq!(intent.reason, AutoReason::ThreatAvoidance);
     assert_eq!(intent.target, Pos { y: 4, x: 2 });
 }
@@ -70,22 +73,24 @@ fn frontier_selection_ignores_non_visible_frontiers() {
     for x in 2..=5 {
         map.set_visible(Pos { y: 4, x }, true);
     }
-    map.discovered[(4 * map.internal_width) + 6] = false;
-    map.discovered[(6 * map.internal_width) + 8] = false;
+    undiscover(&mut map, Pos { y: 4, x: 6 });
+    undiscover(&mut map, Pos { y: 6, x: 8 });
 
     let start = Pos { y: 4, x: 3 };
-    let intent = choose_frontier_intent(&map, start).expect("visible frontier");
+    let intent =
+        choose_frontier_intent(&map, start, &[], Aggro::Conserve).expect("visible frontier");
     assert_eq!(intent.target, Pos { y: 4, x: 5 });
 }
 
 #[test]
 fn choose_frontier_intent_optimized_behavior() {
     let (mut map, start) = hazard_lane_fixture();
-    map.discovered[3 * map.internal_width + 3] = false;
-    map.discovered[4 * map.internal_width + 6] = false;
+    undiscover(&mut map, Pos { y: 3, x: 3 });
+    undiscover(&mut map, Pos { y: 4, x: 6 });
     map.set_hazard(Pos { y: 4, x: 4 }, true);
 
-    let intent = choose_frontier_intent(&map, start).expect("frontier should be found");
+    let intent = choose_frontier_intent(&map, start, &[], Aggro::Conserve)
+        .expect("frontier should be found");
     assert_eq!(intent.target, Pos { y: 4, x: 3 }, "should prefer safe frontier");
     assert_eq!(intent.reason, AutoReason::Frontier);
 }
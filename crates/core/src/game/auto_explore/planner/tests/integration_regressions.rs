@@ -8,22 +8,14 @@ fn threat_avoidance_intent_is_reused_without_retarget_replan() {
     game.state.items.clear();
     game.state.actors.retain(|id, _| id == game.state.player_id);
 
-    let mut map = Map::new(11, 9);
-    for y in 1..8 {
-        for x in 1..10 {
-            map.set_tile(Pos { y, x }, TileKind::Wall);
-        }
-    }
-    for x in 2..=8 {
-        map.set_tile(Pos { y: 4, x }, TileKind::Floor);
-    }
-    map.discovered.fill(true);
-    map.visible.fill(true);
-    map.set_hazard(Pos { y: 4, x: 4 }, true);
-    map.set_hazard(Pos { y: 4, x: 6 }, true);
-    map.discovered[(4 * map.internal_width) + 1] = false;
-    map.discovered[(4 * map.internal_width) + 9] = false;
-    game.state.map = map;
+    game.state.map = MapBuilder::solid(11, 9)
+        .corridor(Pos { y: 4, x: 2 }, Pos { y: 4, x: 8 })
+        .visible_all()
+        .hazard(Pos { y: 4, x: 4 })
+        .hazard(Pos { y: 4, x: 6 })
+        .undiscovered(Pos { y: 4, x: 1 })
+        .undiscovered(Pos { y: 4, x: 9 })
+        .build();
 
     let p1 = Pos { y: 4, x: 5 };
     game.state.actors[game.state.player_id].pos = p1;
@@ -57,42 +49,35 @@ fn auto_explore_frontier_regression() {
     let (map, player_pos) = open_room_fixture();
     let mut map = map;
     map.discovered.fill(true);
-    map.discovered[3 * map.internal_width + 5] = false;
-    let intent = choose_frontier_intent(&map, player_pos).expect("frontier should be found");
+    undiscover(&mut map, Pos { y: 3, x: 5 });
+    let intent = choose_frontier_intent(&map, player_pos, &[], Aggro::Conserve)
+        .expect("frontier should be found");
     assert_eq!(intent.target, Pos { y: 4, x: 5 });
     assert_eq!(intent.path_len, 1);
 
-    let mut map = Map::new(10, 10);
-    for y in 0..10 {
-        for x in 0..10 {
-            map.set_tile(Pos { y, x }, TileKind::Wall);
-        }
-    }
-    for x in 1..=8 {
-        map.set_tile(Pos { y: 1, x }, TileKind::Floor);
-    }
-    for y in 2..=8 {
-        map.set_tile(Pos { y, x: 8 }, TileKind::Floor);
-    }
-    for x in 1..=7 {
-        map.set_tile(Pos { y: 8, x }, TileKind::Floor);
-    }
-    map.discovered.fill(true);
-    map.discovered[9 * 10 + 1] = false;
+    let map = MapBuilder::solid(10, 10)
+        .corridor(Pos { y: 1, x: 1 }, Pos { y: 8, x: 8 })
+        .corridor(Pos { y: 8, x: 8 }, Pos { y: 8, x: 1 })
+        .discover_all()
+        .undiscovered(Pos { y: 9, x: 1 })
+        .build();
     let start = Pos { y: 1, x: 1 };
-    let intent = choose_frontier_intent(&map, start).expect("frontier should be found in maze");
+    let intent = choose_frontier_intent(&map, start, &[], Aggro::Conserve)
+        .expect("frontier should be found in maze");
     assert_eq!(intent.target, Pos { y: 8, x: 1 });
     assert_eq!(intent.path_len, 21);
 
     let (mut map, start) = hazard_lane_fixture();
-    map.discovered[4 * map.internal_width + 6] = false;
+    undiscover(&mut map, Pos { y: 4, x: 6 });
     map.set_hazard(Pos { y: 4, x: 4 }, true);
-    let intent = choose_frontier_intent(&map, start).expect("hazard fallback should work");
+    let intent = choose_frontier_intent(&map, start, &[], Aggro::Conserve)
+        .expect("hazard fallback should work");
     assert_eq!(intent.reason, AutoReason::ThreatAvoidance);
     assert_eq!(intent.target, Pos { y: 4, x: 5 });
 
     let (map, start, door) = closed_door_choke_fixture();
-    let intent = choose_frontier_intent(&map, start).expect("door frontier should be found");
+    let intent = choose_frontier_intent(&map, start, &[], Aggro::Conserve)
+        .expect("door frontier should be found");
     assert_eq!(intent.target, door);
     assert_eq!(intent.reason, AutoReason::Door);
 }
@@ -0,0 +1,53 @@
+//! Tests for visible-loot targeting under each resource-aggression policy.
+
+use super::*;
+
+fn explored_hall() -> (Map, Pos) {
+    let map = MapBuilder::open(16, 5)
+        .discover_all()
+        .visible_all()
+        .undiscovered(Pos { y: 2, x: 14 })
+        .build();
+    (map, Pos { y: 2, x: 1 })
+}
+
+#[test]
+fn greedy_collects_visible_loot_before_the_frontier() {
+    let (map, start) = explored_hall();
+    let loot = [Pos { y: 1, x: 3 }, Pos { y: 3, x: 10 }];
+
+    let intent = choose_frontier_intent(&map, start, &loot, Aggro::Greedy).expect("loot intent");
+    assert_eq!(intent.reason, AutoReason::Loot);
+    assert_eq!(intent.target, loot[0]);
+    assert_eq!(intent.path_len, 3);
+}
+
+#[test]
+fn conserve_keeps_exploring_past_visible_loot() {
+    let (map, start) = explored_hall();
+    let loot = [Pos { y: 2, x: 2 }];
+
+    let intent = choose_frontier_intent(&map, start, &loot, Aggro::Conserve).expect("intent");
+    assert_eq!(intent.reason, AutoReason::Frontier);
+    assert_eq!(intent.target, Pos { y: 2, x: 13 });
+}
+
+#[test]
+fn loot_runs_are_logged_distinctly_from_frontier_pushes() {
+    let mut game = Game::new(2707, &ContentPack::default(), GameMode::Ironman);
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    let (map, start) = explored_hall();
+    game.state.map = map;
+    game.state.actors[game.state.player_id].pos = start;
+    let item = game.state.items.keys().next().expect("the first floor has loot");
+    game.state.items.retain(|id, _| id == item);
+    game.state.items[item].pos = Pos { y: 2, x: 4 };
+    game.state.policy.resource_aggression = Aggro::Greedy;
+
+    game.plan_auto_intent(start);
+    assert_eq!(game.state.auto_intent.map(|intent| intent.reason), Some(AutoReason::Loot));
+    assert!(game.log().iter().any(|event| matches!(
+        event,
+        LogEvent::AutoReasonChanged { reason: AutoReason::Loot, .. }
+    )));
+}
@@ -0,0 +1,159 @@
+//! Rush route planning: the fewest-tick way to known down stairs, counting the stops for doors
+//! and loot, and whether a detour for a healing potion is worth its extra ticks.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::super::{AutoExploreIntent, AutoReason, Pos, TileKind, neighbors};
+use crate::content::ActionCosts;
+use crate::state::Map;
+
+/// What a Rush route heads for and the stops it must account for.
+pub(in crate::game) struct RushPlan<'a> {
+    pub(in crate::game) stairs: Pos,
+    /// Tiles whose loot stops the player for a pickup.
+    pub(in crate::game) loot: &'a [Pos],
+    /// Known healing potions worth a detour; empty while the player needs no healing.
+    pub(in crate::game) potions: &'a [Pos],
+    pub(in crate::game) max_detour_ticks: u32,
+}
+
+/// Cheapest tick cost from one start to every reachable tile, with the routes taken.
+struct TickMap {
+    ticks: BTreeMap<Pos, u32>,
+    came_from: BTreeMap<Pos, Pos>,
+}
+
+impl TickMap {
+    /// Dijkstra over discovered, hazard-free tiles. Unlike the step planner it crosses closed
+    /// doors, charging the ticks to open them.
+    fn from(map: &Map, start: Pos, costs: &ActionCosts, loot: &[Pos]) -> Self {
+        let mut ticks = BTreeMap::from([(start, 0)]);
+        let mut came_from = BTreeMap::new();
+        let mut open = BTreeSet::from([(0, start)]);
+        while let Some((cost, pos)) = open.pop_first() {
+            if ticks.get(&pos).is_some_and(|best| *best < cost) {
+                continue;
+            }
+            for next in neighbors(pos) {
+                if !map.is_discovered_walkable_safe(next) {
+                    continue;
+                }
+                let mut step = costs.move_step;
+                if map.tile_at(next) == TileKind::ClosedDoor {
+                    step += costs.open_door;
+                }
+                if loot.contains(&next) {
+                    step += costs.pick_up;
+                }
+                if cost + step < *ticks.get(&next).unwrap_or(&u32::MAX) {
+                    ticks.insert(next, cost + step);
+                    came_from.insert(next, pos);
+                    open.insert((cost + step, next));
+                }
+            }
+        }
+        Self { ticks, came_from }
+    }
+
+    /// Tiles after `start` up to and including `goal`.
+    fn route_to(&self, start: Pos, goal: Pos) -> Vec<Pos> {
+        let mut route = vec![goal];
+        let mut pos = goal;
+        while let Some(&prev) = self.came_from.get(&pos).filter(|_| pos != start) {
+            route.push(prev);
+            pos = prev;
+        }
+        route.pop();
+        route.reverse();
+        route
+    }
+}
+
+/// Next Rush waypoint: the stairs, or a healing potion whose detour fits the budget, cut short
+/// at the first closed door since doors are opened before walking on.
+pub(in crate::game) fn choose_rush_intent(
+    map: &Map,
+    start: Pos,
+    costs: &ActionCosts,
+    plan: &RushPlan,
+) -> Option<AutoExploreIntent> {
+    let from_start = TickMap::from(map, start, costs, plan.loot);
+    let direct = *from_start.ticks.get(&plan.stairs)?;
+    let detour = plan
+        .potions
+        .iter()
+        .filter_map(|&potion| {
+            let to_potion = *from_start.ticks.get(&potion)?;
+            let onward = TickMap::from(map, potion, costs, plan.loot);
+            let extra = (to_potion + onward.ticks.get(&plan.stairs)?).saturating_sub(direct);
+            (extra <= plan.max_detour_ticks).then_some((extra, potion))
+        })
+        .min();
+    let (goal, reason) = detour
+        .map_or((plan.stairs, AutoReason::Frontier), |(_, potion)| (potion, AutoReason::Loot));
+
+    let route = from_start.route_to(start, goal);
+    let door = route.iter().position(|pos| map.tile_at(*pos) == TileKind::ClosedDoor);
+    let end = door.unwrap_or(route.len().checked_sub(1)?);
+    Some(AutoExploreIntent {
+        target: route[end],
+        reason: if door.is_some() { AutoReason::Door } else { reason },
+        path_len: (end + 1) as u16,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MapBuilder;
+
+    /// Corridor from the start to the stairs with a side pocket two tiles off it.
+    fn rush_fixture() -> (MapBuilder, Pos, Pos, Pos) {
+        let map = MapBuilder::solid(14, 7)
+            .corridor(Pos { y: 3, x: 1 }, Pos { y: 3, x: 12 })
+            .corridor(Pos { y: 1, x: 6 }, Pos { y: 2, x: 6 })
+            .stairs(Pos { y: 3, x: 12 })
+            .discover_all();
+        (map, Pos { y: 3, x: 1 }, Pos { y: 3, x: 12 }, Pos { y: 1, x: 6 })
+    }
+
+    fn plan<'a>(stairs: Pos, potions: &'a [Pos], max_detour_ticks: u32) -> RushPlan<'a> {
+        RushPlan { stairs, loot: potions, potions, max_detour_ticks }
+    }
+
+    #[test]
+    fn rush_detours_for_a_potion_only_within_the_tick_budget() {
+        let (map, start, stairs, potion) = rush_fixture();
+        let map = map.build();
+        let costs = ActionCosts::default();
+
+        let direct = choose_rush_intent(&map, start, &costs, &plan(stairs, &[], 12)).unwrap();
+        assert_eq!(
+            (direct.target, direct.reason, direct.path_len),
+            (stairs, AutoReason::Frontier, 11)
+        );
+
+        // Up and back is four steps plus the pickup.
+        let extra = 4 * costs.move_step + costs.pick_up;
+        let detour = choose_rush_intent(&map, start, &costs, &plan(stairs, &[potion], extra));
+        assert_eq!(
+            detour.map(|intent| (intent.target, intent.reason)),
+            Some((potion, AutoReason::Loot))
+        );
+        let skipped = choose_rush_intent(&map, start, &costs, &plan(stairs, &[potion], extra - 1));
+        assert_eq!(skipped.map(|intent| intent.target), Some(stairs));
+    }
+
+    #[test]
+    fn rush_stops_at_closed_doors_and_gives_up_without_a_route() {
+        let (map, start, stairs, _) = rush_fixture();
+        let door = Pos { y: 3, x: 8 };
+        let doored = map.clone().door(door).build();
+        let costs = ActionCosts::default();
+        let intent = choose_rush_intent(&doored, start, &costs, &plan(stairs, &[], 0)).unwrap();
+        assert_eq!((intent.target, intent.reason, intent.path_len), (door, AutoReason::Door, 7));
+
+        let walled = map.wall(door).build();
+        assert_eq!(choose_rush_intent(&walled, start, &costs, &plan(stairs, &[], 0)), None);
+    }
+}
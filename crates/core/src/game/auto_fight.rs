@@ -0,0 +1,148 @@
+//! Policy-driven auto-fighting that resolves trivial encounters without raising a prompt.
+//! An encounter is trivial when a lone adjacent enemy's fight preview shows no expected
+//! damage taken; with auto-resolve on, any routine encounter is settled by the fight mode.
+
+use super::*;
+use crate::content::ActionKind;
+
+impl Game {
+    /// Fight `adjacent`'s only enemy per policy, spending the action's time; false if the
+    /// policy is off or the encounter is not trivial.
+    pub(in crate::game) fn try_auto_fight(&mut self, adjacent: &[EntityId]) -> bool {
+        let &[enemy] = adjacent else {
+            return false;
+        };
+        if !self.state.policy.auto_fight_trivial {
+            return false;
+        }
+        let Some(preview) = self.preview_fight(enemy) else {
+            return false;
+        };
+        if preview.expected_damage_taken() > 0 {
+            return false;
+        }
+        let kind = self.state.actors[enemy].kind;
+        self.apply_player_strike(enemy, preview.damage_dealt);
+        self.spend_player_action(ActionKind::Fight);
+        self.log.push(LogEvent::EncounterAutoFought { kind, damage: preview.damage_dealt });
+        true
+    }
+
+    /// Fight or avoid `engaged`'s only enemy as `fight_or_avoid` says, spending the action's
+    /// time; false if the policy is off or the encounter is unusual: a pack, a unique enemy,
+    /// or a player at or below the retreat threshold, all of which still prompt.
+    pub(in crate::game) fn try_auto_resolve_encounter(&mut self, engaged: &[EntityId]) -> bool {
+        let &[enemy] = engaged else {
+            return false;
+        };
+        let policy = &self.state.policy;
+        let player = &self.state.actors[self.state.player_id];
+        let retreat_eligible =
+            (player.hp * 100) / player.max_hp <= i32::from(policy.retreat_hp_threshold);
+        if !policy.auto_resolve_encounters
+            || retreat_eligible
+            || self.state.actors[enemy].unique.is_some()
+        {
+            return false;
+        }
+        match policy.fight_or_avoid {
+            FightMode::Fight => {
+                self.resolve_fight_choice(enemy);
+                self.spend_player_action(ActionKind::Fight);
+            }
+            FightMode::Avoid => {
+                self.resolve_avoid_choice(enemy);
+                self.log.push(LogEvent::EncounterResolved { enemy, fought: false });
+                self.spend_player_action(ActionKind::Avoid);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::add_goblin;
+    use crate::state::MAX_AWARENESS;
+
+    fn game_beside_goblin(goblin_hp: i32, auto_fight_trivial: bool) -> (Game, EntityId) {
+        let mut game = Game::new(2742, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.policy.auto_fight_trivial = auto_fight_trivial;
+        let player = game.state.actors[game.state.player_id].pos;
+        let goblin = add_goblin(&mut game, Pos { y: player.y, x: player.x + 1 });
+        game.state.actors[goblin].hp = goblin_hp;
+        (game, goblin)
+    }
+
+    #[test]
+    fn trivial_encounters_resolve_without_a_prompt() {
+        let (mut game, goblin) = game_beside_goblin(1, true);
+        let result = game.advance(1);
+        assert!(!matches!(result.stop_reason, AdvanceStopReason::Interrupted(_)));
+        assert!(!game.state.actors.contains_key(goblin));
+        assert_eq!(game.state.kills_this_floor, 1);
+        assert!(game.log.iter().any(|event| matches!(
+            event,
+            LogEvent::EncounterAutoFought { kind: ActorKind::Goblin, .. }
+        )));
+        assert!(game.log.contains(&LogEvent::EnemySlain { kind: ActorKind::Goblin }));
+    }
+
+    #[test]
+    fn risky_encounters_and_disabled_policy_still_prompt() {
+        for (hp, enabled) in [(40, true), (1, false)] {
+            let (mut game, goblin) = game_beside_goblin(hp, enabled);
+            let result = game.advance(1);
+            assert!(matches!(
+                result.stop_reason,
+                AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { .. })
+            ));
+            assert!(game.state.actors.contains_key(goblin));
+        }
+    }
+
+    #[test]
+    fn auto_resolve_settles_routine_encounters_by_fight_mode() {
+        let (mut game, goblin) = game_beside_goblin(40, false);
+        game.state.policy.auto_resolve_encounters = true;
+        let result = game.advance(1);
+        assert!(!matches!(result.stop_reason, AdvanceStopReason::Interrupted(_)));
+        assert!(game.state.actors[goblin].hp < 40);
+        assert!(game.log.contains(&LogEvent::EncounterResolved { enemy: goblin, fought: true }));
+
+        let (mut game, goblin) = game_beside_goblin(40, false);
+        game.state.policy.auto_resolve_encounters = true;
+        game.state.policy.fight_or_avoid = FightMode::Avoid;
+        game.state.actors[goblin].awareness = MAX_AWARENESS;
+        let result = game.advance(1);
+        assert!(!matches!(result.stop_reason, AdvanceStopReason::Interrupted(_)));
+        assert_eq!(game.state.actors[goblin].hp, 40);
+        assert!(game.log.contains(&LogEvent::EncounterResolved { enemy: goblin, fought: false }));
+    }
+
+    #[test]
+    fn auto_resolve_still_prompts_for_packs_and_low_health() {
+        let (mut game, _) = game_beside_goblin(40, false);
+        game.state.policy.auto_resolve_encounters = true;
+        let player = game.state.actors[game.state.player_id].pos;
+        add_goblin(&mut game, Pos { y: player.y + 1, x: player.x });
+        let result = game.advance(1);
+        assert!(matches!(
+            result.stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { .. })
+        ));
+
+        let (mut game, _) = game_beside_goblin(40, false);
+        game.state.policy.auto_resolve_encounters = true;
+        game.state.actors[game.state.player_id].hp = 1;
+        let result = game.advance(1);
+        assert!(matches!(
+            result.stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { .. })
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+//! Balance lookups against the content pack the run was started with.
+//! Keys missing from the pack fall back to the built-in numbers, so old checkpoints still load.
+
+use super::*;
+use crate::content::{AggroRange, EnemyStats, default_aggro_range, get_enemy_stats};
+
+impl Game {
+    /// Unscaled stats for `kind`; difficulty scaling is applied by the caller.
+    pub(in crate::game) fn enemy_base_stats(&self, kind: ActorKind) -> EnemyStats {
+        self.enemy_stats.get(&kind).copied().unwrap_or_else(|| get_enemy_stats(kind))
+    }
+
+    pub(in crate::game) fn aggro_range(&self, kind: ActorKind) -> AggroRange {
+        self.aggro.get(&kind).copied().unwrap_or_else(|| default_aggro_range(kind))
+    }
+
+    pub(in crate::game) fn weapon_attack_bonus(&self, weapon: &str) -> i32 {
+        self.weapons.iter().find(|entry| entry.id == weapon).map_or(0, |entry| entry.attack_bonus)
+    }
+
+    pub(in crate::game) fn weapon_weight(&self, weapon: &str) -> u32 {
+        self.weapons.iter().find(|entry| entry.id == weapon).map_or(0, |entry| entry.weight)
+    }
+
+    /// Base HP a consumable restores before difficulty scaling; zero for non-healing items.
+    pub(in crate::game) fn consumable_heal(&self, consumable: &str) -> i32 {
+        self.consumables
+            .iter()
+            .find(|entry| entry.id == consumable)
+            .map_or(0, |entry| entry.heal_amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    #[test]
+    fn a_retuned_pack_changes_the_numbers_the_run_plays_with() {
+        let pack = ContentPack::from_toml_str(
+            "[[enemies]]\nkind = \"Goblin\"\nhp = 30\n\n[[weapons]]\nid = \"weapon_iron_mace\"\nweight = 2",
+        )
+        .expect("valid balance file");
+        let game = Game::new(2754, &pack, GameMode::Ironman);
+        let goblins: Vec<i32> = game
+            .state
+            .actors
+            .values()
+            .filter(|actor| actor.kind == ActorKind::Goblin)
+            .map(|actor| actor.max_hp)
+            .collect();
+        assert!(!goblins.is_empty());
+        assert!(goblins.iter().all(|hp| *hp == 30));
+        assert_eq!(game.weapon_weight(keys::WEAPON_IRON_MACE), 2);
+        assert_eq!(
+            game.enemy_base_stats(ActorKind::Player).hp,
+            20,
+            "kinds outside the pack fall back"
+        );
+    }
+}
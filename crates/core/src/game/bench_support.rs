@@ -0,0 +1,16 @@
+//! Thin public wrappers over internal navigation and visibility routines for `benches/`.
+//! Not a supported API; signatures follow the internals and change with them.
+
+use super::*;
+use crate::state::Map;
+
+/// The widest FOV the player can have, with the scout perk and every Keen Sight boon.
+pub const MAX_FOV_RADIUS: i32 = FOV_RADIUS + 2 + MAX_FOV_BONUS as i32;
+
+pub fn astar_path(map: &Map, start: Pos, goal: Pos) -> Option<Vec<Pos>> {
+    pathfinding::astar_path(map, start, goal)
+}
+
+pub fn compute_fov(map: &mut Map, origin: Pos, range: i32) {
+    visibility::compute_fov(map, origin, range);
+}
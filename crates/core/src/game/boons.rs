@@ -0,0 +1,137 @@
+//! God boons offered on non-first descents and what each one grants.
+//! Offers are rolled from the run seed and floor, so a replay sees the same boons.
+
+use rand_chacha::ChaCha8Rng;
+use rand_chacha::rand_core::{Rng, SeedableRng};
+
+use super::*;
+
+const BOON_ROLL_STREAM: u64 = 0x6B0D_B00B_5EED_0F11;
+/// Boon strengths and their relative weights; the stronger roll is the rarer one.
+const BOON_AMOUNT_WEIGHTS: [(u8, u64); 2] = [(1, 3), (2, 1)];
+/// Max HP granted per point of a Vigor boon.
+const VIGOR_HP_PER_AMOUNT: i32 = 3;
+
+fn god_boon_kinds(god: GodId) -> [BoonKind; 2] {
+    match god {
+        GodId::Veil => [BoonKind::KeenSight, BoonKind::BlinkCharge],
+        GodId::Forge => [BoonKind::Bulwark, BoonKind::Vigor],
+    }
+}
+
+/// The boons `god` offers when leaving `floor_index`; Keen Sight drops out at the FOV cap.
+pub(super) fn roll_boon_offer(
+    seed: u64,
+    floor_index: u8,
+    god: GodId,
+    fov_bonus: u8,
+) -> Vec<GodBoon> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed ^ BOON_ROLL_STREAM ^ u64::from(floor_index));
+    god_boon_kinds(god)
+        .map(|kind| GodBoon { kind, amount: roll_amount(&mut rng) })
+        .into_iter()
+        .filter(|boon| boon.kind != BoonKind::KeenSight || fov_bonus < MAX_FOV_BONUS)
+        .collect()
+}
+
+fn roll_amount(rng: &mut ChaCha8Rng) -> u8 {
+    let total: u64 = BOON_AMOUNT_WEIGHTS.iter().map(|(_, weight)| weight).sum();
+    let mut roll = rng.next_u64() % total;
+    for (amount, weight) in BOON_AMOUNT_WEIGHTS {
+        if roll < weight {
+            return amount;
+        }
+        roll -= weight;
+    }
+    unreachable!("the roll is below the total weight")
+}
+
+impl Game {
+    pub(super) fn apply_boon(&mut self, boon: GodBoon) {
+        let player = self.state.actors.get_mut(self.state.player_id).expect("player should exist");
+        match boon.kind {
+            BoonKind::KeenSight => {
+                self.state.fov_bonus = (self.state.fov_bonus + boon.amount).min(MAX_FOV_BONUS);
+            }
+            BoonKind::BlinkCharge => {
+                self.state.blink_charges = self.state.blink_charges.saturating_add(boon.amount);
+            }
+            BoonKind::Bulwark => player.defense += i32::from(boon.amount),
+            BoonKind::Vigor => {
+                let gain = i32::from(boon.amount) * VIGOR_HP_PER_AMOUNT;
+                player.max_hp += gain;
+                player.hp = (player.hp + gain).min(player.max_hp);
+            }
+        }
+        self.log.push(LogEvent::BoonGranted { boon });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::MapBuilder;
+    use crate::mapgen::BranchProfile;
+
+    #[test]
+    fn offers_are_stable_per_floor_and_follow_the_weights() {
+        for god in [GodId::Veil, GodId::Forge] {
+            assert_eq!(roll_boon_offer(7, 2, god, 0), roll_boon_offer(7, 2, god, 0));
+            let kinds: Vec<_> = roll_boon_offer(7, 2, god, 0).iter().map(|b| b.kind).collect();
+            assert_eq!(kinds, god_boon_kinds(god));
+        }
+
+        let rolls: Vec<u8> = (0..400)
+            .flat_map(|seed| roll_boon_offer(seed, 3, GodId::Forge, 0))
+            .map(|boon| boon.amount)
+            .collect();
+        let major = rolls.iter().filter(|amount| **amount == 2).count();
+        assert!(major > rolls.len() / 8 && major < rolls.len() / 2, "{major} of {}", rolls.len());
+    }
+
+    #[test]
+    fn keen_sight_is_capped_and_withdrawn_at_the_cap() {
+        let offer = roll_boon_offer(7, 2, GodId::Veil, MAX_FOV_BONUS);
+        assert!(offer.iter().all(|boon| boon.kind != BoonKind::KeenSight));
+
+        let mut game = Game::new(7, &ContentPack::default(), GameMode::Ironman);
+        for _ in 0..4 {
+            game.apply_boon(GodBoon { kind: BoonKind::KeenSight, amount: 2 });
+        }
+        assert_eq!(game.get_fov_radius(), FOV_RADIUS + i32::from(MAX_FOV_BONUS));
+    }
+
+    #[test]
+    fn descending_with_an_offered_boon_applies_it() {
+        let mut game = Game::new(2702, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(7, 7).stairs(Pos { y: 3, x: 3 }).discover_all().build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 3, x: 3 };
+        game.state.floor_index = 2;
+        game.state.branch_profile = BranchProfile::BranchA;
+        game.state.active_god = Some(GodId::Forge);
+        let defense = game.state.actors[game.state.player_id].defense;
+
+        let AdvanceStopReason::Interrupted(Interrupt::FloorTransition { prompt_id, boons, .. }) =
+            game.advance(1).stop_reason
+        else {
+            panic!("expected a floor transition prompt");
+        };
+        assert_eq!(boons, roll_boon_offer(2702, 2, GodId::Forge, 0));
+        let bulwark = boons[0];
+        let sight = Choice::DescendWithBoon(BoonKind::KeenSight);
+        assert!(matches!(
+            game.apply_choice(prompt_id, sight),
+            Err(GameError::InvalidChoice { .. })
+        ));
+
+        game.apply_choice(prompt_id, Choice::DescendWithBoon(BoonKind::Bulwark))
+            .expect("an offered boon should apply");
+        let player = &game.state.actors[game.state.player_id];
+        assert_eq!(player.defense, defense + i32::from(bulwark.amount));
+        assert_eq!(game.state.floor_index, 3);
+        assert!(game.log.contains(&LogEvent::BoonGranted { boon: bulwark }));
+    }
+}
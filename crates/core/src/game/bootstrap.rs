@@ -2,17 +2,28 @@
 //! This module exists to isolate initialization details from runtime simulation flow.
 //! It does not own tick advancement or choice resolution once a run has started.
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use rand_chacha::rand_core::SeedableRng;
 
 use super::*;
 use crate::content::{ContentPack, get_enemy_stats, keys};
 use crate::mapgen::{BranchProfile, STARTING_FLOOR_INDEX};
-use crate::state::{Actor, Item, Map};
+use crate::state::{Actor, DirectorState, Item, Map};
+use items::shuffled_potion_appearances;
 
 impl Game {
-    pub fn new(seed: u64, _content: &ContentPack, _mode: GameMode) -> Self {
+    pub fn new(seed: u64, content: &ContentPack, mode: GameMode) -> Self {
+        Self::new_with_difficulty(seed, content, mode, Difficulty::Normal)
+    }
+
+    pub fn new_with_difficulty(
+        seed: u64,
+        content: &ContentPack,
+        _mode: GameMode,
+        difficulty: Difficulty,
+    ) -> Self {
+        let scaling = content.difficulty.for_difficulty(difficulty);
         let rng = ChaCha8Rng::seed_from_u64(seed);
         let mut actors = slotmap::SlotMap::with_key();
         let player = Actor {
@@ -26,13 +37,21 @@ impl Game {
             active_weapon_slot: WeaponSlot::Primary,
             equipped_weapon: None,
             reserve_weapon: None,
-            next_action_tick: 10,
+            next_action_tick: 0,
             speed: 10,
+            awareness: 0,
+            unique: None,
+            home: Pos { y: 5, x: 4 },
         };
         let player_id = actors.insert(player);
         actors[player_id].id = player_id;
 
-        let stats_a = get_enemy_stats(ActorKind::Goblin);
+        let goblin = content
+            .enemies
+            .get(&ActorKind::Goblin)
+            .copied()
+            .unwrap_or_else(|| get_enemy_stats(ActorKind::Goblin));
+        let stats_a = scaling.scale_enemy_stats(goblin);
         let enemy_a = Actor {
             id: EntityId::default(),
             kind: ActorKind::Goblin,
@@ -46,11 +65,14 @@ impl Game {
             reserve_weapon: None,
             next_action_tick: stats_a.speed as u64,
             speed: stats_a.speed,
+            awareness: 0,
+            unique: None,
+            home: Pos { y: 5, x: 11 },
         };
         let enemy_a_id = actors.insert(enemy_a);
         actors[enemy_a_id].id = enemy_a_id;
 
-        let stats_b = get_enemy_stats(ActorKind::Goblin);
+        let stats_b = scaling.scale_enemy_stats(goblin);
         let enemy_b = Actor {
             id: EntityId::default(),
             kind: ActorKind::Goblin,
@@ -64,11 +86,14 @@ impl Game {
             reserve_weapon: None,
             next_action_tick: stats_b.speed as u64,
             speed: stats_b.speed,
+            awareness: 0,
+            unique: None,
+            home: Pos { y: 11, x: 11 },
         };
         let enemy_b_id = actors.insert(enemy_b);
         actors[enemy_b_id].id = enemy_b_id;
 
-        let stats_c = get_enemy_stats(ActorKind::Goblin);
+        let stats_c = scaling.scale_enemy_stats(goblin);
         let enemy_c = Actor {
             id: EntityId::default(),
             kind: ActorKind::Goblin,
@@ -82,11 +107,14 @@ impl Game {
             reserve_weapon: None,
             next_action_tick: stats_c.speed as u64,
             speed: stats_c.speed,
+            awareness: 0,
+            unique: None,
+            home: Pos { y: 6, x: 10 },
         };
         let enemy_c_id = actors.insert(enemy_c);
         actors[enemy_c_id].id = enemy_c_id;
 
-        let stats_d = get_enemy_stats(ActorKind::Goblin);
+        let stats_d = scaling.scale_enemy_stats(goblin);
         let enemy_d = Actor {
             id: EntityId::default(),
             kind: ActorKind::Goblin,
@@ -100,16 +128,17 @@ impl Game {
             reserve_weapon: None,
             next_action_tick: stats_d.speed as u64,
             speed: stats_d.speed,
+            awareness: 0,
+            unique: None,
+            home: Pos { y: 7, x: 9 },
         };
         let enemy_d_id = actors.insert(enemy_d);
         actors[enemy_d_id].id = enemy_d_id;
 
         let mut map = Map::new(20, 15);
 
-        for y in 1..(map.internal_height - 1) {
-            for x in 1..(map.internal_width - 1) {
-                map.set_tile(Pos { y: y as i32, x: x as i32 }, TileKind::Wall);
-            }
+        for pos in map.interior_positions() {
+            map.set_tile(pos, TileKind::Wall);
         }
 
         for y in 3..=7 {
@@ -147,6 +176,7 @@ impl Game {
             id: ItemId::default(),
             kind: ItemKind::Consumable(keys::CONSUMABLE_MINOR_HP_POT),
             pos: Pos { y: 5, x: 6 },
+            cursed: false,
         };
         let item_id = items.insert(item);
         items[item_id].id = item_id;
@@ -166,21 +196,71 @@ impl Game {
                 sanctuary_active: false,
                 floor_index: STARTING_FLOOR_INDEX,
                 branch_profile: BranchProfile::Uncommitted,
+                floor_provenance: FloorProvenance::authored(MapgenAlgorithm::StarterFloor),
                 active_god: None,
+                altar_tile: None,
+                pact_broken: false,
+                curse_floors_remaining: 0,
+                fov_bonus: 0,
+                blink_charges: 0,
+                braced: false,
+                wait_ticks_remaining: 0,
+                manual_step: None,
                 auto_intent: None,
                 policy: Policy::default(),
-                threat_trace: VecDeque::new(),
+                threat_trace: VecDeque::with_capacity(content.threat_trace_depth),
                 active_perks: Vec::new(),
+                cursed_perks: Vec::new(),
+                inventory: Vec::new(),
                 kills_this_floor: 0,
+                player_xp: 0,
+                player_level: 1,
+                perk_drafts_pending: 0,
+                floor_entry_tick: 0,
+                wanderers_this_floor: 0,
+                floor_objective: None,
+                completed_objectives: Vec::new(),
+                torch_ticks: 0,
+                ambience: roll_ambience(seed, STARTING_FLOOR_INDEX),
+                potion_appearances: shuffled_potion_appearances(seed),
+                identified_consumables: BTreeSet::new(),
+                stored_floors: BTreeMap::new(),
+                slain_uniques: BTreeSet::new(),
+                director: DirectorState::default(),
             },
-            log: Vec::new(),
+            log: EventLog::new(content.event_log_depth),
             next_input_seq: 0,
             pending_prompt: None,
             suppressed_enemy: None,
             pause_requested: false,
             at_pause_boundary: true,
             finished_outcome: None,
+            death: None,
             no_progress_ticks: 0,
+            action_costs: content.action_costs,
+            wandering_spawns: content.wandering_spawns,
+            rush_detour: content.rush_detour,
+            threat_trace_depth: content.threat_trace_depth,
+            threat_memory_ticks: content.threat_memory_ticks,
+            enemy_memory: BTreeMap::new(),
+            floor_objectives: content.floor_objectives.clone(),
+            boss_roster: content.bosses.clone(),
+            stairs_lock_acknowledged: false,
+            snoozed_targets: BTreeMap::new(),
+            lighting: content.lighting.clone(),
+            names: content.names.clone(),
+            enemy_stats: content.enemies.clone(),
+            aggro: content.aggro.clone(),
+            weapons: content.weapons.clone(),
+            consumables: content.consumables.clone(),
+            perk_tuning: content.perk_tuning,
+            difficulty,
+            difficulty_scaling: scaling,
+            debug_counters: DebugCounters::default(),
+            advance_tally: AdvanceBreakdown::default(),
+            target_scan: None,
+            path_cache: PathCache::default(),
+            stats: RunStats::default(),
         }
     }
 }
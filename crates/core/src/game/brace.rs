@@ -0,0 +1,133 @@
+//! Bracing: the player gives up a step to counter the next alert enemy that acts beside them.
+//! The counter reuses fight damage, sharpened by a Defensive stance and by holding a chokepoint.
+
+use super::*;
+use crate::content::ActionKind;
+
+/// Extra counter damage while the player holds a Defensive stance.
+const DEFENSIVE_COUNTER_BONUS: i32 = 2;
+/// Extra counter damage when the braced player has at most two open neighbouring tiles.
+const CHOKEPOINT_COUNTER_BONUS: i32 = 1;
+
+impl Game {
+    /// Brace at a pause boundary, forfeiting the next move to counter the first adjacent enemy action.
+    /// The brace lapses once the player moves off the tile.
+    pub fn apply_brace(&mut self) -> Result<(), GameError> {
+        if !self.at_pause_boundary && self.pending_prompt.is_none() {
+            return Err(GameError::NotAtPauseBoundary { tick: self.tick });
+        }
+        self.state.braced = true;
+        self.spend_player_action(ActionKind::Brace);
+        self.no_progress_ticks = 0;
+        self.stats.inputs_accepted += 1;
+        Ok(())
+    }
+
+    /// Spend a held brace on `enemy`, which just acted next to the player.
+    pub(super) fn trigger_brace_counter(&mut self, enemy: EntityId) {
+        if !self.state.braced {
+            return;
+        }
+        self.state.braced = false;
+        let damage = self.player_strike_damage(enemy) + self.counter_bonus();
+        self.log.push(LogEvent::Counterattack { enemy, damage });
+        self.apply_player_strike(enemy, damage);
+    }
+
+    fn counter_bonus(&self) -> i32 {
+        let mut bonus = 0;
+        if self.state.policy.stance == Stance::Defensive {
+            bonus += DEFENSIVE_COUNTER_BONUS;
+        }
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let open_neighbours = neighbors(player_pos)
+            .into_iter()
+            .filter(|pos| self.state.map.is_discovered_walkable(*pos))
+            .count();
+        if open_neighbours <= 2 {
+            bonus += CHOKEPOINT_COUNTER_BONUS;
+        }
+        bonus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::floor_transition::insert_enemy;
+    use crate::game::test_support::MapBuilder;
+    use crate::state::{MAX_AWARENESS, Map};
+
+    fn braced_game(map: Map) -> (Game, EntityId) {
+        let mut game = Game::new(2705, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = map;
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 2 };
+        let enemy = insert_enemy(&mut game, ActorKind::Goblin, Pos { y: 2, x: 4 }, 3);
+        game.state.actors[enemy].awareness = MAX_AWARENESS;
+        game.at_pause_boundary = true;
+        game.apply_brace().expect("brace at a pause boundary");
+        (game, enemy)
+    }
+
+    fn step_ticks(game: &mut Game, ticks: u64) {
+        for _ in 0..ticks {
+            game.tick += 1;
+            game.step_enemy_turns();
+        }
+    }
+
+    fn counter_damage(game: &Game) -> Option<i32> {
+        game.log.iter().find_map(|event| match event {
+            LogEvent::Counterattack { damage, .. } => Some(*damage),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn brace_counters_the_first_adjacent_enemy_action_once() {
+        let (mut game, enemy) = braced_game(MapBuilder::open(8, 5).discover_all().build());
+        assert!(game.player_is_busy(), "bracing forfeits the next move");
+        let full_hp = game.state.actors[enemy].hp;
+
+        step_ticks(&mut game, 2);
+        assert_eq!(counter_damage(&game), None, "the goblin has not acted yet");
+
+        step_ticks(&mut game, 1);
+        assert_eq!(counter_damage(&game), Some(5));
+        assert_eq!(game.state.actors[enemy].hp, full_hp - 5);
+        assert!(!game.state.braced);
+
+        step_ticks(&mut game, 40);
+        assert_eq!(game.state.actors[enemy].hp, full_hp - 5, "a brace counters only once");
+    }
+
+    #[test]
+    fn defensive_stance_and_chokepoints_sharpen_the_counter() {
+        let corridor = MapBuilder::solid(8, 5)
+            .corridor(Pos { y: 2, x: 1 }, Pos { y: 2, x: 6 })
+            .discover_all()
+            .build();
+        let (mut game, _) = braced_game(corridor);
+        game.state.policy.stance = Stance::Defensive;
+
+        step_ticks(&mut game, 3);
+        assert_eq!(counter_damage(&game), Some(5 - 1 + 2 + 1));
+    }
+
+    #[test]
+    fn brace_is_rejected_mid_run_and_lapses_when_the_player_moves() {
+        let mut game = Game::new(2705, &ContentPack::default(), GameMode::Ironman);
+        game.advance(1);
+        assert!(matches!(game.apply_brace(), Err(GameError::NotAtPauseBoundary { .. })));
+
+        game.at_pause_boundary = true;
+        game.apply_brace().expect("brace at a pause boundary");
+        let start = game.state.actors[game.state.player_id].pos;
+        game.advance(3);
+        assert_ne!(game.state.actors[game.state.player_id].pos, start);
+        assert!(!game.state.braced, "moving away drops the brace");
+    }
+}
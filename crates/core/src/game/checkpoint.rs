@@ -0,0 +1,91 @@
+//! In-memory checkpoints for speculative search over alternative futures.
+//! A checkpoint is a full copy of the game, RNG stream position included, so restoring
+//! one resumes exactly where it was taken without replaying from tick zero.
+
+use super::*;
+
+/// Opaque saved copy of a `Game`, restored with `Game::restore`.
+#[derive(Clone)]
+pub struct GameCheckpoint {
+    game: Game,
+}
+
+impl GameCheckpoint {
+    pub fn tick(&self) -> u64 {
+        self.game.tick
+    }
+
+    pub fn snapshot_hash(&self) -> u64 {
+        self.game.snapshot_hash()
+    }
+}
+
+impl Game {
+    pub fn checkpoint(&self) -> GameCheckpoint {
+        GameCheckpoint { game: self.clone() }
+    }
+
+    /// Rewind to `checkpoint`; the checkpoint stays valid for further restores.
+    pub fn restore(&mut self, checkpoint: &GameCheckpoint) {
+        self.clone_from(&checkpoint.game);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    fn resolve_with(game: &mut Game, choice_for: impl Fn(&Interrupt) -> Choice) {
+        for _ in 0..4 {
+            match game.advance(50).stop_reason {
+                AdvanceStopReason::Interrupted(interrupt) => {
+                    let prompt_id = game.pending_prompt.as_ref().expect("prompt pending").id;
+                    if game.apply_choice(prompt_id, choice_for(&interrupt)).is_err() {
+                        return;
+                    }
+                }
+                AdvanceStopReason::Finished(_) | AdvanceStopReason::EngineFailure(_) => return,
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn restore_rewinds_to_the_checkpointed_state() {
+        let mut game = Game::new(2680, &ContentPack::default(), GameMode::Ironman);
+        game.advance(5);
+        let checkpoint = game.checkpoint();
+        let hash_at_checkpoint = game.snapshot_hash();
+        assert_eq!(checkpoint.snapshot_hash(), hash_at_checkpoint);
+
+        resolve_with(&mut game, |_| Choice::DiscardLoot);
+        assert_ne!(game.current_tick(), checkpoint.tick());
+
+        game.restore(&checkpoint);
+        assert_eq!(game.current_tick(), checkpoint.tick());
+        assert_eq!(game.snapshot_hash(), hash_at_checkpoint);
+    }
+
+    #[test]
+    fn restored_branches_replay_identically() {
+        let mut game = Game::new(99, &ContentPack::default(), GameMode::Ironman);
+        let checkpoint = game.checkpoint();
+        let choose = |interrupt: &Interrupt| match interrupt {
+            Interrupt::LootFound { .. } => Choice::KeepLoot,
+            Interrupt::EnemyEncounter { .. } => Choice::Fight,
+            Interrupt::DoorBlocked { .. } => Choice::OpenDoor,
+            Interrupt::PactAltar { .. } => Choice::KeepPact,
+            Interrupt::FloorTransition { .. } => Choice::Descend,
+            Interrupt::StairsLocked { .. } => Choice::SeekObjective,
+            Interrupt::PerkDraft { .. } => Choice::DraftPerk(0),
+        };
+
+        resolve_with(&mut game, choose);
+        let first_branch = (game.current_tick(), game.snapshot_hash(), game.log().len());
+        game.restore(&checkpoint);
+        resolve_with(&mut game, choose);
+        let second_branch = (game.current_tick(), game.snapshot_hash(), game.log().len());
+        assert_eq!(first_branch, second_branch);
+    }
+}
@@ -3,12 +3,14 @@
 //! It does not own combat math, blink movement, policy mutation, or target ordering details.
 
 use super::*;
+use crate::content::ActionKind;
 use crate::game::prompts::PendingPromptKind;
 
 mod avoidance;
 mod combat;
 mod floor_transition;
 mod loot;
+mod pact;
 mod policy;
 mod targeting;
 
@@ -19,37 +21,81 @@ impl Game {
         choice: Choice,
     ) -> Result<(), GameError> {
         let Some(prompt) = self.pending_prompt.clone() else {
-            return Err(GameError::PromptMismatch);
+            return Err(GameError::NoPendingPrompt { submitted: prompt_id });
         };
         if prompt.id != prompt_id {
-            return Err(GameError::PromptMismatch);
+            return Err(GameError::PromptMismatch { expected: prompt.id, submitted: prompt_id });
         }
 
-        let handled = match (prompt.kind, choice) {
+        let prompt_kind = prompt.kind.prompt_kind();
+        let handled = match (prompt.kind, choice.clone()) {
             (PendingPromptKind::Loot { item }, Choice::KeepLoot) => {
                 self.resolve_keep_loot_choice(item);
+                self.spend_player_action(ActionKind::PickUp);
                 true
             }
             (PendingPromptKind::Loot { item }, Choice::DiscardLoot) => {
                 self.resolve_discard_loot_choice(item);
+                self.spend_player_action(ActionKind::Discard);
                 true
             }
             (PendingPromptKind::EnemyEncounter { primary_enemy, .. }, Choice::Fight) => {
                 self.resolve_fight_choice(primary_enemy);
+                self.spend_player_action(ActionKind::Fight);
                 true
             }
             (PendingPromptKind::EnemyEncounter { primary_enemy, .. }, Choice::Avoid) => {
                 self.resolve_avoid_choice(primary_enemy);
+                self.spend_player_action(ActionKind::Avoid);
                 true
             }
             (PendingPromptKind::DoorBlocked { pos }, Choice::OpenDoor) => {
                 self.state.map.set_tile(pos, TileKind::Floor);
-                let radius = self.get_fov_radius();
-                compute_fov(
-                    &mut self.state.map,
-                    self.state.actors[self.state.player_id].pos,
-                    radius,
-                );
+                self.log.push(LogEvent::DoorOpened { pos });
+                self.refresh_fov(self.state.actors[self.state.player_id].pos);
+                self.spend_player_action(ActionKind::OpenDoor);
+                true
+            }
+            (PendingPromptKind::Loot { item }, Choice::IgnoreFor { ticks })
+                if ticks == SNOOZE_TICKS =>
+            {
+                let target = SnoozeTarget::Loot(self.state.items[item].pos);
+                self.resolve_snooze_choice(target, prompt_kind, ticks);
+                true
+            }
+            (PendingPromptKind::DoorBlocked { pos }, Choice::IgnoreFor { ticks })
+                if ticks == SNOOZE_TICKS =>
+            {
+                self.resolve_snooze_choice(SnoozeTarget::Door(pos), prompt_kind, ticks);
+                true
+            }
+            (PendingPromptKind::PactAltar { current_god, .. }, Choice::BreakPact) => {
+                self.resolve_break_pact_choice(current_god);
+                true
+            }
+            (PendingPromptKind::StairsLocked { .. }, Choice::SeekObjective) => {
+                self.resolve_seek_objective_choice();
+                true
+            }
+            (
+                PendingPromptKind::Loot { .. } | PendingPromptKind::EnemyEncounter { .. },
+                Choice::UseItem(slot),
+            ) => self.use_inventory_item(usize::from(slot)),
+            (PendingPromptKind::Loot { .. }, Choice::DropItem(slot)) => {
+                self.drop_inventory_item(usize::from(slot))
+            }
+            (PendingPromptKind::PerkDraft { offer, .. }, Choice::DraftPerk(index)) => {
+                self.resolve_perk_draft_choice(&offer, index)
+            }
+            (PendingPromptKind::PactAltar { .. }, Choice::CleanseCurses)
+                if self.cursed_item_count() > 0 =>
+            {
+                self.cleanse_curses();
+                self.resolve_keep_pact_choice();
+                true
+            }
+            (PendingPromptKind::PactAltar { .. }, Choice::KeepPact) => {
+                self.resolve_keep_pact_choice();
                 true
             }
             (
@@ -57,6 +103,8 @@ impl Game {
                     current_floor,
                     next_floor,
                     requires_branch_god_choice,
+                    boons,
+                    ..
                 },
                 floor_choice,
             ) if Self::is_floor_transition_choice(&floor_choice) => {
@@ -64,6 +112,7 @@ impl Game {
                     current_floor,
                     next_floor,
                     requires_branch_god_choice,
+                    &boons,
                     floor_choice,
                 )?;
                 true
@@ -72,12 +121,65 @@ impl Game {
         };
 
         if !handled {
-            return Err(GameError::InvalidChoice);
+            return Err(GameError::InvalidChoice {
+                prompt: prompt_kind,
+                choice,
+                reason: "not an option for this prompt",
+            });
         }
 
         self.pending_prompt = None;
         self.next_input_seq += 1;
         self.no_progress_ticks = 0;
+        self.stats.inputs_accepted += 1;
+        self.debug_check_invariants();
         Ok(())
     }
+
+    /// Every choice `apply_choice` would accept right now; empty when no prompt is pending.
+    pub fn available_choices(&self) -> Vec<Choice> {
+        let Some(prompt) = &self.pending_prompt else {
+            return Vec::new();
+        };
+        match &prompt.kind {
+            PendingPromptKind::Loot { .. } => {
+                let slots = 0..self.state.inventory.len() as u8;
+                [Choice::KeepLoot, Choice::DiscardLoot, Choice::IgnoreFor { ticks: SNOOZE_TICKS }]
+                    .into_iter()
+                    .chain(slots.clone().map(Choice::UseItem))
+                    .chain(slots.map(Choice::DropItem))
+                    .collect()
+            }
+            PendingPromptKind::EnemyEncounter { .. } => {
+                let slots = 0..self.state.inventory.len() as u8;
+                [Choice::Fight, Choice::Avoid]
+                    .into_iter()
+                    .chain(slots.map(Choice::UseItem))
+                    .collect()
+            }
+            PendingPromptKind::DoorBlocked { .. } => {
+                vec![Choice::OpenDoor, Choice::IgnoreFor { ticks: SNOOZE_TICKS }]
+            }
+            PendingPromptKind::PactAltar { .. } if self.cursed_item_count() > 0 => {
+                vec![Choice::BreakPact, Choice::KeepPact, Choice::CleanseCurses]
+            }
+            PendingPromptKind::PactAltar { .. } => vec![Choice::BreakPact, Choice::KeepPact],
+            PendingPromptKind::StairsLocked { .. } => vec![Choice::SeekObjective],
+            PendingPromptKind::PerkDraft { offer, .. } => {
+                (0..offer.len()).map(|index| Choice::DraftPerk(index as u8)).collect()
+            }
+            PendingPromptKind::FloorTransition {
+                current_floor,
+                next_floor,
+                requires_branch_god_choice,
+                boons,
+                ..
+            } => self.available_floor_transition_choices(
+                *current_floor,
+                next_floor.is_some(),
+                *requires_branch_god_choice,
+                boons,
+            ),
+        }
+    }
 }
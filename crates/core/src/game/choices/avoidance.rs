@@ -7,13 +7,16 @@ use super::*;
 use crate::content::keys;
 
 impl Game {
+    /// The blink landing within three tiles of `player_pos`, preferring tiles out of sight of
+    /// every enemy watching the player, then the farthest, then the top-left-most.
     pub(super) fn choose_blink_destination(
         &self,
         player_pos: Pos,
         avoid_hazards: bool,
     ) -> Option<Pos> {
         let occupied: BTreeSet<Pos> = self.state.actors.values().map(|actor| actor.pos).collect();
-        let mut best: Option<(u32, Pos)> = None;
+        let watchers = self.enemies_seeing_player();
+        let mut best: Option<(bool, u32, Pos)> = None;
         for y in (player_pos.y - 3)..=(player_pos.y + 3) {
             for x in (player_pos.x - 3)..=(player_pos.x + 3) {
                 let pos = Pos { y, x };
@@ -26,45 +29,52 @@ impl Game {
                 if avoid_hazards && self.state.map.is_hazard(pos) {
                     continue;
                 }
+                let unseen = !watchers.iter().any(|&enemy| self.enemy_sees(enemy, pos));
                 let distance = manhattan(player_pos, pos);
                 let is_better = match best {
                     None => true,
-                    Some((best_distance, best_pos)) => {
-                        distance > best_distance
-                            || (distance == best_distance
+                    Some((best_unseen, best_distance, best_pos)) => {
+                        (unseen, distance) > (best_unseen, best_distance)
+                            || ((unseen, distance) == (best_unseen, best_distance)
                                 && (pos.y, pos.x) < (best_pos.y, best_pos.x))
                     }
                 };
                 if is_better {
-                    best = Some((distance, pos));
+                    best = Some((unseen, distance, pos));
                 }
             }
         }
-        best.map(|(_, pos)| pos)
+        best.map(|(_, _, pos)| pos)
     }
 
-    pub(super) fn resolve_avoid_choice(&mut self, primary_enemy: EntityId) {
+    pub(in crate::game) fn resolve_avoid_choice(&mut self, primary_enemy: EntityId) {
         let player_pos = self.state.actors[self.state.player_id].pos;
-        if self.state.active_god == Some(GodId::Veil) {
-            if let Some(best_pos) = self.choose_blink_destination(player_pos, true) {
+        let destination = if self.state.active_god == Some(GodId::Veil) {
+            self.choose_blink_destination(player_pos, true)
+        } else if self.state.active_perks.contains(&keys::PERK_SHADOW_STEP) {
+            Some(self.choose_blink_destination(player_pos, false).unwrap_or(player_pos))
+        } else {
+            None
+        };
+        match destination.or_else(|| self.spend_blink_charge(player_pos)) {
+            Some(best_pos) => {
                 self.state.actors.get_mut(self.state.player_id).expect("player should exist").pos =
                     best_pos;
-                let radius = self.get_fov_radius();
-                compute_fov(&mut self.state.map, best_pos, radius);
+                self.refresh_fov(best_pos);
                 self.suppressed_enemy = None;
-            } else {
-                self.suppressed_enemy = Some(primary_enemy);
             }
-        } else if self.state.active_perks.contains(&keys::PERK_SHADOW_STEP) {
-            let best_pos = self.choose_blink_destination(player_pos, false).unwrap_or(player_pos);
-            self.state.actors.get_mut(self.state.player_id).expect("player should exist").pos =
-                best_pos;
-            let radius = self.get_fov_radius();
-            compute_fov(&mut self.state.map, best_pos, radius);
-            self.suppressed_enemy = None;
-        } else {
-            self.suppressed_enemy = Some(primary_enemy);
+            None => self.suppressed_enemy = Some(primary_enemy),
+        }
+    }
+
+    /// Spend a banked blink charge on a blink that may land on a hazard.
+    fn spend_blink_charge(&mut self, player_pos: Pos) -> Option<Pos> {
+        if self.state.blink_charges == 0 {
+            return None;
         }
+        let destination = self.choose_blink_destination(player_pos, false)?;
+        self.state.blink_charges -= 1;
+        Some(destination)
     }
 }
 
@@ -73,7 +83,7 @@ mod tests {
     use super::*;
     use crate::Map;
     use crate::content::ContentPack;
-    use crate::game::test_support::add_goblin;
+    use crate::game::test_support::{MapBuilder, add_goblin};
 
     #[test]
     fn veil_avoid_blinks_to_farthest_safe_tile() {
@@ -109,6 +119,23 @@ mod tests {
         assert_eq!(game.state.actors[game.state.player_id].pos, Pos { y: 1, x: 1 });
     }
 
+    #[test]
+    fn blink_prefers_tiles_out_of_the_watching_enemies_sight() {
+        let mut game = Game::new(2750, &ContentPack::default(), GameMode::Ironman);
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.sanctuary_active = false;
+        game.state.map = MapBuilder::open(12, 9).discover_all().build();
+        let player_pos = Pos { y: 4, x: 2 };
+        game.state.actors[game.state.player_id].pos = player_pos;
+        let goblin = add_goblin(&mut game, Pos { y: 4, x: 6 });
+        assert_eq!(game.enemies_seeing_player().len(), 1);
+
+        let destination = game.choose_blink_destination(player_pos, true);
+        assert_eq!(destination, Some(Pos { y: 1, x: 1 }), "farther tiles are all in sight");
+        assert!(!game.enemy_sees(goblin, Pos { y: 1, x: 1 }));
+        assert!(game.enemy_sees(goblin, Pos { y: 1, x: 5 }));
+    }
+
     #[test]
     fn veil_avoid_falls_back_to_suppression_when_no_safe_blink_exists() {
         let mut game = Game::new(445566, &ContentPack::default(), GameMode::Ironman);
@@ -144,6 +171,32 @@ mod tests {
         assert_eq!(game.suppressed_enemy, Some(enemy_id));
     }
 
+    #[test]
+    fn blink_charge_escapes_when_only_hazards_are_in_reach() {
+        let mut game = Game::new(445566, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.active_god = Some(GodId::Veil);
+        game.state.blink_charges = 1;
+        game.state.map = MapBuilder::solid(7, 7)
+            .corridor(Pos { y: 3, x: 1 }, Pos { y: 3, x: 4 })
+            .hazard(Pos { y: 3, x: 1 })
+            .hazard(Pos { y: 3, x: 2 })
+            .discover_all()
+            .visible_all()
+            .build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 3, x: 3 };
+        add_goblin(&mut game, Pos { y: 3, x: 4 });
+
+        let AdvanceStopReason::Interrupted(interrupt) = game.advance(1).stop_reason else {
+            panic!("expected enemy encounter interrupt");
+        };
+        game.apply_choice(interrupt.prompt_id(), Choice::Avoid).expect("avoid should apply");
+
+        assert_eq!(game.state.actors[game.state.player_id].pos, Pos { y: 3, x: 1 });
+        assert_eq!(game.state.blink_charges, 0);
+    }
+
     #[test]
     fn avoid_suppresses_only_primary_enemy_and_still_interrupts_on_other_enemy() {
         let mut game = Game::new(12345, &ContentPack::default(), GameMode::Ironman);
@@ -8,11 +8,12 @@ impl Game {
     pub(in crate::game) fn effective_player_defense(&self) -> i32 {
         let mut defense = self.state.actors[self.state.player_id].defense;
         if self.state.active_perks.contains(&keys::PERK_IRON_WILL) {
-            defense += 2;
+            defense += self.perk_tuning.iron_will_defense;
         }
         if self.state.active_perks.contains(&keys::PERK_RECKLESS_STRIKE) {
-            defense -= 2;
+            defense -= self.perk_tuning.reckless_defense_penalty;
         }
+        defense -= self.cursed_defense_penalty();
         match self.state.policy.stance {
             Stance::Aggressive => defense -= 1,
             Stance::Balanced => {}
@@ -24,23 +25,28 @@ impl Game {
         defense
     }
 
-    pub(super) fn resolve_fight_choice(&mut self, primary_enemy: EntityId) {
-        let mut player_attack = self.state.actors[self.state.player_id].attack;
+    pub(in crate::game) fn resolve_fight_choice(&mut self, primary_enemy: EntityId) {
+        let damage = self.player_strike_damage(primary_enemy);
         let _player_defense = self.effective_player_defense();
+        self.log.push(LogEvent::EncounterResolved { enemy: primary_enemy, fought: true });
+        self.apply_player_strike(primary_enemy, damage);
+    }
 
+    /// Damage one player strike deals to `enemy` with the current weapon, perks, and stance.
+    pub(in crate::game) fn player_strike_damage(&self, enemy: EntityId) -> i32 {
+        let mut player_attack = self.state.actors[self.state.player_id].attack;
         let equipped = self.active_player_weapon();
-        let ignores_armor = equipped == Some(keys::WEAPON_PHASE_DAGGER);
-        let lifesteal = equipped == Some(keys::WEAPON_BLOOD_AXE);
-
         if let Some(weapon) = equipped {
-            player_attack += Self::weapon_attack_bonus(weapon);
+            player_attack += self.weapon_attack_bonus(weapon) + self.active_weapon_enchant();
         }
 
+        player_attack -= self.curse_attack_penalty();
+        player_attack += self.cursed_attack_bonus();
         if self.state.active_perks.contains(&keys::PERK_RECKLESS_STRIKE) {
-            player_attack += 4;
+            player_attack += self.perk_tuning.reckless_attack;
         }
         if self.state.active_perks.contains(&keys::PERK_BERSERKER_RHYTHM) && equipped.is_none() {
-            player_attack += 3;
+            player_attack += self.perk_tuning.berserker_unarmed_attack;
         }
 
         match self.state.policy.stance {
@@ -53,45 +59,46 @@ impl Game {
             }
         }
 
-        let mut enemy_defense = self.state.actors[primary_enemy].defense;
-        if ignores_armor {
+        let mut enemy_defense = self.state.actors[enemy].defense;
+        if equipped == Some(keys::WEAPON_PHASE_DAGGER) {
             enemy_defense = 0;
         }
 
-        let damage = (player_attack.saturating_sub(enemy_defense)).max(1);
+        (player_attack.saturating_sub(enemy_defense)).max(1)
+    }
+
+    /// Damage one strike from `enemy` deals the player through its current defense.
+    pub(in crate::game) fn enemy_strike_damage(&self, enemy: EntityId) -> i32 {
+        (self.state.actors[enemy].attack - self.effective_player_defense()).max(1)
+    }
 
-        let enemy_actor =
-            self.state.actors.get_mut(primary_enemy).expect("primary enemy should exist");
+    /// Deal `damage` to `enemy`, removing it and applying on-kill effects when it dies.
+    pub(in crate::game) fn apply_player_strike(&mut self, enemy: EntityId, damage: i32) {
+        let lifesteal = self.active_player_weapon() == Some(keys::WEAPON_BLOOD_AXE);
+        let enemy_actor = self.state.actors.get_mut(enemy).expect("struck enemy should exist");
         enemy_actor.hp -= damage;
 
-        self.log.push(LogEvent::EncounterResolved { enemy: primary_enemy, fought: true });
-
         if enemy_actor.hp <= 0 {
-            self.state.actors.remove(primary_enemy);
+            let kind = self.state.actors[enemy].kind;
+            let unique = self.state.actors[enemy].unique;
+            self.state.actors.remove(enemy);
+            self.record_slain_unique(unique);
             self.state.kills_this_floor += 1;
+            self.log.push(LogEvent::EnemySlain { kind });
+            self.grant_kill_xp(kind);
 
             let has_bloodlust = self.state.active_perks.contains(&keys::PERK_BLOODLUST);
+            let bloodlust_heal = self.perk_tuning.bloodlust_heal;
             let player =
                 self.state.actors.get_mut(self.state.player_id).expect("player should exist");
             if has_bloodlust {
-                player.hp = (player.hp + 2).min(player.max_hp);
+                player.hp = (player.hp + bloodlust_heal).min(player.max_hp);
             }
             if lifesteal {
                 player.hp = (player.hp + 1).min(player.max_hp);
             }
         }
     }
-
-    fn weapon_attack_bonus(weapon: &'static str) -> i32 {
-        match weapon {
-            keys::WEAPON_RUSTY_SWORD => 2,
-            keys::WEAPON_IRON_MACE => 4,
-            keys::WEAPON_STEEL_LONGSWORD => 6,
-            keys::WEAPON_PHASE_DAGGER => 3,
-            keys::WEAPON_BLOOD_AXE => 6,
-            _ => 0,
-        }
-    }
 }
 
 #[cfg(test)]
@@ -99,6 +106,7 @@ mod tests {
     use super::*;
     use crate::content::{ContentPack, keys};
     use crate::game::test_support::add_goblin;
+    use crate::state::ItemInstance;
 
     #[test]
     fn fighting_primary_enemy_leaves_other_enemy_to_interrupt_next_tick() {
@@ -232,8 +240,10 @@ mod tests {
         game.state.actors.retain(|id, _| id == game.state.player_id);
 
         let player_id = game.state.player_id;
-        game.state.actors[player_id].equipped_weapon = Some(keys::WEAPON_RUSTY_SWORD);
-        game.state.actors[player_id].reserve_weapon = Some(keys::WEAPON_STEEL_LONGSWORD);
+        game.state.actors[player_id].equipped_weapon =
+            Some(ItemInstance::new(keys::WEAPON_RUSTY_SWORD));
+        game.state.actors[player_id].reserve_weapon =
+            Some(ItemInstance::new(keys::WEAPON_STEEL_LONGSWORD));
         game.state.actors[player_id].active_weapon_slot = WeaponSlot::Primary;
 
         let player_pos = game.state.actors[player_id].pos;
@@ -10,6 +10,7 @@ impl Game {
         matches!(
             choice,
             Choice::Descend
+                | Choice::DescendWithBoon(_)
                 | Choice::DescendBranchAVeil
                 | Choice::DescendBranchAForge
                 | Choice::DescendBranchBVeil
@@ -22,18 +23,30 @@ impl Game {
         current_floor: u8,
         next_floor: Option<u8>,
         requires_branch_god_choice: bool,
+        boons: &[GodBoon],
         choice: Choice,
     ) -> Result<(), GameError> {
         if self.state.floor_index != current_floor {
-            return Err(GameError::InvalidChoice);
+            return Err(invalid_descend(choice, "the prompt is for a different floor"));
         }
 
         if requires_branch_god_choice && !Self::is_branch_choice(&choice) {
-            return Err(GameError::InvalidChoice);
+            return Err(invalid_descend(choice, "this descent requires a branch and god"));
         }
-        if !requires_branch_god_choice && !matches!(choice, Choice::Descend) {
-            return Err(GameError::InvalidChoice);
+        if !requires_branch_god_choice
+            && !matches!(choice, Choice::Descend | Choice::DescendWithBoon(_))
+        {
+            return Err(invalid_descend(choice, "the branch and god are already chosen"));
         }
+        let boon = match choice {
+            Choice::DescendWithBoon(kind) => {
+                let Some(boon) = boons.iter().find(|boon| boon.kind == kind) else {
+                    return Err(invalid_descend(choice, "that boon is not on offer"));
+                };
+                Some(*boon)
+            }
+            _ => None,
+        };
 
         match &choice {
             Choice::DescendBranchAVeil => {
@@ -52,12 +65,10 @@ impl Game {
                 self.state.branch_profile = BranchProfile::BranchB;
                 self.state.active_god = Some(GodId::Forge);
             }
-            Choice::Descend => {
-                if self.state.branch_profile == BranchProfile::Uncommitted
-                    || self.state.active_god.is_none()
-                {
-                    return Err(GameError::InvalidChoice);
-                }
+            Choice::Descend | Choice::DescendWithBoon(_)
+                if next_floor.is_some() && !self.branch_and_god_chosen() =>
+            {
+                return Err(invalid_descend(choice, "no branch and god have been chosen yet"));
             }
             _ => {}
         }
@@ -69,12 +80,17 @@ impl Game {
             player.hp = (player.hp + 2).min(player.max_hp);
         }
 
+        if let Some(boon) = boon {
+            self.apply_boon(boon);
+        }
+
         if self.state.active_perks.contains(&keys::PERK_PACIFISTS_BOUNTY)
             && self.state.kills_this_floor == 0
         {
+            let bounty = self.perk_tuning.pacifist_max_hp;
             let player =
                 self.state.actors.get_mut(self.state.player_id).expect("player should exist");
-            player.max_hp += 5;
+            player.max_hp += bounty;
             player.hp = player.max_hp;
         }
 
@@ -82,13 +98,43 @@ impl Game {
         match next_floor {
             Some(next_index) => self.descend_to_floor(next_index),
             None => {
-                self.finished_outcome = Some(RunOutcome::Victory);
+                self.finished_outcome = Some(RunOutcome::Victory(self.victory_kind()));
             }
         }
 
         Ok(())
     }
 
+    /// The descend choices `resolve_floor_transition_choice` accepts in the current state.
+    /// Leaving the last floor needs no branch or god, so a run started there can still end.
+    pub(super) fn available_floor_transition_choices(
+        &self,
+        current_floor: u8,
+        has_next_floor: bool,
+        requires_branch_god_choice: bool,
+        boons: &[GodBoon],
+    ) -> Vec<Choice> {
+        if self.state.floor_index != current_floor {
+            Vec::new()
+        } else if requires_branch_god_choice {
+            vec![
+                Choice::DescendBranchAVeil,
+                Choice::DescendBranchAForge,
+                Choice::DescendBranchBVeil,
+                Choice::DescendBranchBForge,
+            ]
+        } else if has_next_floor && !self.branch_and_god_chosen() {
+            Vec::new()
+        } else {
+            let boon_choices = boons.iter().map(|boon| Choice::DescendWithBoon(boon.kind));
+            [Choice::Descend].into_iter().chain(boon_choices).collect()
+        }
+    }
+
+    fn branch_and_god_chosen(&self) -> bool {
+        self.state.branch_profile != BranchProfile::Uncommitted && self.state.active_god.is_some()
+    }
+
     fn is_branch_choice(choice: &Choice) -> bool {
         matches!(
             choice,
@@ -99,3 +145,7 @@ impl Game {
         )
     }
 }
+
+fn invalid_descend(choice: Choice, reason: &'static str) -> GameError {
+    GameError::InvalidChoice { prompt: PromptKind::FloorTransition, choice, reason }
+}
@@ -2,16 +2,27 @@
 //! This module applies keep/discard outcomes for prompted loot pickups.
 
 use super::*;
+use crate::state::Item;
 
 impl Game {
-    pub(super) fn resolve_keep_loot_choice(&mut self, item: ItemId) {
-        let kind = self.state.items[item].kind;
-        self.apply_item_effect(kind);
+    pub(in crate::game) fn resolve_keep_loot_choice(&mut self, item: ItemId) {
+        let Item { kind, cursed, .. } = self.state.items[item];
         self.state.items.remove(item);
         self.log.push(LogEvent::ItemPickedUp { kind });
+        // A consumable waits in the inventory; with no room left it is used on the spot.
+        if let ItemKind::Consumable(id) = kind
+            && self.stow_consumable(id)
+        {
+            return;
+        }
+        if cursed {
+            self.apply_cursed_item(kind);
+        } else {
+            self.apply_item_effect(kind);
+        }
     }
 
-    pub(super) fn resolve_discard_loot_choice(&mut self, item: ItemId) {
+    pub(in crate::game) fn resolve_discard_loot_choice(&mut self, item: ItemId) {
         let kind = self.state.items[item].kind;
         self.state.items.remove(item);
         self.log.push(LogEvent::ItemDiscarded { kind });
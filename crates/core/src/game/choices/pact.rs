@@ -0,0 +1,155 @@
+//! Pact-altar choice handlers and the oathbreaker curse.
+//! Breaking a pact swaps the active god for its alternate at a permanent max HP cost.
+
+use super::*;
+
+/// Max HP permanently lost when the player breaks their pact.
+const PACT_BREAK_MAX_HP_COST: i32 = 6;
+/// Floors the oathbreaker curse lasts, counting the floor the pact was broken on.
+const PACT_CURSE_FLOORS: u8 = 3;
+/// Attack lost while the oathbreaker curse lasts.
+const CURSE_ATTACK_PENALTY: i32 = 2;
+
+impl Game {
+    pub(super) fn resolve_break_pact_choice(&mut self, current_god: GodId) {
+        let alternate = current_god.alternate();
+        self.state.active_god = Some(alternate);
+        self.state.pact_broken = true;
+        self.state.altar_tile = None;
+        self.state.curse_floors_remaining = PACT_CURSE_FLOORS;
+
+        let player = self.state.actors.get_mut(self.state.player_id).expect("player should exist");
+        player.max_hp = (player.max_hp - PACT_BREAK_MAX_HP_COST).max(1);
+        player.hp = player.hp.min(player.max_hp);
+        self.log.push(LogEvent::PactBroken { from: current_god, to: alternate });
+    }
+
+    pub(super) fn resolve_keep_pact_choice(&mut self) {
+        self.state.altar_tile = None;
+    }
+
+    /// Attack penalty from the oathbreaker curse, or zero when the player is not cursed.
+    pub(in crate::game) fn curse_attack_penalty(&self) -> i32 {
+        if self.state.curse_floors_remaining > 0 { CURSE_ATTACK_PENALTY } else { 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::{MapBuilder, add_goblin};
+
+    fn game_at_altar(god: GodId) -> (Game, ChoicePromptId) {
+        let mut game = Game::new(31337, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(7, 7).discover_all().build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 3, x: 3 };
+        game.state.active_god = Some(god);
+        game.state.altar_tile = Some(Pos { y: 3, x: 4 });
+
+        match game.advance(1).stop_reason {
+            AdvanceStopReason::Interrupted(Interrupt::PactAltar {
+                prompt_id,
+                current_god,
+                alternate_god,
+                ..
+            }) => {
+                assert_eq!((current_god, alternate_god), (god, god.alternate()));
+                (game, prompt_id)
+            }
+            other => panic!("expected pact altar interrupt, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn breaking_the_pact_swaps_gods_and_costs_max_hp() {
+        let (mut game, prompt_id) = game_at_altar(GodId::Forge);
+        let forge_defense = game.effective_player_defense();
+        assert_eq!(game.available_choices(), vec![Choice::BreakPact, Choice::KeepPact]);
+
+        game.apply_choice(prompt_id, Choice::BreakPact).expect("break should apply");
+
+        let player = &game.state.actors[game.state.player_id];
+        assert_eq!(game.state.active_god, Some(GodId::Veil));
+        assert_eq!(player.max_hp, 20 - PACT_BREAK_MAX_HP_COST);
+        assert_eq!(player.hp, player.max_hp);
+        assert_eq!(game.effective_player_defense(), forge_defense - 2, "Forge armor is gone");
+        assert_eq!(game.curse_attack_penalty(), CURSE_ATTACK_PENALTY);
+        assert_eq!(game.state.altar_tile, None);
+        assert!(game.log.contains(&LogEvent::PactBroken { from: GodId::Forge, to: GodId::Veil }));
+    }
+
+    #[test]
+    fn keeping_the_pact_only_consumes_the_altar() {
+        let (mut game, prompt_id) = game_at_altar(GodId::Veil);
+        let hash_before = game.snapshot_hash();
+        game.apply_choice(prompt_id, Choice::KeepPact).expect("keep should apply");
+
+        assert_eq!(game.state.active_god, Some(GodId::Veil));
+        assert!(!game.state.pact_broken);
+        assert_eq!(game.state.actors[game.state.player_id].max_hp, 20);
+        assert_ne!(game.snapshot_hash(), hash_before);
+        assert!(
+            !matches!(game.advance(5).stop_reason, AdvanceStopReason::Interrupted(_)),
+            "a kept altar does not ask again"
+        );
+    }
+
+    #[test]
+    fn curse_lifts_after_its_floors_and_no_altar_follows_a_broken_pact() {
+        let (mut game, prompt_id) = game_at_altar(GodId::Veil);
+        game.apply_choice(prompt_id, Choice::BreakPact).expect("break should apply");
+
+        for floor in 2..2 + PACT_CURSE_FLOORS {
+            assert!(game.curse_attack_penalty() > 0, "still cursed before floor {floor}");
+            game.descend_to_floor(floor);
+            assert_eq!(game.state.altar_tile, None);
+        }
+        assert_eq!(game.curse_attack_penalty(), 0);
+        assert_eq!(game.state.active_god, Some(GodId::Forge));
+    }
+
+    #[test]
+    fn cursed_player_hits_for_less_than_one_who_kept_the_pact() {
+        let damage_after = |choice: Choice| {
+            let (mut game, prompt_id) = game_at_altar(GodId::Forge);
+            game.apply_choice(prompt_id, choice).expect("pact choice should apply");
+            let goblin = add_goblin(&mut game, Pos { y: 3, x: 2 });
+            game.state.actors[goblin].hp = 99;
+            game.state.actors[goblin].defense = 0;
+            let AdvanceStopReason::Interrupted(interrupt) = game.advance(1).stop_reason else {
+                panic!("expected the goblin to interrupt");
+            };
+            game.apply_choice(interrupt.prompt_id(), Choice::Fight).expect("fight should apply");
+            99 - game.state.actors[goblin].hp
+        };
+
+        assert_eq!(
+            damage_after(Choice::KeepPact) - damage_after(Choice::BreakPact),
+            CURSE_ATTACK_PENALTY
+        );
+    }
+
+    #[test]
+    fn altar_stands_beside_the_stairs_once_a_god_is_chosen() {
+        let mut game = Game::new(51_515, &ContentPack::default(), GameMode::Ironman);
+        game.state.active_god = Some(GodId::Veil);
+        game.descend_to_floor(2);
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.items.clear();
+
+        let map = &game.state.map;
+        let stairs = map.positions().find(|pos| map.tile_at(*pos) == TileKind::DownStairs);
+        let stairs = stairs.expect("floor 2 should have a stairs tile");
+        let altar = game.state.altar_tile.expect("a pact altar should be placed");
+        assert_eq!(manhattan(altar, stairs), 1);
+
+        game.state.actors[game.state.player_id].pos = stairs;
+        game.advance(1);
+        let batch = game.prompt_batch().expect("the altar should interrupt");
+        assert_eq!(batch.active.prompt_kind(), PromptKind::PactAltar);
+        assert_eq!(batch.queued, vec![PromptKind::FloorTransition]);
+    }
+}
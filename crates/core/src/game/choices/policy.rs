@@ -2,11 +2,12 @@
 //! This module mutates policy settings and active weapon slot at pause boundaries.
 
 use super::*;
+use crate::content::ActionKind;
 
 impl Game {
     pub fn apply_policy_update(&mut self, update: PolicyUpdate) -> Result<(), GameError> {
         if !self.at_pause_boundary && self.pending_prompt.is_none() {
-            return Err(GameError::NotAtPauseBoundary);
+            return Err(GameError::NotAtPauseBoundary { tick: self.tick });
         }
         match update {
             PolicyUpdate::FightMode(mode) => self.state.policy.fight_or_avoid = mode,
@@ -25,22 +26,34 @@ impl Game {
                 self.state.policy.resource_aggression = aggression
             }
             PolicyUpdate::ExplorationMode(mode) => self.state.policy.exploration_mode = mode,
+            PolicyUpdate::AutoPickup(rules) => self.state.policy.auto_pickup = rules,
+            PolicyUpdate::AutoFightTrivial(enabled) => {
+                self.state.policy.auto_fight_trivial = enabled
+            }
+            PolicyUpdate::AutoResolveEncounters(enabled) => {
+                self.state.policy.auto_resolve_encounters = enabled
+            }
         }
         self.no_progress_ticks = 0;
+        self.stats.inputs_accepted += 1;
         Ok(())
     }
 
     pub fn apply_swap_weapon(&mut self) -> Result<(), GameError> {
         if !self.at_pause_boundary && self.pending_prompt.is_none() {
-            return Err(GameError::NotAtPauseBoundary);
+            return Err(GameError::NotAtPauseBoundary { tick: self.tick });
+        }
+        if self.active_weapon_cursed() {
+            return Err(GameError::CursedWeapon);
         }
         let player = self.state.actors.get_mut(self.state.player_id).expect("player should exist");
         player.active_weapon_slot = match player.active_weapon_slot {
             WeaponSlot::Primary => WeaponSlot::Reserve,
             WeaponSlot::Reserve => WeaponSlot::Primary,
         };
-        player.next_action_tick += 10;
+        self.spend_player_action(ActionKind::SwapWeapon);
         self.no_progress_ticks = 0;
+        self.stats.inputs_accepted += 1;
         Ok(())
     }
 }
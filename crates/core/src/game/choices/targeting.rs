@@ -24,20 +24,7 @@ impl Game {
                     return cmp;
                 }
             }
-
-            let dist_cmp = manhattan(pos, a.pos).cmp(&manhattan(pos, b.pos));
-            if dist_cmp != Ordering::Equal {
-                return dist_cmp;
-            }
-            let y_cmp = a.pos.y.cmp(&b.pos.y);
-            if y_cmp != Ordering::Equal {
-                return y_cmp;
-            }
-            let x_cmp = a.pos.x.cmp(&b.pos.x);
-            if x_cmp != Ordering::Equal {
-                return x_cmp;
-            }
-            a.kind.cmp(&b.kind)
+            ordering::nearest_actor(Some(pos), a, b)
         });
         enemies
     }
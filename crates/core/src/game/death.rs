@@ -0,0 +1,108 @@
+//! The player death pipeline: every blow that can bring HP to zero goes through
+//! `damage_player`, which ends the run and records the final blow exactly once.
+
+use super::*;
+
+impl Game {
+    /// Deal `amount` damage of `source` to the player, from an enemy of kind `attacker` if
+    /// one dealt it. HP reaching zero ends the run in defeat; a finished run takes no damage.
+    pub fn damage_player(
+        &mut self,
+        amount: i32,
+        source: DamageSource,
+        attacker: Option<ActorKind>,
+    ) {
+        if self.finished_outcome.is_some() {
+            return;
+        }
+        let player = &mut self.state.actors[self.state.player_id];
+        player.hp = (player.hp - amount).max(0);
+        if player.hp == 0 {
+            self.kill_player(amount, source, attacker);
+        }
+    }
+
+    /// How the player died, once the run has ended in defeat.
+    pub fn death_record(&self) -> Option<&DeathRecord> {
+        self.death.as_ref()
+    }
+
+    fn kill_player(&mut self, damage: i32, source: DamageSource, killer: Option<ActorKind>) {
+        self.death = Some(DeathRecord {
+            killer,
+            source,
+            damage,
+            floor_index: self.state.floor_index,
+            tick: self.tick,
+            recent_events: self.log.recent(DEATH_LOG_TAIL).cloned().collect(),
+        });
+        self.log.push(LogEvent::PlayerSlain { killer, source });
+        self.finished_outcome = Some(RunOutcome::Defeat(source.death_cause()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::{MapBuilder, add_goblin};
+    use crate::state::MAX_AWARENESS;
+
+    #[test]
+    fn lethal_blow_records_the_killer_and_ends_the_next_advance() {
+        let mut game = Game::new(2745, &ContentPack::default(), GameMode::Ironman);
+        for seq in 0..12 {
+            game.log.push(LogEvent::Notice(format!("event {seq}")));
+        }
+        let hp = game.state.actors[game.state.player_id].hp;
+        game.damage_player(hp - 1, DamageSource::Poison, Some(ActorKind::BloodAcolyte));
+        assert_eq!(game.death_record(), None, "one HP left is still alive");
+
+        game.damage_player(4, DamageSource::Melee, Some(ActorKind::Goblin));
+        let record = game.death_record().expect("the second blow is fatal").clone();
+        assert_eq!(game.state.actors[game.state.player_id].hp, 0);
+        assert_eq!(
+            (record.killer, record.source, record.damage, record.floor_index),
+            (Some(ActorKind::Goblin), DamageSource::Melee, 4, game.state.floor_index)
+        );
+        assert_eq!(record.recent_events.len(), DEATH_LOG_TAIL);
+        assert_eq!(record.recent_events.last(), Some(&LogEvent::Notice("event 11".to_string())));
+        let slain =
+            LogEvent::PlayerSlain { killer: Some(ActorKind::Goblin), source: DamageSource::Melee };
+        assert_eq!(game.log.last(), Some(&slain));
+
+        game.damage_player(9, DamageSource::Hazard, None);
+        assert_eq!(game.death_record(), Some(&record), "the first death is the one recorded");
+        assert!(matches!(
+            game.advance(10).stop_reason,
+            AdvanceStopReason::Finished(RunOutcome::Defeat(DeathCause::Damage))
+        ));
+    }
+
+    #[test]
+    fn an_adjacent_enemy_kills_the_player_on_its_own_turn() {
+        let mut game = Game::new(2752, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(4, 3).discover_all().build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 1, x: 1 };
+        let goblin = add_goblin(&mut game, Pos { y: 1, x: 2 });
+        game.state.actors[goblin].awareness = MAX_AWARENESS;
+        game.state.actors[game.state.player_id].hp = 1;
+
+        let stop = loop {
+            match game.advance(50).stop_reason {
+                AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { prompt_id, .. }) => {
+                    game.apply_choice(prompt_id, Choice::Avoid).expect("avoid should apply");
+                }
+                other => break other,
+            }
+        };
+        assert!(
+            matches!(stop, AdvanceStopReason::Finished(RunOutcome::Defeat(DeathCause::Damage))),
+            "{stop:?}"
+        );
+        let record = game.death_record().expect("the goblin's strike is fatal");
+        assert_eq!((record.killer, record.source), (Some(ActorKind::Goblin), DamageSource::Melee));
+    }
+}
@@ -0,0 +1,123 @@
+//! Engine failure diagnostics and debug counters captured from the live game state.
+//! This module only gathers context; reporting and persistence belong to the caller.
+
+use super::*;
+
+/// Bookkeeping kept only for debug overlays; it is never hashed or checkpointed as state.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct DebugCounters {
+    pub(super) planner_pass: Option<PlannerPass>,
+    pub(super) last_astar_expansions: u32,
+    pub(super) fov_recomputes: u64,
+    pub(super) target_scans: u64,
+    pub(super) path_cache_hits: u64,
+}
+
+impl Game {
+    /// Engine bookkeeping for a debug overlay.
+    pub fn engine_internals(&self) -> EngineInternals {
+        EngineInternals {
+            no_progress_ticks: self.no_progress_ticks,
+            suppressed_enemy: self.suppressed_enemy,
+            pending_prompt_id: self.pending_prompt.as_ref().map(|prompt| prompt.id),
+            planner_pass: self.debug_counters.planner_pass,
+            last_astar_expansions: self.debug_counters.last_astar_expansions,
+            fov_recomputes: self.debug_counters.fov_recomputes,
+            target_scans: self.debug_counters.target_scans,
+            path_cache_hits: self.debug_counters.path_cache_hits,
+        }
+    }
+
+    /// Route the player toward `intent`, noting the planner pass and A* effort for debugging.
+    /// A route already searched on this map revision is reused rather than searched again.
+    /// `None` also when a budgeted search runs out first; `PathCache::yielded` tells them apart.
+    pub(super) fn route_auto_intent(
+        &mut self,
+        player_pos: Pos,
+        intent: AutoExploreIntent,
+    ) -> Option<Vec<Pos>> {
+        let revision = (self.state.floor_index, self.state.map.revision);
+        let (search, cached) =
+            self.path_cache.path_for_intent(revision, &self.state.map, player_pos, intent)?;
+        self.debug_counters.planner_pass = Some(planner_pass(intent));
+        if cached {
+            self.debug_counters.path_cache_hits += 1;
+        } else {
+            self.debug_counters.last_astar_expansions = search.expansions;
+        }
+        search.path
+    }
+
+    /// Recompute the player's field of view from `origin` at the current sight radius.
+    pub(super) fn refresh_fov(&mut self, origin: Pos) {
+        let radius = self.get_fov_radius();
+        compute_fov(&mut self.state.map, origin, radius);
+        self.debug_counters.fov_recomputes += 1;
+    }
+
+    /// Snapshot of the context needed to debug a stalled or failed simulation.
+    pub fn engine_diagnostics(&self) -> EngineDiagnostics {
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let reachable_frontier_count =
+            reachable_discovered_walkable_tiles(&self.state.map, player_pos)
+                .into_iter()
+                .filter(|&pos| is_frontier_candidate(&self.state.map, pos))
+                .count();
+        EngineDiagnostics {
+            tick: self.tick,
+            floor_index: self.state.floor_index,
+            player_pos,
+            last_intent: self.state.auto_intent,
+            reachable_frontier_count,
+            snapshot_hash: self.snapshot_hash(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::MapBuilder;
+
+    #[test]
+    fn internals_track_planner_pass_astar_effort_cache_hits_and_fov_recomputes() {
+        let mut game = Game::new(2712, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::solid(12, 5)
+            .corridor(Pos { y: 2, x: 1 }, Pos { y: 2, x: 10 })
+            .hazard(Pos { y: 2, x: 4 })
+            .discover_all()
+            .build();
+        let start = Pos { y: 2, x: 1 };
+        game.state.actors[game.state.player_id].pos = start;
+        assert_eq!(game.engine_internals().planner_pass, None);
+
+        let target = Pos { y: 2, x: 8 };
+        let fallback =
+            AutoExploreIntent { target, reason: AutoReason::ThreatAvoidance, path_len: 7 };
+        assert!(game.route_auto_intent(start, fallback).is_some());
+        let internals = game.engine_internals();
+        assert_eq!(internals.planner_pass, Some(PlannerPass::HazardFallback));
+        assert_eq!(
+            internals.last_astar_expansions, 8,
+            "a straight corridor expands each tile once"
+        );
+
+        assert!(game.route_auto_intent(start, fallback).is_some());
+        assert_eq!(game.engine_internals().path_cache_hits, 1, "the same route is reused");
+        game.state.map.set_tile(Pos { y: 2, x: 9 }, TileKind::Wall);
+        assert!(game.route_auto_intent(start, fallback).is_some());
+        assert_eq!(game.engine_internals().path_cache_hits, 1, "a map change forces a search");
+
+        let safe = AutoExploreIntent { reason: AutoReason::Frontier, ..fallback };
+        assert_eq!(game.route_auto_intent(start, safe), None, "the safe pass refuses the hazard");
+        assert_eq!(game.engine_internals().planner_pass, Some(PlannerPass::Safe));
+
+        let before = game.engine_internals().fov_recomputes;
+        game.refresh_fov(start);
+        assert_eq!(game.engine_internals().fov_recomputes, before + 1);
+        assert_eq!(game.engine_internals().pending_prompt_id, None);
+    }
+}
@@ -0,0 +1,153 @@
+//! Optional adaptive difficulty director: after each floor it weighs how the player fared and
+//! nudges wandering spawns and the next floor's loot. It reads only simulation state, so a
+//! journal that records the run's director flag replays the same nudges.
+
+use super::*;
+use crate::content::WanderingSpawnRate;
+use crate::state::{DirectorState, MAX_DIRECTOR_PRESSURE};
+
+/// Change in wandering-spawn chance per pressure step, in percent of the base chance.
+const WANDERING_CHANCE_STEP_PERCENT: i32 = 25;
+/// Change in the floor loot budget per pressure step, in percent of the base budget.
+const LOOT_BUDGET_STEP_PERCENT: i32 = 25;
+
+impl Game {
+    /// Turn the adaptive director on or off for this run. Call before the first advance;
+    /// replays apply the journal's flag at the same point.
+    pub fn set_director_enabled(&mut self, enabled: bool) {
+        let hp = self.state.actors[self.state.player_id].hp;
+        self.state.director = if enabled {
+            DirectorState { enabled, entry_hp: hp, lowest_hp: hp, ..DirectorState::default() }
+        } else {
+            DirectorState::default()
+        };
+    }
+
+    pub fn director_enabled(&self) -> bool {
+        self.state.director.enabled
+    }
+
+    /// Track damage and close calls for the current floor; runs once per tick.
+    pub(super) fn observe_director_tick(&mut self) {
+        if !self.state.director.enabled {
+            return;
+        }
+        let hp = self.state.actors[self.state.player_id].hp;
+        let retreating =
+            self.state.threat_trace.front().is_some_and(|trace| trace.retreat_triggered);
+        let director = &mut self.state.director;
+        director.lowest_hp = director.lowest_hp.min(hp);
+        if retreating && !director.in_close_call {
+            director.close_calls = director.close_calls.saturating_add(1);
+        }
+        director.in_close_call = retreating;
+    }
+
+    /// Judge the floor being left and start tracking the next one.
+    /// Heavy damage or a close call eases off; an untouched floor pushes harder.
+    pub(super) fn settle_director_floor(&mut self) {
+        if !self.state.director.enabled {
+            return;
+        }
+        let player = &self.state.actors[self.state.player_id];
+        let (hp, max_hp) = (player.hp, player.max_hp.max(1));
+        let director = &mut self.state.director;
+        let damage_taken = (director.entry_hp - director.lowest_hp).max(0);
+        let step = if director.close_calls > 0 || damage_taken * 2 >= max_hp {
+            -1
+        } else if damage_taken * 5 < max_hp {
+            1
+        } else {
+            0
+        };
+        *director = DirectorState {
+            enabled: true,
+            pressure: (director.pressure + step)
+                .clamp(-MAX_DIRECTOR_PRESSURE, MAX_DIRECTOR_PRESSURE),
+            entry_hp: hp,
+            lowest_hp: hp,
+            close_calls: 0,
+            in_close_call: false,
+        };
+    }
+
+    /// Wandering-spawn pacing after the director's nudge; unchanged while it is off.
+    pub(super) fn director_wandering_rate(&self) -> WanderingSpawnRate {
+        let base = self.wandering_spawns;
+        let pressure = i32::from(self.state.director.pressure);
+        if !self.state.director.enabled || pressure == 0 || base.max_per_floor == 0 {
+            return base;
+        }
+        let chance = base.chance_percent as i32 * (100 + WANDERING_CHANCE_STEP_PERCENT * pressure);
+        let max_per_floor = (i32::from(base.max_per_floor) + pressure).clamp(1, i32::from(u8::MAX));
+        WanderingSpawnRate {
+            chance_percent: (chance / 100).clamp(0, 100) as u32,
+            max_per_floor: max_per_floor as u8,
+            ..base
+        }
+    }
+
+    /// Loot budget for the next generated floor, in percent; a struggling player finds more.
+    pub(super) fn director_loot_percent(&self) -> u32 {
+        if !self.state.director.enabled {
+            return 100;
+        }
+        (100 - LOOT_BUDGET_STEP_PERCENT * i32::from(self.state.director.pressure)) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    fn director_game() -> Game {
+        let mut game = Game::new(2716, &ContentPack::default(), GameMode::Ironman);
+        game.set_director_enabled(true);
+        game
+    }
+
+    #[test]
+    fn clean_floors_push_harder_and_close_calls_ease_off() {
+        let mut game = director_game();
+        let base = game.wandering_spawns;
+        for _ in 0..3 {
+            game.settle_director_floor();
+        }
+        assert_eq!(game.state.director.pressure, MAX_DIRECTOR_PRESSURE);
+        let pushed = game.director_wandering_rate();
+        assert!(pushed.chance_percent > base.chance_percent);
+        assert_eq!(pushed.max_per_floor, base.max_per_floor + 2);
+        assert_eq!(game.director_loot_percent(), 50);
+
+        let player = game.state.player_id;
+        game.state.threat_trace.push_front(ThreatTrace {
+            tick: 1,
+            visible_enemy_count: 1,
+            min_enemy_distance: Some(1),
+            retreat_triggered: true,
+        });
+        game.observe_director_tick();
+        game.observe_director_tick();
+        assert_eq!(game.state.director.close_calls, 1, "one scrape counts once");
+        game.settle_director_floor();
+        assert_eq!(game.state.director.pressure, 1);
+
+        game.state.actors[player].hp -= game.state.actors[player].max_hp / 2;
+        game.observe_director_tick();
+        game.settle_director_floor();
+        assert_eq!(game.state.director.pressure, 0);
+        assert_eq!(game.director_wandering_rate(), base);
+    }
+
+    #[test]
+    fn a_disabled_director_never_nudges() {
+        let mut game = Game::new(2716, &ContentPack::default(), GameMode::Ironman);
+        for _ in 0..3 {
+            game.settle_director_floor();
+        }
+        assert_eq!(game.state.director, DirectorState::default());
+        assert_eq!(game.director_wandering_rate(), game.wandering_spawns);
+        assert_eq!(game.director_loot_percent(), 100);
+    }
+}
@@ -0,0 +1,98 @@
+//! Enemy memory: where each enemy was last seen, kept after it leaves sight so a smoke bomb
+//! or a corner does not wipe the player's picture of the floor. Entries fade after
+//! `ContentPack::threat_memory_ticks` and are forgotten on death or a floor change.
+
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// One enemy's last sighting.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub(super) struct EnemySighting {
+    kind: ActorKind,
+    pos: Pos,
+    tick: u64,
+}
+
+impl Game {
+    /// Note every visible enemy's position and forget the dead and the faded.
+    pub(super) fn remember_enemies(&mut self) {
+        let map = &self.state.map;
+        for (id, actor) in &self.state.actors {
+            if id != self.state.player_id && map.is_visible(actor.pos) {
+                let sighting = EnemySighting { kind: actor.kind, pos: actor.pos, tick: self.tick };
+                self.enemy_memory.insert(id, sighting);
+            }
+        }
+        let (actors, now, span) = (&self.state.actors, self.tick, self.threat_memory_ticks);
+        self.enemy_memory
+            .retain(|id, sighting| actors.contains_key(*id) && now < sighting.tick + span);
+    }
+
+    /// Remembered enemies the player cannot see now, at tiles they cannot see either.
+    pub fn last_known_threats(&self) -> Vec<LastKnownThreat> {
+        let map = &self.state.map;
+        let span = self.threat_memory_ticks.max(1);
+        self.enemy_memory
+            .iter()
+            .filter(|(id, sighting)| {
+                let in_sight = self.state.actors.get(**id).is_some_and(|a| map.is_visible(a.pos));
+                !in_sight && !map.is_visible(sighting.pos)
+            })
+            .map(|(_, sighting)| {
+                let left = (sighting.tick + span).saturating_sub(self.tick);
+                LastKnownThreat {
+                    kind: sighting.kind,
+                    pos: sighting.pos,
+                    seen_tick: sighting.tick,
+                    fade_percent: (left * 100 / span).min(100) as u8,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+    use crate::game::floor_transition::insert_enemy;
+    use crate::game::test_support::MapBuilder;
+
+    #[test]
+    fn smoke_clears_the_trace_but_the_last_known_threat_fades_over_ticks() {
+        let mut game = Game::new(2737, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(9, 5).discover_all().build();
+        let player = Pos { y: 2, x: 1 };
+        game.state.actors[game.state.player_id].pos = player;
+        game.refresh_fov(player);
+        let seen_at = Pos { y: 2, x: 6 };
+        let enemy = insert_enemy(&mut game, ActorKind::Goblin, seen_at, 999);
+        game.state.threat_trace.push_front(ThreatTrace {
+            tick: 0,
+            visible_enemy_count: 1,
+            min_enemy_distance: Some(5),
+            retreat_triggered: false,
+        });
+
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_SMOKE_BOMB));
+        assert!(game.state.threat_trace.is_empty());
+        assert!(game.last_known_threats().is_empty(), "the goblin is still in sight");
+
+        game.state.actors[enemy].pos = Pos { y: 3, x: 7 };
+        game.state.map.clear_visible();
+        game.state.map.set_visible(player, true);
+        let threats = game.last_known_threats();
+        assert_eq!(threats.len(), 1);
+        assert_eq!((threats[0].kind, threats[0].pos), (ActorKind::Goblin, seen_at));
+        assert_eq!(threats[0].fade_percent, 100);
+
+        game.tick += 30;
+        game.remember_enemies();
+        assert_eq!(game.last_known_threats()[0].fade_percent, 50);
+        game.tick += 30;
+        game.remember_enemies();
+        assert!(game.last_known_threats().is_empty(), "the memory has faded");
+    }
+}
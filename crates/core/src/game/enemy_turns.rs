@@ -0,0 +1,261 @@
+//! Enemy turn scheduling: each enemy acts when the clock reaches its `next_action_tick`.
+//! Turn length scales with actor speed, so fast enemies act more often than slow ones.
+//! It does not own encounters; alert enemies close distance, then strike once the player is
+//! within reach: beside them for melee kinds, along a clear line for archers.
+//! Enemies that lose track of the player, or are pulled beyond their leash, walk back home.
+
+use std::collections::BTreeSet;
+
+use super::visibility::has_direct_line_of_sight;
+use super::*;
+use crate::content::{BASE_ACTION_SPEED, attack_reach};
+use crate::state::Actor;
+
+/// Ticks one enemy turn takes for an actor at `BASE_ACTION_SPEED`.
+const ENEMY_TURN_TICKS: u32 = 10;
+/// Longest path, in steps, over which an alert enemy closes in on the player.
+/// Farther enemies hold position so pursuit does not crowd the run with extra encounters.
+const PURSUIT_STEPS: usize = 2;
+
+/// Ticks between turns for an enemy moving at `speed`; every turn costs at least one tick.
+pub(super) fn enemy_turn_ticks(speed: u32) -> u64 {
+    u64::from((ENEMY_TURN_TICKS * BASE_ACTION_SPEED).div_ceil(speed.max(1)).max(1))
+}
+
+impl Game {
+    /// Give a turn to every enemy whose next action is due this tick.
+    /// Alert enemies with the player in reach strike it; those within pursuit range step one tile
+    /// toward it; enemies beyond their leash forget the player, and any enemy that is not alert
+    /// steps back toward its home tile.
+    pub(super) fn step_enemy_turns(&mut self) {
+        let mut enemy_ids: Vec<EntityId> =
+            self.state.actors.keys().filter(|id| *id != self.state.player_id).collect();
+        enemy_ids.sort_by_key(|id| ordering::enemy_turn_key(&self.state.actors[*id]));
+
+        for enemy_id in enemy_ids {
+            if self.tick < self.state.actors[enemy_id].next_action_tick {
+                continue;
+            }
+            let Actor { kind, pos, home, .. } = self.state.actors[enemy_id];
+            if manhattan(pos, home) > self.aggro_range(kind).leash_radius {
+                self.state.actors[enemy_id].awareness = 0;
+            }
+            if self.state.actors[enemy_id].awareness_state() != AwarenessState::Alert {
+                self.step_enemy_toward(enemy_id, home);
+            } else {
+                let player_pos = self.state.actors[self.state.player_id].pos;
+                let in_reach = self.enemy_can_strike(enemy_id, player_pos);
+                if !in_reach {
+                    self.step_enemy_toward_player(enemy_id);
+                }
+                if manhattan(player_pos, self.state.actors[enemy_id].pos) == 1 {
+                    self.trigger_brace_counter(enemy_id);
+                }
+                if !self.state.actors.contains_key(enemy_id) {
+                    continue;
+                }
+                if in_reach {
+                    self.enemy_strike(enemy_id);
+                }
+            }
+            let now = self.tick;
+            let enemy = &mut self.state.actors[enemy_id];
+            enemy.next_action_tick =
+                enemy.next_action_tick.max(now) + enemy_turn_ticks(enemy.speed);
+        }
+    }
+
+    /// Whether `enemy` can hit `target` from where it stands: from an adjacent tile, or for
+    /// kinds that shoot, from within their reach along a line no wall or closed door blocks.
+    pub(in crate::game) fn enemy_can_strike(&self, enemy_id: EntityId, target: Pos) -> bool {
+        let Actor { kind, pos, .. } = self.state.actors[enemy_id];
+        let distance = manhattan(pos, target);
+        distance == 1
+            || (distance <= attack_reach(kind)
+                && has_direct_line_of_sight(&self.state.map, pos, target))
+    }
+
+    /// `enemy`, with the player in reach at the start of its turn, strikes instead of moving.
+    fn enemy_strike(&mut self, enemy_id: EntityId) {
+        let damage = self.enemy_strike_damage(enemy_id);
+        let Actor { kind, pos, .. } = self.state.actors[enemy_id];
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let source =
+            if manhattan(pos, player_pos) > 1 { DamageSource::Ranged } else { DamageSource::Melee };
+        self.log.push(LogEvent::EnemyStruck { enemy: enemy_id, damage });
+        self.damage_player(damage, source, Some(kind));
+    }
+
+    fn step_enemy_toward(&mut self, enemy_id: EntityId, goal: Pos) {
+        let enemy_pos = self.state.actors[enemy_id].pos;
+        let sanctuary = self.state.sanctuary_active.then_some(self.state.sanctuary_tile);
+        let Some(next_step) = enemy_path_to_player(&self.state.map, enemy_pos, goal, sanctuary)
+            .and_then(|path| path.first().copied())
+        else {
+            return;
+        };
+        if self.state.actors.values().all(|actor| actor.pos != next_step) {
+            self.state.actors[enemy_id].pos = next_step;
+        }
+    }
+
+    fn step_enemy_toward_player(&mut self, enemy_id: EntityId) {
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let enemy_pos = self.state.actors[enemy_id].pos;
+        let sanctuary = self.state.sanctuary_active.then_some(self.state.sanctuary_tile);
+        let Some(path) = enemy_path_to_player(&self.state.map, enemy_pos, player_pos, sanctuary)
+        else {
+            return;
+        };
+        let Some(next_step) = path.first().copied().filter(|step| *step != player_pos) else {
+            return;
+        };
+        if path.len() > PURSUIT_STEPS {
+            return;
+        }
+        let occupied: BTreeSet<Pos> = self.state.actors.values().map(|actor| actor.pos).collect();
+        if !occupied.contains(&next_step) {
+            self.state.actors[enemy_id].pos = next_step;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, get_enemy_stats};
+    use crate::game::floor_transition::insert_enemy;
+    use crate::game::test_support::MapBuilder;
+    use crate::state::MAX_AWARENESS;
+
+    fn open_game() -> Game {
+        let mut game = Game::new(2704, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(20, 9).discover_all().build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 4, x: 1 };
+        game
+    }
+
+    fn count_turns(game: &mut Game, enemies: &[EntityId], window: u64) -> Vec<u32> {
+        let mut turns = vec![0; enemies.len()];
+        for _ in 0..window {
+            let before: Vec<u64> =
+                enemies.iter().map(|id| game.state.actors[*id].next_action_tick).collect();
+            game.tick += 1;
+            game.step_enemy_turns();
+            for (i, id) in enemies.iter().enumerate() {
+                if game.state.actors[*id].next_action_tick != before[i] {
+                    turns[i] += 1;
+                }
+            }
+        }
+        turns
+    }
+
+    #[test]
+    fn fast_enemies_take_more_turns_than_slow_ones() {
+        let mut game = open_game();
+        let enemies: Vec<EntityId> =
+            [ActorKind::FeralHound, ActorKind::Goblin, ActorKind::LivingArmor]
+                .into_iter()
+                .enumerate()
+                .map(|(i, kind)| {
+                    let pos = Pos { y: 2 * i as i32 + 2, x: 18 };
+                    insert_enemy(&mut game, kind, pos, 0)
+                })
+                .collect();
+
+        let turns = count_turns(&mut game, &enemies, 140);
+        assert_eq!(turns, vec![20, 16, 7]);
+        assert_eq!(enemy_turn_ticks(get_enemy_stats(ActorKind::FeralHound).speed), 7);
+        assert_eq!(enemy_turn_ticks(get_enemy_stats(ActorKind::LivingArmor).speed), 20);
+    }
+
+    #[test]
+    fn alert_enemies_close_in_at_their_own_pace() {
+        let mut game = open_game();
+        let hound = insert_enemy(&mut game, ActorKind::FeralHound, Pos { y: 3, x: 2 }, 7);
+        let armor = insert_enemy(&mut game, ActorKind::LivingArmor, Pos { y: 5, x: 2 }, 20);
+        let far = insert_enemy(&mut game, ActorKind::FeralHound, Pos { y: 4, x: 8 }, 7);
+        for id in [hound, armor, far] {
+            game.state.actors[id].awareness = MAX_AWARENESS;
+        }
+
+        count_turns(&mut game, &[hound, armor, far], 10);
+        let player_pos = game.state.actors[game.state.player_id].pos;
+        let distance = |id: EntityId| manhattan(player_pos, game.state.actors[id].pos);
+        assert_eq!(distance(hound), 1, "the hound's turn comes first and it closes in");
+        assert_eq!(distance(armor), 2, "the armor is still waiting for its turn");
+        assert_eq!(game.state.actors[far].pos, Pos { y: 4, x: 8 }, "pursuit range is short");
+    }
+
+    #[test]
+    fn alert_enemies_beside_the_player_strike_on_their_turn() {
+        let mut game = open_game();
+        let goblin = insert_enemy(&mut game, ActorKind::Goblin, Pos { y: 4, x: 2 }, 1);
+        let hound = insert_enemy(&mut game, ActorKind::FeralHound, Pos { y: 2, x: 1 }, 1);
+        game.state.actors[goblin].awareness = MAX_AWARENESS;
+        game.state.actors[hound].awareness = MAX_AWARENESS;
+        let hp = game.state.actors[game.state.player_id].hp;
+        let damage = game.enemy_strike_damage(goblin);
+
+        count_turns(&mut game, &[goblin, hound], 1);
+        assert_eq!(game.state.actors[game.state.player_id].hp, hp - damage);
+        assert!(game.log.contains(&LogEvent::EnemyStruck { enemy: goblin, damage }));
+        assert_eq!(game.state.actors[hound].pos, Pos { y: 3, x: 1 }, "it closes in instead");
+    }
+
+    #[test]
+    fn enemies_pulled_beyond_their_leash_forget_the_player_and_walk_home() {
+        let mut game = open_game();
+        let guard = insert_enemy(&mut game, ActorKind::CorruptedGuard, Pos { y: 4, x: 8 }, 1);
+        game.state.actors[guard].home = Pos { y: 4, x: 13 };
+        game.state.actors[guard].awareness = MAX_AWARENESS;
+        assert!(!game.enemy_sees(guard, Pos { y: 4, x: 7 }), "leashed enemies see nothing");
+
+        count_turns(&mut game, &[guard], 1);
+        assert_eq!(game.state.actors[guard].awareness, 0);
+        assert_eq!(game.state.actors[guard].pos, Pos { y: 4, x: 9 });
+        count_turns(&mut game, &[guard], 60);
+        assert_eq!(game.state.actors[guard].pos, Pos { y: 4, x: 13 }, "it stops once home");
+    }
+
+    #[test]
+    fn archers_shoot_along_a_clear_line_but_not_through_walls() {
+        let mut game = open_game();
+        let player_pos = game.state.actors[game.state.player_id].pos;
+        let archer = insert_enemy(&mut game, ActorKind::BoneArcher, Pos { y: 4, x: 5 }, 1);
+        game.state.actors[archer].awareness = MAX_AWARENESS;
+        let hp = game.state.actors[game.state.player_id].hp;
+        let damage = game.enemy_strike_damage(archer);
+
+        count_turns(&mut game, &[archer], 1);
+        assert_eq!(game.state.actors[game.state.player_id].hp, hp - damage);
+        assert_eq!(game.state.actors[archer].pos, Pos { y: 4, x: 5 }, "it shoots from range");
+
+        game.state.map.set_tile(Pos { y: 4, x: 3 }, TileKind::Wall);
+        assert!(!game.enemy_can_strike(archer, player_pos));
+        count_turns(&mut game, &[archer], 10);
+        assert_eq!(game.state.actors[game.state.player_id].hp, hp - damage);
+    }
+
+    #[test]
+    fn an_alert_archer_in_reach_raises_an_encounter_with_a_ranged_threat() {
+        let mut game = open_game();
+        let archer = insert_enemy(&mut game, ActorKind::BoneArcher, Pos { y: 4, x: 4 }, 50);
+        game.state.actors[archer].awareness = MAX_AWARENESS;
+
+        match game.advance(1).stop_reason {
+            AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter {
+                primary_enemy,
+                threat,
+                ..
+            }) => {
+                assert_eq!(primary_enemy, archer);
+                assert_eq!(threat.danger_tags, vec![DangerTag::Ranged]);
+            }
+            other => panic!("expected an encounter at range, got {other:?}"),
+        }
+    }
+}
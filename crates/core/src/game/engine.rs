@@ -4,8 +4,10 @@
 use super::*;
 
 mod advance;
+mod budgeted;
 mod encounters;
 mod intent;
+mod prompt_batch;
 
 #[cfg(test)]
 mod tests;
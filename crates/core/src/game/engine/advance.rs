@@ -1,88 +1,146 @@
 //! Per-tick simulation loop and stop-reason handling for the game engine.
 
 use super::*;
+use crate::content::ActionKind;
 
 impl Game {
     pub fn advance(&mut self, max_steps: u32) -> AdvanceResult {
+        self.advance_tally = AdvanceBreakdown::default();
+        let mut result = self.advance_steps(max_steps);
+        result.breakdown = self.advance_tally;
+        self.debug_check_invariants();
+        result
+    }
+
+    fn advance_steps(&mut self, max_steps: u32) -> AdvanceResult {
         self.at_pause_boundary = false;
         let mut steps = 0;
         if let Some(outcome) = self.finished_outcome {
-            return AdvanceResult {
-                simulated_ticks: 0,
-                stop_reason: AdvanceStopReason::Finished(outcome),
-            };
+            return AdvanceResult::new(0, AdvanceStopReason::Finished(outcome));
         }
         if let Some(prompt) = self.pending_prompt.clone() {
-            return AdvanceResult {
-                simulated_ticks: 0,
-                stop_reason: AdvanceStopReason::Interrupted(self.prompt_to_interrupt(prompt)),
-            };
+            let interrupt = self.prompt_to_interrupt(prompt);
+            return AdvanceResult::new(0, AdvanceStopReason::Interrupted(interrupt));
         }
 
         while steps < max_steps {
             if self.pause_requested {
                 self.pause_requested = false;
                 self.at_pause_boundary = true;
-                return AdvanceResult {
-                    simulated_ticks: steps,
-                    stop_reason: AdvanceStopReason::PausedAtBoundary { tick: self.tick },
-                };
+                let tick = self.tick;
+                return AdvanceResult::new(steps, AdvanceStopReason::PausedAtBoundary { tick });
             }
 
+            self.update_floor_objective();
             let player_pos = self.state.actors[self.state.player_id].pos;
-            if self.state.map.tile_at(player_pos) == TileKind::DownStairs {
-                return self.interrupt_floor_transition(steps);
-            }
-            if self.state.sanctuary_active && player_pos == self.state.sanctuary_tile {
-                self.suppressed_enemy = None;
-            } else {
-                self.clear_stale_suppressed_enemy(player_pos);
-                let adjacent = self.find_adjacent_enemy_ids(player_pos);
-                if let Some(primary_enemy) = adjacent.first().copied() {
-                    self.log.push(LogEvent::EnemyEncountered { enemy: primary_enemy });
-                    return self.interrupt_enemy(adjacent, primary_enemy, steps);
-                }
-            }
-            if let Some(item_id) = self.find_item_at(player_pos) {
-                return self.interrupt_loot(item_id, steps);
+            if let Some(result) = self.raise_due_prompts(player_pos, steps) {
+                return result;
             }
 
-            self.plan_auto_intent(player_pos);
+            let player_waiting = self.state.wait_ticks_remaining > 0;
+            // Drinking a stowed potion takes the player's free action, ahead of any move.
+            let player_busy = self.player_is_busy() || (!player_waiting && self.try_auto_heal());
+            let player_idle = !player_busy && !player_waiting;
             let mut player_moved = false;
+            if player_waiting {
+                self.state.wait_ticks_remaining -= 1;
+            }
+            let manual_step = if player_idle { self.state.manual_step.take() } else { None };
+            if player_idle && manual_step.is_none() {
+                self.plan_auto_intent(player_pos);
+                if self.path_cache.yielded() {
+                    // The steps before planning are idempotent, so the next call reruns this tick whole.
+                    return AdvanceResult::new(steps, AdvanceStopReason::BudgetExhausted);
+                }
+            }
 
-            if let Some(intent) = self.state.auto_intent
-                && intent.path_len > 0
-                && let Some(path) = path_for_intent(&self.state.map, player_pos, intent)
-                && let Some(next_step) = path.first().copied()
-            {
+            let next_step = match manual_step {
+                // The tile was open when the step was queued; skip it if someone moved in since.
+                Some(direction) => {
+                    Some(direction.step_from(player_pos)).filter(|pos| self.is_open_step(*pos))
+                }
+                None if player_idle => self.auto_next_step(player_pos),
+                None => None,
+            };
+            if let Some(next_step) = next_step {
                 if self.state.map.tile_at(next_step) == TileKind::ClosedDoor {
                     return self.interrupt_door(next_step, steps);
                 }
+                if let Some(room) = self.state.map.room_at(next_step)
+                    && self.state.map.room_at(player_pos) != Some(room)
+                {
+                    self.log.push(LogEvent::RoomEntered { room });
+                    if let Some(vault) = self.state.map.vault(room) {
+                        let template = vault.template;
+                        self.log.push(LogEvent::VaultEntered { template, room });
+                    }
+                }
                 self.state.actors[self.state.player_id].pos = next_step;
-                let radius = self.get_fov_radius();
-                compute_fov(&mut self.state.map, next_step, radius);
+                self.refresh_fov(next_step);
                 player_moved = true;
+                self.state.braced = false;
+                self.spend_player_action(ActionKind::Move);
             }
 
             self.tick += 1;
             steps += 1;
+            self.step_light();
             self.record_threat_trace();
+            self.record_player_visit();
+            self.observe_director_tick();
+            self.update_enemy_awareness();
+            self.step_enemy_turns();
+            self.step_spreading_hazards();
+            self.step_tremors();
+            self.step_wandering_spawns();
+            self.remember_enemies();
+            if let Some(outcome) = self.finished_outcome {
+                return AdvanceResult::new(steps, AdvanceStopReason::Finished(outcome));
+            }
 
             if player_moved {
+                self.advance_tally.ticks_moved += 1;
+            } else if player_busy {
+                self.advance_tally.ticks_busy += 1;
+            } else if player_waiting {
+                self.advance_tally.ticks_waiting += 1;
+            } else {
+                self.advance_tally.ticks_idle += 1;
+            }
+            // A deliberate wait is progress: only idleness nobody asked for counts as a stall.
+            if player_moved || player_busy || player_waiting {
                 self.no_progress_ticks = 0;
             } else {
                 self.no_progress_ticks = self.no_progress_ticks.saturating_add(1);
                 if self.no_progress_ticks >= MAX_NO_PROGRESS_TICKS {
-                    return AdvanceResult {
-                        simulated_ticks: steps,
-                        stop_reason: AdvanceStopReason::EngineFailure(
-                            EngineFailureReason::StalledNoProgress,
-                        ),
-                    };
+                    let diagnostics = self.engine_diagnostics();
+                    return AdvanceResult::new(
+                        steps,
+                        AdvanceStopReason::EngineFailure(EngineFailureReason::StalledNoProgress(
+                            diagnostics,
+                        )),
+                    );
                 }
             }
+            if manual_step.is_some() {
+                // The step's own prompts come first; otherwise hand control straight back.
+                self.update_floor_objective();
+                let player_pos = self.state.actors[self.state.player_id].pos;
+                if let Some(result) = self.raise_due_prompts(player_pos, steps) {
+                    return result;
+                }
+                self.at_pause_boundary = true;
+                let tick = self.tick;
+                return AdvanceResult::new(steps, AdvanceStopReason::PausedAtBoundary { tick });
+            }
         }
-        AdvanceResult { simulated_ticks: steps, stop_reason: AdvanceStopReason::BudgetExhausted }
+        AdvanceResult::new(steps, AdvanceStopReason::BudgetExhausted)
+    }
+
+    /// The next tile on the current auto-explore route, if the planner has one.
+    fn auto_next_step(&mut self, player_pos: Pos) -> Option<Pos> {
+        let intent = self.state.auto_intent.filter(|intent| intent.path_len > 0)?;
+        self.route_auto_intent(player_pos, intent)?.first().copied()
     }
 
     fn record_threat_trace(&mut self) {
@@ -94,6 +152,7 @@ impl Game {
                 *id != self.state.player_id && self.state.map.is_visible(actor.pos)
             })
             .count();
+        let visible_enemy_count = u16::try_from(visible_enemy_count).unwrap_or(u16::MAX);
         let min_enemy_distance = self
             .state
             .actors
@@ -110,14 +169,14 @@ impl Game {
             / self.state.actors[self.state.player_id].max_hp;
         let retreat_triggered = player_hp_pct <= (self.state.policy.retreat_hp_threshold as i32)
             && visible_enemy_count > 0;
+        // Make room first, so the deque never allocates past its depth.
+        self.state.threat_trace.truncate(self.threat_trace_depth.saturating_sub(1));
         self.state.threat_trace.push_front(ThreatTrace {
             tick: self.tick,
             visible_enemy_count,
             min_enemy_distance,
             retreat_triggered,
         });
-        if self.state.threat_trace.len() > 32 {
-            self.state.threat_trace.pop_back();
-        }
+        self.state.threat_trace.truncate(self.threat_trace_depth);
     }
 }
@@ -0,0 +1,59 @@
+//! Budgeted advancement that keeps a frame within its time: it yields between ticks once the
+//! deadline passes, and yields mid-search once the planner's A* has spent its expansion cap.
+//! A yielded search resumes on the next call, so budgeted and unbudgeted runs reach identical
+//! states. Field-of-view updates are bounded by the sight radius and always run whole.
+
+use std::time::Instant;
+
+use super::*;
+
+/// A* expansions the planner may spend in one budgeted call, summed across its searches.
+const PLANNER_EXPANSIONS_PER_CALL: u32 = 2_048;
+
+impl Game {
+    /// Advance up to `max_steps` ticks, yielding with `BudgetExhausted` once `deadline`
+    /// passes or the planner has spent `PLANNER_EXPANSIONS_PER_CALL` expansions.
+    ///
+    /// The deadline is checked between ticks, so at least one tick runs per call unless a long
+    /// route search yields first; its progress is kept and the search resumes next call.
+    pub fn advance_until(&mut self, max_steps: u32, deadline: Instant) -> AdvanceResult {
+        self.advance_within(max_steps, deadline, PLANNER_EXPANSIONS_PER_CALL)
+    }
+
+    pub(in crate::game) fn advance_within(
+        &mut self,
+        max_steps: u32,
+        deadline: Instant,
+        max_expansions: u32,
+    ) -> AdvanceResult {
+        self.path_cache.set_expansion_budget(Some(max_expansions));
+        let result = self.advance_ticks_until(max_steps, deadline);
+        self.path_cache.set_expansion_budget(None);
+        result
+    }
+
+    fn advance_ticks_until(&mut self, max_steps: u32, deadline: Instant) -> AdvanceResult {
+        let mut simulated_ticks = 0;
+        let mut breakdown = AdvanceBreakdown::default();
+        while simulated_ticks < max_steps {
+            let result = self.advance(1);
+            simulated_ticks += result.simulated_ticks;
+            breakdown += result.breakdown;
+            if !matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted) {
+                return AdvanceResult {
+                    simulated_ticks,
+                    stop_reason: result.stop_reason,
+                    breakdown,
+                };
+            }
+            if self.path_cache.yielded() || Instant::now() >= deadline {
+                break;
+            }
+        }
+        AdvanceResult {
+            simulated_ticks,
+            stop_reason: AdvanceStopReason::BudgetExhausted,
+            breakdown,
+        }
+    }
+}
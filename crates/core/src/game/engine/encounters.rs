@@ -7,7 +7,9 @@ impl Game {
         self.state.items.iter().find(|(_, item)| item.pos == pos).map(|(id, _)| id)
     }
 
-    pub(super) fn find_adjacent_enemy_ids(&self, pos: Pos) -> Vec<EntityId> {
+    /// Enemies that would raise an encounter at `pos`: those beside it, and alert archers with
+    /// a clear shot from within their reach.
+    pub(super) fn find_engaging_enemy_ids(&self, pos: Pos) -> Vec<EntityId> {
         let enemies: Vec<EntityId> = self
             .state
             .actors
@@ -19,11 +21,13 @@ impl Game {
                 let sanctuary = self.state.sanctuary_active.then_some(self.state.sanctuary_tile);
                 enemy_path_to_player(&self.state.map, actor.pos, pos, sanctuary).is_some()
             })
+            .filter(|(id, _)| Some(*id) != self.suppressed_enemy && *id != self.state.player_id)
             .filter(|(id, actor)| {
-                Some(*id) != self.suppressed_enemy
-                    && *id != self.state.player_id
-                    && manhattan(pos, actor.pos) == 1
+                manhattan(pos, actor.pos) == 1
+                    || (actor.awareness_state() == AwarenessState::Alert
+                        && self.enemy_can_strike(*id, pos))
             })
+            .filter(|(id, _)| !self.can_sneak_past(*id))
             .map(|(id, _)| id)
             .collect();
         self.sort_adjacent_enemies_by_policy(pos, enemies)
@@ -34,7 +38,7 @@ impl Game {
             return;
         };
         let should_clear = match self.state.actors.get(enemy_id) {
-            Some(actor) => manhattan(player_pos, actor.pos) != 1,
+            Some(_) => !self.enemy_can_strike(enemy_id, player_pos),
             None => true,
         };
         if should_clear {
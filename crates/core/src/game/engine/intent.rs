@@ -1,36 +1,141 @@
 //! Auto-explore intent upkeep for each simulation step.
 
 use super::*;
+use crate::content::keys;
+
+/// Consumables a Rush route may detour for once the player knows what they are.
+const HEALING_POTIONS: [&str; 2] = [keys::CONSUMABLE_MINOR_HP_POT, keys::CONSUMABLE_MAJOR_HP_POT];
 
 impl Game {
+    /// Keep, refresh or replace the auto-explore intent. If a budgeted route search runs out
+    /// first, nothing changes and the path cache reports the yield, so the tick can rerun.
     pub fn plan_auto_intent(&mut self, player_pos: Pos) {
+        self.path_cache.begin_planning();
+        if let Some(intent) = self.rush_intent(player_pos) {
+            self.set_auto_intent(player_pos, Some(intent));
+            return;
+        }
         let mut needs_replan = true;
         if let Some(intent) = self.state.auto_intent {
             if player_pos == intent.target {
                 needs_replan = true;
-            } else if is_intent_target_still_valid(&self.state.map, intent)
-                && let Some(path) = path_for_intent(&self.state.map, player_pos, intent)
-            {
-                let new_len = path.len() as u16;
-                if new_len != intent.path_len {
-                    self.state.auto_intent =
-                        Some(AutoExploreIntent { path_len: new_len, ..intent });
+            } else if self.is_intent_still_valid(intent) {
+                let route = self.route_auto_intent(player_pos, intent);
+                if self.path_cache.yielded() {
+                    return;
+                }
+                if let Some(path) = route {
+                    let new_len = path.len() as u16;
+                    if new_len != intent.path_len {
+                        self.state.auto_intent =
+                            Some(AutoExploreIntent { path_len: new_len, ..intent });
+                    }
+                    needs_replan = false;
                 }
-                needs_replan = false;
             }
         }
         if needs_replan {
-            let next_intent = choose_frontier_intent(&self.state.map, player_pos);
-            let changed = self.state.auto_intent.map(|intent| intent.reason)
-                != next_intent.map(|intent| intent.reason);
-            if changed && let Some(intent) = next_intent {
-                self.log.push(LogEvent::AutoReasonChanged {
-                    reason: intent.reason,
-                    target: intent.target,
-                    path_len: intent.path_len,
+            let visible_loot = self.visible_loot();
+            let aggression = self.state.policy.resource_aggression;
+            let map = &self.state.map;
+            // A snoozed door is only worth a fresh prompt when nothing else is left to do.
+            let mut next_intent = choose_frontier_intent_skipping(
+                map,
+                player_pos,
+                &visible_loot,
+                aggression,
+                &self.snoozed_doors(),
+            )
+            .or_else(|| choose_frontier_intent(map, player_pos, &visible_loot, aggression));
+            if self.stairs_locked()
+                && next_intent.is_none_or(|intent| {
+                    self.state.map.tile_at(intent.target) == TileKind::DownStairs
+                })
+            {
+                next_intent = self.objective_target().and_then(|target| {
+                    choose_objective_intent(&self.state.map, player_pos, target)
                 });
             }
-            self.state.auto_intent = next_intent;
+            if self.set_auto_intent(player_pos, next_intent) {
+                self.advance_tally.replans += 1;
+            }
+        }
+    }
+
+    /// Replace the current intent, logging when the reason behind it changes. The new route
+    /// is searched first, so a budgeted search that runs out leaves the old intent in place;
+    /// false in that case.
+    fn set_auto_intent(&mut self, player_pos: Pos, next_intent: Option<AutoExploreIntent>) -> bool {
+        if let Some(intent) = next_intent.filter(|intent| intent.path_len > 0) {
+            self.route_auto_intent(player_pos, intent);
+            if self.path_cache.yielded() {
+                return false;
+            }
+        }
+        let changed = self.state.auto_intent.map(|intent| intent.reason)
+            != next_intent.map(|intent| intent.reason);
+        if changed && let Some(intent) = next_intent {
+            self.log.push(LogEvent::AutoReasonChanged {
+                reason: intent.reason,
+                target: intent.target,
+                path_len: intent.path_len,
+            });
+        }
+        self.state.auto_intent = next_intent;
+        true
+    }
+
+    /// Visible loot auto-explore may walk to; snoozed loot is left alone.
+    fn visible_loot(&self) -> Vec<Pos> {
+        let map = &self.state.map;
+        self.state
+            .items
+            .values()
+            .map(|item| item.pos)
+            .filter(|pos| map.is_visible(*pos) && !self.is_snoozed(SnoozeTarget::Loot(*pos)))
+            .collect()
+    }
+
+    /// The Rush waypoint, replanned every step, once the policy asks for Rush and the open
+    /// down stairs are known; `None` falls back to ordinary exploration.
+    fn rush_intent(&self, player_pos: Pos) -> Option<AutoExploreIntent> {
+        if self.state.policy.exploration_mode != ExploreMode::Rush || self.stairs_locked() {
+            return None;
+        }
+        let map = &self.state.map;
+        let stairs = map
+            .positions()
+            .find(|pos| map.is_discovered(*pos) && map.tile_at(*pos) == TileKind::DownStairs)?;
+        let loot = self.visible_loot();
+        let player = &self.state.actors[self.state.player_id];
+        let missing_percent = (player.max_hp - player.hp).max(0) * 100 / player.max_hp.max(1);
+        let needs_healing = missing_percent as u32 >= self.rush_detour.min_missing_hp_percent;
+        let potions: Vec<Pos> = self
+            .state
+            .items
+            .values()
+            .filter(|item| needs_healing && loot.contains(&item.pos))
+            .filter(|item| {
+                matches!(item.kind, ItemKind::Consumable(id)
+                    if HEALING_POTIONS.contains(&id) && self.consumable_label(id) == id)
+            })
+            .map(|item| item.pos)
+            .collect();
+        let plan = RushPlan {
+            stairs,
+            loot: &loot,
+            potions: &potions,
+            max_detour_ticks: self.rush_detour.max_extra_ticks,
+        };
+        choose_rush_intent(map, player_pos, &self.action_costs, &plan)
+    }
+
+    /// Loot and objective intents last while their target stays put; others follow map rules.
+    fn is_intent_still_valid(&self, intent: AutoExploreIntent) -> bool {
+        match intent.reason {
+            AutoReason::Loot => self.find_item_at(intent.target).is_some(),
+            AutoReason::Objective => self.objective_target() == Some(intent.target),
+            _ => is_intent_target_still_valid(&self.state.map, intent),
         }
     }
 }
@@ -0,0 +1,108 @@
+//! Same-tick prompt batching: gathers every due prompt and raises them in priority order.
+//! Lower-priority prompts ride along as a queued preview on the active prompt.
+
+use super::*;
+
+enum PromptCandidate {
+    Enemy { adjacent: Vec<EntityId>, primary: EntityId },
+    PerkDraft,
+    Loot(ItemId),
+    PactAltar { pos: Pos, current_god: GodId },
+    FloorTransition,
+    StairsLocked(FloorObjective),
+}
+
+impl PromptCandidate {
+    fn kind(&self) -> PromptKind {
+        match self {
+            Self::Enemy { .. } => PromptKind::EnemyEncounter,
+            Self::PerkDraft => PromptKind::PerkDraft,
+            Self::Loot(_) => PromptKind::LootFound,
+            Self::PactAltar { .. } => PromptKind::PactAltar,
+            Self::FloorTransition => PromptKind::FloorTransition,
+            Self::StairsLocked(_) => PromptKind::StairsLocked,
+        }
+    }
+}
+
+impl Game {
+    /// Raise the highest-priority prompt due at `player_pos`, if any, queueing the rest.
+    /// Auto-pickup only runs when no enemy is adjacent and no encounter was just settled by
+    /// policy, so it never spends time mid-fight.
+    pub(super) fn raise_due_prompts(
+        &mut self,
+        player_pos: Pos,
+        steps: u32,
+    ) -> Option<AdvanceResult> {
+        let mut batch = Vec::new();
+        let mut auto_fought = false;
+        self.expire_snoozes();
+        if self.state.sanctuary_active && player_pos == self.state.sanctuary_tile {
+            self.suppressed_enemy = None;
+        } else {
+            self.clear_stale_suppressed_enemy(player_pos);
+            let adjacent = self.find_engaging_enemy_ids(player_pos);
+            auto_fought =
+                self.try_auto_fight(&adjacent) || self.try_auto_resolve_encounter(&adjacent);
+            if let Some(primary) = adjacent.first().copied()
+                && !auto_fought
+            {
+                batch.push(PromptCandidate::Enemy { adjacent, primary });
+            }
+        }
+        if self.state.perk_drafts_pending > 0 {
+            batch.push(PromptCandidate::PerkDraft);
+        }
+        if let Some(item) = self.find_item_at(player_pos)
+            && !self.is_snoozed(SnoozeTarget::Loot(player_pos))
+        {
+            let needs_prompt = if batch.is_empty() && !auto_fought {
+                !self.try_auto_pickup(item)
+            } else {
+                self.auto_pickup_action(self.state.items[item].kind).is_none()
+            };
+            if needs_prompt {
+                batch.push(PromptCandidate::Loot(item));
+            }
+        }
+        if let (Some(pos), Some(current_god)) = (self.state.altar_tile, self.state.active_god)
+            && manhattan(player_pos, pos) <= 1
+        {
+            batch.push(PromptCandidate::PactAltar { pos, current_god });
+        }
+        if self.state.map.tile_at(player_pos) != TileKind::DownStairs {
+            self.stairs_lock_acknowledged = false;
+        } else if let Some(active) = self.state.floor_objective {
+            if !self.stairs_lock_acknowledged {
+                batch.push(PromptCandidate::StairsLocked(active.objective()));
+            }
+        } else {
+            batch.push(PromptCandidate::FloorTransition);
+        }
+        if batch.is_empty() {
+            return None;
+        }
+
+        batch.sort_by_key(PromptCandidate::kind);
+        let queued = batch[1..].iter().map(PromptCandidate::kind).collect();
+        let result = match batch.swap_remove(0) {
+            PromptCandidate::Enemy { adjacent, primary } => {
+                self.log.push(LogEvent::EnemyEncountered { enemy: primary });
+                self.interrupt_enemy(adjacent, primary, steps)
+            }
+            PromptCandidate::PerkDraft => self.interrupt_perk_draft(steps),
+            PromptCandidate::Loot(item) => self.interrupt_loot(item, steps),
+            PromptCandidate::PactAltar { pos, current_god } => {
+                self.interrupt_pact_altar(pos, current_god, steps)
+            }
+            PromptCandidate::FloorTransition => self.interrupt_floor_transition(steps),
+            PromptCandidate::StairsLocked(objective) => {
+                self.interrupt_stairs_locked(objective, steps)
+            }
+        };
+        if let Some(prompt) = &mut self.pending_prompt {
+            prompt.queued = queued;
+        }
+        Some(result)
+    }
+}
@@ -1,9 +1,15 @@
 //! Regression test module wiring for engine behaviors.
 
+mod advance_breakdown;
 mod bootstrap_layout;
+mod budgeted_advance;
+mod insertion_order;
 mod intent_planning;
 mod interruption_flow;
+mod prompt_batching;
+mod room_entry;
 mod termination_guards;
+mod threat_trace;
 
 /// Shared imports for engine regression tests.
 mod support {
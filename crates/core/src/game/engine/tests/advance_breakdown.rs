@@ -0,0 +1,49 @@
+//! Tests for the tick, replan and interrupt breakdown reported by each advance.
+
+use super::support::*;
+use crate::content::keys;
+use crate::state::Item;
+
+#[test]
+fn breakdown_splits_ticks_and_counts_new_interrupts_once() {
+    let mut game = Game::new(2734, &ContentPack::default(), GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    game.state.map = MapBuilder::open(9, 5).discover_all().build();
+    let start = Pos { y: 2, x: 1 };
+    game.state.actors[game.state.player_id].pos = start;
+    compute_fov(&mut game.state.map, start, FOV_RADIUS);
+    game.state.policy.resource_aggression = Aggro::Greedy;
+    game.state.active_perks.push(keys::PERK_SCOUT);
+    let pos = Pos { y: 2, x: 5 };
+    let kind = ItemKind::Weapon(keys::WEAPON_BLOOD_AXE);
+    let item = game.state.items.insert(Item { id: ItemId::default(), kind, pos, cursed: true });
+    game.state.items[item].id = item;
+
+    let result = game.advance(2);
+    let breakdown = result.breakdown;
+    assert!(matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted));
+    assert_eq!(breakdown.ticks_moved + breakdown.ticks_busy + breakdown.ticks_idle, 2);
+    assert!(breakdown.ticks_moved > 0, "the player walks toward the loot: {breakdown:?}");
+    assert_eq!(breakdown.replans, 1, "only the first tick picks an intent");
+    assert_eq!(breakdown.interrupts_raised, 0);
+
+    let result = game.advance(20);
+    assert!(matches!(
+        result.stop_reason,
+        AdvanceStopReason::Interrupted(Interrupt::LootFound { .. })
+    ));
+    assert_eq!(result.breakdown.interrupts_raised, 1);
+    assert_eq!(result.breakdown.replans, 0, "the route to the loot is still valid");
+    let ticks =
+        result.breakdown.ticks_moved + result.breakdown.ticks_busy + result.breakdown.ticks_idle;
+    assert_eq!(ticks, result.simulated_ticks);
+
+    let repeat = game.advance(20);
+    assert_eq!(repeat.simulated_ticks, 0);
+    assert_eq!(
+        repeat.breakdown,
+        AdvanceBreakdown::default(),
+        "a pending prompt is not raised again"
+    );
+}
@@ -53,6 +53,16 @@ fn starter_layout_auto_flow_reaches_a_multi_enemy_encounter() {
             AdvanceStopReason::Interrupted(Interrupt::DoorBlocked { prompt_id, .. }) => {
                 game.apply_choice(prompt_id, Choice::OpenDoor).expect("door choice should apply");
             }
+            AdvanceStopReason::Interrupted(Interrupt::StairsLocked { prompt_id, .. }) => {
+                game.apply_choice(prompt_id, Choice::SeekObjective)
+                    .expect("objective choice should apply");
+            }
+            AdvanceStopReason::Interrupted(Interrupt::PactAltar { prompt_id, .. }) => {
+                game.apply_choice(prompt_id, Choice::KeepPact).expect("pact choice should apply");
+            }
+            AdvanceStopReason::Interrupted(Interrupt::PerkDraft { prompt_id, .. }) => {
+                game.apply_choice(prompt_id, Choice::DraftPerk(0)).expect("draft should apply");
+            }
             AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter {
                 prompt_id,
                 enemies,
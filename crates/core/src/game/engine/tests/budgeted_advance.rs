@@ -0,0 +1,84 @@
+//! Tests that deadline-budgeted advancement yields without changing simulation results.
+
+use std::time::{Duration, Instant};
+
+use super::support::*;
+
+#[test]
+fn expired_deadline_still_makes_one_tick_of_progress() {
+    let mut game = Game::new(4040, &ContentPack::default(), GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+
+    let result = game.advance_until(50, Instant::now());
+    assert_eq!(result.simulated_ticks, 1);
+    assert!(matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted));
+}
+
+#[test]
+fn yielding_every_tick_matches_one_unbudgeted_advance() {
+    let content = ContentPack::default();
+    let mut budgeted = Game::new(4041, &content, GameMode::Ironman);
+    let mut unbudgeted = budgeted.clone();
+
+    let expected = unbudgeted.advance(40);
+    let mut simulated = 0;
+    let stop_reason = loop {
+        let result = budgeted.advance_until(40 - simulated, Instant::now());
+        simulated += result.simulated_ticks;
+        if simulated >= 40 || !matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted) {
+            break result.stop_reason;
+        }
+    };
+
+    assert_eq!(simulated, expected.simulated_ticks);
+    assert_eq!(format!("{stop_reason:?}"), format!("{:?}", expected.stop_reason));
+    assert_eq!(budgeted.snapshot_hash(), unbudgeted.snapshot_hash());
+
+    let generous = Instant::now() + Duration::from_secs(60);
+    let mut fresh = Game::new(4041, &content, GameMode::Ironman);
+    let whole = fresh.advance_until(40, generous);
+    assert_eq!(whole.simulated_ticks, expected.simulated_ticks);
+    assert_eq!(whole.breakdown, expected.breakdown, "per-tick breakdowns sum to the whole");
+}
+
+/// A one-lane serpentine maze whose only frontier is at the far end of the lane.
+fn serpentine_maze_game(seed: u64) -> Game {
+    let mut game = Game::new(seed, &ContentPack::default(), GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    let mut maze = MapBuilder::solid(41, 41);
+    for row in 0..20 {
+        let y = 1 + row * 2;
+        maze = maze.corridor(Pos { y, x: 1 }, Pos { y, x: 39 });
+        let turn_x = if row % 2 == 0 { 39 } else { 1 };
+        maze = maze.tile(Pos { y: y + 1, x: turn_x }, TileKind::Floor);
+    }
+    game.state.map = maze.discover_all().undiscovered(Pos { y: 39, x: 1 }).build();
+    game.state.actors[game.state.player_id].pos = Pos { y: 1, x: 1 };
+    game.state.auto_intent = None;
+    game
+}
+
+#[test]
+fn long_route_searches_yield_mid_search_and_resume_to_the_same_state() {
+    let mut budgeted = serpentine_maze_game(4042);
+    let mut unbudgeted = budgeted.clone();
+    let expected = unbudgeted.advance(12);
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+    let (mut simulated, mut yields) = (0, 0);
+    while simulated < expected.simulated_ticks {
+        let result = budgeted.advance_within(12 - simulated, deadline, 64);
+        assert!(matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted));
+        if result.simulated_ticks == 0 {
+            yields += 1;
+        }
+        simulated += result.simulated_ticks;
+    }
+
+    assert!(yields >= 3, "each route spans hundreds of expansions, got {yields} yields");
+    assert_eq!(budgeted.current_tick(), unbudgeted.current_tick());
+    assert_eq!(budgeted.snapshot_hash(), unbudgeted.snapshot_hash());
+    assert_eq!(budgeted.log(), unbudgeted.log());
+}
@@ -0,0 +1,63 @@
+//! Property tests that the order enemies were inserted never changes how a run plays out.
+
+use proptest::prelude::*;
+
+use super::support::*;
+use crate::game::floor_transition::insert_enemy;
+
+const KINDS: [ActorKind; 3] = [ActorKind::Goblin, ActorKind::FeralHound, ActorKind::BloodAcolyte];
+
+/// Everything observable about a played-out skirmish that does not name an entity id.
+type Outcome = (u64, u64, usize, i32, Vec<(Pos, ActorKind, i32, u8)>);
+
+fn play(enemies: &[(Pos, ActorKind)]) -> Outcome {
+    let mut game = Game::new(2740, &ContentPack::default(), GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    game.state.map = MapBuilder::open(12, 9).discover_all().build();
+    let start = Pos { y: 4, x: 1 };
+    game.state.actors[game.state.player_id].pos = start;
+    for &(pos, kind) in enemies {
+        insert_enemy(&mut game, kind, pos, 1);
+    }
+    game.refresh_fov(start);
+    game.log = EventLog::new(game.log.depth());
+
+    for _ in 0..12 {
+        match game.advance(20).stop_reason {
+            AdvanceStopReason::Interrupted(interrupt) => {
+                let choice = game.available_choices()[0].clone();
+                game.apply_choice(interrupt.prompt_id(), choice).expect("offered choice applies");
+            }
+            AdvanceStopReason::BudgetExhausted => {}
+            _ => break,
+        }
+    }
+
+    let mut enemies: Vec<_> = game
+        .state
+        .actors
+        .values()
+        .filter(|actor| actor.kind != ActorKind::Player)
+        .map(|actor| (actor.pos, actor.kind, actor.hp, actor.awareness))
+        .collect();
+    enemies.sort();
+    let player_hp = game.state.actors[game.state.player_id].hp;
+    (game.current_tick(), game.snapshot_hash(), game.log.len(), player_hp, enemies)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(48))]
+    #[test]
+    fn permuting_enemy_insertion_order_never_changes_the_run(
+        raw in prop::collection::btree_map((1..8_i32, 4..11_i32), 0..3_usize, 1..6),
+        rotation in any::<usize>(),
+    ) {
+        let enemies: Vec<(Pos, ActorKind)> =
+            raw.iter().map(|(&(y, x), &kind)| (Pos { y, x }, KINDS[kind])).collect();
+        let mut permuted = enemies.clone();
+        permuted.rotate_left(rotation % enemies.len());
+        permuted.reverse();
+        prop_assert_eq!(play(&enemies), play(&permuted));
+    }
+}
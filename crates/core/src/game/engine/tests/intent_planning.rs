@@ -1,6 +1,7 @@
 //! Tests for auto-intent planning, movement side effects, and reason-change logging.
 
 use super::support::*;
+use crate::state::Item;
 
 #[test]
 fn advance_uses_hazard_path_for_threat_avoidance_intent() {
@@ -8,22 +9,14 @@ fn advance_uses_hazard_path_for_threat_avoidance_intent() {
     game.state.items.clear();
     game.state.actors.retain(|id, _| id == game.state.player_id);
 
-    let mut map = Map::new(11, 9);
-    for y in 1..8 {
-        for x in 1..10 {
-            map.set_tile(Pos { y, x }, TileKind::Wall);
-        }
-    }
-    for x in 2..=8 {
-        map.set_tile(Pos { y: 4, x }, TileKind::Floor);
-    }
-    map.discovered.fill(true);
-    map.visible.fill(true);
-    map.set_hazard(Pos { y: 4, x: 4 }, true);
-    map.set_hazard(Pos { y: 4, x: 6 }, true);
-    map.discovered[(4 * map.internal_width) + 1] = false;
-    map.discovered[(4 * map.internal_width) + 9] = false;
-    game.state.map = map;
+    game.state.map = MapBuilder::solid(11, 9)
+        .corridor(Pos { y: 4, x: 2 }, Pos { y: 4, x: 8 })
+        .visible_all()
+        .hazard(Pos { y: 4, x: 4 })
+        .hazard(Pos { y: 4, x: 6 })
+        .undiscovered(Pos { y: 4, x: 1 })
+        .undiscovered(Pos { y: 4, x: 9 })
+        .build();
 
     let start = Pos { y: 4, x: 5 };
     game.state.actors[game.state.player_id].pos = start;
@@ -43,23 +36,14 @@ fn movement_updates_visibility_and_expands_discovery() {
     game.state.items.clear();
     game.state.actors.retain(|id, _| id == game.state.player_id);
 
-    let mut map = Map::new(30, 10);
-    for y in 1..9 {
-        for x in 1..29 {
-            map.set_tile(Pos { y, x }, TileKind::Wall);
-        }
-    }
-    for x in 1..26 {
-        map.set_tile(Pos { y: 4, x }, TileKind::Floor);
-    }
-    map.discovered.fill(false);
-    game.state.map = map;
+    game.state.map =
+        MapBuilder::solid(30, 10).corridor(Pos { y: 4, x: 1 }, Pos { y: 4, x: 25 }).build();
 
     let start = Pos { y: 4, x: 5 };
     game.state.actors[game.state.player_id].pos = start;
     compute_fov(&mut game.state.map, start, FOV_RADIUS);
     // Create visible frontier at (4,15) by leaving (4,16) unknown.
-    game.state.map.discovered[(4 * game.state.map.internal_width) + 16] = false;
+    undiscover(&mut game.state.map, Pos { y: 4, x: 16 });
     let discovered_before = game.state.map.discovered.iter().filter(|&&known| known).count();
 
     let result = game.advance(1);
@@ -104,21 +88,14 @@ fn path_len_only_change_does_not_emit_auto_reason_changed() {
     let mut game = Game::new(12345, &ContentPack::default(), GameMode::Ironman);
     game.state.items.clear();
     game.state.actors.retain(|id, _| id == game.state.player_id);
-    let mut map = Map::new(12, 7);
-    for y in 1..(map.internal_height - 1) {
-        for x in 1..(map.internal_width - 1) {
-            map.set_tile(Pos { y: y as i32, x: x as i32 }, TileKind::Wall);
-        }
-    }
-    for x in 1..=9 {
-        map.set_tile(Pos { y: 3, x }, TileKind::Floor);
-    }
-    map.discovered.fill(true);
-    map.visible.fill(false);
+    let mut map = MapBuilder::solid(12, 7)
+        .corridor(Pos { y: 3, x: 1 }, Pos { y: 3, x: 9 })
+        .discover_all()
+        .undiscovered(Pos { y: 3, x: 9 })
+        .build();
     for x in 1..=8 {
         map.set_visible(Pos { y: 3, x }, true);
     }
-    map.discovered[(3 * map.internal_width) + 9] = false;
 
     game.state.map = map;
     let first_pos = Pos { y: 3, x: 3 };
@@ -148,19 +125,11 @@ fn auto_reason_changed_emits_only_on_reason_or_target_changes() {
     let mut game = Game::new(12345, &ContentPack::default(), GameMode::Ironman);
     game.state.items.clear();
     game.state.actors.retain(|id, _| id == game.state.player_id);
-    let mut map = Map::new(10, 7);
-    for y in 1..6 {
-        for x in 1..9 {
-            map.set_tile(Pos { y, x }, TileKind::Wall);
-        }
-    }
-    for x in 1..=7 {
-        map.set_tile(Pos { y: 3, x }, TileKind::Floor);
-    }
-    map.discovered.fill(true);
-    map.visible.fill(true);
-    map.discovered[(3 * map.internal_width) + 8] = false;
-    game.state.map = map;
+    game.state.map = MapBuilder::solid(10, 7)
+        .corridor(Pos { y: 3, x: 1 }, Pos { y: 3, x: 7 })
+        .visible_all()
+        .undiscovered(Pos { y: 3, x: 8 })
+        .build();
 
     let pos = Pos { y: 3, x: 2 };
     game.state.actors[game.state.player_id].pos = pos;
@@ -191,3 +160,38 @@ fn auto_reason_changed_emits_only_on_reason_or_target_changes() {
         .count();
     assert_eq!(count_after_repeat, 2);
 }
+
+#[test]
+fn rush_heads_for_known_stairs_and_detours_only_for_known_healing() {
+    let mut game = Game::new(2728, &ContentPack::default(), GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    game.state.map = MapBuilder::solid(16, 7)
+        .corridor(Pos { y: 3, x: 1 }, Pos { y: 3, x: 14 })
+        .corridor(Pos { y: 1, x: 5 }, Pos { y: 2, x: 5 })
+        .stairs(Pos { y: 3, x: 10 })
+        .visible_all()
+        .undiscovered(Pos { y: 3, x: 14 })
+        .build();
+    let start = Pos { y: 3, x: 2 };
+    let player = game.state.player_id;
+    game.state.actors[player].pos = start;
+    game.state.actors[player].hp = game.state.actors[player].max_hp / 2;
+    let kind = ItemKind::Consumable(keys::CONSUMABLE_MINOR_HP_POT);
+    let potion = Pos { y: 1, x: 5 };
+    game.state.items.insert(Item { id: ItemId::default(), kind, pos: potion, cursed: false });
+
+    let target = |game: &mut Game| {
+        game.state.auto_intent = None;
+        game.plan_auto_intent(start);
+        game.state.auto_intent.map(|intent| intent.target)
+    };
+    assert_eq!(target(&mut game), Some(Pos { y: 3, x: 13 }), "Thorough explores first");
+
+    game.state.policy.exploration_mode = ExploreMode::Rush;
+    assert_eq!(target(&mut game), Some(Pos { y: 3, x: 10 }), "an unknown potion is no reason");
+    game.state.identified_consumables.insert(keys::CONSUMABLE_MINOR_HP_POT);
+    assert_eq!(target(&mut game), Some(potion));
+    game.state.actors[player].hp = game.state.actors[player].max_hp;
+    assert_eq!(target(&mut game), Some(Pos { y: 3, x: 10 }), "no detour at full health");
+}
@@ -51,6 +51,7 @@ fn door_interrupt_open_then_resume_moves_forward() {
         other => panic!("expected door interrupt, got {other:?}"),
     };
     game.apply_choice(prompt_id, Choice::OpenDoor).expect("open door");
+    assert_eq!(game.log.last(), Some(&LogEvent::DoorOpened { pos: door }));
 
     let second = game.advance(1);
     assert!(
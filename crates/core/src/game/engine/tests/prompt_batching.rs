@@ -0,0 +1,56 @@
+//! Tests for same-tick prompt batching and its priority order.
+
+use super::support::*;
+use crate::state::Item;
+
+fn stairs_with_loot_game() -> (Game, Pos) {
+    let mut game = Game::new(2678, &ContentPack::default(), GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    let (mut map, origin) = open_room_fixture();
+    map.set_tile(origin, TileKind::DownStairs);
+    game.state.map = map;
+    game.state.actors[game.state.player_id].pos = origin;
+    compute_fov(&mut game.state.map, origin, FOV_RADIUS);
+    let kind = ItemKind::Consumable(keys::CONSUMABLE_MINOR_HP_POT);
+    let item =
+        game.state.items.insert(Item { id: ItemId::default(), kind, pos: origin, cursed: false });
+    game.state.items[item].id = item;
+    (game, origin)
+}
+
+#[test]
+fn simultaneous_prompts_raise_enemy_first_and_queue_the_rest() {
+    let (mut game, origin) = stairs_with_loot_game();
+    add_goblin(&mut game, Pos { y: origin.y, x: origin.x + 1 });
+
+    let result = game.advance(1);
+    let AdvanceStopReason::Interrupted(interrupt) = result.stop_reason else {
+        panic!("expected an interrupt, got {:?}", result.stop_reason);
+    };
+    assert_eq!(interrupt.prompt_kind(), PromptKind::EnemyEncounter);
+    let batch = game.prompt_batch().expect("prompt should be pending");
+    assert_eq!(batch.active, interrupt);
+    assert_eq!(batch.queued, vec![PromptKind::LootFound, PromptKind::FloorTransition]);
+}
+
+#[test]
+fn queued_prompts_follow_once_the_active_prompt_resolves() {
+    let (mut game, _) = stairs_with_loot_game();
+
+    let first = game.advance(1);
+    let AdvanceStopReason::Interrupted(Interrupt::LootFound { prompt_id, .. }) = first.stop_reason
+    else {
+        panic!("expected loot before stairs, got {:?}", first.stop_reason);
+    };
+    assert_eq!(game.prompt_batch().unwrap().queued, vec![PromptKind::FloorTransition]);
+    game.apply_choice(prompt_id, Choice::KeepLoot).unwrap();
+    assert!(game.prompt_batch().is_none());
+
+    let second = game.advance(1);
+    let AdvanceStopReason::Interrupted(interrupt) = second.stop_reason else {
+        panic!("expected stairs prompt, got {:?}", second.stop_reason);
+    };
+    assert_eq!(interrupt.prompt_kind(), PromptKind::FloorTransition);
+    assert!(game.prompt_batch().unwrap().queued.is_empty());
+}
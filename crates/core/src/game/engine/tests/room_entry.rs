@@ -0,0 +1,44 @@
+//! Tests for room-entry log events driven by generated room labels.
+
+use super::support::*;
+
+#[test]
+fn stepping_into_a_labelled_vault_logs_one_room_and_one_vault_event() {
+    let mut game = Game::new(2708, &ContentPack::default(), GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    let mut map = MapBuilder::solid(40, 5)
+        .corridor(Pos { y: 2, x: 1 }, Pos { y: 2, x: 38 })
+        .discover_all()
+        .build();
+    for x in 5..=10 {
+        map.rooms[2 * 40 + x] = Some(RoomId(3));
+    }
+    map.vaults.push(VaultStamp {
+        template: VaultTemplate::ShrineRoom,
+        room: RoomId(3),
+        origin: Pos { y: 2, x: 5 },
+        width: 6,
+        height: 1,
+    });
+    for x in 20..40 {
+        undiscover(&mut map, Pos { y: 2, x });
+    }
+    game.state.map = map;
+    game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 1 };
+    game.log = EventLog::new(game.log.depth());
+
+    game.advance(15);
+    let entries: Vec<(RoomId, Option<VaultTemplate>)> = game
+        .log
+        .iter()
+        .filter_map(|event| match event {
+            LogEvent::RoomEntered { room } => Some((*room, None)),
+            LogEvent::VaultEntered { template, room } => Some((*room, Some(*template))),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(entries, vec![(RoomId(3), None), (RoomId(3), Some(VaultTemplate::ShrineRoom))]);
+    assert_eq!(game.state.map.room_at(Pos { y: 2, x: 7 }), Some(RoomId(3)));
+    assert_eq!(game.state.map.room_tiles(RoomId(3)).count(), 6);
+}
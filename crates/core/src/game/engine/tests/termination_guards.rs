@@ -41,8 +41,13 @@ fn no_progress_simulation_finishes_instead_of_spinning_budget_forever() {
         result.simulated_ticks, MAX_NO_PROGRESS_TICKS,
         "stall watchdog should terminate within a fixed tick budget"
     );
-    assert!(matches!(
-        result.stop_reason,
-        AdvanceStopReason::EngineFailure(crate::EngineFailureReason::StalledNoProgress)
-    ));
+    let AdvanceStopReason::EngineFailure(reason) = result.stop_reason else {
+        panic!("expected a stall failure, got {:?}", result.stop_reason);
+    };
+    let diagnostics = reason.diagnostics();
+    assert_eq!(diagnostics.player_pos, isolated);
+    assert_eq!(diagnostics.tick, game.current_tick());
+    assert_eq!(diagnostics.reachable_frontier_count, 0);
+    assert_eq!(diagnostics.last_intent, None);
+    assert_eq!(diagnostics.snapshot_hash, game.snapshot_hash());
 }
@@ -0,0 +1,24 @@
+//! Tests for threat-trace retention depth.
+
+use super::support::*;
+
+fn idle_game(content: &ContentPack) -> Game {
+    let mut game = Game::new(2706, content, GameMode::Ironman);
+    game.state.items.clear();
+    game.state.actors.retain(|id, _| id == game.state.player_id);
+    game.state.map = MapBuilder::open(8, 8).discover_all().build();
+    game.state.actors[game.state.player_id].pos = Pos { y: 4, x: 4 };
+    game
+}
+
+#[test]
+fn threat_trace_keeps_the_configured_number_of_newest_entries() {
+    let mut game = idle_game(&ContentPack { threat_trace_depth: 4, ..ContentPack::default() });
+    game.advance(10);
+    let ticks: Vec<u64> = game.state.threat_trace.iter().map(|trace| trace.tick).collect();
+    assert_eq!(ticks, vec![10, 9, 8, 7]);
+
+    let mut deep = idle_game(&ContentPack::default());
+    deep.advance(40);
+    assert_eq!(deep.state.threat_trace.len(), 32);
+}
@@ -0,0 +1,58 @@
+//! Fight previews for the encounter prompt, built from the same strike math that resolves
+//! a fight. Previews only read state.
+
+use super::*;
+
+impl Game {
+    /// The damage exchange fighting `enemy` would open with; `None` unless `enemy` is a
+    /// living enemy on this floor.
+    pub fn preview_fight(&self, enemy: EntityId) -> Option<FightPreview> {
+        if enemy == self.state.player_id {
+            return None;
+        }
+        let actor = self.state.actors.get(enemy)?;
+        let damage_dealt = self.player_strike_damage(enemy);
+        let damage_taken = self.enemy_strike_damage(enemy);
+        let strikes_to_kill = (actor.hp.max(1) as u32).div_ceil(damage_dealt as u32);
+        Some(FightPreview { damage_dealt, damage_taken, strikes_to_kill })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::add_goblin;
+
+    #[test]
+    fn preview_matches_the_fight_it_predicts_and_leaves_state_alone() {
+        let mut game = Game::new(2741, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let player = game.state.actors[game.state.player_id].pos;
+        let enemy = add_goblin(&mut game, Pos { y: player.y, x: player.x + 1 });
+        game.state.actors[enemy].hp = 10;
+        game.state.actors[enemy].defense = 1;
+        assert_eq!(game.preview_fight(game.state.player_id), None);
+
+        let AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { prompt_id, .. }) =
+            game.advance(1).stop_reason
+        else {
+            panic!("expected an encounter");
+        };
+        let hash = game.snapshot_hash();
+        let balanced = game.preview_fight(enemy).expect("the goblin is alive");
+        assert_eq!(game.snapshot_hash(), hash, "previewing is read-only");
+        assert_eq!((balanced.damage_dealt, balanced.strikes_to_kill), (4, 3));
+        assert!(!balanced.kills_outright());
+
+        game.state.policy.stance = Stance::Defensive;
+        let defensive = game.preview_fight(enemy).unwrap();
+        assert!(defensive.damage_dealt < balanced.damage_dealt);
+        assert!(defensive.damage_taken <= balanced.damage_taken);
+        game.state.policy.stance = Stance::Balanced;
+
+        game.apply_choice(prompt_id, Choice::Fight).unwrap();
+        assert_eq!(game.state.actors[enemy].hp, 10 - balanced.damage_dealt);
+    }
+}
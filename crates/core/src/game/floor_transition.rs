@@ -5,6 +5,10 @@ use super::*;
 
 mod actors;
 mod install;
+mod persistence;
+
+pub(super) use actors::insert_enemy;
+pub(super) use install::{install_floor, preview_branches};
 
 #[cfg(test)]
 mod tests;
@@ -12,5 +16,12 @@ mod tests;
 impl Game {
     pub(super) fn descend_to_floor(&mut self, floor_index: u8) {
         install::install_generated_floor(self, floor_index);
+        self.log.push(LogEvent::FloorEntered { floor_index, name: self.floor_name() });
+        let snapshot_hash = self.snapshot_hash();
+        self.stats.floor_checkpoints.push(FloorCheckpoint {
+            floor_index,
+            tick: self.tick,
+            snapshot_hash,
+        });
     }
 }
@@ -1,7 +1,6 @@
 //! Actor-installation rules for floor transitions.
 
 use super::*;
-use crate::content::get_enemy_stats;
 use crate::mapgen::GeneratedFloor;
 use crate::state::Actor;
 
@@ -11,22 +10,38 @@ pub(in crate::game) fn install_floor_actors(game: &mut Game, generated: &Generat
     game.state.actors[player_id].pos = generated.entry_tile;
 
     for spawn in &generated.enemy_spawns {
-        let stats = get_enemy_stats(spawn.kind);
-        let enemy = Actor {
-            id: EntityId::default(),
-            kind: spawn.kind,
-            pos: spawn.pos,
-            hp: stats.hp,
-            max_hp: stats.hp,
-            attack: stats.attack,
-            defense: stats.defense,
-            active_weapon_slot: WeaponSlot::Primary,
-            equipped_weapon: None,
-            reserve_weapon: None,
-            next_action_tick: stats.speed as u64,
-            speed: stats.speed,
-        };
-        let enemy_id = game.state.actors.insert(enemy);
-        game.state.actors[enemy_id].id = enemy_id;
+        let stats = game.enemy_base_stats(spawn.kind);
+        let enemy = insert_enemy(game, spawn.kind, spawn.pos, stats.speed as u64);
+        game.state.actors[enemy].unique = spawn.boss.then_some(UniqueEnemy::Boss(spawn.kind));
     }
 }
+
+/// Add an enemy of `kind` with difficulty-scaled stats that first acts at `first_action_tick`.
+pub(in crate::game) fn insert_enemy(
+    game: &mut Game,
+    kind: ActorKind,
+    pos: Pos,
+    first_action_tick: u64,
+) -> EntityId {
+    let stats = game.difficulty_scaling.scale_enemy_stats(game.enemy_base_stats(kind));
+    let enemy = Actor {
+        id: EntityId::default(),
+        kind,
+        pos,
+        hp: stats.hp,
+        max_hp: stats.hp,
+        attack: stats.attack,
+        defense: stats.defense,
+        active_weapon_slot: WeaponSlot::Primary,
+        equipped_weapon: None,
+        reserve_weapon: None,
+        next_action_tick: first_action_tick,
+        speed: stats.speed,
+        awareness: 0,
+        unique: None,
+        home: pos,
+    };
+    let enemy_id = game.state.actors.insert(enemy);
+    game.state.actors[enemy_id].id = enemy_id;
+    enemy_id
+}
@@ -1,31 +1,80 @@
 //! End-to-end installation workflow for generated floors.
 
 use super::actors::install_floor_actors;
+use super::persistence::{restore_stored_floor, stash_current_floor};
 use super::*;
-use crate::mapgen::{GeneratedFloor, MapGenerator};
+use crate::mapgen::{BranchPreview, BranchProfile, GeneratedFloor, MapGenerator};
 use crate::state::{Item, Map};
 
 pub(in crate::game) fn install_generated_floor(game: &mut Game, floor_index: u8) {
-    let generated = MapGenerator::new(game.seed, game.state.branch_profile).generate(floor_index);
+    game.settle_director_floor();
+    stash_current_floor(game);
+    game.state.ambience = roll_ambience(game.seed, floor_index);
+    if let Some(restored) = restore_stored_floor(game, floor_index) {
+        apply_floor_transition_state(game, floor_index, restored.entry_tile);
+        game.state.floor_objective = restored.objective;
+        game.relight_floor(restored.entry_tile);
+        return;
+    }
+
+    let generated = floor_generator(game, game.state.branch_profile).generate(floor_index);
+    install_floor(game, floor_index, generated);
+}
+
+/// The generator for floors down `branch`, scaled for this run's difficulty and director.
+fn floor_generator(game: &Game, branch: BranchProfile) -> MapGenerator {
+    MapGenerator::new(game.seed, branch)
+        .with_enemy_count_percent(game.difficulty_scaling.enemy_count_percent)
+        .with_item_count_percent(game.director_loot_percent())
+        .with_boss_roster(game.boss_roster.clone())
+}
 
+/// Spoiler-safe previews of `floor_index` down each branch the pact prompt offers.
+pub(in crate::game) fn preview_branches(game: &Game, floor_index: u8) -> Vec<BranchPreview> {
+    [BranchProfile::BranchA, BranchProfile::BranchB]
+        .into_iter()
+        .map(|branch| BranchPreview {
+            branch,
+            floor: floor_generator(game, branch).preview(floor_index),
+        })
+        .collect()
+}
+
+/// Replace the current floor with `generated` and place the player on its entry tile.
+pub(in crate::game) fn install_floor(game: &mut Game, floor_index: u8, generated: GeneratedFloor) {
     install_floor_actors(game, &generated);
     install_floor_items(game, &generated);
 
     let mut map = Map::new(generated.width, generated.height);
     map.tiles = generated.tiles;
     map.hazards = generated.hazards;
+    map.rooms = generated.rooms;
+    map.vaults = generated.vaults;
+    game.state.floor_provenance = generated.provenance;
 
-    compute_fov(&mut map, generated.entry_tile, FOV_RADIUS);
     game.state.map = map;
+    game.refresh_fov(generated.entry_tile);
 
     apply_floor_transition_state(game, floor_index, generated.entry_tile);
+    game.cull_slain_uniques();
+    game.assign_floor_objective(generated.entry_tile);
+    game.light_floor(generated.entry_tile);
+    if game.state.active_god.is_some() && !game.state.pact_broken {
+        game.state.altar_tile = altar_beside(&game.state.map, generated.down_stairs_tile);
+    }
+}
+
+/// The first open floor tile beside the stairs, so every descent passes the altar.
+fn altar_beside(map: &Map, stairs: Pos) -> Option<Pos> {
+    neighbors(stairs).into_iter().find(|pos| map.tile_at(*pos) == TileKind::Floor)
 }
 
 fn install_floor_items(game: &mut Game, generated: &GeneratedFloor) {
     game.state.items.clear();
 
     for spawn in &generated.item_spawns {
-        let item = Item { id: ItemId::default(), kind: spawn.kind, pos: spawn.pos };
+        let item =
+            Item { id: ItemId::default(), kind: spawn.kind, pos: spawn.pos, cursed: spawn.cursed };
         let item_id = game.state.items.insert(item);
         game.state.items[item_id].id = item_id;
     }
@@ -35,7 +84,66 @@ fn apply_floor_transition_state(game: &mut Game, floor_index: u8, entry: Pos) {
     game.state.sanctuary_tile = entry;
     game.state.sanctuary_active = true;
     game.state.floor_index = floor_index;
+    game.state.floor_entry_tick = game.tick;
+    game.state.wanderers_this_floor = 0;
+    game.state.altar_tile = None;
+    game.state.braced = false;
+    game.state.wait_ticks_remaining = 0;
+    game.state.manual_step = None;
+    game.state.floor_objective = None;
+    game.stairs_lock_acknowledged = false;
+    game.snoozed_targets.clear();
+    game.state.curse_floors_remaining = game.state.curse_floors_remaining.saturating_sub(1);
     game.state.auto_intent = None;
+    game.enemy_memory.clear();
     game.suppressed_enemy = None;
     game.no_progress_ticks = 0;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    fn stairs_interrupt(game: &mut Game) -> Interrupt {
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.items.clear();
+        game.state.floor_objective = None;
+        game.state.altar_tile = None;
+        let stairs = game
+            .state
+            .map
+            .positions()
+            .find(|pos| game.state.map.tile_at(*pos) == TileKind::DownStairs);
+        game.state.actors[game.state.player_id].pos = stairs.expect("floor has down stairs");
+        match game.advance(1).stop_reason {
+            AdvanceStopReason::Interrupted(interrupt) => interrupt,
+            other => panic!("expected a floor transition, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn first_descent_previews_each_branch_without_later_repeats() {
+        let mut game = Game::new(22222, &ContentPack::default(), GameMode::Ironman);
+        let Interrupt::FloorTransition { prompt_id, branch_previews, .. } =
+            stairs_interrupt(&mut game)
+        else {
+            panic!("expected a floor transition");
+        };
+        let expected: Vec<BranchPreview> = [BranchProfile::BranchA, BranchProfile::BranchB]
+            .into_iter()
+            .map(|branch| BranchPreview {
+                branch,
+                floor: MapGenerator::new(22222, branch).preview(2),
+            })
+            .collect();
+        assert_eq!(branch_previews, expected);
+
+        game.apply_choice(prompt_id, Choice::DescendBranchBForge).unwrap();
+        let later = stairs_interrupt(&mut game);
+        assert!(matches!(
+            later,
+            Interrupt::FloorTransition { ref branch_previews, .. } if branch_previews.is_empty()
+        ));
+    }
+}
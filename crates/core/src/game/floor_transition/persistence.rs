@@ -0,0 +1,160 @@
+//! Per-floor persistence so a revisited floor comes back as the player left it.
+//! Leaving a floor stashes its map, surviving enemies, remaining items and open objective in
+//! `GameState`.
+
+use super::*;
+use crate::state::StoredFloor;
+
+pub(in crate::game) fn stash_current_floor(game: &mut Game) {
+    let player_id = game.state.player_id;
+    let stored = StoredFloor {
+        map: game.state.map.clone(),
+        actors: game
+            .state
+            .actors
+            .iter()
+            .filter(|(id, _)| *id != player_id)
+            .map(|(_, actor)| actor.clone())
+            .collect(),
+        items: game.state.items.values().cloned().collect(),
+        entry_tile: game.state.sanctuary_tile,
+        provenance: game.state.floor_provenance,
+        objective: game.state.floor_objective,
+    };
+    game.state.stored_floors.insert(game.state.floor_index, stored);
+}
+
+/// What a revisit brings back besides the map, enemies and items already reinstalled.
+pub(in crate::game) struct RestoredFloor {
+    pub entry_tile: Pos,
+    /// The objective left open, pointing at the reinserted elite or sigil.
+    pub objective: Option<ActiveObjective>,
+}
+
+/// Reinstall a previously visited floor, or `None` if it was never visited. Slain uniques
+/// stay dead, and the open objective follows its elite or sigil to their new ids.
+pub(in crate::game) fn restore_stored_floor(
+    game: &mut Game,
+    floor_index: u8,
+) -> Option<RestoredFloor> {
+    let stored = game.state.stored_floors.remove(&floor_index)?;
+    let player_id = game.state.player_id;
+    let mut objective = None;
+    game.state.actors.retain(|id, _| id == player_id);
+    game.state.actors[player_id].pos = stored.entry_tile;
+    for actor in stored.actors {
+        if game.unique_slain(&actor) {
+            continue;
+        }
+        let stored_id = actor.id;
+        let actor_id = game.state.actors.insert(actor);
+        game.state.actors[actor_id].id = actor_id;
+        if stored.objective == Some(ActiveObjective::SlayElite(stored_id)) {
+            objective = Some(ActiveObjective::SlayElite(actor_id));
+        }
+    }
+    game.state.items.clear();
+    for item in stored.items {
+        let stored_id = item.id;
+        let item_id = game.state.items.insert(item);
+        game.state.items[item_id].id = item_id;
+        if stored.objective == Some(ActiveObjective::FindSigil(stored_id)) {
+            objective = Some(ActiveObjective::FindSigil(item_id));
+        }
+    }
+
+    game.state.map = stored.map;
+    game.state.floor_provenance = stored.provenance;
+    game.state.map.clear_visible();
+    Some(RestoredFloor { entry_tile: stored.entry_tile, objective })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use crate::content::{ContentPack, LightingRules};
+    use crate::mapgen::{MAPGEN_VERSION, MapGenerator};
+
+    #[test]
+    fn revisiting_a_floor_restores_its_map_enemies_and_items() {
+        let mut game = Game::new(24680, &ContentPack::default(), GameMode::Ironman);
+        let floor_one = game.state.floor_index;
+        let tiles = game.state.map.tiles.clone();
+        let discovered = game.state.map.discovered.clone();
+        let enemy_count = game.state.actors.len() - 1;
+        let item_count = game.state.items.len();
+
+        game.descend_to_floor(floor_one + 1);
+        assert!(game.state.stored_floors.contains_key(&floor_one));
+        let hash_with_stored_floor = game.snapshot_hash();
+        game.state.stored_floors.get_mut(&floor_one).unwrap().map.discovered.fill(true);
+        assert_ne!(game.snapshot_hash(), hash_with_stored_floor, "stored floors are hashed");
+
+        game.descend_to_floor(floor_one);
+        assert_eq!(game.state.floor_index, floor_one);
+        assert_eq!(game.state.map.tiles, tiles);
+        assert_ne!(game.state.map.discovered, discovered, "the edited stored map came back");
+        assert_eq!(game.state.actors.len() - 1, enemy_count);
+        assert_eq!(game.state.items.len(), item_count);
+        assert!(game.state.stored_floors.contains_key(&(floor_one + 1)));
+        assert!(!game.state.stored_floors.contains_key(&floor_one));
+    }
+
+    #[test]
+    fn an_open_objective_and_the_floor_light_survive_a_revisit() {
+        let content = ContentPack {
+            floor_objectives: BTreeMap::from([(2, FloorObjective::SlayElite)]),
+            lighting: LightingRules { dark_floors: BTreeSet::from([2]), ..Default::default() },
+            ..ContentPack::default()
+        };
+        let mut game = Game::new(2688, &content, GameMode::Ironman);
+        game.descend_to_floor(2);
+        let Some(ActiveObjective::SlayElite(elite)) = game.state.floor_objective else {
+            panic!("expected an elite objective on floor 2");
+        };
+        let (elite_pos, braziers) =
+            (game.state.actors[elite].pos, game.state.map.light.braziers.clone());
+        let items = game.state.items.len();
+        assert_eq!(game.state.map.light.unlit_range, Some(3));
+
+        game.descend_to_floor(1);
+        let stored_hash = game.snapshot_hash();
+        game.state.stored_floors.get_mut(&2).unwrap().objective = None;
+        assert_ne!(game.snapshot_hash(), stored_hash, "the stored objective is hashed");
+        game.state.stored_floors.get_mut(&2).unwrap().objective =
+            Some(ActiveObjective::SlayElite(elite));
+        game.state.torch_ticks = 10;
+
+        game.descend_to_floor(2);
+        let Some(ActiveObjective::SlayElite(restored)) = game.state.floor_objective else {
+            panic!("the elite objective stays open");
+        };
+        assert_eq!(game.state.actors[restored].pos, elite_pos);
+        assert!(game.stairs_locked());
+        assert_eq!(game.state.map.light.braziers, braziers);
+        assert_eq!(game.state.map.light.unlit_range, None, "the carried torch lights the way");
+        assert_eq!(game.state.items.len(), items, "no second torch or sigil is placed");
+    }
+
+    #[test]
+    fn floor_provenance_follows_the_floor_across_a_revisit() {
+        let mut game = Game::new(2739, &ContentPack::default(), GameMode::Ironman);
+        let floor_one = game.state.floor_index;
+        let starter = FloorProvenance::authored(MapgenAlgorithm::StarterFloor);
+        assert_eq!(game.state.floor_provenance, starter);
+
+        game.descend_to_floor(floor_one + 1);
+        let generated = game.state.floor_provenance;
+        assert_eq!(generated.algorithm, MapgenAlgorithm::RoomsAndCorridors);
+        assert_eq!(generated.version, MAPGEN_VERSION);
+        let rebuilt = MapGenerator::new(2739, game.state.branch_profile).generate(floor_one + 1);
+        assert_eq!(rebuilt.provenance, generated);
+
+        game.descend_to_floor(floor_one);
+        assert_eq!(game.state.floor_provenance, starter);
+        game.descend_to_floor(floor_one + 1);
+        assert_eq!(game.state.floor_provenance, generated);
+    }
+}
@@ -19,7 +19,10 @@ fn descending_from_floor_one_loads_floor_two_with_different_map_state() {
             current_floor,
             next_floor,
             requires_branch_god_choice,
+            boons,
+            ..
         }) => {
+            assert!(boons.is_empty(), "no god offers boons before one is chosen");
             assert_eq!(current_floor, 1);
             assert_eq!(next_floor, Some(2));
             assert!(requires_branch_god_choice, "first descent should require branch choice");
@@ -43,14 +46,8 @@ fn floor_index_never_decreases_during_play() {
     for _ in 0..300 {
         let result = game.advance(8);
         match result.stop_reason {
-            AdvanceStopReason::Interrupted(Interrupt::LootFound { prompt_id, .. }) => {
-                game.apply_choice(prompt_id, Choice::KeepLoot).expect("keep loot");
-            }
-            AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { prompt_id, .. }) => {
-                game.apply_choice(prompt_id, Choice::Fight).expect("fight");
-            }
-            AdvanceStopReason::Interrupted(Interrupt::DoorBlocked { prompt_id, .. }) => {
-                game.apply_choice(prompt_id, Choice::OpenDoor).expect("open door");
+            AdvanceStopReason::Interrupted(Interrupt::PactAltar { prompt_id, .. }) => {
+                game.apply_choice(prompt_id, Choice::KeepPact).expect("keep pact");
             }
             AdvanceStopReason::Interrupted(int @ Interrupt::FloorTransition { prompt_id, .. }) => {
                 let choice = if matches!(
@@ -63,6 +60,11 @@ fn floor_index_never_decreases_during_play() {
                 };
                 game.apply_choice(prompt_id, choice).expect("descend");
             }
+            AdvanceStopReason::Interrupted(int) => {
+                // Keep loot, fight, open doors, seek objectives, and take the first drafted perk.
+                let choice = game.available_choices()[0].clone();
+                game.apply_choice(int.prompt_id(), choice).expect("first choice");
+            }
             AdvanceStopReason::Finished(_) => break,
             AdvanceStopReason::PausedAtBoundary { .. } | AdvanceStopReason::BudgetExhausted => {}
             AdvanceStopReason::EngineFailure(e) => panic!("Engine failure in test: {:?}", e),
@@ -115,21 +117,11 @@ fn branch_prompt_is_emitted_once_on_first_descent_only() {
     };
     game.apply_choice(first_prompt, Choice::DescendBranchAVeil).expect("select branch A");
 
-    let mut stairs = None;
-    for y in 0..game.state.map.internal_height {
-        for x in 0..game.state.map.internal_width {
-            let pos = Pos { y: y as i32, x: x as i32 };
-            if game.state.map.tile_at(pos) == TileKind::DownStairs {
-                stairs = Some(pos);
-                break;
-            }
-        }
-        if stairs.is_some() {
-            break;
-        }
-    }
+    let map = &game.state.map;
+    let stairs = map.positions().find(|pos| map.tile_at(*pos) == TileKind::DownStairs);
     let stairs = stairs.expect("floor 2 should have a stairs tile");
     game.state.actors[game.state.player_id].pos = stairs;
+    game.state.altar_tile = None;
 
     match game.advance(1).stop_reason {
         AdvanceStopReason::Interrupted(Interrupt::FloorTransition {
@@ -219,7 +211,7 @@ fn first_descent_rejects_plain_descend_and_requires_combined_choice() {
     };
 
     let result = game.apply_choice(prompt_id, Choice::Descend);
-    assert!(matches!(result, Err(GameError::InvalidChoice)));
+    assert!(matches!(result, Err(GameError::InvalidChoice { .. })));
 
     game.apply_choice(prompt_id, Choice::DescendBranchAForge)
         .expect("combined branch+god choice should apply");
@@ -241,20 +233,10 @@ fn non_first_descent_rejects_combined_choice_and_accepts_descend() {
     game.apply_choice(first_prompt, Choice::DescendBranchBVeil)
         .expect("first descent combined choice should apply");
 
-    let mut stairs = None;
-    for y in 0..game.state.map.internal_height {
-        for x in 0..game.state.map.internal_width {
-            let pos = Pos { y: y as i32, x: x as i32 };
-            if game.state.map.tile_at(pos) == TileKind::DownStairs {
-                stairs = Some(pos);
-                break;
-            }
-        }
-        if stairs.is_some() {
-            break;
-        }
-    }
+    let map = &game.state.map;
+    let stairs = map.positions().find(|pos| map.tile_at(*pos) == TileKind::DownStairs);
     game.state.actors[game.state.player_id].pos = stairs.expect("floor 2 stairs");
+    game.state.altar_tile = None;
 
     let prompt_id = match game.advance(1).stop_reason {
         AdvanceStopReason::Interrupted(Interrupt::FloorTransition {
@@ -268,7 +250,7 @@ fn non_first_descent_rejects_combined_choice_and_accepts_descend() {
         other => panic!("expected second floor transition interrupt, got {other:?}"),
     };
     let invalid = game.apply_choice(prompt_id, Choice::DescendBranchAForge);
-    assert!(matches!(invalid, Err(GameError::InvalidChoice)));
+    assert!(matches!(invalid, Err(GameError::InvalidChoice { .. })));
     game.apply_choice(prompt_id, Choice::Descend).expect("plain descend should apply");
 }
 
@@ -2,16 +2,20 @@
 //! This module exists to keep hashing concerns separate from simulation control code.
 //! It does not own replay execution or journal persistence policies.
 
-use std::hash::Hasher;
+use std::hash::{Hash, Hasher};
 
 use super::*;
 use crate::mapgen::BranchProfile;
+use crate::state::{Actor, StoredFloor};
 use xxhash_rust::xxh3::Xxh3;
 
+mod full_state;
+
 impl Game {
     pub fn snapshot_hash(&self) -> u64 {
         let mut hasher = Xxh3::new();
         hasher.write_u64(self.seed);
+        hasher.write_u8(self.difficulty as u8);
         hasher.write_u64(self.tick);
         hasher.write_u64(self.next_input_seq);
         hasher.write_u32(self.no_progress_ticks);
@@ -21,17 +25,79 @@ impl Game {
             BranchProfile::BranchA => 1,
             BranchProfile::BranchB => 2,
         });
+        self.state.floor_provenance.hash(&mut hasher);
         hasher.write_u8(match self.state.active_god {
             None => 0,
             Some(GodId::Veil) => 1,
             Some(GodId::Forge) => 2,
         });
+        match self.state.altar_tile {
+            Some(pos) => {
+                hasher.write_i32(pos.x);
+                hasher.write_i32(pos.y);
+            }
+            None => hasher.write_u8(0xFF),
+        }
+        hasher.write_u8(u8::from(self.state.pact_broken));
+        hasher.write_u8(self.state.curse_floors_remaining);
+        hasher.write_u8(self.state.fov_bonus);
+        hasher.write_u8(self.state.blink_charges);
+        hasher.write_u8(u8::from(self.state.braced));
+        hasher.write_u32(self.state.wait_ticks_remaining);
+        // Only a pending step is hashed, so runs that never step manually keep their hashes.
+        if let Some(direction) = self.state.manual_step {
+            hasher.write_u8(direction as u8);
+        }
+        hasher.write_u64(self.state.floor_entry_tick);
+        hasher.write_u8(self.state.wanderers_this_floor);
+        hasher.write_u32(self.state.player_xp);
+        hasher.write_u8(self.state.player_level);
+        hasher.write_u8(self.state.perk_drafts_pending);
+        for perk in &self.state.cursed_perks {
+            hasher.write(perk.as_bytes());
+        }
+        self.state.inventory.hash(&mut hasher);
+        self.state.director.hash(&mut hasher);
+        self.state.floor_objective.hash(&mut hasher);
+        hasher.write_u8(u8::from(self.stairs_lock_acknowledged));
+        for (target, until) in &self.snoozed_targets {
+            let (tag, pos) = match *target {
+                SnoozeTarget::Loot(pos) => (0, pos),
+                SnoozeTarget::Door(pos) => (1, pos),
+            };
+            hasher.write_u8(tag);
+            hasher.write_i32(pos.x);
+            hasher.write_i32(pos.y);
+            hasher.write_u64(*until);
+        }
+        hasher.write_u8(u8::from(self.state.map.light.dark));
+        hasher.write_u64(self.state.torch_ticks);
+        self.state.ambience.hash(&mut hasher);
+        for record in &self.state.completed_objectives {
+            hasher.write_u8(record.floor_index);
+            record.objective.hash(&mut hasher);
+            hasher.write_u64(record.completed_tick);
+        }
         let player = &self.state.actors[self.state.player_id];
         hasher.write_i32(player.pos.x);
         hasher.write_i32(player.pos.y);
         hasher.write_i32(self.state.sanctuary_tile.x);
         hasher.write_i32(self.state.sanctuary_tile.y);
         hasher.write_u8(u8::from(self.state.sanctuary_active));
+        hasher.write_u8(player.active_weapon_slot as u8);
+        hash_weapons(&mut hasher, player);
+        for (pos, marker) in &self.state.map.markers {
+            hasher.write_i32(pos.x);
+            hasher.write_i32(pos.y);
+            hasher.write_u8(*marker as u8);
+        }
+        for unique in &self.state.slain_uniques {
+            unique.hash(&mut hasher);
+        }
+        for (floor_index, floor) in &self.state.stored_floors {
+            hasher.write_u8(*floor_index);
+            hash_stored_floor(&mut hasher, floor);
+        }
         if let Some(intent) = self.state.auto_intent {
             hasher.write_i32(intent.target.x);
             hasher.write_i32(intent.target.y);
@@ -41,3 +107,77 @@ impl Game {
         hasher.finish()
     }
 }
+
+fn hash_weapons(hasher: &mut Xxh3, actor: &Actor) {
+    for weapon in [actor.equipped_weapon, actor.reserve_weapon] {
+        match weapon {
+            Some(instance) => {
+                hasher.write(instance.key.as_bytes());
+                hasher.write_u8(instance.enchant);
+                hasher.write_u8(u8::from(instance.cursed));
+            }
+            None => hasher.write_u8(0xFF),
+        }
+    }
+}
+
+fn hash_stored_floor(hasher: &mut Xxh3, floor: &StoredFloor) {
+    for (tile, discovered) in floor.map.tiles.iter().zip(&floor.map.discovered) {
+        tile.hash(hasher);
+        hasher.write_u8(u8::from(*discovered));
+    }
+    for actor in &floor.actors {
+        actor.kind.hash(hasher);
+        hasher.write_i32(actor.pos.x);
+        hasher.write_i32(actor.pos.y);
+        hasher.write_i32(actor.hp);
+        hasher.write_u8(actor.awareness);
+        hash_weapons(hasher, actor);
+    }
+    for item in &floor.items {
+        item.kind.hash(hasher);
+        hasher.write_i32(item.pos.x);
+        hasher.write_i32(item.pos.y);
+        hasher.write_u8(u8::from(item.cursed));
+    }
+    hasher.write_i32(floor.entry_tile.x);
+    hasher.write_i32(floor.entry_tile.y);
+    floor.provenance.hash(hasher);
+    floor.objective.hash(hasher);
+    hasher.write_u8(u8::from(floor.map.light.dark));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use rand_chacha::rand_core::Rng;
+
+    #[test]
+    fn full_state_hash_sees_what_the_snapshot_hash_skips() {
+        let mut game = Game::new(2755, &ContentPack::default(), GameMode::Ironman);
+        let (snapshot, full) = (game.snapshot_hash(), game.full_state_hash());
+        let enemy = game.state.actors.keys().find(|id| *id != game.state.player_id).unwrap();
+        game.state.actors[enemy].hp -= 1;
+        assert_eq!(game.snapshot_hash(), snapshot);
+        assert_ne!(game.full_state_hash(), full);
+
+        game.state.actors[enemy].hp += 1;
+        assert_eq!(game.full_state_hash(), full, "the hash is a pure function of the state");
+        game.state.active_perks.push(keys::PERK_SCOUT);
+        assert_ne!(game.full_state_hash(), full);
+    }
+
+    #[test]
+    fn full_state_hash_covers_the_policy_and_the_rng_position() {
+        let mut game = Game::new(2755, &ContentPack::default(), GameMode::Ironman);
+        let full = game.full_state_hash();
+        game.state.policy.auto_heal_if_below_threshold = Some(40);
+        assert_ne!(game.full_state_hash(), full);
+
+        game.state.policy.auto_heal_if_below_threshold = None;
+        assert_eq!(game.full_state_hash(), full);
+        game.rng.next_u32();
+        assert_ne!(game.full_state_hash(), full, "a drawn word moves the hash");
+    }
+}
@@ -0,0 +1,121 @@
+//! The full state hash recorded at every answered prompt and checked on replay.
+
+use std::hash::{Hash, Hasher};
+
+use xxhash_rust::xxh3::Xxh3;
+
+use super::super::*;
+use crate::state::{Actor, Item, Map};
+
+impl Game {
+    /// Canonical hash of the whole game state: everything `snapshot_hash` covers plus every
+    /// tile, actor, item, perk and policy setting on the current floor, the threat trace and
+    /// the RNG stream position. Actors and items are hashed in position order, so the hash
+    /// does not depend on slot ids. Fields are written one by one rather than through their
+    /// `Debug` text, so renaming a field or reformatting a type never moves the hash.
+    pub fn full_state_hash(&self) -> u64 {
+        let mut hasher = Xxh3::new();
+        hasher.write_u64(self.snapshot_hash());
+        hash_map(&mut hasher, &self.state.map);
+        let mut actors: Vec<&Actor> = self.state.actors.values().collect();
+        actors.sort_by_key(|actor| (ordering::row_major(actor.pos), actor.kind));
+        for actor in actors {
+            hash_actor(&mut hasher, actor);
+        }
+        let mut items: Vec<&Item> = self.state.items.values().collect();
+        items.sort_by_key(|item| (ordering::row_major(item.pos), item.kind));
+        for item in items {
+            item.kind.hash(&mut hasher);
+            hasher.write_i32(item.pos.x);
+            hasher.write_i32(item.pos.y);
+            hasher.write_u8(u8::from(item.cursed));
+        }
+        for perk in &self.state.active_perks {
+            hasher.write(perk.as_bytes());
+        }
+        for (potion, appearance) in &self.state.potion_appearances {
+            hasher.write(potion.as_bytes());
+            hasher.write(appearance.as_bytes());
+        }
+        for consumable in &self.state.identified_consumables {
+            hasher.write(consumable.as_bytes());
+        }
+        hasher.write_u32(self.state.kills_this_floor);
+        hash_policy(&mut hasher, &self.state.policy);
+        hasher.write_usize(self.state.threat_trace.len());
+        for trace in &self.state.threat_trace {
+            hasher.write_u64(trace.tick);
+            hasher.write_u16(trace.visible_enemy_count);
+            hasher.write_u32(trace.min_enemy_distance.unwrap_or(u32::MAX));
+            hasher.write_u8(u8::from(trace.retreat_triggered));
+        }
+        // Two runs that drew a different number of random words have already diverged.
+        hasher.write_u128(self.rng.get_word_pos());
+        hasher.finish()
+    }
+}
+
+fn hash_map(hasher: &mut Xxh3, map: &Map) {
+    hasher.write_usize(map.internal_width);
+    hasher.write_usize(map.internal_height);
+    for (index, tile) in map.tiles.iter().enumerate() {
+        tile.hash(hasher);
+        let flags = [map.discovered[index], map.visible[index], map.hazards[index]];
+        hasher.write_u8(flags.iter().fold(0, |bits, flag| bits << 1 | u8::from(*flag)));
+        match map.spreading_hazards[index] {
+            Some(hazard) => {
+                hasher.write_u8(hazard.kind as u8);
+                hasher.write_u8(hazard.intensity);
+            }
+            None => hasher.write_u8(0xFF),
+        }
+    }
+}
+
+fn hash_actor(hasher: &mut Xxh3, actor: &Actor) {
+    actor.kind.hash(hasher);
+    hasher.write_i32(actor.pos.x);
+    hasher.write_i32(actor.pos.y);
+    for stat in [actor.hp, actor.max_hp, actor.attack, actor.defense] {
+        hasher.write_i32(stat);
+    }
+    hasher.write_u8(actor.active_weapon_slot as u8);
+    actor.equipped_weapon.hash(hasher);
+    actor.reserve_weapon.hash(hasher);
+    hasher.write_u64(actor.next_action_tick);
+    hasher.write_u32(actor.speed);
+    hasher.write_u8(actor.awareness);
+    actor.unique.hash(hasher);
+    hasher.write_i32(actor.home.x);
+    hasher.write_i32(actor.home.y);
+}
+
+fn hash_policy(hasher: &mut Xxh3, policy: &Policy) {
+    hasher.write_u8(policy.fight_or_avoid as u8);
+    hasher.write_u8(policy.stance as u8);
+    hasher.write_usize(policy.target_priority.len());
+    for tag in &policy.target_priority {
+        hasher.write_u8(*tag as u8);
+    }
+    hasher.write_u8(policy.retreat_hp_threshold);
+    hasher.write_u16(policy.auto_heal_if_below_threshold.map_or(u16::MAX, u16::from));
+    hasher.write_u8(policy.position_intent as u8);
+    hasher.write_u8(policy.resource_aggression as u8);
+    hasher.write_u8(policy.exploration_mode as u8);
+    hasher.write_usize(policy.auto_pickup.len());
+    for rule in &policy.auto_pickup {
+        match &rule.filter {
+            PickupFilter::Item(key) => {
+                hasher.write_u8(0);
+                hasher.write(key.as_bytes());
+            }
+            PickupFilter::Potions => hasher.write_u8(1),
+            PickupFilter::Consumables => hasher.write_u8(2),
+            PickupFilter::Weapons => hasher.write_u8(3),
+            PickupFilter::Perks => hasher.write_u8(4),
+        }
+        hasher.write_u8(rule.action as u8);
+    }
+    hasher.write_u8(u8::from(policy.auto_fight_trivial));
+    hasher.write_u8(u8::from(policy.auto_resolve_encounters));
+}
@@ -0,0 +1,164 @@
+//! Deterministic cellular simulation of spreading fire and gas hazards.
+//! Each tick reads the previous grid and writes a fresh one, so scan order never matters.
+//! It does not own static mapgen hazards; those stay fixed in `Map::hazards`.
+
+use super::*;
+use crate::state::SpreadingHazard;
+
+/// Intensity lost by every hazard tile per tick.
+const HAZARD_DECAY_PER_TICK: u8 = 1;
+
+struct SpreadRule {
+    initial_intensity: u8,
+    /// Tiles below this intensity only smoulder and no longer spread.
+    min_spread_intensity: u8,
+    spread_loss: u8,
+}
+
+fn spread_rule(kind: HazardKind) -> SpreadRule {
+    match kind {
+        HazardKind::Fire => {
+            SpreadRule { initial_intensity: 5, min_spread_intensity: 3, spread_loss: 2 }
+        }
+        HazardKind::Gas => {
+            SpreadRule { initial_intensity: 6, min_spread_intensity: 2, spread_loss: 1 }
+        }
+        HazardKind::Rubble => {
+            SpreadRule { initial_intensity: 3, min_spread_intensity: u8::MAX, spread_loss: 0 }
+        }
+    }
+}
+
+fn merge_hazard(slot: &mut Option<SpreadingHazard>, incoming: SpreadingHazard) {
+    let stronger = match *slot {
+        Some(current) => (incoming.intensity, incoming.kind) > (current.intensity, current.kind),
+        None => true,
+    };
+    if stronger {
+        *slot = Some(incoming);
+    }
+}
+
+impl Game {
+    /// Start a fire or gas cloud on `pos` at its kind's initial intensity.
+    pub(super) fn release_hazard(&mut self, pos: Pos, kind: HazardKind) {
+        let Some(idx) = self.state.map.index(pos) else {
+            return;
+        };
+        if self.state.map.tile_at(pos) == TileKind::Wall {
+            return;
+        }
+        let intensity = spread_rule(kind).initial_intensity;
+        if self.state.map.spreading_hazards[idx].is_none() {
+            self.state.map.revision += 1;
+        }
+        merge_hazard(
+            &mut self.state.map.spreading_hazards[idx],
+            SpreadingHazard { kind, intensity },
+        );
+    }
+
+    pub(super) fn step_spreading_hazards(&mut self) {
+        let map = &self.state.map;
+        if map.spreading_hazards.iter().all(Option::is_none) {
+            return;
+        }
+        let mut next = vec![None; map.spreading_hazards.len()];
+        for (idx, cell) in map.spreading_hazards.iter().enumerate() {
+            let Some(hazard) = *cell else {
+                continue;
+            };
+            let decayed = hazard.intensity.saturating_sub(HAZARD_DECAY_PER_TICK);
+            if decayed > 0 {
+                merge_hazard(&mut next[idx], SpreadingHazard { intensity: decayed, ..hazard });
+            }
+
+            let rule = spread_rule(hazard.kind);
+            let spread = hazard.intensity.saturating_sub(rule.spread_loss);
+            if hazard.intensity < rule.min_spread_intensity || spread == 0 {
+                continue;
+            }
+            for neighbor in neighbors(map.pos_at(idx)) {
+                if matches!(map.tile_at(neighbor), TileKind::Floor | TileKind::DownStairs)
+                    && let Some(n_idx) = map.index(neighbor)
+                {
+                    merge_hazard(&mut next[n_idx], SpreadingHazard { intensity: spread, ..hazard });
+                }
+            }
+        }
+        // Only where hazards lie matters to routes; intensity changes keep the revision.
+        let covered = |cells: &[Option<SpreadingHazard>]| -> Vec<bool> {
+            cells.iter().map(Option::is_some).collect()
+        };
+        if covered(&next) != covered(&map.spreading_hazards) {
+            self.state.map.revision += 1;
+        }
+        self.state.map.spreading_hazards = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::state::Map;
+
+    fn open_game() -> Game {
+        let mut game = Game::new(606, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = Map::new(15, 9);
+        game
+    }
+
+    fn hazard_tiles(game: &Game) -> usize {
+        game.state.map.spreading_hazards.iter().flatten().count()
+    }
+
+    #[test]
+    fn fire_spreads_then_burns_out() {
+        let mut game = open_game();
+        let origin = Pos { y: 4, x: 7 };
+        game.release_hazard(origin, HazardKind::Fire);
+
+        game.step_spreading_hazards();
+        assert_eq!(hazard_tiles(&game), 5, "fire should reach the four orthogonal neighbours");
+        assert!(game.state.map.is_hazard(Pos { y: 4, x: 8 }));
+
+        for _ in 0..8 {
+            game.step_spreading_hazards();
+        }
+        assert_eq!(hazard_tiles(&game), 0, "fire should decay away");
+        assert!(!game.state.map.is_hazard(origin));
+    }
+
+    #[test]
+    fn gas_spreads_further_than_fire_and_stops_at_walls() {
+        let mut fire = open_game();
+        let mut gas = open_game();
+        let origin = Pos { y: 4, x: 7 };
+        fire.release_hazard(origin, HazardKind::Fire);
+        gas.release_hazard(origin, HazardKind::Gas);
+        for _ in 0..3 {
+            fire.step_spreading_hazards();
+            gas.step_spreading_hazards();
+        }
+        assert!(hazard_tiles(&gas) > hazard_tiles(&fire));
+        assert!(gas.state.map.spreading_hazard_at(Pos { y: 0, x: 7 }).is_none());
+    }
+
+    #[test]
+    fn spread_is_deterministic_and_blocks_auto_paths() {
+        let mut first = open_game();
+        let mut second = open_game();
+        for game in [&mut first, &mut second] {
+            game.release_hazard(Pos { y: 2, x: 3 }, HazardKind::Gas);
+            game.release_hazard(Pos { y: 6, x: 10 }, HazardKind::Fire);
+            for _ in 0..2 {
+                game.step_spreading_hazards();
+            }
+        }
+        assert_eq!(first.state.map.spreading_hazards, second.state.map.spreading_hazards);
+        assert!(!first.state.map.is_discovered_walkable_safe(Pos { y: 2, x: 3 }));
+    }
+}
@@ -0,0 +1,85 @@
+//! HUD snapshot assembly. Reads state only, so frontends may call it every frame.
+
+use super::*;
+use crate::state::Actor;
+use threat::danger_tags_for_kind;
+
+impl Game {
+    /// Everything a status panel needs this frame: vitals, floor, god, policy summary,
+    /// current intent, the nearest visible enemies and the latest log events.
+    pub fn hud_snapshot(&self) -> HudSnapshot {
+        let player = &self.state.actors[self.state.player_id];
+        let policy = &self.state.policy;
+        let mut visible: Vec<(EntityId, &Actor)> = self
+            .state
+            .actors
+            .iter()
+            .filter(|(id, actor)| {
+                *id != self.state.player_id && self.state.map.is_visible(actor.pos)
+            })
+            .collect();
+        visible.sort_by(|(_, a), (_, b)| ordering::nearest_actor(Some(player.pos), a, b));
+        let threats = visible
+            .into_iter()
+            .take(HUD_THREAT_COUNT)
+            .map(|(enemy, actor)| HudThreat {
+                enemy,
+                kind: actor.kind,
+                hp: actor.hp,
+                distance: manhattan(player.pos, actor.pos),
+                danger_tags: danger_tags_for_kind(actor.kind),
+            })
+            .collect();
+        HudSnapshot {
+            tick: self.tick,
+            hp: player.hp,
+            max_hp: player.max_hp,
+            floor_index: self.state.floor_index,
+            active_god: self.state.active_god,
+            policy: PolicySummary {
+                fight_or_avoid: policy.fight_or_avoid,
+                stance: policy.stance,
+                retreat_hp_threshold: policy.retreat_hp_threshold,
+                auto_heal_if_below_threshold: policy.auto_heal_if_below_threshold,
+            },
+            intent: self.state.auto_intent,
+            threats,
+            recent_events: self.log.recent(HUD_EVENT_COUNT).cloned().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::add_goblin;
+    use crate::state::Map;
+
+    #[test]
+    fn snapshot_ranks_visible_threats_and_keeps_the_latest_events() {
+        let mut game = Game::new(2759, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let mut map = Map::new(20, 5);
+        map.visible.fill(true);
+        game.state.map = map;
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 1 };
+        let far = add_goblin(&mut game, Pos { y: 2, x: 9 });
+        let near = add_goblin(&mut game, Pos { y: 2, x: 3 });
+        add_goblin(&mut game, Pos { y: 1, x: 15 });
+        add_goblin(&mut game, Pos { y: 3, x: 16 });
+        for room in 0..=HUD_EVENT_COUNT as u16 {
+            game.log.push(LogEvent::RoomEntered { room: RoomId(room) });
+        }
+
+        let hud = game.hud_snapshot();
+        assert_eq!(hud.threats.len(), HUD_THREAT_COUNT);
+        assert_eq!((hud.threats[0].enemy, hud.threats[0].distance), (near, 2));
+        assert_eq!((hud.threats[1].enemy, hud.threats[1].distance), (far, 8));
+        assert_eq!(hud.threats[0].danger_tags, vec![DangerTag::Melee]);
+        assert_eq!(hud.recent_events.first(), Some(&LogEvent::RoomEntered { room: RoomId(1) }));
+        assert_eq!(hud.recent_events.len(), HUD_EVENT_COUNT);
+        assert_eq!(hud.hp, game.state.actors[game.state.player_id].hp);
+    }
+}
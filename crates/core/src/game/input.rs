@@ -0,0 +1,22 @@
+//! Journaled inputs applied to a live game. Replay, the stepper and lockstep all dispatch
+//! through here, so a new input kind is wired up once.
+
+use super::*;
+use crate::journal::InputPayload;
+
+impl Game {
+    /// Apply one journaled input the way the original run accepted it.
+    pub fn apply_input(&mut self, input: &InputPayload) -> Result<(), GameError> {
+        match input {
+            InputPayload::Choice { prompt_id, choice } => {
+                self.apply_choice(*prompt_id, choice.clone())
+            }
+            InputPayload::PolicyUpdate { update, .. } => self.apply_policy_update(update.clone()),
+            InputPayload::SwapActiveWeapon { .. } => self.apply_swap_weapon(),
+            InputPayload::Brace { .. } => self.apply_brace(),
+            InputPayload::Wait { ticks, .. } => self.apply_wait(*ticks),
+            InputPayload::SetMarker { pos, marker, .. } => self.set_marker(*pos, *marker),
+            InputPayload::ManualStep { direction, .. } => self.apply_manual_step(*direction),
+        }
+    }
+}
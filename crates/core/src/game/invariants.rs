@@ -0,0 +1,141 @@
+//! Structural self-checks that catch state corruption close to the tick that caused it.
+//! With the `invariant-checks` feature, debug builds run them after every advance and choice.
+
+#[cfg(any(test, feature = "invariant-checks"))]
+use std::collections::BTreeMap;
+
+use super::*;
+#[cfg(any(test, feature = "invariant-checks"))]
+use crate::game::prompts::PendingPromptKind;
+
+impl Game {
+    /// Verify the state is internally consistent, describing the first broken invariant.
+    #[cfg(any(test, feature = "invariant-checks"))]
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let map = &self.state.map;
+        if !self.state.actors.contains_key(self.state.player_id) {
+            return Err("the player actor is missing".to_string());
+        }
+
+        let mut occupied = BTreeMap::new();
+        for (id, actor) in &self.state.actors {
+            if map.tile_at(actor.pos) == TileKind::Wall {
+                return Err(format!(
+                    "{:?} stands on a wall or off the map at {:?}",
+                    actor.kind, actor.pos
+                ));
+            }
+            // The player may slip through an enemy it avoided or sneaked past; enemies never stack.
+            if id == self.state.player_id {
+                continue;
+            }
+            if let Some(other) = occupied.insert(actor.pos, id) {
+                return Err(format!(
+                    "{:?} and {:?} share {:?}",
+                    self.state.actors[other].kind, actor.kind, actor.pos
+                ));
+            }
+        }
+        if let Some(item) = self.state.items.values().find(|item| !map.in_bounds(item.pos)) {
+            return Err(format!("{:?} lies out of bounds at {:?}", item.kind, item.pos));
+        }
+
+        if let Some(prompt) = &self.pending_prompt {
+            self.check_prompt_consistency(prompt.id, &prompt.kind)?;
+        }
+
+        let clone_hash = self.clone().snapshot_hash();
+        if clone_hash != self.snapshot_hash() {
+            return Err("snapshot hash changed after cloning the game".to_string());
+        }
+        Ok(())
+    }
+
+    #[cfg(any(test, feature = "invariant-checks"))]
+    fn check_prompt_consistency(
+        &self,
+        id: ChoicePromptId,
+        kind: &PendingPromptKind,
+    ) -> Result<(), String> {
+        if id.0 != self.next_input_seq {
+            return Err(format!(
+                "pending prompt {} does not match input sequence {}",
+                id.0, self.next_input_seq
+            ));
+        }
+        let consistent = match kind {
+            PendingPromptKind::Loot { item } => self.state.items.contains_key(*item),
+            PendingPromptKind::EnemyEncounter { enemies, primary_enemy, .. } => {
+                enemies.contains(primary_enemy)
+                    && enemies.iter().all(|enemy| self.state.actors.contains_key(*enemy))
+            }
+            PendingPromptKind::DoorBlocked { pos } => {
+                self.state.map.tile_at(*pos) == TileKind::ClosedDoor
+            }
+            PendingPromptKind::FloorTransition { current_floor, .. } => {
+                *current_floor == self.state.floor_index
+            }
+            PendingPromptKind::PactAltar { pos, current_god } => {
+                self.state.altar_tile == Some(*pos) && self.state.active_god == Some(*current_god)
+            }
+            PendingPromptKind::StairsLocked { objective } => {
+                self.state.floor_objective.is_some_and(|active| active.objective() == *objective)
+            }
+            PendingPromptKind::PerkDraft { offer, .. } => {
+                self.state.perk_drafts_pending > 0
+                    && offer.iter().all(|id| !self.state.active_perks.contains(id))
+            }
+        };
+        if consistent {
+            Ok(())
+        } else {
+            Err(format!(
+                "pending {:?} prompt refers to state that no longer exists",
+                kind.prompt_kind()
+            ))
+        }
+    }
+
+    /// Panic on a broken invariant in debug builds with `invariant-checks`; a no-op otherwise.
+    pub(in crate::game) fn debug_check_invariants(&self) {
+        #[cfg(all(debug_assertions, feature = "invariant-checks"))]
+        if let Err(violation) = self.check_invariants() {
+            panic!("invariant violated at tick {}: {violation}", self.tick);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    #[test]
+    fn fresh_and_advanced_games_hold_invariants() {
+        let mut game = Game::new(2693, &ContentPack::default(), GameMode::Ironman);
+        assert_eq!(game.check_invariants(), Ok(()));
+        for _ in 0..40 {
+            game.advance(25);
+            assert_eq!(game.check_invariants(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn stacked_actors_and_stale_prompts_are_reported() {
+        let mut game = Game::new(2693, &ContentPack::default(), GameMode::Ironman);
+        let player_pos = game.state.actors[game.state.player_id].pos;
+        let enemies: Vec<EntityId> =
+            game.state.actors.keys().filter(|id| *id != game.state.player_id).collect();
+        assert!(enemies.len() >= 2, "a fresh floor has several enemies");
+        let mut stacked = game.clone();
+        stacked.state.actors[enemies[1]].pos = stacked.state.actors[enemies[0]].pos;
+        assert!(stacked.check_invariants().is_err_and(|e| e.contains("share")));
+
+        game.pending_prompt = Some(PendingPrompt {
+            id: ChoicePromptId(game.next_input_seq),
+            kind: PendingPromptKind::DoorBlocked { pos: player_pos },
+            queued: Vec::new(),
+        });
+        assert!(game.check_invariants().is_err_and(|e| e.contains("DoorBlocked")));
+    }
+}
@@ -4,12 +4,21 @@
 
 use super::*;
 
+mod auto_heal;
+mod auto_pickup;
+mod consumable_stacks;
 mod consumables;
+mod curses;
+mod enchantment;
+mod encumbrance;
 mod fortification;
+mod identification;
 mod inventory;
 mod search;
 mod spatial_effects;
 
+pub(super) use identification::shuffled_potion_appearances;
+
 impl Game {
     pub(super) fn apply_item_effect(&mut self, kind: ItemKind) {
         match kind {
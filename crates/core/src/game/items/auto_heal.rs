@@ -0,0 +1,86 @@
+//! Healing potions in the inventory, drunk by policy once HP runs low. Only potions the
+//! player has identified are drunk; the policy cannot tell an unknown potion heals.
+
+use super::*;
+use crate::content::ActionKind;
+
+impl Game {
+    fn is_known_healing_potion(&self, id: &'static str) -> bool {
+        self.consumable_label(id) == id && self.consumable_heal(id) > 0
+    }
+
+    /// Drink the first known healing potion in the inventory if HP is below the auto-heal
+    /// threshold, spending the player's action; false if the policy has nothing to do.
+    pub(in crate::game) fn try_auto_heal(&mut self) -> bool {
+        let Some(threshold) = self.state.policy.auto_heal_if_below_threshold else {
+            return false;
+        };
+        let player = &self.state.actors[self.state.player_id];
+        if (player.hp * 100) / player.max_hp >= i32::from(threshold) {
+            return false;
+        }
+        let Some(slot) =
+            self.state.inventory.iter().position(|slot| self.is_known_healing_potion(slot.id))
+        else {
+            return false;
+        };
+        let id = self.take_from_inventory(slot).expect("slot was just found");
+        self.log.push(LogEvent::PotionDrunk { id });
+        self.apply_consumable_effect(id);
+        self.spend_player_action(ActionKind::UseItem);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+    use crate::state::{InventorySlot, Item};
+
+    fn game_with_potion_underfoot(potion: &'static str) -> Game {
+        let mut game = Game::new(2759, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let pos = game.state.actors[game.state.player_id].pos;
+        let kind = ItemKind::Consumable(potion);
+        let id = game.state.items.insert(Item { id: ItemId::default(), kind, pos, cursed: false });
+        game.state.items[id].id = id;
+        game.apply_policy_update(PolicyUpdate::AutoHealIfBelowThreshold(Some(50))).unwrap();
+        game
+    }
+
+    #[test]
+    fn kept_healing_potions_are_stowed_and_drunk_once_hp_drops() {
+        let potion = keys::CONSUMABLE_MINOR_HP_POT;
+        let mut game = game_with_potion_underfoot(potion);
+        game.state.identified_consumables.insert(potion);
+        let item = game.state.items.keys().next().unwrap();
+        game.resolve_keep_loot_choice(item);
+        let stack = InventorySlot { id: potion, count: 1 };
+        assert_eq!(game.state.inventory, vec![stack]);
+
+        let player_id = game.state.player_id;
+        let max_hp = game.state.actors[player_id].max_hp;
+        game.state.actors[player_id].hp = (max_hp + 1) / 2;
+        game.advance(1);
+        assert_eq!(game.state.inventory, vec![stack], "at the threshold, not below it");
+
+        game.state.actors[player_id].hp = 1;
+        game.advance(1);
+        assert!(game.state.inventory.is_empty());
+        assert!(game.state.actors[player_id].hp > 1);
+        assert!(game.log.contains(&LogEvent::PotionDrunk { id: potion }));
+    }
+
+    #[test]
+    fn unidentified_potions_are_kept_but_never_drunk_by_policy() {
+        let mut game = game_with_potion_underfoot(keys::CONSUMABLE_MINOR_HP_POT);
+        let item = game.state.items.keys().next().unwrap();
+        game.resolve_keep_loot_choice(item);
+        game.state.actors[game.state.player_id].hp = 1;
+        game.advance(1);
+        assert_eq!(game.state.inventory.len(), 1);
+        assert_eq!(game.state.actors[game.state.player_id].hp, 1);
+    }
+}
@@ -0,0 +1,106 @@
+//! Policy-driven auto-pickup that resolves loot without raising a prompt.
+//! Only items no rule matches fall through to the loot interrupt.
+
+use super::*;
+use crate::content::{ActionKind, UNIDENTIFIED_POTIONS};
+
+fn rule_matches(filter: &PickupFilter, kind: ItemKind) -> bool {
+    match (filter, kind) {
+        (
+            PickupFilter::Item(key),
+            ItemKind::Weapon(id) | ItemKind::Consumable(id) | ItemKind::Perk(id),
+        ) => key == id,
+        (PickupFilter::Potions, ItemKind::Consumable(id)) => UNIDENTIFIED_POTIONS.contains(&id),
+        (PickupFilter::Consumables, ItemKind::Consumable(_))
+        | (PickupFilter::Weapons, ItemKind::Weapon(_))
+        | (PickupFilter::Perks, ItemKind::Perk(_)) => true,
+        _ => false,
+    }
+}
+
+impl Game {
+    /// First auto-pickup rule action matching `kind`, if any.
+    pub fn auto_pickup_action(&self, kind: ItemKind) -> Option<PickupAction> {
+        self.state
+            .policy
+            .auto_pickup
+            .iter()
+            .find(|rule| rule_matches(&rule.filter, kind))
+            .map(|rule| rule.action)
+    }
+
+    /// Keep or discard `item` per policy, spending the action's time; false if no rule applies.
+    /// A sensed curse or a full inventory always raises the loot prompt so the player sees
+    /// the warning.
+    pub(in crate::game) fn try_auto_pickup(&mut self, item: ItemId) -> bool {
+        if self.curse_detected(item) || self.inventory_full_for(self.state.items[item].kind) {
+            return false;
+        }
+        match self.auto_pickup_action(self.state.items[item].kind) {
+            Some(PickupAction::Keep) => {
+                self.resolve_keep_loot_choice(item);
+                self.spend_player_action(ActionKind::PickUp);
+            }
+            Some(PickupAction::Discard) => {
+                self.resolve_discard_loot_choice(item);
+                self.spend_player_action(ActionKind::Discard);
+            }
+            None => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::state::Item;
+
+    fn game_with_item_underfoot(kind: ItemKind, rules: Vec<AutoPickupRule>) -> Game {
+        let mut game = Game::new(515, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let pos = game.state.actors[game.state.player_id].pos;
+        let id = game.state.items.insert(Item { id: ItemId::default(), kind, pos, cursed: false });
+        game.state.items[id].id = id;
+        game.apply_policy_update(PolicyUpdate::AutoPickup(rules)).unwrap();
+        game
+    }
+
+    fn rule(filter: PickupFilter, action: PickupAction) -> AutoPickupRule {
+        AutoPickupRule { filter, action }
+    }
+
+    #[test]
+    fn matching_rules_resolve_loot_without_prompting() {
+        let potion = ItemKind::Consumable(keys::CONSUMABLE_HASTE_POTION);
+        let mut game =
+            game_with_item_underfoot(potion, vec![rule(PickupFilter::Potions, PickupAction::Keep)]);
+        let result = game.advance(1);
+        assert!(!matches!(result.stop_reason, AdvanceStopReason::Interrupted(_)));
+        assert!(game.state.items.is_empty());
+        assert!(game.log.contains(&LogEvent::ItemPickedUp { kind: potion }));
+
+        let rusty = ItemKind::Weapon(keys::WEAPON_RUSTY_SWORD);
+        let rules = vec![
+            rule(PickupFilter::Item(keys::WEAPON_RUSTY_SWORD.to_string()), PickupAction::Discard),
+            rule(PickupFilter::Weapons, PickupAction::Keep),
+        ];
+        let mut game = game_with_item_underfoot(rusty, rules);
+        game.advance(1);
+        assert!(game.log.contains(&LogEvent::ItemDiscarded { kind: rusty }), "first rule wins");
+    }
+
+    #[test]
+    fn unmatched_items_still_raise_the_loot_prompt() {
+        let scroll = ItemKind::Consumable(keys::CONSUMABLE_ENCHANT_SCROLL);
+        let mut game =
+            game_with_item_underfoot(scroll, vec![rule(PickupFilter::Potions, PickupAction::Keep)]);
+        let result = game.advance(1);
+        assert!(matches!(
+            result.stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::LootFound { kind, .. }) if kind == scroll
+        ));
+    }
+}
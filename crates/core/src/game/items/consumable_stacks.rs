@@ -0,0 +1,139 @@
+//! The consumable inventory: kept consumables stack by id in a limited number of slots and
+//! are used or dropped by slot index from a prompt.
+
+use super::*;
+use crate::content::{ActionKind, INVENTORY_CAPACITY};
+use crate::state::InventorySlot;
+
+impl Game {
+    /// Whether keeping `kind` would find no room: a consumable with no stack of its own while
+    /// every slot is taken.
+    pub(in crate::game) fn inventory_full_for(&self, kind: ItemKind) -> bool {
+        let ItemKind::Consumable(id) = kind else {
+            return false;
+        };
+        self.state.inventory.len() >= INVENTORY_CAPACITY
+            && !self.state.inventory.iter().any(|slot| slot.id == id)
+    }
+
+    /// Add one `id` to its stack, or to a new slot; false when the inventory has no room.
+    pub(in crate::game) fn stow_consumable(&mut self, id: &'static str) -> bool {
+        if let Some(slot) = self.state.inventory.iter_mut().find(|slot| slot.id == id) {
+            slot.count += 1;
+        } else if self.state.inventory.len() < INVENTORY_CAPACITY {
+            self.state.inventory.push(InventorySlot { id, count: 1 });
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Take one consumable out of `slot`; a slot whose stack runs out is removed.
+    pub(super) fn take_from_inventory(&mut self, slot: usize) -> Option<&'static str> {
+        let stack = self.state.inventory.get_mut(slot)?;
+        stack.count -= 1;
+        let id = stack.id;
+        if stack.count == 0 {
+            self.state.inventory.remove(slot);
+        }
+        Some(id)
+    }
+
+    /// Use one consumable from `slot`, spending the player's action; false for an empty slot.
+    pub(in crate::game) fn use_inventory_item(&mut self, slot: usize) -> bool {
+        let Some(id) = self.take_from_inventory(slot) else {
+            return false;
+        };
+        self.log.push(LogEvent::ConsumableUsed { id });
+        self.apply_consumable_effect(id);
+        self.spend_player_action(ActionKind::UseItem);
+        true
+    }
+
+    /// Throw away the whole stack in `slot`; false for an empty slot.
+    pub(in crate::game) fn drop_inventory_item(&mut self, slot: usize) -> bool {
+        if slot >= self.state.inventory.len() {
+            return false;
+        }
+        let stack = self.state.inventory.remove(slot);
+        self.log.push(LogEvent::ItemDiscarded { kind: ItemKind::Consumable(stack.id) });
+        self.spend_player_action(ActionKind::Discard);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+    use crate::state::Item;
+
+    const FILLERS: [&str; INVENTORY_CAPACITY] = [
+        keys::CONSUMABLE_TELEPORT_RUNE,
+        keys::CONSUMABLE_SMOKE_BOMB,
+        keys::CONSUMABLE_SHRAPNEL_BOMB,
+        keys::CONSUMABLE_WHETSTONE,
+        keys::CONSUMABLE_TORCH,
+    ];
+
+    fn game_on_loot_prompt(potion: &'static str) -> (Game, ChoicePromptId, bool) {
+        let mut game = Game::new(2760, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let pos = game.state.actors[game.state.player_id].pos;
+        let kind = ItemKind::Consumable(potion);
+        let id = game.state.items.insert(Item { id: ItemId::default(), kind, pos, cursed: false });
+        game.state.items[id].id = id;
+        for filler in FILLERS {
+            assert!(game.stow_consumable(filler));
+        }
+        let AdvanceStopReason::Interrupted(Interrupt::LootFound {
+            prompt_id, inventory_full, ..
+        }) = game.advance(1).stop_reason
+        else {
+            panic!("expected a loot prompt");
+        };
+        (game, prompt_id, inventory_full)
+    }
+
+    #[test]
+    fn consumables_stack_and_a_full_inventory_uses_kept_loot_at_once() {
+        let (mut game, prompt_id, inventory_full) =
+            game_on_loot_prompt(keys::CONSUMABLE_MINOR_HP_POT);
+        assert!(inventory_full);
+        assert!(game.stow_consumable(keys::CONSUMABLE_TORCH), "a stack always has room");
+        assert_eq!(game.state.inventory[4], InventorySlot { id: keys::CONSUMABLE_TORCH, count: 2 });
+
+        game.state.actors[game.state.player_id].hp = 1;
+        game.apply_choice(prompt_id, Choice::KeepLoot).unwrap();
+        assert_eq!(game.state.inventory.len(), INVENTORY_CAPACITY);
+        assert!(game.state.actors[game.state.player_id].hp > 1, "the potion was drunk");
+    }
+
+    #[test]
+    fn inventory_slots_are_used_or_dropped_from_the_loot_prompt() {
+        let (mut game, prompt_id, _) = game_on_loot_prompt(keys::CONSUMABLE_MINOR_HP_POT);
+        let available = game.available_choices();
+        assert!(
+            available.contains(&Choice::UseItem(4)) && available.contains(&Choice::DropItem(4))
+        );
+        let out_of_range = Choice::UseItem(INVENTORY_CAPACITY as u8);
+        assert!(game.clone().apply_choice(prompt_id, out_of_range).is_err());
+
+        game.apply_choice(prompt_id, Choice::DropItem(1)).unwrap();
+        let smoke = ItemKind::Consumable(keys::CONSUMABLE_SMOKE_BOMB);
+        assert!(game.log.contains(&LogEvent::ItemDiscarded { kind: smoke }));
+        let AdvanceStopReason::Interrupted(Interrupt::LootFound {
+            prompt_id, inventory_full, ..
+        }) = game.advance(10).stop_reason
+        else {
+            panic!("the loot is still underfoot");
+        };
+        assert!(!inventory_full, "dropping a stack made room");
+
+        game.apply_choice(prompt_id, Choice::UseItem(0)).unwrap();
+        let rune = keys::CONSUMABLE_TELEPORT_RUNE;
+        assert!(game.log.contains(&LogEvent::ConsumableUsed { id: rune }));
+        assert_eq!(game.state.inventory.len(), INVENTORY_CAPACITY - 2);
+    }
+}
@@ -2,12 +2,18 @@
 
 use super::*;
 use crate::content::keys;
+use crate::geometry::TargetTemplate;
+
+/// Steps from the player the shrapnel bomb's blast reaches.
+const SHRAPNEL_BLAST_RADIUS: u32 = 6;
 
 impl Game {
     pub(super) fn apply_consumable_effect(&mut self, id: &'static str) {
+        self.identify_consumable(id);
         match id {
-            keys::CONSUMABLE_MINOR_HP_POT => self.apply_heal(10),
-            keys::CONSUMABLE_MAJOR_HP_POT => self.apply_heal(25),
+            keys::CONSUMABLE_MINOR_HP_POT | keys::CONSUMABLE_MAJOR_HP_POT => {
+                self.apply_heal(self.consumable_heal(id));
+            }
             keys::CONSUMABLE_TELEPORT_RUNE => self.apply_teleport_rune(),
             keys::CONSUMABLE_FORTIFICATION_SCROLL => self.apply_fortification_scroll(),
             keys::CONSUMABLE_STASIS_HOURGLASS => self.delay_visible_enemies(50),
@@ -16,11 +22,15 @@ impl Game {
             keys::CONSUMABLE_SHRAPNEL_BOMB => self.apply_shrapnel_bomb(),
             keys::CONSUMABLE_HASTE_POTION => self.apply_haste_potion(),
             keys::CONSUMABLE_IRON_SKIN_POTION => self.apply_iron_skin_potion(),
+            keys::CONSUMABLE_WHETSTONE => self.enchant_active_weapon(1),
+            keys::CONSUMABLE_ENCHANT_SCROLL => self.enchant_active_weapon(2),
+            keys::CONSUMABLE_TORCH => self.light_torch(),
             _ => {}
         }
     }
 
     fn apply_heal(&mut self, amount: i32) {
+        let amount = self.difficulty_scaling.scale_healing(amount);
         let player = self.state.actors.get_mut(self.state.player_id).expect("player should exist");
         player.hp = (player.hp + amount).min(player.max_hp);
     }
@@ -32,15 +42,28 @@ impl Game {
         }
     }
 
+    /// The smoke clears the threat trace; enemy memory keeps where the enemies were.
     fn apply_smoke_bomb(&mut self) {
+        self.remember_enemies();
         self.state.threat_trace.clear();
         self.suppressed_enemy = None;
+        for enemy_id in self.visible_enemy_ids_sorted(None) {
+            self.release_hazard(self.state.actors[enemy_id].pos, HazardKind::Gas);
+        }
         self.delay_visible_enemies(20);
     }
 
+    /// Shreds every visible enemy within the blast radius of the player, nearest first.
     fn apply_shrapnel_bomb(&mut self) {
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let blast = TargetTemplate::Radius { radius: SHRAPNEL_BLAST_RADIUS };
+        let blast_tiles = blast.tiles(&self.state.map, player_pos);
         let mut defeated = Vec::new();
-        for enemy_id in self.visible_enemy_ids_sorted(None) {
+        for enemy_id in self.visible_enemy_ids_sorted(Some(player_pos)) {
+            if !blast_tiles.contains(&self.state.actors[enemy_id].pos) {
+                continue;
+            }
+            self.release_hazard(self.state.actors[enemy_id].pos, HazardKind::Fire);
             let actor = self.state.actors.get_mut(enemy_id).expect("enemy should exist");
             actor.hp -= 5;
             if actor.hp <= 0 {
@@ -48,7 +71,10 @@ impl Game {
             }
         }
         for enemy_id in defeated {
-            self.state.actors.remove(enemy_id);
+            if let Some(enemy) = self.state.actors.remove(enemy_id) {
+                self.record_slain_unique(enemy.unique);
+                self.grant_kill_xp(enemy.kind);
+            }
         }
     }
 
@@ -65,3 +91,30 @@ impl Game {
         player.hp += 5;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::add_goblin;
+    use crate::state::Map;
+
+    #[test]
+    fn shrapnel_bomb_spares_enemies_beyond_the_blast_radius() {
+        let mut game = Game::new(2751, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let mut map = Map::new(20, 5);
+        map.visible.fill(true);
+        game.state.map = map;
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 1 };
+
+        let near = add_goblin(&mut game, Pos { y: 2, x: 7 });
+        let far = add_goblin(&mut game, Pos { y: 2, x: 8 });
+        let (near_hp, far_hp) = (game.state.actors[near].hp, game.state.actors[far].hp);
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_SHRAPNEL_BOMB));
+
+        assert_eq!(game.state.actors.get(near).map(|actor| actor.hp), Some(near_hp - 5));
+        assert_eq!(game.state.actors[far].hp, far_hp);
+    }
+}
@@ -0,0 +1,150 @@
+//! Cursed weapons and perks: each one held adds attack and costs defense, and a cursed
+//! weapon cannot leave its slot. Kneeling at a pact altar cleanses every curse.
+
+use super::*;
+use crate::content::keys;
+use crate::state::ItemInstance;
+
+/// Attack each cursed item adds while the player holds it.
+const CURSED_ATTACK_BONUS: i32 = 3;
+/// Defense each cursed item costs while the player holds it.
+const CURSED_DEFENSE_PENALTY: i32 = 2;
+
+impl Game {
+    /// Cursed weapons in either slot plus cursed perks.
+    pub fn cursed_item_count(&self) -> i32 {
+        let player = &self.state.actors[self.state.player_id];
+        let weapons = [player.equipped_weapon, player.reserve_weapon]
+            .into_iter()
+            .filter(|weapon| weapon.is_some_and(|instance| instance.cursed))
+            .count();
+        (weapons + self.state.cursed_perks.len()) as i32
+    }
+
+    pub(in crate::game) fn cursed_attack_bonus(&self) -> i32 {
+        self.cursed_item_count() * CURSED_ATTACK_BONUS
+    }
+
+    pub(in crate::game) fn cursed_defense_penalty(&self) -> i32 {
+        self.cursed_item_count() * CURSED_DEFENSE_PENALTY
+    }
+
+    /// Whether the active weapon is cursed and so cannot be swapped away.
+    pub(in crate::game) fn active_weapon_cursed(&self) -> bool {
+        let player = &self.state.actors[self.state.player_id];
+        let weapon = match player.active_weapon_slot {
+            WeaponSlot::Primary => player.equipped_weapon,
+            WeaponSlot::Reserve => player.reserve_weapon,
+        };
+        weapon.is_some_and(|instance| instance.cursed)
+    }
+
+    /// Whether the player can tell `item` is cursed before keeping it; the Scout perk senses curses.
+    pub(in crate::game) fn curse_detected(&self, item: ItemId) -> bool {
+        self.state.items[item].cursed && self.state.active_perks.contains(&keys::PERK_SCOUT)
+    }
+
+    /// Apply a kept cursed item; consumables carry no curse and apply as usual.
+    pub(in crate::game) fn apply_cursed_item(&mut self, kind: ItemKind) {
+        match kind {
+            ItemKind::Weapon(id) => {
+                self.equip_weapon(ItemInstance { cursed: true, ..ItemInstance::new(id) });
+            }
+            ItemKind::Perk(id) => {
+                self.apply_perk_pickup(id);
+                if !self.state.cursed_perks.contains(&id) {
+                    self.state.cursed_perks.push(id);
+                }
+            }
+            ItemKind::Consumable(_) => return self.apply_item_effect(kind),
+        }
+        self.log.push(LogEvent::CurseBound { kind });
+    }
+
+    /// Lift every item curse; cleansed items keep their ordinary effects.
+    pub(in crate::game) fn cleanse_curses(&mut self) {
+        let player = self.state.actors.get_mut(self.state.player_id).expect("player should exist");
+        for instance in
+            [&mut player.equipped_weapon, &mut player.reserve_weapon].into_iter().flatten()
+        {
+            instance.cursed = false;
+        }
+        self.state.cursed_perks.clear();
+        self.log.push(LogEvent::CursesCleansed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::MapBuilder;
+    use crate::state::Item;
+
+    fn game_on_cursed_blade() -> (Game, ChoicePromptId, bool) {
+        let mut game = Game::new(2731, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(7, 7).discover_all().build();
+        game.state.active_perks.push(keys::PERK_SCOUT);
+        let pos = Pos { y: 3, x: 3 };
+        let kind = ItemKind::Weapon(keys::WEAPON_BLOOD_AXE);
+        let item = game.state.items.insert(Item { id: ItemId::default(), kind, pos, cursed: true });
+        game.state.items[item].id = item;
+        game.state.actors[game.state.player_id].pos = pos;
+
+        let AdvanceStopReason::Interrupted(Interrupt::LootFound {
+            prompt_id, curse_detected, ..
+        }) = game.advance(1).stop_reason
+        else {
+            panic!("expected a loot prompt");
+        };
+        (game, prompt_id, curse_detected)
+    }
+
+    #[test]
+    fn kept_cursed_weapon_binds_until_an_altar_cleanses_it() {
+        let (mut game, prompt_id, curse_detected) = game_on_cursed_blade();
+        assert!(curse_detected, "Scout senses the curse at the loot prompt");
+        let defense = game.effective_player_defense();
+
+        game.apply_choice(prompt_id, Choice::KeepLoot).expect("keeping cursed loot is allowed");
+        assert_eq!(game.cursed_item_count(), 1);
+        assert_eq!(game.effective_player_defense(), defense - CURSED_DEFENSE_PENALTY);
+        assert!(
+            game.log
+                .contains(&LogEvent::CurseBound { kind: ItemKind::Weapon(keys::WEAPON_BLOOD_AXE) })
+        );
+        game.state.actors[game.state.player_id].reserve_weapon =
+            Some(ItemInstance::new(keys::WEAPON_IRON_MACE));
+        game.at_pause_boundary = true;
+        assert_eq!(game.apply_swap_weapon(), Err(GameError::CursedWeapon));
+
+        game.state.active_god = Some(GodId::Veil);
+        game.state.altar_tile = Some(Pos { y: 3, x: 4 });
+        let AdvanceStopReason::Interrupted(Interrupt::PactAltar { prompt_id, can_cleanse, .. }) =
+            game.advance(1).stop_reason
+        else {
+            panic!("expected a pact altar prompt");
+        };
+        assert!(can_cleanse);
+        game.apply_choice(prompt_id, Choice::CleanseCurses).expect("cleansing is on offer");
+        assert_eq!(game.cursed_item_count(), 0);
+        assert_eq!(game.state.altar_tile, None);
+        game.at_pause_boundary = true;
+        assert!(game.apply_swap_weapon().is_ok());
+    }
+
+    #[test]
+    fn curses_go_unnoticed_without_scout_and_cursed_perks_bind_too() {
+        let (mut game, _, _) = game_on_cursed_blade();
+        game.state.active_perks.clear();
+        let item = game.state.items.keys().next().expect("the blade is still there");
+        assert!(!game.curse_detected(item));
+
+        game.apply_cursed_item(ItemKind::Perk(keys::PERK_SWIFT));
+        assert_eq!(game.state.cursed_perks, vec![keys::PERK_SWIFT]);
+        assert!(game.state.active_perks.contains(&keys::PERK_SWIFT));
+        assert_eq!(game.cursed_attack_bonus(), CURSED_ATTACK_BONUS);
+    }
+}
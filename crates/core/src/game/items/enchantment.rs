@@ -0,0 +1,85 @@
+//! Whetstone and enchant-scroll upgrades for the weapon in the active slot.
+//! Enchantment lives on the weapon's `ItemInstance`, so replacing that weapon clears it.
+
+use super::*;
+use crate::content::MAX_WEAPON_ENCHANT;
+use crate::state::ItemInstance;
+
+impl Game {
+    pub(in crate::game) fn active_weapon_enchant(&self) -> i32 {
+        let player = &self.state.actors[self.state.player_id];
+        let weapon = match player.active_weapon_slot {
+            WeaponSlot::Primary => player.equipped_weapon,
+            WeaponSlot::Reserve => player.reserve_weapon,
+        };
+        weapon.map_or(0, |instance| i32::from(instance.enchant))
+    }
+
+    pub(super) fn enchant_active_weapon(&mut self, amount: u8) {
+        let player = self.state.actors.get_mut(self.state.player_id).expect("player should exist");
+        let weapon: Option<&mut ItemInstance> = match player.active_weapon_slot {
+            WeaponSlot::Primary => player.equipped_weapon.as_mut(),
+            WeaponSlot::Reserve => player.reserve_weapon.as_mut(),
+        };
+        if let Some(instance) = weapon {
+            instance.enchant = instance.enchant.saturating_add(amount).min(MAX_WEAPON_ENCHANT);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+
+    fn armed_game() -> Game {
+        let mut game = Game::new(5150, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.apply_weapon_pickup(keys::WEAPON_RUSTY_SWORD);
+        game.apply_weapon_pickup(keys::WEAPON_IRON_MACE);
+        game
+    }
+
+    #[test]
+    fn upgrades_stack_on_active_weapon_up_to_cap() {
+        let mut game = armed_game();
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_WHETSTONE));
+        assert_eq!(game.active_weapon_enchant(), 1);
+
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_ENCHANT_SCROLL));
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_ENCHANT_SCROLL));
+        assert_eq!(game.active_weapon_enchant(), i32::from(MAX_WEAPON_ENCHANT));
+        let reserve = game.state.actors[game.state.player_id].reserve_weapon;
+        assert_eq!(reserve.map(|weapon| weapon.enchant), Some(0));
+    }
+
+    #[test]
+    fn enchantment_stays_with_its_weapon_across_swaps_and_clears_on_replace() {
+        let mut game = armed_game();
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_WHETSTONE));
+
+        game.apply_swap_weapon().expect("swap should succeed at pause boundary");
+        assert_eq!(game.active_weapon_enchant(), 0);
+        game.apply_swap_weapon().expect("swap should succeed at pause boundary");
+        assert_eq!(game.active_weapon_enchant(), 1);
+
+        game.apply_weapon_pickup(keys::WEAPON_BLOOD_AXE);
+        assert_eq!(game.active_weapon_enchant(), 0);
+    }
+
+    #[test]
+    fn enchantment_is_covered_by_snapshot_hash() {
+        let mut game = armed_game();
+        let before = game.snapshot_hash();
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_WHETSTONE));
+        assert_ne!(game.snapshot_hash(), before);
+    }
+
+    #[test]
+    fn upgrades_without_a_weapon_are_wasted() {
+        let mut game = Game::new(5150, &ContentPack::default(), GameMode::Ironman);
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_ENCHANT_SCROLL));
+        assert_eq!(game.active_weapon_enchant(), 0);
+    }
+}
@@ -0,0 +1,90 @@
+//! Carry weight, capacity, and the action slowdown from being over capacity.
+//! It does not own which items are carried; weapon slots stay in inventory handling.
+
+use super::*;
+use crate::content::{BASE_CARRY_CAPACITY, keys};
+
+impl Game {
+    /// Total weight of the weapons the player is carrying in both slots.
+    pub fn player_carry_weight(&self) -> u32 {
+        let player = &self.state.actors[self.state.player_id];
+        [player.equipped_weapon, player.reserve_weapon]
+            .into_iter()
+            .flatten()
+            .map(|weapon| self.weapon_weight(weapon.key))
+            .sum()
+    }
+
+    pub fn player_carry_capacity(&self) -> u32 {
+        let mut capacity = BASE_CARRY_CAPACITY;
+        if self.state.active_perks.contains(&keys::PERK_TOUGHNESS) {
+            capacity += self.perk_tuning.toughness_carry_bonus;
+        }
+        capacity
+    }
+
+    pub fn is_player_encumbered(&self) -> bool {
+        self.player_carry_weight() > self.player_carry_capacity()
+    }
+
+    /// Scale an action's ticks by how far the carried weight exceeds capacity.
+    pub(in crate::game) fn apply_encumbrance(&self, ticks: u32) -> u32 {
+        let weight = self.player_carry_weight();
+        let capacity = self.player_carry_capacity().max(1);
+        if weight <= capacity {
+            return ticks;
+        }
+        (ticks * weight).div_ceil(capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ActionKind, ContentPack};
+    use crate::state::ItemInstance;
+
+    fn game_with_weapons(primary: &'static str, reserve: &'static str) -> Game {
+        let mut game = Game::new(4242, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let player = game.state.actors.get_mut(game.state.player_id).expect("player should exist");
+        player.equipped_weapon = Some(ItemInstance::new(primary));
+        player.reserve_weapon = Some(ItemInstance::new(reserve));
+        game
+    }
+
+    #[test]
+    fn light_loadout_keeps_base_action_costs() {
+        let game = game_with_weapons(keys::WEAPON_RUSTY_SWORD, keys::WEAPON_PHASE_DAGGER);
+        assert_eq!(game.player_carry_weight(), 4);
+        assert!(!game.is_player_encumbered());
+        assert_eq!(game.player_action_ticks(ActionKind::SwapWeapon), 10);
+    }
+
+    #[test]
+    fn overloaded_player_pays_more_until_toughness_raises_capacity() {
+        let mut game = game_with_weapons(keys::WEAPON_IRON_MACE, keys::WEAPON_BLOOD_AXE);
+        assert_eq!(game.player_carry_weight(), 13);
+        assert!(game.is_player_encumbered());
+        assert_eq!(game.player_action_ticks(ActionKind::SwapWeapon), 13);
+        assert_eq!(game.player_action_ticks(ActionKind::Move), 2);
+
+        game.state.active_perks.push(keys::PERK_TOUGHNESS);
+        assert!(!game.is_player_encumbered());
+        assert_eq!(game.player_action_ticks(ActionKind::Move), 1);
+    }
+
+    #[test]
+    fn keeping_heavy_loot_over_capacity_slows_movement() {
+        let mut game = game_with_weapons(keys::WEAPON_IRON_MACE, keys::WEAPON_RUSTY_SWORD);
+        assert_eq!(game.player_action_ticks(ActionKind::Move), 1);
+
+        game.apply_weapon_pickup(keys::WEAPON_BLOOD_AXE);
+        assert_eq!(game.player_carry_weight(), 10);
+        game.state.actors[game.state.player_id].active_weapon_slot = WeaponSlot::Reserve;
+        game.apply_weapon_pickup(keys::WEAPON_STEEL_LONGSWORD);
+        assert_eq!(game.player_carry_weight(), 12);
+        assert_eq!(game.player_action_ticks(ActionKind::Move), 2);
+    }
+}
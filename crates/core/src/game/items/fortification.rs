@@ -9,38 +9,38 @@ impl Game {
         let player_pos = self.state.actors[self.state.player_id].pos;
         let occupied_positions: BTreeSet<Pos> =
             self.state.actors.values().map(|actor| actor.pos).collect();
-        let mut fortified_map = self.state.map.clone();
-        let mut reachable_before = reachable_discovered_walkable_tiles(&fortified_map, player_pos);
-        let had_intent_before = choose_frontier_intent(&fortified_map, player_pos).is_some();
+        // Walls go up in place and come straight back down if they cut the player off.
+        let map = &mut self.state.map;
+        let mut reachable_before = reachable_discovered_walkable_tiles(map, player_pos);
+        let had_intent_before =
+            choose_frontier_intent(map, player_pos, &[], Aggro::Conserve).is_some();
 
         for neighbor in neighbors(player_pos) {
-            if !fortified_map.is_discovered_walkable(neighbor)
-                || fortified_map.tile_at(neighbor) == TileKind::DownStairs
+            if !map.is_discovered_walkable(neighbor)
+                || map.tile_at(neighbor) == TileKind::DownStairs
                 || occupied_positions.contains(&neighbor)
-                || is_frontier_candidate(&fortified_map, neighbor)
+                || is_frontier_candidate(map, neighbor)
             {
                 continue;
             }
 
-            let original_tile = fortified_map.tile_at(neighbor);
-            fortified_map.set_tile(neighbor, TileKind::Wall);
+            let original_tile = map.tile_at(neighbor);
+            map.set_tile(neighbor, TileKind::Wall);
 
-            let reachable_after = reachable_discovered_walkable_tiles(&fortified_map, player_pos);
+            let reachable_after = reachable_discovered_walkable_tiles(map, player_pos);
             let preserves_reachable_component = reachable_before
                 .iter()
                 .all(|pos| *pos == neighbor || reachable_after.contains(pos));
-            let preserves_progress_intent =
-                !had_intent_before || choose_frontier_intent(&fortified_map, player_pos).is_some();
+            let preserves_progress_intent = !had_intent_before
+                || choose_frontier_intent(map, player_pos, &[], Aggro::Conserve).is_some();
 
             if preserves_reachable_component && preserves_progress_intent {
                 reachable_before = reachable_after;
             } else {
-                fortified_map.set_tile(neighbor, original_tile);
+                map.set_tile(neighbor, original_tile);
             }
         }
-        self.state.map = fortified_map;
-        let radius = self.get_fov_radius();
-        compute_fov(&mut self.state.map, self.state.actors[self.state.player_id].pos, radius);
+        self.refresh_fov(self.state.actors[self.state.player_id].pos);
     }
 }
 
@@ -0,0 +1,85 @@
+//! Unidentified potion appearances and identify-on-use bookkeeping.
+//! The appearance shuffle is seeded from the run seed so replays see the same labels.
+
+use std::collections::BTreeMap;
+
+use rand_chacha::ChaCha8Rng;
+use rand_chacha::rand_core::{Rng, SeedableRng};
+
+use super::*;
+use crate::content::{POTION_APPEARANCES, UNIDENTIFIED_POTIONS};
+
+const APPEARANCE_SHUFFLE_STREAM: u64 = 0xA11C_E5ED_0F00_D5ED;
+
+pub(in crate::game) fn shuffled_potion_appearances(
+    seed: u64,
+) -> BTreeMap<&'static str, &'static str> {
+    let mut appearances = POTION_APPEARANCES;
+    let mut rng = ChaCha8Rng::seed_from_u64(seed ^ APPEARANCE_SHUFFLE_STREAM);
+    for i in (1..appearances.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        appearances.swap(i, j);
+    }
+    UNIDENTIFIED_POTIONS.into_iter().zip(appearances).collect()
+}
+
+impl Game {
+    /// Label the player sees for a consumable: its id once known, otherwise its appearance.
+    pub fn consumable_label(&self, id: &'static str) -> &'static str {
+        match self.state.potion_appearances.get(id) {
+            Some(appearance) if !self.state.identified_consumables.contains(id) => appearance,
+            _ => id,
+        }
+    }
+
+    pub(super) fn identify_consumable(&mut self, id: &'static str) {
+        if self.state.potion_appearances.contains_key(id)
+            && self.state.identified_consumables.insert(id)
+        {
+            self.log.push(LogEvent::ConsumableIdentified { id });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+
+    #[test]
+    fn appearance_shuffle_is_stable_per_seed_and_varies_across_seeds() {
+        let first = shuffled_potion_appearances(77);
+        assert_eq!(first, shuffled_potion_appearances(77));
+        assert_eq!(first.len(), UNIDENTIFIED_POTIONS.len());
+
+        let differs = (0..16).any(|seed| shuffled_potion_appearances(seed) != first);
+        assert!(differs, "expected at least one seed to shuffle appearances differently");
+    }
+
+    #[test]
+    fn first_use_identifies_potion_for_rest_of_run() {
+        let mut game = Game::new(31337, &ContentPack::default(), GameMode::Ironman);
+        let potion = keys::CONSUMABLE_HASTE_POTION;
+        let appearance = game.state.potion_appearances[potion];
+        assert_eq!(game.consumable_label(potion), appearance);
+
+        game.apply_item_effect(ItemKind::Consumable(potion));
+        game.apply_item_effect(ItemKind::Consumable(potion));
+
+        assert_eq!(game.consumable_label(potion), potion);
+        let identified = game
+            .log()
+            .iter()
+            .filter(|event| matches!(event, LogEvent::ConsumableIdentified { .. }))
+            .count();
+        assert_eq!(identified, 1, "identification should be logged only once");
+    }
+
+    #[test]
+    fn non_potion_consumables_are_always_known() {
+        let mut game = Game::new(31337, &ContentPack::default(), GameMode::Ironman);
+        assert_eq!(game.consumable_label(keys::CONSUMABLE_SMOKE_BOMB), keys::CONSUMABLE_SMOKE_BOMB);
+        game.apply_item_effect(ItemKind::Consumable(keys::CONSUMABLE_SMOKE_BOMB));
+        assert!(game.state.identified_consumables.is_empty());
+    }
+}
@@ -1,31 +1,57 @@
 //! Inventory policy for equipping weapons and activating perks.
 
 use super::*;
+use crate::state::{Actor, ItemInstance};
 
 impl Game {
     pub(in crate::game) fn active_player_weapon(&self) -> Option<&'static str> {
         let player = &self.state.actors[self.state.player_id];
-        match player.active_weapon_slot {
+        let weapon = match player.active_weapon_slot {
             WeaponSlot::Primary => player.equipped_weapon,
             WeaponSlot::Reserve => player.reserve_weapon,
-        }
+        };
+        weapon.map(|instance| instance.key)
     }
 
     pub(super) fn apply_weapon_pickup(&mut self, id: &'static str) {
+        self.equip_weapon(ItemInstance::new(id));
+    }
+
+    /// Fill an empty slot, else replace the active weapon; cursed weapons are never replaced,
+    /// so a weapon kept while both slots are cursed is lost.
+    pub(super) fn equip_weapon(&mut self, instance: ItemInstance) {
         let player = self.state.actors.get_mut(self.state.player_id).expect("player should exist");
-        if player.equipped_weapon.is_none() {
-            player.equipped_weapon = Some(id);
+        let replaceable = |weapon: Option<ItemInstance>| weapon.is_none_or(|held| !held.cursed);
+        let active = player.active_weapon_slot;
+        let inactive = match active {
+            WeaponSlot::Primary => WeaponSlot::Reserve,
+            WeaponSlot::Reserve => WeaponSlot::Primary,
+        };
+        let slot = if player.equipped_weapon.is_none() {
+            WeaponSlot::Primary
         } else if player.reserve_weapon.is_none() {
-            player.reserve_weapon = Some(id);
+            WeaponSlot::Reserve
+        } else if replaceable(Self::weapon_in(player, active)) {
+            active
+        } else if replaceable(Self::weapon_in(player, inactive)) {
+            inactive
         } else {
-            match player.active_weapon_slot {
-                WeaponSlot::Primary => player.equipped_weapon = Some(id),
-                WeaponSlot::Reserve => player.reserve_weapon = Some(id),
-            }
+            return;
+        };
+        match slot {
+            WeaponSlot::Primary => player.equipped_weapon = Some(instance),
+            WeaponSlot::Reserve => player.reserve_weapon = Some(instance),
+        }
+    }
+
+    fn weapon_in(player: &Actor, slot: WeaponSlot) -> Option<ItemInstance> {
+        match slot {
+            WeaponSlot::Primary => player.equipped_weapon,
+            WeaponSlot::Reserve => player.reserve_weapon,
         }
     }
 
-    pub(super) fn apply_perk_pickup(&mut self, id: &'static str) {
+    pub(in crate::game) fn apply_perk_pickup(&mut self, id: &'static str) {
         if !self.state.active_perks.contains(&id) {
             self.state.active_perks.push(id);
         }
@@ -0,0 +1,123 @@
+//! Kill experience, level-ups, and the perk drafts each level-up earns.
+//! Drafts are rolled from the run seed and level, so a replay sees the same offer.
+
+use rand_chacha::ChaCha8Rng;
+use rand_chacha::rand_core::{Rng, SeedableRng};
+
+use super::*;
+use crate::content::keys;
+
+const PERK_DRAFT_STREAM: u64 = 0x9E6C_D4AF_7D12_A3B5;
+/// Perks offered per draft when enough unowned ones remain.
+const PERK_DRAFT_SIZE: usize = 3;
+/// Total XP needed to reach level 2, 3, and so on; kills grant their kind's base HP.
+const LEVEL_XP_THRESHOLDS: [u32; 4] = [60, 150, 270, 420];
+/// Perks a draft can offer; the Floor Sigil only makes sense on the floor it was found on.
+const DRAFTABLE_PERKS: [&str; 10] = [
+    keys::PERK_TOUGHNESS,
+    keys::PERK_SWIFT,
+    keys::PERK_BERSERKER_RHYTHM,
+    keys::PERK_PACIFISTS_BOUNTY,
+    keys::PERK_SNIPERS_EYE,
+    keys::PERK_IRON_WILL,
+    keys::PERK_BLOODLUST,
+    keys::PERK_SCOUT,
+    keys::PERK_RECKLESS_STRIKE,
+    keys::PERK_SHADOW_STEP,
+];
+
+/// Up to three distinct draftable perks for reaching `level`, skipping any in `owned`.
+pub(super) fn roll_perk_draft(seed: u64, level: u8, owned: &[&'static str]) -> Vec<&'static str> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed ^ PERK_DRAFT_STREAM ^ u64::from(level));
+    let mut pool: Vec<_> = DRAFTABLE_PERKS.into_iter().filter(|id| !owned.contains(id)).collect();
+    let mut offer = Vec::new();
+    while offer.len() < PERK_DRAFT_SIZE && !pool.is_empty() {
+        let index = (rng.next_u64() % pool.len() as u64) as usize;
+        offer.push(pool.remove(index));
+    }
+    offer
+}
+
+impl Game {
+    /// Credit the XP for killing an enemy of `kind`, owing one perk draft per level gained.
+    pub(super) fn grant_kill_xp(&mut self, kind: ActorKind) {
+        let xp = u32::try_from(self.enemy_base_stats(kind).hp).unwrap_or(0);
+        self.state.player_xp += xp;
+        while let Some(&threshold) =
+            LEVEL_XP_THRESHOLDS.get(usize::from(self.state.player_level) - 1)
+            && self.state.player_xp >= threshold
+        {
+            self.state.player_level += 1;
+            self.state.perk_drafts_pending += 1;
+            self.log.push(LogEvent::LeveledUp { level: self.state.player_level });
+        }
+    }
+
+    /// The level the oldest unanswered draft was earned at.
+    pub(super) fn next_perk_draft_level(&self) -> u8 {
+        self.state.player_level + 1 - self.state.perk_drafts_pending
+    }
+
+    /// Activate the perk at `index` of `offer`; false when the index is not on offer.
+    pub(super) fn resolve_perk_draft_choice(&mut self, offer: &[&'static str], index: u8) -> bool {
+        let Some(&id) = offer.get(usize::from(index)) else {
+            return false;
+        };
+        self.apply_perk_pickup(id);
+        self.state.perk_drafts_pending -= 1;
+        self.log.push(LogEvent::PerkDrafted { id });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::MapBuilder;
+
+    #[test]
+    fn drafts_are_stable_distinct_and_skip_owned_perks() {
+        let offer = roll_perk_draft(2730, 2, &[]);
+        assert_eq!(offer, roll_perk_draft(2730, 2, &[]));
+        assert_eq!(offer.len(), PERK_DRAFT_SIZE);
+        assert!(offer.iter().all(|id| offer.iter().filter(|other| *other == id).count() == 1));
+
+        let owned = &DRAFTABLE_PERKS[..8];
+        let tail = roll_perk_draft(2730, 5, owned);
+        assert_eq!(tail.len(), 2);
+        assert!(tail.iter().all(|id| !owned.contains(id)));
+    }
+
+    #[test]
+    fn leveling_up_raises_a_draft_whose_pick_becomes_active() {
+        let mut game = Game::new(2730, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(7, 7).discover_all().build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 3, x: 3 };
+        game.state.active_perks.push(keys::PERK_SWIFT);
+
+        game.grant_kill_xp(ActorKind::Goblin);
+        assert_eq!(game.state.player_level, 1);
+        game.grant_kill_xp(ActorKind::AbyssalWarden);
+        assert_eq!((game.state.player_level, game.state.perk_drafts_pending), (2, 1));
+
+        let AdvanceStopReason::Interrupted(Interrupt::PerkDraft { prompt_id, level, offer }) =
+            game.advance(1).stop_reason
+        else {
+            panic!("expected a perk draft prompt");
+        };
+        assert_eq!(level, 2);
+        assert_eq!(offer, roll_perk_draft(2730, 2, &[keys::PERK_SWIFT]));
+        assert!(matches!(
+            game.apply_choice(prompt_id, Choice::DraftPerk(3)),
+            Err(GameError::InvalidChoice { .. })
+        ));
+
+        game.apply_choice(prompt_id, Choice::DraftPerk(1)).expect("offered perk should apply");
+        assert_eq!(game.state.active_perks, vec![keys::PERK_SWIFT, offer[1]]);
+        assert_eq!(game.state.perk_drafts_pending, 0);
+        assert!(game.log.contains(&LogEvent::PerkDrafted { id: offer[1] }));
+    }
+}
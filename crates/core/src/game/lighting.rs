@@ -0,0 +1,165 @@
+//! Light on Dark floors: braziers light fixed areas and a carried torch burns down each tick.
+//! Veil followers standing in shadow without a torch are harder for enemies to notice.
+//! It does not own sight itself; `compute_fov` reads the floor light this module maintains.
+
+use std::collections::BTreeSet;
+
+use super::visibility::has_direct_line_of_sight;
+use super::*;
+use crate::content::keys;
+use crate::state::{FloorLight, Item};
+
+/// Farthest Manhattan distance at which an enemy notices a Veil follower hidden in shadow.
+pub(super) const VEIL_SHADOW_NOTICE_RADIUS: u32 = 2;
+
+impl Game {
+    /// Light a freshly generated floor. Dark floors get a brazier in every other room
+    /// and a torch waiting in the entry room; other floors stay fully lit.
+    pub(super) fn light_floor(&mut self, entry: Pos) {
+        self.state.map.light = FloorLight::default();
+        if self.lighting.dark_floors.contains(&self.state.floor_index) {
+            let map = &self.state.map;
+            let entry_room = map.room_at(entry);
+            let rooms: BTreeSet<RoomId> = map.rooms.iter().flatten().copied().collect();
+            let braziers: Vec<Pos> = rooms
+                .into_iter()
+                .filter(|room| room.0 % 2 == 1 && Some(*room) != entry_room)
+                .filter_map(|room| {
+                    let tiles: Vec<Pos> = map.room_tiles(room).collect();
+                    tiles.get(tiles.len() / 2).copied()
+                })
+                .collect();
+            let radius = self.lighting.brazier_radius;
+            let lit = map
+                .positions()
+                .map(|pos| {
+                    braziers.iter().any(|brazier| {
+                        let (dy, dx) = (pos.y - brazier.y, pos.x - brazier.x);
+                        dy * dy + dx * dx <= radius * radius
+                            && has_direct_line_of_sight(map, *brazier, pos)
+                    })
+                })
+                .collect();
+            self.state.map.light = FloorLight { dark: true, braziers, lit, unlit_range: None };
+            if let Some(room) = entry_room {
+                self.place_torch(room, entry);
+            }
+            self.update_unlit_range();
+            self.refresh_fov(entry);
+        }
+    }
+
+    /// Bring a revisited floor's stored light up to date with the torch the player carries
+    /// now, then look around from `entry`.
+    pub(super) fn relight_floor(&mut self, entry: Pos) {
+        self.update_unlit_range();
+        self.refresh_fov(entry);
+    }
+
+    /// Burn one tick of torchlight on a Dark floor and refresh how far the player sees in the dark.
+    pub(super) fn step_light(&mut self) {
+        if self.state.map.light.dark && self.state.torch_ticks > 0 {
+            self.state.torch_ticks -= 1;
+        }
+        self.update_unlit_range();
+    }
+
+    pub(super) fn light_torch(&mut self) {
+        self.state.torch_ticks += self.lighting.torch_ticks;
+        self.update_unlit_range();
+    }
+
+    /// Whether a Veil follower at `pos` is hidden in shadow, with no torch giving them away.
+    pub(super) fn veiled_in_shadow(&self, pos: Pos) -> bool {
+        self.state.active_god == Some(GodId::Veil)
+            && self.state.torch_ticks == 0
+            && self.state.map.is_shadowed(pos)
+    }
+
+    fn update_unlit_range(&mut self) {
+        let light = &mut self.state.map.light;
+        light.unlit_range =
+            (light.dark && self.state.torch_ticks == 0).then_some(self.lighting.dark_fov_radius);
+    }
+
+    fn place_torch(&mut self, room: RoomId, entry: Pos) {
+        let taken: Vec<Pos> = self
+            .state
+            .actors
+            .values()
+            .map(|actor| actor.pos)
+            .chain(self.state.items.values().map(|item| item.pos))
+            .collect();
+        let Some(pos) = self.state.map.room_tiles(room).find(|pos| {
+            *pos != entry && self.state.map.tile_at(*pos) == TileKind::Floor && !taken.contains(pos)
+        }) else {
+            return;
+        };
+        let kind = ItemKind::Consumable(keys::CONSUMABLE_TORCH);
+        let item =
+            self.state.items.insert(Item { id: ItemId::default(), kind, pos, cursed: false });
+        self.state.items[item].id = item;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, LightingRules};
+    use crate::game::test_support::{MapBuilder, add_goblin};
+
+    fn dark_content(floor_index: u8) -> ContentPack {
+        let lighting =
+            LightingRules { dark_floors: BTreeSet::from([floor_index]), ..Default::default() };
+        ContentPack { lighting, ..ContentPack::default() }
+    }
+
+    #[test]
+    fn dark_floors_limit_sight_to_lit_tiles_until_a_torch_burns() {
+        let mut game = Game::new(2710, &dark_content(2), GameMode::Ironman);
+        game.descend_to_floor(2);
+        let map = &game.state.map;
+        assert!(map.light.dark && !map.light.braziers.is_empty());
+        assert_eq!(map.light.unlit_range, Some(3));
+
+        let entry = game.state.actors[game.state.player_id].pos;
+        for pos in map.positions().filter(|pos| map.is_visible(*pos)) {
+            let (dy, dx) = (pos.y - entry.y, pos.x - entry.x);
+            assert!(dy * dy + dx * dx <= 9 || map.is_lit(pos), "{pos:?} is unlit and too far");
+        }
+        let torch = game
+            .state
+            .items
+            .values()
+            .find(|item| item.kind == ItemKind::Consumable(keys::CONSUMABLE_TORCH));
+        let torch = torch.expect("a torch waits in the entry room");
+        assert_eq!(map.room_at(torch.pos), map.room_at(entry));
+
+        game.light_torch();
+        assert_eq!(game.state.map.light.unlit_range, None);
+        for _ in 0..400 {
+            game.step_light();
+        }
+        assert_eq!(game.state.torch_ticks, 0);
+        assert_eq!(game.state.map.light.unlit_range, Some(3), "the torch has burned out");
+    }
+
+    #[test]
+    fn veil_followers_in_shadow_slip_past_at_range() {
+        let mut game = Game::new(2710, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(12, 5).visible_all().build();
+        game.state.map.light.dark = true;
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 2 };
+        game.state.active_god = Some(GodId::Veil);
+        let goblin = add_goblin(&mut game, Pos { y: 2, x: 6 });
+
+        game.update_enemy_awareness();
+        assert_eq!(game.state.actors[goblin].awareness, 0, "shadow hides the player at range");
+
+        game.light_torch();
+        game.update_enemy_awareness();
+        assert!(game.state.actors[goblin].awareness > 0, "a burning torch gives them away");
+    }
+}
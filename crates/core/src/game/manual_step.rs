@@ -0,0 +1,95 @@
+//! Manual movement: one journaled step in a chosen direction, taken on the player's next free
+//! action. The run pauses again right after it, so a frontend can drive the player step by step.
+
+use super::*;
+
+impl Game {
+    /// Queue a single step in `direction`; the next `advance` takes it and then pauses.
+    /// Prompts the step walks into are raised as usual, and a closed door asks to be opened.
+    pub fn apply_manual_step(&mut self, direction: Direction) -> Result<(), GameError> {
+        if !self.at_pause_boundary || self.pending_prompt.is_some() {
+            return Err(GameError::NotAtPauseBoundary { tick: self.tick });
+        }
+        let target = direction.step_from(self.state.actors[self.state.player_id].pos);
+        if !self.is_open_step(target) {
+            return Err(GameError::BlockedStep { pos: target });
+        }
+        self.state.manual_step = Some(direction);
+        self.state.wait_ticks_remaining = 0;
+        self.no_progress_ticks = 0;
+        self.stats.inputs_accepted += 1;
+        Ok(())
+    }
+
+    /// Whether the player could step onto `pos` now: discovered, walkable and unoccupied.
+    pub(in crate::game) fn is_open_step(&self, pos: Pos) -> bool {
+        self.state.map.is_discovered_walkable(pos)
+            && !self.state.actors.values().any(|actor| actor.pos == pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::{MapBuilder, add_goblin};
+
+    fn paused_in_room() -> Game {
+        let mut game = Game::new(2754, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(7, 5).discover_all().build();
+        let pos = Pos { y: 2, x: 3 };
+        game.state.actors[game.state.player_id].pos = pos;
+        game.refresh_fov(pos);
+        game.at_pause_boundary = true;
+        game
+    }
+
+    #[test]
+    fn a_manual_step_moves_one_tile_and_pauses() {
+        let mut game = paused_in_room();
+        game.apply_manual_step(Direction::West).expect("open floor to the west");
+        let result = game.advance(50);
+        assert!(matches!(result.stop_reason, AdvanceStopReason::PausedAtBoundary { .. }));
+        assert_eq!(result.simulated_ticks, 1);
+        assert_eq!(result.breakdown.ticks_moved, 1);
+        assert_eq!(game.state.actors[game.state.player_id].pos, Pos { y: 2, x: 2 });
+        assert_eq!(game.state.manual_step, None);
+
+        game.apply_manual_step(Direction::North).expect("paused again after the step");
+        game.advance(50);
+        assert_eq!(game.state.actors[game.state.player_id].pos, Pos { y: 1, x: 2 });
+    }
+
+    #[test]
+    fn steps_into_walls_or_actors_are_rejected() {
+        let mut game = paused_in_room();
+        add_goblin(&mut game, Pos { y: 2, x: 4 });
+        let blocked = game.apply_manual_step(Direction::East);
+        assert_eq!(blocked, Err(GameError::BlockedStep { pos: Pos { y: 2, x: 4 } }));
+
+        game.state.actors[game.state.player_id].pos = Pos { y: 0, x: 3 };
+        assert!(game.apply_manual_step(Direction::North).is_err(), "off the map");
+        game.at_pause_boundary = false;
+        assert!(matches!(
+            game.apply_manual_step(Direction::South),
+            Err(GameError::NotAtPauseBoundary { .. })
+        ));
+    }
+
+    #[test]
+    fn a_step_toward_a_closed_door_asks_to_open_it() {
+        let mut game = paused_in_room();
+        game.state.map.set_tile(Pos { y: 1, x: 4 }, TileKind::Wall);
+        game.state.map.set_tile(Pos { y: 3, x: 4 }, TileKind::Wall);
+        game.state.map.set_tile(Pos { y: 2, x: 4 }, TileKind::ClosedDoor);
+        game.apply_manual_step(Direction::East).expect("doors can be stepped toward");
+        let result = game.advance(50);
+        assert!(matches!(
+            result.stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::DoorBlocked { .. })
+        ));
+        assert_eq!(game.state.actors[game.state.player_id].pos, Pos { y: 2, x: 3 });
+    }
+}
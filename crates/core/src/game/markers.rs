@@ -0,0 +1,76 @@
+//! Player-placed map markers on discovered tiles.
+//! Markers are journaled inputs so replays see the same planner costs.
+//! Path penalties live on `MarkerKind`; this module only validates and stores markers.
+
+use super::*;
+
+impl Game {
+    /// Place, replace, or clear (`None`) the marker on a discovered tile.
+    pub fn set_marker(&mut self, pos: Pos, marker: Option<MarkerKind>) -> Result<(), GameError> {
+        if !self.at_pause_boundary && self.pending_prompt.is_none() {
+            return Err(GameError::NotAtPauseBoundary { tick: self.tick });
+        }
+        if !self.state.map.is_discovered(pos) {
+            return Err(GameError::UndiscoveredTile { pos });
+        }
+        match marker {
+            Some(kind) => self.state.map.markers.insert(pos, kind),
+            None => self.state.map.markers.remove(&pos),
+        };
+        self.state.map.revision += 1;
+        self.no_progress_ticks = 0;
+        self.stats.inputs_accepted += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::state::Map;
+
+    fn open_game() -> Game {
+        let mut game = Game::new(4242, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = Map::new(9, 7);
+        game.state.map.discovered.fill(true);
+        game
+    }
+
+    #[test]
+    fn markers_require_discovered_tiles_and_can_be_cleared() {
+        let mut game = open_game();
+        let tile = Pos { y: 3, x: 4 };
+        game.state.map.discovered.fill(false);
+        assert!(matches!(
+            game.set_marker(tile, Some(MarkerKind::Danger)),
+            Err(GameError::UndiscoveredTile { .. })
+        ));
+
+        game.state.map.reveal(tile);
+        game.set_marker(tile, Some(MarkerKind::Danger)).expect("discovered tile accepts markers");
+        game.set_marker(tile, Some(MarkerKind::Stairs)).expect("markers can be replaced");
+        assert_eq!(game.state.map.marker_at(tile), Some(MarkerKind::Stairs));
+
+        game.set_marker(tile, None).expect("markers can be cleared");
+        assert!(game.state.map.markers.is_empty());
+    }
+
+    #[test]
+    fn avoid_markers_detour_player_paths_but_not_enemies() {
+        let mut game = open_game();
+        let start = Pos { y: 3, x: 2 };
+        let goal = Pos { y: 3, x: 6 };
+        game.set_marker(Pos { y: 3, x: 4 }, Some(MarkerKind::Avoid)).unwrap();
+
+        let map = &game.state.map;
+        let path = astar_path(map, start, goal).expect("room should stay connected");
+        assert!(!path.contains(&Pos { y: 3, x: 4 }), "player path should skirt the avoid marker");
+        assert_eq!(path.len(), 6);
+
+        let enemy_path = enemy_path_to_player(map, start, goal, None).expect("enemy path exists");
+        assert_eq!(enemy_path.len(), 4, "enemies ignore the player's markers");
+    }
+}
@@ -0,0 +1,110 @@
+//! Memory footprint estimates. Read-only and cheap, so batch drivers can sample every game.
+
+use std::mem::size_of;
+
+use super::*;
+use crate::state::{Actor, Item, Map, StoredFloor};
+
+fn vec_bytes<T>(items: &Vec<T>) -> usize {
+    items.capacity() * size_of::<T>()
+}
+
+fn map_bytes(map: &Map) -> usize {
+    vec_bytes(&map.tiles)
+        + vec_bytes(&map.discovered)
+        + vec_bytes(&map.visible)
+        + vec_bytes(&map.hazards)
+        + vec_bytes(&map.spreading_hazards)
+        + vec_bytes(&map.rooms)
+        + vec_bytes(&map.vaults)
+        + map.markers.len() * size_of::<(Pos, MarkerKind)>()
+}
+
+fn stored_floor_bytes(floor: &StoredFloor) -> usize {
+    size_of::<StoredFloor>()
+        + map_bytes(&floor.map)
+        + vec_bytes(&floor.actors)
+        + vec_bytes(&floor.items)
+}
+
+/// Heap text an event carries beyond its inline size.
+fn event_text_bytes(event: &LogEvent) -> usize {
+    match event {
+        LogEvent::FloorEntered { name: text, .. }
+        | LogEvent::RecoveryHint { hash_hex: text, .. }
+        | LogEvent::Notice(text) => text.capacity(),
+        _ => 0,
+    }
+}
+
+impl Game {
+    /// Approximate bytes this game holds, by subsystem.
+    pub fn memory_footprint(&self) -> MemoryFootprint {
+        let scan = self.target_scan.as_ref().map_or(0, |(_, scan)| {
+            vec_bytes(&scan.frontiers)
+                + vec_bytes(&scan.items)
+                + vec_bytes(&scan.stairs)
+                + vec_bytes(&scan.enemies)
+        });
+        MemoryFootprint {
+            inline: size_of::<Self>(),
+            map: map_bytes(&self.state.map),
+            entities: self.state.actors.capacity() * size_of::<Actor>()
+                + self.state.items.capacity() * size_of::<Item>(),
+            stored_floors: self.state.stored_floors.values().map(stored_floor_bytes).sum(),
+            threat_trace: self.state.threat_trace.capacity() * size_of::<ThreatTrace>(),
+            log: self.log.heap_bytes() + self.log.iter().map(event_text_bytes).sum::<usize>(),
+            caches: self.path_cache.heap_bytes()
+                + scan
+                + self.enemy_memory.len() * size_of::<(EntityId, EnemySighting)>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    #[test]
+    fn footprint_counts_left_floors_and_keeps_the_threat_trace_within_its_depth() {
+        let content = ContentPack::default();
+        let mut game = Game::new(2761, &content, GameMode::Ironman);
+        let fresh = game.memory_footprint();
+        assert_eq!(fresh.stored_floors, 0);
+        assert!(fresh.map > 0 && fresh.total() > fresh.map + fresh.inline);
+
+        let start_floor = game.state.floor_index;
+        while game.state.floor_index == start_floor && game.finished_outcome.is_none() {
+            if let AdvanceStopReason::Interrupted(interrupt) = game.advance(500).stop_reason {
+                let choice = game.policy_default_choice().expect("a prompt is pending");
+                game.apply_choice(interrupt.prompt_id(), choice).unwrap();
+            }
+        }
+        let later = game.memory_footprint();
+        assert!(later.stored_floors > fresh.map / 2, "{later:?}");
+        assert!(later.log > 0);
+        let trace_limit = content.threat_trace_depth * size_of::<ThreatTrace>();
+        assert!(later.threat_trace <= trace_limit, "{later:?}");
+    }
+
+    #[test]
+    fn the_log_stays_within_its_depth_over_a_long_run() {
+        let content = ContentPack { event_log_depth: 16, ..ContentPack::default() };
+        let mut game = Game::new(2761, &content, GameMode::Ironman);
+        let mut largest = 0;
+        for _ in 0..400 {
+            if game.finished_outcome.is_some() {
+                break;
+            }
+            if let AdvanceStopReason::Interrupted(interrupt) = game.advance(50).stop_reason {
+                let choice = game.policy_default_choice().expect("a prompt is pending");
+                game.apply_choice(interrupt.prompt_id(), choice).unwrap();
+            }
+            largest = largest.max(game.log.len());
+        }
+        assert!(game.log.total_logged() > 64, "the run logged {}", game.log.total_logged());
+        assert_eq!(largest, 16);
+        assert!(game.memory_footprint().log < 64 * size_of::<LogEvent>());
+    }
+}
@@ -0,0 +1,46 @@
+//! Cosmetic names for the current floor and its objective elite, read from the content tables.
+//! Both follow the floor seed alone, so they never touch the snapshot hash or the RNG.
+
+use super::*;
+
+impl Game {
+    /// The current floor's name, e.g. `The Sunken Gallery`.
+    pub fn floor_name(&self) -> String {
+        self.names.floor_name(self.state.floor_provenance.floor_seed)
+    }
+
+    /// The epithet-bearing name of `enemy` when it is the current floor's objective elite.
+    pub fn elite_name(&self, enemy: EntityId) -> Option<String> {
+        let actor = self.state.actors.get(enemy)?;
+        let floor_elite = UniqueEnemy::FloorElite(self.state.floor_index);
+        (actor.unique == Some(floor_elite))
+            .then(|| self.names.elite_name(self.state.floor_provenance.floor_seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::floor_transition::insert_enemy;
+
+    #[test]
+    fn floor_and_elite_names_follow_the_floor_seed() {
+        let mut game = Game::new(2749, &ContentPack::default(), GameMode::Ironman);
+        game.descend_to_floor(2);
+        let seed = game.state.floor_provenance.floor_seed;
+        assert_eq!(game.floor_name(), game.names.floor_name(seed));
+        let entered = LogEvent::FloorEntered { floor_index: 2, name: game.floor_name() };
+        assert!(game.log.contains(&entered));
+
+        let before = game.snapshot_hash();
+        let elite = insert_enemy(&mut game, ActorKind::Gargoyle, Pos { y: 1, x: 1 }, 3);
+        let plain = insert_enemy(&mut game, ActorKind::Gargoyle, Pos { y: 1, x: 2 }, 3);
+        game.state.actors[elite].unique = Some(UniqueEnemy::FloorElite(2));
+        assert_eq!(game.elite_name(elite), Some(game.names.elite_name(seed)));
+        assert_eq!(game.elite_name(plain), None);
+        game.state.actors.remove(elite);
+        game.state.actors.remove(plain);
+        assert_eq!(game.snapshot_hash(), before, "naming never touches the hash");
+    }
+}
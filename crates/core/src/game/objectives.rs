@@ -0,0 +1,193 @@
+//! Floor objectives: designated floors lock their down stairs until the objective is done.
+//! The elite is the floor's toughest enemy; the sigil lands on the open tile farthest from entry.
+//! It does not own the locked-stairs prompt; prompt batching raises it from `stairs_locked`.
+
+use std::cmp::Reverse;
+
+use super::*;
+use crate::content::keys;
+use crate::state::Item;
+
+impl Game {
+    /// Whether the current floor's down stairs wait on an unfinished objective.
+    pub fn stairs_locked(&self) -> bool {
+        self.state.floor_objective.is_some()
+    }
+
+    /// Open the content-designated objective for a freshly generated floor, if it has one.
+    pub(super) fn assign_floor_objective(&mut self, entry: Pos) {
+        let Some(objective) = self.floor_objectives.get(&self.state.floor_index).copied() else {
+            return;
+        };
+        if self.state.completed_objectives.iter().any(|record| {
+            record.floor_index == self.state.floor_index && record.objective == objective
+        }) {
+            return;
+        }
+        self.state.floor_objective = match objective {
+            FloorObjective::SlayElite => self.pick_elite().map(|elite| {
+                let floor = UniqueEnemy::FloorElite(self.state.floor_index);
+                self.state.actors[elite].unique.get_or_insert(floor);
+                ActiveObjective::SlayElite(elite)
+            }),
+            FloorObjective::FindSigil => self.place_sigil(entry).map(ActiveObjective::FindSigil),
+        };
+    }
+
+    /// Close the open objective once its elite is dead or its sigil has left the floor.
+    pub(super) fn update_floor_objective(&mut self) {
+        let Some(active) = self.state.floor_objective else {
+            return;
+        };
+        let done = match active {
+            ActiveObjective::SlayElite(enemy) => !self.state.actors.contains_key(enemy),
+            ActiveObjective::FindSigil(item) => !self.state.items.contains_key(item),
+        };
+        if !done {
+            return;
+        }
+        let objective = active.objective();
+        self.state.floor_objective = None;
+        self.state.completed_objectives.push(ObjectiveRecord {
+            floor_index: self.state.floor_index,
+            objective,
+            completed_tick: self.tick,
+        });
+        self.log.push(LogEvent::ObjectiveCompleted { objective });
+    }
+
+    /// Where auto-explore should head to finish the open objective.
+    pub(super) fn objective_target(&self) -> Option<Pos> {
+        match self.state.floor_objective? {
+            ActiveObjective::SlayElite(enemy) if self.suppressed_enemy != Some(enemy) => {
+                self.state.actors.get(enemy).map(|actor| actor.pos)
+            }
+            ActiveObjective::SlayElite(_) => None,
+            ActiveObjective::FindSigil(item) => self.state.items.get(item).map(|item| item.pos),
+        }
+    }
+
+    pub(super) fn resolve_seek_objective_choice(&mut self) {
+        self.stairs_lock_acknowledged = true;
+    }
+
+    pub(super) fn pick_elite(&self) -> Option<EntityId> {
+        self.state
+            .actors
+            .iter()
+            .filter(|(id, _)| *id != self.state.player_id)
+            .max_by_key(|(_, actor)| (actor.max_hp, Reverse((actor.pos.y, actor.pos.x))))
+            .map(|(id, _)| id)
+    }
+
+    fn place_sigil(&mut self, entry: Pos) -> Option<ItemId> {
+        let map = &self.state.map;
+        let blocked: Vec<Pos> = self
+            .state
+            .actors
+            .values()
+            .map(|actor| actor.pos)
+            .chain(self.state.items.values().map(|item| item.pos))
+            .collect();
+        let pos = map
+            .positions()
+            .filter(|pos| {
+                map.tile_at(*pos) == TileKind::Floor
+                    && !map.is_hazard(*pos)
+                    && !blocked.contains(pos)
+            })
+            .max_by_key(|pos| (manhattan(entry, *pos), Reverse((pos.y, pos.x))))?;
+        let kind = ItemKind::Perk(keys::PERK_FLOOR_SIGIL);
+        let item =
+            self.state.items.insert(Item { id: ItemId::default(), kind, pos, cursed: false });
+        self.state.items[item].id = item;
+        Some(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::floor_transition::insert_enemy;
+    use crate::game::test_support::MapBuilder;
+    use crate::mapgen::STARTING_FLOOR_INDEX;
+
+    fn content_with(floor_index: u8, objective: FloorObjective) -> ContentPack {
+        let floor_objectives = BTreeMap::from([(floor_index, objective)]);
+        ContentPack { floor_objectives, ..ContentPack::default() }
+    }
+
+    #[test]
+    fn sigil_locks_the_stairs_until_the_player_finds_it() {
+        let content = content_with(STARTING_FLOOR_INDEX, FloorObjective::FindSigil);
+        let mut game = Game::new(2709, &content, GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(9, 5).stairs(Pos { y: 2, x: 1 }).discover_all().build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 1 };
+        game.assign_floor_objective(Pos { y: 2, x: 2 });
+        let Some(ActiveObjective::FindSigil(sigil)) = game.state.floor_objective else {
+            panic!("expected a sigil objective");
+        };
+        assert_eq!(game.state.items[sigil].pos, Pos { y: 1, x: 7 });
+
+        let AdvanceStopReason::Interrupted(Interrupt::StairsLocked { prompt_id, objective }) =
+            game.advance(1).stop_reason
+        else {
+            panic!("expected the locked stairs to interrupt");
+        };
+        assert_eq!(objective, FloorObjective::FindSigil);
+        game.apply_choice(prompt_id, Choice::SeekObjective).expect("seek the objective");
+
+        let mut saw_transition = false;
+        for _ in 0..20 {
+            match game.advance(50).stop_reason {
+                AdvanceStopReason::Interrupted(Interrupt::LootFound { prompt_id, .. }) => {
+                    game.apply_choice(prompt_id, Choice::KeepLoot).expect("keep the sigil");
+                }
+                AdvanceStopReason::Interrupted(Interrupt::FloorTransition { .. }) => {
+                    saw_transition = true;
+                    break;
+                }
+                other => panic!("unexpected stop: {other:?}"),
+            }
+        }
+        assert!(saw_transition, "the stairs open once the sigil is found");
+        assert!(!game.stairs_locked());
+        assert_eq!(game.state.completed_objectives.len(), 1);
+        assert!(game.log.contains(&LogEvent::ObjectiveCompleted { objective }));
+    }
+
+    #[test]
+    fn elite_is_the_toughest_enemy_and_its_death_unlocks_the_stairs() {
+        let content = content_with(2, FloorObjective::SlayElite);
+        let mut game = Game::new(2709, &content, GameMode::Ironman);
+        assert!(!game.stairs_locked(), "floors without an objective stay open");
+
+        game.descend_to_floor(2);
+        let Some(ActiveObjective::SlayElite(elite)) = game.state.floor_objective else {
+            panic!("expected an elite objective on floor 2");
+        };
+        let toughest = game.state.actors.values().filter(|actor| actor.kind != ActorKind::Player);
+        assert_eq!(
+            game.state.actors[elite].max_hp,
+            toughest.map(|actor| actor.max_hp).max().unwrap()
+        );
+        assert_eq!(game.state.actors[elite].unique, Some(UniqueEnemy::FloorElite(2)));
+
+        let decoy = insert_enemy(&mut game, ActorKind::Goblin, Pos { y: 0, x: 0 }, 0);
+        game.state.actors.remove(decoy);
+        game.update_floor_objective();
+        assert!(game.stairs_locked(), "only the elite's death counts");
+
+        game.tick = 77;
+        game.state.actors.remove(elite);
+        game.update_floor_objective();
+        assert!(!game.stairs_locked());
+        let record = game.state.completed_objectives[0];
+        assert_eq!((record.floor_index, record.completed_tick), (2, 77));
+    }
+}
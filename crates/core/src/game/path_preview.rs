@@ -0,0 +1,85 @@
+//! Path previews built from the auto-explore planner's own cost model, so a frontend can show
+//! why a route was chosen. Previews only read state and never touch the RNG.
+
+use super::*;
+
+/// Manhattan distance within which a visible enemy counts as a threat to a tile.
+const ENEMY_THREAT_RADIUS: u32 = 2;
+/// Danger added by a hazard on the tile.
+const HAZARD_DANGER: u32 = 2;
+/// Danger added per visible enemy within `ENEMY_THREAT_RADIUS`.
+const ENEMY_DANGER: u32 = 3;
+
+impl Game {
+    /// The route the planner would take from `from` to `to` under `pass`, annotated step by
+    /// step; `None` when the pass finds no route. `PlannerPass::Safe` refuses hazard tiles.
+    pub fn path_preview(&self, from: Pos, to: Pos, pass: PlannerPass) -> Option<PathPreview> {
+        let map = &self.state.map;
+        let path = astar_search(map, from, to, pass == PlannerPass::Safe).path?;
+        let enemies: Vec<(EntityId, Pos)> = self
+            .state
+            .actors
+            .iter()
+            .filter(|(id, actor)| *id != self.state.player_id && map.is_visible(actor.pos))
+            .map(|(id, actor)| (id, actor.pos))
+            .collect();
+        let steps = path
+            .into_iter()
+            .map(|pos| {
+                let marker = map.marker_at(pos);
+                let hazard = map.is_hazard(pos);
+                let mut nearby_enemies: Vec<EntityId> = enemies
+                    .iter()
+                    .filter(|(_, enemy_pos)| manhattan(pos, *enemy_pos) <= ENEMY_THREAT_RADIUS)
+                    .map(|(id, _)| *id)
+                    .collect();
+                nearby_enemies.sort();
+                let danger =
+                    u32::from(hazard) * HAZARD_DANGER + nearby_enemies.len() as u32 * ENEMY_DANGER;
+                PathStep {
+                    pos,
+                    cost: 1 + marker.map_or(0, MarkerKind::path_penalty),
+                    marker,
+                    hazard,
+                    nearby_enemies,
+                    danger,
+                }
+            })
+            .collect();
+        Some(PathPreview { pass, steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::{MapBuilder, add_goblin};
+
+    #[test]
+    fn preview_annotates_markers_hazards_and_nearby_enemies() {
+        let mut game = Game::new(2727, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::solid(12, 5)
+            .corridor(Pos { y: 2, x: 1 }, Pos { y: 2, x: 10 })
+            .hazard(Pos { y: 2, x: 4 })
+            .visible_all()
+            .build();
+        game.state.map.markers.insert(Pos { y: 2, x: 3 }, MarkerKind::Danger);
+        let goblin = add_goblin(&mut game, Pos { y: 2, x: 9 });
+        let (from, to) = (Pos { y: 2, x: 1 }, Pos { y: 2, x: 8 });
+
+        assert_eq!(game.path_preview(from, to, PlannerPass::Safe), None);
+        let preview = game.path_preview(from, to, PlannerPass::HazardFallback).unwrap();
+        assert_eq!(preview.steps.len(), 7);
+        assert_eq!(preview.steps.last().map(|step| step.pos), Some(to));
+        assert_eq!(preview.total_cost(), 7 + MarkerKind::Danger.path_penalty());
+
+        let hazard_step = &preview.steps[2];
+        assert!(hazard_step.hazard && hazard_step.nearby_enemies.is_empty());
+        assert_eq!(preview.steps[1].marker, Some(MarkerKind::Danger));
+        assert_eq!(preview.steps[6].nearby_enemies, vec![goblin]);
+        assert_eq!(preview.total_danger(), HAZARD_DANGER + 2 * ENEMY_DANGER);
+    }
+}
@@ -3,6 +3,7 @@
 //! It does not own high-level exploration policy or player decision flow.
 
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::mem::size_of;
 
 use super::*;
 use crate::state::Map;
@@ -36,12 +37,53 @@ pub(super) fn reachable_discovered_walkable_tiles(map: &Map, start: Pos) -> BTre
     visited
 }
 
+/// An A* route, if one exists, with how many nodes the search expanded to find it.
+#[derive(Clone, Debug)]
+pub(super) struct PathSearch {
+    pub(super) path: Option<Vec<Pos>>,
+    pub(super) expansions: u32,
+}
+
 pub(super) fn astar_path(map: &Map, start: Pos, goal: Pos) -> Option<Vec<Pos>> {
-    astar_path_internal(map, start, goal, true, None, true)
+    astar_search(map, start, goal, true).path
 }
 
-pub(super) fn astar_path_allow_hazards(map: &Map, start: Pos, goal: Pos) -> Option<Vec<Pos>> {
-    astar_path_internal(map, start, goal, false, None, true)
+/// How an A* search treats hazards, one blocked tile and player markers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct SearchOptions {
+    pub(super) avoid_hazards: bool,
+    /// A tile the route may not cross, such as the sanctuary enemies cannot enter.
+    pub(super) blocked_tile: Option<Pos>,
+    /// Whether the route may still end on `blocked_tile`.
+    pub(super) allow_goal_on_blocked_tile: bool,
+    /// Whether player markers add to step costs; only the player's planner heeds them.
+    pub(super) respect_markers: bool,
+}
+
+impl SearchOptions {
+    /// The player's planner: markers count, and hazards are avoided when asked.
+    pub(super) fn player(avoid_hazards: bool) -> Self {
+        Self {
+            avoid_hazards,
+            blocked_tile: None,
+            allow_goal_on_blocked_tile: true,
+            respect_markers: true,
+        }
+    }
+
+    /// An enemy closing in: hazards and markers are ignored, and the sanctuary is off limits.
+    fn enemy(sanctuary_tile: Option<Pos>) -> Self {
+        Self {
+            avoid_hazards: false,
+            blocked_tile: sanctuary_tile,
+            allow_goal_on_blocked_tile: false,
+            respect_markers: false,
+        }
+    }
+}
+
+pub(super) fn astar_search(map: &Map, start: Pos, goal: Pos, avoid_hazards: bool) -> PathSearch {
+    astar_path_internal(map, start, goal, SearchOptions::player(avoid_hazards))
 }
 
 pub(super) fn enemy_path_to_player(
@@ -50,78 +92,143 @@ pub(super) fn enemy_path_to_player(
     player_pos: Pos,
     sanctuary_tile: Option<Pos>,
 ) -> Option<Vec<Pos>> {
-    astar_path_internal(map, enemy_pos, player_pos, false, sanctuary_tile, false)
+    astar_path_internal(map, enemy_pos, player_pos, SearchOptions::enemy(sanctuary_tile)).path
 }
 
-fn astar_path_internal(
-    map: &Map,
+fn astar_path_internal(map: &Map, start: Pos, goal: Pos, options: SearchOptions) -> PathSearch {
+    let mut unlimited = u32::MAX;
+    let mut search = ResumableSearch::new(map, start, goal, options);
+    search.resume(map, &mut unlimited).expect("an unlimited search always finishes")
+}
+
+/// An A* search that can stop once its expansion budget runs out and pick up on the next
+/// call exactly where it stopped, so a budgeted search finds the same route as a whole one.
+/// The map must not change between calls.
+#[derive(Clone, Debug)]
+pub(super) struct ResumableSearch {
     start: Pos,
     goal: Pos,
-    avoid_hazards: bool,
-    blocked_tile: Option<Pos>,
-    allow_goal_on_blocked_tile: bool,
-) -> Option<Vec<Pos>> {
-    if !map.is_discovered_walkable(start) || !map.is_discovered_walkable(goal) {
-        return None;
-    }
-    if blocked_tile.is_some_and(|blocked| blocked == goal && !allow_goal_on_blocked_tile) {
-        return None;
-    }
-    if start == goal {
-        return Some(vec![]);
-    }
-    let mut open_set = BTreeSet::new();
-    let mut g_score = BTreeMap::new();
-    let mut came_from = BTreeMap::new();
-    let h = manhattan(start, goal);
-    open_set.insert(OpenNode { f: h, h, y: start.y, x: start.x });
-    g_score.insert(start, 0);
-    while let Some(curr) = open_set.pop_first() {
-        let p = Pos { y: curr.y, x: curr.x };
-        if p == goal {
-            return Some(reconstruct_path(&came_from, start, goal));
+    options: SearchOptions,
+    open_set: BTreeSet<OpenNode>,
+    /// Nodes already expanded; later, costlier copies of them in `open_set` are skipped.
+    closed_set: BTreeSet<Pos>,
+    g_score: BTreeMap<Pos, u32>,
+    came_from: BTreeMap<Pos, Pos>,
+    expansions: u32,
+    /// Set up front when the search is settled without expanding anything.
+    settled: Option<PathSearch>,
+}
+
+impl ResumableSearch {
+    pub(super) fn new(map: &Map, start: Pos, goal: Pos, options: SearchOptions) -> Self {
+        let mut search = Self {
+            start,
+            goal,
+            options,
+            open_set: BTreeSet::new(),
+            closed_set: BTreeSet::new(),
+            g_score: BTreeMap::new(),
+            came_from: BTreeMap::new(),
+            expansions: 0,
+            settled: None,
+        };
+        let unreachable = !map.is_discovered_walkable(start)
+            || !map.is_discovered_walkable(goal)
+            || options
+                .blocked_tile
+                .is_some_and(|blocked| blocked == goal && !options.allow_goal_on_blocked_tile);
+        if unreachable {
+            search.settled = Some(PathSearch { path: None, expansions: 0 });
+        } else if start == goal {
+            search.settled = Some(PathSearch { path: Some(vec![]), expansions: 0 });
+        } else {
+            let h = manhattan(start, goal);
+            search.open_set.insert(OpenNode { f: h, h, y: start.y, x: start.x });
+            search.g_score.insert(start, 0);
         }
-        let cur_g = *g_score.get(&p).expect("current node must have g-score");
-        for n in neighbors_for_astar(p, blocked_tile) {
-            if !is_astar_step_walkable(
-                map,
-                n,
-                goal,
-                avoid_hazards,
-                blocked_tile,
-                allow_goal_on_blocked_tile,
-            ) {
+        search
+    }
+
+    /// Expand nodes until the route is settled or `budget` runs out, spending one unit per
+    /// expansion; `None` means the search is unfinished and should be resumed later.
+    pub(super) fn resume(&mut self, map: &Map, budget: &mut u32) -> Option<PathSearch> {
+        if let Some(settled) = &self.settled {
+            return Some(settled.clone());
+        }
+        while let Some(&curr) = self.open_set.first() {
+            let p = Pos { y: curr.y, x: curr.x };
+            if self.closed_set.contains(&p) {
+                self.open_set.pop_first();
+                continue;
+            }
+            if *budget == 0 {
+                return None;
+            }
+            *budget -= 1;
+            self.open_set.pop_first();
+            self.closed_set.insert(p);
+            self.expansions += 1;
+            if p == self.goal {
+                let path = reconstruct_path(&self.came_from, self.start, self.goal);
+                return Some(self.settle(Some(path)));
+            }
+            self.expand(map, p);
+        }
+        Some(self.settle(None))
+    }
+
+    fn expand(&mut self, map: &Map, p: Pos) {
+        let options = self.options;
+        let cur_g = *self.g_score.get(&p).expect("current node must have g-score");
+        for n in neighbors_for_astar(p, options.blocked_tile) {
+            if !is_astar_step_walkable(map, n, self.goal, options) {
                 continue;
             }
-            let tg = cur_g + 1;
-            if tg < *g_score.get(&n).unwrap_or(&u32::MAX) {
-                came_from.insert(n, p);
-                g_score.insert(n, tg);
-                let h = manhattan(n, goal);
-                open_set.insert(OpenNode { f: tg + h, h, y: n.y, x: n.x });
+            let marker_cost = if options.respect_markers {
+                map.marker_at(n).map_or(0, MarkerKind::path_penalty)
+            } else {
+                0
+            };
+            let tg = cur_g + 1 + marker_cost;
+            if tg < *self.g_score.get(&n).unwrap_or(&u32::MAX) {
+                self.came_from.insert(n, p);
+                self.g_score.insert(n, tg);
+                let h = manhattan(n, self.goal);
+                self.open_set.insert(OpenNode { f: tg + h, h, y: n.y, x: n.x });
             }
         }
     }
-    None
+
+    /// Record the outcome and drop the search frontier, which is no longer needed.
+    fn settle(&mut self, path: Option<Vec<Pos>>) -> PathSearch {
+        let settled = PathSearch { path, expansions: self.expansions };
+        self.open_set.clear();
+        self.closed_set.clear();
+        self.g_score.clear();
+        self.came_from.clear();
+        self.settled = Some(settled.clone());
+        settled
+    }
+
+    /// Bytes the search frontier and bookkeeping hold.
+    pub(super) fn heap_bytes(&self) -> usize {
+        self.open_set.len() * size_of::<OpenNode>()
+            + self.closed_set.len() * size_of::<Pos>()
+            + self.g_score.len() * size_of::<(Pos, u32)>()
+            + self.came_from.len() * size_of::<(Pos, Pos)>()
+    }
 }
 
-fn is_astar_step_walkable(
-    map: &Map,
-    pos: Pos,
-    goal: Pos,
-    avoid_hazards: bool,
-    blocked_tile: Option<Pos>,
-    allow_goal_on_blocked_tile: bool,
-) -> bool {
-    if blocked_tile
-        .is_some_and(|blocked| blocked == pos && (pos != goal || !allow_goal_on_blocked_tile))
-    {
+fn is_astar_step_walkable(map: &Map, pos: Pos, goal: Pos, options: SearchOptions) -> bool {
+    if options.blocked_tile.is_some_and(|blocked| {
+        blocked == pos && (pos != goal || !options.allow_goal_on_blocked_tile)
+    }) {
         return false;
     }
     if !map.is_discovered_walkable(pos) {
         return false;
     }
-    if avoid_hazards && map.is_hazard(pos) {
+    if options.avoid_hazards && map.is_hazard(pos) {
         return false;
     }
     if map.tile_at(pos) == TileKind::ClosedDoor && pos != goal {
@@ -0,0 +1,52 @@
+//! The answer the standing policy gives a pending prompt when the player does not, e.g. when
+//! a blitz timer runs out. Applying it is an ordinary journaled choice.
+
+use super::*;
+use crate::game::prompts::PendingPromptKind;
+
+impl Game {
+    /// The choice the current policy makes for the pending prompt; `None` when nothing is
+    /// pending. Always one of `available_choices`.
+    pub fn policy_default_choice(&self) -> Option<Choice> {
+        let prompt = self.pending_prompt.as_ref()?;
+        let choice = match &prompt.kind {
+            PendingPromptKind::Loot { .. } => Choice::KeepLoot,
+            PendingPromptKind::EnemyEncounter { .. } => match self.state.policy.fight_or_avoid {
+                FightMode::Fight => Choice::Fight,
+                FightMode::Avoid => Choice::Avoid,
+            },
+            PendingPromptKind::DoorBlocked { .. } => Choice::OpenDoor,
+            PendingPromptKind::PactAltar { .. } => Choice::KeepPact,
+            PendingPromptKind::StairsLocked { .. } => Choice::SeekObjective,
+            // The first offer, and for a floor transition the plain descent or first branch.
+            PendingPromptKind::PerkDraft { .. } | PendingPromptKind::FloorTransition { .. } => {
+                return self.available_choices().into_iter().next();
+            }
+        };
+        Some(choice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+
+    #[test]
+    fn policy_defaults_are_accepted_and_carry_a_run_to_its_end() {
+        let mut game = Game::new(2756, &ContentPack::default(), GameMode::Ironman);
+        assert_eq!(game.policy_default_choice(), None);
+        for _ in 0..400 {
+            match game.advance(2_000).stop_reason {
+                AdvanceStopReason::Interrupted(interrupt) => {
+                    let choice = game.policy_default_choice().expect("a prompt is pending");
+                    assert!(game.available_choices().contains(&choice), "{choice:?}");
+                    game.apply_choice(interrupt.prompt_id(), choice).expect("default is valid");
+                }
+                AdvanceStopReason::Finished(_) => return,
+                other => panic!("unexpected stop {other:?}"),
+            }
+        }
+        panic!("policy defaults never finished the run");
+    }
+}
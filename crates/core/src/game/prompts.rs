@@ -3,9 +3,13 @@
 //! It does not own the gameplay consequences of accepted choices.
 
 use super::*;
-use crate::mapgen::{BranchProfile, MAX_FLOORS, STARTING_FLOOR_INDEX};
+use crate::game::boons::roll_boon_offer;
+use crate::game::floor_transition::preview_branches;
+use crate::game::leveling::roll_perk_draft;
+use crate::mapgen::{BranchPreview, BranchProfile, MAX_FLOORS, STARTING_FLOOR_INDEX};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(super) enum PendingPromptKind {
     Loot {
         item: ItemId,
@@ -23,26 +27,64 @@ pub(super) enum PendingPromptKind {
         current_floor: u8,
         next_floor: Option<u8>,
         requires_branch_god_choice: bool,
+        boons: Vec<GodBoon>,
+        branch_previews: Vec<BranchPreview>,
+    },
+    PactAltar {
+        pos: Pos,
+        current_god: GodId,
+    },
+    StairsLocked {
+        objective: FloorObjective,
+    },
+    PerkDraft {
+        level: u8,
+        #[serde(deserialize_with = "crate::content::static_keys::key_list")]
+        offer: Vec<&'static str>,
     },
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl PendingPromptKind {
+    pub(super) fn prompt_kind(&self) -> PromptKind {
+        match self {
+            Self::Loot { .. } => PromptKind::LootFound,
+            Self::EnemyEncounter { .. } => PromptKind::EnemyEncounter,
+            Self::DoorBlocked { .. } => PromptKind::DoorBlocked,
+            Self::FloorTransition { .. } => PromptKind::FloorTransition,
+            Self::PactAltar { .. } => PromptKind::PactAltar,
+            Self::StairsLocked { .. } => PromptKind::StairsLocked,
+            Self::PerkDraft { .. } => PromptKind::PerkDraft,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub(super) struct PendingPrompt {
     pub(super) id: ChoicePromptId,
     pub(super) kind: PendingPromptKind,
+    /// Other prompts that were due on the same tick, in the order they will be raised.
+    pub(super) queued: Vec<PromptKind>,
 }
 
 impl Game {
+    /// Make `prompt` the pending prompt and stop the advance on it.
+    /// A prompt cuts a wait short and drops a manual step that has not been taken yet.
+    fn raise_prompt(&mut self, prompt: PendingPrompt, steps: u32) -> AdvanceResult {
+        self.advance_tally.interrupts_raised += 1;
+        self.state.wait_ticks_remaining = 0;
+        self.state.manual_step = None;
+        self.pending_prompt = Some(prompt.clone());
+        let interrupt = self.prompt_to_interrupt(prompt);
+        AdvanceResult::new(steps, AdvanceStopReason::Interrupted(interrupt))
+    }
+
     pub(super) fn interrupt_loot(&mut self, item: ItemId, steps: u32) -> AdvanceResult {
         let prompt = PendingPrompt {
             id: ChoicePromptId(self.next_input_seq),
+            queued: Vec::new(),
             kind: PendingPromptKind::Loot { item },
         };
-        self.pending_prompt = Some(prompt.clone());
-        AdvanceResult {
-            simulated_ticks: steps,
-            stop_reason: AdvanceStopReason::Interrupted(self.prompt_to_interrupt(prompt)),
-        }
+        self.raise_prompt(prompt, steps)
     }
 
     pub(super) fn interrupt_enemy(
@@ -96,6 +138,7 @@ impl Game {
 
         let prompt = PendingPrompt {
             id: ChoicePromptId(self.next_input_seq),
+            queued: Vec::new(),
             kind: PendingPromptKind::EnemyEncounter {
                 enemies,
                 primary_enemy,
@@ -103,23 +146,16 @@ impl Game {
                 threat: threat.clone(),
             },
         };
-        self.pending_prompt = Some(prompt.clone());
-        AdvanceResult {
-            simulated_ticks: steps,
-            stop_reason: AdvanceStopReason::Interrupted(self.prompt_to_interrupt(prompt)),
-        }
+        self.raise_prompt(prompt, steps)
     }
 
     pub(super) fn interrupt_door(&mut self, pos: Pos, steps: u32) -> AdvanceResult {
         let prompt = PendingPrompt {
             id: ChoicePromptId(self.next_input_seq),
+            queued: Vec::new(),
             kind: PendingPromptKind::DoorBlocked { pos },
         };
-        self.pending_prompt = Some(prompt.clone());
-        AdvanceResult {
-            simulated_ticks: steps,
-            stop_reason: AdvanceStopReason::Interrupted(self.prompt_to_interrupt(prompt)),
-        }
+        self.raise_prompt(prompt, steps)
     }
 
     pub(super) fn interrupt_floor_transition(&mut self, steps: u32) -> AdvanceResult {
@@ -132,19 +168,73 @@ impl Game {
             && self.state.branch_profile == BranchProfile::Uncommitted
             && self.state.active_god.is_none()
             && next_floor.is_some();
+        let boons = match self.state.active_god {
+            Some(god) if !requires_branch_god_choice && next_floor.is_some() => {
+                roll_boon_offer(self.seed, self.state.floor_index, god, self.state.fov_bonus)
+            }
+            _ => Vec::new(),
+        };
+        let branch_previews = match next_floor {
+            Some(floor) if requires_branch_god_choice => preview_branches(self, floor),
+            _ => Vec::new(),
+        };
         let prompt = PendingPrompt {
             id: ChoicePromptId(self.next_input_seq),
+            queued: Vec::new(),
             kind: PendingPromptKind::FloorTransition {
                 current_floor: self.state.floor_index,
                 next_floor,
                 requires_branch_god_choice,
+                boons,
+                branch_previews,
             },
         };
-        self.pending_prompt = Some(prompt.clone());
-        AdvanceResult {
-            simulated_ticks: steps,
-            stop_reason: AdvanceStopReason::Interrupted(self.prompt_to_interrupt(prompt)),
-        }
+        self.raise_prompt(prompt, steps)
+    }
+
+    pub(super) fn interrupt_pact_altar(
+        &mut self,
+        pos: Pos,
+        current_god: GodId,
+        steps: u32,
+    ) -> AdvanceResult {
+        let prompt = PendingPrompt {
+            id: ChoicePromptId(self.next_input_seq),
+            queued: Vec::new(),
+            kind: PendingPromptKind::PactAltar { pos, current_god },
+        };
+        self.raise_prompt(prompt, steps)
+    }
+
+    pub(super) fn interrupt_stairs_locked(
+        &mut self,
+        objective: FloorObjective,
+        steps: u32,
+    ) -> AdvanceResult {
+        let prompt = PendingPrompt {
+            id: ChoicePromptId(self.next_input_seq),
+            queued: Vec::new(),
+            kind: PendingPromptKind::StairsLocked { objective },
+        };
+        self.raise_prompt(prompt, steps)
+    }
+
+    pub(super) fn interrupt_perk_draft(&mut self, steps: u32) -> AdvanceResult {
+        let level = self.next_perk_draft_level();
+        let offer = roll_perk_draft(self.seed, level, &self.state.active_perks);
+        let prompt = PendingPrompt {
+            id: ChoicePromptId(self.next_input_seq),
+            queued: Vec::new(),
+            kind: PendingPromptKind::PerkDraft { level, offer },
+        };
+        self.raise_prompt(prompt, steps)
+    }
+
+    /// The pending prompt as an interrupt, with the prompts queued behind it.
+    pub fn prompt_batch(&self) -> Option<PromptBatch> {
+        let prompt = self.pending_prompt.clone()?;
+        let queued = prompt.queued.clone();
+        Some(PromptBatch { active: self.prompt_to_interrupt(prompt), queued })
     }
 
     pub(super) fn prompt_to_interrupt(&self, prompt: PendingPrompt) -> Interrupt {
@@ -153,6 +243,8 @@ impl Game {
                 prompt_id: prompt.id,
                 item,
                 kind: self.state.items[item].kind,
+                curse_detected: self.curse_detected(item),
+                inventory_full: self.inventory_full_for(self.state.items[item].kind),
             },
             PendingPromptKind::EnemyEncounter {
                 enemies,
@@ -161,6 +253,7 @@ impl Game {
                 threat,
             } => Interrupt::EnemyEncounter {
                 prompt_id: prompt.id,
+                boss_intro: self.boss_intro(primary_enemy),
                 enemies,
                 primary_enemy,
                 retreat_eligible,
@@ -173,12 +266,29 @@ impl Game {
                 current_floor,
                 next_floor,
                 requires_branch_god_choice,
+                boons,
+                branch_previews,
             } => Interrupt::FloorTransition {
                 prompt_id: prompt.id,
                 current_floor,
                 next_floor,
                 requires_branch_god_choice,
+                boons,
+                branch_previews,
+            },
+            PendingPromptKind::PactAltar { pos, current_god } => Interrupt::PactAltar {
+                prompt_id: prompt.id,
+                pos,
+                current_god,
+                alternate_god: current_god.alternate(),
+                can_cleanse: self.cursed_item_count() > 0,
             },
+            PendingPromptKind::StairsLocked { objective } => {
+                Interrupt::StairsLocked { prompt_id: prompt.id, objective }
+            }
+            PendingPromptKind::PerkDraft { level, offer } => {
+                Interrupt::PerkDraft { prompt_id: prompt.id, level, offer }
+            }
         }
     }
 }
@@ -0,0 +1,208 @@
+//! Save files for in-progress runs: the run's dynamic state as versioned JSON.
+//! Content-derived tables are not stored; loading rebuilds them from the pack the save was
+//! made with, and a save from another format version or pack is refused before it is read.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{Read, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+use crate::content::ContentPack;
+
+/// Version of the save layout; bump it whenever a saved field changes shape.
+pub const SAVE_FORMAT_VERSION: u32 = 7;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameSaveError {
+    Io(String),
+    /// The file is not a save, or a field does not match the save layout.
+    Malformed(String),
+    UnsupportedVersion {
+        found: u32,
+    },
+    /// The save was made with a different content pack, so its ids would not line up.
+    ContentMismatch {
+        saved: u64,
+        current: u64,
+    },
+}
+
+impl fmt::Display for GameSaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(message) => write!(f, "cannot access save: {message}"),
+            Self::Malformed(message) => write!(f, "save is damaged: {message}"),
+            Self::UnsupportedVersion { found } => {
+                write!(f, "save format {found} is not supported (expected {SAVE_FORMAT_VERSION})")
+            }
+            Self::ContentMismatch { saved, current } => {
+                write!(f, "save was made with content {saved:016x}, not {current:016x}")
+            }
+        }
+    }
+}
+
+impl Error for GameSaveError {}
+
+/// Read first, so an old or foreign save is rejected without parsing the rest of it.
+#[derive(Deserialize)]
+struct SaveHeader {
+    format_version: u32,
+    content_hash: u64,
+}
+
+/// Everything about a run that changes while it is played; caches are rebuilt on demand.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SavedRun {
+    format_version: u32,
+    content_hash: u64,
+    seed: u64,
+    tick: u64,
+    rng: ChaCha8Rng,
+    state: GameState,
+    log: EventLog,
+    next_input_seq: u64,
+    pending_prompt: Option<PendingPrompt>,
+    suppressed_enemy: Option<EntityId>,
+    at_pause_boundary: bool,
+    finished_outcome: Option<RunOutcome>,
+    death: Option<DeathRecord>,
+    no_progress_ticks: u32,
+    #[serde(with = "crate::state::keyed_pairs")]
+    enemy_memory: BTreeMap<EntityId, EnemySighting>,
+    stairs_lock_acknowledged: bool,
+    #[serde(with = "crate::state::keyed_pairs")]
+    snoozed_targets: BTreeMap<SnoozeTarget, u64>,
+    difficulty: Difficulty,
+    stats: RunStats,
+}
+
+impl Game {
+    /// Write the run as it stands, to be resumed with `load_from_reader` and the same pack.
+    pub fn save_to_writer(
+        &self,
+        writer: impl Write,
+        content: &ContentPack,
+    ) -> Result<(), GameSaveError> {
+        let saved = SavedRun {
+            format_version: SAVE_FORMAT_VERSION,
+            content_hash: content.content_hash(),
+            seed: self.seed,
+            tick: self.tick,
+            rng: self.rng.clone(),
+            state: self.state.clone(),
+            log: self.log.clone(),
+            next_input_seq: self.next_input_seq,
+            pending_prompt: self.pending_prompt.clone(),
+            suppressed_enemy: self.suppressed_enemy,
+            at_pause_boundary: self.at_pause_boundary,
+            finished_outcome: self.finished_outcome,
+            death: self.death.clone(),
+            no_progress_ticks: self.no_progress_ticks,
+            enemy_memory: self.enemy_memory.clone(),
+            stairs_lock_acknowledged: self.stairs_lock_acknowledged,
+            snoozed_targets: self.snoozed_targets.clone(),
+            difficulty: self.difficulty,
+            stats: self.stats.clone(),
+        };
+        serde_json::to_writer(writer, &saved).map_err(|error| GameSaveError::Io(error.to_string()))
+    }
+
+    /// Resume a run written by `save_to_writer` with the same content pack.
+    pub fn load_from_reader(
+        mut reader: impl Read,
+        content: &ContentPack,
+    ) -> Result<Self, GameSaveError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).map_err(|error| GameSaveError::Io(error.to_string()))?;
+        let malformed = |error: serde_json::Error| GameSaveError::Malformed(error.to_string());
+        let header: SaveHeader = serde_json::from_str(&text).map_err(malformed)?;
+        if header.format_version != SAVE_FORMAT_VERSION {
+            return Err(GameSaveError::UnsupportedVersion { found: header.format_version });
+        }
+        let current = content.content_hash();
+        if header.content_hash != current {
+            return Err(GameSaveError::ContentMismatch { saved: header.content_hash, current });
+        }
+        let saved: SavedRun = serde_json::from_str(&text).map_err(malformed)?;
+
+        let mut game =
+            Self::new_with_difficulty(saved.seed, content, GameMode::Ironman, saved.difficulty);
+        game.tick = saved.tick;
+        game.rng = saved.rng;
+        game.state = saved.state;
+        game.log = saved.log.with_depth(content.event_log_depth);
+        game.next_input_seq = saved.next_input_seq;
+        game.pending_prompt = saved.pending_prompt;
+        game.suppressed_enemy = saved.suppressed_enemy;
+        game.at_pause_boundary = saved.at_pause_boundary;
+        game.finished_outcome = saved.finished_outcome;
+        game.death = saved.death;
+        game.no_progress_ticks = saved.no_progress_ticks;
+        game.enemy_memory = saved.enemy_memory;
+        game.stairs_lock_acknowledged = saved.stairs_lock_acknowledged;
+        game.snoozed_targets = saved.snoozed_targets;
+        game.stats = saved.stats;
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved(game: &Game, content: &ContentPack) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        game.save_to_writer(&mut bytes, content).expect("save should write");
+        bytes
+    }
+
+    #[test]
+    fn a_loaded_run_continues_exactly_like_the_original() {
+        let content = ContentPack::default();
+        let mut game = Game::new(2756, &content, GameMode::Ironman);
+        let stop = game.advance(3_000).stop_reason;
+        assert!(matches!(stop, AdvanceStopReason::Interrupted(_)), "{stop:?}");
+        let player = game.state.actors[game.state.player_id].pos;
+        game.state.map.markers.insert(player, MarkerKind::Danger);
+
+        let mut loaded = Game::load_from_reader(saved(&game, &content).as_slice(), &content)
+            .expect("save should load");
+        assert_eq!(loaded.full_state_hash(), game.full_state_hash());
+        assert_eq!(loaded.no_progress_ticks, game.no_progress_ticks);
+        assert_eq!(loaded.log(), game.log());
+        for _ in 0..20 {
+            let choice = game.policy_default_choice();
+            let prompt_id = game.pending_prompt.as_ref().map(|prompt| prompt.id);
+            if let (Some(choice), Some(prompt_id)) = (choice, prompt_id) {
+                game.apply_choice(prompt_id, choice.clone()).expect("original accepts");
+                loaded.apply_choice(prompt_id, choice).expect("loaded copy accepts");
+            }
+            let (ours, theirs) = (game.advance(500), loaded.advance(500));
+            assert_eq!(ours.simulated_ticks, theirs.simulated_ticks);
+            assert_eq!(loaded.full_state_hash(), game.full_state_hash());
+        }
+    }
+
+    #[test]
+    fn saves_from_another_version_or_pack_are_refused() {
+        let content = ContentPack::default();
+        let game = Game::new(7, &content, GameMode::Ironman);
+        let text = String::from_utf8(saved(&game, &content)).unwrap();
+
+        let current = format!("\"format_version\":{SAVE_FORMAT_VERSION}");
+        let old = text.replacen(&current, "\"format_version\":0", 1);
+        let error = Game::load_from_reader(old.as_bytes(), &content).err();
+        assert_eq!(error, Some(GameSaveError::UnsupportedVersion { found: 0 }));
+
+        let modded = ContentPack::from_toml_str("[perks]\nscout_fov_bonus = 4").unwrap();
+        let error = Game::load_from_reader(text.as_bytes(), &modded).err();
+        assert!(matches!(error, Some(GameSaveError::ContentMismatch { .. })));
+
+        let error = Game::load_from_reader(&b"{\"seed\": 1}"[..], &content).err();
+        assert!(matches!(error, Some(GameSaveError::Malformed(_))));
+    }
+}
@@ -0,0 +1,125 @@
+//! Loading hand-authored scenarios into a fresh game.
+//! Parsing and validation live in `crate::scenario`; this module only installs the result.
+
+use super::*;
+use crate::content::ContentPack;
+use crate::scenario::{Scenario, ScenarioError};
+use floor_transition::install_floor;
+
+impl Game {
+    /// A new game whose first floor, enemies, items, equipment, and policy come from `scenario`.
+    pub fn from_scenario(
+        seed: u64,
+        content: &ContentPack,
+        difficulty: Difficulty,
+        scenario: &Scenario,
+    ) -> Result<Self, ScenarioError> {
+        let floor = scenario.to_floor(content)?;
+        let kit = scenario.starting_kit(content)?;
+        let mut game = Self::new_with_difficulty(seed, content, GameMode::Ironman, difficulty);
+        install_floor(&mut game, scenario.floor_index, floor);
+        for kind in kit {
+            game.apply_item_effect(kind);
+        }
+        if let Some(policy) = &scenario.policy {
+            game.state.policy = policy.clone();
+        }
+        if scenario.reveal_map {
+            game.state.map.discovered.fill(true);
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::keys;
+    use crate::scenario::{ARENA_MAX_ENEMIES, ScenarioEnemy, ScenarioItem};
+
+    fn corridor(rows: &[&str]) -> Scenario {
+        Scenario {
+            name: "test".to_string(),
+            description: String::new(),
+            map: rows.iter().map(|row| row.to_string()).collect(),
+            enemies: Vec::new(),
+            items: Vec::new(),
+            equipment: Vec::new(),
+            policy: None,
+            floor_index: 1,
+            reveal_map: false,
+        }
+    }
+
+    #[test]
+    fn installs_map_enemies_and_items_from_the_scenario() {
+        let mut scenario = corridor(&["#######", "#@.~.>#", "#######"]);
+        scenario
+            .enemies
+            .push(ScenarioEnemy { kind: ActorKind::FeralHound, pos: Pos { y: 1, x: 4 } });
+        let content = ContentPack::default();
+        let game = Game::from_scenario(5, &content, Difficulty::Normal, &scenario).unwrap();
+
+        let player = &game.state.actors[game.state.player_id];
+        assert_eq!(player.pos, Pos { y: 1, x: 1 });
+        assert_eq!(game.state.actors.len(), 2);
+        assert!(game.state.items.is_empty());
+        assert!(game.state.map.is_hazard(Pos { y: 1, x: 3 }));
+        assert_eq!(game.state.map.tile_at(Pos { y: 1, x: 5 }), TileKind::DownStairs);
+        assert!(game.state.map.is_visible(Pos { y: 1, x: 2 }));
+    }
+
+    #[test]
+    fn rejects_malformed_scenarios() {
+        let content = ContentPack::default();
+        let load = |scenario: &Scenario| {
+            Game::from_scenario(5, &content, Difficulty::Normal, scenario).err()
+        };
+
+        assert_eq!(load(&corridor(&["#..#"])), Some(ScenarioError::PlayerStart { found: 0 }));
+        assert_eq!(load(&corridor(&["#@#", "##"])), Some(ScenarioError::RaggedMap { row: 1 }));
+        assert!(matches!(load(&corridor(&["#@?"])), Some(ScenarioError::UnknownTile { .. })));
+
+        let mut walled_item = corridor(&["#@#"]);
+        walled_item.items.push(ScenarioItem {
+            key: "consumable_minor_hp_pot".to_string(),
+            pos: Pos { y: 0, x: 0 },
+        });
+        assert!(matches!(load(&walled_item), Some(ScenarioError::BadPlacement { .. })));
+        walled_item.items[0].key = "consumable_missing".to_string();
+        assert!(matches!(load(&walled_item), Some(ScenarioError::UnknownItem(_))));
+
+        let mut unknown_kit = corridor(&["#@#"]);
+        unknown_kit.equipment.push("weapon_missing".to_string());
+        assert!(matches!(load(&unknown_kit), Some(ScenarioError::UnknownItem(_))));
+    }
+
+    #[test]
+    fn arena_run_equips_the_kit_and_ends_on_its_stairs() {
+        let kit = vec![keys::WEAPON_BLOOD_AXE.to_string(), keys::PERK_TOUGHNESS.to_string()];
+        let arena = Scenario::arena(&[ActorKind::Goblin, ActorKind::Goblin], kit).unwrap();
+        let content = ContentPack::default();
+        let mut game = Game::from_scenario(9, &content, Difficulty::Normal, &arena).unwrap();
+        assert_eq!(game.active_player_weapon(), Some(keys::WEAPON_BLOOD_AXE));
+        assert_eq!(game.state.active_perks, vec![keys::PERK_TOUGHNESS]);
+        assert_eq!(game.state.actors.len(), 3);
+
+        let outcome = loop {
+            match game.advance(200).stop_reason {
+                AdvanceStopReason::Interrupted(interrupt) => {
+                    let choice = game.available_choices()[0].clone();
+                    game.apply_choice(interrupt.prompt_id(), choice).unwrap();
+                }
+                AdvanceStopReason::Finished(outcome) => break outcome,
+                other => panic!("arena run stalled: {other:?}"),
+            }
+        };
+        assert!(matches!(outcome, RunOutcome::Victory(_)));
+
+        let crowd = [ActorKind::Goblin; ARENA_MAX_ENEMIES + 1];
+        assert_eq!(
+            Scenario::arena(&crowd, Vec::new()),
+            Err(ScenarioError::TooManyEnemies { max: ARENA_MAX_ENEMIES })
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Prompt snoozes: loot and door prompts the player set aside for a number of ticks.
+//! While a snooze lasts its target raises no prompt and auto-explore looks elsewhere.
+
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// A prompt target that can be snoozed; loot is keyed by its tile so the hash stays id-free.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(super) enum SnoozeTarget {
+    Loot(Pos),
+    Door(Pos),
+}
+
+impl Game {
+    /// Snooze `target` for `ticks` ticks and drop the intent that led to it, so the next
+    /// plan looks elsewhere.
+    pub(super) fn resolve_snooze_choice(
+        &mut self,
+        target: SnoozeTarget,
+        prompt: PromptKind,
+        ticks: u32,
+    ) {
+        self.snoozed_targets.insert(target, self.tick + u64::from(ticks));
+        self.state.auto_intent = None;
+        self.log.push(LogEvent::PromptSnoozed { prompt, ticks });
+    }
+
+    pub(super) fn is_snoozed(&self, target: SnoozeTarget) -> bool {
+        self.snoozed_targets.get(&target).is_some_and(|&until| until > self.tick)
+    }
+
+    /// Forget snoozes that have run out.
+    pub(super) fn expire_snoozes(&mut self) {
+        let tick = self.tick;
+        self.snoozed_targets.retain(|_, until| *until > tick);
+    }
+
+    /// Doors auto-explore should not pick as targets right now.
+    pub(super) fn snoozed_doors(&self) -> Vec<Pos> {
+        self.snoozed_targets
+            .keys()
+            .filter_map(|target| match *target {
+                SnoozeTarget::Door(pos) if self.is_snoozed(*target) => Some(pos),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+    use crate::state::Item;
+    use crate::test_support::MapBuilder;
+
+    fn open_game() -> Game {
+        let mut game = Game::new(2744, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game
+    }
+
+    fn answer(game: &mut Game, choice: Choice) {
+        let prompt_id = game.pending_prompt.as_ref().expect("a prompt is pending").id;
+        game.apply_choice(prompt_id, choice).unwrap();
+    }
+
+    #[test]
+    fn snoozed_loot_stays_put_and_reprompts_once_the_snooze_runs_out() {
+        let mut game = open_game();
+        game.state.map = MapBuilder::open(12, 5).discover_all().build();
+        let start = Pos { y: 2, x: 1 };
+        game.state.actors[game.state.player_id].pos = start;
+        let kind = ItemKind::Consumable(keys::CONSUMABLE_MINOR_HP_POT);
+        let item = game.state.items.insert(Item {
+            id: ItemId::default(),
+            kind,
+            pos: start,
+            cursed: false,
+        });
+        game.state.items[item].id = item;
+
+        assert!(matches!(
+            game.advance(1).stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::LootFound { .. })
+        ));
+        assert!(game.available_choices().contains(&Choice::IgnoreFor { ticks: SNOOZE_TICKS }));
+        answer(&mut game, Choice::IgnoreFor { ticks: SNOOZE_TICKS });
+        let snoozed =
+            LogEvent::PromptSnoozed { prompt: PromptKind::LootFound, ticks: SNOOZE_TICKS };
+        assert!(game.log.contains(&snoozed));
+
+        let early = SNOOZE_TICKS - 2;
+        assert!(matches!(game.advance(early).stop_reason, AdvanceStopReason::BudgetExhausted));
+        assert!(game.state.items.contains_key(item), "snoozing leaves the loot on the floor");
+        assert!(matches!(
+            game.advance(10).stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::LootFound { .. })
+        ));
+        assert_eq!(game.current_tick(), u64::from(SNOOZE_TICKS));
+        assert!(game.snoozed_targets.is_empty(), "expired snoozes are forgotten");
+    }
+
+    #[test]
+    fn snoozes_of_any_other_length_are_refused() {
+        let mut game = open_game();
+        game.state.map = MapBuilder::open(12, 5)
+            .wall(Pos { y: 1, x: 4 })
+            .wall(Pos { y: 3, x: 4 })
+            .door(Pos { y: 2, x: 4 })
+            .discover_all()
+            .undiscovered(Pos { y: 2, x: 5 })
+            .build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 3 };
+        assert!(matches!(
+            game.advance(1).stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::DoorBlocked { .. })
+        ));
+        let prompt_id = game.pending_prompt.as_ref().expect("a prompt is pending").id;
+
+        for ticks in [0, 1, SNOOZE_TICKS + 1, u32::MAX] {
+            let result = game.apply_choice(prompt_id, Choice::IgnoreFor { ticks });
+            assert!(matches!(result, Err(GameError::InvalidChoice { .. })), "{ticks} ticks");
+        }
+        assert!(game.snoozed_targets.is_empty());
+        answer(&mut game, Choice::IgnoreFor { ticks: SNOOZE_TICKS });
+    }
+
+    #[test]
+    fn auto_explore_passes_a_snoozed_door_for_other_frontiers() {
+        let mut game = open_game();
+        let door = Pos { y: 2, x: 4 };
+        game.state.map = MapBuilder::open(12, 5)
+            .wall(Pos { y: 1, x: 4 })
+            .wall(Pos { y: 3, x: 4 })
+            .door(door)
+            .discover_all()
+            .undiscovered(Pos { y: 2, x: 5 })
+            .undiscovered(Pos { y: 1, x: 1 })
+            .build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 3 };
+
+        let AdvanceStopReason::Interrupted(Interrupt::DoorBlocked { pos, .. }) =
+            game.advance(1).stop_reason
+        else {
+            panic!("the door is the nearest frontier");
+        };
+        assert_eq!(pos, door);
+        answer(&mut game, Choice::IgnoreFor { ticks: SNOOZE_TICKS });
+        assert_eq!(game.snoozed_doors(), vec![door]);
+
+        game.advance(1);
+        let intent = game.state.auto_intent.expect("auto-explore found another target");
+        assert_ne!(intent.target, door);
+        assert_ne!(game.state.actors[game.state.player_id].pos, Pos { y: 2, x: 3 });
+        assert_eq!(game.state.map.tile_at(door), TileKind::ClosedDoor);
+    }
+}
@@ -0,0 +1,51 @@
+//! Run statistics accumulation: the engine counts player movement here once per tick.
+//! Stats ride along with the game for checkpoints but stay out of the snapshot hash.
+
+use super::*;
+
+impl Game {
+    pub fn run_stats(&self) -> &RunStats {
+        &self.stats
+    }
+
+    /// Count the player's tile when they stand somewhere new since the last tick.
+    pub(super) fn record_player_visit(&mut self) {
+        let floor_index = self.state.floor_index;
+        let pos = self.state.actors[self.state.player_id].pos;
+        if self.stats.last_visit == Some((floor_index, pos)) {
+            return;
+        }
+        self.stats.last_visit = Some((floor_index, pos));
+        let map = &self.state.map;
+        self.stats
+            .floor_visits
+            .entry(floor_index)
+            .or_insert_with(|| FloorVisits::new(map.internal_width, map.internal_height))
+            .record(pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::MapBuilder;
+
+    #[test]
+    fn visits_count_each_arrival_and_ignore_standing_still() {
+        let mut game = Game::new(2718, &ContentPack::default(), GameMode::Ironman);
+        game.state.map = MapBuilder::open(6, 3).build();
+        let player = game.state.player_id;
+        for x in [1, 1, 2, 3, 2, 1] {
+            game.state.actors[player].pos = Pos { y: 1, x };
+            game.record_player_visit();
+        }
+
+        let visits = &game.run_stats().floor_visits[&game.state.floor_index];
+        assert_eq!(visits.count_at(Pos { y: 1, x: 1 }), 2);
+        assert_eq!(visits.count_at(Pos { y: 1, x: 2 }), 2);
+        assert_eq!(visits.count_at(Pos { y: 1, x: 3 }), 1);
+        assert_eq!((visits.total(), visits.revisits(), visits.max_count()), (5, 2, 2));
+        assert_eq!(game.snapshot_hash(), game.clone().snapshot_hash());
+    }
+}
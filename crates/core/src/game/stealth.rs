@@ -0,0 +1,187 @@
+//! Enemy awareness tracking for stealthy play.
+//! This module exists so noticing, spotting, and forgetting the player follow one deterministic rule set.
+//! It does not own encounter prompts; the engine only asks whether an enemy has spotted the player.
+
+use super::lighting::VEIL_SHADOW_NOTICE_RADIUS;
+use super::visibility::has_direct_line_of_sight;
+use super::*;
+use crate::state::{Actor, MAX_AWARENESS};
+
+/// Distance at which noticing is slowest for an enemy with the standard aggro radius;
+/// enemies that notice from farther still gain a little awareness per tick.
+const NOTICE_RADIUS: u32 = 5;
+/// Awareness lost each tick an enemy cannot notice the player.
+const AWARENESS_DECAY_PER_TICK: u8 = 4;
+
+/// Awareness gained in one tick by an enemy that can see the player at `distance`.
+/// Closer enemies notice much faster, so slipping past at range is easier than brushing by.
+fn awareness_gain(distance: u32) -> u8 {
+    let closeness = (NOTICE_RADIUS + 1).saturating_sub(distance).max(1);
+    (closeness * closeness * 2) as u8
+}
+
+impl Game {
+    /// Raise or decay every enemy's awareness for the tick that just finished.
+    /// Enemies ignore a player resting on the active sanctuary tile.
+    pub(super) fn update_enemy_awareness(&mut self) {
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        for enemy_id in self.enemy_ids_in_tile_order() {
+            let distance = manhattan(self.state.actors[enemy_id].pos, player_pos);
+            let notices = self.enemy_sees(enemy_id, player_pos);
+
+            let enemy = &mut self.state.actors[enemy_id];
+            let was_alert = enemy.awareness_state() == AwarenessState::Alert;
+            enemy.awareness = if notices {
+                enemy.awareness.saturating_add(awareness_gain(distance)).min(MAX_AWARENESS)
+            } else {
+                enemy.awareness.saturating_sub(AWARENESS_DECAY_PER_TICK)
+            };
+            if !was_alert && enemy.awareness_state() == AwarenessState::Alert {
+                self.log.push(LogEvent::PlayerSpotted { enemy: enemy_id });
+            }
+        }
+    }
+
+    /// Enemies with line of sight to the player right now, in tile order: the ones that would
+    /// notice the player this tick, as opposed to the ones the player can see.
+    pub fn enemies_seeing_player(&self) -> Vec<EntityId> {
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let mut seeing = self.enemy_ids_in_tile_order();
+        seeing.retain(|id| self.enemy_sees(*id, player_pos));
+        seeing
+    }
+
+    /// Whether `enemy` would see the player standing on `target`: within its kind's aggro
+    /// radius, with a clear line from the enemy's side, and not onto the sanctuary. An enemy
+    /// pulled beyond its leash sees nothing until it is back within range of home.
+    pub(super) fn enemy_sees(&self, enemy: EntityId, target: Pos) -> bool {
+        if self.state.sanctuary_active && target == self.state.sanctuary_tile {
+            return false;
+        }
+        let Actor { kind, pos: enemy_pos, home, .. } = self.state.actors[enemy];
+        let range = self.aggro_range(kind);
+        if manhattan(enemy_pos, home) > range.leash_radius {
+            return false;
+        }
+        let mut notice_radius = self.ambient_notice_radius(range.aggro_radius);
+        if self.veiled_in_shadow(target) {
+            notice_radius = notice_radius.min(VEIL_SHADOW_NOTICE_RADIUS);
+        }
+        manhattan(enemy_pos, target) <= notice_radius
+            && has_direct_line_of_sight(&self.state.map, enemy_pos, target)
+    }
+
+    fn enemy_ids_in_tile_order(&self) -> Vec<EntityId> {
+        let mut enemy_ids: Vec<EntityId> =
+            self.state.actors.keys().filter(|id| *id != self.state.player_id).collect();
+        enemy_ids.sort_by_key(|id| ordering::actor_tile_key(&self.state.actors[*id]));
+        enemy_ids
+    }
+
+    /// Whether `enemy` may be slipped past instead of raising an encounter.
+    pub(super) fn can_sneak_past(&self, enemy: EntityId) -> bool {
+        self.state.policy.fight_or_avoid == FightMode::Avoid
+            && self.state.actors[enemy].awareness_state() != AwarenessState::Alert
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::test_support::add_goblin;
+    use crate::state::Map;
+
+    fn open_floor_game() -> Game {
+        let mut game = Game::new(4242, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let mut map = Map::new(12, 9);
+        map.discovered.fill(true);
+        map.visible.fill(true);
+        game.state.map = map;
+        game.state.actors[game.state.player_id].pos = Pos { y: 4, x: 2 };
+        game
+    }
+
+    #[test]
+    fn nearby_enemy_becomes_alert_and_logs_spotting_once() {
+        let mut game = open_floor_game();
+        let enemy = add_goblin(&mut game, Pos { y: 4, x: 3 });
+
+        game.update_enemy_awareness();
+        assert_eq!(game.state.actors[enemy].awareness, 50);
+        assert_eq!(game.state.actors[enemy].awareness_state(), AwarenessState::Suspicious);
+
+        game.update_enemy_awareness();
+        game.update_enemy_awareness();
+        assert_eq!(game.state.actors[enemy].awareness_state(), AwarenessState::Alert);
+        let spotted = game
+            .log()
+            .iter()
+            .filter(|event| matches!(event, LogEvent::PlayerSpotted { .. }))
+            .count();
+        assert_eq!(spotted, 1, "spotting should be logged only when alert is first reached");
+    }
+
+    #[test]
+    fn only_enemies_in_range_with_a_clear_line_see_the_player() {
+        let mut game = open_floor_game();
+        let near = add_goblin(&mut game, Pos { y: 4, x: 5 });
+        add_goblin(&mut game, Pos { y: 4, x: 10 });
+        add_goblin(&mut game, Pos { y: 1, x: 2 });
+        game.state.map.set_tile(Pos { y: 2, x: 2 }, TileKind::Wall);
+        assert_eq!(game.enemies_seeing_player(), vec![near], "the player sees all three");
+
+        game.state.sanctuary_active = true;
+        game.state.sanctuary_tile = game.state.actors[game.state.player_id].pos;
+        assert!(game.enemies_seeing_player().is_empty());
+    }
+
+    #[test]
+    fn awareness_decays_deterministically_out_of_sight() {
+        let mut game = open_floor_game();
+        let enemy = add_goblin(&mut game, Pos { y: 4, x: 10 });
+        game.state.actors[enemy].awareness = 10;
+
+        game.update_enemy_awareness();
+        assert_eq!(game.state.actors[enemy].awareness, 6);
+        game.update_enemy_awareness();
+        game.update_enemy_awareness();
+        assert_eq!(game.state.actors[enemy].awareness, 0);
+        assert_eq!(game.state.actors[enemy].awareness_state(), AwarenessState::Unaware);
+    }
+
+    #[test]
+    fn avoid_mode_sneaks_past_unaware_adjacent_enemy() {
+        let mut game = open_floor_game();
+        let player = game.state.actors[game.state.player_id].pos;
+        add_goblin(&mut game, Pos { y: player.y, x: player.x + 1 });
+        game.state.policy.fight_or_avoid = FightMode::Avoid;
+
+        let result = game.advance(1);
+        assert!(
+            !matches!(
+                result.stop_reason,
+                AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { .. })
+            ),
+            "an unaware enemy should not interrupt a sneaking player"
+        );
+    }
+
+    #[test]
+    fn alert_enemy_still_interrupts_in_avoid_mode() {
+        let mut game = open_floor_game();
+        let player = game.state.actors[game.state.player_id].pos;
+        let enemy = add_goblin(&mut game, Pos { y: player.y, x: player.x + 1 });
+        game.state.actors[enemy].awareness = MAX_AWARENESS;
+        game.state.policy.fight_or_avoid = FightMode::Avoid;
+
+        match game.advance(1).stop_reason {
+            AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { primary_enemy, .. }) => {
+                assert_eq!(primary_enemy, enemy);
+            }
+            other => panic!("expected alert enemy to interrupt, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! One Dijkstra pass from the player costing every frontier, item, stairs and enemy target.
+//! The result is kept until the map or any actor or item changes, so repeated queries are free.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::Hasher;
+
+use super::*;
+use crate::state::Map;
+use xxhash_rust::xxh3::Xxh3;
+
+impl Game {
+    /// Costs from the player to every target reachable without crossing a hazard, reusing the
+    /// last scan while nothing it depends on has changed.
+    pub fn target_scan(&mut self) -> &TargetScan {
+        let fingerprint = self.target_scan_fingerprint();
+        if self.target_scan.as_ref().is_none_or(|(cached, _)| *cached != fingerprint) {
+            let scan = self.scan_targets();
+            self.debug_counters.target_scans += 1;
+            self.target_scan = Some((fingerprint, scan));
+        }
+        &self.target_scan.as_ref().expect("target scan was just cached").1
+    }
+
+    fn scan_targets(&self) -> TargetScan {
+        let map = &self.state.map;
+        let origin = self.state.actors[self.state.player_id].pos;
+        let costs = dijkstra_costs(map, origin);
+        let hit = |pos: Pos| costs.get(&pos).map(|&cost| ScanHit { pos, cost });
+        let by_cost = |hit: &ScanHit| (hit.cost, ordering::row_major(hit.pos));
+
+        let mut frontiers: Vec<ScanHit> = costs
+            .keys()
+            .filter(|pos| **pos != origin && is_frontier_candidate(map, **pos))
+            .filter_map(|pos| hit(*pos))
+            .collect();
+        frontiers.sort_by_key(by_cost);
+        let mut stairs: Vec<ScanHit> = costs
+            .keys()
+            .filter(|pos| map.tile_at(**pos) == TileKind::DownStairs)
+            .filter_map(|pos| hit(*pos))
+            .collect();
+        stairs.sort_by_key(by_cost);
+        let mut items: Vec<(ItemId, ScanHit)> =
+            self.state.items.iter().filter_map(|(id, item)| Some((id, hit(item.pos)?))).collect();
+        items.sort_by_key(|(id, hit)| (by_cost(hit), *id));
+        let mut enemies: Vec<(EntityId, ScanHit)> = self
+            .state
+            .actors
+            .iter()
+            .filter(|(id, actor)| *id != self.state.player_id && map.is_visible(actor.pos))
+            .filter_map(|(id, actor)| Some((id, hit(actor.pos)?)))
+            .collect();
+        enemies.sort_by_key(|(id, hit)| (by_cost(hit), *id));
+        TargetScan { origin, frontiers, items, stairs, enemies }
+    }
+
+    /// Everything a scan reads: the player's tile, what the map shows, and who stands where.
+    fn target_scan_fingerprint(&self) -> u64 {
+        let map = &self.state.map;
+        let mut hasher = Xxh3::new();
+        let write_pos = |hasher: &mut Xxh3, pos: Pos| {
+            hasher.write_i32(pos.y);
+            hasher.write_i32(pos.x);
+        };
+        hasher.write_u8(self.state.floor_index);
+        for pos in map.positions() {
+            hasher.write_u8(map.tile_at(pos) as u8);
+            let flags = [map.is_discovered(pos), map.is_visible(pos), map.is_hazard(pos)];
+            hasher.write_u8(flags.iter().fold(0, |bits, flag| bits << 1 | u8::from(*flag)));
+        }
+        for (pos, marker) in &map.markers {
+            write_pos(&mut hasher, *pos);
+            hasher.write_u8(*marker as u8);
+        }
+        for actor in self.state.actors.values() {
+            write_pos(&mut hasher, actor.pos);
+        }
+        for item in self.state.items.values() {
+            write_pos(&mut hasher, item.pos);
+        }
+        hasher.finish()
+    }
+}
+
+/// Cheapest cost from `origin` to each discovered tile reachable off hazards. Closed doors are
+/// reached but not passed through, matching the auto-explore target search.
+fn dijkstra_costs(map: &Map, origin: Pos) -> BTreeMap<Pos, u32> {
+    let mut costs = BTreeMap::new();
+    if !map.is_discovered_walkable(origin) {
+        return costs;
+    }
+    let mut open = BTreeSet::from([(0, origin.y, origin.x)]);
+    costs.insert(origin, 0);
+    while let Some((cost, y, x)) = open.pop_first() {
+        let current = Pos { y, x };
+        if map.tile_at(current) == TileKind::ClosedDoor {
+            continue;
+        }
+        for next in neighbors(current) {
+            if !map.is_discovered_walkable_safe(next) {
+                continue;
+            }
+            let next_cost = cost + 1 + map.marker_at(next).map_or(0, MarkerKind::path_penalty);
+            if next_cost < *costs.get(&next).unwrap_or(&u32::MAX) {
+                costs.insert(next, next_cost);
+                open.insert((next_cost, next.y, next.x));
+            }
+        }
+    }
+    costs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, keys};
+    use crate::game::test_support::{MapBuilder, add_goblin};
+    use crate::state::Item;
+
+    #[test]
+    fn one_scan_costs_every_target_and_is_reused_until_something_moves() {
+        let mut game = Game::new(2752, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let mut map = MapBuilder::open(9, 5).discover_all().build();
+        map.set_tile(Pos { y: 2, x: 7 }, TileKind::DownStairs);
+        map.markers.insert(Pos { y: 1, x: 2 }, MarkerKind::Avoid);
+        map.visible.fill(true);
+        game.state.map = map;
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 1 };
+        let kind = ItemKind::Consumable(keys::CONSUMABLE_TORCH);
+        let item = Item { id: ItemId::default(), kind, pos: Pos { y: 1, x: 2 }, cursed: false };
+        game.state.items.insert(item);
+        let near = add_goblin(&mut game, Pos { y: 3, x: 2 });
+        let far = add_goblin(&mut game, Pos { y: 2, x: 5 });
+
+        let scan = game.target_scan().clone();
+        assert_eq!(scan.stairs, vec![ScanHit { pos: Pos { y: 2, x: 7 }, cost: 6 }]);
+        assert_eq!(scan.enemies.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![near, far]);
+        assert_eq!(scan.nearest_enemies(1)[0].1.cost, 2);
+        assert!(scan.frontiers.is_empty(), "every tile is discovered");
+        let [(_, flagged)] = scan.nearest_items(2) else { panic!("one item on the floor") };
+        assert_eq!(flagged.cost, 2 + MarkerKind::Avoid.path_penalty(), "markers add to the cost");
+
+        game.target_scan();
+        assert_eq!(game.engine_internals().target_scans, 1, "an unchanged state reuses the scan");
+        game.state.actors[far].pos = Pos { y: 2, x: 3 };
+        assert_eq!(game.target_scan().nearest_enemies(1)[0].0, far);
+        assert_eq!(game.engine_internals().target_scans, 2);
+    }
+}
@@ -4,6 +4,13 @@
 
 use super::*;
 use crate::state::{Actor, Map};
+pub(super) use crate::test_support::MapBuilder;
+
+/// Mark a single tile as not yet discovered.
+pub(super) fn undiscover(map: &mut Map, pos: Pos) {
+    let idx = map.index(pos).expect("fixture position is on the map");
+    map.discovered[idx] = false;
+}
 
 pub(super) fn open_room_fixture() -> (Map, Pos) {
     let map = Map::new(10, 10);
@@ -12,62 +19,38 @@ pub(super) fn open_room_fixture() -> (Map, Pos) {
 }
 
 pub(super) fn wall_occlusion_fixture() -> (Map, Pos) {
-    let mut map = Map::new(11, 11);
-    for y in 1..10 {
-        for x in 1..10 {
-            map.set_tile(Pos { y, x }, TileKind::Wall);
-        }
-    }
-    for x in 1..10 {
-        map.set_tile(Pos { y: 5, x }, TileKind::Floor);
-    }
-    map.set_tile(Pos { y: 5, x: 6 }, TileKind::Wall);
+    let map = MapBuilder::solid(11, 11)
+        .corridor(Pos { y: 5, x: 1 }, Pos { y: 5, x: 9 })
+        .wall(Pos { y: 5, x: 6 })
+        .build();
     (map, Pos { y: 5, x: 3 })
 }
 
 pub(super) fn hazard_lane_fixture() -> (Map, Pos) {
-    let mut map = Map::new(9, 9);
-    for y in 1..8 {
-        for x in 1..8 {
-            map.set_tile(Pos { y, x }, TileKind::Wall);
-        }
-    }
-    for x in 2..=5 {
-        map.set_tile(Pos { y: 4, x }, TileKind::Floor);
-    }
-    map.discovered.fill(true);
-    map.visible.fill(true);
+    let map = MapBuilder::solid(9, 9)
+        .corridor(Pos { y: 4, x: 2 }, Pos { y: 4, x: 5 })
+        .visible_all()
+        .build();
     (map, Pos { y: 4, x: 2 })
 }
 
 pub(super) fn closed_door_choke_fixture() -> (Map, Pos, Pos) {
-    let mut map = Map::new(10, 10);
-    for x in 0..10 {
-        for y in 0..10 {
-            map.set_tile(Pos { y, x }, if y == 5 { TileKind::Floor } else { TileKind::Wall });
-        }
-    }
-    map.discovered.fill(true);
     let door = Pos { y: 5, x: 6 };
-    map.set_tile(door, TileKind::ClosedDoor);
-    map.discovered[5 * 10 + 7] = false;
+    let map = MapBuilder::solid(10, 10)
+        .corridor(Pos { y: 5, x: 0 }, Pos { y: 5, x: 9 })
+        .door(door)
+        .discover_all()
+        .undiscovered(Pos { y: 5, x: 7 })
+        .build();
     (map, Pos { y: 5, x: 5 }, door)
 }
 
 pub(super) fn corner_handle_fixture() -> (Map, Pos) {
-    let mut map = Map::new(20, 15);
-    for y in 1..(map.internal_height - 1) {
-        for x in 1..(map.internal_width - 1) {
-            map.set_tile(Pos { y: y as i32, x: x as i32 }, TileKind::Wall);
-        }
-    }
-    for y in 3..=7 {
-        for x in 2..=6 {
-            map.set_tile(Pos { y, x }, TileKind::Floor);
-        }
-    }
-    map.set_tile(Pos { y: 5, x: 7 }, TileKind::Floor);
-    map.set_tile(Pos { y: 5, x: 8 }, TileKind::ClosedDoor);
+    let map = MapBuilder::solid(20, 15)
+        .room(Pos { y: 3, x: 2 }, Pos { y: 7, x: 6 })
+        .tile(Pos { y: 5, x: 7 }, TileKind::Floor)
+        .door(Pos { y: 5, x: 8 })
+        .build();
     (map, Pos { y: 5, x: 6 })
 }
 
@@ -85,6 +68,9 @@ pub(super) fn add_goblin(game: &mut Game, pos: Pos) -> EntityId {
         reserve_weapon: None,
         next_action_tick: 12,
         speed: 12,
+        awareness: 0,
+        unique: None,
+        home: pos,
     };
     let id = game.state.actors.insert(enemy);
     game.state.actors[id].id = id;
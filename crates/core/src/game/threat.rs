@@ -15,6 +15,8 @@ pub(super) fn danger_tags_for_kind(kind: ActorKind) -> Vec<DangerTag> {
         ActorKind::Gargoyle => vec![DangerTag::Melee],
         ActorKind::ShadowStalker => vec![DangerTag::Melee, DangerTag::Burst],
         ActorKind::AbyssalWarden => vec![DangerTag::Melee, DangerTag::Burst],
+        ActorKind::HollowKing => vec![DangerTag::Melee, DangerTag::Burst],
+        ActorKind::BoneArcher => vec![DangerTag::Ranged],
     }
 }
 
@@ -38,6 +40,8 @@ mod tests {
             ActorKind::Gargoyle,
             ActorKind::ShadowStalker,
             ActorKind::AbyssalWarden,
+            ActorKind::HollowKing,
+            ActorKind::BoneArcher,
         ];
         for kind in kinds {
             let tags = danger_tags_for_kind(kind);
@@ -0,0 +1,151 @@
+//! The run's ledger of slain uniques: bosses and objective elites.
+//! Kill sites record them and floor installs and revisits consult the ledger, so a slain
+//! unique never comes back. Wandering spawns are never unique.
+
+use super::*;
+use crate::state::Actor;
+
+impl Game {
+    /// Enter a just-killed enemy in the ledger if it was unique.
+    pub(super) fn record_slain_unique(&mut self, unique: Option<UniqueEnemy>) {
+        if let Some(unique) = unique {
+            self.state.slain_uniques.insert(unique);
+        }
+    }
+
+    /// Whether `actor` is a unique the run has already slain.
+    pub(super) fn unique_slain(&self, actor: &Actor) -> bool {
+        actor.unique.is_some_and(|unique| self.state.slain_uniques.contains(&unique))
+    }
+
+    /// Drop slain uniques from a freshly installed floor: its boss, and its elite when the
+    /// floor was generated before. Runs before the floor's objective is assigned.
+    pub(super) fn cull_slain_uniques(&mut self) {
+        let slain: Vec<EntityId> = self
+            .state
+            .actors
+            .iter()
+            .filter(|(_, actor)| self.unique_slain(actor))
+            .map(|(id, _)| id)
+            .collect();
+        let floor = UniqueEnemy::FloorElite(self.state.floor_index);
+        let elite = self.state.slain_uniques.contains(&floor).then(|| self.pick_elite()).flatten();
+        for enemy in slain.into_iter().chain(elite) {
+            self.state.actors.remove(enemy);
+        }
+    }
+
+    /// The roster's intro line for `enemy` when it is a branch boss.
+    pub(super) fn boss_intro(&self, enemy: EntityId) -> Option<&'static str> {
+        let Some(UniqueEnemy::Boss(kind)) = self.state.actors.get(enemy)?.unique else {
+            return None;
+        };
+        self.boss_roster.rule_of(kind).map(|rule| rule.intro)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::content::{BossPlacement, BossRule, ContentPack};
+    use crate::game::floor_transition::insert_enemy;
+    use crate::mapgen::{BranchProfile, MAX_FLOORS};
+
+    fn boss_count(game: &Game) -> usize {
+        let boss = Some(UniqueEnemy::Boss(ActorKind::AbyssalWarden));
+        game.state.actors.values().filter(|actor| actor.unique == boss).count()
+    }
+
+    #[test]
+    fn slain_elites_and_bosses_enter_the_hashed_ledger() {
+        let mut game = Game::new(2736, &ContentPack::default(), GameMode::Ironman);
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        let floor = game.state.floor_index;
+        let elite = insert_enemy(&mut game, ActorKind::Goblin, Pos { y: 1, x: 1 }, 3);
+        game.state.actors[elite].unique = Some(UniqueEnemy::FloorElite(floor));
+        let boss = insert_enemy(&mut game, ActorKind::AbyssalWarden, Pos { y: 1, x: 2 }, 3);
+        game.state.actors[boss].unique = Some(UniqueEnemy::Boss(ActorKind::AbyssalWarden));
+        let ordinary = insert_enemy(&mut game, ActorKind::Gargoyle, Pos { y: 1, x: 3 }, 3);
+        let before = game.snapshot_hash();
+
+        for enemy in [elite, boss, ordinary] {
+            game.apply_player_strike(enemy, 999);
+        }
+        assert_eq!(
+            game.state.slain_uniques,
+            BTreeSet::from([
+                UniqueEnemy::Boss(ActorKind::AbyssalWarden),
+                UniqueEnemy::FloorElite(floor)
+            ])
+        );
+        assert_ne!(game.snapshot_hash(), before, "the ledger is part of the snapshot hash");
+    }
+
+    #[test]
+    fn a_slain_boss_stays_dead_on_fresh_and_revisited_floors() {
+        let mut game = Game::new(2736, &ContentPack::default(), GameMode::Ironman);
+        let mut control = game.clone();
+        control.descend_to_floor(MAX_FLOORS);
+        assert_eq!(boss_count(&control), 1, "the final floor spawns its boss");
+
+        game.state.slain_uniques.insert(UniqueEnemy::Boss(ActorKind::AbyssalWarden));
+        game.descend_to_floor(MAX_FLOORS);
+        assert_eq!(boss_count(&game), 0);
+        assert_eq!(game.state.actors.len(), control.state.actors.len() - 1, "only the boss");
+
+        let stale = insert_enemy(&mut game, ActorKind::AbyssalWarden, Pos { y: 1, x: 1 }, 3);
+        game.state.actors[stale].unique = Some(UniqueEnemy::Boss(ActorKind::AbyssalWarden));
+        game.descend_to_floor(MAX_FLOORS - 1);
+        game.descend_to_floor(MAX_FLOORS);
+        assert_eq!(boss_count(&game), 0, "a revisit does not bring a stashed boss back");
+    }
+
+    #[test]
+    fn a_regenerated_floor_loses_the_elite_already_slain_there() {
+        let mut game = Game::new(2736, &ContentPack::default(), GameMode::Ironman);
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        insert_enemy(&mut game, ActorKind::Goblin, Pos { y: 1, x: 1 }, 3);
+        let elite = insert_enemy(&mut game, ActorKind::LivingArmor, Pos { y: 1, x: 2 }, 3);
+        game.state.slain_uniques.insert(UniqueEnemy::FloorElite(game.state.floor_index));
+
+        game.cull_slain_uniques();
+        assert!(!game.state.actors.contains_key(elite));
+        assert_eq!(game.state.actors.len(), 2, "only the elite is culled");
+    }
+
+    #[test]
+    fn each_branch_ends_on_its_roster_boss_with_its_own_intro() {
+        let boss_on_final_floor = |content: &ContentPack, branch| {
+            let mut game = Game::new(2748, content, GameMode::Ironman);
+            game.state.branch_profile = branch;
+            game.descend_to_floor(MAX_FLOORS);
+            let (id, actor) = game
+                .state
+                .actors
+                .iter()
+                .find(|(_, actor)| matches!(actor.unique, Some(UniqueEnemy::Boss(_))))
+                .expect("the final floor spawns a boss");
+            (actor.kind, game.boss_intro(id))
+        };
+        let content = ContentPack::default();
+        let (kind, intro) = boss_on_final_floor(&content, BranchProfile::BranchA);
+        assert_eq!(kind, ActorKind::AbyssalWarden);
+        assert_eq!(intro, Some("The Abyssal Warden bars the final stairs"));
+        let (kind, intro) = boss_on_final_floor(&content, BranchProfile::BranchB);
+        assert_eq!(kind, ActorKind::HollowKing);
+        assert_eq!(intro, Some("The Hollow King rises from his empty throne"));
+
+        let mut content = ContentPack::default();
+        let rule = BossRule {
+            kind: ActorKind::Gargoyle,
+            floor: MAX_FLOORS,
+            placement: BossPlacement::HoldCenter,
+            intro: "Stone stirs",
+        };
+        content.bosses.by_branch.insert(BranchProfile::BranchB, rule);
+        let (kind, intro) = boss_on_final_floor(&content, BranchProfile::BranchB);
+        assert_eq!((kind, intro), (ActorKind::Gargoyle, Some("Stone stirs")));
+    }
+}
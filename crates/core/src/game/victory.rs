@@ -0,0 +1,68 @@
+//! Victory classification: whether the run fully cleared, speed cleared or skipped the boss.
+//! Settled as the player leaves the final floor, from the floors as they were left.
+
+use super::*;
+use crate::state::Map;
+
+impl Game {
+    /// The category of the win the player is about to take by leaving the final floor.
+    pub(super) fn victory_kind(&self) -> VictoryKind {
+        let boss_alive = self
+            .state
+            .actors
+            .values()
+            .any(|actor| matches!(actor.unique, Some(UniqueEnemy::Boss(_))));
+        if boss_alive {
+            return VictoryKind::BossSkip;
+        }
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let left_unexplored = has_reachable_frontier(&self.state.map, player_pos)
+            || self
+                .state
+                .stored_floors
+                .values()
+                .any(|floor| has_reachable_frontier(&floor.map, floor.entry_tile));
+        if left_unexplored { VictoryKind::SpeedClear } else { VictoryKind::FullClear }
+    }
+}
+
+/// Whether auto-explore could still find something new on `map` from `start`.
+fn has_reachable_frontier(map: &Map, start: Pos) -> bool {
+    reachable_discovered_walkable_tiles(map, start)
+        .into_iter()
+        .any(|pos| is_frontier_candidate(map, pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::floor_transition::insert_enemy;
+    use crate::state::StoredFloor;
+    use crate::test_support::MapBuilder;
+
+    #[test]
+    fn victory_kind_tells_full_clears_from_speed_clears_and_boss_skips() {
+        let mut game = Game::new(2747, &ContentPack::default(), GameMode::Ironman);
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 2 };
+        game.state.map = MapBuilder::open(8, 5).discover_all().build();
+        assert_eq!(game.victory_kind(), VictoryKind::FullClear);
+
+        let mut skimmed = game.clone();
+        let earlier = StoredFloor {
+            map: MapBuilder::open(8, 5).discover_all().undiscovered(Pos { y: 3, x: 6 }).build(),
+            actors: Vec::new(),
+            items: Vec::new(),
+            entry_tile: Pos { y: 1, x: 1 },
+            provenance: game.state.floor_provenance,
+            objective: None,
+        };
+        skimmed.state.stored_floors.insert(1, earlier);
+        assert_eq!(skimmed.victory_kind(), VictoryKind::SpeedClear, "an earlier floor was skimmed");
+
+        let boss = insert_enemy(&mut game, ActorKind::AbyssalWarden, Pos { y: 1, x: 6 }, 1);
+        game.state.actors[boss].unique = Some(UniqueEnemy::Boss(ActorKind::AbyssalWarden));
+        assert_eq!(game.victory_kind(), VictoryKind::BossSkip);
+    }
+}
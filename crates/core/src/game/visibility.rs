@@ -3,6 +3,7 @@
 //! It does not own movement planning or encounter policy decisions.
 
 use super::*;
+use crate::geometry;
 use crate::state::Map;
 
 fn transform_octant(orig: Pos, x: i32, y: i32, oct: u8) -> Pos {
@@ -21,6 +22,7 @@ fn transform_octant(orig: Pos, x: i32, y: i32, oct: u8) -> Pos {
 
 pub(super) fn compute_fov(map: &mut Map, origin: Pos, range: i32) {
     let prev_discovered = map.discovered.clone();
+    let prev_revision = map.revision;
     map.clear_visible();
     map.set_visible(origin, true);
     for octant in 0..8 {
@@ -38,13 +40,20 @@ pub(super) fn compute_fov(map: &mut Map, origin: Pos, range: i32) {
             if p == origin || !map.is_visible(p) {
                 continue;
             }
-            if !has_direct_line_of_sight(map, origin, p) {
-                let idx = (y as usize) * map.internal_width + (x as usize);
+            let (dy, dx) = (p.y - origin.y, p.x - origin.x);
+            let in_darkness =
+                map.light.unlit_range.is_some_and(|unlit| dy * dy + dx * dx > unlit * unlit)
+                    && !map.is_lit(p);
+            if (in_darkness || !has_direct_line_of_sight(map, origin, p))
+                && let Some(idx) = map.index(p)
+            {
                 map.visible[idx] = false;
                 map.discovered[idx] = prev_discovered[idx];
             }
         }
     }
+    // Tiles lit and then culled for lack of a sight line leave discovery as it was.
+    map.revision = prev_revision + u64::from(map.discovered != prev_discovered);
 }
 
 #[derive(Clone, Copy)]
@@ -99,45 +108,10 @@ fn scan_octant(map: &mut Map, orig: Pos, range: i32, dist: i32, start: Slope, en
     }
 }
 
-fn has_direct_line_of_sight(map: &Map, origin: Pos, target: Pos) -> bool {
-    let dx = target.x - origin.x;
-    let dy = target.y - origin.y;
-    let sx = dx.signum();
-    let sy = dy.signum();
-    let total_dist_x = dx.abs();
-    let total_dist_y = dy.abs();
-
-    let mut x = origin.x;
-    let mut y = origin.y;
-    let mut current_step_x = 0;
-    let mut current_step_y = 0;
-
-    while current_step_x < total_dist_x || current_step_y < total_dist_y {
-        let lhs = (1 + 2 * current_step_x) * total_dist_y;
-        let rhs = (1 + 2 * current_step_y) * total_dist_x;
-
-        if lhs == rhs {
-            x += sx;
-            y += sy;
-            current_step_x += 1;
-            current_step_y += 1;
-        } else if lhs < rhs {
-            x += sx;
-            current_step_x += 1;
-        } else {
-            y += sy;
-            current_step_y += 1;
-        }
-
-        if x == target.x && y == target.y {
-            break;
-        }
-        let tile = map.tile_at(Pos { y, x });
-        if tile == TileKind::Wall || tile == TileKind::ClosedDoor {
-            return false;
-        }
-    }
-    true
+pub(super) fn has_direct_line_of_sight(map: &Map, origin: Pos, target: Pos) -> bool {
+    let line = geometry::line_tiles(origin, target);
+    let between = &line[..line.len().saturating_sub(1)];
+    between.iter().all(|pos| !matches!(map.tile_at(*pos), TileKind::Wall | TileKind::ClosedDoor))
 }
 
 #[allow(dead_code)]
@@ -235,17 +209,14 @@ mod tests {
             for px in (r_start + 1)..r_end {
                 let origin = Pos { y: py, x: px };
                 compute_fov(&mut map, origin, 15);
-                for y in 0..map.internal_height {
-                    for x in 0..map.internal_width {
-                        let p = Pos { y: y as i32, x: x as i32 };
-                        if (p.y < r_start || p.y > r_end || p.x < r_start || p.x > r_end)
-                            && map.is_visible(p)
-                        {
-                            panic!(
-                                "Light leaked to {p:?} from origin {origin:?}\n{}",
-                                draw_map_diag(&map, origin)
-                            );
-                        }
+                for p in map.positions() {
+                    if (p.y < r_start || p.y > r_end || p.x < r_start || p.x > r_end)
+                        && map.is_visible(p)
+                    {
+                        panic!(
+                            "Light leaked to {p:?} from origin {origin:?}\n{}",
+                            draw_map_diag(&map, origin)
+                        );
                     }
                 }
             }
@@ -0,0 +1,75 @@
+//! Deliberate waiting: the player holds position for a set number of ticks, say to rest on
+//! the sanctuary. The stall watchdog treats a wait as progress, unlike idleness it never asked for.
+
+use super::*;
+
+impl Game {
+    /// Hold position for the next `ticks` ticks; `0` ends a wait early.
+    /// Enemies still act while the player waits, and any new prompt cuts the wait short.
+    pub fn apply_wait(&mut self, ticks: u32) -> Result<(), GameError> {
+        if !self.at_pause_boundary && self.pending_prompt.is_none() {
+            return Err(GameError::NotAtPauseBoundary { tick: self.tick });
+        }
+        self.state.wait_ticks_remaining = ticks;
+        self.no_progress_ticks = 0;
+        self.stats.inputs_accepted += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentPack;
+    use crate::game::floor_transition::insert_enemy;
+    use crate::game::test_support::MapBuilder;
+    use crate::state::MAX_AWARENESS;
+
+    /// A fully explored room with nothing left to do, so an unattended player idles.
+    fn finished_room() -> Game {
+        let mut game = Game::new(2735, &ContentPack::default(), GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(7, 5).discover_all().build();
+        let pos = Pos { y: 2, x: 1 };
+        game.state.actors[game.state.player_id].pos = pos;
+        game.refresh_fov(pos);
+        game.state.sanctuary_tile = pos;
+        game.state.sanctuary_active = true;
+        game.at_pause_boundary = true;
+        game
+    }
+
+    #[test]
+    fn waiting_on_the_sanctuary_is_not_a_stall() {
+        let mut game = finished_room();
+        assert!(game.apply_wait(2 * MAX_NO_PROGRESS_TICKS).is_ok());
+        let result = game.advance(2 * MAX_NO_PROGRESS_TICKS);
+        assert!(matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted));
+        assert_eq!(result.breakdown.ticks_waiting, 2 * MAX_NO_PROGRESS_TICKS);
+        assert_eq!(game.state.actors[game.state.player_id].pos, game.state.sanctuary_tile);
+        assert_eq!(game.state.wait_ticks_remaining, 0);
+
+        let result = game.advance(2 * MAX_NO_PROGRESS_TICKS);
+        assert_eq!(result.simulated_ticks, MAX_NO_PROGRESS_TICKS, "idling unasked still stalls");
+        assert!(matches!(result.stop_reason, AdvanceStopReason::EngineFailure(_)));
+        assert!(matches!(game.apply_wait(1), Err(GameError::NotAtPauseBoundary { .. })));
+    }
+
+    #[test]
+    fn a_new_prompt_cuts_a_wait_short() {
+        let mut game = finished_room();
+        game.state.sanctuary_active = false;
+        let enemy = insert_enemy(&mut game, ActorKind::Goblin, Pos { y: 2, x: 3 }, 3);
+        game.state.actors[enemy].awareness = MAX_AWARENESS;
+        game.apply_wait(50).expect("wait at a pause boundary");
+
+        let result = game.advance(50);
+        assert!(matches!(
+            result.stop_reason,
+            AdvanceStopReason::Interrupted(Interrupt::EnemyEncounter { .. })
+        ));
+        assert_eq!(result.breakdown.ticks_waiting, result.simulated_ticks);
+        assert_eq!(game.state.wait_ticks_remaining, 0);
+    }
+}
@@ -0,0 +1,137 @@
+//! Wandering monsters: enemies that arrive on a floor the longer the player stays on it.
+//! Spawn rolls are seeded from the run seed and tick, so replays see the same arrivals.
+
+use std::collections::BTreeSet;
+
+use rand_chacha::ChaCha8Rng;
+use rand_chacha::rand_core::{Rng, SeedableRng};
+
+use super::floor_transition::insert_enemy;
+use super::*;
+use crate::mapgen::roll_enemy_kind;
+
+const WANDERING_SPAWN_STREAM: u64 = 0x3A9D_57E1_C0DE_4A11;
+
+impl Game {
+    /// Roll for a wandering enemy when this tick lands on the floor's spawn schedule.
+    pub(super) fn step_wandering_spawns(&mut self) {
+        let rate = self.director_wandering_rate();
+        let on_floor = self.tick.saturating_sub(self.state.floor_entry_tick);
+        if self.state.wanderers_this_floor >= rate.max_per_floor
+            || on_floor < rate.grace_ticks
+            || !(on_floor - rate.grace_ticks).is_multiple_of(rate.interval_ticks.max(1))
+        {
+            return;
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed ^ WANDERING_SPAWN_STREAM ^ self.tick);
+        if rng.next_u64() % 100 >= u64::from(rate.chance_percent) {
+            return;
+        }
+        let candidates = self.wandering_spawn_tiles(rate.min_distance);
+        if candidates.is_empty() {
+            return;
+        }
+        let pos = candidates[(rng.next_u64() % candidates.len() as u64) as usize];
+        let roll_index = usize::from(self.state.wanderers_this_floor);
+        let kind = roll_enemy_kind(self.state.floor_index, rng.next_u64(), roll_index);
+        let first_action_tick = self.tick + u64::from(self.enemy_base_stats(kind).speed);
+        insert_enemy(self, kind, pos, first_action_tick);
+        self.state.wanderers_this_floor += 1;
+    }
+
+    /// Discovered, currently unseen open tiles at least `min_distance` from the player.
+    fn wandering_spawn_tiles(&self, min_distance: u32) -> Vec<Pos> {
+        let map = &self.state.map;
+        let player_pos = self.state.actors[self.state.player_id].pos;
+        let occupied: BTreeSet<Pos> = self.state.actors.values().map(|actor| actor.pos).collect();
+        map.positions()
+            .filter(|pos| {
+                map.tile_at(*pos) == TileKind::Floor
+                    && map.is_discovered(*pos)
+                    && !map.is_visible(*pos)
+                    && !map.is_hazard(*pos)
+                    && !occupied.contains(pos)
+                    && *pos != self.state.sanctuary_tile
+                    && manhattan(player_pos, *pos) >= min_distance
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::{ContentPack, WanderingSpawnRate};
+    use crate::game::test_support::MapBuilder;
+
+    fn lingering_game(rate: WanderingSpawnRate) -> Game {
+        let content = ContentPack { wandering_spawns: rate, ..ContentPack::default() };
+        let mut game = Game::new(2703, &content, GameMode::Ironman);
+        game.state.items.clear();
+        game.state.actors.retain(|id, _| id == game.state.player_id);
+        game.state.map = MapBuilder::open(30, 5).discover_all().build();
+        game.state.actors[game.state.player_id].pos = Pos { y: 2, x: 1 };
+        game.state.map.set_visible(Pos { y: 2, x: 1 }, true);
+        game
+    }
+
+    fn step_next_tick(game: &mut Game) {
+        game.tick += 1;
+        game.step_wandering_spawns();
+    }
+
+    fn enemy_count(game: &Game) -> usize {
+        game.state.actors.len() - 1
+    }
+
+    #[test]
+    fn wanderers_arrive_after_the_grace_period_up_to_the_floor_cap() {
+        let rate = WanderingSpawnRate {
+            grace_ticks: 10,
+            interval_ticks: 5,
+            chance_percent: 100,
+            max_per_floor: 2,
+            min_distance: 20,
+        };
+        let mut game = lingering_game(rate);
+        for _ in 0..10 {
+            step_next_tick(&mut game);
+        }
+        assert_eq!(enemy_count(&game), 1, "the first wanderer arrives once the grace ends");
+
+        for _ in 0..20 {
+            step_next_tick(&mut game);
+        }
+        assert_eq!(enemy_count(&game), 2, "spawns stop at the per-floor cap");
+        let player_pos = game.state.actors[game.state.player_id].pos;
+        for actor in game.state.actors.values().filter(|actor| actor.kind != ActorKind::Player) {
+            assert!(manhattan(player_pos, actor.pos) >= 20);
+            assert!(!game.state.map.is_visible(actor.pos));
+        }
+    }
+
+    #[test]
+    fn spawns_are_deterministic_and_can_be_turned_off() {
+        let rate = WanderingSpawnRate { grace_ticks: 0, chance_percent: 50, ..Default::default() };
+        let run = |rate| {
+            let mut game = lingering_game(rate);
+            for _ in 0..1000 {
+                step_next_tick(&mut game);
+            }
+            let mut wanderers: Vec<_> =
+                game.state.actors.values().map(|actor| (actor.kind, actor.pos)).collect();
+            wanderers.sort();
+            wanderers
+        };
+        assert!(run(rate).len() > 1, "a long stay should draw wanderers");
+        assert_eq!(run(rate), run(rate));
+
+        let off = WanderingSpawnRate { max_per_floor: 0, ..rate };
+        let mut game = lingering_game(off);
+        for _ in 0..1000 {
+            step_next_tick(&mut game);
+        }
+        assert_eq!(enemy_count(&game), 0);
+    }
+}
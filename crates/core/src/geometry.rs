@@ -0,0 +1,150 @@
+//! Targeting templates: the tiles a line, cone or radius effect covers from an origin.
+//! Enumeration order is fixed per template and off-map tiles are clipped, never wrapped.
+
+use crate::ordering::row_major;
+use crate::state::Map;
+use crate::types::Pos;
+
+/// The cardinal direction a cone opens toward.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Facing {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Facing {
+    /// One step in this direction as `(dy, dx)`.
+    fn step(self) -> (i32, i32) {
+        match self {
+            Self::North => (-1, 0),
+            Self::East => (0, 1),
+            Self::South => (1, 0),
+            Self::West => (0, -1),
+        }
+    }
+}
+
+/// The shape of an area effect, laid out from the tile it is used at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TargetTemplate {
+    /// The tiles stepped through on the way to `target`, ending on it; stops at the map edge.
+    Line { target: Pos },
+    /// A 90-degree wedge opening toward `facing`, `length` tiles deep; excludes the origin.
+    Cone { facing: Facing, length: u32 },
+    /// Every tile within `radius` steps, origin included.
+    Radius { radius: u32 },
+}
+
+impl TargetTemplate {
+    /// The on-map tiles this template covers from `origin`.
+    ///
+    /// Lines come in traversal order, cones nearest row first and row-major within a row,
+    /// and radii nearest first and row-major among equal distances. Walls are not filtered.
+    pub fn tiles(&self, map: &Map, origin: Pos) -> Vec<Pos> {
+        match *self {
+            Self::Line { target } => line_tiles(origin, target)
+                .into_iter()
+                .take_while(|pos| map.in_bounds(*pos))
+                .collect(),
+            Self::Cone { facing, length } => cone_tiles(map, origin, facing, length as i32),
+            Self::Radius { radius } => radius_tiles(map, origin, radius as i32),
+        }
+    }
+}
+
+/// The tiles from `origin` to `target`, excluding the origin and ending on the target.
+/// Diagonal steps are taken where the line passes exactly through a corner.
+pub fn line_tiles(origin: Pos, target: Pos) -> Vec<Pos> {
+    let (dx, dy) = (target.x - origin.x, target.y - origin.y);
+    let (sx, sy) = (dx.signum(), dy.signum());
+    let (total_x, total_y) = (dx.abs(), dy.abs());
+    let (mut step_x, mut step_y) = (0, 0);
+    let mut pos = origin;
+    let mut tiles = Vec::new();
+    while step_x < total_x || step_y < total_y {
+        let lhs = (1 + 2 * step_x) * total_y;
+        let rhs = (1 + 2 * step_y) * total_x;
+        if lhs <= rhs {
+            pos.x += sx;
+            step_x += 1;
+        }
+        if lhs >= rhs {
+            pos.y += sy;
+            step_y += 1;
+        }
+        tiles.push(pos);
+    }
+    tiles
+}
+
+fn cone_tiles(map: &Map, origin: Pos, facing: Facing, length: i32) -> Vec<Pos> {
+    let (dy, dx) = facing.step();
+    let mut tiles = Vec::new();
+    for depth in 1..=length {
+        let mut row: Vec<Pos> = (-depth..=depth)
+            .map(|side| Pos {
+                y: origin.y + dy * depth + dx * side,
+                x: origin.x + dx * depth + dy * side,
+            })
+            .filter(|pos| map.in_bounds(*pos))
+            .collect();
+        row.sort_by_key(|pos| row_major(*pos));
+        tiles.extend(row);
+    }
+    tiles
+}
+
+fn radius_tiles(map: &Map, origin: Pos, radius: i32) -> Vec<Pos> {
+    let mut tiles: Vec<Pos> = (-radius..=radius)
+        .flat_map(|dy| {
+            let reach = radius - dy.abs();
+            (-reach..=reach).map(move |dx| Pos { y: origin.y + dy, x: origin.x + dx })
+        })
+        .filter(|pos| map.in_bounds(*pos))
+        .collect();
+    tiles.sort_by_key(|pos| (pos.y.abs_diff(origin.y) + pos.x.abs_diff(origin.x), row_major(*pos)));
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, x: i32) -> Pos {
+        Pos { y, x }
+    }
+
+    #[test]
+    fn radius_lists_nearest_first_and_clips_at_the_corner() {
+        let map = Map::new(6, 5);
+        let tiles = TargetTemplate::Radius { radius: 1 }.tiles(&map, at(0, 0));
+        assert_eq!(tiles, vec![at(0, 0), at(0, 1), at(1, 0)]);
+
+        let full = TargetTemplate::Radius { radius: 2 }.tiles(&map, at(2, 2));
+        assert_eq!(full.len(), 13);
+        assert_eq!(&full[..5], &[at(2, 2), at(1, 2), at(2, 1), at(2, 3), at(3, 2)]);
+    }
+
+    #[test]
+    fn cone_widens_by_row_and_drops_tiles_past_the_border() {
+        let map = Map::new(6, 5);
+        let tiles = TargetTemplate::Cone { facing: Facing::East, length: 2 }.tiles(&map, at(0, 3));
+        assert_eq!(tiles, vec![at(0, 4), at(1, 4), at(0, 5), at(1, 5), at(2, 5)]);
+
+        let north = TargetTemplate::Cone { facing: Facing::North, length: 3 }.tiles(&map, at(1, 2));
+        assert_eq!(north, vec![at(0, 1), at(0, 2), at(0, 3)]);
+    }
+
+    #[test]
+    fn line_follows_the_sight_line_and_stops_at_the_edge() {
+        let map = Map::new(6, 5);
+        assert_eq!(line_tiles(at(0, 0), at(2, 2)), vec![at(1, 1), at(2, 2)]);
+        assert_eq!(line_tiles(at(1, 1), at(1, 1)), Vec::<Pos>::new());
+
+        let clipped = TargetTemplate::Line { target: at(2, 8) }.tiles(&map, at(2, 3));
+        assert_eq!(clipped, vec![at(2, 4), at(2, 5)]);
+        assert_eq!(clipped, TargetTemplate::Line { target: at(2, 8) }.tiles(&map, at(2, 3)));
+    }
+}
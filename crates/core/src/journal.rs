@@ -1,4 +1,4 @@
-use crate::types::{Choice, ChoicePromptId, PolicyUpdate};
+use crate::types::{Choice, ChoicePromptId, Difficulty, Direction, MarkerKind, PolicyUpdate, Pos};
 
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +8,11 @@ pub struct InputJournal {
     pub build_id: String,
     pub content_hash: u64,
     pub seed: u64,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    /// Whether the run used the adaptive difficulty director; absent in older journals.
+    #[serde(default)]
+    pub director: bool,
     pub inputs: Vec<InputRecord>,
 }
 
@@ -15,6 +20,18 @@ pub struct InputJournal {
 pub struct InputRecord {
     pub seq: u64,
     pub payload: InputPayload,
+    /// Wall-clock pacing when the run was recorded with it; never fed to the simulation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pacing: Option<InputPacing>,
+}
+
+/// Real-time context for one input, kept out of the hash chain and the snapshot hash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputPacing {
+    /// Milliseconds since the previous input (or since the run started, for the first one).
+    pub wall_ms_since_prev: u64,
+    /// Milliseconds of that interval the game spent paused or waiting on a prompt.
+    pub paused_ms: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -22,6 +39,25 @@ pub enum InputPayload {
     Choice { prompt_id: ChoicePromptId, choice: Choice },
     PolicyUpdate { tick_boundary: u64, update: PolicyUpdate },
     SwapActiveWeapon { tick_boundary: u64 },
+    Brace { tick_boundary: u64 },
+    Wait { tick_boundary: u64, ticks: u32 },
+    SetMarker { tick_boundary: u64, pos: Pos, marker: Option<MarkerKind> },
+    ManualStep { tick_boundary: u64, direction: Direction },
+}
+
+impl InputPayload {
+    /// Tick at which a pause-boundary input was accepted; choices are keyed by prompt instead.
+    pub fn tick_boundary(&self) -> Option<u64> {
+        match self {
+            Self::Choice { .. } => None,
+            Self::PolicyUpdate { tick_boundary, .. }
+            | Self::SwapActiveWeapon { tick_boundary }
+            | Self::Brace { tick_boundary }
+            | Self::Wait { tick_boundary, .. }
+            | Self::SetMarker { tick_boundary, .. }
+            | Self::ManualStep { tick_boundary, .. } => Some(*tick_boundary),
+        }
+    }
 }
 
 impl InputJournal {
@@ -31,23 +67,47 @@ impl InputJournal {
             build_id: "dev".to_string(),
             content_hash: 0,
             seed,
+            difficulty: Difficulty::Normal,
+            director: false,
             inputs: Vec::new(),
         }
     }
 
+    fn push(&mut self, seq: u64, payload: InputPayload) {
+        self.inputs.push(InputRecord { seq, payload, pacing: None });
+    }
+
     pub fn append_choice(&mut self, prompt_id: ChoicePromptId, choice: Choice, seq: u64) {
-        self.inputs.push(InputRecord { seq, payload: InputPayload::Choice { prompt_id, choice } });
+        self.push(seq, InputPayload::Choice { prompt_id, choice });
     }
 
     pub fn append_policy_update(&mut self, tick_boundary: u64, update: PolicyUpdate, seq: u64) {
-        self.inputs.push(InputRecord {
-            seq,
-            payload: InputPayload::PolicyUpdate { tick_boundary, update },
-        });
+        self.push(seq, InputPayload::PolicyUpdate { tick_boundary, update });
     }
 
     pub fn append_swap_weapon(&mut self, tick_boundary: u64, seq: u64) {
-        self.inputs
-            .push(InputRecord { seq, payload: InputPayload::SwapActiveWeapon { tick_boundary } });
+        self.push(seq, InputPayload::SwapActiveWeapon { tick_boundary });
+    }
+
+    pub fn append_brace(&mut self, tick_boundary: u64, seq: u64) {
+        self.push(seq, InputPayload::Brace { tick_boundary });
+    }
+
+    pub fn append_wait(&mut self, tick_boundary: u64, ticks: u32, seq: u64) {
+        self.push(seq, InputPayload::Wait { tick_boundary, ticks });
+    }
+
+    pub fn append_set_marker(
+        &mut self,
+        tick_boundary: u64,
+        pos: Pos,
+        marker: Option<MarkerKind>,
+        seq: u64,
+    ) {
+        self.push(seq, InputPayload::SetMarker { tick_boundary, pos, marker });
+    }
+
+    pub fn append_manual_step(&mut self, tick_boundary: u64, direction: Direction, seq: u64) {
+        self.push(seq, InputPayload::ManualStep { tick_boundary, direction });
     }
 }
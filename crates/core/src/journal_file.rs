@@ -1,13 +1,16 @@
 //! File-backed JSONL journal with SHA-256 hash chain for crash recovery.
 //!
 //! The file format is line-delimited JSON (`.jsonl`):
-//! - Line 1: header with `format_version`, `build_id`, `content_hash`, `seed`.
+//! - Line 1: header with `format_version`, `build_id`, `content_hash`, `seed`,
+//!   the run's `difficulty` (absent in older files, meaning `Normal`), and whether the
+//!   adaptive `director` was on (absent meaning off).
 //! - Lines 2+: one record per accepted simulation input, each carrying a
 //!   SHA-256 hash chain (`prev_sha256_hex`, `sha256_hex`) for corruption detection.
+//!   Records may carry optional `pacing` metadata, which is left out of the chain.
 //!
 //! Writing flushes each record immediately so the file survives crashes.
 //! Loading validates every line's JSON shape and SHA-256 chain, stopping
-//! at the first invalid or incomplete line.
+//! at the first invalid or incomplete line; recovery keeps the valid prefix instead.
 
 use std::fmt;
 use std::fs::{self, File, OpenOptions};
@@ -17,7 +20,14 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use crate::journal::{InputJournal, InputPayload, InputRecord};
+use crate::journal::{InputJournal, InputPacing, InputPayload, InputRecord};
+use crate::types::Difficulty;
+
+mod recovery;
+
+pub use recovery::{
+    RecoveredJournal, damaged_tail_path, recover_journal_from_file, truncate_to_valid_prefix,
+};
 
 // ---------------------------------------------------------------------------
 // File format structs
@@ -30,6 +40,10 @@ struct FileHeader {
     build_id: String,
     content_hash: u64,
     seed: u64,
+    #[serde(default)]
+    difficulty: Difficulty,
+    #[serde(default)]
+    director: bool,
 }
 
 /// Fields used to compute the canonical SHA-256 for a record.
@@ -47,6 +61,8 @@ struct FileRecord {
     seq: u64,
     tick_boundary: u64,
     payload: InputPayload,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pacing: Option<InputPacing>,
     prev_sha256_hex: String,
     sha256_hex: String,
 }
@@ -79,8 +95,31 @@ pub struct JournalWriter {
 }
 
 impl JournalWriter {
-    /// Create a new journal file, writing the header line immediately.
+    /// Create a new journal file for a `Normal` difficulty run.
     pub fn create(path: &Path, seed: u64, build_id: &str, content_hash: u64) -> io::Result<Self> {
+        Self::create_with_difficulty(path, seed, Difficulty::Normal, build_id, content_hash)
+    }
+
+    /// Create a new journal file for a run without the adaptive director.
+    pub fn create_with_difficulty(
+        path: &Path,
+        seed: u64,
+        difficulty: Difficulty,
+        build_id: &str,
+        content_hash: u64,
+    ) -> io::Result<Self> {
+        Self::create_for_run(path, seed, difficulty, false, build_id, content_hash)
+    }
+
+    /// Create a new journal file, writing the header line immediately.
+    pub fn create_for_run(
+        path: &Path,
+        seed: u64,
+        difficulty: Difficulty,
+        director: bool,
+        build_id: &str,
+        content_hash: u64,
+    ) -> io::Result<Self> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -88,8 +127,14 @@ impl JournalWriter {
         let file = File::create(path)?;
         let mut writer = BufWriter::new(file);
 
-        let header =
-            FileHeader { format_version: 1, build_id: build_id.to_string(), content_hash, seed };
+        let header = FileHeader {
+            format_version: 1,
+            build_id: build_id.to_string(),
+            content_hash,
+            seed,
+            difficulty,
+            director,
+        };
         let header_json = serde_json::to_string(&header).map_err(io::Error::other)?;
         writeln!(writer, "{header_json}")?;
         writer.flush()?;
@@ -107,6 +152,16 @@ impl JournalWriter {
 
     /// Append one accepted input and flush immediately.
     pub fn append(&mut self, tick_boundary: u64, payload: &InputPayload) -> io::Result<()> {
+        self.append_with_pacing(tick_boundary, payload, None)
+    }
+
+    /// Append an input together with the wall-clock pacing it was made at.
+    pub fn append_with_pacing(
+        &mut self,
+        tick_boundary: u64,
+        payload: &InputPayload,
+        pacing: Option<InputPacing>,
+    ) -> io::Result<()> {
         let body = RecordBody { seq: self.next_seq, tick_boundary, payload };
         let body_json = serde_json::to_string(&body).map_err(io::Error::other)?;
         let sha256_hex = compute_record_sha256(&body_json, &self.last_sha256_hex);
@@ -115,6 +170,7 @@ impl JournalWriter {
             seq: self.next_seq,
             tick_boundary,
             payload: payload.clone(),
+            pacing,
             prev_sha256_hex: self.last_sha256_hex.clone(),
             sha256_hex: sha256_hex.clone(),
         };
@@ -190,84 +246,123 @@ impl fmt::Display for JournalLoadError {
 /// an error describing the problem.
 pub fn load_journal_from_file(path: &Path) -> Result<LoadedJournal, JournalLoadError> {
     let content = fs::read_to_string(path).map_err(JournalLoadError::Io)?;
-    if content.is_empty() {
-        return Err(JournalLoadError::EmptyFile);
+    let parsed = parse_journal(&content)?;
+    match parsed.stopped_by {
+        Some(error) => Err(error),
+        None => Ok(parsed.loaded),
     }
-    let has_trailing_newline = content.ends_with('\n');
-    let lines: Vec<&str> = content.lines().collect();
-    if lines.is_empty() {
+}
+
+/// A journal parsed up to its first bad line, with the byte length of the valid prefix.
+pub(crate) struct ParsedJournal {
+    pub(crate) loaded: LoadedJournal,
+    pub(crate) valid_prefix_bytes: u64,
+    pub(crate) stopped_by: Option<JournalLoadError>,
+}
+
+/// Parse the header and every record up to the first invalid, incomplete, or
+/// hash-broken line. Only problems with the file as a whole (I/O, empty file,
+/// bad header) are returned as errors.
+pub(crate) fn parse_journal(content: &str) -> Result<ParsedJournal, JournalLoadError> {
+    if content.is_empty() {
         return Err(JournalLoadError::EmptyFile);
     }
-    if !has_trailing_newline {
-        return Err(JournalLoadError::IncompleteLine { line: lines.len() });
-    }
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
 
     // --- header (line 1) ---
-    let header_line = lines[0];
+    let Some(header_line) = lines[0].strip_suffix('\n') else {
+        return Err(JournalLoadError::IncompleteLine { line: 1 });
+    };
 
     let header: FileHeader = serde_json::from_str(header_line)
         .map_err(|e| JournalLoadError::InvalidHeader { line: 1, message: e.to_string() })?;
 
-    let mut journal = InputJournal {
+    let journal = InputJournal {
         format_version: header.format_version,
         build_id: header.build_id,
         content_hash: header.content_hash,
         seed: header.seed,
+        difficulty: header.difficulty,
+        director: header.director,
         inputs: Vec::new(),
     };
-
-    let mut prev_sha256_hex = INITIAL_HASH.to_string();
-    let mut next_seq: u64 = 0;
+    let mut parsed = ParsedJournal {
+        loaded: LoadedJournal { journal, last_sha256_hex: INITIAL_HASH.to_string(), next_seq: 0 },
+        valid_prefix_bytes: lines[0].len() as u64,
+        stopped_by: None,
+    };
 
     // --- records (lines 2+) ---
-    for (line_index, line) in lines.iter().skip(1).enumerate() {
-        let line_number = line_index + 2; // 1-indexed; header is line 1
-
-        if line.is_empty() {
-            return Err(JournalLoadError::InvalidRecord {
-                line: line_number,
-                message: "empty line".to_string(),
-            });
+    for (line_index, raw_line) in lines.iter().enumerate().skip(1) {
+        let line_number = line_index + 1; // 1-indexed; header is line 1
+        match parse_record(raw_line, line_number, &parsed.loaded) {
+            Ok(record) => {
+                parsed.loaded.journal.inputs.push(InputRecord {
+                    seq: record.seq,
+                    payload: record.payload,
+                    pacing: record.pacing,
+                });
+                parsed.loaded.last_sha256_hex = record.sha256_hex;
+                parsed.loaded.next_seq += 1;
+                parsed.valid_prefix_bytes += raw_line.len() as u64;
+            }
+            Err(error) => {
+                parsed.stopped_by = Some(error);
+                break;
+            }
         }
+    }
 
-        let record: FileRecord = serde_json::from_str(line).map_err(|e| {
-            JournalLoadError::InvalidRecord { line: line_number, message: e.to_string() }
-        })?;
-
-        if record.seq != next_seq {
-            return Err(JournalLoadError::InvalidRecord {
-                line: line_number,
-                message: format!("expected seq {next_seq}, found {}", record.seq),
-            });
-        }
+    Ok(parsed)
+}
 
-        // Verify prev_sha256 link
-        if record.prev_sha256_hex != prev_sha256_hex {
-            return Err(JournalLoadError::HashChainBroken { line: line_number });
-        }
+/// Validate one record line against the chain loaded so far.
+fn parse_record(
+    raw_line: &str,
+    line_number: usize,
+    loaded: &LoadedJournal,
+) -> Result<FileRecord, JournalLoadError> {
+    let Some(line) = raw_line.strip_suffix('\n') else {
+        return Err(JournalLoadError::IncompleteLine { line: line_number });
+    };
+    if line.is_empty() {
+        return Err(JournalLoadError::InvalidRecord {
+            line: line_number,
+            message: "empty line".to_string(),
+        });
+    }
 
-        // Recompute canonical hash and verify
-        let body = RecordBody {
-            seq: record.seq,
-            tick_boundary: record.tick_boundary,
-            payload: &record.payload,
-        };
-        let body_json = serde_json::to_string(&body).map_err(|e| {
-            JournalLoadError::InvalidRecord { line: line_number, message: e.to_string() }
-        })?;
-        let expected_sha256 = compute_record_sha256(&body_json, &prev_sha256_hex);
+    let record: FileRecord = serde_json::from_str(line).map_err(|e| {
+        JournalLoadError::InvalidRecord { line: line_number, message: e.to_string() }
+    })?;
 
-        if record.sha256_hex != expected_sha256 {
-            return Err(JournalLoadError::HashChainBroken { line: line_number });
-        }
+    if record.seq != loaded.next_seq {
+        return Err(JournalLoadError::InvalidRecord {
+            line: line_number,
+            message: format!("expected seq {}, found {}", loaded.next_seq, record.seq),
+        });
+    }
 
-        journal.inputs.push(InputRecord { seq: record.seq, payload: record.payload });
+    // Verify prev_sha256 link
+    if record.prev_sha256_hex != loaded.last_sha256_hex {
+        return Err(JournalLoadError::HashChainBroken { line: line_number });
+    }
 
-        prev_sha256_hex = record.sha256_hex;
-        next_seq += 1;
+    // Recompute canonical hash and verify
+    let body = RecordBody {
+        seq: record.seq,
+        tick_boundary: record.tick_boundary,
+        payload: &record.payload,
+    };
+    let body_json = serde_json::to_string(&body).map_err(|e| JournalLoadError::InvalidRecord {
+        line: line_number,
+        message: e.to_string(),
+    })?;
+    if record.sha256_hex != compute_record_sha256(&body_json, &loaded.last_sha256_hex) {
+        return Err(JournalLoadError::HashChainBroken { line: line_number });
     }
 
-    Ok(LoadedJournal { journal, last_sha256_hex: prev_sha256_hex, next_seq })
+    Ok(record)
 }
 
 // ---------------------------------------------------------------------------
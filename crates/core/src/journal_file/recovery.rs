@@ -0,0 +1,120 @@
+//! Best-effort loading of a damaged journal that keeps the longest valid prefix of records.
+//! Used after crashes or interrupted writes, where failing the whole load would lose the run.
+
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{JournalLoadError, LoadedJournal, parse_journal};
+
+/// A journal salvaged up to its first bad record.
+#[derive(Debug)]
+pub struct RecoveredJournal {
+    pub loaded: LoadedJournal,
+    /// Number of records kept from the valid prefix.
+    pub salvaged_records: usize,
+    /// Byte length of the valid prefix, including the header line.
+    pub valid_prefix_bytes: u64,
+    /// Why the rest of the file was dropped, or `None` if every record was valid.
+    pub discarded_tail: Option<JournalLoadError>,
+}
+
+/// Load a journal, dropping everything from the first invalid, incomplete, or
+/// hash-broken record onward. I/O errors, empty files, and bad headers still fail.
+pub fn recover_journal_from_file(path: &Path) -> Result<RecoveredJournal, JournalLoadError> {
+    let content = fs::read_to_string(path).map_err(JournalLoadError::Io)?;
+    let parsed = parse_journal(&content)?;
+    Ok(RecoveredJournal {
+        salvaged_records: parsed.loaded.journal.inputs.len(),
+        loaded: parsed.loaded,
+        valid_prefix_bytes: parsed.valid_prefix_bytes,
+        discarded_tail: parsed.stopped_by,
+    })
+}
+
+/// Where `truncate_to_valid_prefix` keeps the bytes it cuts: the journal path plus `.damaged`.
+pub fn damaged_tail_path(path: &Path) -> PathBuf {
+    let mut sidecar = path.as_os_str().to_owned();
+    sidecar.push(".damaged");
+    PathBuf::from(sidecar)
+}
+
+/// Cut the discarded tail off the file so resumed appends chain onto the salvaged prefix.
+/// The tail is copied to `damaged_tail_path` first, so the evidence outlives the cut.
+pub fn truncate_to_valid_prefix(path: &Path, recovered: &RecoveredJournal) -> io::Result<()> {
+    let bytes = fs::read(path)?;
+    let cut = usize::try_from(recovered.valid_prefix_bytes).unwrap_or(usize::MAX);
+    fs::write(damaged_tail_path(path), bytes.get(cut..).unwrap_or_default())?;
+    OpenOptions::new().write(true).open(path)?.set_len(recovered.valid_prefix_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::journal::InputPayload;
+    use crate::journal_file::{JournalWriter, load_journal_from_file};
+    use crate::types::{Choice, ChoicePromptId};
+
+    fn write_two_records(path: &Path) {
+        let mut writer = JournalWriter::create(path, 8, "dev", 0).unwrap();
+        for id in 1..=2 {
+            let payload =
+                InputPayload::Choice { prompt_id: ChoicePromptId(id), choice: Choice::Fight };
+            writer.append(id, &payload).unwrap();
+        }
+    }
+
+    #[test]
+    fn interrupted_write_keeps_the_valid_prefix() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("interrupted.jsonl");
+        write_two_records(&path);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"seq\":2,\"tick").unwrap();
+
+        let recovered = recover_journal_from_file(&path).expect("prefix should be salvaged");
+        assert_eq!(recovered.salvaged_records, 2);
+        assert!(matches!(
+            recovered.discarded_tail,
+            Some(JournalLoadError::IncompleteLine { line: 4 })
+        ));
+
+        truncate_to_valid_prefix(&path, &recovered).unwrap();
+        let damaged = fs::read_to_string(damaged_tail_path(&path)).unwrap();
+        assert_eq!(damaged, "{\"seq\":2,\"tick");
+        let mut writer = JournalWriter::resume(
+            &path,
+            recovered.loaded.last_sha256_hex.clone(),
+            recovered.loaded.next_seq,
+        )
+        .unwrap();
+        writer.append(3, &InputPayload::SwapActiveWeapon { tick_boundary: 3 }).unwrap();
+        assert_eq!(load_journal_from_file(&path).unwrap().journal.inputs.len(), 3);
+    }
+
+    #[test]
+    fn corrupted_middle_record_drops_it_and_everything_after() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("corrupted.jsonl");
+        write_two_records(&path);
+        let content = fs::read_to_string(&path).unwrap();
+        let mut lines: Vec<&str> = content.lines().collect();
+        let tampered = lines[1].replace("Fight", "Avoid");
+        lines[1] = &tampered;
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let recovered = recover_journal_from_file(&path).unwrap();
+        assert_eq!(recovered.salvaged_records, 0);
+        assert!(matches!(
+            recovered.discarded_tail,
+            Some(JournalLoadError::HashChainBroken { line: 2 })
+        ));
+
+        fs::write(&path, "").unwrap();
+        assert!(matches!(recover_journal_from_file(&path), Err(JournalLoadError::EmptyFile)));
+    }
+}
@@ -233,3 +233,26 @@ fn invalid_header_returns_error() {
         "expected invalid header error, got: {result:?}"
     );
 }
+
+#[test]
+fn pacing_metadata_round_trips_outside_the_hash_chain() {
+    let dir = tempdir().unwrap();
+    let payload = InputPayload::Choice { prompt_id: ChoicePromptId(1), choice: Choice::Fight };
+    let pacing = InputPacing { wall_ms_since_prev: 1_250, paused_ms: 900 };
+
+    let plain_path = make_test_path(dir.path(), "plain.jsonl");
+    let mut writer = JournalWriter::create(&plain_path, 3, "dev", 0).unwrap();
+    writer.append(4, &payload).unwrap();
+    drop(writer);
+
+    let paced_path = make_test_path(dir.path(), "paced.jsonl");
+    let mut writer = JournalWriter::create(&paced_path, 3, "dev", 0).unwrap();
+    writer.append_with_pacing(4, &payload, Some(pacing)).unwrap();
+    drop(writer);
+
+    let plain = load_journal_from_file(&plain_path).unwrap();
+    let paced = load_journal_from_file(&paced_path).unwrap();
+    assert_eq!(plain.journal.inputs[0].pacing, None);
+    assert_eq!(paced.journal.inputs[0].pacing, Some(pacing));
+    assert_eq!(paced.last_sha256_hex, plain.last_sha256_hex);
+}
@@ -1,18 +1,29 @@
 //! Public crate surface for the core simulation, map generation, and replay systems.
 
 pub mod game;
+pub mod geometry;
 pub mod journal;
 pub mod journal_file;
+pub mod lockstep;
 pub mod mapgen;
+mod ordering;
 pub mod replay;
+pub mod scenario;
 pub mod state;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 pub mod types;
 
-pub use game::Game;
-pub use journal::{InputJournal, InputPayload, InputRecord};
-pub use journal_file::{JournalLoadError, JournalWriter, LoadedJournal, load_journal_from_file};
+pub use game::{Game, GameCheckpoint, GameSaveError, SAVE_FORMAT_VERSION};
+pub use geometry::{Facing, TargetTemplate};
+pub use journal::{InputJournal, InputPacing, InputPayload, InputRecord};
+pub use journal_file::{
+    JournalLoadError, JournalWriter, LoadedJournal, RecoveredJournal, damaged_tail_path,
+    load_journal_from_file, recover_journal_from_file, truncate_to_valid_prefix,
+};
 pub use mapgen::{
-    BranchProfile, GeneratedFloor, MAX_FLOORS, MapGenerator, STARTING_FLOOR_INDEX, generate_floor,
+    BranchPreview, BranchProfile, DensityTier, FloorPreview, GeneratedFloor, HazardTier,
+    LayoutStyle, MAX_FLOORS, MapGenerator, STARTING_FLOOR_INDEX, generate_floor,
 };
 pub use replay::*;
 pub mod content;
@@ -0,0 +1,215 @@
+//! Deterministic lockstep sessions for two peers sharing one run seed.
+//! Each round both peers swap their journaled inputs and a snapshot hash, verify the
+//! hash, apply the inputs, and advance to the next boundary with the same budget.
+//! The session is transport-agnostic; see `transport` for in-memory and TCP carriers.
+
+use std::fmt;
+use std::io;
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::ContentPack;
+use crate::game::Game;
+use crate::journal::InputPayload;
+use crate::types::{AdvanceResult, AdvanceStopReason, Difficulty, GameMode};
+
+mod transport;
+
+pub use transport::{InMemoryTransport, LockstepTransport, TcpTransport};
+
+/// How the two peers' inputs relate to the games they simulate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LockstepMode {
+    /// Both peers drive one shared run; every input is applied on both sides.
+    SharedControl,
+    /// Each peer plays its own run and mirrors the other's as a ghost.
+    GhostRace,
+}
+
+/// Fixed seat used to order inputs deterministically; the host's inputs apply first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PeerRole {
+    Host,
+    Guest,
+}
+
+/// One peer's contribution to a round.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RoundMessage {
+    pub round: u64,
+    pub inputs: Vec<InputPayload>,
+    /// Hash of the sender's own run at the start of the round, before any inputs.
+    pub snapshot_hash: u64,
+}
+
+#[derive(Debug)]
+pub enum LockstepError {
+    Io(io::Error),
+    Disconnected,
+    InvalidMessage(String),
+    RoundMismatch {
+        expected: u64,
+        received: u64,
+    },
+    /// The peer's run no longer matches our copy of it.
+    Desync {
+        round: u64,
+        local_hash: u64,
+        remote_hash: u64,
+    },
+}
+
+impl fmt::Display for LockstepError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "lockstep I/O error: {e}"),
+            Self::Disconnected => write!(f, "lockstep peer disconnected"),
+            Self::InvalidMessage(message) => write!(f, "invalid lockstep message: {message}"),
+            Self::RoundMismatch { expected, received } => {
+                write!(f, "expected lockstep round {expected}, peer sent {received}")
+            }
+            Self::Desync { round, local_hash, remote_hash } => write!(
+                f,
+                "desync in round {round}: local 0x{local_hash:016x}, peer 0x{remote_hash:016x}"
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for LockstepError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// What a completed round did to the local run.
+#[derive(Clone, Debug)]
+pub struct RoundReport {
+    pub round: u64,
+    /// Inputs the simulation refused, such as a second answer to the same prompt.
+    pub rejected_inputs: usize,
+    pub advance: AdvanceResult,
+}
+
+pub struct LockstepSession<T: LockstepTransport> {
+    mode: LockstepMode,
+    role: PeerRole,
+    game: Game,
+    /// The peer's run as replayed from its inputs; only used in `GhostRace`.
+    ghost: Option<Game>,
+    transport: T,
+    steps_per_round: u32,
+    round: u64,
+}
+
+impl<T: LockstepTransport> LockstepSession<T> {
+    pub fn new(
+        mode: LockstepMode,
+        role: PeerRole,
+        seed: u64,
+        difficulty: Difficulty,
+        transport: T,
+        steps_per_round: u32,
+    ) -> Self {
+        let content = ContentPack::default();
+        let game = Game::new_with_difficulty(seed, &content, GameMode::Ironman, difficulty);
+        let ghost = (mode == LockstepMode::GhostRace).then(|| game.clone());
+        Self {
+            mode,
+            role,
+            game,
+            ghost,
+            transport,
+            steps_per_round: steps_per_round.max(1),
+            round: 0,
+        }
+    }
+
+    pub fn mode(&self) -> LockstepMode {
+        self.mode
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// The mirrored peer run in `GhostRace` mode.
+    pub fn ghost(&self) -> Option<&Game> {
+        self.ghost.as_ref()
+    }
+
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    /// Swap inputs with the peer, verify hashes, apply both sides' inputs, then advance.
+    pub fn run_round(
+        &mut self,
+        local_inputs: Vec<InputPayload>,
+    ) -> Result<RoundReport, LockstepError> {
+        let outgoing = RoundMessage {
+            round: self.round,
+            inputs: local_inputs,
+            snapshot_hash: self.game.snapshot_hash(),
+        };
+        self.transport.send(&outgoing)?;
+        let incoming = self.transport.recv()?;
+        if incoming.round != self.round {
+            return Err(LockstepError::RoundMismatch {
+                expected: self.round,
+                received: incoming.round,
+            });
+        }
+        let mirrored = self.ghost.as_ref().unwrap_or(&self.game);
+        if mirrored.snapshot_hash() != incoming.snapshot_hash {
+            return Err(LockstepError::Desync {
+                round: self.round,
+                local_hash: mirrored.snapshot_hash(),
+                remote_hash: incoming.snapshot_hash,
+            });
+        }
+
+        let rejected_inputs = match (&mut self.ghost, self.role) {
+            (Some(ghost), _) => {
+                apply_inputs(ghost, &incoming.inputs)
+                    + apply_inputs(&mut self.game, &outgoing.inputs)
+            }
+            (None, PeerRole::Host) => {
+                apply_inputs(&mut self.game, &outgoing.inputs)
+                    + apply_inputs(&mut self.game, &incoming.inputs)
+            }
+            (None, PeerRole::Guest) => {
+                apply_inputs(&mut self.game, &incoming.inputs)
+                    + apply_inputs(&mut self.game, &outgoing.inputs)
+            }
+        };
+        if let Some(ghost) = &mut self.ghost {
+            advance_to_boundary(ghost, self.steps_per_round);
+        }
+        let advance = advance_to_boundary(&mut self.game, self.steps_per_round);
+        self.round += 1;
+        Ok(RoundReport { round: self.round - 1, rejected_inputs, advance })
+    }
+}
+
+/// Apply journaled inputs in order, returning how many the game rejected.
+fn apply_inputs(game: &mut Game, inputs: &[InputPayload]) -> usize {
+    inputs.iter().filter(|input| game.apply_input(input).is_err()).count()
+}
+
+/// Advance up to `steps`, then settle on a pause boundary so the next round's
+/// policy inputs are accepted; prompts and run completion already stop there.
+fn advance_to_boundary(game: &mut Game, steps: u32) -> AdvanceResult {
+    let result = game.advance(steps);
+    if !matches!(result.stop_reason, AdvanceStopReason::BudgetExhausted) {
+        return result;
+    }
+    game.request_pause();
+    let mut paused = game.advance(1);
+    paused.simulated_ticks += result.simulated_ticks;
+    paused.breakdown += result.breakdown;
+    paused
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,179 @@
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use super::*;
+use crate::types::{Choice, Interrupt, PolicyUpdate, Stance};
+
+const ROUNDS: usize = 24;
+
+/// Answer the pending prompt, if any, the way a scripted player would.
+fn scripted_inputs(game: &Game, prefer_keep: bool) -> Vec<InputPayload> {
+    let Some(batch) = game.prompt_batch() else {
+        return Vec::new();
+    };
+    let (prompt_id, choice) = match batch.active {
+        Interrupt::LootFound { prompt_id, .. } => {
+            (prompt_id, if prefer_keep { Choice::KeepLoot } else { Choice::DiscardLoot })
+        }
+        Interrupt::EnemyEncounter { prompt_id, .. } => (prompt_id, Choice::Fight),
+        Interrupt::DoorBlocked { prompt_id, .. } => (prompt_id, Choice::OpenDoor),
+        Interrupt::StairsLocked { prompt_id, .. } => (prompt_id, Choice::SeekObjective),
+        Interrupt::PactAltar { prompt_id, .. } => (prompt_id, Choice::KeepPact),
+        Interrupt::PerkDraft { prompt_id, .. } => (prompt_id, Choice::DraftPerk(0)),
+        Interrupt::FloorTransition { prompt_id, requires_branch_god_choice, .. } => {
+            let choice = if requires_branch_god_choice {
+                Choice::DescendBranchAVeil
+            } else {
+                Choice::Descend
+            };
+            (prompt_id, choice)
+        }
+    };
+    vec![InputPayload::Choice { prompt_id, choice }]
+}
+
+fn play<T: LockstepTransport>(
+    session: &mut LockstepSession<T>,
+    answers_prompts: bool,
+) -> Result<(), LockstepError> {
+    let policy = PolicyUpdate::Stance(Stance::Aggressive);
+    let first = vec![InputPayload::PolicyUpdate { tick_boundary: 0, update: policy }];
+    session.run_round(if answers_prompts { first } else { Vec::new() })?;
+    for _ in 1..ROUNDS {
+        let inputs = if answers_prompts || session.mode() == LockstepMode::GhostRace {
+            scripted_inputs(session.game(), answers_prompts)
+        } else {
+            Vec::new()
+        };
+        session.run_round(inputs)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn shared_control_keeps_both_peers_on_one_run() {
+    let (host_link, guest_link) = InMemoryTransport::pair();
+    let (host_hash, guest_hash) = thread::scope(|scope| {
+        let host = scope.spawn(|| {
+            let mut session = LockstepSession::new(
+                LockstepMode::SharedControl,
+                PeerRole::Host,
+                2681,
+                Difficulty::Normal,
+                host_link,
+                20,
+            );
+            play(&mut session, true).expect("host rounds should stay in sync");
+            assert_eq!(session.game().state().policy.stance, Stance::Aggressive);
+            session.game().snapshot_hash()
+        });
+        let mut guest = LockstepSession::new(
+            LockstepMode::SharedControl,
+            PeerRole::Guest,
+            2681,
+            Difficulty::Normal,
+            guest_link,
+            20,
+        );
+        play(&mut guest, false).expect("guest rounds should stay in sync");
+        (host.join().unwrap(), guest.game().snapshot_hash())
+    });
+    assert_eq!(host_hash, guest_hash);
+}
+
+#[test]
+fn ghost_race_mirrors_the_peer_run() {
+    let (host_link, guest_link) = InMemoryTransport::pair();
+    thread::scope(|scope| {
+        let host = scope.spawn(|| {
+            let mut session = LockstepSession::new(
+                LockstepMode::GhostRace,
+                PeerRole::Host,
+                77,
+                Difficulty::Normal,
+                host_link,
+                25,
+            );
+            play(&mut session, true).expect("host ghost race should stay in sync");
+            session
+        });
+        let mut guest = LockstepSession::new(
+            LockstepMode::GhostRace,
+            PeerRole::Guest,
+            77,
+            Difficulty::Normal,
+            guest_link,
+            25,
+        );
+        play(&mut guest, false).expect("guest ghost race should stay in sync");
+        let host = host.join().unwrap();
+        let host_ghost = host.ghost().expect("ghost race keeps a ghost");
+        let guest_ghost = guest.ghost().expect("ghost race keeps a ghost");
+        assert_eq!(host_ghost.snapshot_hash(), guest.game().snapshot_hash());
+        assert_eq!(guest_ghost.snapshot_hash(), host.game().snapshot_hash());
+    });
+}
+
+#[test]
+fn mismatched_seeds_report_a_desync() {
+    let (host_link, guest_link) = InMemoryTransport::pair();
+    let mut host = LockstepSession::new(
+        LockstepMode::SharedControl,
+        PeerRole::Host,
+        1,
+        Difficulty::Normal,
+        host_link,
+        10,
+    );
+    let mut guest = LockstepSession::new(
+        LockstepMode::SharedControl,
+        PeerRole::Guest,
+        2,
+        Difficulty::Normal,
+        guest_link,
+        10,
+    );
+    // Channels are buffered, so a single thread can send both messages before receiving.
+    guest
+        .transport
+        .send(&RoundMessage {
+            round: 0,
+            inputs: Vec::new(),
+            snapshot_hash: guest.game.snapshot_hash(),
+        })
+        .unwrap();
+    assert!(matches!(host.run_round(Vec::new()), Err(LockstepError::Desync { round: 0, .. })));
+}
+
+#[test]
+fn tcp_transport_carries_rounds_between_peers() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("loopback listener");
+    let addr = listener.local_addr().unwrap();
+    let hashes = thread::scope(|scope| {
+        let guest = scope.spawn(move || {
+            let link = TcpTransport::new(TcpStream::connect(addr).unwrap()).unwrap();
+            let mut session = LockstepSession::new(
+                LockstepMode::SharedControl,
+                PeerRole::Guest,
+                9,
+                Difficulty::Brutal,
+                link,
+                20,
+            );
+            play(&mut session, false).expect("guest tcp rounds should stay in sync");
+            session.game().snapshot_hash()
+        });
+        let (stream, _) = listener.accept().unwrap();
+        let mut host = LockstepSession::new(
+            LockstepMode::SharedControl,
+            PeerRole::Host,
+            9,
+            Difficulty::Brutal,
+            TcpTransport::new(stream).unwrap(),
+            20,
+        );
+        play(&mut host, true).expect("host tcp rounds should stay in sync");
+        (host.game().snapshot_hash(), guest.join().unwrap())
+    });
+    assert_eq!(hashes.0, hashes.1);
+}
@@ -0,0 +1,73 @@
+//! Transports that carry lockstep round messages between two peers.
+//! Messages are newline-delimited JSON on streams, and moved as values in memory.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+use super::{LockstepError, RoundMessage};
+
+/// Bidirectional, ordered delivery of round messages to the other peer.
+pub trait LockstepTransport {
+    fn send(&mut self, message: &RoundMessage) -> Result<(), LockstepError>;
+    /// Block until the peer's next message arrives.
+    fn recv(&mut self) -> Result<RoundMessage, LockstepError>;
+}
+
+/// Channel-backed transport for two sessions in one process (tests, local ghost races).
+pub struct InMemoryTransport {
+    outgoing: Sender<RoundMessage>,
+    incoming: Receiver<RoundMessage>,
+}
+
+impl InMemoryTransport {
+    /// Two connected endpoints; what one sends, the other receives.
+    pub fn pair() -> (Self, Self) {
+        let (a_tx, b_rx) = channel();
+        let (b_tx, a_rx) = channel();
+        (Self { outgoing: a_tx, incoming: a_rx }, Self { outgoing: b_tx, incoming: b_rx })
+    }
+}
+
+impl LockstepTransport for InMemoryTransport {
+    fn send(&mut self, message: &RoundMessage) -> Result<(), LockstepError> {
+        self.outgoing.send(message.clone()).map_err(|_| LockstepError::Disconnected)
+    }
+
+    fn recv(&mut self) -> Result<RoundMessage, LockstepError> {
+        self.incoming.recv().map_err(|_| LockstepError::Disconnected)
+    }
+}
+
+/// TCP transport sending one JSON round message per line.
+pub struct TcpTransport {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl TcpTransport {
+    pub fn new(stream: TcpStream) -> Result<Self, LockstepError> {
+        stream.set_nodelay(true)?;
+        let writer = stream.try_clone()?;
+        Ok(Self { reader: BufReader::new(stream), writer })
+    }
+}
+
+impl LockstepTransport for TcpTransport {
+    fn send(&mut self, message: &RoundMessage) -> Result<(), LockstepError> {
+        let mut line = serde_json::to_string(message)
+            .map_err(|e| LockstepError::InvalidMessage(e.to_string()))?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn recv(&mut self) -> Result<RoundMessage, LockstepError> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(LockstepError::Disconnected);
+        }
+        serde_json::from_str(&line).map_err(|e| LockstepError::InvalidMessage(e.to_string()))
+    }
+}
@@ -1,19 +1,32 @@
 //! Procedural map generation domain split into coherent submodules.
 
+use crate::types::ActorKind;
+
 pub mod model;
 pub mod progression;
 
 mod generator;
 mod grid;
 mod layout;
+mod preview;
 mod seed;
 mod spawns;
 mod vaults;
 
 pub use generator::MapGenerator;
 pub use model::{EnemySpawn, GeneratedFloor, ItemSpawn};
+pub use preview::{BranchPreview, DensityTier, FloorPreview, HazardTier, LayoutStyle};
 pub use progression::{BranchProfile, MAX_FLOORS, STARTING_FLOOR_INDEX};
 
+/// Version of the rooms-and-corridors generator; bump it whenever a change alters the
+/// layout an existing floor seed produces.
+pub const MAPGEN_VERSION: u32 = 1;
+
+/// An enemy kind from `floor_index`'s spawn table, for enemies that arrive after generation.
+pub fn roll_enemy_kind(floor_index: u8, seed: u64, roll_index: usize) -> ActorKind {
+    spawns::pick_enemy_kind(floor_index, seed, roll_index)
+}
+
 pub fn generate_floor(
     run_seed: u64,
     floor_index: u8,
@@ -37,4 +50,32 @@ mod tests {
 
         assert_eq!(from_helper, from_generator);
     }
+
+    #[test]
+    fn enemy_count_percent_scales_spawn_budget() {
+        let generator = |percent| {
+            MapGenerator::new(77, BranchProfile::BranchA).with_enemy_count_percent(percent)
+        };
+        let relaxed = generator(50).generate(3).enemy_spawns.len();
+        let normal = generator(100).generate(3).enemy_spawns.len();
+        let brutal = generator(200).generate(3).enemy_spawns.len();
+
+        assert!(relaxed < normal, "expected fewer enemies: {relaxed} vs {normal}");
+        assert!(brutal > normal, "expected more enemies: {brutal} vs {normal}");
+    }
+
+    #[test]
+    fn item_count_percent_scales_loot_budget() {
+        let items = |percent| {
+            MapGenerator::new(77, BranchProfile::BranchA)
+                .with_item_count_percent(percent)
+                .generate(5)
+                .item_spawns
+                .len()
+        };
+        let (lean, normal, generous) = (items(50), items(100), items(150));
+
+        assert!(lean < normal, "expected fewer items: {lean} vs {normal}");
+        assert!(generous > normal, "expected more items: {generous} vs {normal}");
+    }
 }
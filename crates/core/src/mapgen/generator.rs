@@ -2,6 +2,7 @@
 
 use super::model::GeneratedFloor;
 use super::progression::BranchProfile;
+use crate::content::BossRoster;
 
 mod hazards;
 mod pipeline;
@@ -11,21 +12,44 @@ pub struct MapGenerator {
     branch_profile: BranchProfile,
     width: usize,
     height: usize,
+    enemy_count_percent: u32,
+    item_count_percent: u32,
+    bosses: BossRoster,
 }
 
 impl MapGenerator {
     pub fn new(run_seed: u64, branch_profile: BranchProfile) -> Self {
-        Self { run_seed, branch_profile, width: 20, height: 15 }
+        Self {
+            run_seed,
+            branch_profile,
+            width: 20,
+            height: 15,
+            enemy_count_percent: 100,
+            item_count_percent: 100,
+            bosses: BossRoster::default(),
+        }
+    }
+
+    /// Scale the per-floor enemy budget, e.g. for difficulty presets.
+    pub fn with_enemy_count_percent(mut self, percent: u32) -> Self {
+        self.enemy_count_percent = percent;
+        self
+    }
+
+    /// Scale the per-floor item placement attempts, e.g. for the adaptive director.
+    pub fn with_item_count_percent(mut self, percent: u32) -> Self {
+        self.item_count_percent = percent;
+        self
+    }
+
+    /// Place bosses from `bosses` instead of the default roster.
+    pub fn with_boss_roster(mut self, bosses: BossRoster) -> Self {
+        self.bosses = bosses;
+        self
     }
 
     pub fn generate(&self, floor_index: u8) -> GeneratedFloor {
-        pipeline::generate_floor(
-            self.run_seed,
-            self.branch_profile,
-            self.width,
-            self.height,
-            floor_index,
-        )
+        pipeline::generate_floor(self, floor_index)
     }
 }
 
@@ -1,23 +1,19 @@
 //! Floor-construction pipeline that composes mapgen subsystems.
 
-use crate::types::{Pos, TileKind};
+use crate::types::{FloorProvenance, MapgenAlgorithm, Pos, TileKind};
 
+use super::super::MAPGEN_VERSION;
 use super::super::grid::{farthest_walkable_tile_from_entry, nearest_walkable_floor_tile};
 use super::super::layout::{build_room_layout, carve_room, carve_room_corridors};
 use super::super::model::GeneratedFloor;
-use super::super::progression::BranchProfile;
 use super::super::seed::derive_floor_seed;
 use super::super::spawns::{SpawnContext, generate_enemy_spawns, generate_item_spawns};
 use super::super::vaults::{VaultApplicationContext, apply_vault_stamps};
+use super::MapGenerator;
 use super::hazards::{BranchHazardContext, apply_branch_hazards};
 
-pub(super) fn generate_floor(
-    run_seed: u64,
-    branch_profile: BranchProfile,
-    width: usize,
-    height: usize,
-    floor_index: u8,
-) -> GeneratedFloor {
+pub(super) fn generate_floor(generator: &MapGenerator, floor_index: u8) -> GeneratedFloor {
+    let MapGenerator { run_seed, branch_profile, width, height, .. } = *generator;
     let mut tiles = vec![TileKind::Wall; width * height];
     let floor_seed = derive_floor_seed(run_seed, floor_index, branch_profile);
     let layout = build_room_layout(floor_seed, width, height);
@@ -35,6 +31,9 @@ pub(super) fn generate_floor(
         floor_index,
         branch_profile,
         floor_seed,
+        enemy_count_percent: generator.enemy_count_percent,
+        item_count_percent: generator.item_count_percent,
+        boss: generator.bosses.boss_for(branch_profile, floor_index),
         width,
         height,
         tiles: &tiles,
@@ -46,7 +45,7 @@ pub(super) fn generate_floor(
     let mut hazards = vec![false; width * height];
 
     tiles[tile_index(down_stairs_tile, width)] = TileKind::DownStairs;
-    apply_vault_stamps(&mut VaultApplicationContext {
+    let vaults = apply_vault_stamps(&mut VaultApplicationContext {
         floor_seed,
         width,
         height,
@@ -71,16 +70,24 @@ pub(super) fn generate_floor(
         down_stairs_tile,
     });
     hazards[tile_index(down_stairs_tile, width)] = false;
+    let rooms = layout.room_ids(&tiles, width);
 
     GeneratedFloor {
         width,
         height,
         tiles,
         hazards,
+        rooms,
         entry_tile,
         down_stairs_tile,
         enemy_spawns,
         item_spawns,
+        vaults,
+        provenance: FloorProvenance {
+            algorithm: MapgenAlgorithm::RoomsAndCorridors,
+            version: MAPGEN_VERSION,
+            floor_seed,
+        },
     }
 }
 
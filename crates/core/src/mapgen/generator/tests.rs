@@ -175,6 +175,39 @@ fn generated_floor_has_single_connected_walkable_region() {
     assert!(all_walkable_tiles_connected(&generated));
 }
 
+#[test]
+fn room_labels_cover_room_interiors_and_skip_corridors() {
+    let seed = 2708;
+    let generated = MapGenerator::new(seed, BranchProfile::BranchA).generate(2);
+    let layout = build_room_layout(
+        derive_floor_seed(seed, 2, BranchProfile::BranchA),
+        generated.width,
+        generated.height,
+    );
+    let entry_index =
+        generated.entry_tile.y as usize * generated.width + generated.entry_tile.x as usize;
+    assert!(generated.rooms[entry_index].is_some(), "the entry sits in the first room");
+
+    let mut labelled = BTreeSet::new();
+    for (idx, room) in generated.rooms.iter().enumerate() {
+        match room {
+            Some(room) => {
+                assert_ne!(generated.tiles[idx], TileKind::Wall);
+                labelled.insert(*room);
+            }
+            None => continue,
+        }
+    }
+    assert_eq!(labelled.len(), layout.rooms.len(), "every room keeps at least one open tile");
+    let corridor_tiles = generated
+        .tiles
+        .iter()
+        .zip(&generated.rooms)
+        .filter(|(tile, room)| **tile == TileKind::Floor && room.is_none())
+        .count();
+    assert!(corridor_tiles > 0, "corridors between rooms stay unlabelled");
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(1024))]
     #[test]
@@ -1,6 +1,7 @@
 //! Room placement and corridor carving logic for base map topology.
 
-use crate::types::{Pos, TileKind};
+use crate::ordering;
+use crate::types::{Pos, RoomId, TileKind};
 
 use super::grid::manhattan;
 use super::seed::{mix_seed_stream, random_usize};
@@ -60,6 +61,28 @@ pub(super) struct RoomLayout {
     pub(super) down_stairs_tile: Pos,
 }
 
+impl RoomLayout {
+    pub(super) fn room(&self, room: RoomId) -> RoomRect {
+        self.rooms[usize::from(room.0)]
+    }
+
+    /// Label every open tile inside a room rect with that room's id, in layout order.
+    pub(super) fn room_ids(&self, tiles: &[TileKind], width: usize) -> Vec<Option<RoomId>> {
+        let mut room_ids = vec![None; tiles.len()];
+        for (index, room) in self.rooms.iter().enumerate() {
+            for y in room.y..=room.bottom() {
+                for x in room.x..=room.right() {
+                    let idx = y * width + x;
+                    if tiles[idx] != TileKind::Wall && room_ids[idx].is_none() {
+                        room_ids[idx] = Some(RoomId(index as u16));
+                    }
+                }
+            }
+        }
+        room_ids
+    }
+}
+
 pub(super) fn build_room_layout(floor_seed: u64, width: usize, height: usize) -> RoomLayout {
     let minimum_room_width = 4usize;
     let maximum_room_width = 7usize;
@@ -99,10 +122,7 @@ pub(super) fn build_room_layout(floor_seed: u64, width: usize, height: usize) ->
     }
 
     add_fallback_rooms(width, height, &mut rooms);
-    rooms.sort_by_key(|room| {
-        let center = room.center();
-        (center.y, center.x, room.height, room.width)
-    });
+    rooms.sort_by_key(|room| ordering::room_key(room.center(), room.width, room.height));
 
     let entry_tile = rooms.first().map(|room| room.center()).unwrap_or(Pos { y: 1, x: 1 });
 
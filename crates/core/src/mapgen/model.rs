@@ -1,17 +1,20 @@
 //! Public data models for generated maps, enemy spawns, and item spawns.
 
-use crate::types::{ActorKind, ItemKind, Pos, TileKind};
+use crate::types::{ActorKind, FloorProvenance, ItemKind, Pos, RoomId, TileKind, VaultStamp};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EnemySpawn {
     pub kind: ActorKind,
     pub pos: Pos,
+    /// Set on the branch boss the roster places.
+    pub boss: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ItemSpawn {
     pub kind: ItemKind,
     pub pos: Pos,
+    pub cursed: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -20,10 +23,16 @@ pub struct GeneratedFloor {
     pub height: usize,
     pub tiles: Vec<TileKind>,
     pub hazards: Vec<bool>,
+    /// Room label per tile; left out of `canonical_bytes` since it follows from the layout.
+    pub rooms: Vec<Option<RoomId>>,
     pub entry_tile: Pos,
     pub down_stairs_tile: Pos,
     pub enemy_spawns: Vec<EnemySpawn>,
     pub item_spawns: Vec<ItemSpawn>,
+    /// Rooms stamped with a vault template; like `rooms`, left out of `canonical_bytes`.
+    pub vaults: Vec<VaultStamp>,
+    /// Generator, version and floor seed that built this floor.
+    pub provenance: FloorProvenance,
 }
 
 impl GeneratedFloor {
@@ -59,6 +68,8 @@ impl GeneratedFloor {
                 ActorKind::Gargoyle => 6,
                 ActorKind::ShadowStalker => 7,
                 ActorKind::AbyssalWarden => 8,
+                ActorKind::HollowKing => 9,
+                ActorKind::BoneArcher => 10,
             });
             bytes.extend(spawn.pos.y.to_le_bytes());
             bytes.extend(spawn.pos.x.to_le_bytes());
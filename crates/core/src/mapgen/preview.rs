@@ -0,0 +1,127 @@
+//! Spoiler-safe floor previews: coarse tiers summarizing a generated floor.
+//! A preview never carries positions, counts or tiles, so it cannot leak the layout.
+
+use std::collections::BTreeSet;
+
+use super::generator::MapGenerator;
+use super::model::GeneratedFloor;
+use super::progression::BranchProfile;
+use crate::types::TileKind;
+use serde::{Deserialize, Serialize};
+
+/// Enemies per 100 walkable tiles below which a floor reads as sparse.
+const SPARSE_ENEMIES_PER_100_TILES: usize = 4;
+/// Enemies per 100 walkable tiles at which a floor reads as dense.
+const DENSE_ENEMIES_PER_100_TILES: usize = 6;
+/// Hazard tiles at which a floor reads as heavily hazardous.
+const HEAVY_HAZARD_TILES: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DensityTier {
+    Sparse,
+    Moderate,
+    Dense,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HazardTier {
+    Clear,
+    Scattered,
+    Heavy,
+}
+
+/// Overall shape of a floor, judged by how many rooms it has.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutStyle {
+    /// Three or fewer large rooms.
+    Halls,
+    /// Four rooms.
+    Chambers,
+    /// Five or more smaller rooms.
+    Warren,
+}
+
+/// What a player may know about a floor before choosing to go there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloorPreview {
+    pub enemy_density: DensityTier,
+    pub hazards: HazardTier,
+    pub layout: LayoutStyle,
+}
+
+/// The preview of the next floor down one branch, as offered on the pact prompt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchPreview {
+    pub branch: BranchProfile,
+    pub floor: FloorPreview,
+}
+
+impl MapGenerator {
+    /// Preview `floor_index` without handing out the generated floor itself.
+    pub fn preview(&self, floor_index: u8) -> FloorPreview {
+        FloorPreview::summarize(&self.generate(floor_index))
+    }
+}
+
+impl FloorPreview {
+    fn summarize(floor: &GeneratedFloor) -> Self {
+        let walkable = floor.tiles.iter().filter(|tile| **tile != TileKind::Wall).count().max(1);
+        let enemies_per_100_tiles = floor.enemy_spawns.len() * 100 / walkable;
+        let enemy_density = if enemies_per_100_tiles < SPARSE_ENEMIES_PER_100_TILES {
+            DensityTier::Sparse
+        } else if enemies_per_100_tiles < DENSE_ENEMIES_PER_100_TILES {
+            DensityTier::Moderate
+        } else {
+            DensityTier::Dense
+        };
+
+        let hazards = match floor.hazards.iter().filter(|hazard| **hazard).count() {
+            0 => HazardTier::Clear,
+            count if count < HEAVY_HAZARD_TILES => HazardTier::Scattered,
+            _ => HazardTier::Heavy,
+        };
+
+        let rooms: BTreeSet<_> = floor.rooms.iter().flatten().collect();
+        let layout = match rooms.len() {
+            0..=3 => LayoutStyle::Halls,
+            4 => LayoutStyle::Chambers,
+            _ => LayoutStyle::Warren,
+        };
+
+        Self { enemy_density, hazards, layout }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews_are_deterministic_and_tell_the_branches_apart() {
+        let preview = |seed, branch| MapGenerator::new(seed, branch).preview(2);
+        assert_eq!(preview(11, BranchProfile::BranchA), preview(11, BranchProfile::BranchA));
+
+        let heavy_b = (0..12_u64)
+            .filter(|seed| {
+                preview(seed * 7919, BranchProfile::BranchB).hazards == HazardTier::Heavy
+            })
+            .count();
+        let heavy_a = (0..12_u64)
+            .filter(|seed| {
+                preview(seed * 7919, BranchProfile::BranchA).hazards == HazardTier::Heavy
+            })
+            .count();
+        assert!(heavy_b > heavy_a, "branch B floors carry extra hazards: {heavy_b} vs {heavy_a}");
+    }
+
+    #[test]
+    fn enemy_budget_moves_the_density_tier() {
+        let density = |percent| {
+            MapGenerator::new(77, BranchProfile::BranchA)
+                .with_enemy_count_percent(percent)
+                .preview(3)
+                .enemy_density
+        };
+        assert!(density(50) < density(200));
+    }
+}
@@ -1,6 +1,8 @@
 //! Floor progression and branch-policy rules used by map generation.
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum BranchProfile {
     Uncommitted,
     BranchA,
@@ -29,10 +31,6 @@ pub(super) fn branch_hazard_count(floor_index: u8, branch_profile: BranchProfile
     }
 }
 
-pub(super) fn is_final_floor(floor_index: u8) -> bool {
-    floor_index == MAX_FLOORS
-}
-
 pub(super) fn branch_code(branch_profile: BranchProfile) -> u64 {
     match branch_profile {
         BranchProfile::Uncommitted => 0,
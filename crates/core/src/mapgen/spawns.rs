@@ -1,7 +1,8 @@
 //! Enemy and item spawn selection and placement rules for generated maps.
 
 use crate::{
-    content::keys,
+    content::{BossPlacement, BossRule, floor_spawn_table, keys},
+    ordering,
     types::{ActorKind, ItemKind, Pos, TileKind},
 };
 
@@ -12,11 +13,17 @@ use super::seed::random_usize;
 
 const ITEM_ROLL_WEAPON_THRESHOLD: usize = 22;
 const ITEM_ROLL_CONSUMABLE_THRESHOLD: usize = 72;
+/// Chance out of 100 that a spawned weapon or perk is cursed.
+const CURSED_ITEM_PERCENT: usize = 15;
 
 pub(super) struct SpawnContext<'a> {
     pub(super) floor_index: u8,
     pub(super) branch_profile: BranchProfile,
     pub(super) floor_seed: u64,
+    pub(super) enemy_count_percent: u32,
+    pub(super) item_count_percent: u32,
+    /// The roster boss spawning on this floor, if any.
+    pub(super) boss: Option<BossRule>,
     pub(super) width: usize,
     pub(super) height: usize,
     pub(super) tiles: &'a [TileKind],
@@ -25,13 +32,17 @@ pub(super) struct SpawnContext<'a> {
 }
 
 pub(super) fn generate_enemy_spawns(context: &SpawnContext<'_>) -> Vec<EnemySpawn> {
-    let enemy_count = progression::enemy_spawn_count(context.floor_index, context.branch_profile);
-    let target_total = enemy_count + usize::from(progression::is_final_floor(context.floor_index));
+    let base_count = progression::enemy_spawn_count(context.floor_index, context.branch_profile);
+    let enemy_count = ((base_count * context.enemy_count_percent as usize + 50) / 100).max(1);
+    let target_total = enemy_count + usize::from(context.boss.is_some());
 
     let mut enemy_spawns = Vec::with_capacity(target_total);
-    if progression::is_final_floor(context.floor_index) {
-        enemy_spawns
-            .push(EnemySpawn { kind: ActorKind::AbyssalWarden, pos: context.down_stairs_tile });
+    if let Some(rule) = context.boss {
+        enemy_spawns.push(EnemySpawn {
+            kind: rule.kind,
+            pos: boss_tile(context, rule),
+            boss: true,
+        });
     }
 
     for enemy_index in 0..enemy_count {
@@ -49,7 +60,7 @@ pub(super) fn generate_enemy_spawns(context: &SpawnContext<'_>) -> Vec<EnemySpaw
             && !enemy_spawns.iter().any(|spawn| spawn.pos == pos)
         {
             let kind = pick_enemy_kind(context.floor_index, context.floor_seed, enemy_index);
-            enemy_spawns.push(EnemySpawn { kind, pos });
+            enemy_spawns.push(EnemySpawn { kind, pos, boss: false });
         }
     }
 
@@ -72,21 +83,36 @@ pub(super) fn generate_enemy_spawns(context: &SpawnContext<'_>) -> Vec<EnemySpaw
                 }
                 let kind =
                     pick_enemy_kind(context.floor_index, context.floor_seed, enemy_spawns.len());
-                enemy_spawns.push(EnemySpawn { kind, pos });
+                enemy_spawns.push(EnemySpawn { kind, pos, boss: false });
             }
         }
     }
 
-    enemy_spawns.sort_by_key(|spawn| (spawn.pos.y, spawn.pos.x, spawn.kind));
+    enemy_spawns.sort_by_key(ordering::enemy_spawn_key);
     enemy_spawns
 }
 
+/// Where `rule` places its boss on this floor; a center too close to the entry falls back to
+/// the stairs so the boss never starts next to the player.
+fn boss_tile(context: &SpawnContext<'_>, rule: BossRule) -> Pos {
+    match rule.placement {
+        BossPlacement::GuardStairs => context.down_stairs_tile,
+        BossPlacement::HoldCenter => {
+            let center = Pos { y: (context.height / 2) as i32, x: (context.width / 2) as i32 };
+            let pos =
+                nearest_walkable_floor_tile(context.tiles, context.width, context.height, center);
+            if manhattan(pos, context.entry_tile) > 1 { pos } else { context.down_stairs_tile }
+        }
+    }
+}
+
 pub(super) fn generate_item_spawns(
     context: &SpawnContext<'_>,
     enemy_spawns: &[EnemySpawn],
 ) -> Vec<ItemSpawn> {
     let mut item_spawns = Vec::new();
-    let spawn_attempts = progression::item_spawn_attempts(context.floor_index);
+    let base_attempts = progression::item_spawn_attempts(context.floor_index);
+    let spawn_attempts = (base_attempts * context.item_count_percent as usize + 50) / 100;
 
     for item_index in 0..spawn_attempts {
         let item_y_shift = ((6 + item_index * 4) % 64) as u32;
@@ -107,14 +133,13 @@ pub(super) fn generate_item_spawns(
             && !item_spawns.iter().any(|spawn: &ItemSpawn| spawn.pos == item_pos)
             && !enemy_spawns.iter().any(|spawn| spawn.pos == item_pos)
         {
-            item_spawns.push(ItemSpawn {
-                kind: pick_item_kind(context.floor_seed, item_index),
-                pos: item_pos,
-            });
+            let kind = pick_item_kind(context.floor_seed, item_index);
+            let cursed = roll_cursed(kind, context.floor_seed, item_index);
+            item_spawns.push(ItemSpawn { kind, pos: item_pos, cursed });
         }
     }
 
-    item_spawns.sort_by_key(|spawn| (spawn.pos.y, spawn.pos.x, spawn.kind));
+    item_spawns.sort_by_key(ordering::item_spawn_key);
     item_spawns
 }
 
@@ -132,7 +157,7 @@ pub(super) fn pick_item_kind(floor_seed: u64, spawn_index: usize) -> ItemKind {
         }
     } else if roll < ITEM_ROLL_CONSUMABLE_THRESHOLD {
         let consumable_roll = random_usize(floor_seed, 6002 + spawn_index as u64, 0, 99);
-        match consumable_roll % 10 {
+        match consumable_roll % 12 {
             0 => ItemKind::Consumable(keys::CONSUMABLE_MINOR_HP_POT),
             1 => ItemKind::Consumable(keys::CONSUMABLE_MAJOR_HP_POT),
             2 => ItemKind::Consumable(keys::CONSUMABLE_TELEPORT_RUNE),
@@ -142,7 +167,9 @@ pub(super) fn pick_item_kind(floor_seed: u64, spawn_index: usize) -> ItemKind {
             6 => ItemKind::Consumable(keys::CONSUMABLE_SMOKE_BOMB),
             7 => ItemKind::Consumable(keys::CONSUMABLE_SHRAPNEL_BOMB),
             8 => ItemKind::Consumable(keys::CONSUMABLE_HASTE_POTION),
-            _ => ItemKind::Consumable(keys::CONSUMABLE_IRON_SKIN_POTION),
+            9 => ItemKind::Consumable(keys::CONSUMABLE_IRON_SKIN_POTION),
+            10 => ItemKind::Consumable(keys::CONSUMABLE_WHETSTONE),
+            _ => ItemKind::Consumable(keys::CONSUMABLE_ENCHANT_SCROLL),
         }
     } else {
         let perk_roll = random_usize(floor_seed, 6003 + spawn_index as u64, 0, 99);
@@ -161,63 +188,23 @@ pub(super) fn pick_item_kind(floor_seed: u64, spawn_index: usize) -> ItemKind {
     }
 }
 
-fn pick_enemy_kind(floor_index: u8, floor_seed: u64, spawn_index: usize) -> ActorKind {
-    let roll = random_usize(floor_seed, 5000 + spawn_index as u64, 0, 99);
-    match floor_index {
-        1 => {
-            if roll < 60 {
-                ActorKind::Goblin
-            } else if roll < 90 {
-                ActorKind::FeralHound
-            } else {
-                ActorKind::BloodAcolyte
-            }
-        }
-        2 => {
-            if roll < 20 {
-                ActorKind::FeralHound
-            } else if roll < 50 {
-                ActorKind::BloodAcolyte
-            } else if roll < 80 {
-                ActorKind::CorruptedGuard
-            } else {
-                ActorKind::Gargoyle
-            }
-        }
-        3 => {
-            if roll < 20 {
-                ActorKind::BloodAcolyte
-            } else if roll < 50 {
-                ActorKind::CorruptedGuard
-            } else if roll < 80 {
-                ActorKind::Gargoyle
-            } else {
-                ActorKind::LivingArmor
-            }
-        }
-        4 => {
-            if roll < 20 {
-                ActorKind::CorruptedGuard
-            } else if roll < 50 {
-                ActorKind::Gargoyle
-            } else if roll < 80 {
-                ActorKind::LivingArmor
-            } else {
-                ActorKind::ShadowStalker
-            }
-        }
-        _ => {
-            if roll < 20 {
-                ActorKind::Gargoyle
-            } else if roll < 40 {
-                ActorKind::LivingArmor
-            } else if roll < 70 {
-                ActorKind::ShadowStalker
-            } else {
-                ActorKind::AbyssalWarden
-            }
+/// Whether a weapon or perk spawn is cursed; consumables never are.
+pub(super) fn roll_cursed(kind: ItemKind, floor_seed: u64, spawn_index: usize) -> bool {
+    matches!(kind, ItemKind::Weapon(_) | ItemKind::Perk(_))
+        && random_usize(floor_seed, 6004 + spawn_index as u64, 0, 99) < CURSED_ITEM_PERCENT
+}
+
+pub(super) fn pick_enemy_kind(floor_index: u8, floor_seed: u64, spawn_index: usize) -> ActorKind {
+    let roll = random_usize(floor_seed, 5000 + spawn_index as u64, 0, 99) as u32;
+    let row = floor_spawn_table(floor_index);
+    let mut threshold = 0;
+    for &(kind, percent) in row {
+        threshold += percent;
+        if roll < threshold {
+            return kind;
         }
     }
+    row[row.len() - 1].0
 }
 
 #[cfg(test)]
@@ -261,6 +248,9 @@ mod tests {
             floor_index: 3,
             branch_profile: BranchProfile::BranchA,
             floor_seed: 9_876,
+            enemy_count_percent: 100,
+            item_count_percent: 100,
+            boss: None,
             width,
             height,
             tiles: &tiles,
@@ -1,25 +1,13 @@
 //! Vault template selection and post-layout map mutation logic.
 
-use crate::types::{Pos, TileKind};
+use crate::ordering;
+use crate::types::{Pos, RoomId, TileKind, VaultStamp, VaultTemplate};
 
 use super::grid::in_bounds;
 use super::layout::{RoomLayout, RoomRect};
 use super::model::{EnemySpawn, ItemSpawn};
 use super::seed::random_usize;
-use super::spawns::pick_item_kind;
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum VaultTemplate {
-    ShrineRoom,
-    GoblinCamp,
-    PillarRoom,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-struct VaultStamp {
-    template: VaultTemplate,
-    room_index: usize,
-}
+use super::spawns::{pick_item_kind, roll_cursed};
 
 pub(super) struct VaultApplicationContext<'a> {
     pub(super) floor_seed: u64,
@@ -34,7 +22,8 @@ pub(super) struct VaultApplicationContext<'a> {
     pub(super) item_spawns: &'a mut Vec<ItemSpawn>,
 }
 
-pub(super) fn apply_vault_stamps(context: &mut VaultApplicationContext<'_>) {
+/// Stamp vault templates onto the floor and return the stamps applied, in room order.
+pub(super) fn apply_vault_stamps(context: &mut VaultApplicationContext<'_>) -> Vec<VaultStamp> {
     let stamps = build_vault_stamps(
         context.floor_seed,
         &context.layout.rooms,
@@ -42,8 +31,8 @@ pub(super) fn apply_vault_stamps(context: &mut VaultApplicationContext<'_>) {
         context.down_stairs_tile,
     );
 
-    for stamp in stamps {
-        let room = context.layout.rooms[stamp.room_index];
+    for stamp in &stamps {
+        let room = context.layout.room(stamp.room);
         let center = room.center();
         let center_y = center.y as usize;
         let center_x = center.x as usize;
@@ -93,18 +82,19 @@ pub(super) fn apply_vault_stamps(context: &mut VaultApplicationContext<'_>) {
                     && center != context.down_stairs_tile
                     && !context.item_spawns.iter().any(|spawn| spawn.pos == center)
                 {
-                    context.item_spawns.push(ItemSpawn {
-                        kind: pick_item_kind(context.floor_seed, context.item_spawns.len()),
-                        pos: center,
-                    });
+                    let index = context.item_spawns.len();
+                    let kind = pick_item_kind(context.floor_seed, index);
+                    let cursed = roll_cursed(kind, context.floor_seed, index);
+                    context.item_spawns.push(ItemSpawn { kind, pos: center, cursed });
                 }
             }
         }
     }
 
-    context.enemy_spawns.sort_by_key(|spawn| (spawn.pos.y, spawn.pos.x, spawn.kind));
+    context.enemy_spawns.sort_by_key(ordering::enemy_spawn_key);
     context.enemy_spawns.dedup_by_key(|spawn| spawn.pos);
-    context.item_spawns.sort_by_key(|spawn| (spawn.pos.y, spawn.pos.x, spawn.kind));
+    context.item_spawns.sort_by_key(ordering::item_spawn_key);
+    stamps
 }
 
 fn build_vault_stamps(
@@ -139,10 +129,17 @@ fn build_vault_stamps(
             _ => VaultTemplate::PillarRoom,
         };
 
-        stamps.push(VaultStamp { template, room_index });
+        let rect = rooms[room_index];
+        stamps.push(VaultStamp {
+            template,
+            room: RoomId(room_index as u16),
+            origin: Pos { y: rect.y as i32, x: rect.x as i32 },
+            width: rect.width,
+            height: rect.height,
+        });
     }
 
-    stamps.sort_by_key(|stamp| stamp.room_index);
+    stamps.sort_by_key(|stamp| stamp.room);
     stamps
 }
 
@@ -150,6 +147,8 @@ fn build_vault_stamps(
 mod tests {
     use super::*;
     use crate::mapgen::layout::build_room_layout;
+    use crate::mapgen::seed::derive_floor_seed;
+    use crate::mapgen::{BranchProfile, MapGenerator};
 
     #[test]
     fn vault_stamps_are_deterministic_for_same_floor_seed() {
@@ -170,10 +169,33 @@ mod tests {
                 build_vault_stamps(seed, &layout.rooms, layout.entry_tile, layout.down_stairs_tile);
             assert!(!stamps.is_empty(), "expected vault stamps for seed {seed}");
             for stamp in stamps {
-                let room = layout.rooms[stamp.room_index];
+                let room = layout.room(stamp.room);
                 assert!(!room.contains(layout.entry_tile));
                 assert!(!room.contains(layout.down_stairs_tile));
             }
         }
     }
+
+    #[test]
+    fn vault_stamps_report_their_room_rect_and_skip_the_entry_room() {
+        let seed = 2738;
+        let generated = MapGenerator::new(seed, BranchProfile::BranchA).generate(2);
+        let layout = build_room_layout(
+            derive_floor_seed(seed, 2, BranchProfile::BranchA),
+            generated.width,
+            generated.height,
+        );
+        assert!(!generated.vaults.is_empty(), "every generated floor stamps at least one vault");
+        for vault in &generated.vaults {
+            let rect = layout.room(vault.room);
+            assert_eq!(vault.origin, Pos { y: rect.y as i32, x: rect.x as i32 });
+            assert_eq!((vault.width, vault.height), (rect.width, rect.height));
+            assert!(!vault.contains(generated.entry_tile));
+            let center = rect.center();
+            let center_index = center.y as usize * generated.width + center.x as usize;
+            if vault.template != VaultTemplate::PillarRoom {
+                assert_eq!(generated.rooms[center_index], Some(vault.room));
+            }
+        }
+    }
 }
@@ -0,0 +1,117 @@
+//! Named tie-break orders for every sorted query the simulation depends on.
+//! Each one is total over distinct entities, so no result hinges on insertion order.
+
+use std::cmp::Ordering;
+
+use crate::mapgen::{EnemySpawn, ItemSpawn};
+use crate::state::Actor;
+use crate::types::{ActorKind, ItemKind, Pos};
+
+/// Row-major tile order: top row first, then left to right.
+pub(crate) fn row_major(pos: Pos) -> (i32, i32) {
+    (pos.y, pos.x)
+}
+
+/// Enemy turn order: the soonest action first, then row-major tile, then kind.
+pub(crate) fn enemy_turn_key(actor: &Actor) -> (u64, (i32, i32), ActorKind) {
+    (actor.next_action_tick, row_major(actor.pos), actor.kind)
+}
+
+/// Order for sweeps over every enemy, such as awareness updates: row-major tile, then kind.
+pub(crate) fn actor_tile_key(actor: &Actor) -> ((i32, i32), ActorKind) {
+    (row_major(actor.pos), actor.kind)
+}
+
+/// Nearest to `origin` first when there is one, then row-major tile, kind, HP and next
+/// action; the trailing keys only matter for actors sharing a tile.
+pub(crate) fn nearest_actor(origin: Option<Pos>, a: &Actor, b: &Actor) -> Ordering {
+    let distance = |actor: &Actor| origin.map(|origin| manhattan(origin, actor.pos));
+    distance(a)
+        .cmp(&distance(b))
+        .then_with(|| actor_tile_key(a).cmp(&actor_tile_key(b)))
+        .then(a.hp.cmp(&b.hp))
+        .then(a.next_action_tick.cmp(&b.next_action_tick))
+}
+
+/// Generated enemy spawns: row-major tile, then kind.
+pub(crate) fn enemy_spawn_key(spawn: &EnemySpawn) -> (i32, i32, ActorKind) {
+    (spawn.pos.y, spawn.pos.x, spawn.kind)
+}
+
+/// Generated item spawns: row-major tile, then kind.
+pub(crate) fn item_spawn_key(spawn: &ItemSpawn) -> (i32, i32, ItemKind) {
+    (spawn.pos.y, spawn.pos.x, spawn.kind)
+}
+
+/// Generated rooms: row-major centre, then the shorter and narrower room first.
+pub(crate) fn room_key(center: Pos, width: usize, height: usize) -> (i32, i32, usize, usize) {
+    (center.y, center.x, height, width)
+}
+
+fn manhattan(a: Pos, b: Pos) -> u32 {
+    a.y.abs_diff(b.y) + a.x.abs_diff(b.x)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::state::Actor;
+    use crate::types::{EntityId, WeaponSlot};
+
+    const KINDS: [ActorKind; 3] = [ActorKind::Goblin, ActorKind::FeralHound, ActorKind::Gargoyle];
+
+    fn actor(y: i32, x: i32, kind: usize, next_action_tick: u64) -> Actor {
+        Actor {
+            id: EntityId::default(),
+            kind: KINDS[kind],
+            pos: Pos { y, x },
+            hp: 5,
+            max_hp: 5,
+            attack: 1,
+            defense: 0,
+            active_weapon_slot: WeaponSlot::Primary,
+            equipped_weapon: None,
+            reserve_weapon: None,
+            next_action_tick,
+            speed: 10,
+            awareness: 0,
+            unique: None,
+            home: Pos { y, x },
+        }
+    }
+
+    fn tiles(actors: &[Actor]) -> Vec<Pos> {
+        actors.iter().map(|actor| actor.pos).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn actor_orders_ignore_insertion_order(
+            raw in prop::collection::btree_map((0..6_i32, 0..6_i32), (0..3_usize, 0..4_u64), 1..12),
+            origin in prop::option::of((0..6_i32, 0..6_i32)),
+            rotation in any::<usize>(),
+        ) {
+            let actors: Vec<Actor> =
+                raw.iter().map(|(&(y, x), &(kind, next))| actor(y, x, kind, next)).collect();
+            let mut permuted = actors.clone();
+            permuted.rotate_left(rotation % actors.len());
+            permuted.reverse();
+            let origin = origin.map(|(y, x)| Pos { y, x });
+
+            let sort_both = |sort: &dyn Fn(&mut Vec<Actor>)| {
+                let (mut left, mut right) = (actors.clone(), permuted.clone());
+                sort(&mut left);
+                sort(&mut right);
+                (tiles(&left), tiles(&right))
+            };
+            let (left, right) = sort_both(&|list| list.sort_by_key(enemy_turn_key));
+            prop_assert_eq!(left, right);
+            let (left, right) = sort_both(&|list| list.sort_by_key(actor_tile_key));
+            prop_assert_eq!(left, right);
+            let (left, right) = sort_both(&|list| list.sort_by(|a, b| nearest_actor(origin, a, b)));
+            prop_assert_eq!(left, right);
+        }
+    }
+}
@@ -1,29 +1,49 @@
 use std::fmt;
 
 use crate::{
-    AdvanceStopReason, EngineFailureReason, GameMode, RunOutcome,
+    AdvanceStopReason, EngineFailureReason, GameError, GameMode, RunOutcome,
     content::ContentPack,
     game::Game,
     journal::{InputJournal, InputPayload},
 };
 
+mod report;
+mod stepper;
+
+pub use report::{ReproducibilityReport, replay_reproducibility_report};
+pub use stepper::ReplayStepper;
+
 #[derive(Debug, PartialEq)]
 pub enum ReplayError {
-    UnexpectedInterruption,
+    /// The game refused a journaled input it accepted in the original run.
+    InputRejected(GameError),
     MissingInput,
     SimulationStalled,
     EngineFailure(EngineFailureReason),
+    /// The full state hash at an answered prompt differs from the recorded one.
+    StateDivergence {
+        prompt: usize,
+        tick: u64,
+        expected: u64,
+        actual: u64,
+    },
 }
 
 impl fmt::Display for ReplayError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::UnexpectedInterruption => write!(f, "unexpected interruption during replay"),
+            Self::InputRejected(error) => {
+                write!(f, "journaled input rejected during replay: {error}")
+            }
             Self::MissingInput => write!(f, "journal is missing an expected input"),
             Self::SimulationStalled => write!(f, "simulation stalled during replay"),
             Self::EngineFailure(reason) => {
                 write!(f, "engine failure during replay: {reason:?}")
             }
+            Self::StateDivergence { prompt, tick, expected, actual } => write!(
+                f,
+                "state diverged at prompt {prompt} (tick {tick}): expected {expected:#018x}, got {actual:#018x}"
+            ),
         }
     }
 }
@@ -33,15 +53,83 @@ pub struct ReplayResult {
     pub final_outcome: RunOutcome,
     pub final_snapshot_hash: u64,
     pub final_tick: u64,
+    /// `Game::full_state_hash` just before each prompt was answered, in journal order.
+    pub prompt_state_hashes: Vec<u64>,
 }
 
 const MAX_REPLAY_BATCHES: u32 = 512;
 
+/// A fresh game set up the way the journal's run started: same seed, difficulty and director.
+fn new_game_for_journal(content: &ContentPack, journal: &InputJournal) -> Game {
+    let mut game =
+        Game::new_with_difficulty(journal.seed, content, GameMode::Ironman, journal.difficulty);
+    game.set_director_enabled(journal.director);
+    game
+}
+
 pub fn replay_to_end(
     content: &ContentPack,
     journal: &InputJournal,
 ) -> Result<ReplayResult, ReplayError> {
-    let mut game = Game::new(journal.seed, content, GameMode::Ironman);
+    replay_to_end_checked(content, journal, PromptHashes::default())
+}
+
+/// Like `replay_to_end`, but fails with `StateDivergence` at the first answered prompt whose
+/// full state hash differs from `expected`. Prompts past the end of `expected` are not checked.
+pub fn replay_to_end_verified(
+    content: &ContentPack,
+    journal: &InputJournal,
+    expected: &[u64],
+) -> Result<ReplayResult, ReplayError> {
+    replay_to_end_checked(content, journal, PromptHashes { expected, seen: Vec::new() })
+}
+
+fn replay_to_end_checked(
+    content: &ContentPack,
+    journal: &InputJournal,
+    mut hashes: PromptHashes<'_>,
+) -> Result<ReplayResult, ReplayError> {
+    let (game, final_outcome) = replay_game_to_end(content, journal, &mut hashes)?;
+    Ok(ReplayResult {
+        final_outcome,
+        final_snapshot_hash: game.snapshot_hash(),
+        final_tick: game.current_tick(),
+        prompt_state_hashes: hashes.seen,
+    })
+}
+
+/// Full state hashes taken at each answered prompt, checked against any recorded ones.
+#[derive(Default)]
+struct PromptHashes<'a> {
+    expected: &'a [u64],
+    seen: Vec<u64>,
+}
+
+impl PromptHashes<'_> {
+    fn observe(&mut self, game: &Game) -> Result<(), ReplayError> {
+        let (prompt, actual) = (self.seen.len(), game.full_state_hash());
+        self.seen.push(actual);
+        match self.expected.get(prompt) {
+            Some(&expected) if expected != actual => {
+                let tick = game.current_tick();
+                Err(ReplayError::StateDivergence { prompt, tick, expected, actual })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn apply_recorded(game: &mut Game, input: &InputPayload) -> Result<(), ReplayError> {
+    game.apply_input(input).map_err(ReplayError::InputRejected)
+}
+
+/// Re-simulate the journal's whole run, returning the finished game and how it ended.
+fn replay_game_to_end(
+    content: &ContentPack,
+    journal: &InputJournal,
+    hashes: &mut PromptHashes<'_>,
+) -> Result<(Game, RunOutcome), ReplayError> {
+    let mut game = new_game_for_journal(content, journal);
     let mut input_iter = journal.inputs.iter();
     let mut replay_batches = 0_u32;
 
@@ -59,66 +147,23 @@ pub fn replay_to_end(
         }
 
         match batch.stop_reason {
-            AdvanceStopReason::Finished(outcome) => {
-                return Ok(ReplayResult {
-                    final_outcome: outcome,
-                    final_snapshot_hash: game.snapshot_hash(),
-                    final_tick: game.current_tick(),
-                });
-            }
+            AdvanceStopReason::Finished(outcome) => return Ok((game, outcome)),
             AdvanceStopReason::Interrupted(_) => {
-                let mut record_peek = input_iter.clone();
-                if let Some(record) = record_peek.next() {
-                    match &record.payload {
-                        InputPayload::Choice { prompt_id, choice } => {
-                            if game.apply_choice(*prompt_id, choice.clone()).is_err() {
-                                return Err(ReplayError::UnexpectedInterruption);
-                            }
-                            input_iter.next(); // consume
-                        }
-                        InputPayload::PolicyUpdate { update, .. } => {
-                            if game.apply_policy_update(update.clone()).is_err() {
-                                return Err(ReplayError::UnexpectedInterruption);
-                            }
-                            input_iter.next(); // consume
-                            // Keep checking since multiple policy updates can happen at a boundary
-                            continue;
-                        }
-                        InputPayload::SwapActiveWeapon { .. } => {
-                            if game.apply_swap_weapon().is_err() {
-                                return Err(ReplayError::UnexpectedInterruption);
-                            }
-                            input_iter.next(); // consume
-                            continue;
-                        }
-                    }
-                } else {
-                    return Err(ReplayError::MissingInput);
+                let record = input_iter.next().ok_or(ReplayError::MissingInput)?;
+                if matches!(record.payload, InputPayload::Choice { .. }) {
+                    hashes.observe(&game)?;
                 }
+                apply_recorded(&mut game, &record.payload)?;
             }
             AdvanceStopReason::PausedAtBoundary { .. } => {
-                // Peek for policy updates
-                let mut record_peek = input_iter.clone();
-                if let Some(record) = record_peek.next() {
-                    match &record.payload {
-                        InputPayload::PolicyUpdate { update, .. } => {
-                            if game.apply_policy_update(update.clone()).is_err() {
-                                return Err(ReplayError::UnexpectedInterruption);
-                            }
-                            input_iter.next(); // consume
-                            continue; // Multiple updates might exist here
-                        }
-                        InputPayload::SwapActiveWeapon { .. } => {
-                            if game.apply_swap_weapon().is_err() {
-                                return Err(ReplayError::UnexpectedInterruption);
-                            }
-                            input_iter.next();
-                            continue;
-                        }
-                        _ => {}
-                    }
+                // Boundary inputs apply here; a choice waits for its prompt. Several inputs can
+                // share a boundary, and the next advance resumes the headless run either way.
+                if let Some(record) = input_iter.clone().next()
+                    && !matches!(record.payload, InputPayload::Choice { .. })
+                {
+                    apply_recorded(&mut game, &record.payload)?;
+                    input_iter.next();
                 }
-                // Automatically resume since this is headless continuous simulation
             }
             AdvanceStopReason::BudgetExhausted => {
                 // Just continue next loop iteration
@@ -142,7 +187,7 @@ pub fn replay_journal_inputs(
     content: &ContentPack,
     journal: &InputJournal,
 ) -> Result<Game, ReplayError> {
-    let mut game = Game::new(journal.seed, content, GameMode::Ironman);
+    let mut game = new_game_for_journal(content, journal);
     let inputs = &journal.inputs;
 
     if inputs.is_empty() {
@@ -168,45 +213,21 @@ pub fn replay_journal_inputs(
         match batch.stop_reason {
             AdvanceStopReason::Finished(_) => return Ok(game),
             AdvanceStopReason::Interrupted(_) => {
-                // Apply as many inputs as possible at this boundary.
-                while cursor < inputs.len() {
-                    let record = &inputs[cursor];
-                    match &record.payload {
-                        InputPayload::Choice { prompt_id, choice } => {
-                            game.apply_choice(*prompt_id, choice.clone())
-                                .map_err(|_| ReplayError::UnexpectedInterruption)?;
-                            cursor += 1;
-                            break; // Advance again after consuming a choice
-                        }
-                        InputPayload::PolicyUpdate { update, .. } => {
-                            game.apply_policy_update(update.clone())
-                                .map_err(|_| ReplayError::UnexpectedInterruption)?;
-                            cursor += 1;
-                        }
-                        InputPayload::SwapActiveWeapon { .. } => {
-                            game.apply_swap_weapon()
-                                .map_err(|_| ReplayError::UnexpectedInterruption)?;
-                            cursor += 1;
-                        }
+                // Apply as many inputs as possible at this boundary, advancing again after a choice.
+                while let Some(record) = inputs.get(cursor) {
+                    apply_recorded(&mut game, &record.payload)?;
+                    cursor += 1;
+                    if matches!(record.payload, InputPayload::Choice { .. }) {
+                        break;
                     }
                 }
             }
             AdvanceStopReason::PausedAtBoundary { .. } => {
-                while cursor < inputs.len() {
-                    let record = &inputs[cursor];
-                    match &record.payload {
-                        InputPayload::PolicyUpdate { update, .. } => {
-                            game.apply_policy_update(update.clone())
-                                .map_err(|_| ReplayError::UnexpectedInterruption)?;
-                            cursor += 1;
-                        }
-                        InputPayload::SwapActiveWeapon { .. } => {
-                            game.apply_swap_weapon()
-                                .map_err(|_| ReplayError::UnexpectedInterruption)?;
-                            cursor += 1;
-                        }
-                        _ => break,
-                    }
+                while let Some(record) = inputs.get(cursor)
+                    && !matches!(record.payload, InputPayload::Choice { .. })
+                {
+                    apply_recorded(&mut game, &record.payload)?;
+                    cursor += 1;
                 }
             }
             AdvanceStopReason::BudgetExhausted => {}
@@ -0,0 +1,104 @@
+//! Reproducibility reports: the identity of a finished run plus the hashes a faithful
+//! re-simulation of its journal must reproduce, from floor entry to the final snapshot.
+
+use serde::{Deserialize, Serialize};
+
+use super::{PromptHashes, ReplayError, replay_game_to_end};
+use crate::content::ContentPack;
+use crate::game::Game;
+use crate::journal::InputJournal;
+use crate::types::{Difficulty, FloorCheckpoint};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReproducibilityReport {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub director: bool,
+    pub content_hash: u64,
+    /// Journaled inputs the game accepted over the run.
+    pub input_count: u64,
+    pub final_tick: u64,
+    pub final_snapshot_hash: u64,
+    pub floor_checkpoints: Vec<FloorCheckpoint>,
+}
+
+impl ReproducibilityReport {
+    /// The first field on which `self` and `other` disagree, or `None` when they match.
+    pub fn first_difference(&self, other: &Self) -> Option<&'static str> {
+        [
+            ("seed", self.seed == other.seed),
+            ("difficulty", self.difficulty == other.difficulty),
+            ("director", self.director == other.director),
+            ("content hash", self.content_hash == other.content_hash),
+            ("input count", self.input_count == other.input_count),
+            ("floor checkpoints", self.floor_checkpoints == other.floor_checkpoints),
+            ("final tick", self.final_tick == other.final_tick),
+            ("final snapshot hash", self.final_snapshot_hash == other.final_snapshot_hash),
+        ]
+        .into_iter()
+        .find_map(|(field, same)| (!same).then_some(field))
+    }
+}
+
+impl Game {
+    /// The reproducibility report for this game as it stands; meant for finished runs.
+    pub fn reproducibility_report(&self, content: &ContentPack) -> ReproducibilityReport {
+        let stats = self.run_stats();
+        ReproducibilityReport {
+            seed: self.seed(),
+            difficulty: self.difficulty(),
+            director: self.director_enabled(),
+            content_hash: content.content_hash(),
+            input_count: stats.inputs_accepted,
+            final_tick: self.current_tick(),
+            final_snapshot_hash: self.snapshot_hash(),
+            floor_checkpoints: stats.floor_checkpoints.clone(),
+        }
+    }
+}
+
+/// Re-simulate the journal's run to its end and report it, for comparison with the
+/// report the original game produced.
+pub fn replay_reproducibility_report(
+    content: &ContentPack,
+    journal: &InputJournal,
+) -> Result<ReproducibilityReport, ReplayError> {
+    let (game, _) = replay_game_to_end(content, journal, &mut PromptHashes::default())?;
+    Ok(game.reproducibility_report(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AdvanceStopReason, GameMode};
+
+    #[test]
+    fn replayed_journal_reproduces_the_finished_games_report() {
+        let content = ContentPack::default();
+        let mut game = Game::new(2733, &content, GameMode::Ironman);
+        let mut journal = InputJournal::new(2733);
+        let mut seq = 0;
+        loop {
+            match game.advance(200).stop_reason {
+                AdvanceStopReason::Interrupted(interrupt) => {
+                    let choice = game.available_choices()[0].clone();
+                    journal.append_choice(interrupt.prompt_id(), choice.clone(), seq);
+                    game.apply_choice(interrupt.prompt_id(), choice).unwrap();
+                    seq += 1;
+                }
+                AdvanceStopReason::Finished(_) => break,
+                AdvanceStopReason::BudgetExhausted => {}
+                other => panic!("unexpected stop: {other:?}"),
+            }
+        }
+
+        let report = game.reproducibility_report(&content);
+        assert_eq!(report.input_count, seq);
+        assert!(!report.floor_checkpoints.is_empty(), "the run should descend at least once");
+        let replayed = replay_reproducibility_report(&content, &journal).unwrap();
+        assert_eq!(report.first_difference(&replayed), None);
+
+        let drifted = ReproducibilityReport { final_tick: report.final_tick + 1, ..replayed };
+        assert_eq!(report.first_difference(&drifted), Some("final tick"));
+    }
+}
@@ -0,0 +1,168 @@
+//! Incremental replay that follows a journal up to a requested tick.
+//! Used to drive a ghost of a previous run alongside a live game of the same seed.
+
+use super::*;
+use crate::journal::InputRecord;
+
+const MAX_STEP_BATCH: u64 = 100;
+const MAX_STEP_ATTEMPTS: u32 = 4096;
+
+/// A replayed game that can be stepped forward tick by tick, applying journal
+/// inputs at the ticks and prompts where they were originally accepted.
+pub struct ReplayStepper {
+    game: Game,
+    inputs: Vec<InputRecord>,
+    cursor: usize,
+    /// Set once the run finished or the journal ran out of inputs for a prompt.
+    exhausted: bool,
+}
+
+impl ReplayStepper {
+    pub fn new(content: &ContentPack, journal: &InputJournal) -> Self {
+        Self {
+            game: new_game_for_journal(content, journal),
+            inputs: journal.inputs.clone(),
+            cursor: 0,
+            exhausted: false,
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// True once stepping can make no further progress.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Advance the replay until its tick reaches `target_tick` or the journal ends.
+    pub fn step_to_tick(&mut self, target_tick: u64) -> Result<(), ReplayError> {
+        for _ in 0..MAX_STEP_ATTEMPTS {
+            if self.exhausted {
+                return Ok(());
+            }
+            self.apply_due_boundary_inputs()?;
+            let tick = self.game.current_tick();
+            if tick >= target_tick {
+                return Ok(());
+            }
+            let next_due = self.next_record().and_then(|record| record.payload.tick_boundary());
+            let stop_at =
+                next_due.filter(|due| *due > tick).map_or(target_tick, |due| due.min(target_tick));
+            let budget = (stop_at - tick).clamp(1, MAX_STEP_BATCH) as u32;
+
+            let batch = self.game.advance(budget);
+            match batch.stop_reason {
+                AdvanceStopReason::Finished(_) => self.exhausted = true,
+                AdvanceStopReason::EngineFailure(reason) => {
+                    self.exhausted = true;
+                    return Err(ReplayError::EngineFailure(reason));
+                }
+                AdvanceStopReason::Interrupted(_) => match self.next_record().cloned() {
+                    Some(record) => {
+                        self.game
+                            .apply_input(&record.payload)
+                            .map_err(ReplayError::InputRejected)?;
+                        self.cursor += 1;
+                    }
+                    None => self.exhausted = true,
+                },
+                AdvanceStopReason::BudgetExhausted if batch.simulated_ticks == 0 => {
+                    return Err(ReplayError::SimulationStalled);
+                }
+                AdvanceStopReason::BudgetExhausted | AdvanceStopReason::PausedAtBoundary { .. } => {
+                }
+            }
+        }
+        Err(ReplayError::SimulationStalled)
+    }
+
+    fn next_record(&self) -> Option<&InputRecord> {
+        self.inputs.get(self.cursor)
+    }
+
+    /// Apply pause-boundary inputs recorded at or before the current tick, pausing first
+    /// so the game accepts them exactly as it did in the original run.
+    fn apply_due_boundary_inputs(&mut self) -> Result<(), ReplayError> {
+        while let Some(record) = self.next_record().cloned() {
+            let Some(due) = record.payload.tick_boundary() else {
+                return Ok(());
+            };
+            if due > self.game.current_tick() {
+                return Ok(());
+            }
+            if self.game.prompt_batch().is_none() {
+                self.game.request_pause();
+                self.game.advance(1);
+            }
+            self.game.apply_input(&record.payload).map_err(ReplayError::InputRejected)?;
+            self.cursor += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Choice, Interrupt, PolicyUpdate, Stance};
+
+    /// Play `ticks` ticks in small batches, journaling a mid-run policy change and every choice.
+    fn record_run(seed: u64, ticks: u64) -> (Game, InputJournal) {
+        let mut game = Game::new(seed, &ContentPack::default(), GameMode::Ironman);
+        let mut journal = InputJournal::new(seed);
+        let mut seq = 0;
+        let mut policy_changed = false;
+        while game.current_tick() < ticks {
+            if game.current_tick() >= 30 && !policy_changed {
+                policy_changed = true;
+                game.request_pause();
+                game.advance(1);
+                let update = PolicyUpdate::Stance(Stance::Defensive);
+                game.apply_policy_update(update.clone()).unwrap();
+                journal.append_policy_update(game.current_tick(), update, seq);
+                seq += 1;
+            }
+            match game.advance(7).stop_reason {
+                AdvanceStopReason::Interrupted(interrupt) => {
+                    let (prompt_id, choice) = match interrupt {
+                        Interrupt::LootFound { prompt_id, .. } => (prompt_id, Choice::KeepLoot),
+                        Interrupt::EnemyEncounter { prompt_id, .. } => (prompt_id, Choice::Fight),
+                        Interrupt::DoorBlocked { prompt_id, .. } => (prompt_id, Choice::OpenDoor),
+                        Interrupt::StairsLocked { prompt_id, .. } => {
+                            (prompt_id, Choice::SeekObjective)
+                        }
+                        Interrupt::PactAltar { prompt_id, .. } => (prompt_id, Choice::KeepPact),
+                        Interrupt::PerkDraft { prompt_id, .. } => (prompt_id, Choice::DraftPerk(0)),
+                        Interrupt::FloorTransition { prompt_id, .. } => {
+                            (prompt_id, Choice::DescendBranchAVeil)
+                        }
+                    };
+                    game.apply_choice(prompt_id, choice.clone()).unwrap();
+                    journal.append_choice(prompt_id, choice, seq);
+                    seq += 1;
+                }
+                AdvanceStopReason::Finished(_) | AdvanceStopReason::EngineFailure(_) => break,
+                _ => {}
+            }
+        }
+        (game, journal)
+    }
+
+    #[test]
+    fn stepping_in_small_increments_tracks_the_recorded_run() {
+        let (original, journal) = record_run(4321, 120);
+        let mut stepper = ReplayStepper::new(&ContentPack::default(), &journal);
+
+        let mut target = 0;
+        while target < original.current_tick() {
+            target = (target + 5).min(original.current_tick());
+            stepper.step_to_tick(target).expect("ghost should keep up");
+            assert!(stepper.game().current_tick() >= target || stepper.is_exhausted());
+        }
+        assert_eq!(stepper.game().current_tick(), original.current_tick());
+        assert_eq!(stepper.game().state().policy.stance, Stance::Defensive);
+        assert_eq!(stepper.game().snapshot_hash(), original.snapshot_hash());
+    }
+}
@@ -55,6 +55,21 @@ fn test_replay_policy_equivalence() {
                     journal.append_choice(prompt_id, Choice::OpenDoor, seq);
                     seq += 1;
                 }
+                Interrupt::StairsLocked { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::SeekObjective).unwrap();
+                    journal.append_choice(prompt_id, Choice::SeekObjective, seq);
+                    seq += 1;
+                }
+                Interrupt::PactAltar { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::KeepPact).unwrap();
+                    journal.append_choice(prompt_id, Choice::KeepPact, seq);
+                    seq += 1;
+                }
+                Interrupt::PerkDraft { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::DraftPerk(0)).unwrap();
+                    journal.append_choice(prompt_id, Choice::DraftPerk(0), seq);
+                    seq += 1;
+                }
                 Interrupt::EnemyEncounter { prompt_id, .. } => {
                     game1.apply_choice(prompt_id, Choice::Fight).unwrap();
                     journal.append_choice(prompt_id, Choice::Fight, seq);
@@ -121,6 +136,21 @@ fn test_replay_policy_edit_resume_equivalence() {
                         journal.append_choice(prompt_id, Choice::OpenDoor, seq);
                         seq += 1;
                     }
+                    Interrupt::StairsLocked { prompt_id, .. } => {
+                        game1.apply_choice(prompt_id, Choice::SeekObjective).unwrap();
+                        journal.append_choice(prompt_id, Choice::SeekObjective, seq);
+                        seq += 1;
+                    }
+                    Interrupt::PactAltar { prompt_id, .. } => {
+                        game1.apply_choice(prompt_id, Choice::KeepPact).unwrap();
+                        journal.append_choice(prompt_id, Choice::KeepPact, seq);
+                        seq += 1;
+                    }
+                    Interrupt::PerkDraft { prompt_id, .. } => {
+                        game1.apply_choice(prompt_id, Choice::DraftPerk(0)).unwrap();
+                        journal.append_choice(prompt_id, Choice::DraftPerk(0), seq);
+                        seq += 1;
+                    }
                     Interrupt::EnemyEncounter { prompt_id, .. } => {
                         game1.apply_choice(prompt_id, Choice::Fight).unwrap();
                         journal.append_choice(prompt_id, Choice::Fight, seq);
@@ -177,6 +207,21 @@ fn test_replay_swap_weapon_equivalence() {
                     journal.append_choice(prompt_id, Choice::OpenDoor, seq);
                     seq += 1;
                 }
+                Interrupt::StairsLocked { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::SeekObjective).unwrap();
+                    journal.append_choice(prompt_id, Choice::SeekObjective, seq);
+                    seq += 1;
+                }
+                Interrupt::PactAltar { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::KeepPact).unwrap();
+                    journal.append_choice(prompt_id, Choice::KeepPact, seq);
+                    seq += 1;
+                }
+                Interrupt::PerkDraft { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::DraftPerk(0)).unwrap();
+                    journal.append_choice(prompt_id, Choice::DraftPerk(0), seq);
+                    seq += 1;
+                }
                 Interrupt::EnemyEncounter { prompt_id, .. } => {
                     game1.apply_choice(prompt_id, Choice::Fight).unwrap();
                     journal.append_choice(prompt_id, Choice::Fight, seq);
@@ -226,6 +271,21 @@ fn test_replay_forge_branch_equivalence() {
                     journal.append_choice(prompt_id, Choice::OpenDoor, seq);
                     seq += 1;
                 }
+                Interrupt::StairsLocked { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::SeekObjective).unwrap();
+                    journal.append_choice(prompt_id, Choice::SeekObjective, seq);
+                    seq += 1;
+                }
+                Interrupt::PactAltar { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::KeepPact).unwrap();
+                    journal.append_choice(prompt_id, Choice::KeepPact, seq);
+                    seq += 1;
+                }
+                Interrupt::PerkDraft { prompt_id, .. } => {
+                    game1.apply_choice(prompt_id, Choice::DraftPerk(0)).unwrap();
+                    journal.append_choice(prompt_id, Choice::DraftPerk(0), seq);
+                    seq += 1;
+                }
                 Interrupt::EnemyEncounter { prompt_id, .. } => {
                     game1.apply_choice(prompt_id, Choice::Fight).unwrap();
                     journal.append_choice(prompt_id, Choice::Fight, seq);
@@ -0,0 +1,197 @@
+//! Hand-authored scenarios: an ASCII map plus enemy, item, and policy placements.
+//! A scenario compiles to a `GeneratedFloor`, so it installs through the same path as mapgen.
+//! Scenario games start from the scenario rather than the seed, so seed-only replay cannot rebuild them.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::ContentPack;
+use crate::mapgen::{EnemySpawn, GeneratedFloor, ItemSpawn, STARTING_FLOOR_INDEX};
+use crate::state::Map;
+use crate::types::{ActorKind, FloorProvenance, ItemKind, MapgenAlgorithm, Policy, Pos, TileKind};
+
+mod arena;
+
+pub use arena::ARENA_MAX_ENEMIES;
+
+/// One scenario file, stored as JSON.
+///
+/// Map legend: `#` wall, `.` floor, `+` closed door, `>` down stairs, `~` hazardous floor,
+/// `@` the player's starting floor tile (exactly one).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub map: Vec<String>,
+    #[serde(default)]
+    pub enemies: Vec<ScenarioEnemy>,
+    #[serde(default)]
+    pub items: Vec<ScenarioItem>,
+    /// Item keys the player starts with, applied in order as if picked up.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equipment: Vec<String>,
+    /// Starting policy; the default policy when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<Policy>,
+    #[serde(default = "default_floor_index")]
+    pub floor_index: u8,
+    /// Start with the whole map discovered instead of only the player's field of view.
+    #[serde(default)]
+    pub reveal_map: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioEnemy {
+    pub kind: ActorKind,
+    pub pos: Pos,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioItem {
+    /// A weapon, consumable, or perk key from the content pack.
+    pub key: String,
+    pub pos: Pos,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScenarioError {
+    Parse(String),
+    /// A map character outside the legend.
+    UnknownTile {
+        pos: Pos,
+        glyph: char,
+    },
+    RaggedMap {
+        row: usize,
+    },
+    /// The map must contain exactly one `@`.
+    PlayerStart {
+        found: usize,
+    },
+    UnknownItem(String),
+    /// A placement on a wall or off the map, or an enemy on a tile already taken.
+    BadPlacement {
+        what: String,
+        pos: Pos,
+    },
+    /// An arena has room for at most `max` enemies.
+    TooManyEnemies {
+        max: usize,
+    },
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(message) => write!(f, "scenario is not valid JSON: {message}"),
+            Self::UnknownTile { pos, glyph } => {
+                write!(f, "unknown map glyph '{glyph}' at row {}, column {}", pos.y, pos.x)
+            }
+            Self::RaggedMap { row } => write!(f, "map row {row} has a different width"),
+            Self::PlayerStart { found } => {
+                write!(f, "map needs exactly one '@' player start, found {found}")
+            }
+            Self::UnknownItem(key) => write!(f, "unknown item key {key}"),
+            Self::BadPlacement { what, pos } => {
+                write!(f, "{what} cannot be placed at row {}, column {}", pos.y, pos.x)
+            }
+            Self::TooManyEnemies { max } => write!(f, "an arena holds at most {max} enemies"),
+        }
+    }
+}
+
+fn default_floor_index() -> u8 {
+    STARTING_FLOOR_INDEX
+}
+
+impl Scenario {
+    pub fn from_json(json: &str) -> Result<Self, ScenarioError> {
+        serde_json::from_str(json).map_err(|e| ScenarioError::Parse(e.to_string()))
+    }
+
+    /// Compile the scenario into the floor layout `Game::from_scenario` installs.
+    pub fn to_floor(&self, content: &ContentPack) -> Result<GeneratedFloor, ScenarioError> {
+        let width = self.map.first().map_or(0, |row| row.chars().count());
+        let mut map = Map::new(width, self.map.len());
+        let mut starts = Vec::new();
+        let mut down_stairs_tile = None;
+        for (y, row) in self.map.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(ScenarioError::RaggedMap { row: y });
+            }
+            for (x, glyph) in row.chars().enumerate() {
+                let pos = Pos { y: y as i32, x: x as i32 };
+                let tile = match glyph {
+                    '#' => TileKind::Wall,
+                    '.' | '~' => TileKind::Floor,
+                    '+' => TileKind::ClosedDoor,
+                    '>' => {
+                        down_stairs_tile.get_or_insert(pos);
+                        TileKind::DownStairs
+                    }
+                    '@' => {
+                        starts.push(pos);
+                        TileKind::Floor
+                    }
+                    _ => return Err(ScenarioError::UnknownTile { pos, glyph }),
+                };
+                map.set_tile(pos, tile);
+                map.set_hazard(pos, glyph == '~');
+            }
+        }
+        let [entry_tile] = starts[..] else {
+            return Err(ScenarioError::PlayerStart { found: starts.len() });
+        };
+
+        let mut enemy_tiles = vec![entry_tile];
+        let mut enemy_spawns = Vec::new();
+        for enemy in &self.enemies {
+            if map.tile_at(enemy.pos) == TileKind::Wall || enemy_tiles.contains(&enemy.pos) {
+                let what = format!("{:?}", enemy.kind);
+                return Err(ScenarioError::BadPlacement { what, pos: enemy.pos });
+            }
+            enemy_tiles.push(enemy.pos);
+            enemy_spawns.push(EnemySpawn { kind: enemy.kind, pos: enemy.pos, boss: false });
+        }
+        let mut item_spawns = Vec::new();
+        for item in &self.items {
+            let kind = resolve_item(content, &item.key)?;
+            if map.tile_at(item.pos) == TileKind::Wall {
+                return Err(ScenarioError::BadPlacement { what: item.key.clone(), pos: item.pos });
+            }
+            item_spawns.push(ItemSpawn { kind, pos: item.pos, cursed: false });
+        }
+
+        Ok(GeneratedFloor {
+            width: map.internal_width,
+            height: map.internal_height,
+            tiles: map.tiles,
+            hazards: map.hazards,
+            rooms: map.rooms,
+            entry_tile,
+            down_stairs_tile: down_stairs_tile.unwrap_or(entry_tile),
+            enemy_spawns,
+            item_spawns,
+            vaults: Vec::new(),
+            provenance: FloorProvenance::authored(MapgenAlgorithm::Scenario),
+        })
+    }
+
+    /// The starting equipment resolved against `content`, in application order.
+    pub fn starting_kit(&self, content: &ContentPack) -> Result<Vec<ItemKind>, ScenarioError> {
+        self.equipment.iter().map(|key| resolve_item(content, key)).collect()
+    }
+}
+
+fn resolve_item(content: &ContentPack, key: &str) -> Result<ItemKind, ScenarioError> {
+    let weapon = content.weapons.iter().find(|weapon| weapon.id == key);
+    let consumable = content.consumables.iter().find(|consumable| consumable.id == key);
+    let perk = content.perks.iter().find(|perk| perk.id == key);
+    weapon
+        .map(|weapon| ItemKind::Weapon(weapon.id))
+        .or(consumable.map(|consumable| ItemKind::Consumable(consumable.id)))
+        .or(perk.map(|perk| ItemKind::Perk(perk.id)))
+        .ok_or_else(|| ScenarioError::UnknownItem(key.to_string()))
+}
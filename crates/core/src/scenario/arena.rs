@@ -0,0 +1,50 @@
+//! Practice arenas: one open room with the player on the west wall, a chosen enemy
+//! composition across the floor, and the stairs behind them.
+//! The arena sits on the last floor, so taking its stairs ends the run.
+
+use super::{Scenario, ScenarioEnemy, ScenarioError};
+use crate::mapgen::MAX_FLOORS;
+use crate::types::{ActorKind, Pos};
+
+const ARENA_MAP: [&str; 9] = [
+    "#############",
+    "#...........#",
+    "#...........#",
+    "#...........#",
+    "#@.........>#",
+    "#...........#",
+    "#...........#",
+    "#...........#",
+    "#############",
+];
+/// Columns enemies fill, nearest the player first.
+const ENEMY_COLUMNS: [i32; 3] = [8, 9, 10];
+/// Rows within a column, filled from the middle outward.
+const ENEMY_ROWS: [i32; 7] = [4, 3, 5, 2, 6, 1, 7];
+/// Enemies an arena has tiles for.
+pub const ARENA_MAX_ENEMIES: usize = ENEMY_COLUMNS.len() * ENEMY_ROWS.len();
+
+impl Scenario {
+    /// A fully revealed arena holding `enemies`, with the player starting on `equipment`.
+    pub fn arena(enemies: &[ActorKind], equipment: Vec<String>) -> Result<Self, ScenarioError> {
+        if enemies.len() > ARENA_MAX_ENEMIES {
+            return Err(ScenarioError::TooManyEnemies { max: ARENA_MAX_ENEMIES });
+        }
+        let slots = ENEMY_COLUMNS
+            .into_iter()
+            .flat_map(|x| ENEMY_ROWS.into_iter().map(move |y| Pos { y, x }));
+        let enemies =
+            enemies.iter().zip(slots).map(|(&kind, pos)| ScenarioEnemy { kind, pos }).collect();
+        Ok(Self {
+            name: "Practice arena".to_string(),
+            description: String::new(),
+            map: ARENA_MAP.iter().map(|row| row.to_string()).collect(),
+            enemies,
+            items: Vec::new(),
+            equipment,
+            policy: None,
+            floor_index: MAX_FLOORS,
+            reveal_map: true,
+        })
+    }
+}
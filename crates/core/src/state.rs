@@ -1,36 +1,36 @@
 //! Runtime world state and storage structures used by the simulation engine.
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use slotmap::SlotMap;
 
 use crate::mapgen::BranchProfile;
 use crate::types::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
-pub struct Actor {
-    pub id: EntityId,
-    pub kind: ActorKind,
-    pub pos: Pos,
-    pub hp: i32,
-    pub max_hp: i32,
-    pub attack: i32,
-    pub defense: i32,
-    pub active_weapon_slot: WeaponSlot,
-    pub equipped_weapon: Option<&'static str>,
-    pub reserve_weapon: Option<&'static str>,
-    pub next_action_tick: u64,
-    pub speed: u32,
-}
+mod actor;
+mod director;
+mod inventory_slot;
+mod item_instance;
+pub(crate) mod keyed_pairs;
+mod lighting;
+
+pub use actor::{Actor, MAX_AWARENESS};
+pub use director::{DirectorState, MAX_DIRECTOR_PRESSURE};
+pub use inventory_slot::InventorySlot;
+pub use item_instance::ItemInstance;
+pub use lighting::FloorLight;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Item {
     pub id: ItemId,
     pub kind: ItemKind,
     pub pos: Pos,
+    /// Cursed weapons and perks bind to the player when kept; see `Game::cursed_item_count`.
+    pub cursed: bool,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Map {
     pub internal_width: usize,
     pub internal_height: usize,
@@ -38,39 +38,75 @@ pub struct Map {
     pub discovered: Vec<bool>,
     pub visible: Vec<bool>,
     pub hazards: Vec<bool>,
+    /// Fire and gas clouds, stepped each tick by the hazard-spread simulation.
+    pub spreading_hazards: Vec<Option<SpreadingHazard>>,
+    /// Player-placed markers on discovered tiles; cleared with the map on each new floor.
+    #[serde(with = "keyed_pairs")]
+    pub markers: BTreeMap<Pos, MarkerKind>,
+    /// Room each tile belongs to; corridors and rock are `None`.
+    pub rooms: Vec<Option<RoomId>>,
+    /// Vault-stamped rooms, as mapgen reported them.
+    pub vaults: Vec<VaultStamp>,
+    pub light: FloorLight,
+    /// Bumped whenever tiles, discovery, hazards or markers change, so path caches can tell
+    /// a stale route from a current one. Never hashed.
+    pub revision: u64,
+}
+
+/// One tile of a spreading hazard; it vanishes once `intensity` decays to zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpreadingHazard {
+    pub kind: HazardKind,
+    pub intensity: u8,
 }
 
 impl Map {
+    /// An open floor enclosed by a one-tile wall ring.
     pub fn new(width: usize, height: usize) -> Self {
-        let mut tiles = vec![TileKind::Floor; width * height];
-        for x in 0..width {
-            tiles[x] = TileKind::Wall;
-            tiles[(height - 1) * width + x] = TileKind::Wall;
-        }
-        for y in 0..height {
-            tiles[y * width] = TileKind::Wall;
-            tiles[y * width + (width - 1)] = TileKind::Wall;
-        }
-        Self {
+        let mut map = Self {
             internal_width: width,
             internal_height: height,
-            tiles,
+            tiles: vec![TileKind::Wall; width * height],
             discovered: vec![false; width * height],
             visible: vec![false; width * height],
             hazards: vec![false; width * height],
+            spreading_hazards: vec![None; width * height],
+            markers: BTreeMap::new(),
+            rooms: vec![None; width * height],
+            vaults: Vec::new(),
+            light: FloorLight::default(),
+            revision: 0,
+        };
+        for pos in map.interior_positions() {
+            map.set_tile(pos, TileKind::Floor);
         }
+        map
+    }
+
+    /// Row-major index of `pos` into the per-tile vectors, or `None` off the map.
+    pub fn index(&self, pos: Pos) -> Option<usize> {
+        pos.grid_index(self.internal_width, self.internal_height)
+    }
+
+    /// The position at row-major `index`; the inverse of `index`.
+    pub fn pos_at(&self, index: usize) -> Pos {
+        Pos { y: (index / self.internal_width) as i32, x: (index % self.internal_width) as i32 }
+    }
+
+    /// Every position in row-major order, matching the per-tile vectors.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<> {
+        let (width, height) = (self.internal_width as i32, self.internal_height as i32);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Pos { y, x }))
+    }
+
+    /// Positions inside the outer ring of tiles, in row-major order.
+    pub fn interior_positions(&self) -> impl Iterator<Item = Pos> + use<> {
+        let (width, height) = (self.internal_width as i32, self.internal_height as i32);
+        (1..height - 1).flat_map(move |y| (1..width - 1).map(move |x| Pos { y, x }))
     }
 
     pub fn tile_at(&self, pos: Pos) -> TileKind {
-        if pos.x < 0 || pos.y < 0 {
-            return TileKind::Wall;
-        }
-        let xu = pos.x as usize;
-        let yu = pos.y as usize;
-        if xu >= self.internal_width || yu >= self.internal_height {
-            return TileKind::Wall;
-        }
-        self.tiles[yu * self.internal_width + xu]
+        self.index(pos).map_or(TileKind::Wall, |idx| self.tiles[idx])
     }
 
     pub fn in_bounds(&self, pos: Pos) -> bool {
@@ -81,26 +117,39 @@ impl Map {
     }
 
     pub fn set_tile(&mut self, pos: Pos, tile: TileKind) {
-        if !self.in_bounds(pos) {
-            return;
+        if let Some(idx) = self.index(pos)
+            && self.tiles[idx] != tile
+        {
+            self.tiles[idx] = tile;
+            self.revision += 1;
         }
-        let idx = self.index(pos);
-        self.tiles[idx] = tile;
+    }
+
+    pub fn room_at(&self, pos: Pos) -> Option<RoomId> {
+        self.index(pos).and_then(|idx| self.rooms[idx])
+    }
+
+    /// The vault stamped on `room`, if any.
+    pub fn vault(&self, room: RoomId) -> Option<&VaultStamp> {
+        self.vaults.iter().find(|vault| vault.room == room)
+    }
+
+    /// Every tile labelled with `room`, in row-major order.
+    pub fn room_tiles(&self, room: RoomId) -> impl Iterator<Item = Pos> + '_ {
+        self.positions().filter(move |pos| self.room_at(*pos) == Some(room))
     }
 
     pub fn reveal(&mut self, pos: Pos) {
-        if !self.in_bounds(pos) {
-            return;
+        if let Some(idx) = self.index(pos)
+            && !self.discovered[idx]
+        {
+            self.discovered[idx] = true;
+            self.revision += 1;
         }
-        let idx = self.index(pos);
-        self.discovered[idx] = true;
     }
 
     pub fn is_discovered(&self, pos: Pos) -> bool {
-        if !self.in_bounds(pos) {
-            return false;
-        }
-        self.discovered[self.index(pos)]
+        self.index(pos).is_some_and(|idx| self.discovered[idx])
     }
 
     pub fn is_discovered_walkable(&self, pos: Pos) -> bool {
@@ -115,47 +164,48 @@ impl Map {
     }
 
     pub fn set_visible(&mut self, pos: Pos, val: bool) {
-        if !self.in_bounds(pos) {
+        let Some(idx) = self.index(pos) else {
             return;
-        }
-        let idx = self.index(pos);
+        };
         self.visible[idx] = val;
-        if val {
+        if val && !self.discovered[idx] {
             self.discovered[idx] = true;
+            self.revision += 1;
         }
     }
 
     pub fn is_visible(&self, pos: Pos) -> bool {
-        if !self.in_bounds(pos) {
-            return false;
-        }
-        self.visible[self.index(pos)]
+        self.index(pos).is_some_and(|idx| self.visible[idx])
     }
 
     pub fn set_hazard(&mut self, pos: Pos, val: bool) {
-        if !self.in_bounds(pos) {
-            return;
+        if let Some(idx) = self.index(pos)
+            && self.hazards[idx] != val
+        {
+            self.hazards[idx] = val;
+            self.revision += 1;
         }
-        let idx = self.index(pos);
-        self.hazards[idx] = val;
     }
 
     pub fn is_hazard(&self, pos: Pos) -> bool {
-        if !self.in_bounds(pos) {
-            return false;
-        }
-        self.hazards[self.index(pos)]
+        self.index(pos)
+            .is_some_and(|idx| self.hazards[idx] || self.spreading_hazards[idx].is_some())
     }
 
-    pub fn is_discovered_walkable_safe(&self, pos: Pos) -> bool {
-        self.is_discovered_walkable(pos) && !self.is_hazard(pos)
+    pub fn spreading_hazard_at(&self, pos: Pos) -> Option<SpreadingHazard> {
+        self.index(pos).and_then(|idx| self.spreading_hazards[idx])
     }
 
-    fn index(&self, pos: Pos) -> usize {
-        (pos.y as usize) * self.internal_width + (pos.x as usize)
+    pub fn marker_at(&self, pos: Pos) -> Option<MarkerKind> {
+        self.markers.get(&pos).copied()
+    }
+
+    pub fn is_discovered_walkable_safe(&self, pos: Pos) -> bool {
+        self.is_discovered_walkable(pos) && !self.is_hazard(pos)
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameState {
     pub map: Map,
     pub actors: SlotMap<EntityId, Actor>,
@@ -165,12 +215,77 @@ pub struct GameState {
     pub sanctuary_active: bool,
     pub floor_index: u8,
     pub branch_profile: BranchProfile,
+    /// Generator, version and floor seed behind the current floor's layout.
+    pub floor_provenance: FloorProvenance,
     pub active_god: Option<GodId>,
+    /// Pact altar on the current floor; cleared once the player answers it.
+    pub altar_tile: Option<Pos>,
+    /// Set once the player abandons their god; a broken pact cannot be broken again.
+    pub pact_broken: bool,
+    /// Floors left before the oathbreaker curse from a broken pact lifts.
+    pub curse_floors_remaining: u8,
+    /// FOV radius gained from Keen Sight boons.
+    pub fov_bonus: u8,
+    /// Blinks banked from boons, spent when an avoid would otherwise leave the player in place.
+    pub blink_charges: u8,
+    /// Set by a brace; the next adjacent enemy action draws an automatic counterattack.
+    pub braced: bool,
+    /// Ticks left of a deliberate wait; the player holds position until it runs out.
+    pub wait_ticks_remaining: u32,
+    /// A journaled single step the player takes on their next free action, then pauses.
+    pub manual_step: Option<Direction>,
     pub auto_intent: Option<AutoExploreIntent>,
     pub policy: Policy,
     pub threat_trace: VecDeque<ThreatTrace>,
+    #[serde(deserialize_with = "crate::content::static_keys::key_list")]
     pub active_perks: Vec<&'static str>,
+    /// Active perks that came from cursed loot and still carry their curse.
+    #[serde(deserialize_with = "crate::content::static_keys::key_list")]
+    pub cursed_perks: Vec<&'static str>,
+    /// Consumables kept for later, one stack per id in pickup order, at most
+    /// `INVENTORY_CAPACITY` stacks.
+    pub inventory: Vec<InventorySlot>,
     pub kills_this_floor: u32,
+    /// Experience from kills this run; crossing a level threshold owes a perk draft.
+    pub player_xp: u32,
+    pub player_level: u8,
+    /// Level-up perk drafts earned but not yet answered.
+    pub perk_drafts_pending: u8,
+    /// Tick the player arrived on the current floor; wandering spawns pace from here.
+    pub floor_entry_tick: u64,
+    /// Wandering enemies that have spawned since `floor_entry_tick`.
+    pub wanderers_this_floor: u8,
+    /// Open objective on this floor; its down stairs stay locked while it is set.
+    pub floor_objective: Option<ActiveObjective>,
+    pub completed_objectives: Vec<ObjectiveRecord>,
+    /// Ticks of torchlight left; while it burns, Dark floors do not shorten sight.
+    pub torch_ticks: u64,
+    /// Weather-like modifier on the current floor, rolled from the run seed on arrival.
+    pub ambience: Ambience,
+    /// Run-specific appearance shown for each unidentified potion, keyed by consumable id.
+    #[serde(deserialize_with = "crate::content::static_keys::key_map")]
+    pub potion_appearances: BTreeMap<&'static str, &'static str>,
+    /// Consumable ids the player has identified by using them this run.
+    #[serde(deserialize_with = "crate::content::static_keys::key_set")]
+    pub identified_consumables: BTreeSet<&'static str>,
+    /// Floors the player has left, keyed by floor index, restored as they were on a revisit.
+    pub stored_floors: BTreeMap<u8, StoredFloor>,
+    /// Bosses and elites slain this run; no floor, revisit or wandering roll brings them back.
+    pub slain_uniques: BTreeSet<UniqueEnemy>,
+    /// Optional adaptive difficulty director; see `Game::set_director_enabled`.
+    pub director: DirectorState,
+}
+
+/// A floor as the player left it: its map and light plus the enemies and items still on it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StoredFloor {
+    pub map: Map,
+    pub actors: Vec<Actor>,
+    pub items: Vec<Item>,
+    pub entry_tile: Pos,
+    pub provenance: FloorProvenance,
+    /// The objective still open when the player left, with the ids it had then.
+    pub objective: Option<ActiveObjective>,
 }
 
 #[cfg(test)]
@@ -196,5 +311,30 @@ mod tests {
         map.clear_visible();
         assert!(!map.is_visible(in_bounds));
         assert!(map.is_discovered(in_bounds), "clear_visible should not erase discovery");
+
+        let revision = map.revision;
+        map.set_visible(in_bounds, true);
+        map.set_tile(in_bounds, TileKind::Floor);
+        assert_eq!(map.revision, revision, "changes that change nothing keep the revision");
+        map.set_hazard(in_bounds, true);
+        assert_eq!(map.revision, revision + 1);
+    }
+
+    #[test]
+    fn index_and_positions_agree_with_row_major_storage() {
+        let map = Map::new(4, 3);
+        assert_eq!(map.index(Pos { y: 2, x: 1 }), Some(9));
+        assert_eq!(map.index(Pos { y: 0, x: 4 }), None);
+        assert_eq!(map.index(Pos { y: -1, x: 0 }), None);
+
+        for (idx, pos) in map.positions().enumerate() {
+            assert_eq!(map.index(pos), Some(idx));
+            assert_eq!(map.pos_at(idx), pos);
+        }
+        assert_eq!(map.positions().count(), map.tiles.len());
+        let interior: Vec<Pos> = map.interior_positions().collect();
+        assert_eq!(interior, vec![Pos { y: 1, x: 1 }, Pos { y: 1, x: 2 }]);
+        assert!(interior.iter().all(|pos| map.tile_at(*pos) == TileKind::Floor));
+        assert_eq!(map.tiles.iter().filter(|tile| **tile == TileKind::Wall).count(), 10);
     }
 }
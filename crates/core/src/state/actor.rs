@@ -0,0 +1,42 @@
+//! Actors: the player and every enemy on the current floor, with their combat stats,
+//! weapons, turn clock and stealth awareness.
+
+use serde::{Deserialize, Serialize};
+
+use super::ItemInstance;
+use crate::types::{ActorKind, AwarenessState, EntityId, Pos, UniqueEnemy, WeaponSlot};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Actor {
+    pub id: EntityId,
+    pub kind: ActorKind,
+    pub pos: Pos,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub attack: i32,
+    pub defense: i32,
+    pub active_weapon_slot: WeaponSlot,
+    pub equipped_weapon: Option<ItemInstance>,
+    pub reserve_weapon: Option<ItemInstance>,
+    pub next_action_tick: u64,
+    pub speed: u32,
+    /// Stealth awareness of the player from 0 (unaware) to `MAX_AWARENESS` (alert).
+    pub awareness: u8,
+    /// Set on bosses and objective elites, which a run meets at most once.
+    pub unique: Option<UniqueEnemy>,
+    /// Tile the actor was placed on; an enemy pulled beyond its leash walks back here.
+    pub home: Pos,
+}
+
+/// Awareness level at which an enemy has fully spotted the player.
+pub const MAX_AWARENESS: u8 = 100;
+
+impl Actor {
+    pub fn awareness_state(&self) -> AwarenessState {
+        match self.awareness {
+            0 => AwarenessState::Unaware,
+            MAX_AWARENESS.. => AwarenessState::Alert,
+            _ => AwarenessState::Suspicious,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Adaptive difficulty director state, carried with the run so checkpoints and hashes see it.
+//! The director is off unless a run turns it on; while off none of these fields change.
+
+use serde::{Deserialize, Serialize};
+
+/// Largest pressure step either way: positive pushes harder, negative eases off.
+pub const MAX_DIRECTOR_PRESSURE: i8 = 2;
+
+/// How the current floor is going and how hard the director is currently pushing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DirectorState {
+    pub enabled: bool,
+    /// Current nudge, from `-MAX_DIRECTOR_PRESSURE` to `MAX_DIRECTOR_PRESSURE`.
+    pub pressure: i8,
+    /// Player HP on arriving at the current floor.
+    pub entry_hp: i32,
+    /// Lowest player HP seen since arriving.
+    pub lowest_hp: i32,
+    /// Times on this floor the player dropped to their retreat threshold with enemies in view.
+    pub close_calls: u8,
+    /// Whether the last tick was part of a close call, so one scrape counts once.
+    pub in_close_call: bool,
+}
@@ -0,0 +1,13 @@
+//! One stack of consumables in the player's inventory.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::ContentKey;
+
+/// Copies of one consumable kept for later; a slot holds any number of the same id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InventorySlot {
+    #[serde(deserialize_with = "crate::content::static_keys::key")]
+    pub id: ContentKey,
+    pub count: u32,
+}
@@ -0,0 +1,57 @@
+//! Per-instance state of items the player holds, and its save-file form.
+
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::content::resolve_item_key;
+
+/// A concrete item the player holds: its content definition key plus per-instance state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct ItemInstance {
+    pub key: &'static str,
+    /// Enchantment bonus from whetstones and scrolls, capped by `MAX_WEAPON_ENCHANT`.
+    pub enchant: u8,
+    /// A cursed weapon cannot be swapped away or replaced until the curse is cleansed.
+    pub cursed: bool,
+}
+
+impl ItemInstance {
+    pub fn new(key: &'static str) -> Self {
+        Self { key, enchant: 0, cursed: false }
+    }
+}
+
+/// Owned wire form of `ItemInstance`, resolved back to static content keys on load.
+#[derive(Deserialize)]
+struct StoredItemInstance {
+    key: String,
+    enchant: u8,
+    #[serde(default)]
+    cursed: bool,
+}
+
+impl<'de> Deserialize<'de> for ItemInstance {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = StoredItemInstance::deserialize(deserializer)?;
+        let key = resolve_item_key(&stored.key)
+            .ok_or_else(|| D::Error::custom(format!("unknown item key: {}", stored.key)))?;
+        Ok(Self { key, enchant: stored.enchant, cursed: stored.cursed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::keys;
+
+    #[test]
+    fn item_instance_round_trips_through_serde() {
+        let instance = ItemInstance { key: keys::WEAPON_BLOOD_AXE, enchant: 2, cursed: true };
+        let json = serde_json::to_string(&instance).expect("instance should serialize");
+        let restored: ItemInstance = serde_json::from_str(&json).expect("instance should load");
+        assert_eq!(restored, instance);
+
+        let unknown = json.replace(keys::WEAPON_BLOOD_AXE, "weapon_missing");
+        assert!(serde_json::from_str::<ItemInstance>(&unknown).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+//! Per-floor light: Dark floors hide unlit tiles beyond a short radius.
+//! Braziers light fixed areas; a burning torch lifts the limit entirely.
+
+use super::Map;
+use crate::types::Pos;
+use serde::{Deserialize, Serialize};
+
+/// Light on one floor, stored with its map so a revisited floor keeps its braziers.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloorLight {
+    pub dark: bool,
+    pub braziers: Vec<Pos>,
+    /// Tiles within reach of a brazier, row-major like `Map::tiles`; empty on lit floors.
+    pub lit: Vec<bool>,
+    /// Sight radius over unlit tiles, or `None` while a torch burns or the floor is not dark.
+    pub unlit_range: Option<i32>,
+}
+
+impl Map {
+    /// Whether a brazier lights `pos`.
+    pub fn is_lit(&self, pos: Pos) -> bool {
+        self.index(pos).is_some_and(|idx| self.light.lit.get(idx).copied().unwrap_or(false))
+    }
+
+    /// Whether `pos` lies in shadow: on a Dark floor, outside every brazier's light.
+    pub fn is_shadowed(&self, pos: Pos) -> bool {
+        self.light.dark && !self.is_lit(pos)
+    }
+}
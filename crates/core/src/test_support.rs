@@ -0,0 +1,113 @@
+//! Fixture builders shared by core's own tests and, behind the `test-support` feature,
+//! by downstream crates that need hand-made maps.
+
+use crate::state::Map;
+use crate::types::{Pos, TileKind};
+
+/// Declarative fixture maps: start solid or open, carve rooms and corridors, then place
+/// doors, stairs, and hazards. Later steps overwrite earlier ones tile by tile.
+#[derive(Clone)]
+pub struct MapBuilder {
+    map: Map,
+}
+
+impl MapBuilder {
+    /// Every tile is wall; carve the playable space out of it.
+    pub fn solid(width: usize, height: usize) -> Self {
+        let mut map = Map::new(width, height);
+        map.tiles.fill(TileKind::Wall);
+        Self { map }
+    }
+
+    /// Open floor enclosed by a one-tile wall ring, as `Map::new` builds it.
+    pub fn open(width: usize, height: usize) -> Self {
+        Self { map: Map::new(width, height) }
+    }
+
+    /// Floor on the rectangle spanning both corners, inclusive.
+    pub fn room(mut self, corner: Pos, opposite: Pos) -> Self {
+        for y in corner.y.min(opposite.y)..=corner.y.max(opposite.y) {
+            for x in corner.x.min(opposite.x)..=corner.x.max(opposite.x) {
+                self.map.set_tile(Pos { y, x }, TileKind::Floor);
+            }
+        }
+        self
+    }
+
+    /// Floor from `from` to `to`, running along the row first and then down the column.
+    pub fn corridor(self, from: Pos, to: Pos) -> Self {
+        let bend = Pos { y: from.y, x: to.x };
+        self.room(from, bend).room(bend, to)
+    }
+
+    pub fn wall(self, pos: Pos) -> Self {
+        self.tile(pos, TileKind::Wall)
+    }
+
+    pub fn door(self, pos: Pos) -> Self {
+        self.tile(pos, TileKind::ClosedDoor)
+    }
+
+    pub fn stairs(self, pos: Pos) -> Self {
+        self.tile(pos, TileKind::DownStairs)
+    }
+
+    pub fn tile(mut self, pos: Pos, tile: TileKind) -> Self {
+        self.map.set_tile(pos, tile);
+        self
+    }
+
+    /// A static hazard; spreading fire and gas are released through the game instead.
+    pub fn hazard(mut self, pos: Pos) -> Self {
+        self.map.set_hazard(pos, true);
+        self
+    }
+
+    pub fn discover_all(mut self) -> Self {
+        self.map.discovered.fill(true);
+        self
+    }
+
+    /// Every tile visible, and therefore also discovered.
+    pub fn visible_all(mut self) -> Self {
+        self.map.visible.fill(true);
+        self.discover_all()
+    }
+
+    /// Forget one tile, typically to leave a frontier after `discover_all`.
+    pub fn undiscovered(mut self, pos: Pos) -> Self {
+        if let Some(idx) = self.map.index(pos) {
+            self.map.discovered[idx] = false;
+        }
+        self
+    }
+
+    pub fn build(self) -> Map {
+        self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carves_rooms_and_bent_corridors_out_of_solid_rock() {
+        let map = MapBuilder::solid(8, 6)
+            .room(Pos { y: 1, x: 1 }, Pos { y: 2, x: 2 })
+            .corridor(Pos { y: 2, x: 2 }, Pos { y: 4, x: 6 })
+            .door(Pos { y: 2, x: 4 })
+            .stairs(Pos { y: 4, x: 6 })
+            .visible_all()
+            .undiscovered(Pos { y: 3, x: 6 })
+            .build();
+
+        let floors = map.tiles.iter().filter(|tile| **tile != TileKind::Wall).count();
+        assert_eq!(floors, 4 + 4 + 2);
+        assert_eq!(map.tile_at(Pos { y: 2, x: 6 }), TileKind::Floor, "corridor bends here");
+        assert_eq!(map.tile_at(Pos { y: 2, x: 4 }), TileKind::ClosedDoor);
+        assert_eq!(map.tile_at(Pos { y: 0, x: 0 }), TileKind::Wall);
+        assert!(map.is_visible(Pos { y: 0, x: 0 }));
+        assert!(!map.is_discovered(Pos { y: 3, x: 6 }));
+    }
+}
@@ -1,17 +1,27 @@
 use slotmap::new_key_type;
 
+use crate::mapgen::BranchPreview;
+
 new_key_type! {
     pub struct EntityId;
     pub struct ItemId;
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Pos {
     pub y: i32,
     pub x: i32,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+impl Pos {
+    /// Row-major index of this position in a `width` by `height` grid, or `None` outside it.
+    pub fn grid_index(self, width: usize, height: usize) -> Option<usize> {
+        let (x, y) = (usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?);
+        (x < width && y < height).then_some(y * width + x)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum TileKind {
     Wall,
     Floor,
@@ -19,6 +29,10 @@ pub enum TileKind {
     DownStairs,
 }
 
+/// A built-in content id. Named so serde does not read it as borrowed from the input; saves
+/// resolve it by name instead.
+pub type ContentKey = &'static str;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub enum ItemKind {
     Weapon(&'static str),
@@ -26,6 +40,34 @@ pub enum ItemKind {
     Perk(&'static str),
 }
 
+/// Hazards that spread to neighbouring tiles and burn out over time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum HazardKind {
+    Fire,
+    Gas,
+    Rubble,
+}
+
+/// Player-placed note on a discovered tile; some kinds steer the auto-explore planner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MarkerKind {
+    Danger,
+    LootLater,
+    Stairs,
+    Avoid,
+}
+
+impl MarkerKind {
+    /// Extra path cost the player's planner pays to step onto a tile with this marker.
+    pub fn path_penalty(self) -> u32 {
+        match self {
+            Self::Danger => 4,
+            Self::Avoid => 12,
+            Self::LootLater | Self::Stairs => 0,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ActorKind {
     Player,
@@ -37,13 +79,66 @@ pub enum ActorKind {
     Gargoyle,
     ShadowStalker,
     AbyssalWarden,
+    HollowKing,
+    BoneArcher,
 }
 
 use serde::{Deserialize, Serialize};
 
+mod advance;
+mod ambience;
+mod death;
+mod direction;
+mod enemy_memory;
+mod error;
+mod event_log;
+mod fight_preview;
+mod hud;
+mod internals;
+mod log_event;
+mod memory;
+mod objective;
+mod path_preview;
+mod policy;
+mod provenance;
+mod stats;
+mod target_scan;
+mod unique;
+mod vault;
+mod victory;
+
+pub use advance::{AdvanceBreakdown, AdvanceResult, AdvanceStopReason};
+pub use ambience::Ambience;
+pub use death::{DEATH_LOG_TAIL, DamageSource, DeathRecord};
+pub use direction::Direction;
+pub use enemy_memory::LastKnownThreat;
+pub use error::GameError;
+pub use event_log::EventLog;
+pub use fight_preview::FightPreview;
+pub use hud::{HUD_EVENT_COUNT, HUD_THREAT_COUNT, HudSnapshot, HudThreat, PolicySummary};
+pub use internals::{EngineInternals, PlannerPass};
+pub use log_event::LogEvent;
+pub use memory::MemoryFootprint;
+pub use objective::{ActiveObjective, FloorObjective, ObjectiveRecord};
+pub use path_preview::{PathPreview, PathStep};
+pub use policy::{
+    Aggro, AutoPickupRule, ExploreMode, FightMode, PickupAction, PickupFilter, Policy,
+    PolicyUpdate, PositionIntent, Stance, TargetTag,
+};
+pub use provenance::{FloorProvenance, MapgenAlgorithm};
+pub use stats::{FloorCheckpoint, FloorVisits, RunStats};
+pub use target_scan::{ScanHit, TargetScan};
+pub use unique::UniqueEnemy;
+pub use vault::{VaultStamp, VaultTemplate};
+pub use victory::VictoryKind;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ChoicePromptId(pub u64);
 
+/// A generated room on the current floor, numbered in layout order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RoomId(pub u16);
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Choice {
     KeepLoot,
@@ -58,35 +153,124 @@ pub enum Choice {
     DescendBranchAForge,
     DescendBranchBVeil,
     DescendBranchBForge,
+    BreakPact,
+    KeepPact,
+    /// Descend and take the offered boon of this kind.
+    DescendWithBoon(BoonKind),
+    /// Leave locked stairs and let auto-explore pursue the floor objective.
+    SeekObjective,
+    /// Keep the pact and have the god lift every item curse.
+    CleanseCurses,
+    /// Take the perk at this index of a level-up draft's offer.
+    DraftPerk(u8),
+    /// Use one consumable from this inventory slot, at a loot or encounter prompt.
+    UseItem(u8),
+    /// Throw away the stack in this inventory slot to make room, at a loot prompt.
+    DropItem(u8),
+    /// Leave this loot or door alone for `ticks` ticks; auto-explore carries on elsewhere.
+    /// Only [`SNOOZE_TICKS`] is accepted.
+    IgnoreFor {
+        ticks: u32,
+    },
 }
 
+/// Length of the snooze offered on loot and door prompts, in ticks.
+pub const SNOOZE_TICKS: u32 = 40;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GodId {
     Veil,
     Forge,
 }
 
+impl GodId {
+    /// The god whose passives replace this one's when the pact is broken.
+    pub fn alternate(self) -> Self {
+        match self {
+            Self::Veil => Self::Forge,
+            Self::Forge => Self::Veil,
+        }
+    }
+}
+
+/// Boons a god may offer on a non-first descent: Veil grants sight and blinks, Forge grants
+/// defense and vigor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoonKind {
+    KeenSight,
+    BlinkCharge,
+    Bulwark,
+    Vigor,
+}
+
+/// A boon offered on a floor-transition prompt, with its rolled strength.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GodBoon {
+    pub kind: BoonKind,
+    pub amount: u8,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DeathCause {
     /// Player died because HP reached zero from direct damage.
     Damage,
     /// Player died because poison damage reduced HP to zero.
     Poison,
+    /// Player died because a hazard such as fire or gas reduced HP to zero.
+    Hazard,
+    /// Player died because hunger reduced HP to zero.
+    Starvation,
+    /// Player died because a curse reduced HP to zero.
+    Curse,
+}
+
+impl DeathCause {
+    /// Every cause, in declaration order.
+    pub const ALL: [Self; 5] =
+        [Self::Damage, Self::Poison, Self::Hazard, Self::Starvation, Self::Curse];
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum EngineFailureReason {
     /// Fail-safe stop used to prevent infinite loops when simulation cannot advance.
     /// If this appears during real gameplay, floor generation or game logic is broken.
-    StalledNoProgress,
+    StalledNoProgress(EngineDiagnostics),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+impl EngineFailureReason {
+    pub fn diagnostics(&self) -> &EngineDiagnostics {
+        match self {
+            Self::StalledNoProgress(diagnostics) => diagnostics,
+        }
+    }
+}
+
+/// Engine context captured at the moment of a failure, for bug reports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EngineDiagnostics {
+    pub tick: u64,
+    pub floor_index: u8,
+    pub player_pos: Pos,
+    pub last_intent: Option<AutoExploreIntent>,
+    /// Frontier tiles the player could still reach over discovered ground.
+    pub reachable_frontier_count: usize,
+    pub snapshot_hash: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RunOutcome {
-    Victory,
+    Victory(VictoryKind),
     Defeat(DeathCause),
 }
 
+/// How much an enemy has noticed the player, derived from its awareness level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AwarenessState {
+    Unaware,
+    Suspicious,
+    Alert,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum DangerTag {
     Melee,
@@ -109,6 +293,10 @@ pub enum Interrupt {
         prompt_id: ChoicePromptId,
         item: ItemId,
         kind: ItemKind,
+        /// Set when the item is cursed and the player can sense it.
+        curse_detected: bool,
+        /// Set when a kept consumable would not fit the inventory and is used at once.
+        inventory_full: bool,
     },
     EnemyEncounter {
         prompt_id: ChoicePromptId,
@@ -116,6 +304,8 @@ pub enum Interrupt {
         primary_enemy: EntityId,
         retreat_eligible: bool,
         threat: ThreatSummary,
+        /// The roster's intro line when the primary enemy is a branch boss.
+        boss_intro: Option<&'static str>,
     },
     DoorBlocked {
         prompt_id: ChoicePromptId,
@@ -126,52 +316,90 @@ pub enum Interrupt {
         current_floor: u8,
         next_floor: Option<u8>,
         requires_branch_god_choice: bool,
+        /// Boons the active god offers for this descent; empty when none are on offer.
+        boons: Vec<GodBoon>,
+        /// Next-floor previews per branch when a branch must be chosen; empty otherwise.
+        branch_previews: Vec<BranchPreview>,
+    },
+    /// The player reached a pact altar and may abandon `current_god` for `alternate_god`.
+    PactAltar {
+        prompt_id: ChoicePromptId,
+        pos: Pos,
+        current_god: GodId,
+        alternate_god: GodId,
+        /// Whether the altar offers to cleanse the player's cursed items.
+        can_cleanse: bool,
+    },
+    /// The player reached down stairs that stay locked until the floor objective is done.
+    StairsLocked {
+        prompt_id: ChoicePromptId,
+        objective: FloorObjective,
+    },
+    /// The player reached `level` and picks one perk from `offer`, none of them already owned.
+    PerkDraft {
+        prompt_id: ChoicePromptId,
+        level: u8,
+        offer: Vec<&'static str>,
     },
 }
 
-#[derive(Clone, Debug)]
-pub enum AdvanceStopReason {
-    Interrupted(Interrupt),
-    PausedAtBoundary { tick: u64 },
-    Finished(RunOutcome),
-    BudgetExhausted,
-    EngineFailure(EngineFailureReason),
-}
-
-#[derive(Clone, Debug)]
-pub struct AdvanceResult {
-    pub simulated_ticks: u32,
-    pub stop_reason: AdvanceStopReason,
-}
+/// Prompt categories in the order the engine raises them when several apply on one tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PromptKind {
+    EnemyEncounter,
+    PerkDraft,
+    LootFound,
+    PactAltar,
+    FloorTransition,
+    StairsLocked,
+    DoorBlocked,
+}
+
+impl Interrupt {
+    pub fn prompt_kind(&self) -> PromptKind {
+        match self {
+            Self::LootFound { .. } => PromptKind::LootFound,
+            Self::EnemyEncounter { .. } => PromptKind::EnemyEncounter,
+            Self::DoorBlocked { .. } => PromptKind::DoorBlocked,
+            Self::FloorTransition { .. } => PromptKind::FloorTransition,
+            Self::PactAltar { .. } => PromptKind::PactAltar,
+            Self::StairsLocked { .. } => PromptKind::StairsLocked,
+            Self::PerkDraft { .. } => PromptKind::PerkDraft,
+        }
+    }
 
-#[derive(Debug, Clone)]
-pub enum GameError {
-    InvalidChoice,
-    PromptMismatch,
-    NotAtPauseBoundary,
+    pub fn prompt_id(&self) -> ChoicePromptId {
+        match self {
+            Self::LootFound { prompt_id, .. }
+            | Self::EnemyEncounter { prompt_id, .. }
+            | Self::DoorBlocked { prompt_id, .. }
+            | Self::FloorTransition { prompt_id, .. }
+            | Self::PactAltar { prompt_id, .. }
+            | Self::StairsLocked { prompt_id, .. }
+            | Self::PerkDraft { prompt_id, .. } => *prompt_id,
+        }
+    }
 }
 
+/// The active interrupt plus the prompts that were also due on the same tick.
+/// Queued prompts are re-checked after the active one resolves, so they are a preview.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum LogEvent {
-    AutoReasonChanged { reason: AutoReason, target: Pos, path_len: u16 },
-    EnemyEncountered { enemy: EntityId },
-    ItemPickedUp { kind: ItemKind },
-    ItemDiscarded { kind: ItemKind },
-    EncounterResolved { enemy: EntityId, fought: bool },
-    RecoveryHint { seed: u64, hash_hex: String },
-    Notice(String),
+pub struct PromptBatch {
+    pub active: Interrupt,
+    pub queued: Vec<PromptKind>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AutoReason {
     Frontier,
     Loot,
     ThreatAvoidance,
     Stuck,
     Door,
+    Objective,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AutoExploreIntent {
     pub target: Pos,
     pub reason: AutoReason,
@@ -181,7 +409,7 @@ pub struct AutoExploreIntent {
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ThreatTrace {
     pub tick: u64,
-    pub visible_enemy_count: usize,
+    pub visible_enemy_count: u16,
     pub min_enemy_distance: Option<u32>,
     pub retreat_triggered: bool,
 }
@@ -191,17 +419,13 @@ pub enum GameMode {
     Easy,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum FightMode {
-    Fight,
-    Avoid,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Stance {
-    Aggressive,
-    Balanced,
-    Defensive,
+/// Run-wide difficulty preset chosen at run start and recorded in the journal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    Relaxed,
+    #[default]
+    Normal,
+    Brutal,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -210,56 +434,6 @@ pub enum WeaponSlot {
     Reserve,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum TargetTag {
-    Nearest,
-    LowestHp,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum PositionIntent {
-    HoldGround,
-    AdvanceToMelee,
-    FleeToNearestExploredTile,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Aggro {
-    Conserve,
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum ExploreMode {
-    Thorough,
-}
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Policy {
-    pub fight_or_avoid: FightMode,
-    pub stance: Stance,
-    pub target_priority: Vec<TargetTag>,
-    pub retreat_hp_threshold: u8,
-    pub auto_heal_if_below_threshold: Option<u8>,
-    pub position_intent: PositionIntent,
-    pub resource_aggression: Aggro,
-    pub exploration_mode: ExploreMode,
-}
-
-impl Default for Policy {
-    fn default() -> Self {
-        Self {
-            fight_or_avoid: FightMode::Fight,
-            stance: Stance::Balanced,
-            target_priority: vec![TargetTag::Nearest, TargetTag::LowestHp],
-            retreat_hp_threshold: 35,
-            auto_heal_if_below_threshold: None,
-            position_intent: PositionIntent::HoldGround,
-            resource_aggression: Aggro::Conserve,
-            exploration_mode: ExploreMode::Thorough,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,15 +476,3 @@ mod tests {
         assert_eq!(summary.primary_enemy_kind, ActorKind::FeralHound);
     }
 }
-
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub enum PolicyUpdate {
-    FightMode(FightMode),
-    Stance(Stance),
-    TargetPriority(Vec<TargetTag>),
-    RetreatHpThreshold(u8),
-    AutoHealIfBelowThreshold(Option<u8>),
-    PositionIntent(PositionIntent),
-    ResourceAggression(Aggro),
-    ExplorationMode(ExploreMode),
-}
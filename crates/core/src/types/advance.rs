@@ -0,0 +1,57 @@
+//! What one `advance` call did: why it stopped, and where its ticks and planning went.
+//! The breakdown lets frontends tune per-frame budgets and tools spot replanning loops.
+
+use std::ops::AddAssign;
+
+use super::{EngineFailureReason, Interrupt, RunOutcome};
+
+#[derive(Clone, Debug)]
+pub enum AdvanceStopReason {
+    Interrupted(Interrupt),
+    PausedAtBoundary { tick: u64 },
+    Finished(RunOutcome),
+    BudgetExhausted,
+    EngineFailure(EngineFailureReason),
+}
+
+/// Where an advance's simulated ticks went; the four tick counts sum to `simulated_ticks`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AdvanceBreakdown {
+    /// Ticks on which the player stepped along an auto-explore route.
+    pub ticks_moved: u32,
+    /// Ticks the player spent busy finishing an earlier action.
+    pub ticks_busy: u32,
+    /// Ticks the player held position on purpose, as asked by `Game::apply_wait`.
+    pub ticks_waiting: u32,
+    /// Ticks on which the player neither moved, acted nor meant to wait.
+    pub ticks_idle: u32,
+    /// Times the auto-explore planner discarded its intent and picked a new one.
+    pub replans: u32,
+    /// New prompts raised; re-reporting an unanswered prompt does not count.
+    pub interrupts_raised: u32,
+}
+
+impl AddAssign for AdvanceBreakdown {
+    fn add_assign(&mut self, other: Self) {
+        self.ticks_moved += other.ticks_moved;
+        self.ticks_busy += other.ticks_busy;
+        self.ticks_waiting += other.ticks_waiting;
+        self.ticks_idle += other.ticks_idle;
+        self.replans += other.replans;
+        self.interrupts_raised += other.interrupts_raised;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdvanceResult {
+    pub simulated_ticks: u32,
+    pub stop_reason: AdvanceStopReason,
+    pub breakdown: AdvanceBreakdown,
+}
+
+impl AdvanceResult {
+    /// A result with an empty breakdown; `Game::advance` fills it in before returning.
+    pub fn new(simulated_ticks: u32, stop_reason: AdvanceStopReason) -> Self {
+        Self { simulated_ticks, stop_reason, breakdown: AdvanceBreakdown::default() }
+    }
+}
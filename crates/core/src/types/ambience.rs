@@ -0,0 +1,16 @@
+//! Floor ambience: a weather-like modifier each floor rolls from the run seed, so a replay
+//! of the same seed meets the same fog, echoes and tremors on the same floors.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Ambience {
+    #[default]
+    Still,
+    /// Sight shrinks for the player and enemies alike.
+    Fog,
+    /// Sound carries, so enemies notice the player from farther away.
+    Echoes,
+    /// The floor shakes now and then, and rubble briefly rains onto an open tile.
+    Tremors,
+}
@@ -0,0 +1,77 @@
+//! Player deaths: the final blow and the moments before it, as the single death pipeline
+//! recorded them for recaps, morgues and anything else that reports how a run ended.
+
+use serde::{Deserialize, Serialize};
+
+use super::{ActorKind, DeathCause, LogEvent};
+
+/// Log events a death record keeps from just before the final blow.
+pub const DEATH_LOG_TAIL: usize = 10;
+
+/// What harmed the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DamageSource {
+    Melee,
+    /// A shot from an enemy standing out of reach.
+    Ranged,
+    Poison,
+    Hazard,
+    Starvation,
+    Curse,
+}
+
+impl DamageSource {
+    /// The run outcome's cause when a blow of this kind is the last one.
+    pub fn death_cause(self) -> DeathCause {
+        match self {
+            Self::Melee | Self::Ranged => DeathCause::Damage,
+            Self::Poison => DeathCause::Poison,
+            Self::Hazard => DeathCause::Hazard,
+            Self::Starvation => DeathCause::Starvation,
+            Self::Curse => DeathCause::Curse,
+        }
+    }
+}
+
+/// How the player died.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeathRecord {
+    /// Kind of enemy behind the final blow; `None` when no enemy dealt it.
+    pub killer: Option<ActorKind>,
+    pub source: DamageSource,
+    /// Damage the final blow dealt.
+    pub damage: i32,
+    pub floor_index: u8,
+    pub tick: u64,
+    /// Up to `DEATH_LOG_TAIL` log events from before the final blow, oldest first.
+    pub recent_events: Vec<LogEvent>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn death_causes_are_listed_once_and_each_has_a_damage_source() {
+        for (index, cause) in DeathCause::ALL.into_iter().enumerate() {
+            // A new cause fails to compile here until it is also added to `ALL`.
+            let position = match cause {
+                DeathCause::Damage => 0,
+                DeathCause::Poison => 1,
+                DeathCause::Hazard => 2,
+                DeathCause::Starvation => 3,
+                DeathCause::Curse => 4,
+            };
+            assert_eq!(index, position);
+        }
+        let sources = [
+            DamageSource::Melee,
+            DamageSource::Poison,
+            DamageSource::Hazard,
+            DamageSource::Starvation,
+            DamageSource::Curse,
+        ];
+        assert_eq!(sources.map(DamageSource::death_cause), DeathCause::ALL);
+        assert_eq!(DamageSource::Ranged.death_cause(), DeathCause::Damage);
+    }
+}
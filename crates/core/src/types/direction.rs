@@ -0,0 +1,25 @@
+//! Cardinal directions for inputs that name a way to go rather than a tile, like a manual step.
+
+use serde::{Deserialize, Serialize};
+
+use super::Pos;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// The tile one step from `pos` in this direction.
+    pub fn step_from(self, pos: Pos) -> Pos {
+        match self {
+            Self::North => Pos { y: pos.y - 1, x: pos.x },
+            Self::East => Pos { y: pos.y, x: pos.x + 1 },
+            Self::South => Pos { y: pos.y + 1, x: pos.x },
+            Self::West => Pos { y: pos.y, x: pos.x - 1 },
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! The player's memory of enemies that slipped out of sight, for a "last known threat"
+//! map overlay. None of this feeds the snapshot hash; the memory fades over ticks.
+
+use super::{ActorKind, Pos};
+
+/// A remembered enemy out of sight: where it was last seen and how fresh that sighting is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LastKnownThreat {
+    pub kind: ActorKind,
+    pub pos: Pos,
+    pub seen_tick: u64,
+    /// Share of the memory left, from 100 when the enemy was just seen down toward 0.
+    pub fade_percent: u8,
+}
@@ -0,0 +1,80 @@
+//! Errors returned when the game rejects a player input.
+//! Each variant carries enough context for a frontend to explain what to do instead.
+
+use std::error::Error;
+use std::fmt;
+
+use super::{Choice, ChoicePromptId, Pos, PromptKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    /// A choice arrived while no prompt was pending.
+    NoPendingPrompt { submitted: ChoicePromptId },
+    /// A choice answered a prompt other than the pending one, usually a stale input.
+    PromptMismatch { expected: ChoicePromptId, submitted: ChoicePromptId },
+    /// The choice is not a valid answer to the pending prompt.
+    InvalidChoice { prompt: PromptKind, choice: Choice, reason: &'static str },
+    /// Policy, loadout, and marker inputs are only accepted while paused or at a prompt.
+    NotAtPauseBoundary { tick: u64 },
+    /// Markers can only be placed on tiles the player has discovered.
+    UndiscoveredTile { pos: Pos },
+    /// The active weapon is cursed and cannot be swapped away until the curse is cleansed.
+    CursedWeapon,
+    /// A manual step toward a wall, an undiscovered tile, or a tile another actor stands on.
+    BlockedStep { pos: Pos },
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPendingPrompt { submitted } => {
+                write!(f, "no prompt is pending (choice was for prompt {})", submitted.0)
+            }
+            Self::PromptMismatch { expected, submitted } => write!(
+                f,
+                "choice was for prompt {} but prompt {} is pending",
+                submitted.0, expected.0
+            ),
+            Self::InvalidChoice { prompt, choice, reason } => {
+                write!(f, "{choice:?} is not valid for the {prompt:?} prompt: {reason}")
+            }
+            Self::NotAtPauseBoundary { tick } => write!(
+                f,
+                "the simulation is running at tick {tick}; pause before changing policy or markers"
+            ),
+            Self::UndiscoveredTile { pos } => {
+                write!(f, "tile ({}, {}) has not been discovered yet", pos.x, pos.y)
+            }
+            Self::CursedWeapon => {
+                write!(f, "the active weapon is cursed; cleanse it at a pact altar to swap")
+            }
+            Self::BlockedStep { pos } => {
+                write!(f, "cannot step onto tile ({}, {})", pos.x, pos.y)
+            }
+        }
+    }
+}
+
+impl Error for GameError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_name_the_offending_input() {
+        let mismatch =
+            GameError::PromptMismatch { expected: ChoicePromptId(4), submitted: ChoicePromptId(3) };
+        assert_eq!(mismatch.to_string(), "choice was for prompt 3 but prompt 4 is pending");
+
+        let invalid = GameError::InvalidChoice {
+            prompt: PromptKind::DoorBlocked,
+            choice: Choice::Fight,
+            reason: "not an option for this prompt",
+        };
+        assert_eq!(
+            invalid.to_string(),
+            "Fight is not valid for the DoorBlocked prompt: not an option for this prompt"
+        );
+    }
+}
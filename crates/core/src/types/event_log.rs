@@ -0,0 +1,125 @@
+//! The run log as a bounded ring: the newest events up to the pack's depth, plus a count of
+//! the older ones it let go. Readers keep an absolute cursor so eviction never shifts them.
+
+use std::collections::{VecDeque, vec_deque};
+
+use serde::{Deserialize, Serialize};
+
+use super::LogEvent;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLog {
+    events: VecDeque<LogEvent>,
+    /// Events dropped off the front since the run began.
+    evicted: u64,
+    /// Events kept; comes from the content pack, so saves do not carry it.
+    #[serde(skip)]
+    depth: usize,
+}
+
+impl EventLog {
+    pub fn new(depth: usize) -> Self {
+        Self { events: VecDeque::new(), evicted: 0, depth }
+    }
+
+    /// Keep at most `depth` events from now on, dropping the oldest ones that no longer fit.
+    pub(crate) fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self.trim();
+        self
+    }
+
+    pub(crate) fn push(&mut self, event: LogEvent) {
+        self.events.push_back(event);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        let excess = self.events.len().saturating_sub(self.depth);
+        self.events.drain(..excess);
+        self.evicted += excess as u64;
+    }
+
+    /// Events still held, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEvent> + ExactSizeIterator {
+        self.events.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn contains(&self, event: &LogEvent) -> bool {
+        self.events.contains(event)
+    }
+
+    pub fn last(&self) -> Option<&LogEvent> {
+        self.events.back()
+    }
+
+    /// Events ever logged this run, evicted ones included; a cursor for `since`.
+    pub fn total_logged(&self) -> u64 {
+        self.evicted + self.events.len() as u64
+    }
+
+    /// Events logged at or after `cursor` that are still held; older ones are skipped.
+    pub fn since(&self, cursor: u64) -> impl Iterator<Item = &LogEvent> {
+        let skip = cursor.saturating_sub(self.evicted);
+        self.events.iter().skip(usize::try_from(skip).unwrap_or(usize::MAX))
+    }
+
+    /// The newest `count` events, oldest first.
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &LogEvent> {
+        self.events.iter().skip(self.events.len().saturating_sub(count))
+    }
+
+    /// Bytes the ring has allocated for events, before any text they carry.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        self.events.capacity() * size_of::<LogEvent>()
+    }
+}
+
+impl<'a> IntoIterator for &'a EventLog {
+    type Item = &'a LogEvent;
+    type IntoIter = vec_deque::Iter<'a, LogEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notice(index: u64) -> LogEvent {
+        LogEvent::Notice(index.to_string())
+    }
+
+    #[test]
+    fn the_ring_keeps_the_newest_events_and_cursors_survive_eviction() {
+        let mut log = EventLog::new(3);
+        for index in 0..5 {
+            log.push(notice(index));
+        }
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.total_logged(), 5);
+        assert_eq!(log.iter().cloned().collect::<Vec<_>>(), [notice(2), notice(3), notice(4)]);
+        assert_eq!(log.since(4).cloned().collect::<Vec<_>>(), [notice(4)]);
+        assert_eq!(log.since(0).count(), 3, "evicted events are skipped, not replayed");
+        assert_eq!(log.since(5).count(), 0);
+        assert_eq!(log.recent(2).cloned().collect::<Vec<_>>(), [notice(3), notice(4)]);
+
+        let shrunk = log.with_depth(1);
+        assert_eq!(shrunk.last(), Some(&notice(4)));
+        assert_eq!(shrunk.total_logged(), 5);
+    }
+}
@@ -0,0 +1,26 @@
+//! Encounter previews for prompt UIs and bots: the damage exchange a fight would open with,
+//! worked out from the current weapon, stance, perks and the enemy's stats.
+
+/// The expected exchange between the player and one enemy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FightPreview {
+    /// Damage one player strike deals, exactly as choosing to fight would apply it.
+    pub damage_dealt: i32,
+    /// Damage one enemy blow would deal against the player's current defense.
+    pub damage_taken: i32,
+    /// Player strikes needed to bring the enemy down from its current HP.
+    pub strikes_to_kill: u32,
+}
+
+impl FightPreview {
+    /// Whether choosing to fight now kills the enemy outright.
+    pub fn kills_outright(&self) -> bool {
+        self.strikes_to_kill <= 1
+    }
+
+    /// Damage the player can expect to take before the enemy falls: one blow answers
+    /// each strike that leaves it standing.
+    pub fn expected_damage_taken(&self) -> i32 {
+        self.damage_taken * self.strikes_to_kill.saturating_sub(1) as i32
+    }
+}
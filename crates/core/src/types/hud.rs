@@ -0,0 +1,47 @@
+//! Per-frame HUD summaries: everything a status panel shows, gathered in one read-only
+//! pass so every frontend renders the same values.
+
+use super::{
+    ActorKind, AutoExploreIntent, DangerTag, EntityId, FightMode, GodId, LogEvent, Stance,
+};
+
+/// Visible enemies a `HudSnapshot` lists, nearest first.
+pub const HUD_THREAT_COUNT: usize = 3;
+/// Most recent log events a `HudSnapshot` carries, oldest first.
+pub const HUD_EVENT_COUNT: usize = 5;
+
+/// The policy settings a HUD line summarizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PolicySummary {
+    pub fight_or_avoid: FightMode,
+    pub stance: Stance,
+    pub retreat_hp_threshold: u8,
+    pub auto_heal_if_below_threshold: Option<u8>,
+}
+
+/// One visible enemy, as the HUD ranks it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HudThreat {
+    pub enemy: EntityId,
+    pub kind: ActorKind,
+    pub hp: i32,
+    /// Manhattan distance from the player.
+    pub distance: u32,
+    pub danger_tags: Vec<DangerTag>,
+}
+
+/// What a status panel shows this frame; see `Game::hud_snapshot`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HudSnapshot {
+    pub tick: u64,
+    pub hp: i32,
+    pub max_hp: i32,
+    pub floor_index: u8,
+    pub active_god: Option<GodId>,
+    pub policy: PolicySummary,
+    pub intent: Option<AutoExploreIntent>,
+    /// Up to `HUD_THREAT_COUNT` visible enemies, nearest first.
+    pub threats: Vec<HudThreat>,
+    /// Up to `HUD_EVENT_COUNT` latest log events, oldest first.
+    pub recent_events: Vec<LogEvent>,
+}
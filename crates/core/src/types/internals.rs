@@ -0,0 +1,31 @@
+//! Engine internals exposed for debug overlays; none of this feeds the snapshot hash.
+//! Counters are cumulative so frontends can diff them per frame.
+
+use super::{ChoicePromptId, EntityId};
+
+/// Which auto-explore planning pass produced the current route.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlannerPass {
+    /// The route avoids hazard tiles.
+    Safe,
+    /// No safe route existed, so the route may cross hazards.
+    HazardFallback,
+}
+
+/// Point-in-time view of engine bookkeeping that is otherwise private.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EngineInternals {
+    pub no_progress_ticks: u32,
+    pub suppressed_enemy: Option<EntityId>,
+    pub pending_prompt_id: Option<ChoicePromptId>,
+    /// Pass used by the last auto-explore route search, if any has run.
+    pub planner_pass: Option<PlannerPass>,
+    /// Nodes expanded by the last auto-explore A* search that actually ran.
+    pub last_astar_expansions: u32,
+    /// Field-of-view recomputes since the game was created.
+    pub fov_recomputes: u64,
+    /// Target scans computed rather than reused since the game was created.
+    pub target_scans: u64,
+    /// Auto-explore routes reused from the path cache instead of searched again.
+    pub path_cache_hits: u64,
+}
@@ -0,0 +1,118 @@
+//! Structured run log events. Frontends compose their own wording from these; the sim
+//! only records what happened.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    ActorKind, AutoReason, ContentKey, DamageSource, EntityId, FloorObjective, GodBoon, GodId,
+    ItemKind, Pos, PromptKind, RoomId, VaultTemplate,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LogEvent {
+    AutoReasonChanged {
+        reason: AutoReason,
+        target: Pos,
+        path_len: u16,
+    },
+    EnemyEncountered {
+        enemy: EntityId,
+    },
+    /// The player arrived on `floor_index`, announced by its cosmetic name.
+    FloorEntered {
+        floor_index: u8,
+        name: String,
+    },
+    /// A loot or door prompt the player snoozed for `ticks` ticks.
+    PromptSnoozed {
+        prompt: PromptKind,
+        ticks: u32,
+    },
+    /// The player's HP reached zero; `killer` is the enemy kind behind the final blow.
+    PlayerSlain {
+        killer: Option<ActorKind>,
+        source: DamageSource,
+    },
+    /// A trivial encounter the policy fought without a prompt.
+    EncounterAutoFought {
+        kind: ActorKind,
+        damage: i32,
+    },
+    ItemPickedUp {
+        kind: ItemKind,
+    },
+    ItemDiscarded {
+        kind: ItemKind,
+    },
+    EncounterResolved {
+        enemy: EntityId,
+        fought: bool,
+    },
+    /// The player's strike killed an enemy of `kind`.
+    EnemySlain {
+        kind: ActorKind,
+    },
+    PlayerSpotted {
+        enemy: EntityId,
+    },
+    ConsumableIdentified {
+        #[serde(deserialize_with = "crate::content::static_keys::key")]
+        id: ContentKey,
+    },
+    /// A consumable the player used from the inventory.
+    ConsumableUsed {
+        #[serde(deserialize_with = "crate::content::static_keys::key")]
+        id: ContentKey,
+    },
+    /// A stowed healing potion the auto-heal policy drank.
+    PotionDrunk {
+        #[serde(deserialize_with = "crate::content::static_keys::key")]
+        id: ContentKey,
+    },
+    PactBroken {
+        from: GodId,
+        to: GodId,
+    },
+    BoonGranted {
+        boon: GodBoon,
+    },
+    Counterattack {
+        enemy: EntityId,
+        damage: i32,
+    },
+    /// An alert enemy beside the player struck it on its own turn.
+    EnemyStruck {
+        enemy: EntityId,
+        damage: i32,
+    },
+    RoomEntered {
+        room: RoomId,
+    },
+    /// The room just entered is a vault; follows its `RoomEntered`.
+    VaultEntered {
+        template: VaultTemplate,
+        room: RoomId,
+    },
+    DoorOpened {
+        pos: Pos,
+    },
+    ObjectiveCompleted {
+        objective: FloorObjective,
+    },
+    LeveledUp {
+        level: u8,
+    },
+    PerkDrafted {
+        #[serde(deserialize_with = "crate::content::static_keys::key")]
+        id: ContentKey,
+    },
+    CurseBound {
+        kind: ItemKind,
+    },
+    CursesCleansed,
+    RecoveryHint {
+        seed: u64,
+        hash_hex: String,
+    },
+    Notice(String),
+}
@@ -0,0 +1,32 @@
+//! Approximate memory use of one game, split by subsystem, for sizing batch simulations and
+//! long runs. Counts what the large collections have allocated, not allocator overhead.
+
+/// Bytes each part of a game holds; see `Game::memory_footprint`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryFootprint {
+    /// The `Game` value itself, before anything it points to.
+    pub inline: usize,
+    /// Per-tile layers, markers and vaults of the current floor.
+    pub map: usize,
+    /// Actor and item storage on the current floor.
+    pub entities: usize,
+    /// Floors the player has left, kept whole for revisits.
+    pub stored_floors: usize,
+    pub threat_trace: usize,
+    /// The run log, including the text some events carry.
+    pub log: usize,
+    /// Cached routes, the last target scan and enemy memory; all rebuilt on demand.
+    pub caches: usize,
+}
+
+impl MemoryFootprint {
+    pub fn total(&self) -> usize {
+        self.inline
+            + self.map
+            + self.entities
+            + self.stored_floors
+            + self.threat_trace
+            + self.log
+            + self.caches
+    }
+}
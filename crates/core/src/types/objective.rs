@@ -0,0 +1,39 @@
+//! Floor objectives that keep a floor's down stairs locked until the player completes them.
+//! Which floors carry an objective is content; the engine tracks the open one and the record.
+
+use serde::{Deserialize, Serialize};
+
+use super::{EntityId, ItemId};
+
+/// What the player must do on a designated floor before its down stairs unlock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FloorObjective {
+    /// Kill the floor's elite, its toughest enemy.
+    SlayElite,
+    /// Find the sigil placed far from the floor entry.
+    FindSigil,
+}
+
+/// The open objective on the current floor, pointing at what completes it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActiveObjective {
+    SlayElite(EntityId),
+    FindSigil(ItemId),
+}
+
+impl ActiveObjective {
+    pub fn objective(self) -> FloorObjective {
+        match self {
+            Self::SlayElite(_) => FloorObjective::SlayElite,
+            Self::FindSigil(_) => FloorObjective::FindSigil,
+        }
+    }
+}
+
+/// A completed floor objective, kept for the run recap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectiveRecord {
+    pub floor_index: u8,
+    pub objective: FloorObjective,
+    pub completed_tick: u64,
+}
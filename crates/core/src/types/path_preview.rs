@@ -0,0 +1,37 @@
+//! Planner route previews for inspect-style UI: the route auto-explore would take between two
+//! tiles, with what each step cost the planner and what danger lies along it.
+
+use super::{EntityId, MarkerKind, PlannerPass, Pos};
+
+/// One tile of a previewed route.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathStep {
+    pub pos: Pos,
+    /// What the planner paid to enter the tile: one per step plus any marker penalty.
+    pub cost: u32,
+    pub marker: Option<MarkerKind>,
+    pub hazard: bool,
+    /// Visible enemies close enough to threaten the tile, in id order.
+    pub nearby_enemies: Vec<EntityId>,
+    /// Danger score for the tile from hazards and nearby enemies; informational only, the
+    /// planner routes on `cost`.
+    pub danger: u32,
+}
+
+/// The planner's route between two tiles under one planner pass.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathPreview {
+    pub pass: PlannerPass,
+    /// Steps after the start tile, ending on the goal.
+    pub steps: Vec<PathStep>,
+}
+
+impl PathPreview {
+    pub fn total_cost(&self) -> u32 {
+        self.steps.iter().map(|step| step.cost).sum()
+    }
+
+    pub fn total_danger(&self) -> u32 {
+        self.steps.iter().map(|step| step.danger).sum()
+    }
+}
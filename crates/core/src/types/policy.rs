@@ -0,0 +1,124 @@
+//! Autopilot policy: the standing orders the engine follows between prompts, and the
+//! journaled updates that change them mid-run.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FightMode {
+    Fight,
+    Avoid,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stance {
+    Aggressive,
+    Balanced,
+    Defensive,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetTag {
+    Nearest,
+    LowestHp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PositionIntent {
+    HoldGround,
+    AdvanceToMelee,
+    FleeToNearestExploredTile,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Aggro {
+    /// Pick up loot found along the way without detouring for it.
+    Conserve,
+    /// Go after any visible loot before exploring further.
+    Greedy,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExploreMode {
+    Thorough,
+    /// Head for known down stairs by the fewest ticks, detouring only for needed healing.
+    Rush,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Policy {
+    pub fight_or_avoid: FightMode,
+    pub stance: Stance,
+    pub target_priority: Vec<TargetTag>,
+    pub retreat_hp_threshold: u8,
+    pub auto_heal_if_below_threshold: Option<u8>,
+    pub position_intent: PositionIntent,
+    pub resource_aggression: Aggro,
+    pub exploration_mode: ExploreMode,
+    /// Ordered auto-pickup rules; the first match resolves loot without a prompt.
+    #[serde(default)]
+    pub auto_pickup: Vec<AutoPickupRule>,
+    /// Fight a lone adjacent enemy without a prompt when its fight preview shows no
+    /// expected damage taken.
+    #[serde(default)]
+    pub auto_fight_trivial: bool,
+    /// Settle routine encounters by `fight_or_avoid` without a prompt. Packs, uniques and
+    /// encounters at or below the retreat threshold still prompt.
+    #[serde(default)]
+    pub auto_resolve_encounters: bool,
+}
+
+/// Loot a pickup rule applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PickupFilter {
+    /// A single content key, such as `weapon_rusty_sword`.
+    Item(String),
+    Potions,
+    Consumables,
+    Weapons,
+    Perks,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PickupAction {
+    Keep,
+    Discard,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoPickupRule {
+    pub filter: PickupFilter,
+    pub action: PickupAction,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            fight_or_avoid: FightMode::Fight,
+            stance: Stance::Balanced,
+            target_priority: vec![TargetTag::Nearest, TargetTag::LowestHp],
+            retreat_hp_threshold: 35,
+            auto_heal_if_below_threshold: None,
+            position_intent: PositionIntent::HoldGround,
+            resource_aggression: Aggro::Conserve,
+            exploration_mode: ExploreMode::Thorough,
+            auto_pickup: Vec::new(),
+            auto_fight_trivial: false,
+            auto_resolve_encounters: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolicyUpdate {
+    FightMode(FightMode),
+    Stance(Stance),
+    TargetPriority(Vec<TargetTag>),
+    RetreatHpThreshold(u8),
+    AutoHealIfBelowThreshold(Option<u8>),
+    PositionIntent(PositionIntent),
+    ResourceAggression(Aggro),
+    ExplorationMode(ExploreMode),
+    AutoPickup(Vec<AutoPickupRule>),
+    AutoFightTrivial(bool),
+    AutoResolveEncounters(bool),
+}
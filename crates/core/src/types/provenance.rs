@@ -0,0 +1,43 @@
+//! Floor provenance: which generator built the current floor, at which version, from
+//! which floor seed. Enough to rebuild an odd layout from a recap screenshot.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MapgenAlgorithm {
+    /// The fixed first floor every run starts on.
+    StarterFloor,
+    /// Procedural rooms joined by corridors, then vault-stamped.
+    RoomsAndCorridors,
+    /// A hand-authored scenario map.
+    Scenario,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FloorProvenance {
+    pub algorithm: MapgenAlgorithm,
+    /// Generator version; authored floors have none and report 0.
+    pub version: u32,
+    /// Seed the generator derived for this floor; 0 for authored floors.
+    pub floor_seed: u64,
+}
+
+impl FloorProvenance {
+    /// Provenance for a floor no generator built.
+    pub fn authored(algorithm: MapgenAlgorithm) -> Self {
+        Self { algorithm, version: 0, floor_seed: 0 }
+    }
+}
+
+impl fmt::Display for FloorProvenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.algorithm {
+            MapgenAlgorithm::StarterFloor => "starter-floor",
+            MapgenAlgorithm::RoomsAndCorridors => "rooms-and-corridors",
+            MapgenAlgorithm::Scenario => "scenario",
+        };
+        write!(f, "{name} v{} floor seed 0x{:016x}", self.version, self.floor_seed)
+    }
+}
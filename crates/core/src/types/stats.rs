@@ -0,0 +1,72 @@
+//! Run statistics the engine accumulates for post-run summaries; none of this feeds the
+//! snapshot hash. Movement is counted per floor so frontends can draw heatmaps.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::Pos;
+
+/// Times the player entered each tile of one floor, row-major like `Map::tiles`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloorVisits {
+    pub width: usize,
+    pub height: usize,
+    pub counts: Vec<u32>,
+}
+
+impl FloorVisits {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, counts: vec![0; width * height] }
+    }
+
+    pub fn count_at(&self, pos: Pos) -> u32 {
+        self.index(pos).map_or(0, |index| self.counts[index])
+    }
+
+    pub fn max_count(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Every tile entry on this floor, the starting tile included.
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Entries into tiles already visited: steps a route without backtracking would save.
+    pub fn revisits(&self) -> u32 {
+        self.counts.iter().map(|count| count.saturating_sub(1)).sum()
+    }
+
+    fn index(&self, pos: Pos) -> Option<usize> {
+        pos.grid_index(self.width, self.height)
+    }
+
+    pub(crate) fn record(&mut self, pos: Pos) {
+        if let Some(index) = self.index(pos) {
+            self.counts[index] += 1;
+        }
+    }
+}
+
+/// The snapshot hash taken as the player arrives on a floor, so re-simulations can be
+/// compared floor by floor rather than only at the end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FloorCheckpoint {
+    pub floor_index: u8,
+    pub tick: u64,
+    pub snapshot_hash: u64,
+}
+
+/// Statistics accumulated over the whole run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStats {
+    /// Player movement keyed by floor index; a revisited floor keeps adding to its counts.
+    pub floor_visits: BTreeMap<u8, FloorVisits>,
+    /// Choices, policy updates and other journaled inputs the game accepted.
+    pub inputs_accepted: u64,
+    /// One checkpoint per descent, in the order the floors were entered.
+    pub floor_checkpoints: Vec<FloorCheckpoint>,
+    /// Floor and tile counted last, so standing still is not counted again.
+    pub(crate) last_visit: Option<(u8, Pos)>,
+}
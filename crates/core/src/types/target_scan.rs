@@ -0,0 +1,37 @@
+//! Batched target scans for planners: the cost to every reachable frontier, item, down stairs
+//! and visible enemy, found in one pass from a single origin instead of one search per target.
+
+use super::{EntityId, ItemId, Pos};
+
+/// One reachable target tile and what the planner would pay to walk there.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScanHit {
+    pub pos: Pos,
+    /// One per step plus any marker penalty, as the auto-explore route search counts it.
+    pub cost: u32,
+}
+
+/// Every target reachable from `origin` without crossing a hazard. Each list is cheapest
+/// first, with ties broken row-major and then by id.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetScan {
+    pub origin: Pos,
+    pub frontiers: Vec<ScanHit>,
+    pub items: Vec<(ItemId, ScanHit)>,
+    pub stairs: Vec<ScanHit>,
+    pub enemies: Vec<(EntityId, ScanHit)>,
+}
+
+impl TargetScan {
+    pub fn nearest_frontiers(&self, count: usize) -> &[ScanHit] {
+        &self.frontiers[..count.min(self.frontiers.len())]
+    }
+
+    pub fn nearest_items(&self, count: usize) -> &[(ItemId, ScanHit)] {
+        &self.items[..count.min(self.items.len())]
+    }
+
+    pub fn nearest_enemies(&self, count: usize) -> &[(EntityId, ScanHit)] {
+        &self.enemies[..count.min(self.enemies.len())]
+    }
+}
@@ -0,0 +1,13 @@
+//! Unique enemies: bosses and objective elites a run meets at most once.
+//! Once slain, a unique goes into the run's ledger and never spawns again.
+
+use super::ActorKind;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum UniqueEnemy {
+    /// A boss kind; a run holds at most one of each.
+    Boss(ActorKind),
+    /// The elite a slay-elite objective marked on this floor.
+    FloorElite(u8),
+}
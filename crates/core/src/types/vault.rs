@@ -0,0 +1,35 @@
+//! Vaults: generated rooms stamped with a template that reshapes their contents.
+//! Mapgen reports each stamp so the game and UI can react when one is entered.
+
+use super::{Pos, RoomId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum VaultTemplate {
+    /// An extra item waits at the room's centre.
+    ShrineRoom,
+    /// Enemies gather around a hazard at the room's centre.
+    GoblinCamp,
+    /// The room's centre is walled off into a pillar.
+    PillarRoom,
+}
+
+/// One vault on a floor: its template, the room it took over, and that room's rect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultStamp {
+    pub template: VaultTemplate,
+    pub room: RoomId,
+    /// Top-left tile of the room rect.
+    pub origin: Pos,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl VaultStamp {
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x >= self.origin.x
+            && pos.y >= self.origin.y
+            && ((pos.x - self.origin.x) as usize) < self.width
+            && ((pos.y - self.origin.y) as usize) < self.height
+    }
+}
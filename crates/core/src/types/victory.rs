@@ -0,0 +1,20 @@
+//! Victory categories, so leaderboards can rank full clears, speed clears and boss skips
+//! apart. The category is settled once, as the player leaves the final floor.
+
+use serde::{Deserialize, Serialize};
+
+/// How a won run was won.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VictoryKind {
+    /// The final boss is slain and every floor was left with nothing reachable unexplored.
+    FullClear,
+    /// The final boss is slain, but some floor was left partly unexplored.
+    SpeedClear,
+    /// The player left the final floor with its boss still alive.
+    BossSkip,
+}
+
+impl VictoryKind {
+    /// Every category, in declaration order.
+    pub const ALL: [Self; 3] = [Self::FullClear, Self::SpeedClear, Self::BossSkip];
+}
@@ -0,0 +1,101 @@
+use core::replay::replay_journal_inputs;
+use core::{
+    AdvanceStopReason, BoonKind, Choice, ContentPack, Game, GameMode, InputJournal, SNOOZE_TICKS,
+};
+use proptest::{
+    arbitrary::any,
+    collection::vec,
+    sample::Index,
+    test_runner::{Config as ProptestConfig, TestCaseError, TestRunner},
+};
+
+const ALL_CHOICES: [Choice; 24] = [
+    Choice::KeepLoot,
+    Choice::DiscardLoot,
+    Choice::Fight,
+    Choice::Avoid,
+    Choice::OpenDoor,
+    Choice::Descend,
+    Choice::DescendBranchA,
+    Choice::DescendBranchB,
+    Choice::DescendBranchAVeil,
+    Choice::DescendBranchAForge,
+    Choice::DescendBranchBVeil,
+    Choice::DescendBranchBForge,
+    Choice::BreakPact,
+    Choice::KeepPact,
+    Choice::CleanseCurses,
+    Choice::SeekObjective,
+    Choice::DraftPerk(0),
+    Choice::DescendWithBoon(BoonKind::KeenSight),
+    Choice::DescendWithBoon(BoonKind::BlinkCharge),
+    Choice::DescendWithBoon(BoonKind::Bulwark),
+    Choice::DescendWithBoon(BoonKind::Vigor),
+    Choice::UseItem(0),
+    Choice::DropItem(0),
+    Choice::IgnoreFor { ticks: SNOOZE_TICKS },
+];
+
+/// Play `seed` picking each answer from `available_choices()`, checking along the way that
+/// exactly the advertised choices are accepted, then replay the recorded journal.
+fn play_and_replay(seed: u64, picks: &[Index]) -> Result<(), TestCaseError> {
+    let content = ContentPack::default();
+    let mut game = Game::new(seed, &content, GameMode::Ironman);
+    let mut journal = InputJournal::new(seed);
+    let mut hash_after_last_choice = game.snapshot_hash();
+    let mut picks = picks.iter();
+
+    for _ in 0..256 {
+        let result = game.advance(100);
+        let interrupt = match result.stop_reason {
+            AdvanceStopReason::Interrupted(interrupt) => interrupt,
+            AdvanceStopReason::Finished(_) => break,
+            AdvanceStopReason::EngineFailure(reason) => {
+                return Err(TestCaseError::fail(format!("engine failure: {reason:?}")));
+            }
+            AdvanceStopReason::PausedAtBoundary { .. } | AdvanceStopReason::BudgetExhausted => {
+                continue;
+            }
+        };
+        let Some(pick) = picks.next() else {
+            break;
+        };
+
+        let available = game.available_choices();
+        if available.is_empty() {
+            return Err(TestCaseError::fail(format!("{interrupt:?} offered no choices")));
+        }
+        let prompt_id = interrupt.prompt_id();
+        for choice in ALL_CHOICES {
+            let accepted = game.clone().apply_choice(prompt_id, choice.clone()).is_ok();
+            if accepted != available.contains(&choice) {
+                return Err(TestCaseError::fail(format!(
+                    "{choice:?} at {interrupt:?}: accepted {accepted}, advertised {available:?}"
+                )));
+            }
+        }
+
+        let choice = pick.get(&available).clone();
+        journal.append_choice(prompt_id, choice.clone(), journal.inputs.len() as u64);
+        game.apply_choice(prompt_id, choice)
+            .map_err(|e| TestCaseError::fail(format!("advertised choice rejected: {e}")))?;
+        hash_after_last_choice = game.snapshot_hash();
+    }
+
+    let replayed = replay_journal_inputs(&content, &journal)
+        .map_err(|e| TestCaseError::fail(format!("replay failed: {e}")))?;
+    if replayed.snapshot_hash() != hash_after_last_choice {
+        return Err(TestCaseError::fail("replayed journal reached a different state"));
+    }
+    Ok(())
+}
+
+#[test]
+fn legal_choice_sequences_never_error_and_replay_to_the_same_hash() {
+    let mut runner = TestRunner::new(ProptestConfig::with_cases(24));
+    let strategy = (any::<u64>(), vec(any::<Index>(), 1..48));
+
+    runner
+        .run(&strategy, |(seed, picks)| play_and_replay(seed, &picks))
+        .expect("legal choices should be accepted and replay to the same state");
+}
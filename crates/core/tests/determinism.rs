@@ -1,11 +1,20 @@
 use core::ContentPack;
 use core::journal::InputJournal;
-use core::replay::replay_to_end;
+use core::replay::{ReplayError, replay_to_end, replay_to_end_verified};
 use core::{AdvanceStopReason, Choice, Game, GameMode, Interrupt};
 
 fn build_scripted_journal(seed: u64, content: &ContentPack) -> InputJournal {
+    build_scripted_journal_with_director(seed, content, false)
+}
+
+fn build_scripted_journal_with_director(
+    seed: u64,
+    content: &ContentPack,
+    director: bool,
+) -> InputJournal {
     let mut game = Game::new(seed, content, GameMode::Ironman);
-    let mut journal = InputJournal::new(seed);
+    game.set_director_enabled(director);
+    let mut journal = InputJournal { director, ..InputJournal::new(seed) };
     let mut seq = 0u64;
 
     loop {
@@ -17,6 +26,9 @@ fn build_scripted_journal(seed: u64, content: &ContentPack) -> InputJournal {
                     Interrupt::LootFound { prompt_id, .. } => (prompt_id, Choice::KeepLoot),
                     Interrupt::EnemyEncounter { prompt_id, .. } => (prompt_id, Choice::Fight),
                     Interrupt::DoorBlocked { prompt_id, .. } => (prompt_id, Choice::OpenDoor),
+                    Interrupt::StairsLocked { prompt_id, .. } => (prompt_id, Choice::SeekObjective),
+                    Interrupt::PactAltar { prompt_id, .. } => (prompt_id, Choice::KeepPact),
+                    Interrupt::PerkDraft { prompt_id, .. } => (prompt_id, Choice::DraftPerk(0)),
                     Interrupt::FloorTransition {
                         prompt_id, requires_branch_god_choice, ..
                     } => {
@@ -72,6 +84,36 @@ fn test_determinism_different_seeds_produce_different_hashes() {
     );
 }
 
+#[test]
+fn test_verified_replay_stops_at_the_first_prompt_whose_full_state_differs() {
+    let content = ContentPack::default();
+    let journal = build_scripted_journal(2755, &content);
+    let recorded = replay_to_end(&content, &journal).expect("Replay failed");
+    assert_eq!(recorded.prompt_state_hashes.len(), journal.inputs.len());
+
+    let verified = replay_to_end_verified(&content, &journal, &recorded.prompt_state_hashes);
+    assert_eq!(verified.expect("recorded hashes verify"), recorded);
+
+    let mut tampered = recorded.prompt_state_hashes;
+    tampered[1] ^= 1;
+    let error = replay_to_end_verified(&content, &journal, &tampered).unwrap_err();
+    assert!(matches!(error, ReplayError::StateDivergence { prompt: 1, .. }), "{error}");
+}
+
+#[test]
+fn test_director_runs_replay_identically_from_the_journal_flag() {
+    let content = ContentPack::default();
+    let journal = build_scripted_journal_with_director(2716, &content, true);
+    let plain = build_scripted_journal(2716, &content);
+
+    let first = replay_to_end(&content, &journal).expect("Director replay failed");
+    let second = replay_to_end(&content, &journal).expect("Director replay failed");
+    let without = replay_to_end(&content, &plain).expect("Plain replay failed");
+
+    assert_eq!(first, second, "the director must not add nondeterminism");
+    assert_ne!(first.final_snapshot_hash, without.final_snapshot_hash);
+}
+
 #[test]
 fn test_deterministic_smoke_fixed_seed_stable_intent_and_log_sequence() {
     let content = ContentPack::default();
@@ -79,7 +121,7 @@ fn test_deterministic_smoke_fixed_seed_stable_intent_and_log_sequence() {
     fn run_trace(seed: u64, content: &ContentPack) -> Vec<String> {
         let mut game = Game::new(seed, content, GameMode::Ironman);
         let mut trace = Vec::new();
-        let mut seen_logs = 0usize;
+        let mut seen_logs = 0u64;
 
         while game.current_tick() < 40 {
             let result = game.advance(1);
@@ -110,14 +152,19 @@ fn test_deterministic_smoke_fixed_seed_stable_intent_and_log_sequence() {
                     game.apply_choice(prompt_id, choice).expect("descend choice should apply");
                     trace.push("descend".to_string());
                 }
+                AdvanceStopReason::Interrupted(Interrupt::PerkDraft { prompt_id, .. }) => {
+                    game.apply_choice(prompt_id, Choice::DraftPerk(0))
+                        .expect("draft choice should apply");
+                    trace.push("draft".to_string());
+                }
                 _ => {}
             }
 
             let logs = game.log();
-            for event in &logs[seen_logs..] {
+            for event in logs.since(seen_logs) {
                 trace.push(format!("{event:?}"));
             }
-            seen_logs = logs.len();
+            seen_logs = logs.total_logged();
         }
 
         trace
@@ -135,7 +182,7 @@ fn test_starter_layout_auto_run_hits_door_and_threat_avoidance_within_250_ticks(
 
     let mut saw_door_blocked = false;
     let mut saw_threat_avoidance = false;
-    let mut seen_logs = 0usize;
+    let mut seen_logs = 0u64;
 
     while game.current_tick() <= 250 && !(saw_door_blocked && saw_threat_avoidance) {
         let result = game.advance(1);
@@ -166,11 +213,15 @@ fn test_starter_layout_auto_run_hits_door_and_threat_avoidance_within_250_ticks(
                 game.apply_choice(prompt_id, choice)
                     .expect("descend should apply during smoke run");
             }
+            AdvanceStopReason::Interrupted(Interrupt::PerkDraft { prompt_id, .. }) => {
+                game.apply_choice(prompt_id, Choice::DraftPerk(0))
+                    .expect("draft choice should apply during smoke run");
+            }
             _ => {}
         }
 
         let logs = game.log();
-        for event in &logs[seen_logs..] {
+        for event in logs.since(seen_logs) {
             if matches!(
                 event,
                 core::LogEvent::AutoReasonChanged { reason: core::AutoReason::ThreatAvoidance, .. }
@@ -178,7 +229,7 @@ fn test_starter_layout_auto_run_hits_door_and_threat_avoidance_within_250_ticks(
                 saw_threat_avoidance = true;
             }
         }
-        seen_logs = logs.len();
+        seen_logs = logs.total_logged();
     }
 
     assert!(saw_door_blocked, "expected DoorBlocked interrupt within 250 ticks");
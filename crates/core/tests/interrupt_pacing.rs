@@ -22,6 +22,9 @@ fn scripted_interrupt_count(seed: u64) -> Result<u32, String> {
                     Interrupt::LootFound { prompt_id, .. } => (prompt_id, Choice::KeepLoot),
                     Interrupt::EnemyEncounter { prompt_id, .. } => (prompt_id, Choice::Fight),
                     Interrupt::DoorBlocked { prompt_id, .. } => (prompt_id, Choice::OpenDoor),
+                    Interrupt::StairsLocked { prompt_id, .. } => (prompt_id, Choice::SeekObjective),
+                    Interrupt::PactAltar { prompt_id, .. } => (prompt_id, Choice::KeepPact),
+                    Interrupt::PerkDraft { prompt_id, .. } => (prompt_id, Choice::DraftPerk(0)),
                     Interrupt::FloorTransition {
                         prompt_id, requires_branch_god_choice, ..
                     } => {
@@ -36,7 +39,7 @@ fn scripted_interrupt_count(seed: u64) -> Result<u32, String> {
                 game.apply_choice(prompt_id, choice).map_err(|err| format!("{err:?}"))?;
             }
             AdvanceStopReason::PausedAtBoundary { .. } | AdvanceStopReason::BudgetExhausted => {}
-            AdvanceStopReason::EngineFailure(EngineFailureReason::StalledNoProgress) => {
+            AdvanceStopReason::EngineFailure(EngineFailureReason::StalledNoProgress(_)) => {
                 return Err(format!("stalled on seed={seed}"));
             }
         }
@@ -2,11 +2,28 @@ use std::fs;
 
 use core::ContentPack;
 use core::journal::InputPayload;
-use core::replay::{replay_journal_inputs, replay_to_end};
+use core::replay::{ReplayError, replay_journal_inputs, replay_to_end};
 use core::{
-    AdvanceStopReason, Choice, Game, GameMode, Interrupt, JournalWriter, load_journal_from_file,
+    ActorKind, AdvanceStopReason, Choice, Difficulty, Game, GameError, GameMode, GodId,
+    InputJournal, Interrupt, JournalWriter, MarkerKind, Pos, load_journal_from_file,
 };
 
+/// The choice the scripted journal runs make for `interrupt`.
+fn scripted_choice(interrupt: &Interrupt) -> Choice {
+    match interrupt {
+        Interrupt::LootFound { .. } => Choice::KeepLoot,
+        Interrupt::EnemyEncounter { .. } => Choice::Fight,
+        Interrupt::DoorBlocked { .. } => Choice::OpenDoor,
+        Interrupt::StairsLocked { .. } => Choice::SeekObjective,
+        Interrupt::PactAltar { .. } => Choice::KeepPact,
+        Interrupt::PerkDraft { .. } => Choice::DraftPerk(0),
+        Interrupt::FloorTransition { requires_branch_god_choice: true, .. } => {
+            Choice::DescendBranchAVeil
+        }
+        Interrupt::FloorTransition { .. } => Choice::Descend,
+    }
+}
+
 /// Play a full game recording inputs to a JSONL file, then load the file
 /// and replay to completion. The snapshot hash must match.
 #[test]
@@ -29,21 +46,7 @@ fn test_file_journal_replay_equivalence() {
                 break;
             }
             AdvanceStopReason::Interrupted(ref interrupt) => {
-                let (prompt_id, choice) = match interrupt {
-                    Interrupt::LootFound { prompt_id, .. } => (*prompt_id, Choice::KeepLoot),
-                    Interrupt::EnemyEncounter { prompt_id, .. } => (*prompt_id, Choice::Fight),
-                    Interrupt::DoorBlocked { prompt_id, .. } => (*prompt_id, Choice::OpenDoor),
-                    Interrupt::FloorTransition {
-                        prompt_id, requires_branch_god_choice, ..
-                    } => {
-                        let c = if *requires_branch_god_choice {
-                            Choice::DescendBranchAVeil
-                        } else {
-                            Choice::Descend
-                        };
-                        (*prompt_id, c)
-                    }
-                };
+                let (prompt_id, choice) = (interrupt.prompt_id(), scripted_choice(interrupt));
                 writer
                     .append(
                         game.current_tick(),
@@ -88,21 +91,7 @@ fn test_file_journal_corruption_stops_at_bad_line() {
         match result.stop_reason {
             AdvanceStopReason::Finished(_) => break,
             AdvanceStopReason::Interrupted(ref interrupt) => {
-                let (prompt_id, choice) = match interrupt {
-                    Interrupt::LootFound { prompt_id, .. } => (*prompt_id, Choice::KeepLoot),
-                    Interrupt::EnemyEncounter { prompt_id, .. } => (*prompt_id, Choice::Fight),
-                    Interrupt::DoorBlocked { prompt_id, .. } => (*prompt_id, Choice::OpenDoor),
-                    Interrupt::FloorTransition {
-                        prompt_id, requires_branch_god_choice, ..
-                    } => {
-                        let c = if *requires_branch_god_choice {
-                            Choice::DescendBranchAVeil
-                        } else {
-                            Choice::Descend
-                        };
-                        (*prompt_id, c)
-                    }
-                };
+                let (prompt_id, choice) = (interrupt.prompt_id(), scripted_choice(interrupt));
                 writer
                     .append(
                         game.current_tick(),
@@ -152,21 +141,7 @@ fn test_replay_journal_inputs_reconstructs_game_state() {
         match result.stop_reason {
             AdvanceStopReason::Finished(_) => break,
             AdvanceStopReason::Interrupted(ref interrupt) => {
-                let (prompt_id, choice) = match interrupt {
-                    Interrupt::LootFound { prompt_id, .. } => (*prompt_id, Choice::KeepLoot),
-                    Interrupt::EnemyEncounter { prompt_id, .. } => (*prompt_id, Choice::Fight),
-                    Interrupt::DoorBlocked { prompt_id, .. } => (*prompt_id, Choice::OpenDoor),
-                    Interrupt::FloorTransition {
-                        prompt_id, requires_branch_god_choice, ..
-                    } => {
-                        let c = if *requires_branch_god_choice {
-                            Choice::DescendBranchAVeil
-                        } else {
-                            Choice::Descend
-                        };
-                        (*prompt_id, c)
-                    }
-                };
+                let (prompt_id, choice) = (interrupt.prompt_id(), scripted_choice(interrupt));
                 writer
                     .append(
                         game.current_tick(),
@@ -196,3 +171,107 @@ fn test_replay_journal_inputs_reconstructs_game_state() {
         "reconstructed game should have the same hash as the original at that point"
     );
 }
+
+/// Difficulty chosen at run start is stored in the journal header and
+/// restored on replay, so the reconstructed run uses the same scaling.
+#[test]
+fn test_journal_records_and_replays_difficulty() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal_path = dir.path().join("difficulty.jsonl");
+    let content = ContentPack::default();
+
+    let writer =
+        JournalWriter::create_with_difficulty(&journal_path, 9, Difficulty::Brutal, "test", 0)
+            .unwrap();
+    drop(writer);
+
+    let loaded = load_journal_from_file(&journal_path).unwrap();
+    assert_eq!(loaded.journal.difficulty, Difficulty::Brutal);
+
+    let replayed = replay_journal_inputs(&content, &loaded.journal).unwrap();
+    let normal = Game::new(9, &content, GameMode::Ironman);
+    assert_eq!(replayed.difficulty(), Difficulty::Brutal);
+    assert_ne!(replayed.snapshot_hash(), normal.snapshot_hash());
+
+    let goblin_hp = |game: &Game| {
+        game.state().actors.values().find(|actor| actor.kind == ActorKind::Goblin).unwrap().max_hp
+    };
+    assert!(goblin_hp(&replayed) > goblin_hp(&normal), "brutal enemies should be tougher");
+}
+
+#[test]
+fn test_marker_inputs_replay_into_map_state() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal_path = dir.path().join("markers.jsonl");
+    let content = ContentPack::default();
+    let seed = 31337u64;
+
+    let mut game = Game::new(seed, &content, GameMode::Ironman);
+    let start = game.state().actors[game.state().player_id].pos;
+    game.set_marker(start, Some(MarkerKind::Avoid)).unwrap();
+    let mut writer = JournalWriter::create(&journal_path, seed, "test", 0).unwrap();
+    let payload =
+        InputPayload::SetMarker { tick_boundary: 0, pos: start, marker: Some(MarkerKind::Avoid) };
+    writer.append(0, &payload).unwrap();
+
+    let loaded = load_journal_from_file(&journal_path).unwrap();
+    let replayed = replay_journal_inputs(&content, &loaded.journal).unwrap();
+    assert_eq!(replayed.state().map.marker_at(start), Some(MarkerKind::Avoid));
+}
+
+#[test]
+fn test_rejected_inputs_report_why_the_game_refused_them() {
+    let mut journal = InputJournal::new(31337);
+    let pos = Pos { y: -1, x: -1 };
+    journal.append_set_marker(0, pos, Some(MarkerKind::Avoid), 0);
+
+    let error = replay_journal_inputs(&ContentPack::default(), &journal).err();
+    assert_eq!(error, Some(ReplayError::InputRejected(GameError::UndiscoveredTile { pos })));
+}
+
+/// Breaking a pact at an altar is recorded like any other choice, and the replay
+/// reconstructs the swapped god, the max HP cost, and the curse.
+#[test]
+fn test_broken_pact_replays_from_file_journal() {
+    let dir = tempfile::tempdir().unwrap();
+    let journal_path = dir.path().join("pact.jsonl");
+    let content = ContentPack::default();
+    let seed = 4242u64;
+
+    let mut game = Game::new(seed, &content, GameMode::Ironman);
+    let mut writer = JournalWriter::create(&journal_path, seed, "test", 0).unwrap();
+    for _ in 0..512 {
+        let AdvanceStopReason::Interrupted(interrupt) = game.advance(100).stop_reason else {
+            continue;
+        };
+        let choice = match interrupt {
+            Interrupt::LootFound { .. } => Choice::KeepLoot,
+            Interrupt::EnemyEncounter { .. } => Choice::Fight,
+            Interrupt::DoorBlocked { .. } => Choice::OpenDoor,
+            Interrupt::StairsLocked { .. } => Choice::SeekObjective,
+            Interrupt::PactAltar { .. } => Choice::BreakPact,
+            Interrupt::PerkDraft { .. } => Choice::DraftPerk(0),
+            Interrupt::FloorTransition { requires_branch_god_choice: true, .. } => {
+                Choice::DescendBranchAForge
+            }
+            Interrupt::FloorTransition { .. } => Choice::Descend,
+        };
+        let prompt_id = interrupt.prompt_id();
+        let payload = InputPayload::Choice { prompt_id, choice: choice.clone() };
+        writer.append(game.current_tick(), &payload).unwrap();
+        game.apply_choice(prompt_id, choice.clone()).unwrap();
+        if choice == Choice::BreakPact {
+            break;
+        }
+    }
+    drop(writer);
+    assert!(game.state().pact_broken, "the run should reach a pact altar");
+
+    let loaded = load_journal_from_file(&journal_path).unwrap();
+    let replayed = replay_journal_inputs(&content, &loaded.journal).unwrap();
+    assert_eq!(replayed.snapshot_hash(), game.snapshot_hash());
+    assert_eq!(replayed.state().active_god, Some(GodId::Veil));
+    assert!(replayed.state().curse_floors_remaining > 0);
+    let player = &replayed.state().actors[replayed.state().player_id];
+    assert!(player.max_hp < 22, "the Forge max HP bonus should not outlast the pact");
+}
@@ -0,0 +1,45 @@
+use core::scenario::Scenario;
+use core::{AdvanceStopReason, ContentPack, Difficulty, Game, PromptKind};
+
+fn load(json: &str) -> Game {
+    let scenario = Scenario::from_json(json).expect("scenario should parse");
+    Game::from_scenario(7, &ContentPack::default(), Difficulty::Normal, &scenario)
+        .expect("scenario should load")
+}
+
+/// Play by always taking the first available choice, collecting the prompts raised.
+fn scripted_prompts(game: &mut Game, max_prompts: usize) -> Vec<PromptKind> {
+    let mut prompts = Vec::new();
+    while prompts.len() < max_prompts {
+        match game.advance(200).stop_reason {
+            AdvanceStopReason::Interrupted(interrupt) => {
+                prompts.push(interrupt.prompt_kind());
+                let choice = game.available_choices().remove(0);
+                game.apply_choice(interrupt.prompt_id(), choice).expect("available choice");
+            }
+            AdvanceStopReason::PausedAtBoundary { .. } | AdvanceStopReason::BudgetExhausted => {}
+            AdvanceStopReason::Finished(_) | AdvanceStopReason::EngineFailure(_) => break,
+        }
+    }
+    prompts
+}
+
+#[test]
+fn door_ambush_plays_out_the_same_way_every_time() {
+    let json = include_str!("../scenarios/door_ambush.json");
+    let mut first = load(json);
+    let prompts = scripted_prompts(&mut first, 4);
+    assert_eq!(
+        prompts,
+        [
+            PromptKind::DoorBlocked,
+            PromptKind::EnemyEncounter,
+            PromptKind::EnemyEncounter,
+            PromptKind::FloorTransition,
+        ]
+    );
+
+    let mut second = load(json);
+    assert_eq!(scripted_prompts(&mut second, 4), prompts);
+    assert_eq!(second.snapshot_hash(), first.snapshot_hash());
+}
@@ -1,6 +1,6 @@
 use core::{
-    AdvanceStopReason, Choice, ContentPack, DeathCause, EngineFailureReason, Game, GameMode,
-    Interrupt, RunOutcome, TileKind,
+    AdvanceStopReason, Choice, ContentPack, EngineFailureReason, Game, GameMode, Interrupt,
+    RunOutcome, TileKind,
 };
 use proptest::{
     arbitrary::any,
@@ -27,10 +27,9 @@ fn run_fuzz_simulation(map_seed: u64, choice_seed: u64, max_ticks: u32) -> Resul
         total_steps += result.simulated_ticks;
 
         match result.stop_reason {
-            AdvanceStopReason::Finished(RunOutcome::Victory) => break,
-            AdvanceStopReason::Finished(RunOutcome::Defeat(DeathCause::Damage)) => break,
-            AdvanceStopReason::Finished(RunOutcome::Defeat(DeathCause::Poison)) => break,
-            AdvanceStopReason::EngineFailure(EngineFailureReason::StalledNoProgress) => {
+            AdvanceStopReason::Finished(RunOutcome::Victory(_)) => break,
+            AdvanceStopReason::Finished(RunOutcome::Defeat(_)) => break,
+            AdvanceStopReason::EngineFailure(EngineFailureReason::StalledNoProgress(_)) => {
                 return Err(format!(
                     "Invariant failed: StalledNoProgress on map_seed {}",
                     map_seed
@@ -46,6 +45,13 @@ fn run_fuzz_simulation(map_seed: u64, choice_seed: u64, max_ticks: u32) -> Resul
                         (prompt_id, choose(&mut rng, &[Choice::KeepLoot, Choice::DiscardLoot]))
                     }
                     Interrupt::DoorBlocked { prompt_id, .. } => (prompt_id, Choice::OpenDoor),
+                    Interrupt::StairsLocked { prompt_id, .. } => (prompt_id, Choice::SeekObjective),
+                    Interrupt::PactAltar { prompt_id, .. } => {
+                        (prompt_id, choose(&mut rng, &[Choice::BreakPact, Choice::KeepPact]))
+                    }
+                    Interrupt::PerkDraft { prompt_id, .. } => {
+                        (prompt_id, choose(&mut rng, &game.available_choices()))
+                    }
                     Interrupt::FloorTransition {
                         prompt_id, requires_branch_god_choice, ..
                     } => {
@@ -63,7 +69,7 @@ fn run_fuzz_simulation(map_seed: u64, choice_seed: u64, max_ticks: u32) -> Resul
                                 ),
                             )
                         } else {
-                            (prompt_id, Choice::Descend)
+                            (prompt_id, choose(&mut rng, &game.available_choices()))
                         }
                     }
                 };
@@ -23,6 +23,9 @@ fn run_to_floor_five(seed: u64, branch_and_god: Choice) -> u64 {
                     Interrupt::LootFound { prompt_id, .. } => (prompt_id, Choice::KeepLoot),
                     Interrupt::EnemyEncounter { prompt_id, .. } => (prompt_id, Choice::Fight),
                     Interrupt::DoorBlocked { prompt_id, .. } => (prompt_id, Choice::OpenDoor),
+                    Interrupt::StairsLocked { prompt_id, .. } => (prompt_id, Choice::SeekObjective),
+                    Interrupt::PactAltar { prompt_id, .. } => (prompt_id, Choice::KeepPact),
+                    Interrupt::PerkDraft { prompt_id, .. } => (prompt_id, Choice::DraftPerk(0)),
                     Interrupt::FloorTransition {
                         prompt_id, requires_branch_god_choice, ..
                     } => {
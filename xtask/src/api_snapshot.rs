@@ -0,0 +1,257 @@
+//! Public-API snapshot of the replay/journal-facing surface of `core`.
+//!
+//! Scans the surface files with `syn`, renders one line per public item (signatures, fields,
+//! variants and derives, since serde derives decide the journal format) and compares the
+//! result with the committed snapshot. Removed or changed lines are breaking and fail the
+//! check; additions only ask for a `--bless`.
+
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use quote::ToTokens;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+use syn::{Attribute, Fields, ImplItem, Item, TraitItem, Visibility};
+
+/// Committed snapshot, relative to the workspace root.
+const SNAPSHOT_PATH: &str = "xtask/api/core-replay-journal.txt";
+/// Files and directories whose public items make up the replay/journal-facing surface.
+const SURFACE_PATHS: &[&str] = &[
+    "crates/core/src/lib.rs",
+    "crates/core/src/journal.rs",
+    "crates/core/src/journal_file.rs",
+    "crates/core/src/journal_file",
+    "crates/core/src/replay.rs",
+    "crates/core/src/replay",
+];
+
+/// Compares the current surface with the snapshot, or rewrites the snapshot when `bless` is set.
+pub fn check_api(bless: bool) -> Result<()> {
+    let root = env::current_dir()?;
+    let current = collect_surface(&root)?;
+    let snapshot_path = root.join(SNAPSHOT_PATH);
+    let rendered: String = current.iter().map(|line| format!("{line}\n")).collect();
+
+    if bless {
+        if let Some(parent) = snapshot_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&snapshot_path, rendered)?;
+        println!("API snapshot updated: {SNAPSHOT_PATH} ({} items)", current.len());
+        return Ok(());
+    }
+
+    let committed: BTreeSet<String> = fs::read_to_string(&snapshot_path)
+        .map_err(|e| eyre!("Failed to read {SNAPSHOT_PATH} (run with --bless to create it): {e}"))?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let removed: Vec<&String> = committed.difference(&current).collect();
+    let added: Vec<&String> = current.difference(&committed).collect();
+
+    if !added.is_empty() {
+        println!("New public items (run `xtask check-api --bless` to record them):");
+        for line in &added {
+            println!("+ {line}");
+        }
+    }
+    if !removed.is_empty() {
+        println!("BREAKING CHANGES TO THE REPLAY/JOURNAL API");
+        println!("These items were removed or changed; old journals or callers may stop working:");
+        for line in &removed {
+            println!("- {line}");
+        }
+        println!("If the change is intended, review it and run `xtask check-api --bless`.");
+        bail!("Public API of the replay/journal surface changed.");
+    }
+
+    println!("Replay/journal API matches {SNAPSHOT_PATH}.");
+    Ok(())
+}
+
+/// One line per public item across every surface file.
+fn collect_surface(root: &Path) -> Result<BTreeSet<String>> {
+    let mut lines = BTreeSet::new();
+    for surface in SURFACE_PATHS {
+        let path = root.join(surface);
+        let files = if path.is_dir() {
+            let mut files: Vec<_> = fs::read_dir(&path)?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|file| file.extension().is_some_and(|ext| ext == "rs"))
+                .collect();
+            files.sort();
+            files
+        } else {
+            vec![path]
+        };
+        for file in files {
+            if file.file_stem().is_some_and(|stem| stem == "tests") {
+                continue;
+            }
+            let relative = file.strip_prefix(root).unwrap_or(&file).display().to_string();
+            let source = fs::read_to_string(&file)?;
+            let parsed =
+                syn::parse_file(&source).map_err(|e| eyre!("Failed to parse {relative}: {e}"))?;
+            render_items(&module_path(&relative), &parsed.items, &mut lines);
+        }
+    }
+    Ok(lines)
+}
+
+/// `crates/core/src/journal_file/recovery.rs` becomes `core::journal_file::recovery`.
+fn module_path(relative: &str) -> String {
+    let inner = relative.trim_start_matches("crates/core/src/").trim_end_matches(".rs");
+    if inner == "lib" { "core".to_string() } else { format!("core::{}", inner.replace('/', "::")) }
+}
+
+fn render_items(module: &str, items: &[Item], lines: &mut BTreeSet<String>) {
+    for item in items {
+        match item {
+            Item::Fn(item) if is_public(&item.vis) && !is_test_only(&item.attrs) => {
+                lines.insert(format!("{module}: {}", tokens(&item.sig)));
+            }
+            Item::Struct(item) if is_public(&item.vis) && !is_test_only(&item.attrs) => {
+                let head = format!("{module}: struct {}{}", item.ident, tokens(&item.generics));
+                lines.insert(format!("{head}{}", derives(&item.attrs)));
+                for (index, field) in item.fields.iter().enumerate() {
+                    if is_public(&field.vis) {
+                        let name =
+                            field.ident.as_ref().map_or(index.to_string(), |id| id.to_string());
+                        let attrs = serde_attrs(&field.attrs);
+                        lines.insert(format!("{head}.{name}: {}{attrs}", tokens(&field.ty)));
+                    }
+                }
+            }
+            Item::Enum(item) if is_public(&item.vis) && !is_test_only(&item.attrs) => {
+                let head = format!("{module}: enum {}{}", item.ident, tokens(&item.generics));
+                lines.insert(format!("{head}{}", derives(&item.attrs)));
+                for variant in &item.variants {
+                    let fields = match &variant.fields {
+                        Fields::Unit => String::new(),
+                        fields => format!(" {}", tokens(fields)),
+                    };
+                    let attrs = serde_attrs(&variant.attrs);
+                    lines.insert(format!("{head}::{}{fields}{attrs}", variant.ident));
+                }
+            }
+            Item::Trait(item) if is_public(&item.vis) && !is_test_only(&item.attrs) => {
+                lines.insert(format!("{module}: trait {}{}", item.ident, tokens(&item.generics)));
+                for trait_item in &item.items {
+                    if let TraitItem::Fn(method) = trait_item {
+                        lines.insert(format!(
+                            "{module}: trait {}: {}",
+                            item.ident,
+                            tokens(&method.sig)
+                        ));
+                    }
+                }
+            }
+            Item::Const(item) if is_public(&item.vis) => {
+                lines.insert(format!("{module}: const {}: {}", item.ident, tokens(&item.ty)));
+            }
+            Item::Static(item) if is_public(&item.vis) => {
+                lines.insert(format!("{module}: static {}: {}", item.ident, tokens(&item.ty)));
+            }
+            Item::Type(item) if is_public(&item.vis) => {
+                lines.insert(format!("{module}: {}", tokens(item)));
+            }
+            Item::Use(item) if is_public(&item.vis) => {
+                lines.insert(format!("{module}: use {}", tokens(&item.tree)));
+            }
+            Item::Mod(item) if is_public(&item.vis) && !is_test_only(&item.attrs) => {
+                let inner = format!("{module}::{}", item.ident);
+                lines.insert(format!("{module}: mod {}", item.ident));
+                if let Some((_, items)) = &item.content {
+                    render_items(&inner, items, lines);
+                }
+            }
+            Item::Impl(item) if !is_test_only(&item.attrs) => {
+                let self_ty = tokens(&item.self_ty);
+                if let Some((_, trait_path, _)) = &item.trait_ {
+                    lines.insert(format!("{module}: impl {} for {self_ty}", tokens(trait_path)));
+                    continue;
+                }
+                for impl_item in &item.items {
+                    if let ImplItem::Fn(method) = impl_item
+                        && is_public(&method.vis)
+                        && !is_test_only(&method.attrs)
+                    {
+                        lines.insert(format!("{module}: impl {self_ty}: {}", tokens(&method.sig)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Only plain `pub` counts; `pub(crate)` and narrower never reach other crates.
+fn is_public(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+fn is_test_only(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident("cfg") && tokens(&attr.meta).contains("test"))
+}
+
+/// Derive lists on a type, e.g. ` #[derive(Serialize, Deserialize)]`; serde derives fix the format.
+fn derives(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive") || attr.path().is_ident("serde"))
+        .map(|attr| format!(" {}", tokens(attr)))
+        .collect()
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .map(|attr| format!(" {}", tokens(attr)))
+        .collect()
+}
+
+fn tokens(node: &impl ToTokens) -> String {
+    node.to_token_stream().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(source: &str) -> Vec<String> {
+        let mut lines = BTreeSet::new();
+        render_items("core::journal", &syn::parse_file(source).unwrap().items, &mut lines);
+        lines.into_iter().collect()
+    }
+
+    #[test]
+    fn test_render_items_keeps_public_surface_only() {
+        let lines = render(
+            r#"
+            #[derive(Serialize)]
+            pub struct Header { pub seed: u64, #[serde(default)] pub director: bool, hidden: u8 }
+            pub(crate) fn internal() {}
+            impl Header { pub fn seed(&self) -> u64 { self.seed } fn private(&self) {} }
+            #[cfg(test)]
+            pub fn only_in_tests() {}
+            "#,
+        );
+        assert_eq!(
+            lines,
+            vec![
+                "core::journal: impl Header: fn seed (& self) -> u64",
+                "core::journal: struct Header # [derive (Serialize)]",
+                "core::journal: struct Header.director: bool # [serde (default)]",
+                "core::journal: struct Header.seed: u64",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_path_follows_the_file_layout() {
+        assert_eq!(module_path("crates/core/src/lib.rs"), "core");
+        assert_eq!(module_path("crates/core/src/replay/stepper.rs"), "core::replay::stepper");
+    }
+}
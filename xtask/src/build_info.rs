@@ -0,0 +1,94 @@
+//! Build-info generation: writes the app's `build_info/generated.rs` with the git commit,
+//! crate versions and content pack hash. The output depends only on the source tree, so two
+//! builds of the same commit embed identical stamps.
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use core::ContentPack;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use super::get_git_files;
+
+/// Generated module, relative to the workspace root.
+const GENERATED_PATH: &str = "crates/app/src/build_info/generated.rs";
+/// Git hash recorded by `--reset`, the form committed to the repository.
+const DEV_GIT_HASH: &str = "dev";
+
+/// Stamp the current commit into the generated module, or the `dev` placeholder on `reset`.
+pub fn build_info(reset: bool) -> Result<()> {
+    let root = env::current_dir()?;
+    let git_hash = if reset { DEV_GIT_HASH.to_string() } else { get_git_hash(&root)? };
+    let app_version = get_crate_version(&root.join("crates/app/Cargo.toml"))?;
+    let core_version = get_crate_version(&root.join("crates/core/Cargo.toml"))?;
+    let source = render_build_info(
+        &git_hash,
+        &app_version,
+        &core_version,
+        ContentPack::default().content_hash(),
+    );
+    fs::write(root.join(GENERATED_PATH), source)?;
+    println!("Wrote {GENERATED_PATH} for build {app_version}+{git_hash}.");
+    Ok(())
+}
+
+/// Short `HEAD` hash, suffixed with `-dirty` when tracked files other than the generated
+/// module have uncommitted changes.
+fn get_git_hash(root: &Path) -> Result<String> {
+    let head = get_git_files(root, &["rev-parse", "--short=12", "HEAD"])?;
+    let head = head.first().ok_or_else(|| eyre!("git rev-parse printed no hash"))?;
+    let exclude = format!(":!{GENERATED_PATH}");
+    let changes = get_git_files(
+        root,
+        &["status", "--porcelain", "--untracked-files=no", "--", ".", &exclude],
+    )?;
+    Ok(if changes.is_empty() { head.clone() } else { format!("{head}-dirty") })
+}
+
+fn get_crate_version(manifest: &Path) -> Result<String> {
+    let text = fs::read_to_string(manifest)?;
+    let value: toml::Value =
+        toml::from_str(&text).map_err(|e| eyre!("Failed to parse {}: {e}", manifest.display()))?;
+    value
+        .get("package")
+        .and_then(|package| package.get("version"))
+        .and_then(toml::Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| eyre!("{} has no package.version", manifest.display()))
+}
+
+fn render_build_info(
+    git_hash: &str,
+    app_version: &str,
+    core_version: &str,
+    content_hash: u64,
+) -> String {
+    format!(
+        "//! Generated by `cargo xtask build-info`; do not edit by hand.\n\
+         //! `cargo xtask build-info --reset` restores the committed `dev` stamp.\n\
+         \n\
+         use super::BuildInfo;\n\
+         \n\
+         pub const BUILD_INFO: BuildInfo = BuildInfo {{\n    \
+             build_id: \"{app_version}+{git_hash}\",\n    \
+             git_hash: \"{git_hash}\",\n    \
+             app_version: \"{app_version}\",\n    \
+             core_version: \"{core_version}\",\n    \
+             content_hash: 0x{content_hash:016x},\n\
+         }};\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_build_info_is_stable_and_names_the_build() {
+        let source = render_build_info("abc123", "0.2.0", "0.1.0", 0xfeed);
+        assert_eq!(source, render_build_info("abc123", "0.2.0", "0.1.0", 0xfeed));
+        assert!(source.contains("build_id: \"0.2.0+abc123\""));
+        assert!(source.contains("content_hash: 0x000000000000feed"));
+    }
+}
@@ -0,0 +1,118 @@
+//! Determinism guard: flags `HashMap`/`HashSet` in the core simulation sources.
+//!
+//! Their iteration order is randomized per process, so a sim that iterates one can diverge
+//! between a live run and its replay without any test noticing. `BTreeMap`/`BTreeSet` or
+//! `SlotMap` keep order stable. Files that genuinely need a hash collection (lookup only,
+//! never iterated) are listed by regex in `.determinism-exceptions.toml`.
+
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use ra_ap_rustc_lexer::{FrontmatterAllowed, TokenKind};
+use regex::Regex;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use super::{ExceptionsConfig, get_files_to_check};
+
+/// Directory holding the simulation sources this check covers.
+const SIM_SOURCE_PREFIX: &str = "crates/core/src/";
+/// Exception list, in the same format as `.rust-line-count-exceptions.toml`.
+const EXCEPTIONS_FILE: &str = ".determinism-exceptions.toml";
+/// Collection types whose iteration order is not deterministic.
+const FORBIDDEN_COLLECTIONS: &[&str] = &["HashMap", "HashSet"];
+
+/// Checks core simulation files for hash-ordered collections.
+pub fn check_sim_collections(all: bool) -> Result<()> {
+    let root = env::current_dir()?;
+    let exceptions = load_exceptions(&root)?;
+    let mut violations = Vec::new();
+
+    for file_path in get_files_to_check(&root, all)? {
+        let path = root.join(&file_path);
+        if !file_path.starts_with(SIM_SOURCE_PREFIX)
+            || path.extension().and_then(|ext| ext.to_str()) != Some("rs")
+            || !path.is_file()
+            || exceptions.iter().any(|re| re.is_match(&file_path))
+        {
+            continue;
+        }
+        let source = fs::read_to_string(&path)?;
+        for (line, name) in find_forbidden_collections(&source) {
+            violations.push((file_path.clone(), line, name));
+        }
+    }
+
+    if !violations.is_empty() {
+        violations.sort();
+        println!("NONDETERMINISTIC COLLECTIONS IN SIMULATION CODE");
+        println!("HashMap/HashSet iteration order varies between runs and breaks replays.");
+        println!(
+            "Use BTreeMap/BTreeSet, or list the file in {EXCEPTIONS_FILE} if it never iterates."
+        );
+        for (file, line, name) in violations {
+            println!("{file}:{line} -> {name}");
+        }
+        bail!("Found HashMap/HashSet in core simulation code.");
+    }
+
+    println!("No HashMap/HashSet found in core simulation code.");
+    Ok(())
+}
+
+fn load_exceptions(root: &Path) -> Result<Vec<Regex>> {
+    let config_path = root.join(EXCEPTIONS_FILE);
+    if !config_path.exists() {
+        return Ok(Vec::new());
+    }
+    let config: ExceptionsConfig = toml::from_str(&fs::read_to_string(&config_path)?)
+        .map_err(|e| eyre!("Failed to parse {EXCEPTIONS_FILE}: {e}"))?;
+    config
+        .exceptions
+        .into_iter()
+        .map(|pattern| Regex::new(&pattern).map_err(|e| eyre!("Invalid regex {pattern}: {e}")))
+        .collect()
+}
+
+/// Returns the 1-based line and name of every forbidden collection identifier in `source`.
+///
+/// Works on lexer tokens, so mentions in comments and string literals are not reported.
+fn find_forbidden_collections(source: &str) -> Vec<(usize, String)> {
+    let mut hits = Vec::new();
+    let mut offset = 0;
+    for token in ra_ap_rustc_lexer::tokenize(source, FrontmatterAllowed::Yes) {
+        let text = &source[offset..offset + token.len as usize];
+        if token.kind == TokenKind::Ident && FORBIDDEN_COLLECTIONS.contains(&text) {
+            let line = source[..offset].bytes().filter(|byte| *byte == b'\n').count() + 1;
+            hits.push((line, text.to_string()));
+        }
+        offset += token.len as usize;
+    }
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_forbidden_collections_ignores_comments_and_strings() {
+        let source = r#"
+            use std::collections::{BTreeMap, HashMap};
+            // A HashSet mentioned in a comment is fine.
+            fn build() -> HashSet<u8> {
+                let _ = "HashMap in a string";
+                HashSet::new()
+            }
+        "#;
+        let hits = find_forbidden_collections(source);
+        assert_eq!(
+            hits,
+            vec![
+                (2, "HashMap".to_string()),
+                (4, "HashSet".to_string()),
+                (6, "HashSet".to_string()),
+            ]
+        );
+    }
+}
@@ -1,7 +1,9 @@
 //! `xtask` — workspace automation for the project.
 //!
-//! Provides CI-style checks: clippy suppression scanning and
-//! Rust file token-count enforcement.
+//! Provides CI-style checks: clippy suppression scanning, Rust file token-count
+//! enforcement, a determinism guard against hash collections in the core sim, and a
+//! snapshot of the replay/journal-facing public API. `verify-replays` replays the golden
+//! fixture journals; `build-info` stamps the app with the commit and content hash.
 use anyhow::Result as AnyhowResult;
 use clap::{Parser, Subcommand};
 use color_eyre::Result;
@@ -9,12 +11,23 @@ use color_eyre::eyre::{bail, eyre};
 use ra_ap_rustc_lexer::{FrontmatterAllowed, TokenKind};
 use regex::Regex;
 use serde::Deserialize;
+use std::cmp::Reverse;
 use std::collections::HashSet;
 use std::env;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+mod api_snapshot;
+mod build_info;
+mod determinism;
+mod replays;
+
+use api_snapshot::check_api;
+use build_info::build_info;
+use determinism::check_sim_collections;
+use replays::verify_replays;
+
 /// Default maximum number of non-comment, non-whitespace tokens allowed per Rust file.
 const DEFAULT_TOKEN_LIMIT: usize = 2500;
 /// Exact clippy lints that repository policy forbids suppressing with `allow`/`expect`.
@@ -68,6 +81,30 @@ enum Commands {
         #[arg(long)]
         all: bool,
     },
+    /// Compare the replay/journal-facing public API of `core` with the committed snapshot.
+    CheckApi {
+        /// Rewrite the snapshot from the current sources after reviewing the change
+        #[arg(long)]
+        bless: bool,
+    },
+    /// Replay every journal in `fixtures/replays/` and compare with the recorded results.
+    VerifyReplays {
+        /// Record the current results as the new expectations
+        #[arg(long)]
+        bless: bool,
+    },
+    /// Write the app's build-info module from the current commit, versions and content.
+    BuildInfo {
+        /// Write the committed `dev` stamp instead of the current commit
+        #[arg(long)]
+        reset: bool,
+    },
+    /// Check for HashMap/HashSet in core simulation code, whose iteration order breaks replays.
+    CheckSimCollections {
+        /// Scan all tracked files instead of just edited ones
+        #[arg(long)]
+        all: bool,
+    },
     /// Check for Rust files that are too large (token count)
     CheckRustTokenCount {
         /// Scan all tracked files instead of just edited ones
@@ -90,10 +127,16 @@ fn main() -> Result<()> {
     match cli.command {
         Commands::Check { all } => {
             check_clippy_suppressions(all)?;
+            check_sim_collections(all)?;
+            check_api(false)?;
             check_rust_token_count(all, DEFAULT_TOKEN_LIMIT, false)?;
             Ok(())
         }
         Commands::CheckClippySuppressions { all } => check_clippy_suppressions(all),
+        Commands::CheckApi { bless } => check_api(bless),
+        Commands::CheckSimCollections { all } => check_sim_collections(all),
+        Commands::VerifyReplays { bless } => verify_replays(bless),
+        Commands::BuildInfo { reset } => build_info(reset),
         Commands::CheckRustTokenCount { all, limit, print_counts } => {
             check_rust_token_count(all, limit, print_counts)
         }
@@ -204,7 +247,7 @@ fn check_rust_token_count(all: bool, limit: usize, print_counts: bool) -> Result
     }
 
     if !violations.is_empty() {
-        violations.sort_by(|a, b| b.1.cmp(&a.1));
+        violations.sort_by_key(|violation| Reverse(violation.1));
         println!("SOURCE TOKEN LIMIT EXCEEDED");
         println!(
             "This gate enforces Rust SOURCE CODE token count (limit: {}), NOT line count.",
@@ -0,0 +1,185 @@
+//! Golden replay verification: every journal in `fixtures/replays/` is replayed to the end
+//! with `core::replay::replay_to_end` and compared with its recorded outcome, tick and hash.
+//! Recorded per-prompt full state hashes are checked on the way, so a drift is reported at the
+//! first prompt it shows up at rather than only at the end.
+//! `--bless` rewrites the expectations after an intended simulation change.
+
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use core::replay::{ReplayResult, replay_to_end_verified};
+use core::{ContentPack, load_journal_from_file};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Directory of fixture journals, relative to the workspace root.
+const REPLAY_DIR: &str = "fixtures/replays";
+/// Expectations file inside `REPLAY_DIR`, keyed by journal file name.
+const EXPECTATIONS_FILE: &str = "expected.toml";
+
+/// Recorded end state of one fixture journal.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct ExpectedReplay {
+    outcome: String,
+    final_tick: u64,
+    final_snapshot_hash: String,
+    /// Full state hash at each answered prompt; absent in expectations blessed before it.
+    #[serde(default)]
+    prompt_state_hashes: Vec<String>,
+}
+
+impl ExpectedReplay {
+    fn from_result(result: &ReplayResult) -> Self {
+        Self {
+            outcome: format!("{:?}", result.final_outcome),
+            final_tick: result.final_tick,
+            final_snapshot_hash: format!("0x{:016x}", result.final_snapshot_hash),
+            prompt_state_hashes: result
+                .prompt_state_hashes
+                .iter()
+                .map(|hash| format!("0x{hash:016x}"))
+                .collect(),
+        }
+    }
+
+    fn parsed_prompt_hashes(&self) -> Result<Vec<u64>> {
+        self.prompt_state_hashes
+            .iter()
+            .map(|hash| {
+                u64::from_str_radix(hash.trim_start_matches("0x"), 16)
+                    .map_err(|e| eyre!("Bad prompt state hash {hash}: {e}"))
+            })
+            .collect()
+    }
+}
+
+/// Replays every fixture journal and checks it, or records the results when `bless` is set.
+pub fn verify_replays(bless: bool) -> Result<()> {
+    let dir = env::current_dir()?.join(REPLAY_DIR);
+    let expectations_path = dir.join(EXPECTATIONS_FILE);
+    let content = ContentPack::default();
+    let expected: BTreeMap<String, ExpectedReplay> = if expectations_path.exists() && !bless {
+        toml::from_str(&fs::read_to_string(&expectations_path)?)
+            .map_err(|e| eyre!("Failed to parse {EXPECTATIONS_FILE}: {e}"))?
+    } else {
+        BTreeMap::new()
+    };
+    let actual = replay_fixtures(&dir, &content, &expected)?;
+
+    if bless {
+        let text = toml::to_string(&actual)
+            .map_err(|e| eyre!("Failed to serialize {EXPECTATIONS_FILE}: {e}"))?;
+        fs::write(&expectations_path, text)?;
+        println!(
+            "Blessed {} replay expectations in {REPLAY_DIR}/{EXPECTATIONS_FILE}.",
+            actual.len()
+        );
+        return Ok(());
+    }
+
+    let failures = compare(&expected, &actual);
+    if !failures.is_empty() {
+        println!("GOLDEN REPLAY MISMATCH");
+        for failure in &failures {
+            println!("{failure}");
+        }
+        println!("If the simulation change is intended, run `xtask verify-replays --bless`.");
+        bail!("{} golden replay(s) failed.", failures.len());
+    }
+
+    println!("All {} golden replays match.", actual.len());
+    Ok(())
+}
+
+/// Replays each `.jsonl` journal in `dir`, checking the prompt hashes recorded in `expected`;
+/// a journal that cannot replay or drifts from its recorded hashes is an error.
+fn replay_fixtures(
+    dir: &Path,
+    content: &ContentPack,
+    expected: &BTreeMap<String, ExpectedReplay>,
+) -> Result<BTreeMap<String, ExpectedReplay>> {
+    let mut results = BTreeMap::new();
+    for entry in fs::read_dir(dir).map_err(|e| eyre!("Failed to read {REPLAY_DIR}: {e}"))? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+        let name =
+            path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let loaded =
+            load_journal_from_file(&path).map_err(|e| eyre!("Failed to load {name}: {e}"))?;
+        let prompt_hashes = match expected.get(&name) {
+            Some(recorded) => recorded.parsed_prompt_hashes()?,
+            None => Vec::new(),
+        };
+        let result = replay_to_end_verified(content, &loaded.journal, &prompt_hashes)
+            .map_err(|e| eyre!("Failed to replay {name}: {e}"))?;
+        results.insert(name, ExpectedReplay::from_result(&result));
+    }
+    Ok(results)
+}
+
+/// One message per journal whose result differs from, or is missing in, the expectations.
+fn compare(
+    expected: &BTreeMap<String, ExpectedReplay>,
+    actual: &BTreeMap<String, ExpectedReplay>,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    for (name, result) in actual {
+        match expected.get(name) {
+            None => failures.push(format!("{name}: no recorded expectation")),
+            Some(recorded) if recorded != result => {
+                failures.push(format!("{name}: expected {recorded:?}, got {result:?}"))
+            }
+            Some(_) => {}
+        }
+    }
+    for name in expected.keys().filter(|name| !actual.contains_key(*name)) {
+        failures.push(format!("{name}: expectation recorded but the journal is missing"));
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expectation(tick: u64) -> ExpectedReplay {
+        ExpectedReplay {
+            outcome: "Victory".to_string(),
+            final_tick: tick,
+            final_snapshot_hash: "0x0000000000000001".to_string(),
+            prompt_state_hashes: vec!["0x00000000000000ff".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_compare_reports_changed_new_and_missing_journals() {
+        let expected = BTreeMap::from([
+            ("same.jsonl".to_string(), expectation(10)),
+            ("changed.jsonl".to_string(), expectation(20)),
+            ("gone.jsonl".to_string(), expectation(30)),
+        ]);
+        let actual = BTreeMap::from([
+            ("same.jsonl".to_string(), expectation(10)),
+            ("changed.jsonl".to_string(), expectation(21)),
+            ("new.jsonl".to_string(), expectation(40)),
+        ]);
+        let failures = compare(&expected, &actual);
+        assert_eq!(failures.len(), 3);
+        assert!(failures[0].starts_with("changed.jsonl: expected"));
+        assert_eq!(failures[1], "new.jsonl: no recorded expectation");
+        assert_eq!(failures[2], "gone.jsonl: expectation recorded but the journal is missing");
+    }
+
+    #[test]
+    fn test_expectations_round_trip_through_toml() {
+        let expectations = BTreeMap::from([("a.jsonl".to_string(), expectation(5))]);
+        let text = toml::to_string(&expectations).unwrap();
+        let parsed: BTreeMap<String, ExpectedReplay> = toml::from_str(&text).unwrap();
+        assert_eq!(parsed, expectations);
+        assert_eq!(parsed["a.jsonl"].parsed_prompt_hashes().unwrap(), vec![0xff]);
+    }
+}